@@ -0,0 +1,122 @@
+//! Benchmarks for the review pipeline's chunker and progress store.
+//!
+//! Run with `cargo bench --features bench --bench review_pipeline`. Before measuring, each
+//! benchmark checks one pass against a fixed budget and fails when it is over, so a refactor
+//! that makes the chunker or store several times slower is caught even without comparing
+//! against a saved baseline. Set `ROVEX_BENCH_BUDGET_SCALE` (e.g. `2`) on slow machines.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use rovex_lib::bench_support::{
+    merge_context_windows, parse_file_chunks, parse_hunk_chunks, synthetic_diff, ProgressStore,
+};
+
+/// 500 files of 24 hunks: 12,000 hunks, about 2 MB of diff.
+const FILES: usize = 500;
+const HUNKS_PER_FILE: usize = 24;
+
+const PARSE_HUNKS_BUDGET: Duration = Duration::from_millis(400);
+const PARSE_FILES_BUDGET: Duration = Duration::from_millis(800);
+const MERGE_WINDOWS_BUDGET: Duration = Duration::from_millis(800);
+/// One progress write on a run that already stores every chunk and finding.
+const APPEND_PROGRESS_BUDGET: Duration = Duration::from_millis(100);
+const BUDGET_SAMPLES: usize = 5;
+
+fn budget_scale() -> f64 {
+    std::env::var("ROVEX_BENCH_BUDGET_SCALE")
+        .ok()
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|value| *value > 0.0)
+        .unwrap_or(1.0)
+}
+
+/// Panics when the median of a few runs of `pass` exceeds `budget`.
+fn check_budget(name: &str, budget: Duration, mut pass: impl FnMut()) {
+    let mut samples = (0..BUDGET_SAMPLES)
+        .map(|_| {
+            let started_at = Instant::now();
+            pass();
+            started_at.elapsed()
+        })
+        .collect::<Vec<_>>();
+    samples.sort();
+    let median = samples[BUDGET_SAMPLES / 2];
+    let budget = budget.mul_f64(budget_scale());
+    assert!(
+        median <= budget,
+        "{name} took {median:?} (median of {BUDGET_SAMPLES}), over its {budget:?} budget"
+    );
+}
+
+fn chunker(c: &mut Criterion) {
+    let diff = synthetic_diff(FILES, HUNKS_PER_FILE);
+    assert_eq!(parse_hunk_chunks(&diff), FILES * HUNKS_PER_FILE);
+    assert_eq!(parse_file_chunks(&diff), FILES);
+
+    check_budget("parse_diff_chunks", PARSE_HUNKS_BUDGET, || {
+        black_box(parse_hunk_chunks(&diff));
+    });
+    check_budget("parse_diff_file_chunks", PARSE_FILES_BUDGET, || {
+        black_box(parse_file_chunks(&diff));
+    });
+    check_budget("merge_line_windows", MERGE_WINDOWS_BUDGET, || {
+        black_box(merge_context_windows(&diff));
+    });
+
+    let mut group = c.benchmark_group("chunker");
+    group.sample_size(20);
+    group.throughput(Throughput::Elements((FILES * HUNKS_PER_FILE) as u64));
+    group.bench_function("parse_diff_chunks/12k_hunks", |b| {
+        b.iter(|| parse_hunk_chunks(black_box(&diff)))
+    });
+    group.bench_function("parse_diff_file_chunks/12k_hunks", |b| {
+        b.iter(|| parse_file_chunks(black_box(&diff)))
+    });
+    group.bench_function("merge_line_windows/12k_hunks", |b| {
+        b.iter(|| merge_context_windows(black_box(&diff)))
+    });
+    group.finish();
+}
+
+fn progress_store(c: &mut Criterion) {
+    let store = tauri::async_runtime::block_on(ProgressStore::new(FILES))
+        .expect("create benchmark database");
+    // Fill the run first, so each measured write rewrites a full set of chunks and findings.
+    for index in 0..FILES {
+        tauri::async_runtime::block_on(store.append_chunk_completed(index))
+            .expect("append progress");
+    }
+
+    let mut next_index = 0usize;
+    check_budget(
+        "append_ai_review_run_progress",
+        APPEND_PROGRESS_BUDGET,
+        || {
+            tauri::async_runtime::block_on(store.append_chunk_completed(next_index))
+                .expect("append progress");
+            next_index += 1;
+        },
+    );
+
+    let mut group = c.benchmark_group("progress_store");
+    group.sample_size(20);
+    group.bench_function("append_ai_review_run_progress/500_chunks", |b| {
+        b.iter_batched(
+            || {
+                next_index += 1;
+                next_index
+            },
+            |index| {
+                tauri::async_runtime::block_on(store.append_chunk_completed(index))
+                    .expect("append progress")
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, chunker, progress_store);
+criterion_main!(benches);
@@ -1,16 +1,36 @@
 use std::{
-    env, fs,
+    env, fs, io,
     path::{Path, PathBuf},
 };
 
-fn find_opencode_source_binary() -> Option<PathBuf> {
-    if let Ok(explicit) = env::var("ROVEX_OPENCODE_BIN") {
-        let path = PathBuf::from(explicit.trim());
-        if path.is_file() {
-            return Some(path);
+use sha2::{Digest, Sha256};
+
+fn target_arch(triple: &str) -> &str {
+    triple.split('-').next().unwrap_or_default()
+}
+
+/// Picks the opencode binary to bundle for `target`. An arch-specific
+/// `ROVEX_OPENCODE_BIN_<ARCH>` (e.g. `ROVEX_OPENCODE_BIN_AARCH64`) wins over
+/// `ROVEX_OPENCODE_BIN`, so one environment can package both ARM and x86 builds. The local
+/// install is only used when building for the host architecture.
+fn find_opencode_source_binary(target: &str) -> Option<PathBuf> {
+    let arch = target_arch(target);
+    let arch_env = format!("ROVEX_OPENCODE_BIN_{}", arch.to_uppercase());
+    println!("cargo:rerun-if-env-changed={arch_env}");
+    println!("cargo:rerun-if-env-changed=ROVEX_OPENCODE_BIN");
+    for name in [arch_env.as_str(), "ROVEX_OPENCODE_BIN"] {
+        if let Ok(explicit) = env::var(name) {
+            let path = PathBuf::from(explicit.trim());
+            if path.is_file() {
+                return Some(path);
+            }
         }
     }
 
+    let host = env::var("HOST").unwrap_or_default();
+    if target_arch(&host) != arch {
+        return None;
+    }
     if let Ok(home) = env::var("HOME") {
         let path = PathBuf::from(home).join(".opencode/bin/opencode");
         if path.is_file() {
@@ -21,6 +41,30 @@ fn find_opencode_source_binary() -> Option<PathBuf> {
     None
 }
 
+/// Records the SHA-256 of the sidecar being bundled, so the app can refuse to spawn a
+/// sidecar that was swapped or corrupted after the build.
+fn record_opencode_sidecar_checksum(sidecar_path: &Path) {
+    println!("cargo:rerun-if-changed={}", sidecar_path.display());
+    let checksum = fs::File::open(sidecar_path).and_then(|mut file| {
+        let mut hasher = Sha256::new();
+        io::copy(&mut file, &mut hasher)?;
+        Ok(hasher.finalize())
+    });
+    match checksum {
+        Ok(checksum) => {
+            let hex = checksum
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>();
+            println!("cargo:rustc-env=ROVEX_OPENCODE_SIDECAR_SHA256={hex}");
+        }
+        Err(error) => println!(
+            "cargo:warning=Failed to checksum OpenCode sidecar {}: {error}",
+            sidecar_path.display()
+        ),
+    }
+}
+
 fn ensure_opencode_sidecar() {
     let Ok(target) = env::var("TARGET") else {
         return;
@@ -35,13 +79,15 @@ fn ensure_opencode_sidecar() {
         .join("binaries")
         .join(format!("opencode-{target}{extension}"));
     if sidecar_path.exists() {
+        record_opencode_sidecar_checksum(&sidecar_path);
         return;
     }
 
-    let Some(source) = find_opencode_source_binary() else {
+    let Some(source) = find_opencode_source_binary(&target) else {
         println!(
-            "cargo:warning=No bundled OpenCode sidecar found at {} and no local source binary detected. Set ROVEX_OPENCODE_BIN to the opencode executable path before packaging.",
-            sidecar_path.display()
+            "cargo:warning=No bundled OpenCode sidecar found at {} and no local source binary detected. Set ROVEX_OPENCODE_BIN_{} or ROVEX_OPENCODE_BIN to the opencode executable path before packaging.",
+            sidecar_path.display(),
+            target_arch(&target).to_uppercase()
         );
         return;
     };
@@ -77,6 +123,7 @@ fn ensure_opencode_sidecar() {
         source.display(),
         sidecar_path.display()
     );
+    record_opencode_sidecar_checksum(&sidecar_path);
 }
 
 fn main() {
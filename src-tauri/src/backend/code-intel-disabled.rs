@@ -0,0 +1,63 @@
+//! Stands in for `code-intel.rs` when the `code-intel` feature is off. Lookups find nothing, so
+//! reviews skip the cross-file pass, and syncing reports that the feature is missing.
+
+use std::env;
+use std::path::Path;
+
+use super::{
+    CodeIntelOptions, CodeIntelSyncInput, CodeIntelSyncResult, CodeIntelSyncStatus,
+    GetSymbolDefinitionResult, ListSymbolReferencesResult, SymbolLookupInput,
+};
+
+const CODE_INTEL_PROJECT_ROOT_ENV: &str = "CODE_INTEL_PROJECT_ROOT";
+const DISABLED_ERROR: &str = "Rovex was built without the code-intel feature.";
+
+/// The project root a sync covers when neither the call nor a thread names one.
+pub(crate) fn default_project_root() -> String {
+    env::var(CODE_INTEL_PROJECT_ROOT_ENV).unwrap_or_else(|_| ".".to_string())
+}
+
+/// A graph node whose symbol name matched a lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) struct CodeIntelSymbolNode {
+    pub(crate) symbol_name: String,
+    pub(crate) node_kind: String,
+    /// Relative to the project root when the node lies inside it.
+    pub(crate) file_path: String,
+    pub(crate) start_line: Option<i64>,
+}
+
+/// Never finds anything, as when no code-intel database is configured.
+pub(crate) async fn find_code_intel_symbols(
+    _project_root: &Path,
+    _symbols: &[String],
+    _limit_per_symbol: usize,
+) -> Result<Vec<CodeIntelSymbolNode>, String> {
+    Ok(Vec::new())
+}
+
+pub async fn get_symbol_definition(
+    _input: SymbolLookupInput,
+) -> Result<GetSymbolDefinitionResult, String> {
+    Err(DISABLED_ERROR.to_string())
+}
+
+pub async fn list_symbol_references(
+    _input: SymbolLookupInput,
+) -> Result<ListSymbolReferencesResult, String> {
+    Err(DISABLED_ERROR.to_string())
+}
+
+pub async fn run_code_intel_sync(
+    _input: Option<CodeIntelSyncInput>,
+) -> Result<CodeIntelSyncResult, String> {
+    Err(DISABLED_ERROR.to_string())
+}
+
+pub async fn code_intel_sync_status(
+    _project_root: Option<String>,
+    _options: CodeIntelOptions,
+) -> Result<CodeIntelSyncStatus, String> {
+    Err(DISABLED_ERROR.to_string())
+}
@@ -1,4 +1,6 @@
+use std::collections::BTreeMap;
 use std::env;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -17,10 +19,18 @@ use argus_search::core::types::GraphLayer;
 use argus_search::core::vector::engine::VectorSink;
 use argus_search::core::vector::provider::{HttpEmbeddingConfig, HttpEmbeddingsProvider};
 use argus_search::core::vector::types::{VectorBatch, VectorRecord};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
 use libsql::{Builder, Connection, Database};
+use sha2::{Digest, Sha256};
 use tokio::runtime::Runtime;
 
-use super::{CodeIntelSyncInput, CodeIntelSyncResult};
+use super::models::CodeSymbolLocation;
+use super::paths::normalize_path;
+use super::{
+    CodeIntelOptions, CodeIntelSyncInput, CodeIntelSyncResult, CodeIntelSyncStatus,
+    GetSymbolDefinitionResult, ListSymbolReferencesResult, SymbolLookupInput,
+};
 
 const DEFAULT_KITEDB_STORE_PATH: &str = ".argus-search/index.kite";
 const DEFAULT_VECTOR_BASE_URL: &str = "https://api.openai.com/v1";
@@ -47,10 +57,12 @@ const CODE_INTEL_PROJECT_ROOT_ENV: &str = "CODE_INTEL_PROJECT_ROOT";
 const CODE_INTEL_PATHS_ENV: &str = "CODE_INTEL_PATHS";
 const CODE_INTEL_INCLUDE_ENV: &str = "CODE_INTEL_INCLUDE";
 const CODE_INTEL_EXCLUDE_ENV: &str = "CODE_INTEL_EXCLUDE";
+const CODE_INTEL_RESPECT_GITIGNORE_ENV: &str = "CODE_INTEL_RESPECT_GITIGNORE";
 const CODE_INTEL_SCIP_ENV: &str = "CODE_INTEL_SCIP";
 const CODE_INTEL_KITEDB_STORE_ENV: &str = "CODE_INTEL_KITEDB_STORE";
 const CODE_INTEL_KITEDB_CLEAR_ENV: &str = "CODE_INTEL_KITEDB_CLEAR";
 const CODE_INTEL_TURSO_CLEAR_PROJECT_ENV: &str = "CODE_INTEL_TURSO_CLEAR_PROJECT";
+const CODE_INTEL_INCREMENTAL_ENV: &str = "CODE_INTEL_INCREMENTAL";
 const CODE_INTEL_VECTOR_BASE_URL_ENV: &str = "CODE_INTEL_VECTOR_BASE_URL";
 const CODE_INTEL_VECTOR_MODEL_ENV: &str = "CODE_INTEL_VECTOR_MODEL";
 const CODE_INTEL_VECTOR_DIMENSION_ENV: &str = "CODE_INTEL_VECTOR_DIMENSION";
@@ -60,6 +72,14 @@ const CODE_INTEL_VECTOR_CONCURRENCY_ENV: &str = "CODE_INTEL_VECTOR_CONCURRENCY";
 const CODE_INTEL_VECTOR_API_KEY_ENV: &str = "CODE_INTEL_VECTOR_API_KEY";
 const OPENAI_API_KEY_ENV: &str = "OPENAI_API_KEY";
 
+/// Node kinds, and SCIP roles in node metadata, that mark a use of a symbol rather than its
+/// declaration.
+const REFERENCE_NODE_KINDS: &[&str] = &["reference", "call", "usage", "import", "occurrence"];
+const DEFAULT_SYMBOL_LOCATIONS: usize = 50;
+const MAX_SYMBOL_LOCATIONS: usize = 500;
+/// Changed, added and removed paths listed by a sync status; the counts cover every file.
+const MAX_STATUS_CHANGED_PATHS: usize = 100;
+
 const TURSO_SYNC_SCHEMA_SQL: &str = r#"
 CREATE TABLE IF NOT EXISTS code_graph_nodes (
   project_root TEXT NOT NULL,
@@ -110,6 +130,15 @@ ON code_embedding_chunks(project_root, chunk_id);
 
 CREATE INDEX IF NOT EXISTS idx_code_embedding_chunks_project_file_path
 ON code_embedding_chunks(project_root, file_path);
+
+CREATE TABLE IF NOT EXISTS code_intel_file_states (
+  project_root TEXT NOT NULL,
+  file_path TEXT NOT NULL,
+  content_hash TEXT NOT NULL,
+  run_id TEXT NOT NULL,
+  synced_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (project_root, file_path)
+);
 "#;
 
 const UPSERT_GRAPH_NODE_SQL: &str = r#"
@@ -207,24 +236,44 @@ fn parse_csv(value: Option<String>, fallback: &[&str]) -> Vec<String> {
     }
 }
 
+/// The non-empty entries of `values`, or the comma-separated `env_name` list when there are
+/// none.
+fn option_list(values: Option<&[String]>, env_name: &str, fallback: &[&str]) -> Vec<String> {
+    let values = values
+        .unwrap_or_default()
+        .iter()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        parse_csv(env::var(env_name).ok(), fallback)
+    } else {
+        values
+    }
+}
+
+/// A non-empty `value`, or `env_name`.
+fn option_or_env(value: Option<&str>, env_name: &str) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+        .or_else(|| env::var(env_name).ok())
+}
+
 fn resolve_project_path(project_root: &Path, candidate: &str) -> PathBuf {
     let candidate_path = PathBuf::from(candidate);
-    if candidate_path.is_absolute() {
+    let path = if candidate_path.is_absolute() {
         candidate_path
     } else {
         project_root.join(candidate_path)
-    }
+    };
+    normalize_path(&path).unwrap_or(path)
 }
 
 fn normalize_project_root(path: PathBuf) -> Result<PathBuf, String> {
-    std::fs::canonicalize(&path)
-        .or_else(|_| {
-            if path.is_absolute() {
-                Ok(path)
-            } else {
-                std::env::current_dir().map(|cwd| cwd.join(path))
-            }
-        })
+    normalize_path(&path)
         .map_err(|error| format!("Failed to resolve CODE_INTEL_PROJECT_ROOT: {error}"))
 }
 
@@ -232,6 +281,45 @@ fn is_remote_turso(url: &str) -> bool {
     url.starts_with("libsql://") || url.starts_with("https://")
 }
 
+/// The files a sync reads: input paths filtered by include globs on file names, exclude
+/// globs on path components, and optionally ignore files.
+#[derive(Debug, Clone)]
+struct SyncInputs {
+    paths: Vec<PathBuf>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    respect_gitignore: bool,
+}
+
+impl SyncInputs {
+    fn resolve(project_root: &Path, options: &CodeIntelOptions) -> Self {
+        let paths = option_list(
+            options.paths.as_deref(),
+            CODE_INTEL_PATHS_ENV,
+            DEFAULT_PATHS,
+        )
+        .into_iter()
+        .map(|path| resolve_project_path(project_root, &path))
+        .collect();
+        Self {
+            paths,
+            include: option_list(
+                options.include.as_deref(),
+                CODE_INTEL_INCLUDE_ENV,
+                DEFAULT_INCLUDE,
+            ),
+            exclude: option_list(
+                options.exclude.as_deref(),
+                CODE_INTEL_EXCLUDE_ENV,
+                DEFAULT_EXCLUDE,
+            ),
+            respect_gitignore: options.respect_gitignore.unwrap_or_else(|| {
+                parse_bool(env::var(CODE_INTEL_RESPECT_GITIGNORE_ENV).ok(), true)
+            }),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct CodeIntelConfig {
     run_id: String,
@@ -242,10 +330,9 @@ struct CodeIntelConfig {
     kitedb_store_path: PathBuf,
     clear_kitedb: bool,
     clear_turso_project: bool,
+    incremental: bool,
     use_scip: bool,
-    inputs_paths: Vec<PathBuf>,
-    inputs_include: Vec<String>,
-    inputs_exclude: Vec<String>,
+    inputs: SyncInputs,
     vector_base_url: String,
     vector_model: String,
     vector_dimension: usize,
@@ -255,15 +342,18 @@ struct CodeIntelConfig {
     vector_api_key: Option<String>,
 }
 
+/// The project root a sync covers when neither the call nor a thread names one.
+pub(crate) fn default_project_root() -> String {
+    dotenvy::dotenv().ok();
+    env::var(CODE_INTEL_PROJECT_ROOT_ENV).unwrap_or_else(|_| ".".to_string())
+}
+
 impl CodeIntelConfig {
     fn from_input(input: Option<CodeIntelSyncInput>) -> Result<Self, String> {
         dotenvy::dotenv().ok();
         let input = input.unwrap_or_default();
 
-        let project_root_raw = input
-            .project_root
-            .or_else(|| env::var(CODE_INTEL_PROJECT_ROOT_ENV).ok())
-            .unwrap_or_else(|| ".".to_string());
+        let project_root_raw = input.project_root.unwrap_or_else(default_project_root);
         let project_root = normalize_project_root(PathBuf::from(project_root_raw))?;
         let project_root_key = project_root.to_string_lossy().to_string();
 
@@ -286,11 +376,15 @@ impl CodeIntelConfig {
             ));
         }
 
-        let kitedb_store_raw = env::var(CODE_INTEL_KITEDB_STORE_ENV)
-            .unwrap_or_else(|_| DEFAULT_KITEDB_STORE_PATH.to_string());
+        let options = &input.options;
+        let kitedb_store_raw = option_or_env(
+            options.kitedb_store_path.as_deref(),
+            CODE_INTEL_KITEDB_STORE_ENV,
+        )
+        .unwrap_or_else(|| DEFAULT_KITEDB_STORE_PATH.to_string());
         let kitedb_store_path = resolve_project_path(&project_root, &kitedb_store_raw);
 
-        let use_scip = input
+        let use_scip = options
             .use_scip
             .unwrap_or_else(|| parse_bool(env::var(CODE_INTEL_SCIP_ENV).ok(), true));
         let clear_kitedb = input
@@ -300,34 +394,50 @@ impl CodeIntelConfig {
             .clear_turso_project
             .unwrap_or_else(|| parse_bool(env::var(CODE_INTEL_TURSO_CLEAR_PROJECT_ENV).ok(), true));
 
-        let input_paths = parse_csv(env::var(CODE_INTEL_PATHS_ENV).ok(), DEFAULT_PATHS)
-            .into_iter()
-            .map(|path| resolve_project_path(&project_root, &path))
-            .collect::<Vec<_>>();
-        let input_include = parse_csv(env::var(CODE_INTEL_INCLUDE_ENV).ok(), DEFAULT_INCLUDE);
-        let input_exclude = parse_csv(env::var(CODE_INTEL_EXCLUDE_ENV).ok(), DEFAULT_EXCLUDE);
-
-        let vector_base_url = env::var(CODE_INTEL_VECTOR_BASE_URL_ENV)
-            .unwrap_or_else(|_| DEFAULT_VECTOR_BASE_URL.to_string());
-        let vector_model = env::var(CODE_INTEL_VECTOR_MODEL_ENV)
-            .unwrap_or_else(|_| DEFAULT_VECTOR_MODEL.to_string());
+        let incremental = input
+            .incremental
+            .unwrap_or_else(|| parse_bool(env::var(CODE_INTEL_INCREMENTAL_ENV).ok(), true));
+        let inputs = SyncInputs::resolve(&project_root, options);
+
+        let vector_base_url = option_or_env(
+            options.vector_base_url.as_deref(),
+            CODE_INTEL_VECTOR_BASE_URL_ENV,
+        )
+        .unwrap_or_else(|| DEFAULT_VECTOR_BASE_URL.to_string());
+        let vector_model =
+            option_or_env(options.vector_model.as_deref(), CODE_INTEL_VECTOR_MODEL_ENV)
+                .unwrap_or_else(|| DEFAULT_VECTOR_MODEL.to_string());
+        let number_or_env =
+            |value: Option<String>, env_name: &str| value.or_else(|| env::var(env_name).ok());
         let vector_dimension = parse_usize(
-            env::var(CODE_INTEL_VECTOR_DIMENSION_ENV).ok(),
+            number_or_env(
+                options.vector_dimension.map(|value| value.to_string()),
+                CODE_INTEL_VECTOR_DIMENSION_ENV,
+            ),
             DEFAULT_VECTOR_DIMENSION,
             1,
         );
         let vector_timeout_ms = parse_u64(
-            env::var(CODE_INTEL_VECTOR_TIMEOUT_MS_ENV).ok(),
+            number_or_env(
+                options.vector_timeout_ms.map(|value| value.to_string()),
+                CODE_INTEL_VECTOR_TIMEOUT_MS_ENV,
+            ),
             DEFAULT_VECTOR_TIMEOUT_MS,
             1_000,
         );
         let vector_batch_size = parse_usize(
-            env::var(CODE_INTEL_VECTOR_BATCH_SIZE_ENV).ok(),
+            number_or_env(
+                options.vector_batch_size.map(|value| value.to_string()),
+                CODE_INTEL_VECTOR_BATCH_SIZE_ENV,
+            ),
             DEFAULT_VECTOR_BATCH_SIZE,
             1,
         );
         let vector_concurrency = parse_usize(
-            env::var(CODE_INTEL_VECTOR_CONCURRENCY_ENV).ok(),
+            number_or_env(
+                options.vector_concurrency.map(|value| value.to_string()),
+                CODE_INTEL_VECTOR_CONCURRENCY_ENV,
+            ),
             DEFAULT_VECTOR_CONCURRENCY,
             1,
         );
@@ -352,10 +462,9 @@ impl CodeIntelConfig {
             kitedb_store_path,
             clear_kitedb,
             clear_turso_project,
+            incremental,
             use_scip,
-            inputs_paths: input_paths,
-            inputs_include: input_include,
-            inputs_exclude: input_exclude,
+            inputs,
             vector_base_url,
             vector_model,
             vector_dimension,
@@ -385,25 +494,475 @@ fn value_to_json(value: &serde_json::Value) -> Result<String, String> {
     serde_json::to_string(value).map_err(|error| format!("Failed to serialize JSON value: {error}"))
 }
 
+async fn open_database(url: String, token: String) -> Result<Database, String> {
+    if is_remote_turso(&url) {
+        return Builder::new_remote(url, token)
+            .build()
+            .await
+            .map_err(|error| format!("Failed to open remote Turso database: {error}"));
+    }
+
+    let local_path = url.strip_prefix("file:").unwrap_or(&url).to_string();
+    Builder::new_local(local_path)
+        .build()
+        .await
+        .map_err(|error| format!("Failed to open local libSQL database: {error}"))
+}
+
 fn open_turso_database(runtime: &Runtime, config: &CodeIntelConfig) -> Result<Database, String> {
     let url = config.turso_database_url.clone();
     let token = config.turso_auth_token.clone().unwrap_or_default();
-    runtime.block_on(async move {
-        if is_remote_turso(&url) {
-            return Builder::new_remote(url, token)
-                .build()
-                .await
-                .map_err(|error| format!("Failed to open remote Turso database: {error}"));
+    runtime.block_on(open_database(url, token))
+}
+
+/// A graph node whose symbol name matched a lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CodeIntelSymbolNode {
+    pub(crate) symbol_name: String,
+    pub(crate) node_kind: String,
+    /// Relative to the project root when the node lies inside it.
+    pub(crate) file_path: String,
+    pub(crate) start_line: Option<i64>,
+}
+
+/// A line of a stored node range. Ranges are either flat (`startLine`) or nested
+/// (`start.line`), depending on the indexer that produced them.
+fn range_line(range_json: &str, edge: &str) -> Option<i64> {
+    let range = serde_json::from_str::<serde_json::Value>(range_json).ok()?;
+    let point = range.get(edge).unwrap_or(&range);
+    let flat_keys: &[&str] = if edge == "start" {
+        &["startLine", "start_line"]
+    } else {
+        &["endLine", "end_line"]
+    };
+    ["line", "row"]
+        .iter()
+        .chain(flat_keys)
+        .find_map(|key| point.get(*key)?.as_i64())
+}
+
+fn range_start_line(range_json: &str) -> Option<i64> {
+    range_line(range_json, "start")
+}
+
+fn range_end_line(range_json: &str) -> Option<i64> {
+    range_line(range_json, "end")
+}
+
+/// Whether a node is a use of its symbol: a reference-like node kind, or SCIP metadata whose
+/// role is not a definition.
+fn is_reference_node(node_kind: &str, metadata_json: Option<&str>) -> bool {
+    let kind = node_kind.to_lowercase();
+    if REFERENCE_NODE_KINDS
+        .iter()
+        .any(|marker| kind.contains(marker))
+    {
+        return true;
+    }
+    let Some(metadata) =
+        metadata_json.and_then(|json| serde_json::from_str::<serde_json::Value>(json).ok())
+    else {
+        return false;
+    };
+    ["role", "symbolRole", "symbolRoles"].iter().any(|key| {
+        let role = match metadata.get(*key) {
+            Some(serde_json::Value::String(role)) => role.to_lowercase(),
+            Some(serde_json::Value::Array(roles)) => roles
+                .iter()
+                .filter_map(|role| role.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase(),
+            _ => return false,
+        };
+        !role.contains("definition")
+            && REFERENCE_NODE_KINDS
+                .iter()
+                .any(|marker| role.contains(marker))
+    })
+}
+
+fn project_relative_path(project_root: &Path, file_path: &str) -> String {
+    Path::new(file_path)
+        .strip_prefix(project_root)
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| file_path.to_string())
+}
+
+/// A connection to the synced code graph and the normalized `project_root` its rows are keyed
+/// by, or `None` when no code-intel database is configured.
+async fn open_code_intel_graph(
+    project_root: &Path,
+) -> Result<Option<(Connection, PathBuf)>, String> {
+    dotenvy::dotenv().ok();
+    let Ok(url) = env::var(TURSO_DATABASE_URL_ENV) else {
+        return Ok(None);
+    };
+    let token = env::var(TURSO_AUTH_TOKEN_ENV).unwrap_or_default();
+    let project_root = normalize_project_root(project_root.to_path_buf())?;
+    let db = open_database(url, token).await?;
+    let conn = db
+        .connect()
+        .map_err(|error| format!("Failed to connect to code-intel database: {error}"))?;
+    Ok(Some((conn, project_root)))
+}
+
+/// Columns read by [`parse_symbol_location`], in order.
+const SYMBOL_LOCATION_COLUMNS: &str =
+    "symbol_name, node_kind, graph_layer, file_path, range_json, scip_symbol, metadata_json";
+
+/// A located graph node and whether it is a reference rather than a declaration.
+fn parse_symbol_location(
+    row: &libsql::Row,
+    project_root: &Path,
+) -> Result<(CodeSymbolLocation, bool), String> {
+    let node_kind: String = row
+        .get(1)
+        .map_err(|error| format!("Failed to parse graph node kind: {error}"))?;
+    let file_path: String = row
+        .get(3)
+        .map_err(|error| format!("Failed to parse graph node file path: {error}"))?;
+    let range_json: Option<String> = row
+        .get(4)
+        .map_err(|error| format!("Failed to parse graph node range: {error}"))?;
+    let metadata_json: Option<String> = row
+        .get(6)
+        .map_err(|error| format!("Failed to parse graph node metadata: {error}"))?;
+    let is_reference = is_reference_node(&node_kind, metadata_json.as_deref());
+    let location = CodeSymbolLocation {
+        symbol_name: row
+            .get(0)
+            .map_err(|error| format!("Failed to parse graph node symbol: {error}"))?,
+        node_kind,
+        graph_layer: row
+            .get(2)
+            .map_err(|error| format!("Failed to parse graph node layer: {error}"))?,
+        file_path: project_relative_path(project_root, &file_path),
+        start_line: range_json.as_deref().and_then(range_start_line),
+        end_line: range_json.as_deref().and_then(range_end_line),
+        scip_symbol: row
+            .get(5)
+            .map_err(|error| format!("Failed to parse graph node SCIP symbol: {error}"))?,
+    };
+    Ok((location, is_reference))
+}
+
+/// Locations of `symbol` in the graph, semantic nodes first. The syntax and semantic layers
+/// index the same declaration twice, so locations that repeat a file and line are dropped.
+async fn query_symbol_locations(
+    conn: &Connection,
+    project_root: &Path,
+    symbol: &str,
+) -> Result<Vec<(CodeSymbolLocation, bool)>, String> {
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {SYMBOL_LOCATION_COLUMNS}
+                 FROM code_graph_nodes
+                 WHERE project_root = ?1 AND symbol_name = ?2 AND file_path IS NOT NULL
+                 ORDER BY graph_layer = 'semantic' DESC, file_path ASC"
+            ),
+            (
+                project_root.to_string_lossy().to_string(),
+                symbol.to_string(),
+            ),
+        )
+        .await
+        .map_err(|error| format!("Failed to query code graph for {symbol}: {error}"))?;
+    let mut locations: Vec<(CodeSymbolLocation, bool)> = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read code graph row: {error}"))?
+    {
+        let (location, is_reference) = parse_symbol_location(&row, project_root)?;
+        if locations.iter().any(|(existing, _)| {
+            existing.file_path == location.file_path && existing.start_line == location.start_line
+        }) {
+            continue;
         }
+        locations.push((location, is_reference));
+    }
+    Ok(locations)
+}
 
-        let local_path = url.strip_prefix("file:").unwrap_or(&url).to_string();
-        Builder::new_local(local_path)
-            .build()
-            .await
-            .map_err(|error| format!("Failed to open local libSQL database: {error}"))
+/// Graph nodes named like `symbols` in the synced index of `project_root`, at most
+/// `limit_per_symbol` locations each, semantic nodes first. Returns nothing when no code-intel
+/// database is configured, so callers can treat the graph as optional.
+pub(crate) async fn find_code_intel_symbols(
+    project_root: &Path,
+    symbols: &[String],
+    limit_per_symbol: usize,
+) -> Result<Vec<CodeIntelSymbolNode>, String> {
+    if symbols.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some((conn, project_root)) = open_code_intel_graph(project_root).await? else {
+        return Ok(Vec::new());
+    };
+
+    let mut nodes = Vec::new();
+    for symbol in symbols {
+        let locations = query_symbol_locations(&conn, &project_root, symbol).await?;
+        nodes.extend(
+            locations
+                .into_iter()
+                .take(limit_per_symbol)
+                .map(|(location, _)| CodeIntelSymbolNode {
+                    symbol_name: symbol.clone(),
+                    node_kind: location.node_kind,
+                    file_path: location.file_path,
+                    start_line: location.start_line,
+                }),
+        );
+    }
+    Ok(nodes)
+}
+
+/// The symbol of the narrowest named node in `file_path` whose range covers `line`.
+async fn symbol_at_position(
+    conn: &Connection,
+    project_root: &Path,
+    file_path: &str,
+    line: i64,
+) -> Result<Option<String>, String> {
+    let relative = project_relative_path(project_root, file_path.trim());
+    let absolute = project_root.join(&relative).to_string_lossy().to_string();
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {SYMBOL_LOCATION_COLUMNS}
+                 FROM code_graph_nodes
+                 WHERE project_root = ?1 AND symbol_name IS NOT NULL
+                   AND (file_path = ?2 OR file_path = ?3)"
+            ),
+            (
+                project_root.to_string_lossy().to_string(),
+                relative.clone(),
+                absolute,
+            ),
+        )
+        .await
+        .map_err(|error| format!("Failed to query code graph for {relative}: {error}"))?;
+    let mut best: Option<(i64, String)> = None;
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read code graph row: {error}"))?
+    {
+        let (location, _) = parse_symbol_location(&row, project_root)?;
+        let Some(start) = location.start_line else {
+            continue;
+        };
+        let end = location.end_line.unwrap_or(start);
+        if line < start || line > end {
+            continue;
+        }
+        let span = end - start;
+        if best.as_ref().is_none_or(|(best_span, _)| span < *best_span) {
+            best = Some((span, location.symbol_name));
+        }
+    }
+    Ok(best.map(|(_, symbol)| symbol))
+}
+
+/// The symbol an input names, directly or by position, with its locations in the graph.
+async fn lookup_symbol(
+    input: &SymbolLookupInput,
+) -> Result<(Option<String>, Vec<(CodeSymbolLocation, bool)>), String> {
+    let workspace = input.workspace.trim();
+    if workspace.is_empty() {
+        return Err("Workspace must not be empty.".to_string());
+    }
+    let symbol = input
+        .symbol
+        .as_deref()
+        .map(str::trim)
+        .filter(|symbol| !symbol.is_empty());
+    let position = input
+        .file_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|file_path| !file_path.is_empty())
+        .zip(input.line);
+    if symbol.is_none() && position.is_none() {
+        return Err("Pass a symbol name, or a file path and line.".to_string());
+    }
+    let Some((conn, project_root)) = open_code_intel_graph(Path::new(workspace)).await? else {
+        return Err(format!(
+            "Code intelligence is not configured. Set {TURSO_DATABASE_URL_ENV} and run run_code_intel_sync first."
+        ));
+    };
+    let symbol = match (symbol, position) {
+        (Some(symbol), _) => Some(symbol.to_string()),
+        (None, Some((file_path, line))) => {
+            symbol_at_position(&conn, &project_root, file_path, line).await?
+        }
+        (None, None) => None,
+    };
+    let Some(symbol) = symbol else {
+        return Ok((None, Vec::new()));
+    };
+    let locations = query_symbol_locations(&conn, &project_root, &symbol).await?;
+    Ok((Some(symbol), locations))
+}
+
+fn symbol_location_limit(limit: Option<u32>) -> usize {
+    limit
+        .map(|limit| (limit as usize).clamp(1, MAX_SYMBOL_LOCATIONS))
+        .unwrap_or(DEFAULT_SYMBOL_LOCATIONS)
+}
+
+/// Where a symbol is declared: every node of the symbol that is not a reference.
+pub async fn get_symbol_definition(
+    input: SymbolLookupInput,
+) -> Result<GetSymbolDefinitionResult, String> {
+    let (symbol, locations) = lookup_symbol(&input).await?;
+    Ok(GetSymbolDefinitionResult {
+        symbol,
+        definitions: locations
+            .into_iter()
+            .filter(|(_, is_reference)| !is_reference)
+            .map(|(location, _)| location)
+            .take(symbol_location_limit(input.limit))
+            .collect(),
+    })
+}
+
+/// Where a symbol is used, as recorded by reference nodes of the graph.
+pub async fn list_symbol_references(
+    input: SymbolLookupInput,
+) -> Result<ListSymbolReferencesResult, String> {
+    let (symbol, locations) = lookup_symbol(&input).await?;
+    Ok(ListSymbolReferencesResult {
+        symbol,
+        references: locations
+            .into_iter()
+            .filter(|(_, is_reference)| *is_reference)
+            .map(|(location, _)| location)
+            .take(symbol_location_limit(input.limit))
+            .collect(),
     })
 }
 
+fn glob_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|error| format!("Invalid code-intel pattern '{pattern}': {error}"))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|error| format!("Invalid code-intel patterns: {error}"))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Content hashes of the files a sync of `inputs` reads, keyed by project-relative path.
+/// Files that cannot be read are left out, as the indexer skips them too.
+fn hash_sync_files(
+    project_root: &Path,
+    inputs: &SyncInputs,
+) -> Result<BTreeMap<String, String>, String> {
+    let include = glob_set(&inputs.include)?;
+    let exclude = glob_set(&inputs.exclude)?;
+    let respect = inputs.respect_gitignore;
+    let mut hashes = BTreeMap::new();
+    for path in inputs.paths.iter().filter(|path| path.exists()) {
+        let exclude = exclude.clone();
+        let walker = WalkBuilder::new(path)
+            .git_ignore(respect)
+            .git_global(respect)
+            .git_exclude(respect)
+            .ignore(respect)
+            .parents(respect)
+            .filter_entry(move |entry| !exclude.is_match(entry.file_name()))
+            .build();
+        for entry in walker.flatten() {
+            if !entry.file_type().is_some_and(|kind| kind.is_file())
+                || !include.is_match(entry.file_name())
+            {
+                continue;
+            }
+            let Ok(content) = fs::read(entry.path()) else {
+                continue;
+            };
+            let relative = project_relative_path(project_root, &entry.path().to_string_lossy());
+            hashes.insert(relative, sha256_hex(&content));
+        }
+    }
+    Ok(hashes)
+}
+
+/// How the files on disk differ from the ones recorded by the last sync, by project-relative
+/// path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct FileChanges {
+    added: Vec<String>,
+    modified: Vec<String>,
+    removed: Vec<String>,
+    unchanged: usize,
+}
+
+impl FileChanges {
+    fn between(synced: &BTreeMap<String, String>, current: &BTreeMap<String, String>) -> Self {
+        let mut changes = Self::default();
+        for (path, hash) in current {
+            match synced.get(path) {
+                None => changes.added.push(path.clone()),
+                Some(synced_hash) if synced_hash != hash => changes.modified.push(path.clone()),
+                Some(_) => changes.unchanged += 1,
+            }
+        }
+        changes.removed = synced
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned()
+            .collect();
+        changes
+    }
+
+    /// Files whose index entries are out of date.
+    fn stale_paths(&self) -> impl Iterator<Item = &String> {
+        self.added.iter().chain(&self.modified).chain(&self.removed)
+    }
+}
+
+/// Content hashes recorded by the last sync of `project_root`, keyed by project-relative path.
+async fn load_file_states(
+    conn: &Connection,
+    project_root: &str,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut rows = conn
+        .query(
+            "SELECT file_path, content_hash FROM code_intel_file_states WHERE project_root = ?1",
+            [project_root.to_string()],
+        )
+        .await
+        .map_err(|error| format!("Failed to load code-intel file states: {error}"))?;
+    let mut states = BTreeMap::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read code-intel file state: {error}"))?
+    {
+        let file_path: String = row
+            .get(0)
+            .map_err(|error| format!("Failed to parse file_path: {error}"))?;
+        let content_hash: String = row
+            .get(1)
+            .map_err(|error| format!("Failed to parse content_hash: {error}"))?;
+        states.insert(file_path, content_hash);
+    }
+    Ok(states)
+}
+
 #[derive(Debug, Clone, Default)]
 struct TursoCounters {
     syntax_nodes_upserted: u64,
@@ -442,9 +1001,6 @@ impl TursoIndexSink {
             error: None,
         };
         sink.ensure_schema()?;
-        if config.clear_turso_project {
-            sink.clear_project_rows()?;
-        }
         Ok(sink)
     }
 
@@ -472,6 +1028,84 @@ impl TursoIndexSink {
         Ok(())
     }
 
+    fn load_file_states(&self) -> Result<BTreeMap<String, String>, String> {
+        self.runtime
+            .block_on(load_file_states(&self.conn, &self.project_root))
+    }
+
+    /// Deletes the graph nodes and vectors of `file_path`, stored under either its relative or
+    /// its absolute path.
+    fn clear_file_rows(&mut self, file_path: &str) -> Result<(), String> {
+        let absolute = Path::new(&self.project_root)
+            .join(file_path)
+            .to_string_lossy()
+            .to_string();
+        for table in ["code_embedding_chunks", "code_graph_nodes"] {
+            self.runtime
+                .block_on(self.conn.execute(
+                    &format!(
+                        "DELETE FROM {table} WHERE project_root = ?1 AND file_path IN (?2, ?3)"
+                    ),
+                    (
+                        self.project_root.clone(),
+                        file_path.to_string(),
+                        absolute.clone(),
+                    ),
+                ))
+                .map_err(|error| format!("Failed to clear {table} rows of {file_path}: {error}"))?;
+        }
+        Ok(())
+    }
+
+    /// Records the hashes of the files this run indexed and forgets `removed` ones. A full
+    /// run replaces every recorded file.
+    fn record_file_states(
+        &mut self,
+        hashes: &BTreeMap<String, String>,
+        indexed: &[String],
+        removed: &[String],
+        full: bool,
+    ) -> Result<(), String> {
+        if full {
+            self.runtime
+                .block_on(self.conn.execute(
+                    "DELETE FROM code_intel_file_states WHERE project_root = ?1",
+                    [self.project_root.clone()],
+                ))
+                .map_err(|error| format!("Failed to clear code-intel file states: {error}"))?;
+        }
+        for file_path in removed {
+            self.runtime
+                .block_on(self.conn.execute(
+                    "DELETE FROM code_intel_file_states WHERE project_root = ?1 AND file_path = ?2",
+                    (self.project_root.clone(), file_path.clone()),
+                ))
+                .map_err(|error| format!("Failed to forget file state of {file_path}: {error}"))?;
+        }
+        for file_path in indexed {
+            let Some(content_hash) = hashes.get(file_path) else {
+                continue;
+            };
+            self.runtime
+                .block_on(self.conn.execute(
+                    "INSERT INTO code_intel_file_states (project_root, file_path, content_hash, run_id)
+                     VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT(project_root, file_path) DO UPDATE SET
+                       content_hash = excluded.content_hash,
+                       run_id = excluded.run_id,
+                       synced_at = CURRENT_TIMESTAMP",
+                    (
+                        self.project_root.clone(),
+                        file_path.clone(),
+                        content_hash.clone(),
+                        self.run_id.clone(),
+                    ),
+                ))
+                .map_err(|error| format!("Failed to record file state of {file_path}: {error}"))?;
+        }
+        Ok(())
+    }
+
     fn upsert_graph_node(
         &mut self,
         layer: GraphLayer,
@@ -623,7 +1257,7 @@ impl HybridSink {
         }
     }
 
-    fn finish(mut self) -> Result<TursoCounters, String> {
+    fn finish(mut self) -> Result<TursoIndexSink, String> {
         if let Some(error) = self.turso.error.take() {
             if let Some(kitedb) = self.kitedb.take() {
                 let _ = kitedb.abort();
@@ -637,7 +1271,7 @@ impl HybridSink {
                 .map_err(|error| format!("Failed to finalize KiteDB sink: {error}"))?;
         }
 
-        Ok(self.turso.counters)
+        Ok(self.turso)
     }
 
     fn abort(mut self) {
@@ -712,13 +1346,77 @@ impl IndexSink for HybridSink {
     }
 }
 
+/// Runs a sync. An incremental sync of a project synced before only indexes added and
+/// modified files and drops the Turso rows of removed ones; other syncs index everything.
 fn run_index_sync_blocking(config: CodeIntelConfig) -> Result<CodeIntelSyncResult, String> {
+    let hashes = hash_sync_files(&config.project_root, &config.inputs)?;
+    let mut turso_sink = TursoIndexSink::new(&config)?;
+    let synced = if config.incremental {
+        turso_sink.load_file_states()?
+    } else {
+        BTreeMap::new()
+    };
+    let incremental = !synced.is_empty();
+    let changes = FileChanges::between(&synced, &hashes);
+
+    let (indexed, paths) = if incremental {
+        for file_path in changes.stale_paths() {
+            turso_sink.clear_file_rows(file_path)?;
+        }
+        let indexed = changes
+            .added
+            .iter()
+            .chain(&changes.modified)
+            .cloned()
+            .collect::<Vec<_>>();
+        let paths = indexed
+            .iter()
+            .map(|file_path| config.project_root.join(file_path))
+            .collect::<Vec<_>>();
+        (indexed, paths)
+    } else {
+        if config.clear_turso_project {
+            turso_sink.clear_project_rows()?;
+        }
+        (
+            hashes.keys().cloned().collect(),
+            config.inputs.paths.clone(),
+        )
+    };
+
+    let mut result = CodeIntelSyncResult {
+        run_id: config.run_id.clone(),
+        project_root: config.project_root_key.clone(),
+        kitedb_store_path: config.kitedb_store_path.to_string_lossy().to_string(),
+        incremental,
+        syntax_nodes_upserted: 0,
+        semantic_nodes_upserted: 0,
+        vectors_upserted: 0,
+        files_parsed: 0,
+        files_skipped: 0,
+        chunks_emitted: 0,
+        files_unchanged: if incremental {
+            changes.unchanged as u64
+        } else {
+            0
+        },
+        files_removed: if incremental {
+            changes.removed.len() as u64
+        } else {
+            0
+        },
+    };
+    if incremental && paths.is_empty() {
+        turso_sink.record_file_states(&hashes, &[], &changes.removed, false)?;
+        return Ok(result);
+    }
+
     let mut options = IndexOptions::new(config.project_root.clone());
     options.inputs = ParseInputs {
-        paths: config.inputs_paths.clone(),
-        include: config.inputs_include.clone(),
-        exclude: config.inputs_exclude.clone(),
-        respect_gitignore: true,
+        paths,
+        include: config.inputs.include.clone(),
+        exclude: config.inputs.exclude.clone(),
+        respect_gitignore: config.inputs.respect_gitignore,
     };
     options.phases = IndexPhases {
         parse: true,
@@ -743,8 +1441,10 @@ fn run_index_sync_blocking(config: CodeIntelConfig) -> Result<CodeIntelSyncResul
     options.vector_provider = Some(Arc::new(provider));
 
     let cancel = CancelToken::new();
-    let kitedb_sink = KiteDbIndexSink::new(config.kitedb_store_path.clone(), config.clear_kitedb);
-    let turso_sink = TursoIndexSink::new(&config)?;
+    let kitedb_sink = KiteDbIndexSink::new(
+        config.kitedb_store_path.clone(),
+        config.clear_kitedb && !incremental,
+    );
     let mut sink = HybridSink::new(kitedb_sink, turso_sink);
 
     let engine = IndexEngine::new(options);
@@ -756,20 +1456,18 @@ fn run_index_sync_blocking(config: CodeIntelConfig) -> Result<CodeIntelSyncResul
         }
     };
 
-    let counters = sink.finish()?;
+    let mut turso_sink = sink.finish()?;
+    turso_sink.record_file_states(&hashes, &indexed, &changes.removed, !incremental)?;
+    let counters = turso_sink.counters;
     let parse_stats = stats.parse.unwrap_or_default();
 
-    Ok(CodeIntelSyncResult {
-        run_id: config.run_id,
-        project_root: config.project_root_key,
-        kitedb_store_path: config.kitedb_store_path.to_string_lossy().to_string(),
-        syntax_nodes_upserted: counters.syntax_nodes_upserted,
-        semantic_nodes_upserted: counters.semantic_nodes_upserted,
-        vectors_upserted: counters.vectors_upserted,
-        files_parsed: parse_stats.files_parsed,
-        files_skipped: parse_stats.files_skipped,
-        chunks_emitted: parse_stats.chunks_emitted,
-    })
+    result.syntax_nodes_upserted = counters.syntax_nodes_upserted;
+    result.semantic_nodes_upserted = counters.semantic_nodes_upserted;
+    result.vectors_upserted = counters.vectors_upserted;
+    result.files_parsed = parse_stats.files_parsed;
+    result.files_skipped = parse_stats.files_skipped;
+    result.chunks_emitted = parse_stats.chunks_emitted;
+    Ok(result)
 }
 
 pub async fn run_code_intel_sync(
@@ -780,3 +1478,131 @@ pub async fn run_code_intel_sync(
         .await
         .map_err(|error| format!("Code intelligence sync task failed: {error}"))?
 }
+
+/// The run and time of the last sync of `project_root`, or `None` when it was never synced.
+async fn last_file_state_sync(
+    conn: &Connection,
+    project_root: &str,
+) -> Result<Option<(String, String)>, String> {
+    let mut rows = conn
+        .query(
+            "SELECT run_id, synced_at FROM code_intel_file_states WHERE project_root = ?1
+             ORDER BY synced_at DESC LIMIT 1",
+            [project_root.to_string()],
+        )
+        .await
+        .map_err(|error| format!("Failed to load last code-intel sync: {error}"))?;
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read last code-intel sync: {error}"))?
+    else {
+        return Ok(None);
+    };
+    let run_id: String = row
+        .get(0)
+        .map_err(|error| format!("Failed to parse run_id: {error}"))?;
+    let synced_at: String = row
+        .get(1)
+        .map_err(|error| format!("Failed to parse synced_at: {error}"))?;
+    Ok(Some((run_id, synced_at)))
+}
+
+/// Compares the files of a project with the hashes its last sync recorded, without indexing.
+pub async fn code_intel_sync_status(
+    project_root: Option<String>,
+    options: CodeIntelOptions,
+) -> Result<CodeIntelSyncStatus, String> {
+    dotenvy::dotenv().ok();
+    let project_root_raw = project_root.unwrap_or_else(default_project_root);
+    let Some((conn, project_root)) = open_code_intel_graph(Path::new(&project_root_raw)).await?
+    else {
+        return Err(format!("Missing {TURSO_DATABASE_URL_ENV}."));
+    };
+    let project_root_key = project_root.to_string_lossy().to_string();
+    conn.execute_batch(TURSO_SYNC_SCHEMA_SQL)
+        .await
+        .map_err(|error| format!("Failed to initialize Turso sync schema: {error}"))?;
+    let synced = load_file_states(&conn, &project_root_key).await?;
+    let last_sync = last_file_state_sync(&conn, &project_root_key).await?;
+
+    let inputs = SyncInputs::resolve(&project_root, &options);
+    let hashes = {
+        let project_root = project_root.clone();
+        tauri::async_runtime::spawn_blocking(move || hash_sync_files(&project_root, &inputs))
+            .await
+            .map_err(|error| format!("Code intelligence status task failed: {error}"))??
+    };
+    let changes = FileChanges::between(&synced, &hashes);
+    let (last_run_id, last_synced_at) = last_sync.unzip();
+
+    Ok(CodeIntelSyncStatus {
+        project_root: project_root_key,
+        last_run_id,
+        last_synced_at,
+        stale: synced.is_empty() || changes.stale_paths().next().is_some(),
+        files_synced: synced.len() as u64,
+        files_unchanged: changes.unchanged as u64,
+        files_added: changes.added.len() as u64,
+        files_modified: changes.modified.len() as u64,
+        files_removed: changes.removed.len() as u64,
+        stale_paths: changes
+            .stale_paths()
+            .take(MAX_STATUS_CHANGED_PATHS)
+            .cloned()
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{is_reference_node, range_end_line, range_start_line, FileChanges};
+
+    #[test]
+    fn node_ranges_and_roles_are_read_from_either_indexer_shape() {
+        let nested = r#"{"start":{"line":12,"column":4},"end":{"line":20,"column":1}}"#;
+        assert_eq!(range_start_line(nested), Some(12));
+        assert_eq!(range_end_line(nested), Some(20));
+        let flat = r#"{"startLine":3,"endLine":5}"#;
+        assert_eq!(range_start_line(flat), Some(3));
+        assert_eq!(range_end_line(flat), Some(5));
+
+        assert!(!is_reference_node("function", None));
+        assert!(is_reference_node("call_expression", None));
+        assert!(is_reference_node(
+            "symbol",
+            Some(r#"{"symbolRoles":["ReadAccess","Reference"]}"#)
+        ));
+        assert!(!is_reference_node(
+            "symbol",
+            Some(r#"{"role":"Definition"}"#)
+        ));
+    }
+
+    #[test]
+    fn file_changes_compare_hashes_by_path() {
+        let states = |entries: &[(&str, &str)]| {
+            entries
+                .iter()
+                .map(|(path, hash)| (path.to_string(), hash.to_string()))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let synced = states(&[("src/a.rs", "1"), ("src/b.rs", "2"), ("src/c.rs", "3")]);
+        let current = states(&[("src/a.rs", "1"), ("src/b.rs", "9"), ("src/d.rs", "4")]);
+        let changes = FileChanges::between(&synced, &current);
+        assert_eq!(changes.added, ["src/d.rs"]);
+        assert_eq!(changes.modified, ["src/b.rs"]);
+        assert_eq!(changes.removed, ["src/c.rs"]);
+        assert_eq!(changes.unchanged, 1);
+        assert_eq!(
+            changes.stale_paths().collect::<Vec<_>>(),
+            ["src/d.rs", "src/b.rs", "src/c.rs"]
+        );
+        assert!(FileChanges::between(&current, &current)
+            .stale_paths()
+            .next()
+            .is_none());
+    }
+}
@@ -0,0 +1,174 @@
+//! Entry points for `benches/review_pipeline.rs`, built only with the `bench` feature. They
+//! wrap crate-private review code and return plain counts so the benchmarks do not depend on
+//! internal types.
+
+use std::path::PathBuf;
+
+use libsql::Builder;
+
+use super::review::diff_chunks::{merge_line_windows, parse_diff_chunks, parse_diff_file_chunks};
+use super::review::store::append_ai_review_run_progress;
+use crate::backend::{db, AiReviewChunk, AiReviewFinding, AiReviewProgressEvent, AppState};
+
+/// A unified diff of `files` files with `hunks_per_file` hunks each. Every hunk replaces two
+/// lines and adds one, and hunks are spaced so none of them overlap.
+pub fn synthetic_diff(files: usize, hunks_per_file: usize) -> String {
+    let mut diff = String::new();
+    for file in 0..files {
+        let path = format!("src/module_{file}/file_{file}.rs");
+        diff.push_str(&format!(
+            "diff --git a/{path} b/{path}\nindex 1111111..2222222 100644\n--- a/{path}\n+++ b/{path}\n"
+        ));
+        for hunk in 0..hunks_per_file {
+            let old_start = hunk * 20 + 1;
+            let new_start = hunk * 21 + 1;
+            diff.push_str(&format!(
+                "@@ -{old_start},4 +{new_start},5 @@ fn item_{hunk}() {{\n let value = {hunk};\n-    old_call(value);\n-    old_check(value);\n+    new_call(value);\n+    new_check(value);\n+    trace(value);\n }}\n"
+            ));
+        }
+    }
+    diff
+}
+
+/// Number of hunk chunks in `diff`.
+pub fn parse_hunk_chunks(diff: &str) -> usize {
+    parse_diff_chunks(diff).len()
+}
+
+/// Number of per-file chunks in `diff`, as reviews split it.
+pub fn parse_file_chunks(diff: &str) -> usize {
+    parse_diff_file_chunks(diff).len()
+}
+
+/// Merges the changed lines of every file in `diff` into file-context windows, as review
+/// prompts do. Returns the number of windows.
+pub fn merge_context_windows(diff: &str) -> usize {
+    parse_diff_file_chunks(diff)
+        .iter()
+        .map(|chunk| {
+            let lines = chunk
+                .addition_lines
+                .iter()
+                .chain(&chunk.deletion_lines)
+                .copied()
+                .collect::<Vec<_>>();
+            let max_line = lines.iter().copied().max().unwrap_or(1);
+            merge_line_windows(&lines, max_line).len()
+        })
+        .sum()
+}
+
+/// A run row in a scratch database, for timing how progress events are persisted.
+pub struct ProgressStore {
+    state: AppState,
+    path: PathBuf,
+    run_id: String,
+    total_chunks: usize,
+}
+
+impl ProgressStore {
+    /// Creates a scratch database in the temp directory with one running run of
+    /// `total_chunks` chunks.
+    pub async fn new(total_chunks: usize) -> Result<Self, String> {
+        let path = std::env::temp_dir().join(format!(
+            "rovex-bench-{}-{total_chunks}.db",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let db = Builder::new_local(&path)
+            .build()
+            .await
+            .map_err(|error| format!("Failed to open benchmark database: {error}"))?;
+        db::initialize_schema(&db).await?;
+        let state = AppState {
+            db,
+            database_url: format!("file:{}", path.display()),
+        };
+        let run_id = "bench-run".to_string();
+        let conn = state.connection()?;
+        conn.execute(
+            "INSERT INTO threads (id, title) VALUES (1, 'Benchmark')",
+            (),
+        )
+        .await
+        .map_err(|error| format!("Failed to create benchmark thread: {error}"))?;
+        conn.execute(
+            "INSERT INTO ai_review_runs (
+               run_id, thread_id, workspace, base_ref, merge_base, head,
+               files_changed, insertions, deletions, status, total_chunks
+             )
+             VALUES (?1, 1, '/tmp/bench', 'origin/main', 'base', 'head', ?2, 0, 0, 'running', ?2)",
+            (run_id.clone(), total_chunks as i64),
+        )
+        .await
+        .map_err(|error| format!("Failed to create benchmark run: {error}"))?;
+        Ok(Self {
+            state,
+            path,
+            run_id,
+            total_chunks,
+        })
+    }
+
+    /// Persists the completion of chunk `index` (modulo the run's chunk count) with one
+    /// finding, the way the executor does after each chunk.
+    pub async fn append_chunk_completed(&self, index: usize) -> Result<(), String> {
+        let index = index % self.total_chunks.max(1);
+        let file_path = format!("src/module_{index}/file_{index}.rs");
+        let chunk_id = format!("{file_path}#file-1");
+        let finding = AiReviewFinding {
+            id: format!("{chunk_id}:new:3:1"),
+            file_path: file_path.clone(),
+            chunk_id: chunk_id.clone(),
+            chunk_index: 1,
+            hunk_header: "@@ -1,4 +1,5 @@".to_string(),
+            side: "new".to_string(),
+            line_number: 3,
+            title: "Result of new_call is ignored".to_string(),
+            body: "The error returned by new_call is dropped, so failures go unnoticed."
+                .to_string(),
+            severity: "medium".to_string(),
+            confidence: None,
+            category: None,
+            fingerprint: None,
+            status: None,
+            code_hash: None,
+            recurring: None,
+            suggested_patch: None,
+            language: None,
+            blame: None,
+        };
+        let event = AiReviewProgressEvent {
+            run_id: Some(self.run_id.clone()),
+            thread_id: 1,
+            status: "chunk-complete".to_string(),
+            message: format!("Reviewed {file_path}."),
+            total_chunks: self.total_chunks,
+            completed_chunks: index + 1,
+            chunk_id: Some(chunk_id.clone()),
+            file_path: Some(file_path.clone()),
+            chunk_index: Some(1),
+            finding_count: Some(1),
+            chunk: Some(AiReviewChunk {
+                id: chunk_id,
+                file_path,
+                chunk_index: 1,
+                hunk_header: "@@ -1,4 +1,5 @@".to_string(),
+                summary: "One issue found.".to_string(),
+                findings: vec![finding.clone()],
+                token_usage: None,
+                outcome: Some("reviewed".to_string()),
+                language: None,
+            }),
+            finding: Some(finding),
+            eta_ms: None,
+        };
+        append_ai_review_run_progress(&self.state, &self.run_id, &event).await
+    }
+}
+
+impl Drop for ProgressStore {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
@@ -0,0 +1,537 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::{json, Map, Value};
+
+/// Nesting limit for decoded structures, which also stops reference cycles in plists.
+const MAX_DECODE_DEPTH: usize = 64;
+/// Binary values longer than this are cut in decoded output.
+const MAX_DECODED_BLOB_BYTES: usize = 64;
+const MAX_SQLITE_PAGES_VISITED: usize = 10_000;
+
+/// Turns a binary file format into text, so changes to it can be diffed and reviewed.
+pub(crate) trait BinaryDecoder: Sync {
+    fn name(&self) -> &'static str;
+    fn extensions(&self) -> &'static [&'static str];
+    /// Errors when `bytes` are not in the decoder's format.
+    fn decode(&self, bytes: &[u8]) -> Result<String, String>;
+}
+
+struct PlistDecoder;
+struct SqliteSchemaDecoder;
+struct ProtobufDecoder;
+
+const BINARY_DECODERS: [&dyn BinaryDecoder; 3] =
+    [&PlistDecoder, &SqliteSchemaDecoder, &ProtobufDecoder];
+
+/// Decoder registered for the extension of `path`.
+pub(crate) fn binary_decoder_for(path: &str) -> Option<&'static dyn BinaryDecoder> {
+    let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+    BINARY_DECODERS
+        .into_iter()
+        .find(|decoder| decoder.extensions().contains(&extension.as_str()))
+}
+
+fn read_uint(bytes: &[u8], offset: usize, size: usize) -> Result<u64, String> {
+    let slice = offset
+        .checked_add(size)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(|| "Unexpected end of data.".to_string())?;
+    Ok(slice
+        .iter()
+        .fold(0u64, |value, byte| (value << 8) | u64::from(*byte)))
+}
+
+fn blob_text(bytes: &[u8]) -> String {
+    let shown = &bytes[..bytes.len().min(MAX_DECODED_BLOB_BYTES)];
+    let mut text = STANDARD.encode(shown);
+    if bytes.len() > shown.len() {
+        text.push_str(&format!("... ({} bytes)", bytes.len()));
+    }
+    text
+}
+
+struct BinaryPlist<'a> {
+    bytes: &'a [u8],
+    offset_size: usize,
+    ref_size: usize,
+    object_count: u64,
+    offset_table: usize,
+}
+
+impl BinaryPlist<'_> {
+    fn object_offset(&self, index: u64) -> Result<usize, String> {
+        if index >= self.object_count {
+            return Err(format!("Object reference {index} is out of range."));
+        }
+        let entry = self.offset_table + index as usize * self.offset_size;
+        Ok(read_uint(self.bytes, entry, self.offset_size)? as usize)
+    }
+
+    /// Element count of the object at `offset` and where its contents start.
+    fn count(&self, offset: usize, low: u8) -> Result<(usize, usize), String> {
+        if low != 0x0F {
+            return Ok((usize::from(low), offset + 1));
+        }
+        let marker = *self
+            .bytes
+            .get(offset + 1)
+            .ok_or_else(|| "Unexpected end of data.".to_string())?;
+        if marker >> 4 != 0x1 {
+            return Err("Invalid object length.".to_string());
+        }
+        let size = 1usize << (marker & 0x0F);
+        let count = read_uint(self.bytes, offset + 2, size)? as usize;
+        // Every element takes at least a byte, which also keeps offset math from overflowing.
+        if count > self.bytes.len() {
+            return Err("Invalid object length.".to_string());
+        }
+        Ok((count, offset + 2 + size))
+    }
+
+    fn slice(&self, start: usize, len: usize) -> Result<&[u8], String> {
+        start
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(start..end))
+            .ok_or_else(|| "Unexpected end of data.".to_string())
+    }
+
+    fn object(&self, index: u64, depth: usize) -> Result<Value, String> {
+        if depth > MAX_DECODE_DEPTH {
+            return Err("Plist nesting is too deep.".to_string());
+        }
+        let offset = self.object_offset(index)?;
+        let marker = *self
+            .bytes
+            .get(offset)
+            .ok_or_else(|| "Object offset is out of range.".to_string())?;
+        let low = marker & 0x0F;
+        match marker >> 4 {
+            0x0 => match low {
+                0x0 => Ok(Value::Null),
+                0x8 => Ok(Value::Bool(false)),
+                0x9 => Ok(Value::Bool(true)),
+                _ => Err(format!("Unknown plist marker {marker:#04x}.")),
+            },
+            0x1 => {
+                let size = 1usize << low;
+                // 16-byte integers only occur for values that also fit in the low 8 bytes.
+                let value =
+                    read_uint(self.bytes, offset + 1 + size.saturating_sub(8), size.min(8))?;
+                Ok(if size >= 8 {
+                    json!(value as i64)
+                } else {
+                    json!(value)
+                })
+            }
+            0x2 | 0x3 => {
+                let size = if marker >> 4 == 0x3 { 8 } else { 1usize << low };
+                let raw = read_uint(self.bytes, offset + 1, size)?;
+                let value = match size {
+                    4 => f64::from(f32::from_bits(raw as u32)),
+                    8 => f64::from_bits(raw),
+                    _ => return Err(format!("Unsupported real size {size}.")),
+                };
+                Ok(if marker >> 4 == 0x3 {
+                    json!(format!("date:{value}"))
+                } else {
+                    json!(value)
+                })
+            }
+            0x4 => {
+                let (count, start) = self.count(offset, low)?;
+                Ok(json!(format!(
+                    "data:{}",
+                    blob_text(self.slice(start, count)?)
+                )))
+            }
+            0x5 => {
+                let (count, start) = self.count(offset, low)?;
+                Ok(json!(String::from_utf8_lossy(self.slice(start, count)?)))
+            }
+            0x6 => {
+                let (count, start) = self.count(offset, low)?;
+                let units = self
+                    .slice(start, count * 2)?
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect::<Vec<_>>();
+                Ok(json!(String::from_utf16_lossy(&units)))
+            }
+            0x8 => Ok(json!({ "UID": read_uint(self.bytes, offset + 1, usize::from(low) + 1)? })),
+            0xA => {
+                let (count, start) = self.count(offset, low)?;
+                let mut items = Vec::with_capacity(count.min(1024));
+                for position in 0..count {
+                    let reference =
+                        read_uint(self.bytes, start + position * self.ref_size, self.ref_size)?;
+                    items.push(self.object(reference, depth + 1)?);
+                }
+                Ok(Value::Array(items))
+            }
+            0xD => {
+                let (count, start) = self.count(offset, low)?;
+                let mut entries = Vec::with_capacity(count.min(1024));
+                for position in 0..count {
+                    let key_ref =
+                        read_uint(self.bytes, start + position * self.ref_size, self.ref_size)?;
+                    let value_ref = read_uint(
+                        self.bytes,
+                        start + (count + position) * self.ref_size,
+                        self.ref_size,
+                    )?;
+                    let key = match self.object(key_ref, depth + 1)? {
+                        Value::String(key) => key,
+                        other => other.to_string(),
+                    };
+                    entries.push((key, self.object(value_ref, depth + 1)?));
+                }
+                entries.sort_by(|left, right| left.0.cmp(&right.0));
+                Ok(Value::Object(entries.into_iter().collect::<Map<_, _>>()))
+            }
+            _ => Err(format!("Unknown plist marker {marker:#04x}.")),
+        }
+    }
+}
+
+impl BinaryDecoder for PlistDecoder {
+    fn name(&self) -> &'static str {
+        "binary plist"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["plist"]
+    }
+
+    /// Renders the plist as pretty JSON with sorted keys, so reordering is not a change.
+    fn decode(&self, bytes: &[u8]) -> Result<String, String> {
+        if !bytes.starts_with(b"bplist00") || bytes.len() < 40 {
+            return Err("Not a binary plist.".to_string());
+        }
+        let trailer = bytes.len() - 32;
+        let plist = BinaryPlist {
+            bytes,
+            offset_size: usize::from(bytes[trailer + 6]),
+            ref_size: usize::from(bytes[trailer + 7]),
+            object_count: read_uint(bytes, trailer + 8, 8)?,
+            offset_table: read_uint(bytes, trailer + 24, 8)? as usize,
+        };
+        if !(1..=8).contains(&plist.offset_size) || !(1..=8).contains(&plist.ref_size) {
+            return Err("Invalid binary plist trailer.".to_string());
+        }
+        let root = plist.object(read_uint(bytes, trailer + 16, 8)?, 0)?;
+        serde_json::to_string_pretty(&root)
+            .map_err(|error| format!("Failed to render plist: {error}"))
+    }
+}
+
+fn read_varint(bytes: &[u8], offset: usize) -> Result<(u64, usize), String> {
+    let mut value = 0u64;
+    for index in 0..9 {
+        let byte = *bytes
+            .get(offset + index)
+            .ok_or_else(|| "Unexpected end of data.".to_string())?;
+        if index == 8 {
+            return Ok(((value << 8) | u64::from(byte), 9));
+        }
+        value = (value << 7) | u64::from(byte & 0x7F);
+        if byte & 0x80 == 0 {
+            return Ok((value, index + 1));
+        }
+    }
+    unreachable!("varints end by the ninth byte")
+}
+
+struct SqliteFile<'a> {
+    bytes: &'a [u8],
+    page_size: usize,
+    usable_size: usize,
+    pages_visited: usize,
+}
+
+impl SqliteFile<'_> {
+    fn page(&self, page_number: u64) -> Result<usize, String> {
+        let start = (page_number as usize)
+            .checked_sub(1)
+            .and_then(|index| index.checked_mul(self.page_size))
+            .filter(|start| start + self.page_size <= self.bytes.len())
+            .ok_or_else(|| format!("Page {page_number} is out of range."))?;
+        Ok(start)
+    }
+
+    /// Payload of a table leaf cell, following overflow pages.
+    fn cell_payload(&self, cell: usize) -> Result<Vec<u8>, String> {
+        let (payload_size, used) = read_varint(self.bytes, cell)?;
+        let (_, rowid_used) = read_varint(self.bytes, cell + used)?;
+        let start = cell + used + rowid_used;
+        let payload_size = payload_size as usize;
+        let max_local = self.usable_size - 35;
+        if payload_size <= max_local {
+            return self
+                .bytes
+                .get(start..start + payload_size)
+                .map(<[u8]>::to_vec)
+                .ok_or_else(|| "Cell payload is out of range.".to_string());
+        }
+        let min_local = (self.usable_size - 12) * 32 / 255 - 23;
+        let surplus = min_local + (payload_size - min_local) % (self.usable_size - 4);
+        let local = if surplus <= max_local {
+            surplus
+        } else {
+            min_local
+        };
+        let mut payload = self
+            .bytes
+            .get(start..start + local)
+            .ok_or_else(|| "Cell payload is out of range.".to_string())?
+            .to_vec();
+        let mut next = read_uint(self.bytes, start + local, 4)?;
+        let mut hops = 0;
+        while payload.len() < payload_size && next != 0 {
+            hops += 1;
+            if hops > MAX_SQLITE_PAGES_VISITED {
+                return Err("Overflow chain is too long.".to_string());
+            }
+            let page = self.page(next)?;
+            let take = (payload_size - payload.len()).min(self.usable_size - 4);
+            payload.extend_from_slice(&self.bytes[page + 4..page + 4 + take]);
+            next = read_uint(self.bytes, page, 4)?;
+        }
+        Ok(payload)
+    }
+
+    fn collect_rows(
+        &mut self,
+        page_number: u64,
+        depth: usize,
+        rows: &mut Vec<Vec<Option<String>>>,
+    ) -> Result<(), String> {
+        self.pages_visited += 1;
+        if depth > MAX_DECODE_DEPTH || self.pages_visited > MAX_SQLITE_PAGES_VISITED {
+            return Err("Schema table is too deep.".to_string());
+        }
+        let page = self.page(page_number)?;
+        let header = page + if page_number == 1 { 100 } else { 0 };
+        let cell_count = read_uint(self.bytes, header + 3, 2)? as usize;
+        match self.bytes[header] {
+            0x05 => {
+                for index in 0..cell_count {
+                    let cell = page + read_uint(self.bytes, header + 12 + index * 2, 2)? as usize;
+                    self.collect_rows(read_uint(self.bytes, cell, 4)?, depth + 1, rows)?;
+                }
+                self.collect_rows(read_uint(self.bytes, header + 8, 4)?, depth + 1, rows)
+            }
+            0x0D => {
+                for index in 0..cell_count {
+                    let cell = page + read_uint(self.bytes, header + 8 + index * 2, 2)? as usize;
+                    rows.push(parse_sqlite_record(&self.cell_payload(cell)?)?);
+                }
+                Ok(())
+            }
+            other => Err(format!("Unexpected schema page type {other:#04x}.")),
+        }
+    }
+}
+
+/// Text columns of a record; other values are `None`.
+fn parse_sqlite_record(payload: &[u8]) -> Result<Vec<Option<String>>, String> {
+    let (header_size, mut cursor) = read_varint(payload, 0)?;
+    let mut serial_types = Vec::new();
+    while cursor < header_size as usize {
+        let (serial_type, used) = read_varint(payload, cursor)?;
+        serial_types.push(serial_type);
+        cursor += used;
+    }
+    let mut offset = header_size as usize;
+    let mut values = Vec::with_capacity(serial_types.len());
+    for serial_type in serial_types {
+        let size = match serial_type {
+            0 | 8 | 9 => 0,
+            1..=4 => serial_type as usize,
+            5 => 6,
+            6 | 7 => 8,
+            value if value >= 12 => ((value - 12) / 2) as usize,
+            value => return Err(format!("Unknown serial type {value}.")),
+        };
+        let bytes = payload
+            .get(offset..offset + size)
+            .ok_or_else(|| "Record is out of range.".to_string())?;
+        values.push(
+            (serial_type >= 13 && serial_type % 2 == 1)
+                .then(|| String::from_utf8_lossy(bytes).into_owned()),
+        );
+        offset += size;
+    }
+    Ok(values)
+}
+
+impl BinaryDecoder for SqliteSchemaDecoder {
+    fn name(&self) -> &'static str {
+        "SQLite schema"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["sqlite", "sqlite3", "db"]
+    }
+
+    /// Lists the schema objects of the database, ordered by type and name. Row data is
+    /// left out.
+    fn decode(&self, bytes: &[u8]) -> Result<String, String> {
+        if !bytes.starts_with(b"SQLite format 3\0") || bytes.len() < 100 {
+            return Err("Not a SQLite database.".to_string());
+        }
+        let page_size = match read_uint(bytes, 16, 2)? {
+            1 => 65_536,
+            size => size as usize,
+        };
+        let reserved = usize::from(bytes[20]);
+        if page_size < 512 || reserved >= page_size - 480 {
+            return Err("Invalid SQLite page size.".to_string());
+        }
+        let mut file = SqliteFile {
+            bytes,
+            page_size,
+            usable_size: page_size - reserved,
+            pages_visited: 0,
+        };
+        let mut rows = Vec::new();
+        file.collect_rows(1, 0, &mut rows)?;
+
+        let mut objects = rows
+            .into_iter()
+            .map(|row| {
+                let column = |index: usize| row.get(index).cloned().flatten();
+                (
+                    column(0).unwrap_or_default(),
+                    column(1).unwrap_or_default(),
+                    column(4),
+                )
+            })
+            .collect::<Vec<_>>();
+        objects.sort();
+        let mut text = String::new();
+        for (kind, name, sql) in objects {
+            text.push_str(&format!("-- {kind} {name}\n"));
+            match sql {
+                Some(sql) => text.push_str(&format!("{};\n", sql.trim())),
+                None => text.push_str("-- (created automatically)\n"),
+            }
+        }
+        Ok(text)
+    }
+}
+
+fn read_protobuf_varint(bytes: &[u8], offset: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*offset)?;
+        *offset += 1;
+        value |= u64::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn is_printable_text(bytes: &[u8]) -> bool {
+    std::str::from_utf8(bytes).is_ok_and(|text| {
+        text.chars()
+            .all(|character| !character.is_control() || matches!(character, '\n' | '\t'))
+    })
+}
+
+/// Appends the fields of a protobuf message as text, or returns `None` when `bytes` is not a
+/// well-formed message. Field names are unknown without the schema, so numbers stand in.
+fn push_protobuf_fields(bytes: &[u8], depth: usize, out: &mut String) -> Option<()> {
+    let indent = "  ".repeat(depth);
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let key = read_protobuf_varint(bytes, &mut offset)?;
+        let field = key >> 3;
+        if field == 0 {
+            return None;
+        }
+        match key & 0x07 {
+            0 => {
+                let value = read_protobuf_varint(bytes, &mut offset)?;
+                out.push_str(&format!("{indent}{field}: {value}\n"));
+            }
+            1 => {
+                let value = read_uint(bytes, offset, 8).ok()?;
+                offset += 8;
+                out.push_str(&format!("{indent}{field}: {value:#018x}\n"));
+            }
+            2 => {
+                let len = usize::try_from(read_protobuf_varint(bytes, &mut offset)?).ok()?;
+                let value = bytes.get(offset..offset.checked_add(len)?)?;
+                offset += len;
+                let mut nested = String::new();
+                if is_printable_text(value) {
+                    let text = String::from_utf8_lossy(value);
+                    out.push_str(&format!("{indent}{field}: {text:?}\n"));
+                } else if depth < MAX_DECODE_DEPTH
+                    && push_protobuf_fields(value, depth + 1, &mut nested).is_some()
+                {
+                    out.push_str(&format!("{indent}{field} {{\n{nested}{indent}}}\n"));
+                } else {
+                    out.push_str(&format!("{indent}{field}: bytes:{}\n", blob_text(value)));
+                }
+            }
+            5 => {
+                let value = read_uint(bytes, offset, 4).ok()?;
+                offset += 4;
+                out.push_str(&format!("{indent}{field}: {value:#010x}\n"));
+            }
+            // Groups are deprecated and never appear in descriptors.
+            _ => return None,
+        }
+    }
+    Some(())
+}
+
+impl BinaryDecoder for ProtobufDecoder {
+    fn name(&self) -> &'static str {
+        "protobuf"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["pb", "binpb", "desc", "protoset"]
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<String, String> {
+        let mut text = String::new();
+        push_protobuf_fields(bytes, 0, &mut text)
+            .ok_or_else(|| "Not a protobuf message.".to_string())?;
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::binary_decoder_for;
+
+    /// `{"name": "rovex", "debug": true, "tags": ["a"]}` written by `plistlib`.
+    const BINARY_PLIST: &[u8] = b"bplist00\xd3\x01\x02\x03\x04\x05\x06TnameUdebugTtagsUrovex\t\xa1\x07Qa\x08\x0f\x14\x1a\x1f%&(\x00\x00\x00\x00\x00\x00\x01\x01\x00\x00\x00\x00\x00\x00\x00\x08\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00*";
+
+    #[test]
+    fn decoders_are_picked_by_extension_and_render_text() {
+        assert!(binary_decoder_for("assets/logo.png").is_none());
+        let plist = binary_decoder_for("Info.PLIST").expect("plist decoder");
+        let decoded = plist.decode(BINARY_PLIST).expect("decode plist");
+        assert_eq!(
+            decoded,
+            "{\n  \"debug\": true,\n  \"name\": \"rovex\",\n  \"tags\": [\n    \"a\"\n  ]\n}"
+        );
+        assert!(plist.decode(b"<?xml version=\"1.0\"?>").is_err());
+
+        // FileDescriptorSet { file { name: "a.proto" message_type { name: "A" } } }
+        let descriptor = b"\x0a\x10\x0a\x07a.proto\x22\x05\x0a\x01A\x10\x01";
+        let decoded = binary_decoder_for("api.desc")
+            .expect("protobuf decoder")
+            .decode(descriptor)
+            .expect("decode descriptor");
+        assert_eq!(
+            decoded,
+            "1 {\n  1: \"a.proto\"\n  4 {\n    1: \"A\"\n    2: 1\n  }\n}\n"
+        );
+    }
+}
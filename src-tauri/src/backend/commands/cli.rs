@@ -0,0 +1,355 @@
+use std::path::Path;
+
+use super::common::format_path;
+use super::review::finding_order::FindingOrder;
+use super::review::findings::{FINDING_STATUS_DISMISSED, FINDING_STATUS_FIXED};
+use super::review::junit::build_junit_report;
+use super::review::report::build_markdown_report;
+use super::review::run_queue::run_review_headless;
+use super::review::sarif::build_sarif_report;
+use super::threads::find_or_create_thread;
+use super::workspace_git::compare_workspace_diff;
+use crate::backend::paths::normalize_path;
+use crate::backend::{
+    AiReviewRun, AppState, CompareWorkspaceDiffInput, CompareWorkspaceDiffResult,
+    StartAiReviewRunInput, WorkspaceDiffTarget,
+};
+
+/// Thread that collects CLI runs of a workspace, so they show up together in the app.
+const CLI_THREAD_TITLE: &str = "CLI reviews";
+
+const EXIT_OK: i32 = 0;
+const EXIT_FINDINGS: i32 = 1;
+const EXIT_ERROR: i32 = 2;
+
+const USAGE: &str = "Usage: rovex-cli <command> [options]
+
+Commands:
+  compare-diff    Print the diff that a review would cover
+  review          Review the diff and print findings (alias: start-review)
+
+Options:
+  --workspace <path>     Repository to review (default: current directory)
+  --base <ref>           Base ref to compare against (default: the app's base ref)
+  --target <target>      branch, working-tree or staged (default: branch)
+  --no-fetch             Do not fetch the remote before comparing
+  --include <glob>       Only review matching paths (repeatable)
+  --exclude <glob>       Skip matching paths (repeatable)
+  --prompt <text>        Reviewer goal for this run
+  --format <format>      markdown, json, sarif or junit (default: markdown)
+  --fail-on <severity>   Exit with 1 when an open finding is at least critical, high,
+                         medium or low, or never with none (default: critical)
+  -h, --help             Show this help
+
+Exit codes: 0 on success, 1 when findings reach --fail-on, 2 on errors.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CliCommand {
+    CompareDiff,
+    Review,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Markdown,
+    Json,
+    Sarif,
+    Junit,
+}
+
+#[derive(Debug)]
+struct CliOptions {
+    command: CliCommand,
+    workspace: Option<String>,
+    base_ref: Option<String>,
+    diff_target: WorkspaceDiffTarget,
+    fetch_remote: bool,
+    include_paths: Vec<String>,
+    exclude_paths: Vec<String>,
+    prompt: Option<String>,
+    format: OutputFormat,
+    /// Lowest severity rank that fails the command, or `None` to never fail on findings.
+    fail_on: Option<u8>,
+}
+
+fn severity_rank(severity: &str) -> Option<u8> {
+    match severity {
+        "critical" => Some(0),
+        "high" => Some(1),
+        "medium" => Some(2),
+        "low" => Some(3),
+        _ => None,
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Option<CliOptions>, String> {
+    let mut args = args.iter();
+    let command = match args.next().map(String::as_str) {
+        None | Some("-h" | "--help" | "help") => return Ok(None),
+        Some("compare-diff") => CliCommand::CompareDiff,
+        Some("review" | "start-review") => CliCommand::Review,
+        Some(other) => return Err(format!("Unknown command '{other}'.")),
+    };
+    let mut options = CliOptions {
+        command,
+        workspace: None,
+        base_ref: None,
+        diff_target: WorkspaceDiffTarget::Branch,
+        fetch_remote: true,
+        include_paths: Vec::new(),
+        exclude_paths: Vec::new(),
+        prompt: None,
+        format: OutputFormat::Markdown,
+        fail_on: severity_rank("critical"),
+    };
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| format!("Missing value for {flag}."))
+        };
+        match flag {
+            "-h" | "--help" => return Ok(None),
+            "--workspace" => options.workspace = Some(value()?),
+            "--base" => options.base_ref = Some(value()?),
+            "--target" => {
+                options.diff_target = match value()?.as_str() {
+                    "branch" => WorkspaceDiffTarget::Branch,
+                    "working-tree" => WorkspaceDiffTarget::WorkingTree,
+                    "staged" => WorkspaceDiffTarget::Staged,
+                    other => return Err(format!("Unknown diff target '{other}'.")),
+                }
+            }
+            "--no-fetch" => options.fetch_remote = false,
+            "--include" => options.include_paths.push(value()?),
+            "--exclude" => options.exclude_paths.push(value()?),
+            "--prompt" => options.prompt = Some(value()?),
+            "--format" => {
+                options.format = match value()?.as_str() {
+                    "markdown" | "md" => OutputFormat::Markdown,
+                    "json" => OutputFormat::Json,
+                    "sarif" => OutputFormat::Sarif,
+                    "junit" => OutputFormat::Junit,
+                    other => return Err(format!("Unknown output format '{other}'.")),
+                }
+            }
+            "--fail-on" => {
+                let severity = value()?;
+                options.fail_on = match severity.as_str() {
+                    "none" => None,
+                    other => Some(
+                        severity_rank(other)
+                            .ok_or_else(|| format!("Unknown severity '{other}'."))?,
+                    ),
+                }
+            }
+            other => return Err(format!("Unknown option '{other}'.")),
+        }
+    }
+    if options.command == CliCommand::CompareDiff
+        && matches!(options.format, OutputFormat::Sarif | OutputFormat::Junit)
+    {
+        return Err("compare-diff supports only markdown and json output.".to_string());
+    }
+    Ok(Some(options))
+}
+
+/// Open findings at or above the `--fail-on` severity.
+fn failing_finding_count(run: &AiReviewRun, fail_on: Option<u8>) -> usize {
+    let Some(fail_on) = fail_on else {
+        return 0;
+    };
+    run.findings
+        .iter()
+        .filter(|finding| {
+            !matches!(
+                finding.status.as_deref(),
+                Some(FINDING_STATUS_DISMISSED | FINDING_STATUS_FIXED)
+            )
+        })
+        .filter(|finding| severity_rank(&finding.severity).is_some_and(|rank| rank <= fail_on))
+        .count()
+}
+
+async fn compare(
+    options: &CliOptions,
+    workspace: &str,
+) -> Result<CompareWorkspaceDiffResult, String> {
+    let as_filter = |paths: &[String]| (!paths.is_empty()).then(|| paths.to_vec());
+    compare_workspace_diff(CompareWorkspaceDiffInput {
+        workspace: workspace.to_string(),
+        base_ref: options.base_ref.clone(),
+        fetch_remote: Some(options.fetch_remote),
+        include_paths: as_filter(&options.include_paths),
+        exclude_paths: as_filter(&options.exclude_paths),
+        diff_target: Some(options.diff_target),
+        include_untracked: None,
+        head_ref: None,
+        include_submodule_diffs: None,
+        force: None,
+        remote: None,
+    })
+    .await
+}
+
+async fn review(options: &CliOptions, workspace: &str) -> Result<AiReviewRun, String> {
+    let diff = compare(options, workspace).await?;
+    if diff.diff.trim().is_empty() {
+        return Err("There are no changes to review.".to_string());
+    }
+    if diff.diff_truncated {
+        eprintln!(
+            "[review] Diff truncated to {} of {} bytes.",
+            diff.diff_bytes_used, diff.diff_bytes_total
+        );
+    }
+
+    let state = AppState::initialize_headless().await?;
+    let thread = find_or_create_thread(&state, CLI_THREAD_TITLE, &diff.workspace).await?;
+    run_review_headless(
+        &state,
+        StartAiReviewRunInput {
+            thread_id: thread.id,
+            workspace: diff.workspace,
+            base_ref: diff.base_ref,
+            merge_base: diff.merge_base,
+            head: diff.head,
+            files_changed: diff.files_changed,
+            insertions: diff.insertions,
+            deletions: diff.deletions,
+            diff: diff.diff,
+            prompt: options.prompt.clone(),
+            scope_label: None,
+            max_parallel_chunks: None,
+            review_mode: None,
+            // The diff is already filtered; passing the filters again only re-applies them.
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: Some(options.diff_target),
+            idempotency_key: None,
+            profile_id: None,
+            min_confidence: None,
+        },
+    )
+    .await
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value)
+        .map_err(|error| format!("Failed to serialize output: {error}"))
+}
+
+async fn run_command(options: &CliOptions) -> Result<i32, String> {
+    let workspace = match options.workspace.as_deref() {
+        Some(workspace) => workspace.to_string(),
+        None => std::env::current_dir()
+            .map_err(|error| format!("Failed to read the current directory: {error}"))?
+            .to_string_lossy()
+            .into_owned(),
+    };
+    let workspace = format_path(&normalize_path(Path::new(&workspace))?);
+
+    match options.command {
+        CliCommand::CompareDiff => {
+            let diff = compare(options, &workspace).await?;
+            match options.format {
+                OutputFormat::Json => println!("{}", to_json(&diff)?),
+                _ => print!("{}", diff.diff),
+            }
+            Ok(EXIT_OK)
+        }
+        CliCommand::Review => {
+            let mut run = review(options, &workspace).await?;
+            if run.status == "failed" {
+                return Err(run
+                    .error
+                    .unwrap_or_else(|| format!("Review run {} failed.", run.run_id)));
+            }
+            if run.status == "completed_with_errors" {
+                eprintln!(
+                    "[review] {} of {} file(s) could not be reviewed.",
+                    run.failed_chunks, run.total_chunks
+                );
+            }
+            run.progress_events.clear();
+            match options.format {
+                OutputFormat::Markdown => {
+                    print!("{}", build_markdown_report(&run, &FindingOrder::default()))
+                }
+                OutputFormat::Json => println!("{}", to_json(&run)?),
+                OutputFormat::Sarif => println!("{}", to_json(&build_sarif_report(&run))?),
+                OutputFormat::Junit => print!("{}", build_junit_report(&run)),
+            }
+
+            let failing = failing_finding_count(&run, options.fail_on);
+            if failing > 0 {
+                eprintln!("[review] {failing} open finding(s) at or above the --fail-on severity.");
+                return Ok(EXIT_FINDINGS);
+            }
+            Ok(EXIT_OK)
+        }
+    }
+}
+
+/// Entry point of the `rovex-cli` binary. `args` excludes the program name.
+pub(crate) fn run(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return EXIT_OK;
+        }
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return EXIT_ERROR;
+        }
+    };
+    match tauri::async_runtime::block_on(run_command(&options)) {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("rovex-cli: {error}");
+            EXIT_ERROR
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_args, CliCommand, OutputFormat};
+    use crate::backend::WorkspaceDiffTarget;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_review_options_and_rejects_unknown_flags() {
+        let options = parse_args(&args(&[
+            "start-review",
+            "--target=staged",
+            "--format",
+            "json",
+            "--exclude",
+            "*.lock",
+            "--fail-on",
+            "none",
+        ]))
+        .expect("valid arguments")
+        .expect("a command");
+        assert_eq!(options.command, CliCommand::Review);
+        assert_eq!(options.diff_target, WorkspaceDiffTarget::Staged);
+        assert_eq!(options.format, OutputFormat::Json);
+        assert_eq!(options.exclude_paths, vec!["*.lock".to_string()]);
+        assert_eq!(options.fail_on, None);
+
+        assert!(parse_args(&args(&["review", "--verbose"])).is_err());
+        assert!(parse_args(&args(&["review", "--base"])).is_err());
+        assert!(parse_args(&args(&["--help"])).expect("help").is_none());
+    }
+}
@@ -0,0 +1,249 @@
+use std::path::Path;
+
+use tauri::State;
+
+use super::common::format_path;
+use super::threads::load_thread_by_id;
+use crate::backend::code_intel;
+use crate::backend::paths::normalize_path;
+use crate::backend::{
+    AppState, CodeIntelOptions, CodeIntelSyncInput, CodeIntelSyncResult, CodeIntelSyncStatus,
+    CodeIntelSyncStatusInput, CodeIntelWorkspaceConfig, GetCodeIntelConfigInput,
+    SetCodeIntelConfigInput,
+};
+
+/// The key a workspace's code-intel config is saved under, matching the project root a sync
+/// of that workspace resolves to.
+fn workspace_key(workspace: &str) -> Result<String, String> {
+    let workspace = workspace.trim();
+    if workspace.is_empty() {
+        return Err("Workspace path must not be empty.".to_string());
+    }
+    Ok(format_path(&normalize_path(Path::new(workspace))?))
+}
+
+fn clean_list(values: Option<Vec<String>>) -> Option<Vec<String>> {
+    let values = values?
+        .into_iter()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect::<Vec<_>>();
+    (!values.is_empty()).then_some(values)
+}
+
+fn clean_text(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Drops blank entries so they fall back to the environment, and rejects zero limits.
+fn clean_options(options: CodeIntelOptions) -> Result<CodeIntelOptions, String> {
+    for (name, value) in [
+        ("Vector dimension", options.vector_dimension),
+        ("Vector batch size", options.vector_batch_size),
+        ("Vector concurrency", options.vector_concurrency),
+    ] {
+        if value == Some(0) {
+            return Err(format!("{name} must be at least 1."));
+        }
+    }
+    if options
+        .vector_timeout_ms
+        .is_some_and(|timeout_ms| timeout_ms < 1_000)
+    {
+        return Err("Vector timeout must be at least 1000 ms.".to_string());
+    }
+    Ok(CodeIntelOptions {
+        paths: clean_list(options.paths),
+        include: clean_list(options.include),
+        exclude: clean_list(options.exclude),
+        kitedb_store_path: clean_text(options.kitedb_store_path),
+        vector_base_url: clean_text(options.vector_base_url),
+        vector_model: clean_text(options.vector_model),
+        ..options
+    })
+}
+
+/// `options`, with the fields it leaves unset taken from `saved`.
+fn overlay_options(options: CodeIntelOptions, saved: CodeIntelOptions) -> CodeIntelOptions {
+    CodeIntelOptions {
+        paths: options.paths.or(saved.paths),
+        include: options.include.or(saved.include),
+        exclude: options.exclude.or(saved.exclude),
+        respect_gitignore: options.respect_gitignore.or(saved.respect_gitignore),
+        use_scip: options.use_scip.or(saved.use_scip),
+        kitedb_store_path: options.kitedb_store_path.or(saved.kitedb_store_path),
+        vector_base_url: options.vector_base_url.or(saved.vector_base_url),
+        vector_model: options.vector_model.or(saved.vector_model),
+        vector_dimension: options.vector_dimension.or(saved.vector_dimension),
+        vector_timeout_ms: options.vector_timeout_ms.or(saved.vector_timeout_ms),
+        vector_batch_size: options.vector_batch_size.or(saved.vector_batch_size),
+        vector_concurrency: options.vector_concurrency.or(saved.vector_concurrency),
+    }
+}
+
+async fn load_code_intel_config(
+    state: &AppState,
+    workspace: &str,
+) -> Result<CodeIntelWorkspaceConfig, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT options_json, updated_at FROM code_intel_configs WHERE workspace = ?1",
+            [workspace.to_string()],
+        )
+        .await
+        .map_err(|error| format!("Failed to load code-intel config of {workspace}: {error}"))?;
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read code-intel config of {workspace}: {error}"))?
+    else {
+        return Ok(CodeIntelWorkspaceConfig {
+            workspace: workspace.to_string(),
+            options: CodeIntelOptions::default(),
+            updated_at: None,
+        });
+    };
+    let options_json: String = row
+        .get(0)
+        .map_err(|error| format!("Failed to parse code-intel config: {error}"))?;
+    Ok(CodeIntelWorkspaceConfig {
+        workspace: workspace.to_string(),
+        options: serde_json::from_str(&options_json)
+            .map_err(|error| format!("Failed to parse code-intel config: {error}"))?,
+        updated_at: row
+            .get(1)
+            .map_err(|error| format!("Failed to parse code-intel config timestamp: {error}"))?,
+    })
+}
+
+/// The project root a sync covers: the one given, else the thread's workspace, else
+/// `CODE_INTEL_PROJECT_ROOT`.
+async fn resolve_project_root(
+    state: &AppState,
+    project_root: Option<String>,
+    thread_id: Option<i64>,
+) -> Result<String, String> {
+    if let Some(project_root) = clean_text(project_root) {
+        return Ok(project_root);
+    }
+    if let Some(thread_id) = thread_id {
+        let thread = load_thread_by_id(state, thread_id).await?;
+        return clean_text(thread.workspace)
+            .ok_or_else(|| format!("Thread {thread_id} has no workspace."));
+    }
+    Ok(code_intel::default_project_root())
+}
+
+/// The project root of a sync or status request and its options, with the saved config of
+/// that workspace filling in what the request leaves unset.
+async fn resolve_sync_options(
+    state: &AppState,
+    project_root: Option<String>,
+    thread_id: Option<i64>,
+    options: CodeIntelOptions,
+) -> Result<(String, CodeIntelOptions), String> {
+    let project_root = resolve_project_root(state, project_root, thread_id).await?;
+    let saved = load_code_intel_config(state, &workspace_key(&project_root)?).await?;
+    Ok((
+        project_root,
+        overlay_options(clean_options(options)?, saved.options),
+    ))
+}
+
+pub async fn get_code_intel_config(
+    state: State<'_, AppState>,
+    input: GetCodeIntelConfigInput,
+) -> Result<CodeIntelWorkspaceConfig, String> {
+    load_code_intel_config(&state, &workspace_key(&input.workspace)?).await
+}
+
+pub async fn set_code_intel_config(
+    state: State<'_, AppState>,
+    input: SetCodeIntelConfigInput,
+) -> Result<CodeIntelWorkspaceConfig, String> {
+    let workspace = workspace_key(&input.workspace)?;
+    let options = clean_options(input.options)?;
+    let options_json = serde_json::to_string(&options)
+        .map_err(|error| format!("Failed to serialize code-intel config: {error}"))?;
+    let conn = state.connection()?;
+    conn.execute(
+        "INSERT INTO code_intel_configs (workspace, options_json) VALUES (?1, ?2)
+         ON CONFLICT(workspace) DO UPDATE SET
+           options_json = excluded.options_json,
+           updated_at = CURRENT_TIMESTAMP",
+        (workspace.clone(), options_json),
+    )
+    .await
+    .map_err(|error| format!("Failed to save code-intel config of {workspace}: {error}"))?;
+    load_code_intel_config(&state, &workspace).await
+}
+
+pub async fn run_code_intel_sync(
+    state: State<'_, AppState>,
+    input: Option<CodeIntelSyncInput>,
+) -> Result<CodeIntelSyncResult, String> {
+    let input = input.unwrap_or_default();
+    let (project_root, options) =
+        resolve_sync_options(&state, input.project_root, input.thread_id, input.options).await?;
+    code_intel::run_code_intel_sync(Some(CodeIntelSyncInput {
+        project_root: Some(project_root),
+        thread_id: input.thread_id,
+        clear_kitedb: input.clear_kitedb,
+        clear_turso_project: input.clear_turso_project,
+        incremental: input.incremental,
+        options,
+    }))
+    .await
+}
+
+pub async fn code_intel_sync_status(
+    state: State<'_, AppState>,
+    input: Option<CodeIntelSyncStatusInput>,
+) -> Result<CodeIntelSyncStatus, String> {
+    let input = input.unwrap_or_default();
+    let options = CodeIntelOptions {
+        respect_gitignore: input.respect_gitignore,
+        ..CodeIntelOptions::default()
+    };
+    let (project_root, options) =
+        resolve_sync_options(&state, input.project_root, input.thread_id, options).await?;
+    code_intel::code_intel_sync_status(Some(project_root), options).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clean_options, overlay_options};
+    use crate::backend::CodeIntelOptions;
+
+    #[test]
+    fn request_options_override_the_saved_config_field_by_field() {
+        let saved = CodeIntelOptions {
+            paths: Some(vec!["packages/api".to_string()]),
+            vector_model: Some("saved-model".to_string()),
+            use_scip: Some(false),
+            ..CodeIntelOptions::default()
+        };
+        let request = clean_options(CodeIntelOptions {
+            paths: Some(vec!["  ".to_string()]),
+            vector_model: Some("request-model".to_string()),
+            vector_dimension: Some(768),
+            ..CodeIntelOptions::default()
+        })
+        .unwrap();
+        let options = overlay_options(request, saved);
+        assert_eq!(options.paths, Some(vec!["packages/api".to_string()]));
+        assert_eq!(options.vector_model.as_deref(), Some("request-model"));
+        assert_eq!(options.vector_dimension, Some(768));
+        assert_eq!(options.use_scip, Some(false));
+        assert_eq!(options.include, None);
+
+        assert!(clean_options(CodeIntelOptions {
+            vector_batch_size: Some(0),
+            ..CodeIntelOptions::default()
+        })
+        .is_err());
+    }
+}
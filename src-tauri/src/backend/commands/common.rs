@@ -1,21 +1,27 @@
-use std::{
-    env, fs,
-    path::{Path, PathBuf},
-};
+use std::path::Path;
 
 use serde::de::DeserializeOwned;
 
+use crate::backend::secrets::get_secret;
+use crate::backend::settings::settings_store;
 use crate::backend::{AiReviewConfig, MessageRole, ProviderKind};
 
 pub(crate) const DEFAULT_LIMIT: i64 = 50;
 pub(crate) const MAX_LIMIT: i64 = 200;
 pub(crate) const DEFAULT_REPOSITORIES_DIR: &str = "rovex/repos";
+pub(crate) const ROVEX_REPOSITORIES_DIR_ENV: &str = "ROVEX_REPOSITORIES_DIR";
 pub(crate) const OPENAI_API_KEY_ENV: &str = "OPENAI_API_KEY";
 pub(crate) const ROVEX_REVIEW_PROVIDER_ENV: &str = "ROVEX_REVIEW_PROVIDER";
 pub(crate) const ROVEX_REVIEW_MODEL_ENV: &str = "ROVEX_REVIEW_MODEL";
 pub(crate) const ROVEX_REVIEW_BASE_URL_ENV: &str = "ROVEX_REVIEW_BASE_URL";
 pub(crate) const ROVEX_REVIEW_MAX_DIFF_CHARS_ENV: &str = "ROVEX_REVIEW_MAX_DIFF_CHARS";
+pub(crate) const ROVEX_REVIEW_MAX_FILE_CHARS_ENV: &str = "ROVEX_REVIEW_MAX_FILE_CHARS";
 pub(crate) const ROVEX_REVIEW_TIMEOUT_MS_ENV: &str = "ROVEX_REVIEW_TIMEOUT_MS";
+pub(crate) const ROVEX_REVIEW_TRIAGE_MODEL_ENV: &str = "ROVEX_REVIEW_TRIAGE_MODEL";
+pub(crate) const ROVEX_REVIEW_TRIAGE_MIN_FILES_ENV: &str = "ROVEX_REVIEW_TRIAGE_MIN_FILES";
+pub(crate) const ROVEX_REVIEW_CROSS_FILE_ENV: &str = "ROVEX_REVIEW_CROSS_FILE";
+pub(crate) const ROVEX_REVIEW_FOLLOW_UP_SUGGESTIONS_ENV: &str =
+    "ROVEX_REVIEW_FOLLOW_UP_SUGGESTIONS";
 pub(crate) const ROVEX_OPENCODE_MODEL_ENV: &str = "ROVEX_OPENCODE_MODEL";
 pub(crate) const ROVEX_OPENCODE_HOSTNAME_ENV: &str = "ROVEX_OPENCODE_HOSTNAME";
 pub(crate) const ROVEX_OPENCODE_PORT_ENV: &str = "ROVEX_OPENCODE_PORT";
@@ -23,15 +29,57 @@ pub(crate) const ROVEX_OPENCODE_SERVER_TIMEOUT_MS_ENV: &str = "ROVEX_OPENCODE_SE
 pub(crate) const ROVEX_OPENCODE_PROVIDER_ENV: &str = "ROVEX_OPENCODE_PROVIDER";
 pub(crate) const ROVEX_OPENCODE_AGENT_ENV: &str = "ROVEX_OPENCODE_AGENT";
 pub(crate) const ROVEX_APP_SERVER_COMMAND_ENV: &str = "ROVEX_APP_SERVER_COMMAND";
+pub(crate) const ROVEX_REVIEW_MAX_PARALLEL_RUNS_ENV: &str = "ROVEX_REVIEW_MAX_PARALLEL_RUNS";
+pub(crate) const ROVEX_REVIEW_MAX_PARALLEL_CHUNKS_ENV: &str = "ROVEX_REVIEW_MAX_PARALLEL_CHUNKS";
+pub(crate) const ROVEX_REVIEW_INTERACTIVE_PARALLEL_CHUNKS_ENV: &str =
+    "ROVEX_REVIEW_INTERACTIVE_PARALLEL_CHUNKS";
+pub(crate) const ROVEX_REVIEW_MAX_PROVIDER_CHUNKS_ENV: &str = "ROVEX_REVIEW_MAX_PROVIDER_CHUNKS";
+pub(crate) const ROVEX_REVIEW_MAX_SESSION_RESTARTS_ENV: &str = "ROVEX_REVIEW_MAX_SESSION_RESTARTS";
+pub(crate) const ROVEX_REVIEW_REQUESTS_PER_MINUTE_ENV: &str = "ROVEX_REVIEW_REQUESTS_PER_MINUTE";
+pub(crate) const ROVEX_REVIEW_MIN_FINDING_BODY_CHARS_ENV: &str =
+    "ROVEX_REVIEW_MIN_FINDING_BODY_CHARS";
+pub(crate) const ROVEX_REVIEW_MIN_CONFIDENCE_ENV: &str = "ROVEX_REVIEW_MIN_CONFIDENCE";
+pub(crate) const ROVEX_REVIEW_VERDICT_MAX_CRITICAL_ENV: &str = "ROVEX_REVIEW_VERDICT_MAX_CRITICAL";
+pub(crate) const ROVEX_REVIEW_VERDICT_MAX_HIGH_ENV: &str = "ROVEX_REVIEW_VERDICT_MAX_HIGH";
+pub(crate) const ROVEX_MAX_RUN_SNAPSHOTS_ENV: &str = "ROVEX_MAX_RUN_SNAPSHOTS";
+pub(crate) const ROVEX_REVIEW_WEBHOOK_URL_ENV: &str = "ROVEX_REVIEW_WEBHOOK_URL";
+pub(crate) const ROVEX_REVIEW_SLACK_WEBHOOK_URL_ENV: &str = "ROVEX_REVIEW_SLACK_WEBHOOK_URL";
+pub(crate) const ROVEX_REVIEW_WEBHOOK_EVENTS_ENV: &str = "ROVEX_REVIEW_WEBHOOK_EVENTS";
+pub(crate) const ROVEX_CLI_PATH_ENV: &str = "ROVEX_CLI_PATH";
+pub(crate) const ROVEX_WORKSPACE_SYNC_INTERVAL_MINUTES_ENV: &str =
+    "ROVEX_WORKSPACE_SYNC_INTERVAL_MINUTES";
+pub(crate) const ROVEX_WORKSPACE_WATCH_INTERVAL_MS_ENV: &str = "ROVEX_WORKSPACE_WATCH_INTERVAL_MS";
+pub(crate) const ROVEX_MAX_ATTACHMENT_BYTES_ENV: &str = "ROVEX_MAX_ATTACHMENT_BYTES";
+pub(crate) const ROVEX_FOLLOW_UP_CACHE_TTL_SECONDS_ENV: &str = "ROVEX_FOLLOW_UP_CACHE_TTL_SECONDS";
+pub(crate) const ROVEX_TEAM_STATS_ENV: &str = "ROVEX_TEAM_STATS";
+pub(crate) const ROVEX_TEAM_STATS_MIN_RUNS_ENV: &str = "ROVEX_TEAM_STATS_MIN_RUNS";
+pub(crate) const ROVEX_FINDING_ACTIONS_ENV: &str = "ROVEX_FINDING_ACTIONS";
 pub(crate) const DEFAULT_REVIEW_PROVIDER: &str = "openai";
 pub(crate) const DEFAULT_REVIEW_MODEL: &str = "gpt-4.1-mini";
 pub(crate) const DEFAULT_REVIEW_BASE_URL: &str = "https://api.openai.com/v1";
 pub(crate) const DEFAULT_REVIEW_MAX_DIFF_CHARS: usize = 120_000;
+pub(crate) const DEFAULT_REVIEW_MAX_FILE_CHARS: usize = 60_000;
 pub(crate) const DEFAULT_REVIEW_TIMEOUT_MS: u64 = 120_000;
+pub(crate) const DEFAULT_REVIEW_REQUESTS_PER_MINUTE: u64 = 120;
+pub(crate) const DEFAULT_REVIEW_MIN_FINDING_BODY_CHARS: usize = 40;
+pub(crate) const DEFAULT_REVIEW_TRIAGE_MIN_FILES: usize = 8;
+pub(crate) const DEFAULT_VERDICT_MAX_CRITICAL: usize = 0;
+pub(crate) const DEFAULT_VERDICT_MAX_HIGH: usize = 2;
+pub(crate) const DEFAULT_MAX_RUN_SNAPSHOTS: usize = 5;
+/// Weeks with fewer completed runs than this are left out of team stats.
+pub(crate) const DEFAULT_TEAM_STATS_MIN_RUNS: u64 = 5;
+pub(crate) const DEFAULT_REVIEW_WEBHOOK_EVENTS: &str = "completed,failed,canceled";
+pub(crate) const DEFAULT_WORKSPACE_SYNC_INTERVAL_MINUTES: u64 = 15;
+pub(crate) const DEFAULT_WORKSPACE_WATCH_INTERVAL_MS: u64 = 2_000;
+pub(crate) const DEFAULT_MAX_ATTACHMENT_BYTES: usize = 64 * 1024;
+pub(crate) const MAX_ATTACHMENTS_PER_MESSAGE: i64 = 5;
+pub(crate) const MAX_IDEMPOTENCY_KEY_CHARS: usize = 128;
 pub(crate) const MAX_COMPARE_DIFF_BYTES: usize = 4_000_000;
 pub(crate) const COMPARE_ENABLE_RENAMES: bool = true;
 pub(crate) const DEFAULT_FOLLOW_UP_HISTORY_CHARS: usize = 40_000;
+pub(crate) const MAX_FOLLOW_UP_DIFF_CHARS: usize = 24_000;
 pub(crate) const MAX_FOLLOW_UP_MESSAGES: i64 = 40;
+pub(crate) const DEFAULT_FOLLOW_UP_CACHE_TTL_SECONDS: u64 = 120;
 pub(crate) const DEFAULT_OPENCODE_HOSTNAME: &str = "127.0.0.1";
 pub(crate) const DEFAULT_OPENCODE_PORT: u16 = 4096;
 pub(crate) const DEFAULT_OPENCODE_SERVER_TIMEOUT_MS: u64 = 5_000;
@@ -42,13 +90,25 @@ pub(crate) const DEFAULT_APP_SERVER_COMMAND: &str = "codex";
 pub(crate) const DEFAULT_APP_SERVER_STATUS_TIMEOUT_MS: u64 = 5_000;
 pub(crate) const OPENCODE_SIDECAR_NAME: &str = "opencode";
 pub(crate) const AI_REVIEW_PROGRESS_EVENT: &str = "rovex://ai-review-progress";
+pub(crate) const WORKSPACE_CHANGED_EVENT: &str = "rovex://workspace-changed";
+pub(crate) const WORKSPACE_FETCH_PROGRESS_EVENT: &str = "rovex://workspace-fetch-progress";
 pub(crate) const MAX_CHUNK_FILE_CONTEXT_CHARS: usize = 6_000;
 pub(crate) const MAX_CHUNK_FILE_CONTEXT_WINDOWS: usize = 8;
-pub(crate) const MAX_PARALLEL_REVIEW_RUNS: usize = 8;
-pub(crate) const MAX_PARALLEL_CHUNKS_PER_RUN: usize = 4;
+pub(crate) const DEFAULT_MAX_PARALLEL_REVIEW_RUNS: usize = 8;
+pub(crate) const DEFAULT_MAX_PARALLEL_CHUNKS_PER_RUN: usize = 4;
+pub(crate) const DEFAULT_INTERACTIVE_PARALLEL_CHUNKS_PER_RUN: usize = 1;
+pub(crate) const MAX_PARALLEL_REVIEW_RUNS_LIMIT: usize = 64;
+pub(crate) const MAX_PARALLEL_CHUNKS_PER_RUN_LIMIT: usize = 64;
+/// Chunks reviewed at once with one provider, across all runs.
+pub(crate) const DEFAULT_MAX_PROVIDER_CHUNKS: usize = 8;
+pub(crate) const MAX_PROVIDER_CHUNKS_LIMIT: usize = 256;
 pub(crate) const MAX_PROGRESS_EVENTS_PER_RUN: usize = 200;
 pub(crate) const CHUNK_RETRY_MAX_ATTEMPTS: usize = 3;
 pub(crate) const CHUNK_RETRY_BASE_DELAY_MS: u64 = 500;
+/// App-server or OpenCode processes one run may restart after they die mid-chunk.
+pub(crate) const DEFAULT_MAX_SESSION_RESTARTS_PER_RUN: usize = 3;
+pub(crate) const DB_WRITE_RETRY_MAX_ATTEMPTS: usize = 4;
+pub(crate) const DB_WRITE_RETRY_BASE_DELAY_MS: u64 = 250;
 
 pub(crate) fn parse_limit(limit: Option<u32>) -> i64 {
     limit
@@ -71,30 +131,145 @@ pub(crate) fn parse_provider_kind(value: String) -> Result<ProviderKind, String>
         .ok_or_else(|| format!("Unexpected provider value in database: {value}"))
 }
 
-pub(crate) fn parse_env_u64(name: &str, fallback: u64, min: u64) -> u64 {
-    env::var(name)
-        .ok()
-        .and_then(|value| value.trim().parse::<u64>().ok())
+pub(crate) fn parse_setting_u64(name: &str, fallback: u64, min: u64) -> u64 {
+    settings_store()
+        .get_string(name)
+        .and_then(|value| value.parse::<u64>().ok())
         .filter(|value| *value >= min)
         .unwrap_or(fallback)
 }
 
-pub(crate) fn parse_env_u16(name: &str, fallback: u16, min: u16) -> u16 {
-    env::var(name)
-        .ok()
-        .and_then(|value| value.trim().parse::<u16>().ok())
+pub(crate) fn parse_setting_u16(name: &str, fallback: u16, min: u16) -> u16 {
+    settings_store()
+        .get_string(name)
+        .and_then(|value| value.parse::<u16>().ok())
         .filter(|value| *value >= min)
         .unwrap_or(fallback)
 }
 
-pub(crate) fn parse_env_usize(name: &str, fallback: usize, min: usize) -> usize {
-    env::var(name)
-        .ok()
-        .and_then(|value| value.trim().parse::<usize>().ok())
+pub(crate) fn parse_setting_usize(name: &str, fallback: usize, min: usize) -> usize {
+    settings_store()
+        .get_string(name)
+        .and_then(|value| value.parse::<usize>().ok())
         .filter(|value| *value >= min)
         .unwrap_or(fallback)
 }
 
+/// Confidence below which findings are suppressed, or `None` when the filter is off. A value
+/// on the run input wins over the `ROVEX_REVIEW_MIN_CONFIDENCE` setting, so a run can pass `0`
+/// to see everything.
+pub(crate) fn current_min_confidence(requested: Option<f64>) -> Option<f64> {
+    requested
+        .or_else(|| {
+            settings_store()
+                .get_string(ROVEX_REVIEW_MIN_CONFIDENCE_ENV)
+                .and_then(|value| value.parse::<f64>().ok())
+        })
+        .filter(|value| value.is_finite())
+        .map(|value| value.clamp(0.0, 1.0))
+        .filter(|value| *value > 0.0)
+}
+
+/// Model that triages changed files before the deep review, or `None` when every file goes
+/// straight to the review model.
+pub(crate) fn current_triage_model() -> Option<String> {
+    settings_store().get_string(ROVEX_REVIEW_TRIAGE_MODEL_ENV)
+}
+
+/// Patch size, in characters, above which a changed file is skipped instead of reviewed.
+pub(crate) fn current_review_max_file_chars() -> usize {
+    parse_setting_usize(
+        ROVEX_REVIEW_MAX_FILE_CHARS_ENV,
+        DEFAULT_REVIEW_MAX_FILE_CHARS,
+        1_000,
+    )
+}
+
+pub(crate) fn current_triage_min_files() -> usize {
+    parse_setting_usize(
+        ROVEX_REVIEW_TRIAGE_MIN_FILES_ENV,
+        DEFAULT_REVIEW_TRIAGE_MIN_FILES,
+        1,
+    )
+}
+
+/// Whether runs on trusted workspaces add a cross-file pass over the code-intel graph. On
+/// unless `ROVEX_REVIEW_CROSS_FILE` turns it off; without a synced graph the pass is skipped.
+pub(crate) fn cross_file_review_enabled() -> bool {
+    !settings_store()
+        .get_string(ROVEX_REVIEW_CROSS_FILE_ENV)
+        .is_some_and(|value| ["0", "false", "no", "off"].contains(&value.to_lowercase().as_str()))
+}
+
+/// Whether app runs ask the model for follow-up questions once the review is done. On unless
+/// `ROVEX_REVIEW_FOLLOW_UP_SUGGESTIONS` turns it off; headless runs never ask.
+pub(crate) fn follow_up_suggestions_enabled() -> bool {
+    !settings_store()
+        .get_string(ROVEX_REVIEW_FOLLOW_UP_SUGGESTIONS_ENV)
+        .is_some_and(|value| ["0", "false", "no", "off"].contains(&value.to_lowercase().as_str()))
+}
+
+/// Whether aggregate team stats may be read from the app database. Off unless
+/// `ROVEX_TEAM_STATS` turns it on.
+pub(crate) fn team_stats_enabled() -> bool {
+    settings_store()
+        .get_string(ROVEX_TEAM_STATS_ENV)
+        .is_some_and(|value| ["1", "true", "yes", "on"].contains(&value.to_lowercase().as_str()))
+}
+
+pub(crate) fn current_team_stats_min_runs() -> u64 {
+    parse_setting_u64(
+        ROVEX_TEAM_STATS_MIN_RUNS_ENV,
+        DEFAULT_TEAM_STATS_MIN_RUNS,
+        2,
+    )
+}
+
+pub(crate) fn current_max_parallel_review_runs() -> usize {
+    parse_setting_usize(
+        ROVEX_REVIEW_MAX_PARALLEL_RUNS_ENV,
+        DEFAULT_MAX_PARALLEL_REVIEW_RUNS,
+        1,
+    )
+    .min(MAX_PARALLEL_REVIEW_RUNS_LIMIT)
+}
+
+pub(crate) fn current_max_parallel_chunks_per_run() -> usize {
+    parse_setting_usize(
+        ROVEX_REVIEW_MAX_PARALLEL_CHUNKS_ENV,
+        DEFAULT_MAX_PARALLEL_CHUNKS_PER_RUN,
+        1,
+    )
+    .min(MAX_PARALLEL_CHUNKS_PER_RUN_LIMIT)
+}
+
+/// Chunks a run starts at once while the user is interacting with the app.
+pub(crate) fn current_interactive_parallel_chunks_per_run() -> usize {
+    parse_setting_usize(
+        ROVEX_REVIEW_INTERACTIVE_PARALLEL_CHUNKS_ENV,
+        DEFAULT_INTERACTIVE_PARALLEL_CHUNKS_PER_RUN,
+        1,
+    )
+    .min(MAX_PARALLEL_CHUNKS_PER_RUN_LIMIT)
+}
+
+pub(crate) fn current_max_provider_chunks() -> usize {
+    parse_setting_usize(
+        ROVEX_REVIEW_MAX_PROVIDER_CHUNKS_ENV,
+        DEFAULT_MAX_PROVIDER_CHUNKS,
+        1,
+    )
+    .min(MAX_PROVIDER_CHUNKS_LIMIT)
+}
+
+pub(crate) fn current_max_session_restarts() -> usize {
+    parse_setting_usize(
+        ROVEX_REVIEW_MAX_SESSION_RESTARTS_ENV,
+        DEFAULT_MAX_SESSION_RESTARTS_PER_RUN,
+        0,
+    )
+}
+
 pub(crate) fn truncate_utf8_by_bytes(value: &str, max_bytes: usize) -> (String, bool) {
     if value.len() <= max_bytes {
         return (value.to_string(), false);
@@ -109,8 +284,14 @@ pub(crate) fn truncate_utf8_by_bytes(value: &str, max_bytes: usize) -> (String,
 }
 
 pub(crate) fn truncate_chars(value: &str, max_chars: usize) -> (String, bool) {
+    let (prefix, truncated) = char_prefix(value, max_chars);
+    (prefix.to_string(), truncated)
+}
+
+/// The first `max_chars` characters of `value` without copying them, and whether any were cut.
+pub(crate) fn char_prefix(value: &str, max_chars: usize) -> (&str, bool) {
     if max_chars == 0 {
-        return (String::new(), !value.is_empty());
+        return ("", !value.is_empty());
     }
 
     let mut end = value.len();
@@ -124,12 +305,9 @@ pub(crate) fn truncate_chars(value: &str, max_chars: usize) -> (String, bool) {
     }
 
     if count < max_chars {
-        (value.to_string(), false)
+        (value, false)
     } else {
-        (
-            value[..end].to_string(),
-            value[end..].chars().next().is_some(),
-        )
+        (&value[..end], value[end..].chars().next().is_some())
     }
 }
 
@@ -140,73 +318,26 @@ pub(crate) fn as_non_empty_trimmed(value: Option<&str>) -> Option<String> {
         .map(ToOwned::to_owned)
 }
 
+/// A client-supplied idempotency key, trimmed. Blank keys mean the caller did not send one.
+pub(crate) fn parse_idempotency_key(value: Option<&str>) -> Result<Option<String>, String> {
+    let key = as_non_empty_trimmed(value);
+    if key
+        .as_deref()
+        .is_some_and(|key| key.chars().count() > MAX_IDEMPOTENCY_KEY_CHARS)
+    {
+        return Err(format!(
+            "Idempotency key must be at most {MAX_IDEMPOTENCY_KEY_CHARS} characters."
+        ));
+    }
+    Ok(key)
+}
+
 pub(crate) fn snippet(value: &str, max_chars: usize) -> String {
     truncate_chars(value, max_chars).0
 }
 
 pub(crate) fn format_path(path: &Path) -> String {
-    path.to_string_lossy().to_string()
-}
-
-pub(crate) fn resolve_env_file_path() -> Option<PathBuf> {
-    if let Ok(configured) = env::var("ROVEX_ENV_FILE") {
-        let configured = configured.trim();
-        if !configured.is_empty() {
-            return Some(PathBuf::from(configured));
-        }
-    }
-
-    let cwd = env::current_dir().ok()?;
-    let direct = cwd.join(".env");
-    if direct.exists() {
-        return Some(direct);
-    }
-
-    let parent = cwd.parent().map(|value| value.join(".env"));
-    if let Some(parent) = parent {
-        if parent.exists() {
-            return Some(parent);
-        }
-    }
-
-    Some(direct)
-}
-
-pub(crate) fn upsert_env_key(path: &Path, key: &str, value: &str) -> Result<(), String> {
-    let existing = match fs::read_to_string(path) {
-        Ok(content) => content,
-        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
-        Err(error) => return Err(format!("Failed to read {}: {error}", format_path(path))),
-    };
-
-    let mut lines: Vec<String> = existing.lines().map(ToOwned::to_owned).collect();
-    let mut updated = false;
-
-    for line in &mut lines {
-        let trimmed = line.trim_start();
-        if trimmed.starts_with('#') {
-            continue;
-        }
-        let Some((name, _)) = line.split_once('=') else {
-            continue;
-        };
-        if name.trim() == key {
-            *line = format!("{key}={value}");
-            updated = true;
-            break;
-        }
-    }
-
-    if !updated {
-        lines.push(format!("{key}={value}"));
-    }
-
-    let mut next = lines.join("\n");
-    if !next.is_empty() && !next.ends_with('\n') {
-        next.push('\n');
-    }
-
-    fs::write(path, next).map_err(|error| format!("Failed to write {}: {error}", format_path(path)))
+    dunce::simplified(path).to_string_lossy().to_string()
 }
 
 pub(crate) fn mask_secret(value: &str) -> Option<String> {
@@ -233,10 +364,9 @@ pub(crate) fn mask_secret(value: &str) -> Option<String> {
 }
 
 pub(crate) fn current_review_provider_value() -> String {
-    let provider = env::var(ROVEX_REVIEW_PROVIDER_ENV)
-        .ok()
-        .map(|value| value.trim().to_lowercase())
-        .filter(|value| !value.is_empty())
+    let provider = settings_store()
+        .get_string(ROVEX_REVIEW_PROVIDER_ENV)
+        .map(|value| value.to_lowercase())
         .unwrap_or_else(|| DEFAULT_REVIEW_PROVIDER.to_string());
     match provider.as_str() {
         "openai" => "openai".to_string(),
@@ -246,35 +376,45 @@ pub(crate) fn current_review_provider_value() -> String {
     }
 }
 
+pub(crate) fn current_openai_api_key() -> Option<String> {
+    let settings = settings_store();
+    if let Some(api_key) = settings.get::<String>(OPENAI_API_KEY_ENV) {
+        return Some(api_key);
+    }
+    match get_secret(OPENAI_API_KEY_ENV) {
+        Ok(Some(api_key)) => Some(api_key),
+        Ok(None) => settings.get_string(OPENAI_API_KEY_ENV),
+        Err(error) => {
+            eprintln!("[backend] {error}");
+            settings.get_string(OPENAI_API_KEY_ENV)
+        }
+    }
+}
+
 pub(crate) fn current_ai_review_config() -> AiReviewConfig {
-    let api_key = env::var(OPENAI_API_KEY_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
-    let review_model = env::var(ROVEX_REVIEW_MODEL_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
+    let settings = settings_store();
+    let api_key = current_openai_api_key();
+    let review_model = settings
+        .get_string(ROVEX_REVIEW_MODEL_ENV)
         .unwrap_or_else(|| DEFAULT_REVIEW_MODEL.to_string());
-    let opencode_provider = env::var(ROVEX_OPENCODE_PROVIDER_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
+    let opencode_provider = settings
+        .get_string(ROVEX_OPENCODE_PROVIDER_ENV)
         .unwrap_or_else(|| DEFAULT_OPENCODE_PROVIDER.to_string());
-    let opencode_model = env::var(ROVEX_OPENCODE_MODEL_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
+    let opencode_model = settings
+        .get_string(ROVEX_OPENCODE_MODEL_ENV)
         .or_else(|| Some(DEFAULT_OPENCODE_MODEL.to_string()));
-    let env_file_path = resolve_env_file_path().map(|path| format_path(path.as_path()));
     AiReviewConfig {
         has_api_key: api_key.is_some(),
         api_key_preview: api_key.as_deref().and_then(mask_secret),
-        env_file_path,
         review_provider: current_review_provider_value(),
         review_model,
         opencode_provider,
         opencode_model,
+        triage_model: current_triage_model(),
+        triage_min_files: current_triage_min_files(),
+        max_parallel_review_runs: current_max_parallel_review_runs(),
+        max_parallel_chunks_per_run: current_max_parallel_chunks_per_run(),
+        max_provider_chunks: current_max_provider_chunks(),
     }
 }
 
@@ -0,0 +1,196 @@
+use libsql::Connection;
+
+use crate::backend::db::rebuild_findings_search_index;
+use crate::backend::{
+    AppState, DatabaseMaintenanceInput, DatabaseMaintenanceResult, DatabaseStats, OrphanedRows,
+};
+
+/// Problems `PRAGMA integrity_check` reports before it stops looking.
+const MAX_INTEGRITY_ERRORS: usize = 100;
+
+/// Tables whose rows belong to a thread, message, run or review group, with the rows whose
+/// owner is gone. Schedules have no foreign key and outlive their thread, and databases
+/// written with foreign keys switched off keep every kind. Rows come before the rows they
+/// reference, so a cleanup removes findings and attachments in the same pass.
+const ORPHAN_TARGETS: [(&str, &str); 10] = [
+    (
+        "message_attachments",
+        "thread_id NOT IN (SELECT id FROM threads) OR message_id NOT IN (SELECT id FROM messages)",
+    ),
+    (
+        "ai_review_findings",
+        "thread_id NOT IN (SELECT id FROM threads) OR run_id NOT IN (SELECT run_id FROM ai_review_runs)",
+    ),
+    ("messages", "thread_id NOT IN (SELECT id FROM threads)"),
+    ("ai_review_runs", "thread_id NOT IN (SELECT id FROM threads)"),
+    (
+        "inline_review_comments",
+        "thread_id NOT IN (SELECT id FROM threads)",
+    ),
+    (
+        "pull_request_comments",
+        "thread_id NOT IN (SELECT id FROM threads)",
+    ),
+    ("thread_diff_chunks", "thread_id NOT IN (SELECT id FROM threads)"),
+    ("thread_settings", "thread_id NOT IN (SELECT id FROM threads)"),
+    ("review_schedules", "thread_id NOT IN (SELECT id FROM threads)"),
+    (
+        "review_group_runs",
+        "group_id NOT IN (SELECT id FROM review_groups)",
+    ),
+];
+
+/// Turso manages storage of remote databases, which do not accept `VACUUM`.
+fn is_remote_database(database_url: &str) -> bool {
+    database_url.starts_with("libsql://") || database_url.starts_with("https://")
+}
+
+async fn query_i64(conn: &Connection, sql: &str, label: &str) -> Result<i64, String> {
+    let mut rows = conn
+        .query(sql, ())
+        .await
+        .map_err(|error| format!("Failed to read the database {label}: {error}"))?;
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read the database {label}: {error}"))?
+    else {
+        return Ok(0);
+    };
+    row.get(0)
+        .map_err(|error| format!("Failed to parse the database {label}: {error}"))
+}
+
+async fn load_database_stats(conn: &Connection) -> Result<DatabaseStats, String> {
+    let page_size = query_i64(conn, "PRAGMA page_size", "page size").await?;
+    let page_count = query_i64(conn, "PRAGMA page_count", "page count").await?;
+    let freelist_count = query_i64(conn, "PRAGMA freelist_count", "free page count").await?;
+    Ok(DatabaseStats {
+        page_size,
+        page_count,
+        freelist_count,
+        size_bytes: page_size * page_count,
+        free_bytes: page_size * freelist_count,
+    })
+}
+
+/// The problems `PRAGMA integrity_check` found; empty when it reported `ok`.
+async fn check_integrity(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut rows = conn
+        .query(
+            &format!("PRAGMA integrity_check({MAX_INTEGRITY_ERRORS})"),
+            (),
+        )
+        .await
+        .map_err(|error| format!("Failed to check database integrity: {error}"))?;
+    let mut problems = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read the integrity check: {error}"))?
+    {
+        let message: String = row
+            .get(0)
+            .map_err(|error| format!("Failed to parse the integrity check: {error}"))?;
+        if message != "ok" {
+            problems.push(message);
+        }
+    }
+    Ok(problems)
+}
+
+async fn count_orphans(conn: &Connection) -> Result<Vec<OrphanedRows>, String> {
+    let mut orphans = Vec::with_capacity(ORPHAN_TARGETS.len());
+    for (table, filter) in ORPHAN_TARGETS {
+        let rows = query_i64(
+            conn,
+            &format!("SELECT COUNT(*) FROM {table} WHERE {filter}"),
+            &format!("{table} orphan count"),
+        )
+        .await?;
+        orphans.push(OrphanedRows {
+            table: table.to_string(),
+            rows,
+        });
+    }
+    Ok(orphans)
+}
+
+/// Deletes every orphaned row in one transaction and returns the deleted row count per table.
+async fn delete_orphans(conn: &Connection) -> Result<Vec<OrphanedRows>, String> {
+    let transaction = conn
+        .transaction()
+        .await
+        .map_err(|error| format!("Failed to start the cleanup transaction: {error}"))?;
+    let mut orphans = Vec::with_capacity(ORPHAN_TARGETS.len());
+    for (table, filter) in ORPHAN_TARGETS {
+        let rows = transaction
+            .execute(&format!("DELETE FROM {table} WHERE {filter}"), ())
+            .await
+            .map_err(|error| format!("Failed to delete orphaned {table} rows: {error}"))?;
+        orphans.push(OrphanedRows {
+            table: table.to_string(),
+            rows: rows as i64,
+        });
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(|error| format!("Failed to commit the cleanup transaction: {error}"))?;
+    Ok(orphans)
+}
+
+/// Reports the size of the app database and the rows left behind by deleted threads, and
+/// on request checks its integrity, deletes those rows and vacuums it.
+pub async fn database_maintenance(
+    state: &AppState,
+    input: DatabaseMaintenanceInput,
+) -> Result<DatabaseMaintenanceResult, String> {
+    let integrity_check = input.integrity_check.unwrap_or(true);
+    let cleanup_orphans = input.cleanup_orphans.unwrap_or(false);
+    let vacuum = input.vacuum.unwrap_or(false);
+    let remote = is_remote_database(state.database_url());
+    if vacuum && remote {
+        return Err(
+            "VACUUM only runs on a local database; Turso manages the storage of remote ones."
+                .to_string(),
+        );
+    }
+
+    let conn = state.connection()?;
+    let stats = load_database_stats(&conn).await?;
+    let integrity_problems = if integrity_check {
+        Some(check_integrity(&conn).await?)
+    } else {
+        None
+    };
+    let orphans = if cleanup_orphans {
+        delete_orphans(&conn).await?
+    } else {
+        count_orphans(&conn).await?
+    };
+    if vacuum {
+        conn.execute("VACUUM", ())
+            .await
+            .map_err(|error| format!("Failed to vacuum the database: {error}"))?;
+        rebuild_findings_search_index(&conn).await?;
+    }
+    let stats_after = if cleanup_orphans || vacuum {
+        Some(load_database_stats(&conn).await?)
+    } else {
+        None
+    };
+
+    Ok(DatabaseMaintenanceResult {
+        database_url: state.database_url().to_string(),
+        remote,
+        stats,
+        integrity_ok: integrity_problems.as_ref().map(Vec::is_empty),
+        integrity_problems: integrity_problems.unwrap_or_default(),
+        orphaned_rows: orphans.iter().map(|orphan| orphan.rows).sum(),
+        orphans,
+        orphans_removed: cleanup_orphans,
+        vacuumed: vacuum,
+        stats_after,
+    })
+}
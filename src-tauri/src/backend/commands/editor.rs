@@ -109,7 +109,12 @@ fn build_ghostty_command(template: &str, file_path: &str) -> String {
 fn spawn_ghostty(command: String) -> Result<(), String> {
     spawn_command(
         "ghostty",
-        vec!["-e".to_string(), "cmd".to_string(), "/C".to_string(), command],
+        vec![
+            "-e".to_string(),
+            "cmd".to_string(),
+            "/C".to_string(),
+            command,
+        ],
     )
 }
 
@@ -117,6 +122,11 @@ fn spawn_ghostty(command: String) -> Result<(), String> {
 fn spawn_ghostty(command: String) -> Result<(), String> {
     spawn_command(
         "ghostty",
-        vec!["-e".to_string(), "sh".to_string(), "-lc".to_string(), command],
+        vec![
+            "-e".to_string(),
+            "sh".to_string(),
+            "-lc".to_string(),
+            command,
+        ],
     )
 }
@@ -0,0 +1,358 @@
+use std::{
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::Path,
+    time::UNIX_EPOCH,
+};
+
+use git2::{Repository, Status, StatusOptions};
+
+use super::workspace_git::{
+    parse_status_summary, read_git_trimmed_if_success, run_git, run_git_trimmed, StatusSummary,
+};
+
+/// A workspace's refs, merge bases and status, read in process with libgit2. A diff refresh
+/// or branch listing asks for these many times, and spawning `git` for each one is slow on
+/// Windows and in repositories with many refs. Diffs themselves are still produced by `git`.
+pub(crate) struct GitRepo<'a> {
+    path: &'a Path,
+    /// `None` when libgit2 cannot open the repository, for example because it uses an
+    /// extension libgit2 does not support; every read then runs `git` instead.
+    repo: Option<Repository>,
+}
+
+/// `refs/remotes/origin/main` as `origin/main` and `refs/heads/main` as `main`, like
+/// `%(refname:short)`.
+fn short_ref_name(name: &str) -> &str {
+    ["refs/heads/", "refs/remotes/", "refs/tags/", "refs/"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name)
+}
+
+impl<'a> GitRepo<'a> {
+    pub(crate) fn open(path: &'a Path) -> Self {
+        Self {
+            path,
+            repo: Repository::discover(path).ok(),
+        }
+    }
+
+    /// Whether the path is inside a working tree, as `git rev-parse --is-inside-work-tree`.
+    pub(crate) fn is_work_tree(&self) -> Result<bool, String> {
+        if let Some(repo) = &self.repo {
+            return Ok(!repo.is_bare());
+        }
+        Ok(run_git_trimmed(
+            self.path,
+            &["rev-parse", "--is-inside-work-tree"],
+            "rev-parse",
+        )? == "true")
+    }
+
+    pub(crate) fn is_shallow(&self) -> bool {
+        match &self.repo {
+            Some(repo) => repo.is_shallow(),
+            None => {
+                read_git_trimmed_if_success(self.path, &["rev-parse", "--is-shallow-repository"])
+                    .as_deref()
+                    == Some("true")
+            }
+        }
+    }
+
+    /// Object id of the commit `reference` names, or `None` when it names none.
+    pub(crate) fn commit_id(&self, reference: &str) -> Option<String> {
+        let Some(repo) = &self.repo else {
+            return read_git_trimmed_if_success(
+                self.path,
+                &[
+                    "rev-parse",
+                    "--verify",
+                    "--quiet",
+                    &format!("{reference}^{{commit}}"),
+                ],
+            );
+        };
+        let commit = repo
+            .revparse_single(reference)
+            .ok()?
+            .peel_to_commit()
+            .ok()?;
+        Some(commit.id().to_string())
+    }
+
+    /// Like [`Self::commit_id`], but with git's explanation when `reference` names no commit.
+    pub(crate) fn resolve_commit(&self, reference: &str, context: &str) -> Result<String, String> {
+        if let Some(commit) = self.commit_id(reference) {
+            return Ok(commit);
+        }
+        run_git_trimmed(
+            self.path,
+            &["rev-parse", "--verify", &format!("{reference}^{{commit}}")],
+            context,
+        )
+    }
+
+    /// Best common ancestor of two commits. When libgit2 finds none, `git merge-base` is asked
+    /// for the answer or the error.
+    pub(crate) fn merge_base(&self, left: &str, right: &str) -> Result<String, String> {
+        let in_process = self.repo.as_ref().and_then(|repo| {
+            let left = repo.revparse_single(left).ok()?.peel_to_commit().ok()?.id();
+            let right = repo
+                .revparse_single(right)
+                .ok()?
+                .peel_to_commit()
+                .ok()?
+                .id();
+            repo.merge_base(left, right).ok()
+        });
+        match in_process {
+            Some(merge_base) => Ok(merge_base.to_string()),
+            None => run_git_trimmed(
+                self.path,
+                &["merge-base", left, right],
+                "resolve merge-base",
+            ),
+        }
+    }
+
+    /// Branch HEAD is on, also before its first commit; `None` when HEAD is detached.
+    pub(crate) fn current_branch(&self) -> Result<Option<String>, String> {
+        let Some(repo) = &self.repo else {
+            let branch = run_git_trimmed(
+                self.path,
+                &["branch", "--show-current"],
+                "branch --show-current",
+            )?;
+            return Ok((!branch.is_empty()).then_some(branch));
+        };
+        let head = repo
+            .find_reference("HEAD")
+            .map_err(|error| format!("Failed to read HEAD: {error}"))?;
+        Ok(head
+            .symbolic_target()
+            .and_then(|target| target.strip_prefix("refs/heads/"))
+            .map(ToOwned::to_owned))
+    }
+
+    /// Short name of the branch `branch` is configured to track, such as `origin/main`.
+    pub(crate) fn upstream_of(&self, branch: &str) -> Result<Option<String>, String> {
+        let branch_ref = format!("refs/heads/{branch}");
+        let Some(repo) = &self.repo else {
+            let upstream = run_git_trimmed(
+                self.path,
+                &["for-each-ref", "--format=%(upstream:short)", &branch_ref],
+                "resolve branch upstream",
+            )?;
+            return Ok((!upstream.is_empty()).then_some(upstream));
+        };
+        Ok(repo
+            .branch_upstream_name(&branch_ref)
+            .ok()
+            .and_then(|name| name.as_str().map(|name| short_ref_name(name).to_string())))
+    }
+
+    /// Short names of the refs under `namespace`, such as `refs/heads`, in no particular order.
+    pub(crate) fn ref_names(&self, namespace: &str) -> Result<Vec<String>, String> {
+        let Some(repo) = &self.repo else {
+            let output = run_git(
+                self.path,
+                &["for-each-ref", "--format=%(refname:short)", namespace],
+                "for-each-ref",
+            )?;
+            return Ok(String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(ToOwned::to_owned)
+                .collect());
+        };
+        let references = repo
+            .references_glob(&format!("{namespace}/*"))
+            .map_err(|error| format!("Failed to list {namespace}: {error}"))?;
+        Ok(references
+            .filter_map(Result::ok)
+            .filter_map(|reference| {
+                reference
+                    .name()
+                    .map(|name| short_ref_name(name).to_string())
+            })
+            .collect())
+    }
+
+    /// The branch `refs/remotes/<remote>/HEAD` points at, such as `origin/main`.
+    pub(crate) fn remote_head(&self, remote: &str) -> Option<String> {
+        let head_ref = format!("refs/remotes/{remote}/HEAD");
+        let Some(repo) = &self.repo else {
+            return read_git_trimmed_if_success(
+                self.path,
+                &["symbolic-ref", "--quiet", "--short", &head_ref],
+            );
+        };
+        let remote_head = repo.find_reference(&head_ref).ok()?;
+        remote_head
+            .symbolic_target()
+            .map(|target| short_ref_name(target).to_string())
+    }
+
+    /// Name of the remote `branch` is configured to track, from `branch.<name>.remote`.
+    pub(crate) fn upstream_remote_of(&self, branch: &str) -> Option<String> {
+        let Some(repo) = &self.repo else {
+            return read_git_trimmed_if_success(
+                self.path,
+                &["config", &format!("branch.{branch}.remote")],
+            );
+        };
+        repo.branch_upstream_remote(&format!("refs/heads/{branch}"))
+            .ok()?
+            .as_str()
+            .map(ToOwned::to_owned)
+    }
+
+    /// The remote fetches and base refs default to: the current branch's upstream remote,
+    /// then `origin`, then the first remote. `origin` when there are no remotes.
+    pub(crate) fn default_remote(&self) -> String {
+        let remotes = self.remote_names();
+        let upstream_remote = self
+            .current_branch()
+            .ok()
+            .flatten()
+            .and_then(|branch| self.upstream_remote_of(&branch))
+            .filter(|remote| remote != ".");
+        upstream_remote
+            .or_else(|| remotes.iter().find(|name| *name == "origin").cloned())
+            .or_else(|| remotes.first().cloned())
+            .unwrap_or_else(|| "origin".to_string())
+    }
+
+    pub(crate) fn remote_url(&self, remote: &str) -> Option<String> {
+        let Some(repo) = &self.repo else {
+            return read_git_trimmed_if_success(self.path, &["remote", "get-url", remote]);
+        };
+        repo.find_remote(remote).ok()?.url().map(ToOwned::to_owned)
+    }
+
+    pub(crate) fn remote_names(&self) -> Vec<String> {
+        let Some(repo) = &self.repo else {
+            return read_git_trimmed_if_success(self.path, &["remote"])
+                .unwrap_or_default()
+                .lines()
+                .map(|name| name.trim().to_string())
+                .collect();
+        };
+        repo.remotes()
+            .map(|names| names.iter().flatten().map(ToOwned::to_owned).collect())
+            .unwrap_or_default()
+    }
+
+    /// A hash of the index's paths, modes and blobs, which changes whenever something is staged
+    /// or unstaged. Unlike the index file's modification time, it ignores the stat data `git
+    /// status` refreshes.
+    pub(crate) fn index_fingerprint(&self) -> Option<u64> {
+        let mut hasher = DefaultHasher::new();
+        match &self.repo {
+            Some(repo) => {
+                for entry in repo.index().ok()?.iter() {
+                    (&entry.path, entry.mode, entry.flags, entry.id.as_bytes()).hash(&mut hasher);
+                }
+            }
+            None => read_git_trimmed_if_success(self.path, &["ls-files", "--stage", "-z"])
+                .unwrap_or_default()
+                .hash(&mut hasher),
+        }
+        Some(hasher.finish())
+    }
+
+    /// When `FETCH_HEAD` was last written, which every fetch does, in milliseconds since the
+    /// Unix epoch.
+    pub(crate) fn last_fetched_at(&self) -> Option<u64> {
+        let fetch_head = match &self.repo {
+            Some(repo) => repo.path().join("FETCH_HEAD"),
+            None => self.path.join(read_git_trimmed_if_success(
+                self.path,
+                &["rev-parse", "--git-path", "FETCH_HEAD"],
+            )?),
+        };
+        let modified = fs::metadata(fetch_head).ok()?.modified().ok()?;
+        Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+    }
+
+    /// What `git status --porcelain=v2 --branch` reports. Falls back to running it when
+    /// libgit2 cannot read the status.
+    pub(crate) fn status(&self) -> Result<StatusSummary, String> {
+        if let Some(summary) = self
+            .repo
+            .as_ref()
+            .and_then(|repo| status_summary(repo).ok())
+        {
+            return Ok(summary);
+        }
+        let output = run_git(
+            self.path,
+            &["status", "--porcelain=v2", "--branch", "-z"],
+            "status",
+        )?;
+        Ok(parse_status_summary(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+}
+
+const STAGED_STATUS: Status = Status::INDEX_NEW
+    .union(Status::INDEX_MODIFIED)
+    .union(Status::INDEX_DELETED)
+    .union(Status::INDEX_RENAMED)
+    .union(Status::INDEX_TYPECHANGE);
+const UNSTAGED_STATUS: Status = Status::WT_MODIFIED
+    .union(Status::WT_DELETED)
+    .union(Status::WT_RENAMED)
+    .union(Status::WT_TYPECHANGE);
+
+fn status_summary(repo: &Repository) -> Result<StatusSummary, git2::Error> {
+    let mut summary = StatusSummary::default();
+    let head = repo.find_reference("HEAD")?;
+    summary.branch = head
+        .symbolic_target()
+        .and_then(|target| target.strip_prefix("refs/heads/"))
+        .map(ToOwned::to_owned);
+    let head_commit = head.resolve().ok().and_then(|head| head.target());
+    summary.head = head_commit.map(|commit| commit.to_string());
+
+    if let Some(branch) = summary.branch.as_deref() {
+        if let Ok(upstream) = repo.branch_upstream_name(&format!("refs/heads/{branch}")) {
+            let upstream = upstream.as_str().unwrap_or_default();
+            summary.upstream = Some(short_ref_name(upstream).to_string());
+            // Like git, the counts are left out when the upstream has not been fetched.
+            let upstream_commit = repo.refname_to_id(upstream).ok();
+            if let (Some(local), Some(upstream)) = (head_commit, upstream_commit) {
+                let (ahead, behind) = repo.graph_ahead_behind(local, upstream)?;
+                summary.ahead_behind = Some((ahead as u64, behind as u64));
+            }
+        }
+    }
+
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .include_ignored(false)
+        .renames_head_to_index(true);
+    for entry in repo.statuses(Some(&mut options))?.iter() {
+        let status = entry.status();
+        if status.is_conflicted() {
+            summary.conflicted += 1;
+            continue;
+        }
+        if status.is_wt_new() && !status.intersects(STAGED_STATUS) {
+            summary.untracked += 1;
+            continue;
+        }
+        if status.intersects(STAGED_STATUS) {
+            summary.staged += 1;
+        }
+        if status.intersects(UNSTAGED_STATUS) {
+            summary.unstaged += 1;
+        }
+    }
+    Ok(summary)
+}
@@ -0,0 +1,602 @@
+//! Fakes for the end-to-end tests in `tests/`, built only with the `integration-test` feature.
+//! A scratch app runs the real connect, clone, diff, review and export code against a fake
+//! git provider and an in-process OpenAI-compatible server, so the tests need no network
+//! access or credentials. Results are returned as the JSON the frontend receives.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use libsql::Builder;
+use serde_json::{json, Value};
+
+use super::super::providers::fake::{install_fake_provider, FakeProviderClient};
+use super::super::providers::ProviderCommitStatus;
+use super::common::{
+    OPENAI_API_KEY_ENV, ROVEX_REVIEW_BASE_URL_ENV, ROVEX_REVIEW_MODEL_ENV,
+    ROVEX_REVIEW_PROVIDER_ENV,
+};
+use super::database_maintenance::database_maintenance;
+use super::providers::upsert_provider_connection;
+use super::review::commit_status::publish_review_commit_status;
+use super::review::run_queue::{
+    export_ai_review_run, run_review_headless, set_ai_review_run_approval,
+};
+use super::thread_settings::get_thread_settings;
+use super::threads::find_or_create_thread;
+use super::workspace_git::{clone_repository, compare_workspace_diff};
+use crate::backend::settings::settings_store;
+use crate::backend::{
+    db, AppState, CloneRepositoryInput, CompareWorkspaceDiffInput, CompareWorkspaceDiffResult,
+    DatabaseMaintenanceInput, ExportAiReviewRunInput, GetThreadSettingsInput, ProviderKind,
+    PublishReviewCommitStatusInput, SetAiReviewRunApprovalInput, StartAiReviewRunInput,
+};
+
+/// Account the fake provider reports for a valid access token.
+pub const FAKE_ACCOUNT_LOGIN: &str = "rovex-test";
+const FAKE_MODEL: &str = "fake-model";
+const THREAD_TITLE: &str = "Integration tests";
+
+static SCRATCH_DIRS: AtomicUsize = AtomicUsize::new(0);
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|error| format!("Failed to serialize result: {error}"))
+}
+
+fn parse_provider(provider: &str) -> Result<ProviderKind, String> {
+    ProviderKind::from_str(provider).ok_or_else(|| format!("Unknown provider '{provider}'."))
+}
+
+/// A chat completion request the fake server received.
+#[derive(Debug, Clone)]
+pub struct FakeChatRequest {
+    pub model: String,
+    pub stream: bool,
+    pub system_prompt: String,
+    pub prompt: String,
+    /// Whether the request asked for a structured (`json_schema`) response.
+    pub structured: bool,
+}
+
+impl FakeChatRequest {
+    fn from_body(body: &Value) -> Self {
+        let message = |role: &str| {
+            body.get("messages")
+                .and_then(Value::as_array)
+                .and_then(|messages| {
+                    messages
+                        .iter()
+                        .find(|message| message.get("role").and_then(Value::as_str) == Some(role))
+                })
+                .and_then(|message| message.get("content"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        Self {
+            model: body
+                .get("model")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            stream: body.get("stream").and_then(Value::as_bool) == Some(true),
+            system_prompt: message("system"),
+            prompt: message("user"),
+            structured: body.get("response_format").is_some(),
+        }
+    }
+}
+
+type ReplyFn = dyn Fn(&FakeChatRequest) -> String + Send + Sync;
+
+/// An OpenAI-compatible `/chat/completions` endpoint on a loopback port. `reply` returns the
+/// assistant text for each request; streaming requests get it as a single server-sent event.
+/// Requests are recorded in the order they arrived.
+pub struct FakeOpenAiServer {
+    address: String,
+    requests: Arc<Mutex<Vec<FakeChatRequest>>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl FakeOpenAiServer {
+    pub fn start(
+        reply: impl Fn(&FakeChatRequest) -> String + Send + Sync + 'static,
+    ) -> Result<Self, String> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .map_err(|error| format!("Failed to start fake OpenAI server: {error}"))?;
+        let address = listener
+            .local_addr()
+            .map_err(|error| format!("Failed to read fake OpenAI server address: {error}"))?
+            .to_string();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let stopped = Arc::new(AtomicBool::new(false));
+        let reply: Arc<ReplyFn> = Arc::new(reply);
+
+        let accept_requests = Arc::clone(&requests);
+        let accept_stopped = Arc::clone(&stopped);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if accept_stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let reply = Arc::clone(&reply);
+                let requests = Arc::clone(&accept_requests);
+                thread::spawn(move || {
+                    if let Err(error) = serve_chat_completion(stream, reply.as_ref(), &requests) {
+                        eprintln!("[fake-openai] {error}");
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            address,
+            requests,
+            stopped,
+        })
+    }
+
+    /// Value for the `ROVEX_REVIEW_BASE_URL` setting.
+    pub fn base_url(&self) -> String {
+        format!("http://{}/v1", self.address)
+    }
+
+    pub fn requests(&self) -> Vec<FakeChatRequest> {
+        self.requests
+            .lock()
+            .map(|requests| requests.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for FakeOpenAiServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wakes the accept loop so it sees the flag.
+        let _ = TcpStream::connect(&self.address);
+    }
+}
+
+fn serve_chat_completion(
+    mut stream: TcpStream,
+    reply: &ReplyFn,
+    requests: &Mutex<Vec<FakeChatRequest>>,
+) -> Result<(), String> {
+    let mut reader = BufReader::new(
+        stream
+            .try_clone()
+            .map_err(|error| format!("Failed to read request: {error}"))?,
+    );
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .map_err(|error| format!("Failed to read request: {error}"))?;
+    let mut content_length = 0usize;
+    loop {
+        let mut header = String::new();
+        let read = reader
+            .read_line(&mut header)
+            .map_err(|error| format!("Failed to read request headers: {error}"))?;
+        if read == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|error| format!("Failed to read request body: {error}"))?;
+
+    let (status, content_type, payload) = if !request_line.contains("/chat/completions") {
+        ("404 Not Found", "text/plain", "Not found".to_string())
+    } else {
+        let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        let request = FakeChatRequest::from_body(&body);
+        let content = reply(&request);
+        let payload = if request.stream {
+            let event = json!({
+                "choices": [{ "index": 0, "delta": { "content": content } }],
+            });
+            format!("data: {event}\n\ndata: [DONE]\n\n")
+        } else {
+            json!({
+                "id": "chatcmpl-fake",
+                "object": "chat.completion",
+                "model": request.model,
+                "choices": [{
+                    "index": 0,
+                    "finish_reason": "stop",
+                    "message": { "role": "assistant", "content": content },
+                }],
+                "usage": { "prompt_tokens": 100, "completion_tokens": 20 },
+            })
+            .to_string()
+        };
+        if let Ok(mut requests) = requests.lock() {
+            requests.push(request.clone());
+        }
+        let content_type = if request.stream {
+            "text/event-stream"
+        } else {
+            "application/json"
+        };
+        ("200 OK", content_type, payload)
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{payload}",
+        payload.len()
+    )
+    .map_err(|error| format!("Failed to write response: {error}"))
+}
+
+/// A git repository built up commit by commit, for use as a clone origin or a workspace.
+pub struct GitRepoFixture {
+    path: PathBuf,
+}
+
+impl GitRepoFixture {
+    /// Creates `path` and runs `git init` in it, on branch `main`.
+    pub fn init(path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        fs::create_dir_all(&path)
+            .map_err(|error| format!("Failed to create {}: {error}", path.display()))?;
+        let fixture = Self { path };
+        fixture.git(&["init", "--initial-branch=main"])?;
+        Ok(fixture)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes `content` to `relative_path`, creating parent directories.
+    pub fn write(&self, relative_path: &str, content: &str) -> Result<&Self, String> {
+        let path = self.path.join(relative_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("Failed to create {}: {error}", parent.display()))?;
+        }
+        fs::write(&path, content)
+            .map_err(|error| format!("Failed to write {}: {error}", path.display()))?;
+        Ok(self)
+    }
+
+    /// Stages every change and commits it.
+    pub fn commit(&self, message: &str) -> Result<&Self, String> {
+        self.git(&["add", "--all"])?;
+        self.git(&["commit", "--quiet", "--message", message])?;
+        Ok(self)
+    }
+
+    pub fn checkout_new_branch(&self, name: &str) -> Result<&Self, String> {
+        self.git(&["checkout", "--quiet", "-b", name])?;
+        Ok(self)
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String, String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args([
+                "-c",
+                "user.name=Rovex Tests",
+                "-c",
+                "user.email=tests@rovex.invalid",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .output()
+            .map_err(|error| format!("Failed to run git {}: {error}", args.join(" ")))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// The backend on a scratch database in its own temp directory, set up to review with the
+/// OpenAI provider at `openai_base_url`. The directory is removed on drop.
+pub struct IntegrationApp {
+    state: AppState,
+    root: PathBuf,
+    commit_statuses: Arc<Mutex<Vec<(String, String, ProviderCommitStatus)>>>,
+}
+
+impl IntegrationApp {
+    pub async fn new(openai_base_url: &str) -> Result<Self, String> {
+        let root = std::env::temp_dir().join(format!(
+            "rovex-integration-{}-{}",
+            std::process::id(),
+            SCRATCH_DIRS.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root)
+            .map_err(|error| format!("Failed to create {}: {error}", root.display()))?;
+        let path = root.join("rovex.db");
+        let db = Builder::new_local(&path)
+            .build()
+            .await
+            .map_err(|error| format!("Failed to open integration database: {error}"))?;
+        db::initialize_schema(&db).await?;
+        let state = AppState {
+            db,
+            database_url: format!("file:{}", path.display()),
+        };
+
+        let conn = state.connection()?;
+        let settings = settings_store();
+        settings.reload(&conn).await?;
+        settings
+            .set(&conn, ROVEX_REVIEW_PROVIDER_ENV, &"openai")
+            .await?;
+        settings
+            .set(&conn, ROVEX_REVIEW_BASE_URL_ENV, &openai_base_url)
+            .await?;
+        settings
+            .set(&conn, ROVEX_REVIEW_MODEL_ENV, &FAKE_MODEL)
+            .await?;
+        settings
+            .set(&conn, OPENAI_API_KEY_ENV, &"sk-integration-test")
+            .await?;
+        Ok(Self {
+            state,
+            root,
+            commit_statuses: Arc::default(),
+        })
+    }
+
+    /// Where [`Self::install_fake_provider`] looks for repositories, as `<owner>/<name>`.
+    pub fn origins_dir(&self) -> PathBuf {
+        self.root.join("origins")
+    }
+
+    /// Replaces `provider` with a fake that clones from [`Self::origins_dir`] and accepts only
+    /// `access_token`.
+    pub fn install_fake_provider(&self, provider: &str, access_token: &str) -> Result<(), String> {
+        install_fake_provider(
+            parse_provider(provider)?,
+            FakeProviderClient {
+                origins_root: self.origins_dir(),
+                access_token: access_token.to_string(),
+                account_login: FAKE_ACCOUNT_LOGIN.to_string(),
+                commit_statuses: self.commit_statuses.clone(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Statuses the fake providers received, oldest first, as
+    /// `{repository, sha, state, context, description, targetUrl}`.
+    pub fn published_commit_statuses(&self) -> Vec<Value> {
+        self.commit_statuses
+            .lock()
+            .map(|statuses| {
+                statuses
+                    .iter()
+                    .map(|(repository, sha, status)| {
+                        json!({
+                            "repository": repository,
+                            "sha": sha,
+                            "state": status.state.as_str(),
+                            "context": status.context,
+                            "description": status.description,
+                            "targetUrl": status.target_url,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub async fn connect_provider(
+        &self,
+        provider: &str,
+        access_token: &str,
+    ) -> Result<Value, String> {
+        let connection =
+            upsert_provider_connection(&self.state, parse_provider(provider)?, access_token)
+                .await?;
+        to_json(&connection)
+    }
+
+    /// Clones `repository` (`owner/name`) into the scratch directory with full history.
+    pub async fn clone_repository(
+        &self,
+        provider: &str,
+        repository: &str,
+    ) -> Result<Value, String> {
+        let result = clone_repository(
+            &self.state,
+            CloneRepositoryInput {
+                provider: parse_provider(provider)?,
+                repository: repository.to_string(),
+                destination_root: Some(
+                    self.root
+                        .join("repositories")
+                        .to_string_lossy()
+                        .into_owned(),
+                ),
+                directory_name: None,
+                shallow: Some(false),
+                recurse_submodules: None,
+            },
+        )
+        .await?;
+        to_json(&result)
+    }
+
+    async fn branch_diff(
+        &self,
+        workspace: &str,
+        base_ref: &str,
+    ) -> Result<CompareWorkspaceDiffResult, String> {
+        compare_workspace_diff(CompareWorkspaceDiffInput {
+            workspace: workspace.to_string(),
+            base_ref: Some(base_ref.to_string()),
+            fetch_remote: Some(false),
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: None,
+            include_untracked: None,
+            head_ref: None,
+            include_submodule_diffs: None,
+            force: None,
+            remote: None,
+        })
+        .await
+    }
+
+    pub async fn compare_diff(&self, workspace: &str, base_ref: &str) -> Result<Value, String> {
+        to_json(&self.branch_diff(workspace, base_ref).await?)
+    }
+
+    /// Reviews `workspace` against `base_ref` to completion, as the headless CLI does, and
+    /// returns the finished run.
+    pub async fn review(&self, workspace: &str, base_ref: &str) -> Result<Value, String> {
+        let diff = self.branch_diff(workspace, base_ref).await?;
+        let thread = find_or_create_thread(&self.state, THREAD_TITLE, &diff.workspace).await?;
+        let run = run_review_headless(
+            &self.state,
+            StartAiReviewRunInput {
+                thread_id: thread.id,
+                workspace: diff.workspace,
+                base_ref: diff.base_ref,
+                merge_base: diff.merge_base,
+                head: diff.head,
+                files_changed: diff.files_changed,
+                insertions: diff.insertions,
+                deletions: diff.deletions,
+                diff: diff.diff,
+                prompt: None,
+                scope_label: None,
+                max_parallel_chunks: None,
+                review_mode: None,
+                include_paths: None,
+                exclude_paths: None,
+                diff_target: None,
+                idempotency_key: None,
+                profile_id: None,
+                min_confidence: None,
+            },
+        )
+        .await?;
+        to_json(&run)
+    }
+
+    /// The defaults a new review in thread `thread_id` starts from.
+    pub async fn thread_settings(&self, thread_id: i64) -> Result<Value, String> {
+        let settings =
+            get_thread_settings(&self.state, GetThreadSettingsInput { thread_id }).await?;
+        to_json(&settings)
+    }
+
+    /// Records a reviewer's `approval` of run `run_id` and returns the updated run.
+    pub async fn set_approval(
+        &self,
+        run_id: &str,
+        approval: &str,
+        note: Option<&str>,
+    ) -> Result<Value, String> {
+        let run = set_ai_review_run_approval(
+            &self.state,
+            SetAiReviewRunApprovalInput {
+                run_id: run_id.to_string(),
+                approval: approval.to_string(),
+                note: note.map(ToString::to_string),
+            },
+        )
+        .await?;
+        to_json(&run)
+    }
+
+    /// Publishes the verdict of run `run_id` as a commit status on `repository`.
+    pub async fn publish_commit_status(
+        &self,
+        run_id: &str,
+        provider: &str,
+        repository: &str,
+    ) -> Result<Value, String> {
+        let result = publish_review_commit_status(
+            &self.state,
+            PublishReviewCommitStatusInput {
+                run_id: run_id.to_string(),
+                provider: parse_provider(provider)?,
+                repository: repository.to_string(),
+                details_url: None,
+            },
+        )
+        .await?;
+        to_json(&result)
+    }
+
+    /// The SARIF export of run `run_id`, parsed.
+    pub async fn export_sarif(&self, run_id: &str) -> Result<Value, String> {
+        let export = export_ai_review_run(
+            &self.state,
+            ExportAiReviewRunInput {
+                run_id: run_id.to_string(),
+                format: Some("sarif".to_string()),
+            },
+        )
+        .await?;
+        serde_json::from_str(&export.content)
+            .map_err(|error| format!("Failed to parse SARIF export: {error}"))
+    }
+
+    /// Deletes thread `thread_id` with foreign keys switched off, leaving its rows behind
+    /// the way a database written without them would.
+    pub async fn delete_thread_without_cascade(&self, thread_id: i64) -> Result<(), String> {
+        let conn = self.state.connection()?;
+        conn.execute("PRAGMA foreign_keys = OFF", ())
+            .await
+            .map_err(|error| format!("Failed to switch off foreign keys: {error}"))?;
+        conn.execute("DELETE FROM threads WHERE id = ?1", [thread_id])
+            .await
+            .map_err(|error| format!("Failed to delete thread: {error}"))?;
+        Ok(())
+    }
+
+    /// Checks the app database, deleting orphaned rows and vacuuming when `repair` is set.
+    pub async fn database_maintenance(&self, repair: bool) -> Result<Value, String> {
+        let result = database_maintenance(
+            &self.state,
+            DatabaseMaintenanceInput {
+                integrity_check: Some(true),
+                cleanup_orphans: Some(repair),
+                vacuum: Some(repair),
+            },
+        )
+        .await?;
+        to_json(&result)
+    }
+}
+
+impl Drop for IntegrationApp {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
@@ -0,0 +1,250 @@
+use tauri::State;
+
+use super::common::{
+    as_non_empty_trimmed, parse_setting_usize, DEFAULT_MAX_ATTACHMENT_BYTES,
+    MAX_ATTACHMENTS_PER_MESSAGE, ROVEX_MAX_ATTACHMENT_BYTES_ENV,
+};
+use super::review::findings::fnv1a;
+use super::threads::{load_message_by_id, load_thread_by_id};
+use crate::backend::{
+    AddMessageAttachmentInput, AppState, ListMessageAttachmentsInput, ListMessageAttachmentsResult,
+    MessageAttachment, MessageAttachmentInput,
+};
+
+const MAX_ATTACHMENT_NAME_CHARS: usize = 200;
+const DEFAULT_ATTACHMENT_MIME_TYPE: &str = "text/plain";
+
+const ATTACHMENT_COLUMNS: &str =
+    "id, message_id, thread_id, name, mime_type, size_bytes, content_hash, content, created_at";
+
+/// An attachment that passed the size and content checks and is ready to store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PreparedAttachment {
+    name: String,
+    mime_type: String,
+    content: String,
+    size_bytes: i64,
+    content_hash: String,
+}
+
+/// Checks an attachment before anything is stored. Attachments are text that goes into
+/// prompts, so binary content is rejected instead of being encoded.
+pub(crate) fn prepare_attachment(
+    input: &MessageAttachmentInput,
+) -> Result<PreparedAttachment, String> {
+    let name = as_non_empty_trimmed(Some(input.name.as_str()))
+        .ok_or_else(|| "Attachment name must not be empty.".to_string())?;
+    if name.chars().count() > MAX_ATTACHMENT_NAME_CHARS {
+        return Err(format!(
+            "Attachment name must be at most {MAX_ATTACHMENT_NAME_CHARS} characters."
+        ));
+    }
+    if input.content.trim().is_empty() {
+        return Err(format!("Attachment {name} is empty."));
+    }
+    if input.content.contains('\0') {
+        return Err(format!(
+            "Attachment {name} looks like a binary file. Attach text such as logs, configs, or code."
+        ));
+    }
+    let max_bytes = parse_setting_usize(
+        ROVEX_MAX_ATTACHMENT_BYTES_ENV,
+        DEFAULT_MAX_ATTACHMENT_BYTES,
+        1,
+    );
+    let size_bytes = input.content.len();
+    if size_bytes > max_bytes {
+        return Err(format!(
+            "Attachment {name} is {size_bytes} bytes; attachments are limited to {max_bytes} bytes."
+        ));
+    }
+    Ok(PreparedAttachment {
+        mime_type: as_non_empty_trimmed(input.mime_type.as_deref())
+            .unwrap_or_else(|| DEFAULT_ATTACHMENT_MIME_TYPE.to_string()),
+        content_hash: format!("{:016x}", fnv1a(&[&input.content])),
+        content: input.content.clone(),
+        size_bytes: size_bytes as i64,
+        name,
+    })
+}
+
+fn parse_attachment_from_row(row: &libsql::Row) -> Result<MessageAttachment, String> {
+    Ok(MessageAttachment {
+        id: row
+            .get(0)
+            .map_err(|error| format!("Failed to parse attachment id: {error}"))?,
+        message_id: row
+            .get(1)
+            .map_err(|error| format!("Failed to parse attachment message_id: {error}"))?,
+        thread_id: row
+            .get(2)
+            .map_err(|error| format!("Failed to parse attachment thread_id: {error}"))?,
+        name: row
+            .get(3)
+            .map_err(|error| format!("Failed to parse attachment name: {error}"))?,
+        mime_type: row
+            .get(4)
+            .map_err(|error| format!("Failed to parse attachment mime_type: {error}"))?,
+        size_bytes: row
+            .get(5)
+            .map_err(|error| format!("Failed to parse attachment size: {error}"))?,
+        content_hash: row
+            .get(6)
+            .map_err(|error| format!("Failed to parse attachment hash: {error}"))?,
+        content: row
+            .get(7)
+            .map_err(|error| format!("Failed to parse attachment content: {error}"))?,
+        created_at: row
+            .get(8)
+            .map_err(|error| format!("Failed to parse attachment created_at: {error}"))?,
+    })
+}
+
+async fn query_attachments(
+    state: &AppState,
+    filter: &str,
+    id: i64,
+) -> Result<Vec<MessageAttachment>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {ATTACHMENT_COLUMNS} FROM message_attachments WHERE {filter}
+                 ORDER BY message_id ASC, id ASC"
+            ),
+            [id],
+        )
+        .await
+        .map_err(|error| format!("Failed to load message attachments: {error}"))?;
+    let mut attachments = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read message attachment row: {error}"))?
+    {
+        attachments.push(parse_attachment_from_row(&row)?);
+    }
+    Ok(attachments)
+}
+
+/// Attachments of every message in a thread, oldest message first.
+pub(crate) async fn load_thread_attachments(
+    state: &AppState,
+    thread_id: i64,
+) -> Result<Vec<MessageAttachment>, String> {
+    query_attachments(state, "thread_id = ?1", thread_id).await
+}
+
+/// Stores `attachment` on a message. Attaching the same content to a message again returns
+/// the stored attachment instead of a copy.
+pub(crate) async fn insert_message_attachment(
+    state: &AppState,
+    message_id: i64,
+    thread_id: i64,
+    attachment: PreparedAttachment,
+) -> Result<MessageAttachment, String> {
+    let existing = query_attachments(state, "message_id = ?1", message_id).await?;
+    if let Some(duplicate) = existing
+        .iter()
+        .find(|stored| stored.content_hash == attachment.content_hash)
+    {
+        return Ok(duplicate.clone());
+    }
+    if existing.len() as i64 >= MAX_ATTACHMENTS_PER_MESSAGE {
+        return Err(format!(
+            "A message can have at most {MAX_ATTACHMENTS_PER_MESSAGE} attachments."
+        ));
+    }
+
+    let conn = state.connection()?;
+    conn.execute(
+        "INSERT INTO message_attachments
+           (message_id, thread_id, name, mime_type, size_bytes, content_hash, content)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        (
+            message_id,
+            thread_id,
+            attachment.name,
+            attachment.mime_type,
+            attachment.size_bytes,
+            attachment.content_hash,
+            attachment.content,
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to add message attachment: {error}"))?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {ATTACHMENT_COLUMNS} FROM message_attachments WHERE id = last_insert_rowid()"
+            ),
+            (),
+        )
+        .await
+        .map_err(|error| format!("Failed to load new message attachment: {error}"))?;
+    let row = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read new message attachment: {error}"))?
+        .ok_or_else(|| "Missing message attachment after adding it.".to_string())?;
+    parse_attachment_from_row(&row)
+}
+
+pub async fn add_message_attachment(
+    state: State<'_, AppState>,
+    input: AddMessageAttachmentInput,
+) -> Result<MessageAttachment, String> {
+    let attachment = prepare_attachment(&MessageAttachmentInput {
+        name: input.name,
+        content: input.content,
+        mime_type: input.mime_type,
+    })?;
+    let message = load_message_by_id(&state, input.message_id).await?;
+    insert_message_attachment(&state, message.id, message.thread_id, attachment).await
+}
+
+pub async fn list_message_attachments(
+    state: State<'_, AppState>,
+    input: ListMessageAttachmentsInput,
+) -> Result<ListMessageAttachmentsResult, String> {
+    let attachments = match (input.message_id, input.thread_id) {
+        (Some(message_id), None) => {
+            let _ = load_message_by_id(&state, message_id).await?;
+            query_attachments(&state, "message_id = ?1", message_id).await?
+        }
+        (None, Some(thread_id)) => {
+            let _ = load_thread_by_id(&state, thread_id).await?;
+            load_thread_attachments(&state, thread_id).await?
+        }
+        _ => return Err("Pass either messageId or threadId.".to_string()),
+    };
+    Ok(ListMessageAttachmentsResult { attachments })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prepare_attachment;
+    use crate::backend::MessageAttachmentInput;
+
+    fn input(name: &str, content: &str) -> MessageAttachmentInput {
+        MessageAttachmentInput {
+            name: name.to_string(),
+            content: content.to_string(),
+            mime_type: None,
+        }
+    }
+
+    #[test]
+    fn attachments_are_hashed_by_content_and_must_be_text() {
+        let log = prepare_attachment(&input(" app.log ", "error: boom\n")).unwrap();
+        let copy = prepare_attachment(&input("copy.log", "error: boom\n")).unwrap();
+        assert_eq!(log.name, "app.log");
+        assert_eq!(log.mime_type, "text/plain");
+        assert_eq!(log.size_bytes, 12);
+        assert_eq!(log.content_hash, copy.content_hash);
+
+        assert!(prepare_attachment(&input("blob.bin", "PK\0\u{3}")).is_err());
+        assert!(prepare_attachment(&input("empty.txt", "  \n")).is_err());
+        assert!(prepare_attachment(&input(" ", "text")).is_err());
+    }
+}
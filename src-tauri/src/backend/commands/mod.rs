@@ -1,28 +1,96 @@
+#[cfg(feature = "bench")]
+pub mod bench_support;
+mod binary_decoders;
+pub(crate) mod cli;
+mod code_intel_config;
 mod common;
+mod database_maintenance;
 mod editor;
+mod git_repo;
+#[cfg(feature = "integration-test")]
+pub mod integration_support;
+mod message_attachments;
+mod name_sort;
+mod path_filter;
+#[cfg(test)]
+mod path_filter_tests;
 mod providers;
+mod pull_request_comments;
+mod repo_config;
 mod review;
+mod thread_archive;
+mod thread_settings;
 mod threads;
+mod workspace_data;
+mod workspace_files;
 mod workspace_git;
 #[cfg(test)]
 mod workspace_git_tests;
+mod workspace_sync;
+mod workspace_trust;
+mod workspace_watch;
+
+pub(crate) use review::groups::start_review_group_watcher;
+pub(crate) use review::run_queue::reconcile_review_runs;
+pub(crate) use review::schedules::start_review_scheduler;
+pub(crate) use review::store::backfill_ai_review_findings;
+pub(crate) use workspace_sync::start_workspace_sync_watcher;
+pub(crate) use workspace_watch::start_workspace_change_watcher;
 
 use tauri::{AppHandle, State};
 
 use super::{
-    AddThreadMessageInput, AppServerAccountStatus, AppServerLoginStartResult, AppState,
-    BackendHealth, CancelAiReviewRunInput, CancelAiReviewRunResult, CheckoutWorkspaceBranchInput,
-    CheckoutWorkspaceBranchResult, CloneRepositoryInput, CloneRepositoryResult, CodeIntelSyncInput,
-    CodeIntelSyncResult, CompareWorkspaceDiffInput, CompareWorkspaceDiffResult,
-    ConnectProviderInput, CreateInlineReviewCommentInput, CreateThreadInput,
-    CreateWorkspaceBranchInput, GenerateAiFollowUpInput, GenerateAiFollowUpResult,
-    GenerateAiReviewInput, GenerateAiReviewResult, GetAiReviewRunInput, InlineReviewComment,
-    ListAiReviewRunsInput, ListAiReviewRunsResult, ListInlineReviewCommentsInput,
-    ListInlineReviewCommentsResult, ListWorkspaceBranchesInput, ListWorkspaceBranchesResult,
-    Message, OpenFileInEditorInput, OpencodeSidecarStatus, PollProviderDeviceAuthInput,
-    PollProviderDeviceAuthResult, ProviderConnection, ProviderKind, SetAiReviewApiKeyInput,
-    SetAiReviewSettingsInput, StartAiReviewRunInput, StartAiReviewRunResult,
-    StartProviderDeviceAuthInput, StartProviderDeviceAuthResult, Thread,
+    AddMessageAttachmentInput, AddThreadMessageInput, AppServerAccountStatus,
+    AppServerLoginStartResult, AppState, ApplyFindingFixInput, ApplyFindingFixResult,
+    ArchiveThreadInput, BackendHealth, BootstrapDemoWorkspaceInput, BootstrapDemoWorkspaceResult,
+    CancelAiRequestInput, CancelAiRequestResult, CancelAiReviewRunInput, CancelAiReviewRunResult,
+    CheckoutWorkspaceBranchInput, CheckoutWorkspaceBranchResult, CloneRepositoryInput,
+    CloneRepositoryResult, CodeIntelSyncInput, CodeIntelSyncResult, CodeIntelSyncStatus,
+    CodeIntelSyncStatusInput, CodeIntelWorkspaceConfig, CommitDetail, CommitWorkspaceChangesInput,
+    CommitWorkspaceChangesResult, CompareAiReviewRunsInput, CompareAiReviewRunsResult,
+    CompareWorkspaceDiffInput, CompareWorkspaceDiffResult, ConnectProviderInput,
+    CreateInlineReviewCommentInput, CreateReviewerProfileInput, CreateThreadInput,
+    CreateWorkspaceBranchInput, DatabaseMaintenanceInput, DatabaseMaintenanceResult,
+    DeleteReviewScheduleInput, DeleteReviewScheduleResult, DeleteReviewerProfileInput,
+    DeleteReviewerProfileResult, ExportAiReviewRunInput, ExportAiReviewRunResult,
+    ExportReviewReportInput, ExportReviewReportResult, ExportThreadInput, ExportThreadResult,
+    ExportWorkspaceDataInput, ExportWorkspaceDataResult, FetchWorkspaceRemoteInput,
+    FetchWorkspaceRemoteResult, GenerateAiFollowUpInput, GenerateAiFollowUpResult,
+    GenerateAiReviewInput, GenerateAiReviewResult, GenerateCommitMessageInput,
+    GenerateCommitMessageResult, GetAiReviewRunInput, GetCodeIntelConfigInput,
+    GetCommitDetailInput, GetLineBlameInput, GetLineBlameResult, GetModelPerformanceStatsInput,
+    GetModelPerformanceStatsResult, GetReviewQueueStatusResult, GetReviewUsageSummaryInput,
+    GetReviewUsageSummaryResult, GetSymbolDefinitionResult, GetTeamReviewStatsInput,
+    GetTeamReviewStatsResult, GetThreadSettingsInput, GetWorkspaceTrustInput,
+    ImportPullRequestCommentsInput, ImportPullRequestCommentsResult, ImportThreadInput,
+    ImportThreadResult, InlineReviewComment, InstallReviewGitHookInput, ListAiReviewRunsInput,
+    ListAiReviewRunsResult, ListFindingActionsResult, ListFindingStatesInput,
+    ListFindingStatesResult, ListInlineReviewCommentsInput, ListInlineReviewCommentsResult,
+    ListMessageAttachmentsInput, ListMessageAttachmentsResult, ListPullRequestCommentsInput,
+    ListPullRequestCommentsResult, ListReviewGroupsInput, ListReviewGroupsResult,
+    ListReviewSchedulesInput, ListReviewSchedulesResult, ListReviewerProfilesResult,
+    ListRunSnapshotsInput, ListRunSnapshotsResult, ListStaleWorkspacesInput,
+    ListStaleWorkspacesResult, ListSymbolReferencesResult, ListThreadMessagesInput,
+    ListThreadMessagesResult, ListWorkspaceBranchesInput, ListWorkspaceBranchesResult,
+    ListWorkspaceCommitsInput, ListWorkspaceCommitsResult, ListWorkspaceFilesInput,
+    ListWorkspaceFilesResult, Message, MessageAttachment, MigrateRepositoriesDirInput,
+    MigrateRepositoriesDirResult, OpenFileInEditorInput, OpencodeSidecarStatus, PinThreadInput,
+    PollProviderDeviceAuthInput, PollProviderDeviceAuthResult, ProviderConnection, ProviderKind,
+    PublishReviewCommitStatusInput, PublishReviewCommitStatusResult, PullWorkspaceBranchInput,
+    PullWorkspaceBranchResult, PurgeWorkspaceDataInput, PurgeWorkspaceDataResult,
+    PushWorkspaceBranchInput, PushWorkspaceBranchResult, ReadWorkspaceFileInput,
+    ReadWorkspaceFileResult, RemoveRunSnapshotResult, RenameThreadInput, RepositoriesDirResult,
+    ReviewFindingState, ReviewGitHook, ReviewGroup, ReviewGroupInput, ReviewSchedule,
+    ReviewerProfile, RunFindingActionInput, RunFindingActionResult, RunSnapshot, RunSnapshotInput,
+    SaveReviewScheduleInput, SearchFindingsInput, SearchFindingsResult, SetAiReviewApiKeyInput,
+    SetAiReviewRunApprovalInput, SetAiReviewSettingsInput, SetCodeIntelConfigInput,
+    SetFindingActionsInput, SetThreadSettingsInput, SetWorkspaceTrustInput, SignalUiActivityInput,
+    StartAiReviewRunInput, StartAiReviewRunResult, StartProviderDeviceAuthInput,
+    StartProviderDeviceAuthResult, StartReviewGroupInput, SuggestThreadTitleInput,
+    SuggestThreadTitleResult, SymbolLookupInput, Thread, ThreadSettings,
+    UninstallReviewGitHookInput, UninstallReviewGitHookResult, UnshallowWorkspaceInput,
+    UnshallowWorkspaceResult, UpdateFindingStateInput, UpdateReviewerProfileInput,
+    WatchWorkspaceInput, WorkspaceStatus, WorkspaceStatusInput, WorkspaceTrust, WorkspaceWatch,
 };
 
 #[tauri::command]
@@ -42,8 +110,33 @@ pub async fn create_thread(
 pub async fn list_threads(
     state: State<'_, AppState>,
     limit: Option<u32>,
+    include_archived: Option<bool>,
 ) -> Result<Vec<Thread>, String> {
-    threads::list_threads(state, limit).await
+    threads::list_threads(state, limit, include_archived).await
+}
+
+#[tauri::command]
+pub async fn rename_thread(
+    state: State<'_, AppState>,
+    input: RenameThreadInput,
+) -> Result<Thread, String> {
+    threads::rename_thread(state, input).await
+}
+
+#[tauri::command]
+pub async fn archive_thread(
+    state: State<'_, AppState>,
+    input: ArchiveThreadInput,
+) -> Result<Thread, String> {
+    threads::archive_thread(state, input).await
+}
+
+#[tauri::command]
+pub async fn pin_thread(
+    state: State<'_, AppState>,
+    input: PinThreadInput,
+) -> Result<Thread, String> {
+    threads::pin_thread(state, input).await
 }
 
 #[tauri::command]
@@ -62,10 +155,73 @@ pub async fn add_thread_message(
 #[tauri::command]
 pub async fn list_thread_messages(
     state: State<'_, AppState>,
-    thread_id: i64,
-    limit: Option<u32>,
-) -> Result<Vec<Message>, String> {
-    threads::list_thread_messages(state, thread_id, limit).await
+    input: ListThreadMessagesInput,
+) -> Result<ListThreadMessagesResult, String> {
+    threads::list_thread_messages(state, input).await
+}
+
+#[tauri::command]
+pub async fn export_thread(
+    state: State<'_, AppState>,
+    input: ExportThreadInput,
+) -> Result<ExportThreadResult, String> {
+    thread_archive::export_thread(state, input).await
+}
+
+#[tauri::command]
+pub async fn import_thread(
+    state: State<'_, AppState>,
+    input: ImportThreadInput,
+) -> Result<ImportThreadResult, String> {
+    thread_archive::import_thread(state, input).await
+}
+
+#[tauri::command]
+pub async fn get_thread_settings(
+    state: State<'_, AppState>,
+    input: GetThreadSettingsInput,
+) -> Result<ThreadSettings, String> {
+    thread_settings::get_thread_settings(&state, input).await
+}
+
+#[tauri::command]
+pub async fn set_thread_settings(
+    state: State<'_, AppState>,
+    input: SetThreadSettingsInput,
+) -> Result<ThreadSettings, String> {
+    thread_settings::set_thread_settings(&state, input).await
+}
+
+#[tauri::command]
+pub async fn add_message_attachment(
+    state: State<'_, AppState>,
+    input: AddMessageAttachmentInput,
+) -> Result<MessageAttachment, String> {
+    message_attachments::add_message_attachment(state, input).await
+}
+
+#[tauri::command]
+pub async fn list_message_attachments(
+    state: State<'_, AppState>,
+    input: ListMessageAttachmentsInput,
+) -> Result<ListMessageAttachmentsResult, String> {
+    message_attachments::list_message_attachments(state, input).await
+}
+
+#[tauri::command]
+pub async fn import_pull_request_comments(
+    state: State<'_, AppState>,
+    input: ImportPullRequestCommentsInput,
+) -> Result<ImportPullRequestCommentsResult, String> {
+    pull_request_comments::import_pull_request_comments(state, input).await
+}
+
+#[tauri::command]
+pub async fn list_pull_request_comments(
+    state: State<'_, AppState>,
+    input: ListPullRequestCommentsInput,
+) -> Result<ListPullRequestCommentsResult, String> {
+    pull_request_comments::list_pull_request_comments(state, input).await
 }
 
 #[tauri::command]
@@ -119,14 +275,42 @@ pub async fn clone_repository(
     state: State<'_, AppState>,
     input: CloneRepositoryInput,
 ) -> Result<CloneRepositoryResult, String> {
-    workspace_git::clone_repository(state, input).await
+    workspace_git::clone_repository(&state, input).await
+}
+
+#[tauri::command]
+pub async fn get_repositories_dir() -> Result<RepositoriesDirResult, String> {
+    workspace_git::get_repositories_dir().await
+}
+
+#[tauri::command]
+pub async fn migrate_repositories_dir(
+    state: State<'_, AppState>,
+    input: MigrateRepositoriesDirInput,
+) -> Result<MigrateRepositoriesDirResult, String> {
+    workspace_git::migrate_repositories_dir(state, input).await
 }
 
 #[tauri::command]
 pub async fn compare_workspace_diff(
+    state: State<'_, AppState>,
     input: CompareWorkspaceDiffInput,
 ) -> Result<CompareWorkspaceDiffResult, String> {
-    workspace_git::compare_workspace_diff(input).await
+    workspace_git::compare_workspace_diff_with_submodules(&state, input).await
+}
+
+#[tauri::command]
+pub async fn list_workspace_files(
+    input: ListWorkspaceFilesInput,
+) -> Result<ListWorkspaceFilesResult, String> {
+    workspace_files::list_workspace_files(input).await
+}
+
+#[tauri::command]
+pub async fn read_workspace_file(
+    input: ReadWorkspaceFileInput,
+) -> Result<ReadWorkspaceFileResult, String> {
+    workspace_files::read_workspace_file(input).await
 }
 
 #[tauri::command]
@@ -150,6 +334,153 @@ pub async fn create_workspace_branch(
     workspace_git::create_workspace_branch(input).await
 }
 
+#[tauri::command]
+pub async fn workspace_status(input: WorkspaceStatusInput) -> Result<WorkspaceStatus, String> {
+    workspace_git::workspace_status(input).await
+}
+
+#[tauri::command]
+pub async fn pull_workspace_branch(
+    state: State<'_, AppState>,
+    input: PullWorkspaceBranchInput,
+) -> Result<PullWorkspaceBranchResult, String> {
+    workspace_git::pull_workspace_branch(&state, input).await
+}
+
+#[tauri::command]
+pub async fn push_workspace_branch(
+    state: State<'_, AppState>,
+    input: PushWorkspaceBranchInput,
+) -> Result<PushWorkspaceBranchResult, String> {
+    workspace_git::push_workspace_branch(&state, input).await
+}
+
+#[tauri::command]
+pub async fn commit_workspace_changes(
+    input: CommitWorkspaceChangesInput,
+) -> Result<CommitWorkspaceChangesResult, String> {
+    workspace_git::commit_workspace_changes(input).await
+}
+
+#[tauri::command]
+pub async fn generate_commit_message(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: GenerateCommitMessageInput,
+) -> Result<GenerateCommitMessageResult, String> {
+    review::commit_message::generate_commit_message(app, state, input).await
+}
+
+#[tauri::command]
+pub async fn suggest_thread_title(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: SuggestThreadTitleInput,
+) -> Result<SuggestThreadTitleResult, String> {
+    review::thread_title::suggest_thread_title(app, state, input).await
+}
+
+#[tauri::command]
+pub async fn unshallow_workspace(
+    state: State<'_, AppState>,
+    input: UnshallowWorkspaceInput,
+) -> Result<UnshallowWorkspaceResult, String> {
+    workspace_git::unshallow_workspace(&state, input).await
+}
+
+#[tauri::command]
+pub async fn fetch_workspace_remote(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: FetchWorkspaceRemoteInput,
+) -> Result<FetchWorkspaceRemoteResult, String> {
+    workspace_git::fetch_workspace_remote(app, &state, input).await
+}
+
+#[tauri::command]
+pub async fn bootstrap_demo_workspace(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: BootstrapDemoWorkspaceInput,
+) -> Result<BootstrapDemoWorkspaceResult, String> {
+    review::demo::bootstrap_demo_workspace(app, state, input).await
+}
+
+#[tauri::command]
+pub async fn get_line_blame(input: GetLineBlameInput) -> Result<GetLineBlameResult, String> {
+    workspace_git::get_line_blame(input).await
+}
+
+#[tauri::command]
+pub async fn list_workspace_commits(
+    input: ListWorkspaceCommitsInput,
+) -> Result<ListWorkspaceCommitsResult, String> {
+    workspace_git::list_workspace_commits(input).await
+}
+
+#[tauri::command]
+pub async fn get_commit_detail(input: GetCommitDetailInput) -> Result<CommitDetail, String> {
+    workspace_git::get_commit_detail(input).await
+}
+
+#[tauri::command]
+pub async fn list_stale_workspaces(
+    state: State<'_, AppState>,
+    input: ListStaleWorkspacesInput,
+) -> Result<ListStaleWorkspacesResult, String> {
+    workspace_sync::list_stale_workspaces(state, input).await
+}
+
+#[tauri::command]
+pub async fn watch_workspace(input: WatchWorkspaceInput) -> Result<WorkspaceWatch, String> {
+    workspace_watch::watch_workspace(input).await
+}
+
+#[tauri::command]
+pub async fn unwatch_workspace(input: WatchWorkspaceInput) -> Result<bool, String> {
+    workspace_watch::unwatch_workspace(input)
+}
+
+#[tauri::command]
+pub async fn get_workspace_trust(
+    state: State<'_, AppState>,
+    input: GetWorkspaceTrustInput,
+) -> Result<WorkspaceTrust, String> {
+    workspace_trust::get_workspace_trust(state, input).await
+}
+
+#[tauri::command]
+pub async fn set_workspace_trust(
+    state: State<'_, AppState>,
+    input: SetWorkspaceTrustInput,
+) -> Result<WorkspaceTrust, String> {
+    workspace_trust::set_workspace_trust(state, input).await
+}
+
+#[tauri::command]
+pub async fn purge_workspace_data(
+    state: State<'_, AppState>,
+    input: PurgeWorkspaceDataInput,
+) -> Result<PurgeWorkspaceDataResult, String> {
+    workspace_data::purge_workspace_data(state, input).await
+}
+
+#[tauri::command]
+pub async fn export_workspace_data(
+    state: State<'_, AppState>,
+    input: ExportWorkspaceDataInput,
+) -> Result<ExportWorkspaceDataResult, String> {
+    workspace_data::export_workspace_data(state, input).await
+}
+
+#[tauri::command]
+pub async fn database_maintenance(
+    state: State<'_, AppState>,
+    input: DatabaseMaintenanceInput,
+) -> Result<DatabaseMaintenanceResult, String> {
+    database_maintenance::database_maintenance(&state, input).await
+}
+
 #[tauri::command]
 pub async fn open_file_in_editor(input: OpenFileInEditorInput) -> Result<(), String> {
     editor::open_file_in_editor(input).await
@@ -169,9 +500,10 @@ pub async fn set_ai_review_api_key(
 
 #[tauri::command]
 pub async fn set_ai_review_settings(
+    state: State<'_, AppState>,
     input: SetAiReviewSettingsInput,
 ) -> Result<super::AiReviewConfig, String> {
-    review::config::set_ai_review_settings(input).await
+    review::config::set_ai_review_settings(state, input).await
 }
 
 #[tauri::command]
@@ -207,6 +539,13 @@ pub async fn cancel_ai_review_run(
     review::run_queue::cancel_ai_review_run(app, state, input).await
 }
 
+#[tauri::command]
+pub async fn cancel_ai_request(
+    input: CancelAiRequestInput,
+) -> Result<CancelAiRequestResult, String> {
+    review::ai_requests::cancel_ai_request(input).await
+}
+
 #[tauri::command]
 pub async fn list_ai_review_runs(
     state: State<'_, AppState>,
@@ -223,6 +562,273 @@ pub async fn get_ai_review_run(
     review::run_queue::get_ai_review_run(state, input).await
 }
 
+#[tauri::command]
+pub async fn set_ai_review_run_approval(
+    state: State<'_, AppState>,
+    input: SetAiReviewRunApprovalInput,
+) -> Result<super::AiReviewRun, String> {
+    review::run_queue::set_ai_review_run_approval(&state, input).await
+}
+
+#[tauri::command]
+pub async fn publish_review_commit_status(
+    state: State<'_, AppState>,
+    input: PublishReviewCommitStatusInput,
+) -> Result<PublishReviewCommitStatusResult, String> {
+    review::commit_status::publish_review_commit_status(&state, input).await
+}
+
+#[tauri::command]
+pub async fn export_ai_review_run(
+    state: State<'_, AppState>,
+    input: ExportAiReviewRunInput,
+) -> Result<ExportAiReviewRunResult, String> {
+    review::run_queue::export_ai_review_run(&state, input).await
+}
+
+#[tauri::command]
+pub async fn compare_ai_review_runs(
+    state: State<'_, AppState>,
+    input: CompareAiReviewRunsInput,
+) -> Result<CompareAiReviewRunsResult, String> {
+    review::run_comparison::compare_ai_review_runs(&state, input).await
+}
+
+#[tauri::command]
+pub async fn export_review_report(
+    state: State<'_, AppState>,
+    input: ExportReviewReportInput,
+) -> Result<ExportReviewReportResult, String> {
+    review::run_queue::export_review_report(state, input).await
+}
+
+#[tauri::command]
+pub async fn checkout_run_snapshot(
+    state: State<'_, AppState>,
+    input: RunSnapshotInput,
+) -> Result<RunSnapshot, String> {
+    review::run_snapshots::checkout_run_snapshot(state, input).await
+}
+
+#[tauri::command]
+pub async fn list_run_snapshots(
+    state: State<'_, AppState>,
+    input: ListRunSnapshotsInput,
+) -> Result<ListRunSnapshotsResult, String> {
+    review::run_snapshots::list_run_snapshots(state, input).await
+}
+
+#[tauri::command]
+pub async fn remove_run_snapshot(
+    state: State<'_, AppState>,
+    input: RunSnapshotInput,
+) -> Result<RemoveRunSnapshotResult, String> {
+    review::run_snapshots::remove_run_snapshot(state, input).await
+}
+
+#[tauri::command]
+pub async fn install_review_git_hook(
+    state: State<'_, AppState>,
+    input: InstallReviewGitHookInput,
+) -> Result<ReviewGitHook, String> {
+    review::git_hooks::install_review_git_hook(state, input).await
+}
+
+#[tauri::command]
+pub async fn uninstall_review_git_hook(
+    state: State<'_, AppState>,
+    input: UninstallReviewGitHookInput,
+) -> Result<UninstallReviewGitHookResult, String> {
+    review::git_hooks::uninstall_review_git_hook(state, input).await
+}
+
+#[tauri::command]
+pub async fn save_review_schedule(
+    state: State<'_, AppState>,
+    input: SaveReviewScheduleInput,
+) -> Result<ReviewSchedule, String> {
+    review::schedules::save_review_schedule(state, input).await
+}
+
+#[tauri::command]
+pub async fn list_review_schedules(
+    state: State<'_, AppState>,
+    input: ListReviewSchedulesInput,
+) -> Result<ListReviewSchedulesResult, String> {
+    review::schedules::list_review_schedules(state, input).await
+}
+
+#[tauri::command]
+pub async fn delete_review_schedule(
+    state: State<'_, AppState>,
+    input: DeleteReviewScheduleInput,
+) -> Result<DeleteReviewScheduleResult, String> {
+    review::schedules::delete_review_schedule(state, input).await
+}
+
+#[tauri::command]
+pub async fn create_reviewer_profile(
+    state: State<'_, AppState>,
+    input: CreateReviewerProfileInput,
+) -> Result<ReviewerProfile, String> {
+    review::reviewer_profiles::create_reviewer_profile(state, input).await
+}
+
+#[tauri::command]
+pub async fn update_reviewer_profile(
+    state: State<'_, AppState>,
+    input: UpdateReviewerProfileInput,
+) -> Result<ReviewerProfile, String> {
+    review::reviewer_profiles::update_reviewer_profile(state, input).await
+}
+
+#[tauri::command]
+pub async fn list_reviewer_profiles(
+    state: State<'_, AppState>,
+) -> Result<ListReviewerProfilesResult, String> {
+    review::reviewer_profiles::list_reviewer_profiles(state).await
+}
+
+#[tauri::command]
+pub async fn delete_reviewer_profile(
+    state: State<'_, AppState>,
+    input: DeleteReviewerProfileInput,
+) -> Result<DeleteReviewerProfileResult, String> {
+    review::reviewer_profiles::delete_reviewer_profile(state, input).await
+}
+
+#[tauri::command]
+pub async fn start_review_group(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: StartReviewGroupInput,
+) -> Result<ReviewGroup, String> {
+    review::groups::start_review_group(app, state, input).await
+}
+
+#[tauri::command]
+pub async fn get_review_group(
+    state: State<'_, AppState>,
+    input: ReviewGroupInput,
+) -> Result<ReviewGroup, String> {
+    review::groups::get_review_group(state, input).await
+}
+
+#[tauri::command]
+pub async fn list_review_groups(
+    state: State<'_, AppState>,
+    input: ListReviewGroupsInput,
+) -> Result<ListReviewGroupsResult, String> {
+    review::groups::list_review_groups(state, input).await
+}
+
+#[tauri::command]
+pub async fn summarize_review_group(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: ReviewGroupInput,
+) -> Result<ReviewGroup, String> {
+    review::groups::summarize_review_group(app, state, input).await
+}
+
+#[tauri::command]
+pub async fn get_review_queue_status(
+    state: State<'_, AppState>,
+) -> Result<GetReviewQueueStatusResult, String> {
+    review::run_queue::get_review_queue_status(state).await
+}
+
+#[tauri::command]
+pub async fn signal_ui_activity(input: SignalUiActivityInput) -> Result<(), String> {
+    review::ui_activity::signal_ui_activity(input).await
+}
+
+#[tauri::command]
+pub async fn get_review_usage_summary(
+    state: State<'_, AppState>,
+    input: GetReviewUsageSummaryInput,
+) -> Result<GetReviewUsageSummaryResult, String> {
+    review::run_queue::get_review_usage_summary(state, input).await
+}
+
+#[tauri::command]
+pub async fn search_findings(
+    state: State<'_, AppState>,
+    input: SearchFindingsInput,
+) -> Result<SearchFindingsResult, String> {
+    review::run_queue::search_findings(state, input).await
+}
+
+#[tauri::command]
+pub async fn dismiss_finding(
+    state: State<'_, AppState>,
+    input: UpdateFindingStateInput,
+) -> Result<ReviewFindingState, String> {
+    review::run_queue::dismiss_finding(state, input).await
+}
+
+#[tauri::command]
+pub async fn mark_finding_fixed(
+    state: State<'_, AppState>,
+    input: UpdateFindingStateInput,
+) -> Result<ReviewFindingState, String> {
+    review::run_queue::mark_finding_fixed(state, input).await
+}
+
+#[tauri::command]
+pub async fn list_finding_states(
+    state: State<'_, AppState>,
+    input: ListFindingStatesInput,
+) -> Result<ListFindingStatesResult, String> {
+    review::run_queue::list_finding_states(state, input).await
+}
+
+#[tauri::command]
+pub async fn apply_finding_fix(
+    state: State<'_, AppState>,
+    input: ApplyFindingFixInput,
+) -> Result<ApplyFindingFixResult, String> {
+    review::run_queue::apply_finding_fix(state, input).await
+}
+
+#[tauri::command]
+pub async fn list_finding_actions() -> Result<ListFindingActionsResult, String> {
+    review::finding_actions::list_finding_actions().await
+}
+
+#[tauri::command]
+pub async fn set_finding_actions(
+    state: State<'_, AppState>,
+    input: SetFindingActionsInput,
+) -> Result<ListFindingActionsResult, String> {
+    review::finding_actions::set_finding_actions(state, input).await
+}
+
+#[tauri::command]
+pub async fn run_finding_action(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: RunFindingActionInput,
+) -> Result<RunFindingActionResult, String> {
+    review::finding_actions::run_finding_action(app, state, input).await
+}
+
+#[tauri::command]
+pub async fn get_model_performance_stats(
+    state: State<'_, AppState>,
+    input: GetModelPerformanceStatsInput,
+) -> Result<GetModelPerformanceStatsResult, String> {
+    review::run_queue::get_model_performance_stats(state, input).await
+}
+
+#[tauri::command]
+pub async fn get_team_review_stats(
+    state: State<'_, AppState>,
+    input: GetTeamReviewStatsInput,
+) -> Result<GetTeamReviewStatsResult, String> {
+    review::team_stats::get_team_review_stats(state, input).await
+}
+
 #[tauri::command]
 pub async fn create_inline_review_comment(
     state: State<'_, AppState>,
@@ -259,7 +865,46 @@ pub async fn generate_ai_follow_up(
 
 #[tauri::command]
 pub async fn run_code_intel_sync(
+    state: State<'_, AppState>,
     input: Option<CodeIntelSyncInput>,
 ) -> Result<CodeIntelSyncResult, String> {
-    super::code_intel::run_code_intel_sync(input).await
+    code_intel_config::run_code_intel_sync(state, input).await
+}
+
+#[tauri::command]
+pub async fn code_intel_sync_status(
+    state: State<'_, AppState>,
+    input: Option<CodeIntelSyncStatusInput>,
+) -> Result<CodeIntelSyncStatus, String> {
+    code_intel_config::code_intel_sync_status(state, input).await
+}
+
+#[tauri::command]
+pub async fn get_code_intel_config(
+    state: State<'_, AppState>,
+    input: GetCodeIntelConfigInput,
+) -> Result<CodeIntelWorkspaceConfig, String> {
+    code_intel_config::get_code_intel_config(state, input).await
+}
+
+#[tauri::command]
+pub async fn set_code_intel_config(
+    state: State<'_, AppState>,
+    input: SetCodeIntelConfigInput,
+) -> Result<CodeIntelWorkspaceConfig, String> {
+    code_intel_config::set_code_intel_config(state, input).await
+}
+
+#[tauri::command]
+pub async fn get_symbol_definition(
+    input: SymbolLookupInput,
+) -> Result<GetSymbolDefinitionResult, String> {
+    super::code_intel::get_symbol_definition(input).await
+}
+
+#[tauri::command]
+pub async fn list_symbol_references(
+    input: SymbolLookupInput,
+) -> Result<ListSymbolReferencesResult, String> {
+    super::code_intel::list_symbol_references(input).await
 }
@@ -0,0 +1,251 @@
+use std::cmp::Ordering;
+
+use icu_normalizer::DecomposingNormalizerBorrowed;
+
+use crate::backend::NameSortOptions;
+
+/// How branch names, file paths and diff chunks are ordered. Unlike comparing
+/// `to_lowercase()` strings, this gives the same order on every platform, puts `file2` before
+/// `file10`, and sorts `École` next to `Ecole` instead of after `Zebra`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NameCollator {
+    numeric: bool,
+    case_sensitive: bool,
+    accent_sensitive: bool,
+}
+
+impl Default for NameCollator {
+    fn default() -> Self {
+        Self {
+            numeric: true,
+            case_sensitive: false,
+            accent_sensitive: false,
+        }
+    }
+}
+
+impl From<&NameSortOptions> for NameCollator {
+    fn from(options: &NameSortOptions) -> Self {
+        let defaults = Self::default();
+        Self {
+            numeric: options.numeric.unwrap_or(defaults.numeric),
+            case_sensitive: options.case_sensitive.unwrap_or(defaults.case_sensitive),
+            accent_sensitive: options
+                .accent_sensitive
+                .unwrap_or(defaults.accent_sensitive),
+        }
+    }
+}
+
+impl From<Option<&NameSortOptions>> for NameCollator {
+    fn from(options: Option<&NameSortOptions>) -> Self {
+        options.map(Self::from).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Segment {
+    /// A run of ASCII digits with leading zeros removed, and how many were removed.
+    Number {
+        digits: String,
+        leading_zeros: usize,
+    },
+    Text(String),
+}
+
+fn is_combining_mark(value: char) -> bool {
+    matches!(
+        value,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+/// Canonically decomposed `value`, without accents and lowercased as asked.
+fn fold(value: &str, strip_accents: bool, lowercase: bool) -> String {
+    let decomposed = DecomposingNormalizerBorrowed::new_nfd().normalize(value);
+    let stripped = decomposed
+        .chars()
+        .filter(|value| !strip_accents || !is_combining_mark(*value))
+        .collect::<String>();
+    if lowercase {
+        stripped.to_lowercase()
+    } else {
+        stripped
+    }
+}
+
+fn segments(value: &str, numeric: bool) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut rest = value;
+    while let Some(first) = rest.chars().next() {
+        let is_digit = numeric && first.is_ascii_digit();
+        let end = rest
+            .char_indices()
+            .find(|(_, value)| (numeric && value.is_ascii_digit()) != is_digit)
+            .map_or(rest.len(), |(index, _)| index);
+        let (run, tail) = rest.split_at(end);
+        segments.push(if is_digit {
+            let digits = run.trim_start_matches('0');
+            Segment::Number {
+                digits: digits.to_string(),
+                leading_zeros: run.len() - digits.len(),
+            }
+        } else {
+            Segment::Text(run.to_string())
+        });
+        rest = tail;
+    }
+    segments
+}
+
+fn compare_segments(left: &[Segment], right: &[Segment]) -> Ordering {
+    for (left, right) in left.iter().zip(right) {
+        let ordering = match (left, right) {
+            (
+                Segment::Number {
+                    digits: left,
+                    leading_zeros: left_zeros,
+                },
+                Segment::Number {
+                    digits: right,
+                    leading_zeros: right_zeros,
+                },
+            ) => left
+                .len()
+                .cmp(&right.len())
+                .then_with(|| left.cmp(right))
+                .then_with(|| left_zeros.cmp(right_zeros)),
+            // Numbers sort before words, as `1-setup` comes before `a-setup`.
+            (Segment::Number { .. }, Segment::Text(_)) => Ordering::Less,
+            (Segment::Text(_), Segment::Number { .. }) => Ordering::Greater,
+            (Segment::Text(left), Segment::Text(right)) => left.cmp(right),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    left.len().cmp(&right.len())
+}
+
+impl NameCollator {
+    fn compare_folded(
+        &self,
+        left: &str,
+        right: &str,
+        strip_accents: bool,
+        lowercase: bool,
+    ) -> Ordering {
+        compare_segments(
+            &segments(&fold(left, strip_accents, lowercase), self.numeric),
+            &segments(&fold(right, strip_accents, lowercase), self.numeric),
+        )
+    }
+
+    /// Compares base letters first, then accents, then case, then raw bytes, so names that
+    /// differ only in accents or case still get a fixed order. Options turn the accent and
+    /// case levels into primary differences.
+    pub(crate) fn compare(&self, left: &str, right: &str) -> Ordering {
+        self.compare_folded(left, right, !self.accent_sensitive, !self.case_sensitive)
+            .then_with(|| self.compare_folded(left, right, false, !self.case_sensitive))
+            // Lowercase sorts first on ties, matching `Intl.Collator`'s default.
+            .then_with(|| self.compare_folded(left, right, false, false).reverse())
+            .then_with(|| left.cmp(right))
+    }
+
+    /// Compares `/`-separated paths one component at a time, so `src/a/b.rs` stays next to
+    /// `src/a.rs` regardless of how `/` compares with the characters after `a`.
+    pub(crate) fn compare_paths(&self, left: &str, right: &str) -> Ordering {
+        let mut left_parts = left.split('/');
+        let mut right_parts = right.split('/');
+        loop {
+            match (left_parts.next(), right_parts.next()) {
+                (Some(left_part), Some(right_part)) => {
+                    let ordering = self.compare(left_part, right_part);
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (None, None) => return Ordering::Equal,
+            }
+        }
+    }
+}
+
+/// `NameCollator::default().compare_paths`, for orderings the client does not configure.
+pub(crate) fn compare_paths(left: &str, right: &str) -> Ordering {
+    NameCollator::default().compare_paths(left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NameCollator;
+    use crate::backend::NameSortOptions;
+
+    fn sorted(collator: NameCollator, names: &[&str]) -> Vec<String> {
+        let mut names = names.iter().map(ToString::to_string).collect::<Vec<_>>();
+        names.sort_by(|left, right| collator.compare(left, right));
+        names
+    }
+
+    #[test]
+    fn names_sort_naturally_and_ignore_case_and_accents() {
+        let collator = NameCollator::default();
+        assert_eq!(
+            sorted(
+                collator,
+                &[
+                    "release-10",
+                    "Zebra",
+                    "release-9",
+                    "École",
+                    "ecole",
+                    "Ecole",
+                    "release-09"
+                ]
+            ),
+            [
+                "ecole",
+                "Ecole",
+                "École",
+                "release-9",
+                "release-09",
+                "release-10",
+                "Zebra"
+            ]
+        );
+        assert_eq!(
+            sorted(collator, &["straße", "Strasse", "STRASSE"]),
+            ["Strasse", "STRASSE", "straße"]
+        );
+    }
+
+    #[test]
+    fn options_make_order_ordinal_or_case_sensitive() {
+        let collator = NameCollator::from(&NameSortOptions {
+            numeric: Some(false),
+            case_sensitive: Some(true),
+            accent_sensitive: None,
+        });
+        assert_eq!(
+            sorted(collator, &["file10", "file2", "File3"]),
+            ["File3", "file10", "file2"]
+        );
+    }
+
+    #[test]
+    fn paths_compare_component_by_component() {
+        let collator = NameCollator::default();
+        let mut paths = vec!["src/a-b.rs", "src/a/b.rs", "src/a.rs", "src/A/c.rs"];
+        paths.sort_by(|left, right| collator.compare_paths(left, right));
+        assert_eq!(
+            paths,
+            ["src/a/b.rs", "src/A/c.rs", "src/a-b.rs", "src/a.rs"]
+        );
+    }
+}
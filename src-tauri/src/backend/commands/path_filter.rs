@@ -0,0 +1,290 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::backend::{AiReviewSkipReason, AiReviewSkippedChunk};
+
+/// Checked-in file at the workspace root, in gitignore syntax, listing paths that should never
+/// be reviewed.
+pub(crate) const ROVEXIGNORE_FILE_NAME: &str = ".rovexignore";
+
+/// Normalizes a user-supplied path glob. Patterns without a `/` match the file name at any
+/// depth (like `.gitignore`), so `*.lock` covers `web/yarn.lock` too.
+fn normalize_path_pattern(pattern: &str) -> Option<String> {
+    let trimmed = pattern.trim().trim_start_matches("./");
+    if trimmed.is_empty() {
+        return None;
+    }
+    let anchored = trimmed.trim_start_matches('/');
+    if anchored.len() != trimmed.len() || anchored.contains('/') {
+        Some(anchored.to_string())
+    } else {
+        Some(format!("**/{anchored}"))
+    }
+}
+
+fn normalize_path_patterns(patterns: Option<&[String]>) -> Vec<String> {
+    patterns
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|pattern| normalize_path_pattern(pattern))
+        .collect()
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<Option<GlobSet>, String> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|error| format!("Invalid path pattern '{pattern}': {error}"))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|error| format!("Invalid path patterns: {error}"))
+}
+
+fn load_rovexignore(workspace_root: &Path) -> Result<Option<Gitignore>, String> {
+    let ignore_path = workspace_root.join(ROVEXIGNORE_FILE_NAME);
+    if !ignore_path.is_file() {
+        return Ok(None);
+    }
+    let mut builder = GitignoreBuilder::new(workspace_root);
+    if let Some(error) = builder.add(&ignore_path) {
+        return Err(format!("Failed to read {ROVEXIGNORE_FILE_NAME}: {error}"));
+    }
+    let ignore = builder
+        .build()
+        .map_err(|error| format!("Invalid {ROVEXIGNORE_FILE_NAME}: {error}"))?;
+    Ok((!ignore.is_empty()).then_some(ignore))
+}
+
+/// Hash of the workspace's `.rovexignore`, or `None` when it has none, so a result filtered
+/// through the old rules is not reused after the file changes.
+pub(crate) fn rovexignore_fingerprint(workspace_root: &Path) -> Option<u64> {
+    let contents = fs::read(workspace_root.join(ROVEXIGNORE_FILE_NAME)).ok()?;
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Include/exclude globs applied to repository-relative file paths, plus the workspace's
+/// `.rovexignore` rules when loaded.
+pub(crate) struct PathFilter {
+    include_patterns: Vec<String>,
+    exclude_patterns: Vec<String>,
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+    rovexignore: Option<Gitignore>,
+}
+
+impl PathFilter {
+    pub(crate) fn new(
+        include_paths: Option<&[String]>,
+        exclude_paths: Option<&[String]>,
+    ) -> Result<Self, String> {
+        let include_patterns = normalize_path_patterns(include_paths);
+        let exclude_patterns = normalize_path_patterns(exclude_paths);
+        Ok(Self {
+            include: build_glob_set(&include_patterns)?,
+            exclude: build_glob_set(&exclude_patterns)?,
+            include_patterns,
+            exclude_patterns,
+            rovexignore: None,
+        })
+    }
+
+    /// Also drops files matched by `.rovexignore` at `workspace_root`, if the file exists.
+    pub(crate) fn with_rovexignore(mut self, workspace_root: &Path) -> Result<Self, String> {
+        self.rovexignore = load_rovexignore(workspace_root)?;
+        Ok(self)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.include.is_none() && self.exclude.is_none() && self.rovexignore.is_none()
+    }
+
+    /// Whether git pathspecs alone cannot express this filter, so diff output has to be
+    /// filtered after the fact.
+    pub(crate) fn needs_diff_filtering(&self) -> bool {
+        self.rovexignore.is_some()
+    }
+
+    pub(crate) fn allows(&self, path: &str) -> bool {
+        self.skip_reason(path).is_none()
+    }
+
+    /// Why `path` is filtered out, or `None` when it passes. A trailing `/` marks a directory.
+    pub(crate) fn skip_reason(&self, path: &str) -> Option<AiReviewSkipReason> {
+        let path = path.trim_start_matches("./");
+        let included = self
+            .include
+            .as_ref()
+            .map(|set| set.is_match(path))
+            .unwrap_or(true);
+        let excluded = self
+            .exclude
+            .as_ref()
+            .map(|set| set.is_match(path))
+            .unwrap_or(false);
+        if !included || excluded {
+            return Some(AiReviewSkipReason::ExcludedPath);
+        }
+        let ignored = self
+            .rovexignore
+            .as_ref()
+            .map(|ignore| {
+                ignore
+                    .matched_path_or_any_parents(path.trim_matches('/'), path.ends_with('/'))
+                    .is_ignore()
+            })
+            .unwrap_or(false);
+        ignored.then_some(AiReviewSkipReason::Rovexignore)
+    }
+
+    /// Git pathspecs equivalent to this filter, for passing after `--`.
+    pub(crate) fn git_pathspecs(&self) -> Vec<String> {
+        let mut pathspecs = self
+            .include_patterns
+            .iter()
+            .map(|pattern| format!(":(glob){pattern}"))
+            .collect::<Vec<_>>();
+        pathspecs.extend(
+            self.exclude_patterns
+                .iter()
+                .map(|pattern| format!(":(glob,exclude){pattern}")),
+        );
+        pathspecs
+    }
+}
+
+pub(crate) struct FilteredDiff {
+    pub(crate) diff: String,
+    pub(crate) files_changed: i64,
+    pub(crate) insertions: i64,
+    pub(crate) deletions: i64,
+    /// Files whose sections were dropped, with the reason their new path was filtered out.
+    pub(crate) skipped: Vec<AiReviewSkippedChunk>,
+}
+
+fn diff_section_paths(section: &str) -> Vec<&str> {
+    let mut paths = Vec::new();
+    for line in section.lines() {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            if let Some((old, new)) = header.split_once(" b/") {
+                paths.push(old.trim_start_matches("a/"));
+                paths.push(new);
+            }
+            continue;
+        }
+        if let Some(path) = line
+            .strip_prefix("--- a/")
+            .or_else(|| line.strip_prefix("+++ b/"))
+        {
+            paths.push(path.trim_end());
+        }
+        if line.starts_with("@@") {
+            break;
+        }
+    }
+    paths
+}
+
+/// Keeps the file sections of a unified git diff whose old or new path passes `filter`, and
+/// recounts files and changed lines for what remains.
+pub(crate) fn filter_diff_by_path(diff: &str, filter: &PathFilter) -> FilteredDiff {
+    let mut sections: Vec<String> = Vec::new();
+    for line in diff.split_inclusive('\n') {
+        if line.starts_with("diff --git ") || sections.is_empty() {
+            sections.push(String::new());
+        }
+        if let Some(section) = sections.last_mut() {
+            section.push_str(line);
+        }
+    }
+
+    let mut filtered = FilteredDiff {
+        diff: String::new(),
+        files_changed: 0,
+        insertions: 0,
+        deletions: 0,
+        skipped: Vec::new(),
+    };
+    for section in sections {
+        let paths = diff_section_paths(&section);
+        if paths.is_empty() {
+            continue;
+        }
+        if !paths.iter().any(|path| filter.allows(path)) {
+            // `diff --git` lists the old path first and the new one second.
+            let file_path = paths.get(1).unwrap_or(&paths[0]).to_string();
+            let previous_path = (paths[0] != file_path).then(|| paths[0].to_string());
+            filtered.skipped.push(AiReviewSkippedChunk {
+                reason: filter
+                    .skip_reason(&file_path)
+                    .unwrap_or(AiReviewSkipReason::ExcludedPath),
+                file_path,
+                previous_path,
+            });
+            continue;
+        }
+        filtered.files_changed += 1;
+        let mut in_hunk = false;
+        for line in section.lines() {
+            if line.starts_with("@@") {
+                in_hunk = true;
+            } else if in_hunk && line.starts_with('+') {
+                filtered.insertions += 1;
+            } else if in_hunk && line.starts_with('-') {
+                filtered.deletions += 1;
+            }
+        }
+        filtered.diff.push_str(&section);
+    }
+    filtered
+}
+
+/// Expands a `--numstat` path column, including rename forms like `old => new` and
+/// `src/{old => new}/mod.rs`, into the paths it mentions.
+fn numstat_paths(column: &str) -> Vec<String> {
+    if let (Some(open), Some(close)) = (column.find('{'), column.rfind('}')) {
+        if let Some((old, new)) = column[open + 1..close].split_once(" => ") {
+            let prefix = &column[..open];
+            let suffix = &column[close + 1..];
+            return [old, new]
+                .iter()
+                .map(|middle| format!("{prefix}{middle}{suffix}").replace("//", "/"))
+                .collect();
+        }
+    }
+    match column.split_once(" => ") {
+        Some((old, new)) => vec![old.to_string(), new.to_string()],
+        None => vec![column.to_string()],
+    }
+}
+
+/// Keeps the `git diff --numstat` lines whose old or new path passes `filter`.
+pub(crate) fn filter_numstat_by_path(numstat: &str, filter: &PathFilter) -> String {
+    numstat
+        .lines()
+        .filter(|line| {
+            line.splitn(3, '\t').nth(2).is_some_and(|column| {
+                numstat_paths(column.trim())
+                    .iter()
+                    .any(|path| filter.allows(path))
+            })
+        })
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
@@ -0,0 +1,88 @@
+use std::{
+    fs,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::path_filter::{
+    filter_diff_by_path, filter_numstat_by_path, PathFilter, ROVEXIGNORE_FILE_NAME,
+};
+use crate::backend::AiReviewSkipReason;
+
+const DIFF: &str = "diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,2 +1,2 @@
+-fn main() {}
++fn main() { run(); }
+diff --git a/web/yarn.lock b/web/yarn.lock
+--- a/web/yarn.lock
++++ b/web/yarn.lock
+@@ -1 +1,2 @@
+ left-pad@1.0.0
++left-pad@1.1.0
+";
+
+#[test]
+fn bare_patterns_match_file_names_at_any_depth() {
+    let filter = PathFilter::new(None, Some(&["*.lock".to_string()])).expect("valid filter");
+    assert!(!filter.allows("web/yarn.lock"));
+    assert!(!filter.allows("Cargo.lock"));
+    assert!(filter.allows("src/main.rs"));
+}
+
+#[test]
+fn include_patterns_with_directories_are_anchored() {
+    let filter = PathFilter::new(Some(&["src/**".to_string()]), None).expect("valid filter");
+    assert!(filter.allows("src/backend/mod.rs"));
+    assert!(!filter.allows("web/src/index.ts"));
+}
+
+#[test]
+fn filter_diff_by_path_drops_excluded_sections_and_recounts() {
+    let filter = PathFilter::new(None, Some(&["*.lock".to_string()])).expect("valid filter");
+    let filtered = filter_diff_by_path(DIFF, &filter);
+    assert!(filtered.diff.contains("src/main.rs"));
+    assert!(!filtered.diff.contains("yarn.lock"));
+    assert_eq!(filtered.files_changed, 1);
+    assert_eq!(filtered.insertions, 1);
+    assert_eq!(filtered.deletions, 1);
+    assert_eq!(filtered.skipped.len(), 1);
+    assert_eq!(filtered.skipped[0].file_path, "web/yarn.lock");
+    assert_eq!(filtered.skipped[0].reason, AiReviewSkipReason::ExcludedPath);
+}
+
+#[test]
+fn rovexignore_rules_drop_ignored_files() {
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time")
+        .as_nanos();
+    let workspace = std::env::temp_dir().join(format!("rovex-rovexignore-test-{suffix}"));
+    fs::create_dir_all(&workspace).expect("create temp workspace");
+    fs::write(
+        workspace.join(ROVEXIGNORE_FILE_NAME),
+        "# generated\n*.lock\nvendor/\n",
+    )
+    .expect("write .rovexignore");
+
+    let filter = PathFilter::new(None, None)
+        .and_then(|filter| filter.with_rovexignore(&workspace))
+        .expect("valid filter");
+    assert!(filter.needs_diff_filtering());
+    assert!(!filter.allows("web/yarn.lock"));
+    assert!(!filter.allows("vendor/lib/mod.rs"));
+    assert!(filter.allows("src/main.rs"));
+
+    let filtered = filter_diff_by_path(DIFF, &filter);
+    assert!(!filtered.diff.contains("yarn.lock"));
+    assert_eq!(filtered.files_changed, 1);
+    assert_eq!(filtered.skipped[0].reason, AiReviewSkipReason::Rovexignore);
+
+    let numstat = "1\t1\tsrc/main.rs\n1\t0\tweb/yarn.lock\n2\t0\t{vendor => src}/util.rs\n";
+    assert_eq!(
+        filter_numstat_by_path(numstat, &filter),
+        "1\t1\tsrc/main.rs\n2\t0\t{vendor => src}/util.rs\n"
+    );
+
+    let _ = fs::remove_dir_all(&workspace);
+}
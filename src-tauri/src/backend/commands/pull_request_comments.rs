@@ -0,0 +1,291 @@
+use std::collections::HashSet;
+
+use tauri::State;
+
+use super::super::providers::provider_client;
+use super::common::parse_provider_kind;
+use super::providers::load_provider_connection_row;
+use super::review::prompt_safety::fence_untrusted;
+use super::threads::{load_message_by_id, load_thread_by_id, persist_thread_message};
+use crate::backend::{
+    AppState, ImportPullRequestCommentsInput, ImportPullRequestCommentsResult,
+    ListPullRequestCommentsInput, ListPullRequestCommentsResult, MessageRole, PullRequestComment,
+};
+
+const COMMENT_COLUMNS: &str = "id, thread_id, message_id, provider, repository,
+    pull_request_number, external_id, author, body, file_path, line_number, url, commented_at,
+    imported_at";
+
+fn parse_comment_from_row(row: &libsql::Row) -> Result<PullRequestComment, String> {
+    let provider: String = row
+        .get(3)
+        .map_err(|error| format!("Failed to parse pull request comment provider: {error}"))?;
+    Ok(PullRequestComment {
+        id: row
+            .get(0)
+            .map_err(|error| format!("Failed to parse pull request comment id: {error}"))?,
+        thread_id: row
+            .get(1)
+            .map_err(|error| format!("Failed to parse pull request comment thread_id: {error}"))?,
+        message_id: row
+            .get(2)
+            .map_err(|error| format!("Failed to parse pull request comment message_id: {error}"))?,
+        provider: parse_provider_kind(provider)?,
+        repository: row
+            .get(4)
+            .map_err(|error| format!("Failed to parse pull request comment repository: {error}"))?,
+        pull_request_number: row
+            .get(5)
+            .map_err(|error| format!("Failed to parse pull request number: {error}"))?,
+        external_id: row.get(6).map_err(|error| {
+            format!("Failed to parse pull request comment external id: {error}")
+        })?,
+        author: row
+            .get(7)
+            .map_err(|error| format!("Failed to parse pull request comment author: {error}"))?,
+        body: row
+            .get(8)
+            .map_err(|error| format!("Failed to parse pull request comment body: {error}"))?,
+        file_path: row
+            .get(9)
+            .map_err(|error| format!("Failed to parse pull request comment file path: {error}"))?,
+        line_number: row
+            .get(10)
+            .map_err(|error| format!("Failed to parse pull request comment line: {error}"))?,
+        url: row
+            .get(11)
+            .map_err(|error| format!("Failed to parse pull request comment url: {error}"))?,
+        commented_at: row
+            .get(12)
+            .map_err(|error| format!("Failed to parse pull request comment date: {error}"))?,
+        imported_at: row.get(13).map_err(|error| {
+            format!("Failed to parse pull request comment import date: {error}")
+        })?,
+    })
+}
+
+async fn query_comments(
+    state: &AppState,
+    filter: &str,
+    id: i64,
+) -> Result<Vec<PullRequestComment>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {COMMENT_COLUMNS} FROM pull_request_comments WHERE {filter}
+                 ORDER BY commented_at ASC, id ASC"
+            ),
+            [id],
+        )
+        .await
+        .map_err(|error| format!("Failed to load pull request comments: {error}"))?;
+    let mut comments = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read pull request comment row: {error}"))?
+    {
+        comments.push(parse_comment_from_row(&row)?);
+    }
+    Ok(comments)
+}
+
+/// Comments imported into a thread from its pull request, oldest first.
+pub(crate) async fn load_thread_pull_request_comments(
+    state: &AppState,
+    thread_id: i64,
+) -> Result<Vec<PullRequestComment>, String> {
+    query_comments(state, "thread_id = ?1", thread_id).await
+}
+
+fn format_comment(comment: &PullRequestComment) -> String {
+    let location = match (&comment.file_path, comment.line_number) {
+        (Some(path), Some(line)) => format!(" on {path}:{line}"),
+        (Some(path), None) => format!(" on {path}"),
+        _ => String::new(),
+    };
+    format!("@{}{location}:\n{}", comment.author, comment.body)
+}
+
+/// Comments as one fenced block for a prompt. They are written by people outside this
+/// machine, so they are fenced like the diff.
+pub(crate) fn format_pull_request_comments(comments: &[PullRequestComment]) -> String {
+    let text = comments
+        .iter()
+        .map(format_comment)
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    fence_untrusted("review-comments", &text)
+}
+
+/// Prompt section listing what teammates already said about `file_path`, so the reviewer
+/// does not report it again. `None` when nobody commented on the file.
+pub(crate) fn teammate_comments_for_file(
+    comments: &[PullRequestComment],
+    file_path: &str,
+) -> Option<String> {
+    let on_file = comments
+        .iter()
+        .filter(|comment| comment.file_path.as_deref() == Some(file_path))
+        .cloned()
+        .collect::<Vec<_>>();
+    if on_file.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "\n\nTeammates already left these review comments on this file. Do not report issues they already raised:\n{}",
+        format_pull_request_comments(&on_file)
+    ))
+}
+
+/// Copies the comments people left on a pull request into the thread, so follow-up answers
+/// and AI reviews know what teammates already flagged. Comments imported before are skipped,
+/// so importing again only picks up new ones.
+pub async fn import_pull_request_comments(
+    state: State<'_, AppState>,
+    input: ImportPullRequestCommentsInput,
+) -> Result<ImportPullRequestCommentsResult, String> {
+    let thread = load_thread_by_id(&state, input.thread_id).await?;
+    if input.number == 0 {
+        return Err("Pull request number must be positive.".to_string());
+    }
+    let connection = load_provider_connection_row(&state, input.provider)
+        .await?
+        .ok_or_else(|| format!("{} is not connected.", input.provider.as_str()))?;
+    let client = provider_client(input.provider);
+    let repository = client.parse_repository(&input.repository)?;
+    let slug = repository.slug();
+    let comments = client
+        .list_pull_request_comments(&connection.access_token, &repository, input.number)
+        .await?;
+
+    let existing = query_comments(&state, "thread_id = ?1", thread.id)
+        .await?
+        .into_iter()
+        .filter(|comment| comment.provider == input.provider && comment.repository == slug)
+        .map(|comment| comment.external_id)
+        .collect::<HashSet<_>>();
+    let (new_comments, skipped): (Vec<_>, Vec<_>) = comments
+        .into_iter()
+        .partition(|comment| !existing.contains(&comment.id));
+    if new_comments.is_empty() {
+        return Ok(ImportPullRequestCommentsResult {
+            thread_id: thread.id,
+            message: None,
+            imported: Vec::new(),
+            skipped_count: skipped.len(),
+        });
+    }
+
+    let mut authors = Vec::new();
+    for comment in &new_comments {
+        let author = format!("@{}", comment.author);
+        if !authors.contains(&author) {
+            authors.push(author);
+        }
+    }
+    let summary = format!(
+        "Imported {} review comment{} by {} from {} {slug}#{}.",
+        new_comments.len(),
+        if new_comments.len() == 1 { "" } else { "s" },
+        authors.join(", "),
+        input.provider.as_str(),
+        input.number
+    );
+    let message_id = persist_thread_message(&state, thread.id, MessageRole::System, &summary)
+        .await?
+        .ok_or_else(|| "Missing import message after importing comments.".to_string())?;
+
+    let conn = state.connection()?;
+    for comment in new_comments {
+        conn.execute(
+            "INSERT INTO pull_request_comments
+               (thread_id, message_id, provider, repository, pull_request_number, external_id,
+                author, body, file_path, line_number, url, commented_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+             ON CONFLICT(thread_id, provider, repository, external_id) DO NOTHING",
+            libsql::params![
+                thread.id,
+                message_id,
+                input.provider.as_str(),
+                slug.clone(),
+                input.number as i64,
+                comment.id,
+                comment.author,
+                comment.body,
+                comment.file_path,
+                comment.line,
+                comment.url,
+                comment.created_at,
+            ],
+        )
+        .await
+        .map_err(|error| format!("Failed to store pull request comment: {error}"))?;
+    }
+
+    Ok(ImportPullRequestCommentsResult {
+        thread_id: thread.id,
+        message: Some(load_message_by_id(&state, message_id).await?),
+        imported: query_comments(&state, "message_id = ?1", message_id).await?,
+        skipped_count: skipped.len(),
+    })
+}
+
+pub async fn list_pull_request_comments(
+    state: State<'_, AppState>,
+    input: ListPullRequestCommentsInput,
+) -> Result<ListPullRequestCommentsResult, String> {
+    let _ = load_thread_by_id(&state, input.thread_id).await?;
+    Ok(ListPullRequestCommentsResult {
+        comments: load_thread_pull_request_comments(&state, input.thread_id).await?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::teammate_comments_for_file;
+    use crate::backend::{ProviderKind, PullRequestComment};
+
+    fn comment(
+        file_path: Option<&str>,
+        line_number: Option<i64>,
+        body: &str,
+    ) -> PullRequestComment {
+        PullRequestComment {
+            id: 1,
+            thread_id: 1,
+            message_id: 1,
+            provider: ProviderKind::Github,
+            repository: "acme/app".to_string(),
+            pull_request_number: 7,
+            external_id: "review-comment:1".to_string(),
+            author: "alice".to_string(),
+            body: body.to_string(),
+            file_path: file_path.map(ToOwned::to_owned),
+            line_number,
+            url: None,
+            commented_at: "2026-01-01T00:00:00Z".to_string(),
+            imported_at: "2026-01-02 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn teammate_comments_are_fenced_and_limited_to_the_file() {
+        let comments = [
+            comment(
+                Some("src/lib.rs"),
+                Some(12),
+                "This unwrap panics on empty input.",
+            ),
+            comment(Some("src/main.rs"), None, "Rename this."),
+            comment(None, None, "Looks good overall."),
+        ];
+        let block = teammate_comments_for_file(&comments, "src/lib.rs").unwrap();
+        assert!(block.contains("@alice on src/lib.rs:12:\nThis unwrap panics on empty input."));
+        assert!(block.contains("BEGIN UNTRUSTED review-comments-"));
+        assert!(!block.contains("Rename this."));
+        assert!(!block.contains("Looks good overall."));
+        assert!(teammate_comments_for_file(&comments, "src/other.rs").is_none());
+    }
+}
@@ -0,0 +1,137 @@
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+
+use super::common::{as_non_empty_trimmed, format_path};
+use super::review::reviewer_profiles::parse_min_severity;
+
+/// Checked-in review settings at the workspace root, in the order they are looked up. Only
+/// the first file found is read.
+pub(crate) const REPO_CONFIG_FILE_NAMES: [&str; 2] = ["rovex.toml", ".rovex/config.toml"];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RepoConfigFile {
+    #[serde(default)]
+    review: RepoReviewSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RepoReviewSection {
+    base_ref: Option<String>,
+    #[serde(default)]
+    exclude_paths: Vec<String>,
+    profile: Option<String>,
+    model: Option<String>,
+    min_severity: Option<String>,
+}
+
+/// The `[review]` table of a workspace's `rovex.toml`. Explicit request values win over
+/// `base_ref` and `profile`; `exclude_paths` adds to the request's excludes; `model` replaces
+/// the model from Settings; `min_severity` applies alongside a reviewer profile's, and the
+/// stricter of the two wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RepoReviewConfig {
+    /// Path of the file the settings came from, for error messages and run notes.
+    pub(crate) path: String,
+    pub(crate) base_ref: Option<String>,
+    pub(crate) exclude_paths: Vec<String>,
+    pub(crate) profile: Option<String>,
+    pub(crate) model: Option<String>,
+    pub(crate) min_severity: Option<&'static str>,
+}
+
+impl RepoReviewConfig {
+    /// `exclude_paths` from the request followed by the ones from the config file.
+    pub(crate) fn merged_exclude_paths(&self, exclude_paths: Option<&[String]>) -> Vec<String> {
+        exclude_paths
+            .unwrap_or_default()
+            .iter()
+            .chain(&self.exclude_paths)
+            .cloned()
+            .collect()
+    }
+}
+
+fn parse_repo_config(path: &str, content: &str) -> Result<RepoReviewConfig, String> {
+    let file: RepoConfigFile =
+        toml::from_str(content).map_err(|error| format!("Invalid {path}: {error}"))?;
+    let review = file.review;
+    Ok(RepoReviewConfig {
+        path: path.to_string(),
+        base_ref: as_non_empty_trimmed(review.base_ref.as_deref()),
+        exclude_paths: review
+            .exclude_paths
+            .iter()
+            .map(|pattern| pattern.trim().to_string())
+            .filter(|pattern| !pattern.is_empty())
+            .collect(),
+        profile: as_non_empty_trimmed(review.profile.as_deref()),
+        model: as_non_empty_trimmed(review.model.as_deref()),
+        min_severity: parse_min_severity(review.min_severity.as_deref())
+            .map_err(|error| format!("Invalid {path}: {error}"))?,
+    })
+}
+
+/// Reads the first of [`REPO_CONFIG_FILE_NAMES`] that exists in `workspace_root`. `None` when
+/// the workspace has none.
+pub(crate) fn load_repo_review_config(
+    workspace_root: &Path,
+) -> Result<Option<RepoReviewConfig>, String> {
+    let Some(path) = REPO_CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| workspace_root.join(name))
+        .find(|path| path.is_file())
+    else {
+        return Ok(None);
+    };
+    let display_path = format_path(&path);
+    let content = fs::read_to_string(&path)
+        .map_err(|error| format!("Failed to read {display_path}: {error}"))?;
+    parse_repo_config(&display_path, &content).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_repo_config;
+
+    #[test]
+    fn review_table_is_parsed_and_validated() {
+        let config = parse_repo_config(
+            "rovex.toml",
+            r#"
+[review]
+base_ref = "origin/develop"
+exclude_paths = ["vendor/**", "  ", "*.lock"]
+profile = "Security review"
+min_severity = "High"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.base_ref.as_deref(), Some("origin/develop"));
+        assert_eq!(config.exclude_paths, ["vendor/**", "*.lock"]);
+        assert_eq!(config.profile.as_deref(), Some("Security review"));
+        assert_eq!(config.model, None);
+        assert_eq!(config.min_severity, Some("high"));
+        assert_eq!(
+            config.merged_exclude_paths(Some(&["docs/**".to_string()])),
+            ["docs/**", "vendor/**", "*.lock"]
+        );
+
+        assert!(parse_repo_config("rovex.toml", "")
+            .unwrap()
+            .base_ref
+            .is_none());
+        assert!(
+            parse_repo_config("rovex.toml", "[review]\nbase = \"main\"\n")
+                .unwrap_err()
+                .contains("unknown field")
+        );
+        assert!(
+            parse_repo_config("rovex.toml", "[review]\nmin_severity = \"urgent\"\n")
+                .unwrap_err()
+                .contains("Unknown minimum severity")
+        );
+    }
+}
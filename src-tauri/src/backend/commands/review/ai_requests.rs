@@ -0,0 +1,143 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+};
+
+use tokio::sync::Notify;
+
+use super::super::common::as_non_empty_trimmed;
+use crate::backend::{CancelAiRequestInput, CancelAiRequestResult};
+
+/// Longest request id a client may pass; ids are usually UUIDs.
+const MAX_REQUEST_ID_CHARS: usize = 128;
+
+#[derive(Default)]
+struct RequestCancel {
+    flag: Arc<AtomicBool>,
+    notify: Notify,
+}
+
+static ACTIVE_AI_REQUESTS: OnceLock<Mutex<HashMap<String, Arc<RequestCancel>>>> = OnceLock::new();
+
+fn active_ai_requests() -> &'static Mutex<HashMap<String, Arc<RequestCancel>>> {
+    ACTIVE_AI_REQUESTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A follow-up or ad-hoc review that `cancel_ai_request` can stop. Requests without a
+/// client-chosen id run the same way but cannot be canceled. Dropping it unregisters the id.
+pub(crate) struct AiRequest {
+    id: Option<String>,
+    cancel: Arc<RequestCancel>,
+}
+
+impl AiRequest {
+    pub(crate) fn register(request_id: Option<&str>) -> Result<Self, String> {
+        let cancel = Arc::new(RequestCancel::default());
+        let Some(id) = as_non_empty_trimmed(request_id) else {
+            return Ok(Self { id: None, cancel });
+        };
+        if id.chars().count() > MAX_REQUEST_ID_CHARS {
+            return Err(format!(
+                "Request id must be at most {MAX_REQUEST_ID_CHARS} characters."
+            ));
+        }
+        let mut requests = active_ai_requests()
+            .lock()
+            .map_err(|_| "Failed to access active AI requests.".to_string())?;
+        if requests.contains_key(&id) {
+            return Err(format!("Request '{id}' is already running."));
+        }
+        requests.insert(id.clone(), cancel.clone());
+        Ok(Self {
+            id: Some(id),
+            cancel,
+        })
+    }
+
+    /// Set once the request is canceled, for code that checks between steps.
+    pub(crate) fn cancel_flag(&self) -> &Arc<AtomicBool> {
+        &self.cancel.flag
+    }
+
+    /// Runs `future` until it finishes or the request is canceled. A canceled future is
+    /// dropped, which closes its provider connection or kills its app-server process.
+    pub(crate) async fn run<T>(
+        &self,
+        future: impl Future<Output = Result<T, String>>,
+    ) -> Result<T, String> {
+        tokio::select! {
+            result = future => result,
+            _ = self.cancel.notify.notified() => Err("Request canceled.".to_string()),
+        }
+    }
+}
+
+impl Drop for AiRequest {
+    fn drop(&mut self) {
+        let Some(id) = self.id.take() else {
+            return;
+        };
+        if let Ok(mut requests) = active_ai_requests().lock() {
+            requests.remove(&id);
+        }
+    }
+}
+
+/// Cancels a running `generate_ai_follow_up` or `generate_ai_review` call by the `requestId`
+/// it was started with. `canceled` is false when no such request is running.
+pub async fn cancel_ai_request(
+    input: CancelAiRequestInput,
+) -> Result<CancelAiRequestResult, String> {
+    let request_id = input.request_id.trim().to_string();
+    if request_id.is_empty() {
+        return Err("Request id must not be empty.".to_string());
+    }
+    let cancel = active_ai_requests()
+        .lock()
+        .map_err(|_| "Failed to access active AI requests.".to_string())?
+        .get(&request_id)
+        .cloned();
+    if let Some(cancel) = &cancel {
+        cancel.flag.store(true, Ordering::Relaxed);
+        // `notify_one` keeps the wakeup if the request is between awaits right now.
+        cancel.notify.notify_one();
+    }
+    Ok(CancelAiRequestResult {
+        request_id,
+        canceled: cancel.is_some(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cancel_ai_request, AiRequest};
+    use crate::backend::CancelAiRequestInput;
+
+    fn cancel(request_id: &str) -> bool {
+        tauri::async_runtime::block_on(cancel_ai_request(CancelAiRequestInput {
+            request_id: request_id.to_string(),
+        }))
+        .unwrap()
+        .canceled
+    }
+
+    #[test]
+    fn canceling_a_request_stops_it_and_frees_its_id() {
+        let request = AiRequest::register(Some("follow-up-1")).unwrap();
+        assert!(AiRequest::register(Some("follow-up-1")).is_err());
+
+        assert!(cancel("follow-up-1"));
+        let result = tauri::async_runtime::block_on(
+            request.run(std::future::pending::<Result<(), String>>()),
+        );
+        assert_eq!(result, Err("Request canceled.".to_string()));
+
+        drop(request);
+        assert!(!cancel("follow-up-1"));
+        assert!(AiRequest::register(Some("follow-up-1")).is_ok());
+    }
+}
@@ -0,0 +1,189 @@
+use super::super::common::{snippet, CHUNK_RETRY_MAX_ATTEMPTS};
+use super::diff_chunks::{describe_chunk_review_parse_error, try_parse_chunk_review_payload};
+
+pub(crate) const MODEL_REFUSAL_ERROR: &str = "The model refused to review this file";
+pub(crate) const CONTENT_FILTER_ERROR: &str =
+    "The AI provider's content filter blocked the review of this file";
+pub(crate) const EMPTY_OUTPUT_ERROR: &str = "AI provider returned an empty response.";
+pub(crate) const INVALID_JSON_ERROR: &str = "The model did not return review JSON";
+
+/// Longest part of a reply that was not review JSON quoted back in the repair prompt.
+const MAX_REPAIR_QUOTE_CHARS: usize = 2_000;
+
+/// Longest reply still checked for refusal phrasing; real reviews are longer.
+const MAX_REFUSAL_CHARS: usize = 600;
+
+const REFUSAL_PHRASES: [&str; 10] = [
+    "i'm sorry",
+    "i am sorry",
+    "i can't help",
+    "i cannot help",
+    "i can't assist",
+    "i cannot assist",
+    "i'm unable to",
+    "i am unable to",
+    "i won't be able to",
+    "i must decline",
+];
+
+/// How a chunk review ended. Persisted on chunks as [`ChunkOutcome::as_str`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChunkOutcome {
+    Reviewed,
+    Refusal,
+    ContentFilter,
+    EmptyOutput,
+    InvalidJson,
+    Error,
+}
+
+impl ChunkOutcome {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Reviewed => "reviewed",
+            Self::Refusal => "refusal",
+            Self::ContentFilter => "content-filter",
+            Self::EmptyOutput => "empty-output",
+            Self::InvalidJson => "invalid-json",
+            Self::Error => "error",
+        }
+    }
+
+    /// Attempts allowed for an outcome, counting the first. Content filters are deterministic
+    /// for the same prompt, refusals and malformed JSON get one more sample, and empty output
+    /// is retried like a transient error. Errors use [`is_transient_chunk_error`] instead.
+    ///
+    /// [`is_transient_chunk_error`]: super::executor::is_transient_chunk_error
+    pub(crate) fn max_attempts(self) -> usize {
+        match self {
+            Self::Reviewed | Self::ContentFilter => 1,
+            Self::Refusal | Self::InvalidJson => 2,
+            Self::EmptyOutput | Self::Error => CHUNK_RETRY_MAX_ATTEMPTS,
+        }
+    }
+
+    /// Error message for a chunk that ended with this outcome after its last attempt.
+    pub(crate) fn error_message(self, raw_output: &str) -> String {
+        let raw_output = snippet(raw_output.trim(), 300);
+        match self {
+            Self::Refusal => format!("{MODEL_REFUSAL_ERROR}: {raw_output}"),
+            Self::ContentFilter => CONTENT_FILTER_ERROR.to_string(),
+            Self::EmptyOutput => EMPTY_OUTPUT_ERROR.to_string(),
+            Self::InvalidJson => format!("{INVALID_JSON_ERROR}: {raw_output}"),
+            Self::Reviewed | Self::Error => raw_output,
+        }
+    }
+}
+
+/// The prompt for the one repair attempt after `raw_output` was not review JSON: the original
+/// prompt, the rejected reply, and why it could not be decoded.
+pub(crate) fn json_repair_prompt(prompt: &str, raw_output: &str) -> String {
+    format!(
+        "{prompt}\n\nYour previous reply could not be decoded ({}):\n{}\n\nReply again with only the JSON object described above, with no prose or code fences around it. Keep the findings from your previous reply.",
+        describe_chunk_review_parse_error(raw_output),
+        snippet(raw_output.trim(), MAX_REPAIR_QUOTE_CHARS)
+    )
+}
+
+fn looks_like_refusal(output: &str) -> bool {
+    if output.chars().count() > MAX_REFUSAL_CHARS || output.contains('{') {
+        return false;
+    }
+    let normalized = output.to_lowercase().replace('\u{2019}', "'");
+    REFUSAL_PHRASES
+        .iter()
+        .any(|phrase| normalized.contains(phrase))
+}
+
+/// Classifies output a transport returned without an error.
+pub(crate) fn classify_chunk_output(raw_output: &str) -> ChunkOutcome {
+    let trimmed = raw_output.trim();
+    if trimmed.is_empty() {
+        ChunkOutcome::EmptyOutput
+    } else if try_parse_chunk_review_payload(trimmed).is_some() {
+        ChunkOutcome::Reviewed
+    } else if looks_like_refusal(trimmed) {
+        ChunkOutcome::Refusal
+    } else {
+        ChunkOutcome::InvalidJson
+    }
+}
+
+/// Classifies the error a chunk review failed with.
+pub(crate) fn classify_chunk_error(message: &str) -> ChunkOutcome {
+    if message.starts_with(MODEL_REFUSAL_ERROR) {
+        return ChunkOutcome::Refusal;
+    }
+    if message.starts_with(EMPTY_OUTPUT_ERROR) {
+        return ChunkOutcome::EmptyOutput;
+    }
+    if message.starts_with(INVALID_JSON_ERROR) {
+        return ChunkOutcome::InvalidJson;
+    }
+    let normalized = message.to_lowercase();
+    // Providers that filter prompts answer with an HTTP error naming the policy.
+    let is_content_filter = message.starts_with(CONTENT_FILTER_ERROR)
+        || [
+            "content_filter",
+            "content_policy_violation",
+            "content management policy",
+        ]
+        .iter()
+        .any(|needle| normalized.contains(needle));
+    if is_content_filter {
+        ChunkOutcome::ContentFilter
+    } else {
+        ChunkOutcome::Error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify_chunk_error, classify_chunk_output, json_repair_prompt, ChunkOutcome};
+
+    #[test]
+    fn classifies_outputs_and_errors() {
+        assert_eq!(classify_chunk_output("  "), ChunkOutcome::EmptyOutput);
+        assert_eq!(
+            classify_chunk_output("```json\n{\"summary\":\"ok\",\"findings\":[]}\n```"),
+            ChunkOutcome::Reviewed
+        );
+        assert_eq!(
+            classify_chunk_output("I’m sorry, but I can’t help with reviewing this code."),
+            ChunkOutcome::Refusal
+        );
+        assert_eq!(
+            classify_chunk_output("The change looks fine to me."),
+            ChunkOutcome::InvalidJson
+        );
+
+        assert_eq!(
+            classify_chunk_error(&ChunkOutcome::Refusal.error_message("I cannot assist.")),
+            ChunkOutcome::Refusal
+        );
+        assert_eq!(
+            classify_chunk_error(
+                "AI provider returned 400 Bad Request. Response: {\"error\":{\"code\":\"content_filter\"}}"
+            ),
+            ChunkOutcome::ContentFilter
+        );
+        assert_eq!(
+            classify_chunk_error("AI provider returned 503 Service Unavailable."),
+            ChunkOutcome::Error
+        );
+    }
+
+    #[test]
+    fn repair_prompt_quotes_the_reply_and_why_it_failed() {
+        let prompt = json_repair_prompt(
+            "Review this file.",
+            "Here you go: {\"summary\": \"ok\", \"findings\": [],}",
+        );
+        assert!(prompt.starts_with(
+            "Review this file.\n\nYour previous reply could not be decoded (invalid JSON: trailing comma"
+        ));
+        assert!(prompt.contains("Here you go: {"));
+        assert!(json_repair_prompt("Review this file.", "Looks fine to me.")
+            .contains("(no JSON object found)"));
+    }
+}
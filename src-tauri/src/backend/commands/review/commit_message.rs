@@ -0,0 +1,129 @@
+use tauri::{AppHandle, State};
+
+use super::super::common::{as_non_empty_trimmed, truncate_chars};
+use super::super::workspace_git::read_staged_diff;
+use super::super::workspace_trust::is_workspace_trusted;
+use super::follow_up::generate_text_with_review_provider;
+use super::prompt_safety::{fence_untrusted, UNTRUSTED_CONTENT_RULE};
+use super::ReviewProvider;
+use crate::backend::{AppState, GenerateCommitMessageInput, GenerateCommitMessageResult};
+
+/// Characters of the staged diff the model sees. Commit messages summarize, so the start of
+/// each file's changes is enough.
+const MAX_COMMIT_MESSAGE_DIFF_CHARS: usize = 40_000;
+const MAX_COMMIT_SUMMARY_CHARS: usize = 72;
+const MAX_LISTED_STAGED_FILES: usize = 200;
+
+fn build_commit_message_prompt(
+    diff: &str,
+    diff_truncated: bool,
+    files: &[String],
+    instructions: Option<&str>,
+) -> String {
+    let mut prompt = format!(
+        "Write a git commit message for the staged changes below.\n\nRules:\n- First line: an imperative summary of at most {MAX_COMMIT_SUMMARY_CHARS} characters, without a trailing period.\n- If the change needs explaining, add a blank line and a short body wrapped at 72 columns that says what changed and why.\n- Do not list every file and do not invent motivation the diff does not show.\n- Reply with the commit message only, without code fences or commentary.\n- {UNTRUSTED_CONTENT_RULE}\n"
+    );
+    if let Some(instructions) = instructions {
+        prompt.push_str(&format!("\nAuthor's instructions: {instructions}\n"));
+    }
+    prompt.push_str(&format!("\nStaged files ({}):\n", files.len()));
+    for file in files.iter().take(MAX_LISTED_STAGED_FILES) {
+        prompt.push_str(&format!("- {file}\n"));
+    }
+    if files.len() > MAX_LISTED_STAGED_FILES {
+        prompt.push_str(&format!(
+            "- ... and {} more\n",
+            files.len() - MAX_LISTED_STAGED_FILES
+        ));
+    }
+    prompt.push_str(&format!(
+        "\nStaged diff{}:\n{}",
+        if diff_truncated { " (truncated)" } else { "" },
+        fence_untrusted("diff", diff.trim_end())
+    ));
+    prompt
+}
+
+/// The model's reply as a commit message: without a surrounding code fence or quotes, with
+/// trailing whitespace and runs of blank lines removed.
+fn clean_commit_message(raw: &str) -> String {
+    let mut text = raw.trim();
+    if let Some(fenced) = text.strip_prefix("```") {
+        let fenced = fenced.split_once('\n').map_or("", |(_, rest)| rest);
+        text = fenced
+            .trim_end()
+            .strip_suffix("```")
+            .unwrap_or(fenced)
+            .trim();
+    }
+    for quote in ['"', '\'', '`'] {
+        if text.len() > 1 && text.starts_with(quote) && text.ends_with(quote) {
+            text = text[1..text.len() - 1].trim();
+        }
+    }
+
+    let mut message = String::new();
+    let mut previous_blank = false;
+    for line in text.lines().map(str::trim_end) {
+        let blank = line.is_empty();
+        if blank && previous_blank {
+            continue;
+        }
+        message.push_str(line);
+        message.push('\n');
+        previous_blank = blank;
+    }
+    message.trim().to_string()
+}
+
+pub async fn generate_commit_message(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: GenerateCommitMessageInput,
+) -> Result<GenerateCommitMessageResult, String> {
+    let (repo_path, diff, files) = read_staged_diff(&input.workspace)?;
+    if files.is_empty() {
+        return Err("Nothing is staged to describe.".to_string());
+    }
+    let workspace = repo_path.to_string_lossy().to_string();
+    let review_provider = ReviewProvider::from_settings()?;
+    review_provider.ensure_allowed_for_workspace(
+        &workspace,
+        is_workspace_trusted(&state, &workspace).await?,
+    )?;
+
+    let (diff, diff_truncated) = truncate_chars(&diff, MAX_COMMIT_MESSAGE_DIFF_CHARS);
+    let instructions = as_non_empty_trimmed(input.instructions.as_deref());
+    let prompt =
+        build_commit_message_prompt(&diff, diff_truncated, &files, instructions.as_deref());
+    let (reply, model) =
+        generate_text_with_review_provider(&app, review_provider, &workspace, &prompt).await?;
+    let message = clean_commit_message(&reply);
+    if message.is_empty() {
+        return Err("The review provider returned an empty commit message.".to_string());
+    }
+
+    Ok(GenerateCommitMessageResult {
+        message,
+        model,
+        diff_truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::clean_commit_message;
+
+    #[test]
+    fn replies_are_stripped_to_the_commit_message() {
+        assert_eq!(
+            clean_commit_message("```text\nFix overflow in parser\n\n\nGuard the index.  \n```"),
+            "Fix overflow in parser\n\nGuard the index."
+        );
+        assert_eq!(
+            clean_commit_message("\"Add retry to uploads\""),
+            "Add retry to uploads"
+        );
+        assert_eq!(clean_commit_message("  Plain message \n"), "Plain message");
+    }
+}
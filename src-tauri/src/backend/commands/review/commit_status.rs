@@ -0,0 +1,105 @@
+use super::super::common::as_non_empty_trimmed;
+use super::super::providers::load_provider_connection_row;
+use super::findings::{FINDING_STATUS_DISMISSED, FINDING_STATUS_FIXED};
+use super::store;
+use super::verdict::{VERDICT_FAIL, VERDICT_PASS};
+use crate::backend::providers::{provider_client, CommitStatusState, ProviderCommitStatus};
+use crate::backend::{
+    AiReviewRun, AppState, PublishReviewCommitStatusInput, PublishReviewCommitStatusResult,
+};
+
+/// Name the review's status is listed under; publishing again replaces the earlier status.
+const REVIEW_STATUS_CONTEXT: &str = "rovex/review";
+
+/// The commit status for `run`: pending while it is queued or running, then the run's
+/// verdict. Runs that ended without a verdict, such as failed or canceled ones, have none.
+fn review_commit_status(
+    run: &AiReviewRun,
+    target_url: Option<String>,
+) -> Result<ProviderCommitStatus, String> {
+    let (state, description) = match (run.status.as_str(), run.verdict.as_deref()) {
+        ("queued" | "running", _) => (
+            CommitStatusState::Pending,
+            "Rovex review is in progress.".to_string(),
+        ),
+        (_, Some(VERDICT_PASS)) => {
+            let open_findings = run
+                .findings
+                .iter()
+                .filter(|finding| {
+                    !matches!(
+                        finding.status.as_deref(),
+                        Some(FINDING_STATUS_DISMISSED | FINDING_STATUS_FIXED)
+                    )
+                })
+                .count();
+            (
+                CommitStatusState::Success,
+                format!("Rovex review passed with {open_findings} open finding(s)."),
+            )
+        }
+        (_, Some(VERDICT_FAIL)) => (
+            CommitStatusState::Failure,
+            format!(
+                "Rovex review failed: {}",
+                run.verdict_reason.as_deref().unwrap_or("blocking findings")
+            ),
+        ),
+        _ => {
+            return Err(format!(
+                "AI review run '{}' has no verdict to publish.",
+                run.run_id
+            ))
+        }
+    };
+    Ok(ProviderCommitStatus {
+        state,
+        context: REVIEW_STATUS_CONTEXT.to_string(),
+        description,
+        target_url,
+    })
+}
+
+/// Posts the verdict of a run as a status on its head commit, with the stored token of the
+/// provider that hosts the repository.
+pub(crate) async fn publish_review_commit_status(
+    state: &AppState,
+    input: PublishReviewCommitStatusInput,
+) -> Result<PublishReviewCommitStatusResult, String> {
+    let run_id = input.run_id.trim();
+    if run_id.is_empty() {
+        return Err("Run id must not be empty.".to_string());
+    }
+    let target_url = as_non_empty_trimmed(input.details_url.as_deref());
+    if target_url
+        .as_deref()
+        .is_some_and(|url| !url.starts_with("https://") && !url.starts_with("http://"))
+    {
+        return Err("Details URL must start with http:// or https://.".to_string());
+    }
+    let run = store::load_ai_review_run_by_id(state, run_id).await?;
+    let sha = run.head.trim().to_string();
+    if sha.is_empty() {
+        return Err(format!("AI review run '{run_id}' has no head commit."));
+    }
+    let status = review_commit_status(&run, target_url)?;
+
+    let connection = load_provider_connection_row(state, input.provider)
+        .await?
+        .ok_or_else(|| format!("{} is not connected.", input.provider.as_str()))?;
+    let client = provider_client(input.provider);
+    let repository = client.parse_repository(&input.repository)?;
+    client
+        .publish_commit_status(&connection.access_token, &repository, &sha, &status)
+        .await?;
+
+    Ok(PublishReviewCommitStatusResult {
+        run_id: run.run_id,
+        provider: input.provider,
+        repository: repository.slug(),
+        sha,
+        state: status.state.as_str().to_string(),
+        context: status.context,
+        description: status.description,
+    })
+}
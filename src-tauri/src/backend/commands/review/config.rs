@@ -1,11 +1,40 @@
-use std::env;
+use tauri::State;
 
 use super::super::common::{
-    current_ai_review_config, resolve_env_file_path, upsert_env_key, DEFAULT_OPENCODE_PROVIDER,
-    OPENAI_API_KEY_ENV, ROVEX_OPENCODE_MODEL_ENV, ROVEX_OPENCODE_PROVIDER_ENV,
-    ROVEX_REVIEW_MODEL_ENV, ROVEX_REVIEW_PROVIDER_ENV,
+    current_ai_review_config, DEFAULT_OPENCODE_PROVIDER, MAX_PARALLEL_CHUNKS_PER_RUN_LIMIT,
+    MAX_PARALLEL_REVIEW_RUNS_LIMIT, MAX_PROVIDER_CHUNKS_LIMIT, OPENAI_API_KEY_ENV,
+    ROVEX_OPENCODE_MODEL_ENV, ROVEX_OPENCODE_PROVIDER_ENV, ROVEX_REVIEW_MAX_PARALLEL_CHUNKS_ENV,
+    ROVEX_REVIEW_MAX_PARALLEL_RUNS_ENV, ROVEX_REVIEW_MAX_PROVIDER_CHUNKS_ENV,
+    ROVEX_REVIEW_MODEL_ENV, ROVEX_REVIEW_PROVIDER_ENV, ROVEX_REVIEW_TRIAGE_MODEL_ENV,
 };
-use crate::backend::{AiReviewConfig, SetAiReviewApiKeyInput, SetAiReviewSettingsInput};
+use super::run_queue::{sync_provider_chunk_slots, sync_review_run_slots};
+use crate::backend::secrets::set_secret;
+use crate::backend::settings::settings_store;
+use crate::backend::{AiReviewConfig, AppState, SetAiReviewApiKeyInput, SetAiReviewSettingsInput};
+
+fn validate_parallel_limit(name: &str, value: Option<usize>, max: usize) -> Result<(), String> {
+    match value {
+        Some(value) if value == 0 || value > max => {
+            Err(format!("{name} must be between 1 and {max}."))
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn save_setting(
+    state: &AppState,
+    persist: bool,
+    key: &str,
+    value: &str,
+) -> Result<(), String> {
+    let settings = settings_store();
+    if persist {
+        let conn = state.connection()?;
+        settings.set(&conn, key, &value).await
+    } else {
+        settings.set_in_memory(key, serde_json::Value::String(value.to_string()))
+    }
+}
 
 pub async fn get_ai_review_config() -> Result<AiReviewConfig, String> {
     Ok(current_ai_review_config())
@@ -19,18 +48,20 @@ pub async fn set_ai_review_api_key(
         return Err("API key must not be empty.".to_string());
     }
 
-    env::set_var(OPENAI_API_KEY_ENV, api_key);
-
-    if input.persist_to_env.unwrap_or(true) {
-        let env_path =
-            resolve_env_file_path().ok_or_else(|| "Unable to resolve .env path.".to_string())?;
-        upsert_env_key(&env_path, OPENAI_API_KEY_ENV, api_key)?;
+    if input.persist.unwrap_or(true) {
+        set_secret(OPENAI_API_KEY_ENV, api_key)?;
+    } else {
+        settings_store().set_in_memory(
+            OPENAI_API_KEY_ENV,
+            serde_json::Value::String(api_key.to_string()),
+        )?;
     }
 
     Ok(current_ai_review_config())
 }
 
 pub async fn set_ai_review_settings(
+    state: State<'_, AppState>,
     input: SetAiReviewSettingsInput,
 ) -> Result<AiReviewConfig, String> {
     let review_provider = match input.review_provider.trim().to_lowercase().as_str() {
@@ -62,22 +93,66 @@ pub async fn set_ai_review_settings(
         .filter(|value| !value.is_empty())
         .map(ToOwned::to_owned);
 
-    env::set_var(ROVEX_REVIEW_PROVIDER_ENV, &review_provider);
-    env::set_var(ROVEX_REVIEW_MODEL_ENV, review_model);
-    env::set_var(ROVEX_OPENCODE_PROVIDER_ENV, opencode_provider);
+    validate_parallel_limit(
+        "Max parallel review runs",
+        input.max_parallel_review_runs,
+        MAX_PARALLEL_REVIEW_RUNS_LIMIT,
+    )?;
+    validate_parallel_limit(
+        "Max parallel chunks per run",
+        input.max_parallel_chunks_per_run,
+        MAX_PARALLEL_CHUNKS_PER_RUN_LIMIT,
+    )?;
+    validate_parallel_limit(
+        "Max chunks per provider",
+        input.max_provider_chunks,
+        MAX_PROVIDER_CHUNKS_LIMIT,
+    )?;
+
+    let persist = input.persist.unwrap_or(true);
+    save_setting(&state, persist, ROVEX_REVIEW_PROVIDER_ENV, &review_provider).await?;
+    save_setting(&state, persist, ROVEX_REVIEW_MODEL_ENV, review_model).await?;
+    save_setting(
+        &state,
+        persist,
+        ROVEX_OPENCODE_PROVIDER_ENV,
+        opencode_provider,
+    )
+    .await?;
     if let Some(model) = &opencode_model {
-        env::set_var(ROVEX_OPENCODE_MODEL_ENV, model);
+        save_setting(&state, persist, ROVEX_OPENCODE_MODEL_ENV, model).await?;
     }
-
-    if input.persist_to_env.unwrap_or(true) {
-        let env_path =
-            resolve_env_file_path().ok_or_else(|| "Unable to resolve .env path.".to_string())?;
-        upsert_env_key(&env_path, ROVEX_REVIEW_PROVIDER_ENV, &review_provider)?;
-        upsert_env_key(&env_path, ROVEX_REVIEW_MODEL_ENV, review_model)?;
-        upsert_env_key(&env_path, ROVEX_OPENCODE_PROVIDER_ENV, opencode_provider)?;
-        if let Some(model) = &opencode_model {
-            upsert_env_key(&env_path, ROVEX_OPENCODE_MODEL_ENV, model)?;
-        }
+    if let Some(model) = input.triage_model.as_deref() {
+        save_setting(&state, persist, ROVEX_REVIEW_TRIAGE_MODEL_ENV, model.trim()).await?;
+    }
+    if let Some(limit) = input.max_parallel_review_runs {
+        save_setting(
+            &state,
+            persist,
+            ROVEX_REVIEW_MAX_PARALLEL_RUNS_ENV,
+            &limit.to_string(),
+        )
+        .await?;
+        sync_review_run_slots();
+    }
+    if let Some(limit) = input.max_parallel_chunks_per_run {
+        save_setting(
+            &state,
+            persist,
+            ROVEX_REVIEW_MAX_PARALLEL_CHUNKS_ENV,
+            &limit.to_string(),
+        )
+        .await?;
+    }
+    if let Some(limit) = input.max_provider_chunks {
+        save_setting(
+            &state,
+            persist,
+            ROVEX_REVIEW_MAX_PROVIDER_CHUNKS_ENV,
+            &limit.to_string(),
+        )
+        .await?;
+        sync_provider_chunk_slots();
     }
 
     Ok(current_ai_review_config())
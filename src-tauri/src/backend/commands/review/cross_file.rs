@@ -0,0 +1,311 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::super::common::truncate_chars;
+use super::super::workspace_files::WorkspaceRoot;
+use super::diff_chunks::{json_object_candidates, ChunkFindingPayload, DiffChunk};
+use super::prompt_safety::{fence_untrusted, UNTRUSTED_CONTENT_RULE};
+use crate::backend::code_intel::{find_code_intel_symbols, CodeIntelSymbolNode};
+
+/// Changed symbols looked up in the code-intel graph per run.
+const MAX_CROSS_FILE_SYMBOLS: usize = 12;
+/// Definitions and references pulled in for each changed symbol.
+const MAX_LOCATIONS_PER_SYMBOL: usize = 6;
+/// Lines shown around each related location; ranges differ on whether lines start at 0, so the
+/// window starts a little before the reported line.
+const LOCATION_LINES_BEFORE: i64 = 2;
+const LOCATION_LINES_AFTER: i64 = 10;
+const CROSS_FILE_PATCH_CHARS: usize = 4_000;
+/// Related code stops being added once the prompt reaches this size.
+const MAX_CROSS_FILE_PROMPT_CHARS: usize = 60_000;
+
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "fn",
+    "func",
+    "function",
+    "def",
+    "class",
+    "struct",
+    "enum",
+    "trait",
+    "interface",
+    "type",
+];
+/// Declarations that only count when they bind a function, as in `const parse = (input) => ...`.
+const BINDING_KEYWORDS: &[&str] = &["const", "let", "var"];
+/// Names too common to say anything about a caller in another file.
+const IGNORED_SYMBOLS: &[&str] = &["new", "main", "default", "init", "test", "self"];
+
+pub(crate) const CROSS_FILE_SYSTEM_PROMPT: &str = "You review how changed code interacts with the rest of a repository. Report only bugs that span files and return strict JSON only.";
+
+/// A symbol declared on a changed line, with the changed file that declares it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ChangedSymbol {
+    pub(crate) name: String,
+    pub(crate) file_path: String,
+}
+
+/// Code elsewhere in the repository that defines or uses a changed symbol.
+#[derive(Debug, Clone)]
+pub(crate) struct RelatedLocation {
+    pub(crate) node: CodeIntelSymbolNode,
+    pub(crate) first_line: i64,
+    pub(crate) snippet: String,
+}
+
+fn identifiers(line: &str) -> Vec<&str> {
+    line.split(|character: char| !(character.is_alphanumeric() || character == '_'))
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+fn is_symbol_name(token: &str) -> bool {
+    token.len() >= 3
+        && !token.starts_with("__")
+        && token
+            .chars()
+            .next()
+            .is_some_and(|character| character.is_alphabetic() || character == '_')
+        && !IGNORED_SYMBOLS.contains(&token)
+}
+
+/// Functions and types declared on the added or removed lines of `chunks`, at most
+/// `MAX_CROSS_FILE_SYMBOLS` and each named once.
+pub(crate) fn changed_symbols(chunks: &[DiffChunk]) -> Vec<ChangedSymbol> {
+    let mut symbols: Vec<ChangedSymbol> = Vec::new();
+    for chunk in chunks {
+        for line in chunk.patch.lines() {
+            if line.starts_with("+++") || line.starts_with("---") {
+                continue;
+            }
+            let Some(content) = line.strip_prefix('+').or_else(|| line.strip_prefix('-')) else {
+                continue;
+            };
+            let content = content.trim_start();
+            if ["//", "#", "*", "/*"]
+                .iter()
+                .any(|prefix| content.starts_with(prefix))
+            {
+                continue;
+            }
+            let binds_function = content.contains("=>") || content.contains("function");
+            let tokens = identifiers(content);
+            for pair in tokens.windows(2) {
+                let declares = DECLARATION_KEYWORDS.contains(&pair[0])
+                    || (binds_function && BINDING_KEYWORDS.contains(&pair[0]));
+                if !declares || !is_symbol_name(pair[1]) {
+                    continue;
+                }
+                if symbols.iter().any(|symbol| symbol.name == pair[1]) {
+                    continue;
+                }
+                symbols.push(ChangedSymbol {
+                    name: pair[1].to_string(),
+                    file_path: chunk.file_path.clone(),
+                });
+                if symbols.len() >= MAX_CROSS_FILE_SYMBOLS {
+                    return symbols;
+                }
+            }
+        }
+    }
+    symbols
+}
+
+/// Numbered lines of `file_path` around `line`, or around the first mention of `symbol` when
+/// the graph has no range. Paths that leave the workspace are not read.
+fn location_snippet(
+    workspace: &WorkspaceRoot,
+    file_path: &str,
+    line: Option<i64>,
+    symbol: &str,
+) -> Option<(i64, String)> {
+    let (path, _) = workspace.resolve(file_path).ok()?;
+    let content = fs::read_to_string(path).ok()?;
+    let lines = content.lines().collect::<Vec<_>>();
+    let line = line.filter(|line| *line >= 1).or_else(|| {
+        lines
+            .iter()
+            .position(|text| text.contains(symbol))
+            .map(|index| index as i64 + 1)
+    })?;
+    let first = (line - LOCATION_LINES_BEFORE).max(1);
+    let last = (line + LOCATION_LINES_AFTER).min(lines.len() as i64);
+    if first > last {
+        return None;
+    }
+    let snippet = (first..=last)
+        .map(|number| format!("{number:>5} | {}", lines[(number - 1) as usize]))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some((first, snippet))
+}
+
+/// Definitions and references of `symbols` outside the file that changed them, read from the
+/// code-intel graph of `workspace`. Empty when the workspace has not been synced.
+pub(crate) async fn load_related_locations(
+    workspace: &str,
+    symbols: &[ChangedSymbol],
+) -> Result<Vec<RelatedLocation>, String> {
+    let names = symbols
+        .iter()
+        .map(|symbol| symbol.name.clone())
+        .collect::<Vec<_>>();
+    // One extra per symbol, since the changed declaration itself is usually among them.
+    let nodes =
+        find_code_intel_symbols(Path::new(workspace), &names, MAX_LOCATIONS_PER_SYMBOL + 1).await?;
+    let workspace = WorkspaceRoot::new(workspace)?;
+    let mut locations = Vec::new();
+    for symbol in symbols {
+        let related = nodes
+            .iter()
+            .filter(|node| node.symbol_name == symbol.name && node.file_path != symbol.file_path)
+            .take(MAX_LOCATIONS_PER_SYMBOL);
+        for node in related {
+            if let Some((first_line, snippet)) =
+                location_snippet(&workspace, &node.file_path, node.start_line, &symbol.name)
+            {
+                locations.push(RelatedLocation {
+                    node: node.clone(),
+                    first_line,
+                    snippet,
+                });
+            }
+        }
+    }
+    Ok(locations)
+}
+
+/// Asks for bugs between the changed symbols and their related code: callers left behind by
+/// a changed signature, return value or error behavior, and changed code that now misuses
+/// unchanged code elsewhere.
+pub(crate) fn build_cross_file_prompt(
+    reviewer_goal: &str,
+    chunks: &[DiffChunk],
+    symbols: &[ChangedSymbol],
+    locations: &[RelatedLocation],
+) -> String {
+    let mut prompt = format!(
+        "Reviewer goal: {reviewer_goal}\n\nEach changed file was already reviewed on its own. Look only for bugs that span files: callers or implementations elsewhere that no longer match a changed signature, return value, error behavior or invariant, and changed code that misuses the related code shown below. Do not repeat problems visible within a single file.\n\n{UNTRUSTED_CONTENT_RULE}\n\nReturn JSON only:\n{{\"findings\": [{{\"filePath\": \"path of a changed file\", \"side\": \"additions|deletions\", \"lineNumber\": 12, \"title\": \"bug title\", \"body\": \"what breaks, naming the other file and line, and how to fix it\", \"severity\": \"critical|high|medium|low\", \"category\": \"correctness|security|performance|concurrency|error-handling|testing\", \"confidence\": 0.0}}]}}\nfilePath and lineNumber must point at a changed line in one of the diffs below. Return an empty findings array when nothing spans files.\n\n## Changed files\n"
+    );
+    for chunk in chunks {
+        let names = symbols
+            .iter()
+            .filter(|symbol| symbol.file_path == chunk.file_path)
+            .map(|symbol| format!("`{}`", symbol.name))
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            continue;
+        }
+        let (patch, truncated) = truncate_chars(&chunk.patch, CROSS_FILE_PATCH_CHARS);
+        prompt.push_str(&format!(
+            "\n### {}\nChanged symbols: {}\n{}\n",
+            chunk.file_path,
+            names.join(", "),
+            fence_untrusted("diff", patch.trim_end())
+        ));
+        if truncated {
+            prompt.push_str("(patch truncated)\n");
+        }
+    }
+
+    prompt.push_str("\n## Related code\n");
+    for location in locations {
+        let section = format!(
+            "\n### `{}` ({}) in {} from line {}\n{}\n",
+            location.node.symbol_name,
+            location.node.node_kind,
+            location.node.file_path,
+            location.first_line,
+            fence_untrusted("code", &location.snippet)
+        );
+        if prompt.chars().count() + section.chars().count() > MAX_CROSS_FILE_PROMPT_CHARS {
+            prompt.push_str("\n(more related code left out)\n");
+            break;
+        }
+        prompt.push_str(&section);
+    }
+    prompt
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CrossFileFindingPayload {
+    pub(crate) file_path: Option<String>,
+    #[serde(flatten)]
+    pub(crate) finding: ChunkFindingPayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrossFilePayload {
+    #[serde(default)]
+    findings: Vec<CrossFileFindingPayload>,
+}
+
+/// Findings of a cross-file reply, or none when it cannot be parsed.
+pub(crate) fn parse_cross_file_reply(raw: &str) -> Vec<CrossFileFindingPayload> {
+    json_object_candidates(raw)
+        .into_iter()
+        .find_map(|candidate| serde_json::from_str::<CrossFilePayload>(candidate).ok())
+        .map(|payload| payload.findings)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::diff_chunks::DiffChunk;
+    use super::{changed_symbols, parse_cross_file_reply, ChangedSymbol};
+
+    fn chunk(file_path: &str, patch: &str) -> DiffChunk {
+        DiffChunk {
+            id: format!("{file_path}#file-1"),
+            file_path: file_path.to_string(),
+            previous_path: None,
+            chunk_index: 1,
+            hunk_header: "@@ -1,3 +1,3 @@".to_string(),
+            patch: patch.into(),
+            addition_lines: vec![1],
+            deletion_lines: vec![1],
+            language: None,
+        }
+    }
+
+    #[test]
+    fn declarations_on_changed_lines_become_symbols() {
+        let chunks = [
+            chunk(
+                "src/config.rs",
+                "@@ -1,3 +1,3 @@\n-pub fn parse_config(input: &str) -> Config {\n+pub fn parse_config(input: &str, strict: bool) -> Result<Config, Error> {\n     let lines = input.lines();\n+    // the type of each line is checked\n+impl Default for Config {\n",
+            ),
+            chunk(
+                "src/app.ts",
+                "@@ -4 +4 @@\n+export const loadUser = async (id: string) => fetchUser(id);\n+const retries = 3;\n+export class UserStore {\n+  fn new() {}\n",
+            ),
+        ];
+        let names = changed_symbols(&chunks)
+            .into_iter()
+            .map(|ChangedSymbol { name, file_path }| format!("{file_path}:{name}"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "src/config.rs:parse_config",
+                "src/app.ts:loadUser",
+                "src/app.ts:UserStore"
+            ]
+        );
+    }
+
+    #[test]
+    fn cross_file_findings_keep_their_file_path() {
+        let findings = parse_cross_file_reply(
+            "```json\n{\"findings\": [{\"filePath\": \"src/config.rs\", \"side\": \"additions\", \"lineNumber\": 1, \"title\": \"Callers pass one argument\", \"severity\": \"high\"}]}\n```",
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].file_path.as_deref(), Some("src/config.rs"));
+        assert_eq!(findings[0].finding.line_number, Some(1));
+        assert!(parse_cross_file_reply("No cross-file issues.").is_empty());
+    }
+}
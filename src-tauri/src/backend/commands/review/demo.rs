@@ -0,0 +1,522 @@
+//! A sample workspace with a finished review, so the review UI can be explored before any
+//! provider or API key is set up. The review is replayed from recorded model replies through
+//! the same finding screening and storage as a live run.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tauri::{AppHandle, State};
+
+use super::super::common::format_path;
+use super::super::threads::{create_thread, persist_thread_message};
+use super::super::workspace_git::{resolve_repository_root, run_git};
+use super::chunk_outcome::ChunkOutcome;
+use super::diff_chunks::{parse_chunk_review_payload, parse_workspace_diff_file_chunks, DiffChunk};
+use super::executor::{screen_finding, FindingFilters, ScreenedFinding};
+use super::follow_up_diff::store_thread_diff_chunks;
+use super::follow_up_suggestions::parse_follow_up_suggestions;
+use super::run_queue::next_review_run_id;
+use super::{emit_and_persist_ai_review_progress, store, ReviewHost};
+use crate::backend::paths::long_path;
+use crate::backend::{
+    AiReviewChunk, AiReviewFinding, AiReviewProgressEvent, AppState, BootstrapDemoWorkspaceInput,
+    BootstrapDemoWorkspaceResult, CreateThreadInput, GenerateAiReviewResult, MessageRole,
+    StartAiReviewRunInput,
+};
+
+const DEMO_REPOSITORY_URL: &str = "https://github.com/octocat/Hello-World.git";
+const DEMO_DIRECTORY_NAME: &str = "rovex-demo";
+const DEMO_BRANCH: &str = "rovex-demo";
+const DEMO_THREAD_TITLE: &str = "Demo review: shopping cart and sessions";
+const DEMO_THREAD_KEY: &str = "rovex-demo-thread";
+/// Shown as the run's model so a replayed review is never mistaken for a live one.
+const DEMO_MODEL: &str = "demo-replay";
+const DEMO_REVIEWER_GOAL: &str =
+    "Review the changed files and report real bugs with actionable fixes.";
+const DEMO_COMMIT_MESSAGE: &str = "Add cart totals and session login";
+
+/// Files the demo branch adds on top of the sample repository.
+const DEMO_FILES: &[(&str, &str)] = &[
+    (
+        "src/cart.js",
+        r#"export function cartTotal(items, discountCode) {
+  let total = 0;
+  for (let i = 0; i <= items.length; i++) {
+    total += items[i].price * items[i].quantity;
+  }
+  if (discountCode == "HALF") {
+    total = total / 2;
+  }
+  return Math.round(total);
+}
+
+export function applyCoupon(cart, coupon) {
+  cart.coupons.push(coupon);
+  return cartTotal(cart.items, coupon.code);
+}
+"#,
+    ),
+    (
+        "src/session.js",
+        r#"const sessions = {};
+
+export function login(user, password) {
+  if (password == user.password) {
+    const token = Math.random().toString(36).slice(2);
+    sessions[token] = user;
+    return token;
+  }
+  return null;
+}
+
+export function currentUser(token) {
+  return sessions[token];
+}
+"#,
+    ),
+];
+
+/// Recorded chunk review replies, by file, in the JSON shape chunk reviews return.
+const DEMO_CHUNK_REPLIES: &[(&str, &str)] = &[
+    (
+        "src/cart.js",
+        r#"{
+  "summary": "Adds cart total and coupon helpers. The loop reads past the end of the items array and totals are rounded to whole currency units.",
+  "findings": [
+    {
+      "title": "Loop reads one item past the end of the cart",
+      "body": "The loop condition uses `<=`, so the last iteration reads `items[items.length]`, which is undefined, and accessing `.price` on it throws a TypeError for every non-empty cart. Use `<` so the loop stops at the last item.",
+      "severity": "high",
+      "category": "correctness",
+      "confidence": 0.96,
+      "side": "new",
+      "lineNumber": 3,
+      "suggestedPatch": "@@ -3 +3 @@\n-  for (let i = 0; i <= items.length; i++) {\n+  for (let i = 0; i < items.length; i++) {\n"
+    },
+    {
+      "title": "Rounding drops cents from every total",
+      "body": "`Math.round` rounds to whole currency units, so a cart worth 19.99 is charged 20 and a half-price discount on 3.99 charges 2. Keep totals in cents as integers, or round to two decimals only when formatting for display.",
+      "severity": "medium",
+      "category": "correctness",
+      "confidence": 0.82,
+      "side": "new",
+      "lineNumber": 9
+    },
+    {
+      "title": "Applying a coupon twice stacks it",
+      "body": "`applyCoupon` pushes the coupon without checking whether the cart already holds it, so calling it again, for example on a double click, stores duplicates. Check for an existing coupon with the same code before pushing.",
+      "severity": "low",
+      "category": "correctness",
+      "confidence": 0.64,
+      "side": "new",
+      "lineNumber": 13
+    }
+  ]
+}"#,
+    ),
+    (
+        "src/session.js",
+        r#"{
+  "summary": "Adds an in-memory login with session tokens. Passwords are compared in plain text and tokens are predictable.",
+  "findings": [
+    {
+      "title": "Passwords are stored and compared in plain text",
+      "body": "The login compares the submitted password with `user.password` directly, which means passwords are kept unhashed and anyone who can read user records can log in as any user. Store a salted hash such as bcrypt or argon2 and verify with its constant-time compare.",
+      "severity": "critical",
+      "category": "security",
+      "confidence": 0.93,
+      "side": "new",
+      "lineNumber": 4
+    },
+    {
+      "title": "Session tokens come from Math.random",
+      "body": "`Math.random` is not a cryptographically secure generator and its output can be predicted from earlier values, so an attacker could guess a live session token. Generate tokens with `crypto.randomUUID()` or `crypto.getRandomValues`.",
+      "severity": "high",
+      "category": "security",
+      "confidence": 0.9,
+      "side": "new",
+      "lineNumber": 5
+    },
+    {
+      "title": "Sessions never expire",
+      "body": "Tokens stay in the `sessions` map until the process restarts: there is no expiry or logout, so a leaked token works indefinitely and the map grows with every login. Store an expiry time with each session and drop expired ones on lookup.",
+      "severity": "medium",
+      "category": "security",
+      "confidence": 0.71,
+      "side": "new",
+      "lineNumber": 6
+    }
+  ]
+}"#,
+    ),
+];
+
+const DEMO_DESCRIPTION: &str = "## Overview\nThis change adds two modules: `src/cart.js` computes cart totals with an optional discount code, and `src/session.js` adds an in-memory login that hands out session tokens.\n\n## Top risks\n- Every non-empty cart throws, because the total loop reads one item past the end.\n- Login stores and compares passwords in plain text, and its session tokens are guessable.\n\n## Notes\nThis is a demo review replayed from recorded replies, so no model was called. Configure a review provider in Settings to review your own changes.";
+
+/// The demo's commits: the branch the sample repository was cloned at, the commit the demo
+/// branch starts from, and the demo branch head.
+struct DemoCheckout {
+    base_ref: String,
+    merge_base: String,
+    head: String,
+}
+
+/// One replay per demo commit, so a re-cloned demo is reviewed again in the same thread.
+fn demo_run_key(checkout: &DemoCheckout) -> String {
+    format!("rovex-demo-run:{}", checkout.head)
+}
+
+fn clone_demo_repository(destination: &Path) -> Result<(), String> {
+    let output = Command::new("git")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .args(["clone", "--depth", "1", DEMO_REPOSITORY_URL])
+        .arg(destination)
+        .output()
+        .map_err(|error| format!("Failed to run git clone: {error}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to clone the demo repository {DEMO_REPOSITORY_URL}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Commits [`DEMO_FILES`] on a new [`DEMO_BRANCH`] and leaves it checked out.
+fn commit_demo_branch(repo_path: &Path) -> Result<(), String> {
+    run_git(repo_path, &["checkout", "-b", DEMO_BRANCH], "checkout -b")?;
+    for (path, contents) in DEMO_FILES {
+        let file_path = repo_path.join(path);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(long_path(parent))
+                .map_err(|error| format!("Failed to create {}: {error}", format_path(parent)))?;
+        }
+        fs::write(long_path(&file_path), contents)
+            .map_err(|error| format!("Failed to write {}: {error}", format_path(&file_path)))?;
+    }
+    run_git(repo_path, &["add", "--all"], "add")?;
+    run_git(
+        repo_path,
+        &[
+            "-c",
+            "user.name=Rovex Demo",
+            "-c",
+            "user.email=demo@rovex.invalid",
+            "commit",
+            "--quiet",
+            "--no-verify",
+            "-m",
+            DEMO_COMMIT_MESSAGE,
+        ],
+        "commit",
+    )?;
+    Ok(())
+}
+
+fn git_stdout(repo_path: &Path, args: &[&str], context: &str) -> Result<String, String> {
+    let output = run_git(repo_path, args, context)?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn resolve_demo_checkout(repo_path: &Path) -> Result<DemoCheckout, String> {
+    let head = git_stdout(
+        repo_path,
+        &["rev-parse", "--verify", &format!("refs/heads/{DEMO_BRANCH}")],
+        "rev-parse",
+    )
+    .map_err(|_| {
+        format!(
+            "{} exists but is not a Rovex demo checkout. Move it aside or choose another destinationRoot.",
+            format_path(repo_path)
+        )
+    })?;
+    let base_ref = git_stdout(
+        repo_path,
+        &["rev-parse", "--abbrev-ref", "origin/HEAD"],
+        "rev-parse origin/HEAD",
+    )?;
+    let merge_base = git_stdout(repo_path, &["merge-base", &base_ref, &head], "merge-base")?;
+    Ok(DemoCheckout {
+        base_ref,
+        merge_base,
+        head,
+    })
+}
+
+/// Clones the sample repository and commits the demo branch, or reuses an earlier demo
+/// checkout at `destination`. Returns whether it cloned.
+fn prepare_demo_checkout(destination: &Path) -> Result<bool, String> {
+    if long_path(destination).exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(long_path(parent))
+            .map_err(|error| format!("Failed to create {}: {error}", format_path(parent)))?;
+    }
+    clone_demo_repository(destination)?;
+    if let Err(error) = commit_demo_branch(destination) {
+        let _ = fs::remove_dir_all(long_path(destination));
+        return Err(error);
+    }
+    Ok(true)
+}
+
+/// Replays the recorded reply for `chunk`; files without one are reviewed with no findings.
+fn replay_chunk_review(chunk: &DiffChunk) -> (AiReviewChunk, Vec<AiReviewFinding>) {
+    let raw = DEMO_CHUNK_REPLIES
+        .iter()
+        .find(|(path, _)| *path == chunk.file_path)
+        .map_or("{}", |(_, reply)| reply);
+    let payload = parse_chunk_review_payload(raw);
+    // Recorded findings are shown whatever the user's own profile and thresholds are.
+    let filters = FindingFilters {
+        min_finding_body_chars: 0,
+        min_severity: None,
+        min_confidence: None,
+        prior_findings: &[],
+    };
+    let findings = payload
+        .findings
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, payload_finding)| {
+            match screen_finding(chunk, payload_finding, &(index + 1).to_string(), &filters) {
+                ScreenedFinding::Kept(finding) => Some(finding),
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    let review = AiReviewChunk {
+        id: chunk.id.clone(),
+        file_path: chunk.file_path.clone(),
+        chunk_index: chunk.chunk_index,
+        hunk_header: chunk.hunk_header.clone(),
+        summary: payload
+            .summary
+            .unwrap_or_else(|| "No issues found in this file.".to_string()),
+        findings: findings.clone(),
+        token_usage: None,
+        outcome: Some(ChunkOutcome::Reviewed.as_str().to_string()),
+        language: chunk.language.map(str::to_string),
+    };
+    (review, findings)
+}
+
+async fn replay_demo_review(
+    app: &AppHandle,
+    state: &AppState,
+    thread_id: i64,
+    repo_path: &Path,
+    checkout: &DemoCheckout,
+) -> Result<String, String> {
+    let workspace = format_path(repo_path);
+    let diff = git_stdout(
+        repo_path,
+        &[
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            &checkout.merge_base,
+            &checkout.head,
+        ],
+        "diff",
+    )?;
+    let reviewable = parse_workspace_diff_file_chunks(repo_path, &diff)?;
+    let diff_chunks = reviewable.chunks;
+    let insertions = diff_chunks
+        .iter()
+        .map(|chunk| chunk.addition_lines.len())
+        .sum::<usize>();
+    let deletions = diff_chunks
+        .iter()
+        .map(|chunk| chunk.deletion_lines.len())
+        .sum::<usize>();
+    let input = StartAiReviewRunInput {
+        thread_id,
+        workspace: workspace.clone(),
+        base_ref: checkout.base_ref.clone(),
+        merge_base: checkout.merge_base.clone(),
+        head: checkout.head.clone(),
+        files_changed: i64::try_from(diff_chunks.len()).unwrap_or(i64::MAX),
+        insertions: i64::try_from(insertions).unwrap_or(i64::MAX),
+        deletions: i64::try_from(deletions).unwrap_or(i64::MAX),
+        diff,
+        prompt: None,
+        scope_label: Some(format!("{} → {DEMO_BRANCH}", checkout.base_ref)),
+        max_parallel_chunks: None,
+        review_mode: None,
+        include_paths: None,
+        exclude_paths: None,
+        diff_target: None,
+        idempotency_key: Some(demo_run_key(checkout)),
+        profile_id: None,
+        min_confidence: None,
+    };
+
+    let run_id = next_review_run_id();
+    store::insert_ai_review_run(
+        state,
+        &run_id,
+        &input,
+        DEMO_REVIEWER_GOAL,
+        diff_chunks.len(),
+        &reviewable.skipped,
+        None,
+    )
+    .await?;
+    store::set_ai_review_run_status(state, &run_id, "running", None, true, false, false).await?;
+    persist_thread_message(
+        state,
+        thread_id,
+        MessageRole::User,
+        "AI review request for current diff.",
+    )
+    .await?;
+    store_thread_diff_chunks(state, thread_id, Some(&run_id), &diff_chunks).await?;
+
+    let (chunks, findings): (Vec<_>, Vec<_>) = diff_chunks.iter().map(replay_chunk_review).unzip();
+    let findings = findings.into_iter().flatten().collect::<Vec<_>>();
+    persist_thread_message(state, thread_id, MessageRole::Assistant, DEMO_DESCRIPTION).await?;
+    emit_and_persist_ai_review_progress(
+        &ReviewHost::App(app.clone()),
+        state,
+        &run_id,
+        AiReviewProgressEvent {
+            run_id: Some(run_id.clone()),
+            thread_id,
+            status: "completed".to_string(),
+            message: format!(
+                "File review complete: {} file(s), {} finding(s), 0 failed file(s).",
+                chunks.len(),
+                findings.len()
+            ),
+            total_chunks: chunks.len(),
+            completed_chunks: chunks.len(),
+            chunk_id: None,
+            file_path: None,
+            chunk_index: None,
+            finding_count: Some(findings.len()),
+            chunk: None,
+            finding: None,
+            eta_ms: None,
+        },
+    )
+    .await;
+
+    let diff_chars = input.diff.chars().count();
+    let result = GenerateAiReviewResult {
+        thread_id,
+        workspace,
+        base_ref: input.base_ref,
+        merge_base: input.merge_base,
+        head: input.head,
+        files_changed: input.files_changed,
+        insertions: input.insertions,
+        deletions: input.deletions,
+        model: DEMO_MODEL.to_string(),
+        review: DEMO_DESCRIPTION.to_string(),
+        diff_chars_used: diff_chars,
+        diff_chars_total: diff_chars,
+        diff_truncated: false,
+        suggested_follow_ups: parse_follow_up_suggestions(None, &findings),
+        chunks,
+        skipped_chunks: reviewable.skipped,
+        findings,
+        suppressed_findings: Vec::new(),
+        min_confidence: None,
+        resource_usage: None,
+        token_usage: None,
+    };
+    store::finalize_ai_review_run(state, &run_id, &result, "completed", None).await?;
+    Ok(run_id)
+}
+
+pub async fn bootstrap_demo_workspace(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: BootstrapDemoWorkspaceInput,
+) -> Result<BootstrapDemoWorkspaceResult, String> {
+    let destination: PathBuf =
+        resolve_repository_root(input.destination_root.as_deref())?.join(DEMO_DIRECTORY_NAME);
+    let cloned = prepare_demo_checkout(&destination)?;
+    let checkout = resolve_demo_checkout(&destination)?;
+    let workspace = format_path(&destination);
+
+    let thread = create_thread(
+        state.clone(),
+        CreateThreadInput {
+            title: DEMO_THREAD_TITLE.to_string(),
+            workspace: Some(workspace.clone()),
+            idempotency_key: Some(DEMO_THREAD_KEY.to_string()),
+        },
+    )
+    .await?;
+    let run = match store::load_ai_review_run_by_idempotency_key(
+        &state,
+        thread.id,
+        &demo_run_key(&checkout),
+    )
+    .await?
+    {
+        Some(run) => run,
+        None => {
+            let run_id =
+                replay_demo_review(&app, &state, thread.id, &destination, &checkout).await?;
+            store::load_ai_review_run_by_id(&state, &run_id).await?
+        }
+    };
+
+    Ok(BootstrapDemoWorkspaceResult {
+        workspace,
+        cloned,
+        thread,
+        run,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::diff_chunks::parse_diff_file_chunks;
+    use super::{replay_chunk_review, DEMO_CHUNK_REPLIES, DEMO_FILES};
+
+    #[test]
+    fn recorded_findings_land_on_the_demo_files() {
+        let diff = DEMO_FILES
+            .iter()
+            .map(|(path, contents)| {
+                let lines = contents.lines().collect::<Vec<_>>();
+                format!(
+                    "diff --git a/{path} b/{path}\nnew file mode 100644\n--- /dev/null\n+++ b/{path}\n@@ -0,0 +1,{} @@\n{}\n",
+                    lines.len(),
+                    lines
+                        .iter()
+                        .map(|line| format!("+{line}"))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                )
+            })
+            .collect::<String>();
+        let chunks = parse_diff_file_chunks(&diff);
+        assert_eq!(chunks.len(), DEMO_FILES.len());
+
+        for chunk in &chunks {
+            let (review, findings) = replay_chunk_review(chunk);
+            let (_, reply) = DEMO_CHUNK_REPLIES
+                .iter()
+                .find(|(path, _)| *path == chunk.file_path)
+                .expect("every demo file has a recorded reply");
+            assert_eq!(
+                findings.len(),
+                reply.matches("\"lineNumber\"").count(),
+                "a recorded finding in {} was dropped",
+                chunk.file_path
+            );
+            assert_eq!(review.findings.len(), findings.len());
+            assert!(findings.iter().all(|finding| finding.fingerprint.is_some()));
+        }
+    }
+}
@@ -1,12 +1,22 @@
-use std::{collections::BTreeSet, fs, path::Path};
+use std::{collections::BTreeSet, fs, path::Path, sync::Arc};
 
 use serde::Deserialize;
 
 use super::super::common::{
-    snippet, truncate_chars, MAX_CHUNK_FILE_CONTEXT_CHARS, MAX_CHUNK_FILE_CONTEXT_WINDOWS,
+    current_review_max_file_chars, snippet, truncate_chars, MAX_CHUNK_FILE_CONTEXT_CHARS,
+    MAX_CHUNK_FILE_CONTEXT_WINDOWS,
+};
+use super::super::name_sort::compare_paths;
+use super::super::path_filter::PathFilter;
+use super::languages::{detect_language, language_name};
+use super::prompt_safety::{fence_untrusted, PROMPT_INJECTION_CATEGORY, UNTRUSTED_CONTENT_RULE};
+use crate::backend::{
+    AiReviewChunk, AiReviewFinding, AiReviewSkipReason, AiReviewSkippedChunk, DiffFile,
+    DiffFileStatus, DiffHunk,
 };
-use crate::backend::{AiReviewChunk, AiReviewFinding};
 
+/// A reviewable part of a diff. The patch is shared, so chunks can be cloned into prompts,
+/// progress events and review workers without copying it.
 #[derive(Debug, Clone)]
 pub(crate) struct DiffChunk {
     pub(crate) id: String,
@@ -14,9 +24,11 @@ pub(crate) struct DiffChunk {
     pub(crate) previous_path: Option<String>,
     pub(crate) chunk_index: usize,
     pub(crate) hunk_header: String,
-    pub(crate) patch: String,
+    pub(crate) patch: Arc<str>,
     pub(crate) addition_lines: Vec<i64>,
     pub(crate) deletion_lines: Vec<i64>,
+    /// Language id from [`detect_language`], the same for every chunk of a file.
+    pub(crate) language: Option<&'static str>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -25,10 +37,12 @@ pub(crate) struct ChunkFindingPayload {
     pub(crate) title: Option<String>,
     pub(crate) body: Option<String>,
     pub(crate) severity: Option<String>,
+    pub(crate) category: Option<String>,
     pub(crate) confidence: Option<f64>,
     pub(crate) side: Option<String>,
     pub(crate) line_number: Option<i64>,
     pub(crate) line: Option<i64>,
+    pub(crate) suggested_patch: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -50,14 +64,37 @@ fn normalize_patch_path(value: &str) -> Option<String> {
     Some(without_prefix.to_string())
 }
 
-fn parse_hunk_line_start(spec: &str, prefix: char) -> Option<i64> {
+/// One side of a hunk header, such as `-12,3`: the start line and, when it parses, the line
+/// count, which is one when omitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HunkRange {
+    start: i64,
+    count: Option<i64>,
+}
+
+impl HunkRange {
+    /// Stands in for a header that does not parse: numbering starts at line one and every
+    /// following line is trusted.
+    const UNKNOWN: Self = Self {
+        start: 1,
+        count: None,
+    };
+}
+
+fn parse_hunk_range(spec: &str, prefix: char) -> Option<HunkRange> {
     let trimmed = spec.trim();
     let rest = trimmed.strip_prefix(prefix)?;
-    let (start, _) = rest.split_once(',').unwrap_or((rest, ""));
-    start.trim().parse::<i64>().ok()
+    let (start, count) = match rest.split_once(',') {
+        Some((start, count)) => (start, count.trim().parse::<i64>().ok()),
+        None => (rest, Some(1)),
+    };
+    Some(HunkRange {
+        start: start.trim().parse::<i64>().ok()?,
+        count: count.filter(|count| *count >= 0),
+    })
 }
 
-fn parse_hunk_positions(header: &str) -> Option<(i64, i64)> {
+fn parse_hunk_ranges(header: &str) -> Option<(HunkRange, HunkRange)> {
     if !header.starts_with("@@") {
         return None;
     }
@@ -66,34 +103,68 @@ fn parse_hunk_positions(header: &str) -> Option<(i64, i64)> {
     if marker != "@@" {
         return None;
     }
-    let old_spec = parts.next()?;
-    let new_spec = parts.next()?;
-    let old_start = parse_hunk_line_start(old_spec, '-')?;
-    let new_start = parse_hunk_line_start(new_spec, '+')?;
-    Some((old_start, new_start))
+    let old_range = parse_hunk_range(parts.next()?, '-')?;
+    let new_range = parse_hunk_range(parts.next()?, '+')?;
+    Some((old_range, new_range))
+}
+
+pub(crate) fn parse_hunk_positions(header: &str) -> Option<(i64, i64)> {
+    parse_hunk_ranges(header).map(|(old_range, new_range)| (old_range.start, new_range.start))
+}
+
+/// Patch text of `lines`, each ending in a newline, built in one allocation.
+fn patch_text(lines: &[&str]) -> Arc<str> {
+    let mut patch = String::with_capacity(lines.iter().map(|line| line.len() + 1).sum());
+    for line in lines {
+        patch.push_str(line);
+        patch.push('\n');
+    }
+    patch.into()
 }
 
 pub(crate) fn parse_diff_chunks(diff: &str) -> Vec<DiffChunk> {
+    // Lines borrow from `diff` until a hunk is finalized into its patch.
     #[derive(Default)]
-    struct FileState {
+    struct FileState<'a> {
         file_path: Option<String>,
         previous_path: Option<String>,
-        headers: Vec<String>,
+        headers: Vec<&'a str>,
         chunk_count: usize,
     }
-    struct HunkState {
-        header: String,
-        lines: Vec<String>,
+    struct HunkState<'a> {
+        header: &'a str,
+        lines: Vec<&'a str>,
         old_line: i64,
         new_line: i64,
+        /// Lines of each side the header says are still to come, when it gave a count.
+        old_remaining: Option<i64>,
+        new_remaining: Option<i64>,
         addition_lines: BTreeSet<i64>,
         deletion_lines: BTreeSet<i64>,
     }
 
+    impl HunkState<'_> {
+        fn is_complete(&self) -> bool {
+            self.old_remaining == Some(0) && self.new_remaining == Some(0)
+        }
+    }
+
+    /// Whether a side can take another line. Lines past a known count are malformed and get
+    /// no line number.
+    fn has_room(remaining: Option<i64>) -> bool {
+        remaining != Some(0)
+    }
+
+    fn take_line(remaining: &mut Option<i64>) {
+        if let Some(remaining) = remaining {
+            *remaining = remaining.saturating_sub(1);
+        }
+    }
+
     fn finalize_hunk(
         chunks: &mut Vec<DiffChunk>,
-        file_state: &mut FileState,
-        hunk_state: Option<HunkState>,
+        file_state: &mut FileState<'_>,
+        hunk_state: Option<HunkState<'_>>,
     ) {
         let Some(hunk_state) = hunk_state else {
             return;
@@ -104,30 +175,39 @@ pub(crate) fn parse_diff_chunks(diff: &str) -> Vec<DiffChunk> {
         file_state.chunk_count += 1;
         let chunk_index = file_state.chunk_count;
         let chunk_id = format!("{file_path}#chunk-{chunk_index}");
-        let mut patch_parts = Vec::new();
-        patch_parts.extend(file_state.headers.clone());
-        patch_parts.push(hunk_state.header.clone());
-        patch_parts.extend(hunk_state.lines.clone());
-        let patch = if patch_parts.is_empty() {
-            String::new()
-        } else {
-            let mut joined = patch_parts.join("\n");
-            joined.push('\n');
-            joined
-        };
+        let mut patch_lines = file_state.headers.clone();
+        patch_lines.push(hunk_state.header);
+        patch_lines.extend(&hunk_state.lines);
+        let patch = patch_text(&patch_lines);
 
         chunks.push(DiffChunk {
             id: chunk_id,
             file_path,
             previous_path: file_state.previous_path.clone(),
             chunk_index,
-            hunk_header: hunk_state.header,
+            hunk_header: hunk_state.header.to_string(),
             patch,
             addition_lines: hunk_state.addition_lines.into_iter().collect(),
             deletion_lines: hunk_state.deletion_lines.into_iter().collect(),
+            language: None,
         });
     }
 
+    /// Detects each file's language from all of its hunks, so the content heuristics give
+    /// every chunk of the file the same answer.
+    fn assign_languages(chunks: &mut [DiffChunk]) {
+        for file_chunks in chunks.chunk_by_mut(|left, right| left.file_path == right.file_path) {
+            let hunks = file_chunks
+                .iter()
+                .flat_map(|chunk| split_patch_header_and_hunks(&chunk.patch).1)
+                .collect::<Vec<_>>();
+            let language = detect_language(&file_chunks[0].file_path, &hunks.join("\n"));
+            for chunk in file_chunks {
+                chunk.language = language;
+            }
+        }
+    }
+
     let mut chunks = Vec::new();
     let mut file_state = FileState::default();
     let mut hunk_state: Option<HunkState> = None;
@@ -136,7 +216,7 @@ pub(crate) fn parse_diff_chunks(diff: &str) -> Vec<DiffChunk> {
         if line.starts_with("diff --git ") {
             finalize_hunk(&mut chunks, &mut file_state, hunk_state.take());
             file_state = FileState::default();
-            file_state.headers.push(line.to_string());
+            file_state.headers.push(line);
 
             let mut parts = line.split_whitespace();
             let _ = parts.next();
@@ -150,12 +230,15 @@ pub(crate) fn parse_diff_chunks(diff: &str) -> Vec<DiffChunk> {
 
         if line.starts_with("@@ ") && line.contains(" @@") {
             finalize_hunk(&mut chunks, &mut file_state, hunk_state.take());
-            let (old_start, new_start) = parse_hunk_positions(line).unwrap_or((1, 1));
+            let (old_range, new_range) =
+                parse_hunk_ranges(line).unwrap_or((HunkRange::UNKNOWN, HunkRange::UNKNOWN));
             hunk_state = Some(HunkState {
-                header: line.to_string(),
+                header: line,
                 lines: Vec::new(),
-                old_line: old_start.max(1),
-                new_line: new_start.max(1),
+                old_line: old_range.start.max(1),
+                new_line: new_range.start.max(1),
+                old_remaining: old_range.count,
+                new_remaining: new_range.count,
                 addition_lines: BTreeSet::new(),
                 deletion_lines: BTreeSet::new(),
             });
@@ -163,28 +246,45 @@ pub(crate) fn parse_diff_chunks(diff: &str) -> Vec<DiffChunk> {
         }
 
         if let Some(hunk) = hunk_state.as_mut() {
-            hunk.lines.push(line.to_string());
-            if let Some(prefix) = line.chars().next() {
-                match prefix {
-                    '+' => {
-                        if !line.starts_with("+++") {
-                            hunk.addition_lines.insert(hunk.new_line.max(1));
-                            hunk.new_line += 1;
-                        }
-                    }
-                    '-' => {
-                        if !line.starts_with("---") {
-                            hunk.deletion_lines.insert(hunk.old_line.max(1));
-                            hunk.old_line += 1;
-                        }
-                    }
-                    ' ' => {
-                        hunk.old_line += 1;
-                        hunk.new_line += 1;
-                    }
-                    '\\' => {}
-                    _ => {}
+            // Once the header's line counts are used up, only "\ No newline" markers still
+            // belong to the hunk. Anything else, such as the `-- ` signature of a
+            // format-patch mail, would otherwise be attributed to lines past the hunk.
+            if hunk.is_complete() && !line.starts_with('\\') {
+                continue;
+            }
+            hunk.lines.push(line);
+            let first = line.chars().next();
+            // Editors that strip trailing whitespace turn empty context lines into blank ones.
+            let is_context = first == Some(' ')
+                || (first.is_none()
+                    && hunk.old_remaining.is_some()
+                    && hunk.new_remaining.is_some());
+            match first {
+                // With a known count, a removed `-- comment` line (`--- comment` in the patch)
+                // is content, not a file header.
+                Some('+')
+                    if has_room(hunk.new_remaining)
+                        && (hunk.new_remaining.is_some() || !line.starts_with("+++")) =>
+                {
+                    hunk.addition_lines.insert(hunk.new_line.max(1));
+                    hunk.new_line = hunk.new_line.saturating_add(1);
+                    take_line(&mut hunk.new_remaining);
+                }
+                Some('-')
+                    if has_room(hunk.old_remaining)
+                        && (hunk.old_remaining.is_some() || !line.starts_with("---")) =>
+                {
+                    hunk.deletion_lines.insert(hunk.old_line.max(1));
+                    hunk.old_line = hunk.old_line.saturating_add(1);
+                    take_line(&mut hunk.old_remaining);
                 }
+                _ if is_context && has_room(hunk.old_remaining) && has_room(hunk.new_remaining) => {
+                    hunk.old_line = hunk.old_line.saturating_add(1);
+                    hunk.new_line = hunk.new_line.saturating_add(1);
+                    take_line(&mut hunk.old_remaining);
+                    take_line(&mut hunk.new_remaining);
+                }
+                _ => {}
             }
             continue;
         }
@@ -198,15 +298,16 @@ pub(crate) fn parse_diff_chunks(diff: &str) -> Vec<DiffChunk> {
                     file_state.file_path = new_path;
                 }
             }
-            file_state.headers.push(line.to_string());
+            file_state.headers.push(line);
         }
     }
 
     finalize_hunk(&mut chunks, &mut file_state, hunk_state.take());
+    assign_languages(&mut chunks);
     chunks
 }
 
-fn merge_line_windows(line_numbers: &[i64], max_line: i64) -> Vec<(i64, i64)> {
+pub(crate) fn merge_line_windows(line_numbers: &[i64], max_line: i64) -> Vec<(i64, i64)> {
     let mut windows: Vec<(i64, i64)> = Vec::new();
     let mut sorted_lines = line_numbers
         .iter()
@@ -218,9 +319,9 @@ fn merge_line_windows(line_numbers: &[i64], max_line: i64) -> Vec<(i64, i64)> {
 
     for line in sorted_lines {
         let start = (line - 10).max(1);
-        let end = (line + 10).min(max_line.max(1));
+        let end = line.saturating_add(10).min(max_line.max(1));
         if let Some((_, previous_end)) = windows.last_mut() {
-            if start <= *previous_end + 2 {
+            if start <= previous_end.saturating_add(2) {
                 *previous_end = (*previous_end).max(end);
                 continue;
             }
@@ -314,6 +415,23 @@ pub(crate) fn normalize_severity(value: Option<&str>) -> &'static str {
     }
 }
 
+pub(crate) fn normalize_category(value: Option<&str>) -> &'static str {
+    let normalized = value
+        .map(str::trim)
+        .map(str::to_lowercase)
+        .unwrap_or_default()
+        .replace(['_', ' '], "-");
+    match normalized.as_str() {
+        "security" => "security",
+        "performance" => "performance",
+        "concurrency" => "concurrency",
+        "error-handling" => "error-handling",
+        "testing" | "tests" => "testing",
+        "prompt-injection" | "injection" => PROMPT_INJECTION_CATEGORY,
+        _ => "correctness",
+    }
+}
+
 pub(crate) fn resolve_line_number_for_chunk(
     chunk: &DiffChunk,
     side: &str,
@@ -339,13 +457,178 @@ pub(crate) fn resolve_line_number_for_chunk(
         .copied()
 }
 
-fn extract_json_object(raw: &str) -> Option<&str> {
-    let start = raw.find('{')?;
-    let end = raw.rfind('}')?;
-    if end <= start {
-        return None;
+/// Returns the text of `line_number` on `side` of the chunk's patch, without its diff marker.
+pub(crate) fn chunk_line_text(chunk: &DiffChunk, side: &str, line_number: i64) -> Option<String> {
+    let on_deletions = side == "deletions";
+    let mut old_line = 0;
+    let mut new_line = 0;
+    let mut in_hunk = false;
+    for line in chunk.patch.lines() {
+        if line.starts_with("@@ ") {
+            let (old_start, new_start) = parse_hunk_positions(line).unwrap_or((1, 1));
+            old_line = old_start.max(1);
+            new_line = new_start.max(1);
+            in_hunk = true;
+            continue;
+        }
+        if !in_hunk {
+            continue;
+        }
+        if let Some(text) = line.strip_prefix('+') {
+            if !on_deletions && new_line == line_number {
+                return Some(text.to_string());
+            }
+            new_line = new_line.saturating_add(1);
+        } else if let Some(text) = line.strip_prefix('-') {
+            if on_deletions && old_line == line_number {
+                return Some(text.to_string());
+            }
+            old_line = old_line.saturating_add(1);
+        } else if !line.starts_with('\\') {
+            let current = if on_deletions { old_line } else { new_line };
+            if current == line_number {
+                return Some(line.strip_prefix(' ').unwrap_or(line).to_string());
+            }
+            old_line = old_line.saturating_add(1);
+            new_line = new_line.saturating_add(1);
+        }
     }
-    Some(&raw[start..=end])
+    None
+}
+
+/// Balanced `{...}` spans found in model output are tried longest first, at most this many.
+const MAX_JSON_OBJECT_CANDIDATES: usize = 8;
+
+/// Spans of `raw` that could hold a JSON object: every balanced `{...}` outside JSON strings,
+/// longest first, then everything from the first `{` to the last `}`. One pass over `raw`, so
+/// garbage full of braces cannot make parsing quadratic.
+pub(crate) fn json_object_candidates(raw: &str) -> Vec<&str> {
+    let mut open_braces = Vec::new();
+    let mut spans = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, character) in raw.char_indices() {
+        if in_string {
+            match character {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match character {
+            // Quotes only start strings inside an object, so a stray `"` in the prose
+            // before the JSON cannot hide it.
+            '"' if !open_braces.is_empty() => in_string = true,
+            '{' => open_braces.push(index),
+            '}' => {
+                if let Some(start) = open_braces.pop() {
+                    spans.push(&raw[start..=index]);
+                }
+            }
+            _ => {}
+        }
+    }
+    spans.sort_by_key(|span| std::cmp::Reverse(span.len()));
+    spans.truncate(MAX_JSON_OBJECT_CANDIDATES);
+    if let (Some(start), Some(end)) = (raw.find('{'), raw.rfind('}')) {
+        if start < end && !spans.contains(&&raw[start..=end]) {
+            spans.push(&raw[start..=end]);
+        }
+    }
+    spans
+}
+
+/// Parses model output as review JSON, also when it is wrapped in prose or code fences.
+pub(crate) fn try_parse_chunk_review_payload(raw: &str) -> Option<ChunkReviewPayload> {
+    let trimmed = raw.trim();
+    serde_json::from_str::<ChunkReviewPayload>(trimmed)
+        .ok()
+        .or_else(|| {
+            // Every field is optional, so an object nested in the payload, like a single
+            // finding, would parse too. Only spans with a summary or findings count.
+            json_object_candidates(trimmed)
+                .into_iter()
+                .find_map(|json_slice| {
+                    serde_json::from_str::<ChunkReviewPayload>(json_slice)
+                        .ok()
+                        .filter(|payload| payload.summary.is_some() || payload.findings.is_some())
+                })
+        })
+}
+
+/// Why `raw` is not review JSON, for the repair prompt. Reports the error of the most
+/// promising JSON object in it, since the whole reply usually fails on surrounding prose.
+pub(crate) fn describe_chunk_review_parse_error(raw: &str) -> String {
+    let trimmed = raw.trim();
+    let candidate = json_object_candidates(trimmed)
+        .into_iter()
+        .next()
+        .unwrap_or(trimmed);
+    match serde_json::from_str::<ChunkReviewPayload>(candidate) {
+        Err(error) if candidate.starts_with('{') => format!("invalid JSON: {error}"),
+        Err(_) => "no JSON object found".to_string(),
+        Ok(_) => "the JSON object has neither `summary` nor `findings`".to_string(),
+    }
+}
+
+/// Name the chunk review schema is sent under to providers with structured output.
+pub(crate) const CHUNK_REVIEW_SCHEMA_NAME: &str = "chunk_review";
+
+/// JSON Schema of the reply [`build_chunk_review_prompt`] asks for, in the strict subset
+/// OpenAI's `json_schema` response format accepts: every property is required and optional
+/// values are nullable instead.
+pub(crate) fn chunk_review_json_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "summary": { "type": "string" },
+            "findings": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "title": { "type": "string" },
+                        "body": { "type": "string" },
+                        "severity": {
+                            "type": "string",
+                            "enum": ["critical", "high", "medium", "low"]
+                        },
+                        "category": {
+                            "type": "string",
+                            "enum": [
+                                "correctness",
+                                "security",
+                                "performance",
+                                "concurrency",
+                                "error-handling",
+                                "testing",
+                                PROMPT_INJECTION_CATEGORY
+                            ]
+                        },
+                        "confidence": { "type": ["number", "null"] },
+                        "side": { "type": "string", "enum": ["additions", "deletions"] },
+                        "lineNumber": { "type": ["integer", "null"] },
+                        "suggestedPatch": { "type": ["string", "null"] }
+                    },
+                    "required": [
+                        "title",
+                        "body",
+                        "severity",
+                        "category",
+                        "confidence",
+                        "side",
+                        "lineNumber",
+                        "suggestedPatch"
+                    ],
+                    "additionalProperties": false
+                }
+            }
+        },
+        "required": ["summary", "findings"],
+        "additionalProperties": false
+    })
 }
 
 pub(crate) fn parse_chunk_review_payload(raw: &str) -> ChunkReviewPayload {
@@ -357,14 +640,9 @@ pub(crate) fn parse_chunk_review_payload(raw: &str) -> ChunkReviewPayload {
         };
     }
 
-    if let Ok(payload) = serde_json::from_str::<ChunkReviewPayload>(trimmed) {
+    if let Some(payload) = try_parse_chunk_review_payload(trimmed) {
         return payload;
     }
-    if let Some(json_slice) = extract_json_object(trimmed) {
-        if let Ok(payload) = serde_json::from_str::<ChunkReviewPayload>(json_slice) {
-            return payload;
-        }
-    }
 
     ChunkReviewPayload {
         summary: Some(snippet(trimmed, 1_200)),
@@ -372,7 +650,7 @@ pub(crate) fn parse_chunk_review_payload(raw: &str) -> ChunkReviewPayload {
     }
 }
 
-fn split_patch_header_and_hunks(patch: &str) -> (Vec<String>, Vec<String>) {
+fn split_patch_header_and_hunks(patch: &str) -> (Vec<&str>, Vec<&str>) {
     let mut headers = Vec::new();
     let mut hunks = Vec::new();
     let mut in_hunk = false;
@@ -382,77 +660,302 @@ fn split_patch_header_and_hunks(patch: &str) -> (Vec<String>, Vec<String>) {
             in_hunk = true;
         }
         if in_hunk {
-            hunks.push(line.to_string());
+            hunks.push(line);
         } else {
-            headers.push(line.to_string());
+            headers.push(line);
         }
     }
 
     (headers, hunks)
 }
 
+/// One review chunk per changed file, merging the file's hunks, in path order.
+#[cfg(any(test, feature = "bench"))]
 pub(crate) fn parse_diff_file_chunks(diff: &str) -> Vec<DiffChunk> {
-    let mut chunks = parse_diff_chunks(diff);
-    if chunks.is_empty() {
-        return Vec::new();
-    }
+    diff_file_chunks(parse_diff_files(diff).iter().collect())
+}
 
-    chunks.sort_by(|left, right| {
-        left.file_path
-            .cmp(&right.file_path)
-            .then(left.chunk_index.cmp(&right.chunk_index))
-    });
+/// One review chunk per file of `files` that has hunks, in path order.
+fn diff_file_chunks(mut files: Vec<&DiffFile>) -> Vec<DiffChunk> {
+    files.retain(|file| !file.hunks.is_empty());
+    files.sort_by(|left, right| compare_paths(&left.path, &right.path));
+    files
+        .into_iter()
+        .enumerate()
+        .map(|(index, file)| {
+            let mut addition_lines = BTreeSet::new();
+            let mut deletion_lines = BTreeSet::new();
+            for hunk in &file.hunks {
+                addition_lines.extend(hunk.addition_lines.iter().copied());
+                deletion_lines.extend(hunk.deletion_lines.iter().copied());
+            }
+            DiffChunk {
+                id: format!("{}#file-{}", file.path, index + 1),
+                file_path: file.path.clone(),
+                previous_path: file.previous_path.clone(),
+                chunk_index: index + 1,
+                hunk_header: file.hunks[0].header.clone(),
+                patch: file.patch.clone(),
+                addition_lines: addition_lines.into_iter().collect(),
+                deletion_lines: deletion_lines.into_iter().collect(),
+                language: file.language,
+            }
+        })
+        .collect()
+}
 
-    let mut files = Vec::new();
-    let mut index = 0usize;
-    while index < chunks.len() {
-        let current_path = chunks[index].file_path.clone();
-        let start = index;
-        while index < chunks.len() && chunks[index].file_path == current_path {
-            index += 1;
-        }
-        let group = &chunks[start..index];
-        let first = &group[0];
-
-        let mut merged_headers: Vec<String> = Vec::new();
-        let mut merged_hunks: Vec<String> = Vec::new();
-        let mut addition_lines = BTreeSet::new();
-        let mut deletion_lines = BTreeSet::new();
-        for (group_index, chunk) in group.iter().enumerate() {
-            addition_lines.extend(chunk.addition_lines.iter().copied());
-            deletion_lines.extend(chunk.deletion_lines.iter().copied());
-            let (headers, hunks) = split_patch_header_and_hunks(&chunk.patch);
-            if group_index == 0 {
-                merged_headers = headers;
+/// A file's patch: the headers of its first hunk's patch, followed by every hunk.
+fn merge_hunk_patches(chunks: &[DiffChunk]) -> Arc<str> {
+    match chunks {
+        [] => Arc::from(""),
+        [chunk] => chunk.patch.clone(),
+        [first, ..] => {
+            let mut lines = split_patch_header_and_hunks(&first.patch).0;
+            for chunk in chunks {
+                lines.extend(split_patch_header_and_hunks(&chunk.patch).1);
             }
-            merged_hunks.extend(hunks);
+            patch_text(&lines)
         }
+    }
+}
 
-        let mut merged_patch_lines = Vec::new();
-        merged_patch_lines.extend(merged_headers);
-        merged_patch_lines.extend(merged_hunks);
-        let mut patch = if merged_patch_lines.is_empty() {
-            first.patch.clone()
-        } else {
-            merged_patch_lines.join("\n")
-        };
-        if !patch.is_empty() && !patch.ends_with('\n') {
-            patch.push('\n');
+/// Files of `files` without any hunk, which there is nothing to review in: binary files git
+/// did not diff as text, and renames, mode changes or empty files.
+fn unreviewable_diff_files<'a>(
+    files: impl IntoIterator<Item = &'a DiffFile>,
+) -> Vec<AiReviewSkippedChunk> {
+    files
+        .into_iter()
+        .filter(|file| file.hunks.is_empty())
+        .map(|file| AiReviewSkippedChunk {
+            file_path: file.path.clone(),
+            previous_path: file.previous_path.clone(),
+            reason: if file.binary {
+                AiReviewSkipReason::Binary
+            } else {
+                AiReviewSkipReason::NoTextChanges
+            },
+        })
+        .collect()
+}
+
+/// Splits `diff` at its `diff --git` lines, keeping each file's section whole, with the
+/// offset each section ends at.
+fn split_diff_files(diff: &str) -> Vec<(&str, usize)> {
+    let mut starts = diff
+        .match_indices("diff --git ")
+        .map(|(index, _)| index)
+        .filter(|index| *index == 0 || diff.as_bytes()[index - 1] == b'\n')
+        .collect::<Vec<_>>();
+    starts.push(diff.len());
+    starts
+        .windows(2)
+        .map(|bounds| (&diff[bounds[0]..bounds[1]], bounds[1]))
+        .collect()
+}
+
+fn parse_diff_file(section: &str, truncated: bool) -> Option<DiffFile> {
+    let mut parts = section.lines().next()?.split_whitespace().skip(2);
+    let mut previous_path = parts.next().and_then(normalize_patch_path);
+    let mut path = parts.next().and_then(normalize_patch_path);
+    let mut status = DiffFileStatus::Modified;
+    let mut similarity = None;
+    let mut binary = false;
+    for line in section.lines().skip(1) {
+        if line.starts_with("@@ ") {
+            break;
         }
+        if line.starts_with("new file mode") {
+            status = DiffFileStatus::Added;
+        } else if line.starts_with("deleted file mode") {
+            status = DiffFileStatus::Deleted;
+        } else if let Some(from) = line.strip_prefix("rename from ") {
+            status = DiffFileStatus::Renamed;
+            previous_path = normalize_patch_path(from);
+        } else if let Some(from) = line.strip_prefix("copy from ") {
+            status = DiffFileStatus::Copied;
+            previous_path = normalize_patch_path(from);
+        } else if let Some(to) = line
+            .strip_prefix("rename to ")
+            .or_else(|| line.strip_prefix("copy to "))
+        {
+            path = normalize_patch_path(to);
+        } else if let Some(to) = line.strip_prefix("+++ ").and_then(normalize_patch_path) {
+            // Unlike the `diff --git` line, this one keeps paths with spaces whole.
+            path = Some(to);
+        } else if let Some(percent) = line.strip_prefix("similarity index ") {
+            similarity = percent.trim_end_matches('%').parse().ok();
+        } else if line.starts_with("Binary files ") || line.starts_with("GIT binary patch") {
+            binary = true;
+        }
+    }
+    let path = path.or_else(|| previous_path.clone())?;
+    let previous_path = previous_path.filter(|previous| {
+        matches!(status, DiffFileStatus::Renamed | DiffFileStatus::Copied) && *previous != path
+    });
 
-        files.push(DiffChunk {
-            id: format!("{}#file-{}", first.file_path, files.len() + 1),
-            file_path: first.file_path.clone(),
-            previous_path: first.previous_path.clone(),
-            chunk_index: files.len() + 1,
-            hunk_header: first.hunk_header.clone(),
-            patch,
-            addition_lines: addition_lines.into_iter().collect(),
-            deletion_lines: deletion_lines.into_iter().collect(),
+    let chunks = parse_diff_chunks(section);
+    let patch = merge_hunk_patches(&chunks);
+    let language = chunks.first().and_then(|chunk| chunk.language);
+    let hunks = chunks
+        .into_iter()
+        .map(|chunk| {
+            let (old_range, new_range) = parse_hunk_ranges(&chunk.hunk_header)
+                .unwrap_or((HunkRange::UNKNOWN, HunkRange::UNKNOWN));
+            DiffHunk {
+                old_start: old_range.start,
+                old_lines: old_range.count.unwrap_or(0),
+                new_start: new_range.start,
+                new_lines: new_range.count.unwrap_or(0),
+                header: chunk.hunk_header,
+                addition_lines: chunk.addition_lines,
+                deletion_lines: chunk.deletion_lines,
+            }
+        })
+        .collect::<Vec<_>>();
+    Some(DiffFile {
+        path,
+        previous_path,
+        status,
+        similarity,
+        binary,
+        language,
+        insertions: hunks
+            .iter()
+            .map(|hunk| hunk.addition_lines.len() as i64)
+            .sum(),
+        deletions: hunks
+            .iter()
+            .map(|hunk| hunk.deletion_lines.len() as i64)
+            .sum(),
+        hunks,
+        truncated,
+        patch,
+    })
+}
+
+/// The files of `diff` in diff order, with hunks numbered by the same parser that cuts review
+/// chunks, so line numbers in the UI and in findings agree.
+pub(crate) fn parse_diff_files(diff: &str) -> Vec<DiffFile> {
+    parse_truncated_diff_files(diff, diff.len())
+}
+
+/// Same as [`parse_diff_files`] for a diff that is shown cut off after `shown_bytes`: files
+/// that do not fit whole are marked `truncated`.
+pub(crate) fn parse_truncated_diff_files(diff: &str, shown_bytes: usize) -> Vec<DiffFile> {
+    split_diff_files(diff)
+        .into_iter()
+        .filter_map(|(section, end)| parse_diff_file(section, end > shown_bytes))
+        .collect()
+}
+
+/// File name endings of build output that is not worth a model's attention.
+const MINIFIED_PATH_SUFFIXES: [&str; 5] =
+    [".min.js", ".min.mjs", ".min.css", ".js.map", ".css.map"];
+/// Changed lines this long on average mean minified or generated code.
+const MINIFIED_AVERAGE_LINE_CHARS: usize = 400;
+
+fn is_minified_chunk(chunk: &DiffChunk) -> bool {
+    let path = chunk.file_path.to_ascii_lowercase();
+    if MINIFIED_PATH_SUFFIXES
+        .iter()
+        .any(|suffix| path.ends_with(suffix))
+    {
+        return true;
+    }
+    let (line_count, char_count) = chunk
+        .patch
+        .lines()
+        .filter(|line| {
+            (line.starts_with('+') && !line.starts_with("+++"))
+                || (line.starts_with('-') && !line.starts_with("---"))
+        })
+        .fold((0usize, 0usize), |(lines, chars), line| {
+            (lines + 1, chars + line.chars().count() - 1)
         });
+    line_count > 0 && char_count / line_count >= MINIFIED_AVERAGE_LINE_CHARS
+}
+
+/// Why `chunk` should be skipped rather than reviewed: minified code, or a patch longer than
+/// `max_file_chars`, which would crowd the rest of the diff out of the prompt.
+pub(crate) fn oversized_chunk_reason(
+    chunk: &DiffChunk,
+    max_file_chars: usize,
+) -> Option<AiReviewSkipReason> {
+    if is_minified_chunk(chunk) {
+        Some(AiReviewSkipReason::Minified)
+    } else if chunk.patch.chars().count() > max_file_chars {
+        Some(AiReviewSkipReason::TooLarge)
+    } else {
+        None
     }
+}
 
-    files
+/// Splits off the chunks [`oversized_chunk_reason`] skips, as skipped files.
+fn split_oversized_chunks(
+    chunks: Vec<DiffChunk>,
+    max_file_chars: usize,
+) -> (Vec<DiffChunk>, Vec<AiReviewSkippedChunk>) {
+    let mut reviewable = Vec::with_capacity(chunks.len());
+    let mut skipped = Vec::new();
+    for chunk in chunks {
+        match oversized_chunk_reason(&chunk, max_file_chars) {
+            Some(reason) => skipped.push(AiReviewSkippedChunk {
+                file_path: chunk.file_path,
+                previous_path: chunk.previous_path,
+                reason,
+            }),
+            None => reviewable.push(chunk),
+        }
+    }
+    (reviewable, skipped)
+}
+
+/// The chunks of a diff to review, and the changed files that will not be.
+pub(crate) struct ReviewableDiff {
+    pub(crate) chunks: Vec<DiffChunk>,
+    pub(crate) skipped: Vec<AiReviewSkippedChunk>,
+}
+
+/// The chunks of `files` to review, after dropping files matched by the `.rovexignore` at the
+/// workspace root and minified or oversized files. Dropped files and files without hunks are
+/// listed in `skipped`.
+pub(crate) fn reviewable_diff_files(
+    workspace: &Path,
+    files: &[DiffFile],
+) -> Result<ReviewableDiff, String> {
+    let filter = PathFilter::new(None, None)?.with_rovexignore(workspace)?;
+    let (kept, ignored): (Vec<&DiffFile>, Vec<&DiffFile>) = files.iter().partition(|file| {
+        filter.is_empty()
+            || filter.allows(&file.path)
+            || file
+                .previous_path
+                .as_deref()
+                .is_some_and(|path| filter.allows(path))
+    });
+    let mut skipped = ignored
+        .into_iter()
+        .map(|file| AiReviewSkippedChunk {
+            reason: filter
+                .skip_reason(&file.path)
+                .unwrap_or(AiReviewSkipReason::ExcludedPath),
+            file_path: file.path.clone(),
+            previous_path: file.previous_path.clone(),
+        })
+        .collect::<Vec<_>>();
+    skipped.extend(unreviewable_diff_files(kept.iter().copied()));
+    let (chunks, oversized) =
+        split_oversized_chunks(diff_file_chunks(kept), current_review_max_file_chars());
+    skipped.extend(oversized);
+    Ok(ReviewableDiff { chunks, skipped })
+}
+
+/// [`reviewable_diff_files`] for the files of `diff`.
+pub(crate) fn parse_workspace_diff_file_chunks(
+    workspace: &Path,
+    diff: &str,
+) -> Result<ReviewableDiff, String> {
+    reviewable_diff_files(workspace, &parse_diff_files(diff))
 }
 
 pub(crate) fn build_chunk_review_prompt(
@@ -488,17 +991,71 @@ pub(crate) fn build_chunk_review_prompt(
     };
 
     let context_block = workspace_context
-        .map(|value| format!("\nWorkspace file context:\n```\n{value}\n```\n"))
+        .map(|value| {
+            format!(
+                "\n\nWorkspace file context:\n{}",
+                fence_untrusted("context", value)
+            )
+        })
         .unwrap_or_default();
 
     format!(
-        "Review this changed file for bugs.\n\nFocus: {reviewer_goal}\nWorkspace: {workspace}\nBase ref: {base_ref}\nMerge base: {merge_base}\nHead: {head}\nFile path: {}\nFile index: {}\nAllowed addition line numbers: {additions}\nAllowed deletion line numbers: {deletions}\nDiff content truncated: {}\n\nReturn STRICT JSON only with this schema:\n{{\n  \"summary\": \"short summary of what changed in this file\",\n  \"findings\": [\n    {{\n      \"title\": \"bug title\",\n      \"body\": \"why this is a real bug and how to fix or test it\",\n      \"severity\": \"critical|high|medium|low\",\n      \"confidence\": 0.0,\n      \"side\": \"additions|deletions\",\n      \"lineNumber\": 123\n    }}\n  ]\n}}\n\nRules:\n- If there is no clear bug, return an empty findings array.\n- Do not include style nits.\n- Do not return markdown.\n\nFile diff:\n```diff\n{patch_for_review}\n```{context_block}",
+        "Review this changed file for bugs.\n\nFocus: {reviewer_goal}\nWorkspace: {workspace}\nBase ref: {base_ref}\nMerge base: {merge_base}\nHead: {head}\nFile path: {}\nLanguage: {}\nFile index: {}\nAllowed addition line numbers: {additions}\nAllowed deletion line numbers: {deletions}\nDiff content truncated: {}\n\nReturn STRICT JSON only with this schema:\n{{\n  \"summary\": \"short summary of what changed in this file\",\n  \"findings\": [\n    {{\n      \"title\": \"bug title\",\n      \"body\": \"why this is a real bug and how to fix or test it\",\n      \"severity\": \"critical|high|medium|low\",\n      \"category\": \"correctness|security|performance|concurrency|error-handling|testing|prompt-injection\",\n      \"confidence\": 0.0,\n      \"side\": \"additions|deletions\",\n      \"lineNumber\": 123,\n      \"suggestedPatch\": \"unified diff of this file that fixes the bug, or null\"\n    }}\n  ]\n}}\n\nRules:\n- If there is no clear bug, return an empty findings array.\n- Do not include style nits.\n- Only include suggestedPatch for small, certain fixes. It must change only this file, use `--- a/<path>` and `+++ b/<path>` headers, and hunks against the new side of the diff.\n- Do not return markdown.\n- {UNTRUSTED_CONTENT_RULE}\n\nFile diff:\n{}{context_block}",
         chunk.file_path,
+        chunk
+            .language
+            .and_then(language_name)
+            .unwrap_or("unknown"),
         chunk.chunk_index,
-        if patch_truncated { "yes" } else { "no" }
+        if patch_truncated { "yes" } else { "no" },
+        fence_untrusted("diff", patch_for_review)
     )
 }
 
+/// Normalizes a model-suggested fix into a unified diff of `file_path` with standard headers.
+/// Returns `None` when the patch has no hunks, names another file, or spans several files.
+pub(crate) fn normalize_suggested_patch(patch: &str, file_path: &str) -> Option<String> {
+    let patch = patch.trim();
+    let patch = patch
+        .strip_prefix("```diff")
+        .or_else(|| patch.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"))
+        .unwrap_or(patch);
+
+    let mut hunks = String::new();
+    for line in patch.lines() {
+        if hunks.is_empty() {
+            let header_path = if let Some(header) = line.strip_prefix("diff --git ") {
+                header.split_once(" b/").map(|(_, new)| new.to_string())
+            } else if let Some(path) = line
+                .strip_prefix("--- ")
+                .or_else(|| line.strip_prefix("+++ "))
+            {
+                normalize_patch_path(path)
+            } else if line.starts_with("@@") {
+                hunks.push_str(line);
+                hunks.push('\n');
+                continue;
+            } else {
+                None
+            };
+            if header_path.is_some_and(|path| path != file_path) {
+                return None;
+            }
+            continue;
+        }
+        if line.starts_with("diff --git ") {
+            return None;
+        }
+        hunks.push_str(line);
+        hunks.push('\n');
+    }
+    if hunks.is_empty() {
+        return None;
+    }
+    Some(format!("--- a/{file_path}\n+++ b/{file_path}\n{hunks}"))
+}
+
 #[allow(dead_code)]
 pub(crate) fn build_chunk_review_markdown(
     reviewer_goal: &str,
@@ -552,7 +1109,15 @@ pub(crate) fn build_chunk_review_markdown(
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_diff_chunks, parse_diff_file_chunks, resolve_line_number_for_chunk};
+    use proptest::prelude::*;
+
+    use super::{
+        chunk_line_text, normalize_suggested_patch, oversized_chunk_reason, parse_diff_chunks,
+        parse_diff_file_chunks, parse_diff_files, parse_hunk_positions, parse_hunk_ranges,
+        parse_truncated_diff_files, resolve_line_number_for_chunk, try_parse_chunk_review_payload,
+        unreviewable_diff_files,
+    };
+    use crate::backend::{AiReviewSkipReason, DiffFileStatus};
 
     #[test]
     fn parse_diff_chunks_tracks_chunk_and_line_mappings() {
@@ -593,6 +1158,97 @@ index 1111111..2222222 100644
         assert_eq!(resolved, Some(11));
     }
 
+    #[test]
+    fn chunk_line_text_reads_lines_on_either_side() {
+        let diff = r#"diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -10,3 +10,3 @@
+ context
+-old_line
++new_line
+ context2
+"#;
+        let chunk = parse_diff_chunks(diff).remove(0);
+        assert_eq!(
+            chunk_line_text(&chunk, "additions", 11).as_deref(),
+            Some("new_line")
+        );
+        assert_eq!(
+            chunk_line_text(&chunk, "deletions", 11).as_deref(),
+            Some("old_line")
+        );
+        assert_eq!(
+            chunk_line_text(&chunk, "additions", 12).as_deref(),
+            Some("context2")
+        );
+        assert_eq!(chunk_line_text(&chunk, "additions", 40), None);
+    }
+
+    #[test]
+    fn normalize_suggested_patch_keeps_single_file_hunks() {
+        let patch = "```diff\n--- a/src/main.rs\n+++ b/src/main.rs\n@@ -1,2 +1,2 @@\n fn main() {\n-    run().unwrap();\n+    run().expect(\"run failed\");\n```";
+        assert_eq!(
+            normalize_suggested_patch(patch, "src/main.rs").as_deref(),
+            Some("--- a/src/main.rs\n+++ b/src/main.rs\n@@ -1,2 +1,2 @@\n fn main() {\n-    run().unwrap();\n+    run().expect(\"run failed\");\n")
+        );
+        assert_eq!(
+            normalize_suggested_patch("@@ -1 +1 @@\n-a\n+b", "src/lib.rs").as_deref(),
+            Some("--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-a\n+b\n")
+        );
+        assert_eq!(normalize_suggested_patch(patch, "src/lib.rs"), None);
+        assert_eq!(
+            normalize_suggested_patch("no hunks here", "src/lib.rs"),
+            None
+        );
+    }
+
+    #[test]
+    fn minified_and_oversized_files_are_skipped() {
+        let file_diff = |path: &str, lines: &[String]| {
+            format!(
+                "diff --git a/{path} b/{path}\nnew file mode 100644\n--- /dev/null\n+++ b/{path}\n@@ -0,0 +1,{} @@\n{}\n",
+                lines.len(),
+                lines
+                    .iter()
+                    .map(|line| format!("+{line}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+        };
+        let short_lines = (0..50)
+            .map(|index| format!("let value{index} = {index};"))
+            .collect::<Vec<_>>();
+        let diff = [
+            file_diff("src/app.js", &short_lines),
+            file_diff("dist/app.min.js", &short_lines),
+            file_diff("vendor/bundle.js", &["x".repeat(5_000)]),
+            file_diff("data/fixtures.json", &vec!["{\"a\": 1},".repeat(10); 400]),
+        ]
+        .concat();
+
+        let chunks = parse_diff_file_chunks(&diff);
+        let reasons = chunks
+            .iter()
+            .map(|chunk| {
+                (
+                    chunk.file_path.as_str(),
+                    oversized_chunk_reason(chunk, 20_000),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reasons,
+            vec![
+                ("data/fixtures.json", Some(AiReviewSkipReason::TooLarge)),
+                ("dist/app.min.js", Some(AiReviewSkipReason::Minified)),
+                ("src/app.js", None),
+                ("vendor/bundle.js", Some(AiReviewSkipReason::Minified)),
+            ]
+        );
+    }
+
     #[test]
     fn parse_diff_file_chunks_collapses_hunks_per_file() {
         let diff = r#"diff --git a/src/main.rs b/src/main.rs
@@ -615,7 +1271,199 @@ index 1111111..2222222 100644
         assert_eq!(chunk.file_path, "src/main.rs");
         assert!(chunk.patch.contains("@@ -1,2 +1,3 @@"));
         assert!(chunk.patch.contains("@@ -10,2 +11,3 @@"));
+        assert_eq!(&*chunk.patch, diff);
         assert!(chunk.addition_lines.contains(&2));
         assert!(chunk.addition_lines.contains(&12));
+        assert_eq!(chunk.language, Some("rust"));
+    }
+
+    #[test]
+    fn files_without_hunks_are_reported_as_unreviewable() {
+        let diff = r#"diff --git a/logo.png b/logo.png
+index 1111111..2222222 100644
+Binary files a/logo.png and b/logo.png differ
+diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1 +1 @@
+-old
++new
+diff --git a/src/old.rs b/src/new.rs
+similarity index 100%
+rename from src/old.rs
+rename to src/new.rs
+"#;
+        let skipped = unreviewable_diff_files(&parse_diff_files(diff));
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(skipped[0].file_path, "logo.png");
+        assert_eq!(skipped[0].reason, AiReviewSkipReason::Binary);
+        assert_eq!(skipped[1].file_path, "src/new.rs");
+        assert_eq!(skipped[1].previous_path.as_deref(), Some("src/old.rs"));
+        assert_eq!(skipped[1].reason, AiReviewSkipReason::NoTextChanges);
+    }
+
+    #[test]
+    fn diff_files_carry_status_renames_and_hunks() {
+        let diff = r#"diff --git a/logo.png b/logo.png
+new file mode 100644
+index 0000000..2222222
+Binary files /dev/null and b/logo.png differ
+diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,2 +1,2 @@
+-old
++new
+ same
+@@ -10 +10,2 @@ fn main() {
+ kept
++added
+diff --git a/src/old.rs b/src/new.rs
+similarity index 90%
+rename from src/old.rs
+rename to src/new.rs
+"#;
+        let files = parse_diff_files(diff);
+        assert_eq!(files.len(), 3);
+
+        assert_eq!(files[0].path, "logo.png");
+        assert_eq!(files[0].status, DiffFileStatus::Added);
+        assert!(files[0].binary && files[0].hunks.is_empty());
+
+        let main = &files[1];
+        assert_eq!(main.status, DiffFileStatus::Modified);
+        assert_eq!((main.insertions, main.deletions), (2, 1));
+        assert_eq!(main.hunks.len(), 2);
+        assert_eq!(main.hunks[0].header, "@@ -1,2 +1,2 @@");
+        let second = &main.hunks[1];
+        assert_eq!(
+            (
+                second.old_start,
+                second.old_lines,
+                second.new_start,
+                second.new_lines
+            ),
+            (10, 1, 10, 2)
+        );
+        assert_eq!(second.addition_lines, vec![11]);
+
+        assert_eq!(files[2].path, "src/new.rs");
+        assert_eq!(files[2].previous_path.as_deref(), Some("src/old.rs"));
+        assert_eq!(files[2].status, DiffFileStatus::Renamed);
+        assert_eq!(files[2].similarity, Some(90));
+        assert!(files.iter().all(|file| !file.truncated));
+
+        let shown_bytes = diff.find("@@ -10").expect("second hunk");
+        let truncated = parse_truncated_diff_files(diff, shown_bytes);
+        assert_eq!(truncated.len(), 3);
+        assert!(!truncated[0].truncated);
+        assert!(truncated[1].truncated && truncated[2].truncated);
+        assert_eq!(truncated[1].hunks.len(), 2);
+    }
+
+    #[test]
+    fn hunk_counts_decide_which_lines_belong_to_the_hunk() {
+        let diff = "diff --git a/schema.sql b/schema.sql
+--- a/schema.sql
++++ b/schema.sql
+@@ -1,3 +1,3 @@
+--- drop the legacy table
++++ keep the legacy table
+
+ SELECT 1;
+-- 
+2.43.0
+";
+        let chunks = parse_diff_chunks(diff);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].deletion_lines, vec![1]);
+        assert_eq!(chunks[0].addition_lines, vec![1]);
+        assert!(!chunks[0].patch.contains("2.43.0"));
+    }
+
+    #[test]
+    fn json_payload_is_found_among_other_braces() {
+        let raw = r#"Checked {the loop} first. {"summary": "Uses } in a string", "findings": [{"title": "Off by one"}]} Done {"#;
+        let payload = try_parse_chunk_review_payload(raw).unwrap();
+        assert_eq!(payload.summary.as_deref(), Some("Uses } in a string"));
+        assert_eq!(payload.findings.unwrap().len(), 1);
+
+        assert!(try_parse_chunk_review_payload(r#"Only {"title": "a finding"} here"#).is_none());
+    }
+
+    fn hunk_start() -> impl Strategy<Value = i64> {
+        prop_oneof![0..2_000i64, Just(i64::MAX - 1), Just(i64::MAX)]
+    }
+
+    fn diff_line() -> impl Strategy<Value = String> {
+        prop_oneof![
+            Just("diff --git a/src/lib.rs b/src/lib.rs".to_string()),
+            Just("--- a/src/lib.rs".to_string()),
+            Just("+++ b/src/lib.rs".to_string()),
+            (hunk_start(), 0..6i64, hunk_start(), 0..6i64).prop_map(
+                |(old_start, old_count, new_start, new_count)| {
+                    format!("@@ -{old_start},{old_count} +{new_start},{new_count} @@")
+                }
+            ),
+            "[-+ \\\\@]{0,3}[a-z @,]{0,8}",
+            any::<String>(),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn diff_lines_stay_inside_their_hunk(
+            lines in prop::collection::vec(diff_line(), 0..48),
+        ) {
+            for chunk in parse_diff_chunks(&lines.join("\n")) {
+                let Some((old_range, new_range)) = parse_hunk_ranges(&chunk.hunk_header) else {
+                    continue;
+                };
+                for (lines, range) in [
+                    (&chunk.addition_lines, new_range),
+                    (&chunk.deletion_lines, old_range),
+                ] {
+                    let first = range.start.max(1);
+                    prop_assert!(lines.iter().all(|line| *line >= first));
+                    if let Some(count) = range.count {
+                        prop_assert!(lines.len() as i64 <= count);
+                        let end = first.saturating_add(count);
+                        prop_assert!(lines.iter().all(|line| *line <= end));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn hunk_headers_round_trip(
+            old_start in hunk_start(),
+            old_count in 0..1_000i64,
+            new_start in hunk_start(),
+            context in "[ -~]{0,24}",
+        ) {
+            let header = format!("@@ -{old_start},{old_count} +{new_start} @@ {context}");
+            prop_assert_eq!(parse_hunk_positions(&header), Some((old_start, new_start)));
+            let (old_range, new_range) = parse_hunk_ranges(&header).unwrap();
+            prop_assert_eq!(old_range.count, Some(old_count));
+            prop_assert_eq!(new_range.count, Some(1));
+        }
+
+        #[test]
+        fn arbitrary_headers_and_model_output_do_not_panic(raw in any::<String>()) {
+            let _ = parse_hunk_positions(&raw);
+            let _ = try_parse_chunk_review_payload(&raw);
+        }
+
+        #[test]
+        fn payload_is_recovered_from_surrounding_prose(
+            prefix in "[^{}]{0,64}",
+            suffix in "[^{}]{0,64}",
+            summary in any::<String>(),
+        ) {
+            let json = serde_json::json!({ "summary": summary, "findings": [] });
+            let raw = format!("{prefix}{json}{suffix}");
+            let payload = try_parse_chunk_review_payload(&raw).unwrap();
+            prop_assert_eq!(payload.summary, Some(summary));
+        }
     }
 }
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+/// Projects the time left in a run from the latencies of the chunks it already reviewed.
+pub(crate) struct ChunkEtaEstimator {
+    latencies_ms: Vec<u64>,
+    parallelism: usize,
+}
+
+impl ChunkEtaEstimator {
+    pub(crate) fn new(parallelism: usize) -> Self {
+        Self {
+            latencies_ms: Vec::new(),
+            parallelism: parallelism.max(1),
+        }
+    }
+
+    pub(crate) fn record(&mut self, latency: Duration) {
+        self.latencies_ms
+            .push(u64::try_from(latency.as_millis()).unwrap_or(u64::MAX));
+    }
+
+    /// Estimates how long the remaining chunks take when reviewed `parallelism` at a time. Uses
+    /// the median latency so a single slow retry does not swing the estimate. Returns `None`
+    /// until at least one chunk has finished.
+    pub(crate) fn estimate_remaining_ms(&self, remaining_chunks: usize) -> Option<u64> {
+        if remaining_chunks == 0 {
+            return Some(0);
+        }
+        if self.latencies_ms.is_empty() {
+            return None;
+        }
+        let mut sorted = self.latencies_ms.clone();
+        sorted.sort_unstable();
+        let median = sorted[sorted.len() / 2];
+        let waves = remaining_chunks.div_ceil(self.parallelism);
+        Some(median.saturating_mul(waves as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::ChunkEtaEstimator;
+
+    #[test]
+    fn estimate_waits_for_the_first_finished_chunk() {
+        let estimator = ChunkEtaEstimator::new(2);
+        assert_eq!(estimator.estimate_remaining_ms(3), None);
+        assert_eq!(estimator.estimate_remaining_ms(0), Some(0));
+    }
+
+    #[test]
+    fn estimate_uses_median_latency_per_parallel_wave() {
+        let mut estimator = ChunkEtaEstimator::new(2);
+        for latency_ms in [1_000, 1_200, 30_000] {
+            estimator.record(Duration::from_millis(latency_ms));
+        }
+        assert_eq!(estimator.estimate_remaining_ms(3), Some(2_400));
+        assert_eq!(estimator.estimate_remaining_ms(1), Some(1_200));
+    }
+}
@@ -1,4 +1,4 @@
-use super::executor::is_transient_chunk_error;
+use super::executor::{is_auth_expired_error, is_rate_limit_error, is_transient_chunk_error};
 
 #[test]
 fn classifies_transient_errors() {
@@ -7,3 +7,28 @@ fn classifies_transient_errors() {
     assert!(is_transient_chunk_error("connection refused"));
     assert!(!is_transient_chunk_error("invalid request payload"));
 }
+
+#[test]
+fn classifies_rate_limit_errors() {
+    assert!(is_rate_limit_error(
+        "AI provider returned 429 Too Many Requests."
+    ));
+    assert!(is_rate_limit_error("Rate limit reached for requests"));
+    assert!(!is_rate_limit_error("request timed out"));
+    assert!(is_rate_limit_error(
+        "Codex app-server rate limited: usage limit reached"
+    ));
+}
+
+#[test]
+fn classifies_auth_expired_errors() {
+    assert!(is_auth_expired_error(
+        "Codex app-server session expired: Your session expired"
+    ));
+    assert!(!is_auth_expired_error(
+        "Codex app-server error: request failed"
+    ));
+    assert!(!is_transient_chunk_error(
+        "Codex app-server session expired: Your session expired"
+    ));
+}
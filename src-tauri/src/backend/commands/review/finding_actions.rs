@@ -0,0 +1,517 @@
+use std::{process::Stdio, time::Duration};
+
+use tauri::{AppHandle, State};
+use tauri_plugin_opener::OpenerExt;
+use tokio::process::Command as TokioCommand;
+
+use super::super::common::{truncate_chars, ROVEX_FINDING_ACTIONS_ENV};
+use super::super::workspace_git::workspace_checkout;
+use super::store;
+use crate::backend::settings::settings_store;
+use crate::backend::{
+    AiReviewFinding, AppState, FindingAction, ListFindingActionsResult, RunFindingActionInput,
+    RunFindingActionResult, SetFindingActionsInput,
+};
+
+const MAX_FINDING_ACTIONS: usize = 32;
+const MAX_ACTION_LABEL_CHARS: usize = 80;
+const FINDING_ACTION_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_ACTION_OUTPUT_CHARS: usize = 16_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ActionKind {
+    Command,
+    Url,
+}
+
+impl ActionKind {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "command" | "shell" => Ok(Self::Command),
+            "url" | "link" => Ok(Self::Url),
+            other => Err(format!(
+                "Unknown finding action kind '{other}'. Use 'command' or 'url'."
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Command => "command",
+            Self::Url => "url",
+        }
+    }
+}
+
+fn is_web_url(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    lower.starts_with("https://") || lower.starts_with("http://")
+}
+
+/// Trimmed actions with normalized kinds, rejecting blank or repeated ids and URL templates
+/// that would open anything other than a web page.
+fn normalize_finding_actions(actions: Vec<FindingAction>) -> Result<Vec<FindingAction>, String> {
+    if actions.len() > MAX_FINDING_ACTIONS {
+        return Err(format!(
+            "At most {MAX_FINDING_ACTIONS} finding actions can be configured."
+        ));
+    }
+    let mut normalized: Vec<FindingAction> = Vec::with_capacity(actions.len());
+    for action in actions {
+        let id = action.id.trim();
+        if id.is_empty() {
+            return Err("Finding action id must not be empty.".to_string());
+        }
+        if normalized
+            .iter()
+            .any(|existing| existing.id.eq_ignore_ascii_case(id))
+        {
+            return Err(format!("Finding action id '{id}' is used more than once."));
+        }
+        let label = match action.label.trim() {
+            "" => id,
+            label => label,
+        };
+        if label.chars().count() > MAX_ACTION_LABEL_CHARS {
+            return Err(format!(
+                "Finding action label must be at most {MAX_ACTION_LABEL_CHARS} characters."
+            ));
+        }
+        let kind = ActionKind::parse(&action.kind)?;
+        let template = action.template.trim();
+        if template.is_empty() {
+            return Err(format!("Finding action '{id}' has an empty template."));
+        }
+        if kind == ActionKind::Url && !is_web_url(template) {
+            return Err(format!(
+                "Finding action '{id}' must be an http:// or https:// URL."
+            ));
+        }
+        normalized.push(FindingAction {
+            id: id.to_string(),
+            label: label.to_string(),
+            kind: kind.as_str().to_string(),
+            template: template.to_string(),
+        });
+    }
+    Ok(normalized)
+}
+
+/// Saved actions, falling back to a JSON array in the environment variable of the same name.
+fn configured_finding_actions() -> Result<Vec<FindingAction>, String> {
+    let actions = match settings_store().get::<Vec<FindingAction>>(ROVEX_FINDING_ACTIONS_ENV) {
+        Some(actions) => actions,
+        None => match settings_store().get_string(ROVEX_FINDING_ACTIONS_ENV) {
+            Some(raw) => serde_json::from_str(&raw).map_err(|error| {
+                format!("{ROVEX_FINDING_ACTIONS_ENV} is not a valid list of actions: {error}")
+            })?,
+            None => Vec::new(),
+        },
+    };
+    normalize_finding_actions(actions)
+}
+
+/// Percent-encodes everything but unreserved characters and `/`, so file paths keep their
+/// directory structure inside URL paths.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+struct ActionContext<'a> {
+    run_id: &'a str,
+    workspace: &'a str,
+    branch: Option<&'a str>,
+    head: Option<&'a str>,
+    finding: &'a AiReviewFinding,
+}
+
+/// Placeholders a template may use, in the order their environment variables are set.
+const PLACEHOLDERS: [&str; 14] = [
+    "file",
+    "line",
+    "side",
+    "branch",
+    "commit",
+    "head",
+    "workspace",
+    "runId",
+    "findingId",
+    "title",
+    "body",
+    "severity",
+    "category",
+    "language",
+];
+
+/// `ROVEX_FINDING_<NAME>` for a placeholder, such as `ROVEX_FINDING_RUN_ID` for `{runId}`
+/// and `ROVEX_FINDING_ID` for `{findingId}`.
+fn placeholder_env_var(name: &str) -> String {
+    let name = name.strip_prefix("finding").unwrap_or(name);
+    let mut var = "ROVEX_FINDING_".to_string();
+    for character in name.chars() {
+        if character.is_ascii_uppercase() && !var.ends_with('_') {
+            var.push('_');
+        }
+        var.push(character.to_ascii_uppercase());
+    }
+    var
+}
+
+/// How a command template refers to a placeholder's environment variable. The shell expands
+/// it after parsing the command line, so finding text never becomes shell syntax.
+fn env_reference(name: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("\"!{}!\"", placeholder_env_var(name))
+    } else {
+        format!("\"${}\"", placeholder_env_var(name))
+    }
+}
+
+/// Expands a command template to a shell command line that reads finding values from the
+/// environment. Quotes a template puts around a placeholder are dropped, so `"{title}"` and
+/// `'{title}'` still pass the title as one argument.
+fn expand_command_template(template: &str, context: &ActionContext<'_>) -> String {
+    let mut command_line = expand_template(template, context, |name, _| env_reference(name));
+    for name in PLACEHOLDERS {
+        let reference = env_reference(name);
+        for quote in ['"', '\''] {
+            command_line = command_line.replace(&format!("{quote}{reference}{quote}"), &reference);
+        }
+    }
+    command_line
+}
+
+impl ActionContext<'_> {
+    /// Every placeholder value under its environment variable, for command actions.
+    fn environment(&self) -> Vec<(String, String)> {
+        PLACEHOLDERS
+            .iter()
+            .filter_map(|name| {
+                self.value(name)
+                    .map(|value| (placeholder_env_var(name), value))
+            })
+            .collect()
+    }
+
+    fn value(&self, name: &str) -> Option<String> {
+        let finding = self.finding;
+        let value = match name {
+            "file" => finding.file_path.clone(),
+            "line" => finding.line_number.to_string(),
+            "side" => finding.side.clone(),
+            "branch" => self.branch.unwrap_or_default().to_string(),
+            "commit" | "head" => self.head.unwrap_or_default().to_string(),
+            "workspace" => self.workspace.to_string(),
+            "runId" => self.run_id.to_string(),
+            "findingId" => finding.id.clone(),
+            "title" => finding.title.clone(),
+            "body" => finding.body.clone(),
+            "severity" => finding.severity.clone(),
+            "category" => finding.category.clone().unwrap_or_default(),
+            "language" => finding.language.clone().unwrap_or_default(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// Replaces each known `{name}` placeholder with `replace(name, value)`. Unknown placeholders
+/// are left as written so templates can still contain literal braces.
+fn expand_template(
+    template: &str,
+    context: &ActionContext<'_>,
+    replace: impl Fn(&str, &str) -> String,
+) -> String {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after
+            .find('}')
+            .and_then(|end| context.value(&after[..end]).map(|value| (end, value)));
+        match value {
+            Some((end, value)) => {
+                expanded.push_str(&replace(&after[..end], &value));
+                rest = &after[end + 1..];
+            }
+            None => {
+                expanded.push('{');
+                rest = after;
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// `cmd` runs with delayed expansion on, so the `!VAR!` references of [`env_reference`]
+/// expand after the line is parsed.
+fn shell_command(command_line: &str) -> TokioCommand {
+    if cfg!(target_os = "windows") {
+        let mut command = TokioCommand::new("cmd");
+        command.arg("/V:ON").arg("/C").arg(command_line);
+        command
+    } else {
+        let mut command = TokioCommand::new("sh");
+        command.arg("-c").arg(command_line);
+        command
+    }
+}
+
+fn truncate_output(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    let (mut output, truncated) = truncate_chars(&text, MAX_ACTION_OUTPUT_CHARS);
+    if truncated {
+        output.push_str("\n… output truncated");
+    }
+    output
+}
+
+pub async fn list_finding_actions() -> Result<ListFindingActionsResult, String> {
+    Ok(ListFindingActionsResult {
+        actions: configured_finding_actions()?,
+    })
+}
+
+pub async fn set_finding_actions(
+    state: State<'_, AppState>,
+    input: SetFindingActionsInput,
+) -> Result<ListFindingActionsResult, String> {
+    let actions = normalize_finding_actions(input.actions)?;
+    let settings = settings_store();
+    if input.persist.unwrap_or(true) {
+        let conn = state.connection()?;
+        settings
+            .set(&conn, ROVEX_FINDING_ACTIONS_ENV, &actions)
+            .await?;
+    } else {
+        let value = serde_json::to_value(&actions)
+            .map_err(|error| format!("Failed to serialize finding actions: {error}"))?;
+        settings.set_in_memory(ROVEX_FINDING_ACTIONS_ENV, value)?;
+    }
+    Ok(ListFindingActionsResult { actions })
+}
+
+pub async fn run_finding_action(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: RunFindingActionInput,
+) -> Result<RunFindingActionResult, String> {
+    let run_id = input.run_id.trim();
+    if run_id.is_empty() {
+        return Err("Run id must not be empty.".to_string());
+    }
+    let finding_id = input.finding_id.trim();
+    if finding_id.is_empty() {
+        return Err("Finding id must not be empty.".to_string());
+    }
+    let action_id = input.action_id.trim();
+    let action = configured_finding_actions()?
+        .into_iter()
+        .find(|action| action.id.eq_ignore_ascii_case(action_id))
+        .ok_or_else(|| format!("Finding action '{action_id}' is not configured."))?;
+    let kind = ActionKind::parse(&action.kind)?;
+
+    let (workspace, head, finding) =
+        store::load_ai_review_finding(&state, run_id, finding_id).await?;
+    let (repo_path, branch) = workspace_checkout(&workspace)?;
+    let context = ActionContext {
+        run_id,
+        workspace: &workspace,
+        branch: branch.as_deref(),
+        head: head.as_deref(),
+        finding: &finding,
+    };
+
+    let mut result = RunFindingActionResult {
+        action_id: action.id.clone(),
+        kind: kind.as_str().to_string(),
+        target: String::new(),
+        exit_code: None,
+        stdout: String::new(),
+        stderr: String::new(),
+        timed_out: false,
+    };
+    match kind {
+        ActionKind::Url => {
+            let url = expand_template(&action.template, &context, |_, value| percent_encode(value));
+            if !is_web_url(&url) {
+                return Err(format!(
+                    "Finding action '{}' did not expand to an http:// or https:// URL.",
+                    action.id
+                ));
+            }
+            app.opener()
+                .open_url(&url, None::<&str>)
+                .map_err(|error| format!("Failed to open {url}: {error}"))?;
+            result.target = url;
+        }
+        ActionKind::Command => {
+            let command_line = expand_command_template(&action.template, &context);
+            let child = shell_command(&command_line)
+                .envs(context.environment())
+                .current_dir(&repo_path)
+                .env("GIT_TERMINAL_PROMPT", "0")
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .kill_on_drop(true)
+                .spawn()
+                .map_err(|error| {
+                    format!("Failed to run finding action '{}': {error}", action.id)
+                })?;
+            match tokio::time::timeout(FINDING_ACTION_TIMEOUT, child.wait_with_output()).await {
+                Ok(output) => {
+                    let output = output.map_err(|error| {
+                        format!("Failed to run finding action '{}': {error}", action.id)
+                    })?;
+                    result.exit_code = output.status.code();
+                    result.stdout = truncate_output(&output.stdout);
+                    result.stderr = truncate_output(&output.stderr);
+                }
+                Err(_) => result.timed_out = true,
+            }
+            result.target = command_line;
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        expand_command_template, expand_template, normalize_finding_actions, percent_encode,
+        placeholder_env_var, ActionContext,
+    };
+    use crate::backend::{AiReviewFinding, FindingAction};
+
+    fn finding() -> AiReviewFinding {
+        AiReviewFinding {
+            id: "finding-1".to_string(),
+            file_path: "src/my file.rs".to_string(),
+            chunk_id: "chunk-1".to_string(),
+            chunk_index: 0,
+            hunk_header: "@@ -1,3 +1,4 @@".to_string(),
+            side: "new".to_string(),
+            line_number: 42,
+            title: "Don't unwrap".to_string(),
+            body: "This can panic.".to_string(),
+            severity: "high".to_string(),
+            confidence: None,
+            category: Some("bug".to_string()),
+            fingerprint: None,
+            status: None,
+            code_hash: None,
+            recurring: None,
+            suggested_patch: None,
+            language: Some("rust".to_string()),
+            blame: None,
+        }
+    }
+
+    #[test]
+    fn templates_fill_known_placeholders_with_escaped_values() {
+        let finding = finding();
+        let context = ActionContext {
+            run_id: "run-1",
+            workspace: "/repo",
+            branch: Some("feature/x"),
+            head: Some("abc123"),
+            finding: &finding,
+        };
+
+        assert_eq!(
+            expand_template(
+                "https://example.com/{branch}/{file}#L{line}?q={title}&raw={unknown}",
+                &context,
+                |_, value| percent_encode(value),
+            ),
+            "https://example.com/feature/x/src/my%20file.rs#L42?q=Don%27t%20unwrap&raw={unknown}"
+        );
+        assert_eq!(
+            expand_template("echo {severity} {{file}} {", &context, |_, value| {
+                format!("<{value}>")
+            }),
+            "echo <high> {<src/my file.rs>} {"
+        );
+    }
+
+    #[test]
+    fn command_templates_pass_finding_text_through_the_environment() {
+        let mut finding = finding();
+        finding.title = "x\" & calc & \"$(touch pwned)`id`'".to_string();
+        let context = ActionContext {
+            run_id: "run-1",
+            workspace: "/repo",
+            branch: None,
+            head: None,
+            finding: &finding,
+        };
+        assert_eq!(placeholder_env_var("runId"), "ROVEX_FINDING_RUN_ID");
+        assert_eq!(placeholder_env_var("findingId"), "ROVEX_FINDING_ID");
+
+        let command_line = expand_command_template("echo {title}", &context);
+        assert!(!command_line.contains("calc"));
+        assert!(command_line.contains("ROVEX_FINDING_TITLE"));
+
+        #[cfg(not(target_os = "windows"))]
+        for template in [
+            "printf %s {title}",
+            "printf %s \"{title}\"",
+            "printf %s '{title}'",
+        ] {
+            let command_line = expand_command_template(template, &context);
+            let output = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(&command_line)
+                .envs(context.environment())
+                .current_dir(std::env::temp_dir())
+                .output()
+                .expect("run sh");
+            let printed = String::from_utf8_lossy(&output.stdout);
+            assert!(!printed.contains("uid="), "{printed}");
+            assert_eq!(printed, finding.title);
+        }
+    }
+
+    #[test]
+    fn actions_are_validated_and_normalized() {
+        let action = |id: &str, kind: &str, template: &str| FindingAction {
+            id: id.to_string(),
+            label: String::new(),
+            kind: kind.to_string(),
+            template: template.to_string(),
+        };
+
+        let actions = normalize_finding_actions(vec![
+            action(
+                " ticket ",
+                "URL",
+                "https://tracker.example.com/new?file={file}",
+            ),
+            action("lint", "shell", "cargo clippy"),
+        ])
+        .expect("valid actions");
+        assert_eq!(actions[0].id, "ticket");
+        assert_eq!(actions[0].label, "ticket");
+        assert_eq!(actions[0].kind, "url");
+        assert_eq!(actions[1].kind, "command");
+
+        assert!(normalize_finding_actions(vec![action("x", "url", "file:///etc/passwd")]).is_err());
+        assert!(normalize_finding_actions(vec![
+            action("x", "command", "true"),
+            action("X", "command", "false"),
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use std::cmp::Ordering;
+
+use super::super::common::as_non_empty_trimmed;
+use super::super::name_sort::compare_paths;
+use crate::backend::{AiReviewFinding, AiReviewFindingGroup, AiReviewRun};
+
+const UNCATEGORIZED_GROUP: &str = "uncategorized";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum FindingSort {
+    /// Most severe first, then by file and line.
+    #[default]
+    Severity,
+    /// By file and line.
+    Location,
+    /// Most confident first; findings without a confidence come last.
+    Confidence,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FindingGrouping {
+    Severity,
+    File,
+    Category,
+}
+
+/// How findings are laid out, shared by `get_ai_review_run` and the report exports so both
+/// show the same order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct FindingOrder {
+    pub(crate) sort: FindingSort,
+    pub(crate) group: Option<FindingGrouping>,
+}
+
+impl FindingOrder {
+    pub(crate) fn parse(sort_by: Option<&str>, group_by: Option<&str>) -> Result<Self, String> {
+        let sort = match as_non_empty_trimmed(sort_by)
+            .map(|value| value.to_lowercase())
+            .as_deref()
+        {
+            None | Some("severity") => FindingSort::Severity,
+            Some("location" | "file") => FindingSort::Location,
+            Some("confidence") => FindingSort::Confidence,
+            Some(other) => {
+                return Err(format!(
+                    "Unsupported finding sort '{other}'. Expected 'severity', 'location' or 'confidence'."
+                ))
+            }
+        };
+        let group = match as_non_empty_trimmed(group_by)
+            .map(|value| value.to_lowercase())
+            .as_deref()
+        {
+            None | Some("none") => None,
+            Some("severity") => Some(FindingGrouping::Severity),
+            Some("file") => Some(FindingGrouping::File),
+            Some("category") => Some(FindingGrouping::Category),
+            Some(other) => {
+                return Err(format!(
+                    "Unsupported finding grouping '{other}'. Expected 'severity', 'file' or 'category'."
+                ))
+            }
+        };
+        Ok(Self { sort, group })
+    }
+}
+
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "critical" => 0,
+        "high" => 1,
+        "medium" => 2,
+        _ => 3,
+    }
+}
+
+fn group_key(finding: &AiReviewFinding, grouping: FindingGrouping) -> &str {
+    match grouping {
+        FindingGrouping::Severity => &finding.severity,
+        FindingGrouping::File => &finding.file_path,
+        FindingGrouping::Category => finding
+            .category
+            .as_deref()
+            .map(str::trim)
+            .filter(|category| !category.is_empty())
+            .unwrap_or(UNCATEGORIZED_GROUP),
+    }
+}
+
+/// Group order: severities most severe first, files by path, categories alphabetically with
+/// uncategorized findings last.
+fn compare_groups(
+    grouping: FindingGrouping,
+    left: &AiReviewFinding,
+    right: &AiReviewFinding,
+) -> Ordering {
+    let (left_key, right_key) = (group_key(left, grouping), group_key(right, grouping));
+    match grouping {
+        FindingGrouping::Severity => severity_rank(left_key)
+            .cmp(&severity_rank(right_key))
+            .then_with(|| left_key.cmp(right_key)),
+        FindingGrouping::File => compare_paths(left_key, right_key),
+        FindingGrouping::Category => (left_key == UNCATEGORIZED_GROUP)
+            .cmp(&(right_key == UNCATEGORIZED_GROUP))
+            .then_with(|| left_key.to_lowercase().cmp(&right_key.to_lowercase()))
+            .then_with(|| left_key.cmp(right_key)),
+    }
+}
+
+fn compare_findings(
+    sort: FindingSort,
+    left: &AiReviewFinding,
+    right: &AiReviewFinding,
+) -> Ordering {
+    let by_severity = || severity_rank(&left.severity).cmp(&severity_rank(&right.severity));
+    let by_location = || {
+        compare_paths(&left.file_path, &right.file_path)
+            .then_with(|| left.line_number.cmp(&right.line_number))
+    };
+    match sort {
+        FindingSort::Severity => by_severity().then_with(by_location),
+        FindingSort::Location => by_location().then_with(by_severity),
+        FindingSort::Confidence => match (left.confidence, right.confidence) {
+            (Some(left), Some(right)) => right.total_cmp(&left),
+            (left, right) => right.is_some().cmp(&left.is_some()),
+        }
+        .then_with(by_severity)
+        .then_with(by_location),
+    }
+    .then_with(|| left.id.cmp(&right.id))
+}
+
+/// Findings in display order: grouped together when a grouping is set, sorted within groups.
+pub(crate) fn ordered_findings<'a>(
+    findings: &'a [AiReviewFinding],
+    order: &FindingOrder,
+) -> Vec<&'a AiReviewFinding> {
+    let mut ordered = findings.iter().collect::<Vec<_>>();
+    ordered.sort_by(|left, right| {
+        order
+            .group
+            .map_or(Ordering::Equal, |grouping| {
+                compare_groups(grouping, left, right)
+            })
+            .then_with(|| compare_findings(order.sort, left, right))
+    });
+    ordered
+}
+
+/// Splits findings already in display order into their groups.
+pub(crate) fn group_findings<'a>(
+    ordered: &[&'a AiReviewFinding],
+    grouping: FindingGrouping,
+) -> Vec<(String, Vec<&'a AiReviewFinding>)> {
+    let mut groups: Vec<(String, Vec<&AiReviewFinding>)> = Vec::new();
+    for finding in ordered {
+        let key = group_key(finding, grouping);
+        match groups.last_mut() {
+            Some((last_key, members)) if last_key == key => members.push(finding),
+            _ => groups.push((key.to_string(), vec![finding])),
+        }
+    }
+    groups
+}
+
+/// Reorders a run's findings and suppressed findings and fills in its finding groups.
+pub(crate) fn organize_run_findings(run: &mut AiReviewRun, order: &FindingOrder) {
+    let findings = ordered_findings(&run.findings, order);
+    run.finding_groups = order
+        .group
+        .map(|grouping| {
+            group_findings(&findings, grouping)
+                .into_iter()
+                .map(|(key, members)| AiReviewFindingGroup {
+                    key,
+                    finding_ids: members.iter().map(|finding| finding.id.clone()).collect(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    run.findings = findings.into_iter().cloned().collect();
+    run.suppressed_findings = ordered_findings(&run.suppressed_findings, order)
+        .into_iter()
+        .cloned()
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{group_findings, ordered_findings, FindingGrouping, FindingOrder, FindingSort};
+    use crate::backend::AiReviewFinding;
+
+    fn finding(
+        file_path: &str,
+        line_number: i64,
+        severity: &str,
+        confidence: Option<f64>,
+    ) -> AiReviewFinding {
+        AiReviewFinding {
+            id: format!("{file_path}:{line_number}"),
+            file_path: file_path.to_string(),
+            chunk_id: format!("{file_path}#file-1"),
+            chunk_index: 1,
+            hunk_header: "@@ -1 +1 @@".to_string(),
+            side: "additions".to_string(),
+            line_number,
+            title: "title".to_string(),
+            body: "body".to_string(),
+            severity: severity.to_string(),
+            confidence,
+            category: None,
+            fingerprint: None,
+            status: None,
+            code_hash: None,
+            recurring: None,
+            suggested_patch: None,
+            language: None,
+            blame: None,
+        }
+    }
+
+    fn ids(findings: &[&AiReviewFinding]) -> Vec<String> {
+        findings.iter().map(|finding| finding.id.clone()).collect()
+    }
+
+    #[test]
+    fn groups_stay_together_and_sort_within() {
+        let findings = [
+            finding("src/b.rs", 4, "low", Some(0.9)),
+            finding("src/a.rs", 9, "high", None),
+            finding("src/b.rs", 2, "high", Some(0.4)),
+            finding("src/a.rs", 1, "low", Some(0.7)),
+        ];
+
+        let by_confidence = FindingOrder::parse(Some("confidence"), None).unwrap();
+        assert_eq!(by_confidence.sort, FindingSort::Confidence);
+        assert_eq!(
+            ids(&ordered_findings(&findings, &by_confidence)),
+            ["src/b.rs:4", "src/a.rs:1", "src/b.rs:2", "src/a.rs:9"]
+        );
+
+        let by_file = FindingOrder::parse(None, Some("file")).unwrap();
+        let ordered = ordered_findings(&findings, &by_file);
+        assert_eq!(
+            ids(&ordered),
+            ["src/a.rs:9", "src/a.rs:1", "src/b.rs:2", "src/b.rs:4"]
+        );
+        let groups = group_findings(&ordered, FindingGrouping::File);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[1].0, "src/b.rs");
+        assert_eq!(ids(&groups[1].1), ["src/b.rs:2", "src/b.rs:4"]);
+
+        assert!(FindingOrder::parse(Some("newest"), None).is_err());
+    }
+}
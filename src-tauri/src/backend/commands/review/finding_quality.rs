@@ -0,0 +1,173 @@
+use super::diff_chunks::ChunkReviewPayload;
+use super::findings::normalize_finding_title;
+
+/// Body given to findings the model returned without one.
+pub(crate) const PLACEHOLDER_FINDING_BODY: &str = "Potential issue detected in this diff chunk.";
+
+/// Changed lines shorter than this are too generic to count as quoted code in a finding body.
+const MIN_QUOTED_LINE_CHARS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FindingQualityIssue {
+    /// The body is missing, the placeholder, or a copy of the title.
+    Boilerplate,
+    TooShort,
+    /// Nothing is left of the body once the changed lines it quotes are removed.
+    RestatesDiff,
+}
+
+impl FindingQualityIssue {
+    fn describe(self) -> &'static str {
+        match self {
+            Self::Boilerplate => "has no real explanation",
+            Self::TooShort => "is too short to act on",
+            Self::RestatesDiff => "only restates the changed code",
+        }
+    }
+}
+
+fn normalize_text(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn changed_lines(patch: &str) -> Vec<String> {
+    let mut in_hunk = false;
+    let mut lines = Vec::new();
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            in_hunk = true;
+            continue;
+        }
+        if !in_hunk {
+            continue;
+        }
+        if let Some(text) = line.strip_prefix('+').or_else(|| line.strip_prefix('-')) {
+            let text = normalize_text(text);
+            if text.chars().count() >= MIN_QUOTED_LINE_CHARS {
+                lines.push(text);
+            }
+        }
+    }
+    // Longest first, so a line is removed whole before a shorter line inside it.
+    lines.sort_by_key(|line| std::cmp::Reverse(line.len()));
+    lines
+}
+
+/// Checks whether a finding's body explains anything beyond its title and the diff it was
+/// raised on. `min_body_chars` of 0 disables the checks.
+pub(crate) fn finding_quality_issue(
+    title: &str,
+    body: &str,
+    chunk_patch: &str,
+    min_body_chars: usize,
+) -> Option<FindingQualityIssue> {
+    if min_body_chars == 0 {
+        return None;
+    }
+    let body = normalize_text(body);
+    if body.is_empty()
+        || body == PLACEHOLDER_FINDING_BODY
+        || normalize_finding_title(&body) == normalize_finding_title(title)
+    {
+        return Some(FindingQualityIssue::Boilerplate);
+    }
+    if body.chars().count() < min_body_chars {
+        return Some(FindingQualityIssue::TooShort);
+    }
+    let mut explanation = body.replace('`', "");
+    for line in changed_lines(chunk_patch) {
+        explanation = explanation.replace(&line, " ");
+    }
+    if normalize_text(&explanation).chars().count() < min_body_chars {
+        return Some(FindingQualityIssue::RestatesDiff);
+    }
+    None
+}
+
+/// Findings of a chunk review that fail [`finding_quality_issue`], as (1-based position,
+/// title, issue).
+pub(crate) fn weak_chunk_findings(
+    payload: &ChunkReviewPayload,
+    chunk_patch: &str,
+    min_body_chars: usize,
+) -> Vec<(usize, String, FindingQualityIssue)> {
+    payload
+        .findings
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, finding)| {
+            let title = finding.title.as_deref().unwrap_or_default().trim();
+            let body = finding.body.as_deref().unwrap_or_default();
+            finding_quality_issue(title, body, chunk_patch, min_body_chars)
+                .map(|issue| (index + 1, title.to_string(), issue))
+        })
+        .collect()
+}
+
+/// Asks the model to explain weak findings from its previous answer, or drop them.
+pub(crate) fn build_finding_elaboration_prompt(
+    chunk_prompt: &str,
+    previous_response: &str,
+    weak_findings: &[(usize, String, FindingQualityIssue)],
+) -> String {
+    let weak_list = weak_findings
+        .iter()
+        .map(|(position, title, issue)| format!("- #{position} \"{title}\" {}", issue.describe()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "{chunk_prompt}\n\nYour previous response was:\n```json\n{}\n```\n\nThese findings need a clearer explanation:\n{weak_list}\n\nReturn the full STRICT JSON again with the same schema. For each listed finding, explain in the body what goes wrong at runtime and why, or drop the finding if it is not a real bug. Keep the other findings unchanged.",
+        previous_response.trim()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{finding_quality_issue, FindingQualityIssue, PLACEHOLDER_FINDING_BODY};
+
+    const PATCH: &str = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,2 @@\n fn load() {\n-    let value = read().ok();\n+    let value = read().unwrap();\n";
+
+    #[test]
+    fn boilerplate_and_short_bodies_are_rejected() {
+        assert_eq!(
+            finding_quality_issue("Unchecked unwrap", PLACEHOLDER_FINDING_BODY, PATCH, 40),
+            Some(FindingQualityIssue::Boilerplate)
+        );
+        assert_eq!(
+            finding_quality_issue("Unchecked unwrap", "Unchecked unwrap.", PATCH, 40),
+            Some(FindingQualityIssue::Boilerplate)
+        );
+        assert_eq!(
+            finding_quality_issue("Unchecked unwrap", "This may panic.", PATCH, 40),
+            Some(FindingQualityIssue::TooShort)
+        );
+        assert_eq!(
+            finding_quality_issue("Unchecked unwrap", "This may panic.", PATCH, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn bodies_that_only_quote_the_diff_are_rejected() {
+        assert_eq!(
+            finding_quality_issue(
+                "Unchecked unwrap",
+                "Changed `let value = read().ok();` to `let value = read().unwrap();`",
+                PATCH,
+                40
+            ),
+            Some(FindingQualityIssue::RestatesDiff)
+        );
+        assert_eq!(
+            finding_quality_issue(
+                "Unchecked unwrap",
+                "`let value = read().unwrap();` panics when the config file is missing, which used to fall back to defaults.",
+                PATCH,
+                40
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+
+use crate::backend::AiReviewFinding;
+
+pub(crate) const FINDING_STATUS_OPEN: &str = "open";
+pub(crate) const FINDING_STATUS_DISMISSED: &str = "dismissed";
+pub(crate) const FINDING_STATUS_FIXED: &str = "fixed";
+
+/// Commits of a reviewed range whose messages are scanned for the findings they fix.
+pub(crate) const FIX_COMMIT_SCAN_COMMITS: usize = 200;
+/// Open findings of a workspace looked for in those commit messages.
+pub(crate) const FIX_COMMIT_SCAN_FINDINGS: usize = 500;
+
+/// Earlier finished runs of a thread whose findings are checked for repeats.
+pub(crate) const RECURRING_LOOKBACK_RUNS: i64 = 5;
+/// Share of title words two findings on the same flagged code must have in common to count
+/// as the same finding.
+const RECURRING_TITLE_SIMILARITY: f64 = 0.5;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+pub(crate) fn fnv1a(parts: &[&str]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    for (index, part) in parts.iter().enumerate() {
+        if index > 0 {
+            hash ^= 0xff;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+        for byte in part.as_bytes() {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    hash
+}
+
+/// Lowercases a finding title and keeps only its words, so rephrasings that differ in
+/// punctuation, casing, or spacing compare equal.
+pub(crate) fn normalize_finding_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|character: char| !character.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn normalize_code_line(code_line: &str) -> String {
+    code_line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Identifies "the same finding" across runs from its file, normalized title, and the
+/// whitespace-normalized code on the flagged line when known. Line numbers, run-scoped ids,
+/// and the model-chosen category are left out because they shift between runs.
+pub(crate) fn finding_fingerprint(finding: &AiReviewFinding, code_line: Option<&str>) -> String {
+    let title = normalize_finding_title(&finding.title);
+    let code = code_line.map(normalize_code_line).unwrap_or_default();
+    let hash = if code.is_empty() {
+        fnv1a(&[finding.file_path.trim(), &title])
+    } else {
+        fnv1a(&[finding.file_path.trim(), &title, &code])
+    };
+    format!("{hash:016x}")
+}
+
+/// Hashes the whitespace-normalized code on a finding's flagged line, so findings can be
+/// compared by the code they point at even when their titles are worded differently.
+pub(crate) fn code_line_hash(code_line: &str) -> Option<String> {
+    let code = normalize_code_line(code_line);
+    (!code.is_empty()).then(|| format!("{:016x}", fnv1a(&[&code])))
+}
+
+fn title_similarity(left: &str, right: &str) -> f64 {
+    let left_title = normalize_finding_title(left);
+    let right_title = normalize_finding_title(right);
+    let left_words = left_title.split(' ').collect::<HashSet<_>>();
+    let right_words = right_title.split(' ').collect::<HashSet<_>>();
+    let union = left_words.union(&right_words).count();
+    if union == 0 {
+        return 0.0;
+    }
+    left_words.intersection(&right_words).count() as f64 / union as f64
+}
+
+/// Whether `finding` repeats `previous`: either the same fingerprint, or a similarly titled
+/// finding on the same code in the same file.
+pub(crate) fn is_same_finding(finding: &AiReviewFinding, previous: &AiReviewFinding) -> bool {
+    if previous.fingerprint.is_some() && previous.fingerprint == finding.fingerprint {
+        return true;
+    }
+    previous.file_path == finding.file_path
+        && previous.code_hash.is_some()
+        && previous.code_hash == finding.code_hash
+        && title_similarity(&previous.title, &finding.title) >= RECURRING_TITLE_SIMILARITY
+}
+
+/// Whether `finding` repeats one of `prior`.
+pub(crate) fn is_recurring_finding(finding: &AiReviewFinding, prior: &[AiReviewFinding]) -> bool {
+    prior
+        .iter()
+        .any(|previous| is_same_finding(finding, previous))
+}
+
+/// Whether a commit message names a finding, either by its fingerprint as a separate word,
+/// as in `Handle empty config (fixes 3f2a9c1d0b4e5f67)`, or by its full finding id.
+pub(crate) fn commit_message_references_finding(
+    message: &str,
+    fingerprint: &str,
+    finding_id: &str,
+) -> bool {
+    let fingerprint = fingerprint.trim();
+    let names_fingerprint = !fingerprint.is_empty()
+        && message
+            .split(|character: char| !character.is_ascii_alphanumeric())
+            .any(|word| word.eq_ignore_ascii_case(fingerprint));
+    let finding_id = finding_id.trim();
+    names_fingerprint || (!finding_id.is_empty() && message.contains(finding_id))
+}
+
+/// Validates a commit hash given for a fixed finding: 7 to 64 hex digits, lowercased.
+pub(crate) fn parse_fix_commit(value: Option<&str>) -> Result<Option<String>, String> {
+    let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    if !(7..=64).contains(&value.len())
+        || !value.chars().all(|character| character.is_ascii_hexdigit())
+    {
+        return Err(format!("Fix commit '{value}' is not a commit hash."));
+    }
+    Ok(Some(value.to_ascii_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        code_line_hash, commit_message_references_finding, finding_fingerprint,
+        is_recurring_finding, normalize_finding_title, parse_fix_commit,
+    };
+    use crate::backend::AiReviewFinding;
+
+    fn finding(file_path: &str, title: &str, line_number: i64) -> AiReviewFinding {
+        AiReviewFinding {
+            id: format!("{file_path}:{line_number}"),
+            file_path: file_path.to_string(),
+            chunk_id: format!("{file_path}#file-1"),
+            chunk_index: 1,
+            hunk_header: "@@ -1 +1 @@".to_string(),
+            side: "additions".to_string(),
+            line_number,
+            title: title.to_string(),
+            body: "body".to_string(),
+            severity: "high".to_string(),
+            confidence: None,
+            category: None,
+            fingerprint: None,
+            status: None,
+            code_hash: None,
+            recurring: None,
+            suggested_patch: None,
+            language: None,
+            blame: None,
+        }
+    }
+
+    #[test]
+    fn normalize_finding_title_ignores_case_and_punctuation() {
+        assert_eq!(
+            normalize_finding_title("  Unchecked `unwrap()` on user input! "),
+            "unchecked unwrap on user input"
+        );
+    }
+
+    #[test]
+    fn fingerprint_is_stable_across_line_moves() {
+        let first = finding("src/lib.rs", "Unchecked unwrap", 10);
+        let moved = finding("src/lib.rs", "unchecked UNWRAP.", 42);
+        let other_file = finding("src/main.rs", "Unchecked unwrap", 10);
+        assert_eq!(
+            finding_fingerprint(&first, None),
+            finding_fingerprint(&moved, None)
+        );
+        assert_ne!(
+            finding_fingerprint(&first, None),
+            finding_fingerprint(&other_file, None)
+        );
+    }
+
+    #[test]
+    fn fingerprint_tracks_flagged_code_but_not_its_indentation() {
+        let first = finding("src/lib.rs", "Unchecked unwrap", 10);
+        let moved = finding("src/lib.rs", "Unchecked unwrap", 42);
+        assert_eq!(
+            finding_fingerprint(&first, Some("let value = input.unwrap();")),
+            finding_fingerprint(&moved, Some("        let value =  input.unwrap();"))
+        );
+        assert_ne!(
+            finding_fingerprint(&first, Some("let value = input.unwrap();")),
+            finding_fingerprint(&moved, Some("let value = other.unwrap();"))
+        );
+    }
+
+    #[test]
+    fn recurring_findings_match_reworded_titles_on_the_same_code() {
+        let code = "let value = input.unwrap();";
+        let mut previous = finding("src/lib.rs", "Unchecked unwrap on user input", 10);
+        previous.fingerprint = Some(finding_fingerprint(&previous, Some(code)));
+        previous.code_hash = code_line_hash(code);
+
+        let mut reworded = finding("src/lib.rs", "Unwrap on user input can panic", 12);
+        reworded.fingerprint = Some(finding_fingerprint(&reworded, Some(code)));
+        reworded.code_hash = code_line_hash(code);
+        assert!(is_recurring_finding(
+            &reworded,
+            std::slice::from_ref(&previous)
+        ));
+
+        let mut unrelated = finding("src/lib.rs", "Missing bounds check", 12);
+        unrelated.fingerprint = Some(finding_fingerprint(&unrelated, Some(code)));
+        unrelated.code_hash = code_line_hash(code);
+        assert!(!is_recurring_finding(&unrelated, &[previous]));
+    }
+
+    #[test]
+    fn commit_messages_reference_findings_by_fingerprint_or_id() {
+        let id = "src/config.rs#file-1:additions:3:1";
+        assert!(commit_message_references_finding(
+            "Handle empty config\n\nFixes 3F2A9C1D0B4E5F67.",
+            "3f2a9c1d0b4e5f67",
+            id
+        ));
+        assert!(commit_message_references_finding(
+            &format!("Fix rovex finding {id}"),
+            "3f2a9c1d0b4e5f67",
+            id
+        ));
+        assert!(!commit_message_references_finding(
+            "Bump 3f2a9c1d0b4e5f678 in lockfile",
+            "3f2a9c1d0b4e5f67",
+            id
+        ));
+
+        assert_eq!(
+            parse_fix_commit(Some(" ABC1234 ")).unwrap().as_deref(),
+            Some("abc1234")
+        );
+        assert_eq!(parse_fix_commit(Some("  ")).unwrap(), None);
+        assert!(parse_fix_commit(Some("main")).is_err());
+    }
+}
@@ -1,24 +1,66 @@
-use std::env;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use tauri::{AppHandle, State};
 
 use super::super::common::{
-    as_non_empty_trimmed, parse_env_u64, parse_env_usize, truncate_chars,
-    DEFAULT_FOLLOW_UP_HISTORY_CHARS, DEFAULT_REVIEW_BASE_URL, DEFAULT_REVIEW_MODEL,
-    DEFAULT_REVIEW_TIMEOUT_MS, MAX_FOLLOW_UP_MESSAGES, OPENAI_API_KEY_ENV,
-    ROVEX_REVIEW_BASE_URL_ENV, ROVEX_REVIEW_MAX_DIFF_CHARS_ENV, ROVEX_REVIEW_MODEL_ENV,
-    ROVEX_REVIEW_TIMEOUT_MS_ENV,
+    as_non_empty_trimmed, current_openai_api_key, parse_setting_u64, parse_setting_usize,
+    truncate_chars, DEFAULT_FOLLOW_UP_CACHE_TTL_SECONDS, DEFAULT_FOLLOW_UP_HISTORY_CHARS,
+    DEFAULT_REVIEW_BASE_URL, DEFAULT_REVIEW_MODEL, DEFAULT_REVIEW_TIMEOUT_MS,
+    MAX_ATTACHMENTS_PER_MESSAGE, MAX_FOLLOW_UP_DIFF_CHARS, MAX_FOLLOW_UP_MESSAGES,
+    OPENAI_API_KEY_ENV, ROVEX_FOLLOW_UP_CACHE_TTL_SECONDS_ENV, ROVEX_REVIEW_BASE_URL_ENV,
+    ROVEX_REVIEW_MAX_DIFF_CHARS_ENV, ROVEX_REVIEW_MODEL_ENV, ROVEX_REVIEW_TIMEOUT_MS_ENV,
+};
+use super::super::message_attachments::{
+    insert_message_attachment, load_thread_attachments, prepare_attachment,
+};
+use super::super::pull_request_comments::{
+    format_pull_request_comments, load_thread_pull_request_comments,
 };
 use super::super::threads::{
-    load_recent_thread_messages, load_thread_by_id, persist_thread_message,
+    delete_thread_message, load_recent_thread_messages, load_thread_by_id, persist_thread_message,
+};
+use super::super::workspace_trust::is_workspace_trusted;
+use super::ai_requests::AiRequest;
+use super::follow_up_cache::{
+    cached_follow_up, follow_up_cache_key, lock_follow_up, store_follow_up,
 };
+use super::follow_up_diff::{
+    format_follow_up_diff, load_thread_diff_chunks, select_follow_up_chunks,
+};
+use super::follow_up_tools::{openai_tool_definitions, WorkspaceTools, MAX_FOLLOW_UP_TOOL_ROUNDS};
+use super::prompt_safety::{fence_untrusted, UNTRUSTED_CONTENT_RULE};
+use super::store::load_recent_thread_findings;
 use super::transports::{app_server, openai, opencode};
 use super::ReviewProvider;
+use crate::backend::settings::settings_store;
 use crate::backend::{
-    AppState, GenerateAiFollowUpInput, GenerateAiFollowUpResult, Message, MessageRole, Thread,
+    AppState, FollowUpToolCall, GenerateAiFollowUpInput, GenerateAiFollowUpResult, Message,
+    MessageAttachment, MessageRole, PullRequestComment, Thread,
 };
 
-fn format_follow_up_history(messages: &[Message], max_chars: usize) -> (String, bool) {
+fn format_attachments(attachments: &[MessageAttachment]) -> String {
+    attachments
+        .iter()
+        .map(|attachment| {
+            format!(
+                "Attachment {} ({}):\n{}",
+                attachment.name,
+                attachment.mime_type,
+                fence_untrusted("attachment", &attachment.content)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_follow_up_history(
+    messages: &[Message],
+    attachments: &HashMap<i64, Vec<MessageAttachment>>,
+    imported_comments: &HashMap<i64, Vec<PullRequestComment>>,
+    max_chars: usize,
+) -> (String, bool) {
     let mut entries = Vec::new();
     for message in messages {
         let content = message.content.trim();
@@ -30,28 +72,175 @@ fn format_follow_up_history(messages: &[Message], max_chars: usize) -> (String,
             MessageRole::User => "User",
             MessageRole::Assistant => "Assistant",
         };
-        entries.push(format!("{role}: {content}"));
+        let mut entry = format!("{role}: {content}");
+        if let Some(attachments) = attachments.get(&message.id) {
+            entry.push('\n');
+            entry.push_str(&format_attachments(attachments));
+        }
+        // Imported review comments hang off the system message that recorded the import.
+        if let Some(comments) = imported_comments.get(&message.id) {
+            entry.push('\n');
+            entry.push_str(&format_pull_request_comments(comments));
+        }
+        entries.push(entry);
     }
 
     let joined = entries.join("\n\n");
     truncate_chars(&joined, max_chars)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_follow_up_prompt(
     thread: &Thread,
     workspace: &str,
     question: &str,
+    question_attachments: &str,
     history: &str,
     history_truncated: bool,
+    diff_context: Option<&str>,
+    has_untrusted_content: bool,
 ) -> String {
-    format!(
-        "Continue this code review conversation.\n\nThread: {}\nWorkspace: {}\nConversation history truncated: {}\n\nConversation history:\n{}\n\nUser follow-up question:\n{}\n\nAnswer only based on available context. If context is missing, say exactly what is missing. Keep the answer concise and actionable.",
+    let question = if question_attachments.is_empty() {
+        question.to_string()
+    } else {
+        format!("{question}\n{question_attachments}")
+    };
+    let diff_context = diff_context
+        .map(|diff| format!("\n\nDiff of the files this question is about:\n{diff}"))
+        .unwrap_or_default();
+    let prompt = format!(
+        "Continue this code review conversation.\n\nThread: {}\nWorkspace: {}\nConversation history truncated: {}\n\nConversation history:\n{}{}\n\nUser follow-up question:\n{}\n\nAnswer only based on available context. If context is missing, say exactly what is missing. Keep the answer concise and actionable.",
         thread.title,
         workspace,
         if history_truncated { "yes" } else { "no" },
         history,
+        diff_context,
         question
-    )
+    );
+    // Attachments, imported comments and the diff are all fenced, so the same rule tells the
+    // model how to read them.
+    if has_untrusted_content {
+        format!("{prompt}\n\n{UNTRUSTED_CONTENT_RULE}")
+    } else {
+        prompt
+    }
+}
+
+/// The configured review model and request timeout.
+fn review_model_settings() -> (String, u64) {
+    let model = settings_store()
+        .get_string(ROVEX_REVIEW_MODEL_ENV)
+        .unwrap_or_else(|| DEFAULT_REVIEW_MODEL.to_string());
+    let timeout_ms = parse_setting_u64(
+        ROVEX_REVIEW_TIMEOUT_MS_ENV,
+        DEFAULT_REVIEW_TIMEOUT_MS,
+        1_000,
+    );
+    (model, timeout_ms)
+}
+
+/// The OpenAI API key and base URL.
+fn openai_settings() -> Result<(String, String), String> {
+    let api_key = current_openai_api_key().ok_or_else(|| {
+        format!("Missing {OPENAI_API_KEY_ENV}. Add it in Settings to enable AI review.")
+    })?;
+    let base_url = settings_store()
+        .get_string(ROVEX_REVIEW_BASE_URL_ENV)
+        .unwrap_or_else(|| DEFAULT_REVIEW_BASE_URL.to_string());
+    Ok((api_key, base_url))
+}
+
+/// Sends a one-off prompt to the configured review provider and returns the answer with the
+/// model that produced it. Callers check workspace trust first.
+pub(crate) async fn generate_text_with_review_provider(
+    app: &AppHandle,
+    review_provider: ReviewProvider,
+    workspace: &str,
+    prompt: &str,
+) -> Result<(String, String), String> {
+    let (model, timeout_ms) = review_model_settings();
+
+    match review_provider {
+        ReviewProvider::OpenAi => {
+            let (api_key, base_url) = openai_settings()?;
+            let answer = openai::generate_review_with_openai(
+                &model, &base_url, timeout_ms, &api_key, prompt,
+            )
+            .await?;
+            Ok((answer, model))
+        }
+        ReviewProvider::Opencode => {
+            opencode::generate_review_with_opencode(app, workspace, prompt, timeout_ms, &model)
+                .await
+        }
+        ReviewProvider::AppServer => {
+            app_server::generate_review_with_app_server(workspace, prompt, timeout_ms, &model).await
+        }
+    }
+}
+
+/// Answers a follow-up prompt while letting the model read and search the workspace through
+/// `tools`. Every tool call, including declined app-server commands, is added to `tool_calls`.
+async fn generate_follow_up_with_tools(
+    review_provider: ReviewProvider,
+    workspace: &str,
+    prompt: &str,
+    tools: &WorkspaceTools,
+    tool_calls: &mut Vec<FollowUpToolCall>,
+) -> Result<(String, String), String> {
+    let (model, timeout_ms) = review_model_settings();
+
+    match review_provider {
+        ReviewProvider::OpenAi => {
+            let (api_key, base_url) = openai_settings()?;
+            let answer = openai::generate_follow_up_with_openai_tools(
+                &model,
+                &base_url,
+                timeout_ms,
+                &api_key,
+                prompt,
+                &openai_tool_definitions(),
+                MAX_FOLLOW_UP_TOOL_ROUNDS,
+                |name, arguments| {
+                    let result = tools.execute(name, arguments);
+                    tool_calls.push(FollowUpToolCall {
+                        tool: name.to_string(),
+                        input: arguments.to_string(),
+                        error: result.as_ref().err().cloned(),
+                    });
+                    result.unwrap_or_else(|error| format!("Error: {error}"))
+                },
+            )
+            .await?;
+            Ok((answer, model))
+        }
+        ReviewProvider::AppServer => {
+            let mut approve = |command: &[String], cwd: Option<&str>| {
+                let allowed = tools.allows_command(command, cwd);
+                tool_calls.push(FollowUpToolCall {
+                    tool: "command".to_string(),
+                    input: command.join(" "),
+                    error: (!allowed).then(|| {
+                        "Declined: only read-only commands inside the workspace can run."
+                            .to_string()
+                    }),
+                });
+                allowed
+            };
+            app_server::generate_follow_up_with_app_server(
+                workspace,
+                prompt,
+                timeout_ms,
+                &model,
+                &mut approve,
+            )
+            .await
+        }
+        ReviewProvider::Opencode => Err(
+            "Tool access for follow-up questions needs the 'openai' or 'app-server' review provider."
+                .to_string(),
+        ),
+    }
 }
 
 pub async fn generate_ai_follow_up(
@@ -67,6 +256,28 @@ pub async fn generate_ai_follow_up(
     if question.is_empty() {
         return Err("Question must not be empty.".to_string());
     }
+    let attachment_inputs = input.attachments.unwrap_or_default();
+    let attachments = attachment_inputs
+        .iter()
+        .map(prepare_attachment)
+        .collect::<Result<Vec<_>, _>>()?;
+    if attachments.len() as i64 > MAX_ATTACHMENTS_PER_MESSAGE {
+        return Err(format!(
+            "A message can have at most {MAX_ATTACHMENTS_PER_MESSAGE} attachments."
+        ));
+    }
+    let use_tools = input.use_tools.unwrap_or(false);
+    let request = AiRequest::register(input.request_id.as_deref())?;
+    // Taken before the history is read, so a re-submit waits for the first answer and then
+    // sees it in the history and the cache.
+    let _in_flight = lock_follow_up(follow_up_cache_key(
+        input.thread_id,
+        "",
+        question,
+        &attachment_inputs,
+        use_tools,
+    ))
+    .await;
 
     let recent_messages =
         load_recent_thread_messages(&state, input.thread_id, MAX_FOLLOW_UP_MESSAGES).await?;
@@ -77,84 +288,185 @@ pub async fn generate_ai_follow_up(
         return Err("Start review before asking follow-up questions.".to_string());
     }
 
-    let history_limit = parse_env_usize(
+    let history_limit = parse_setting_usize(
         ROVEX_REVIEW_MAX_DIFF_CHARS_ENV,
         DEFAULT_FOLLOW_UP_HISTORY_CHARS,
         1_000,
     );
-    let (history, history_truncated) = format_follow_up_history(&recent_messages, history_limit);
+    let mut history_attachments = HashMap::<i64, Vec<MessageAttachment>>::new();
+    for attachment in load_thread_attachments(&state, input.thread_id).await? {
+        history_attachments
+            .entry(attachment.message_id)
+            .or_default()
+            .push(attachment);
+    }
+    let mut imported_comments = HashMap::<i64, Vec<PullRequestComment>>::new();
+    for comment in load_thread_pull_request_comments(&state, input.thread_id).await? {
+        imported_comments
+            .entry(comment.message_id)
+            .or_default()
+            .push(comment);
+    }
+    let (history, history_truncated) = format_follow_up_history(
+        &recent_messages,
+        &history_attachments,
+        &imported_comments,
+        history_limit,
+    );
     if history.trim().is_empty() {
         return Err("No conversation history available for follow-up.".to_string());
     }
-
-    let follow_up_prompt =
-        build_follow_up_prompt(&thread, &workspace, question, &history, history_truncated);
-    let review_provider = ReviewProvider::from_env()?;
-    let model = env::var(ROVEX_REVIEW_MODEL_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
-        .unwrap_or_else(|| DEFAULT_REVIEW_MODEL.to_string());
-    let timeout_ms = parse_env_u64(
-        ROVEX_REVIEW_TIMEOUT_MS_ENV,
-        DEFAULT_REVIEW_TIMEOUT_MS,
-        1_000,
+    let cache_key = follow_up_cache_key(
+        input.thread_id,
+        &history,
+        question,
+        &attachment_inputs,
+        use_tools,
     );
 
-    persist_thread_message(&state, input.thread_id, MessageRole::User, question).await?;
+    let review_provider = ReviewProvider::from_settings()?;
+    let workspace_trusted = is_workspace_trusted(&state, &workspace).await?;
+    review_provider.ensure_allowed_for_workspace(&workspace, workspace_trusted)?;
+    // Tools hand workspace files to the model, which untrusted workspaces never do.
+    let tools = if use_tools {
+        if !workspace_trusted {
+            return Err(format!(
+                "Workspace {workspace} is untrusted, so follow-up questions cannot read its files. Mark the workspace as trusted to use tools."
+            ));
+        }
+        Some(WorkspaceTools::new(&workspace)?)
+    } else {
+        None
+    };
+    if let Some(cached) = cached_follow_up(cache_key) {
+        return Ok(GenerateAiFollowUpResult {
+            thread_id: input.thread_id,
+            workspace,
+            model: cached.model,
+            answer: cached.answer,
+            tool_calls: cached.tool_calls,
+            cached: true,
+        });
+    }
 
-    let (answer, resolved_model) = match review_provider {
-        ReviewProvider::OpenAi => {
-            let api_key = env::var(OPENAI_API_KEY_ENV)
-                .ok()
-                .map(|value| value.trim().to_string())
-                .filter(|value| !value.is_empty())
-                .ok_or_else(|| {
-                    format!("Missing {OPENAI_API_KEY_ENV}. Add it to .env to enable AI review.")
-                })?;
-            let base_url = env::var(ROVEX_REVIEW_BASE_URL_ENV)
-                .ok()
-                .map(|value| value.trim().to_string())
-                .filter(|value| !value.is_empty())
-                .unwrap_or_else(|| DEFAULT_REVIEW_BASE_URL.to_string());
+    let diff_chunks = load_thread_diff_chunks(&state, input.thread_id).await?;
+    let findings = if diff_chunks.is_empty() {
+        Vec::new()
+    } else {
+        load_recent_thread_findings(&state, input.thread_id, None, 1).await?
+    };
+    let diff_context = format_follow_up_diff(
+        &select_follow_up_chunks(&diff_chunks, &findings, question),
+        MAX_FOLLOW_UP_DIFF_CHARS,
+    );
 
-            let answer = openai::generate_review_with_openai(
-                &model,
-                &base_url,
-                timeout_ms,
-                &api_key,
-                &follow_up_prompt,
-            )
-            .await?;
-            (answer, model.clone())
+    let question_id =
+        persist_thread_message(&state, input.thread_id, MessageRole::User, question).await?;
+    let mut question_attachments = Vec::new();
+    if let Some(question_id) = question_id {
+        for attachment in attachments {
+            question_attachments.push(
+                insert_message_attachment(&state, question_id, input.thread_id, attachment).await?,
+            );
         }
-        ReviewProvider::Opencode => {
-            opencode::generate_review_with_opencode(
-                &app,
-                &workspace,
-                &follow_up_prompt,
-                timeout_ms,
-                &model,
-            )
-            .await?
-        }
-        ReviewProvider::AppServer => {
-            app_server::generate_review_with_app_server(
-                &workspace,
-                &follow_up_prompt,
-                timeout_ms,
-                &model,
-            )
-            .await?
+    }
+    let follow_up_prompt = build_follow_up_prompt(
+        &thread,
+        &workspace,
+        question,
+        &format_attachments(&question_attachments),
+        &history,
+        history_truncated,
+        diff_context.as_deref(),
+        diff_context.is_some()
+            || !question_attachments.is_empty()
+            || !history_attachments.is_empty()
+            || !imported_comments.is_empty(),
+    );
+
+    let mut tool_calls = Vec::new();
+    let generated = request
+        .run(async {
+            match tools.as_ref() {
+                Some(tools) => {
+                    generate_follow_up_with_tools(
+                        review_provider,
+                        &workspace,
+                        &follow_up_prompt,
+                        tools,
+                        &mut tool_calls,
+                    )
+                    .await
+                }
+                None => {
+                    generate_text_with_review_provider(
+                        &app,
+                        review_provider,
+                        &workspace,
+                        &follow_up_prompt,
+                    )
+                    .await
+                }
+            }
+        })
+        .await;
+    let (answer, resolved_model) = match generated {
+        Ok(generated) => generated,
+        Err(error) => {
+            // A canceled question was usually sent by mistake, so it is taken back out of
+            // the thread along with its attachments.
+            if request.cancel_flag().load(Ordering::Relaxed) {
+                if let Some(question_id) = question_id {
+                    delete_thread_message(&state, question_id).await?;
+                }
+            }
+            return Err(error);
         }
     };
 
     persist_thread_message(&state, input.thread_id, MessageRole::Assistant, &answer).await?;
 
+    let cache_ttl = Duration::from_secs(parse_setting_u64(
+        ROVEX_FOLLOW_UP_CACHE_TTL_SECONDS_ENV,
+        DEFAULT_FOLLOW_UP_CACHE_TTL_SECONDS,
+        0,
+    ));
+    if !cache_ttl.is_zero() {
+        // The same question asked again right away comes after this answer in the history,
+        // so the answer is also kept under the key of that history.
+        if let Some(question_id) = question_id {
+            history_attachments.insert(question_id, question_attachments);
+        }
+        let messages_after_answer =
+            load_recent_thread_messages(&state, input.thread_id, MAX_FOLLOW_UP_MESSAGES).await?;
+        let (history_after_answer, _) = format_follow_up_history(
+            &messages_after_answer,
+            &history_attachments,
+            &imported_comments,
+            history_limit,
+        );
+        let resubmit_key = follow_up_cache_key(
+            input.thread_id,
+            &history_after_answer,
+            question,
+            &attachment_inputs,
+            use_tools,
+        );
+        store_follow_up(
+            &[cache_key, resubmit_key],
+            &resolved_model,
+            &answer,
+            &tool_calls,
+            cache_ttl,
+        );
+    }
+
     Ok(GenerateAiFollowUpResult {
         thread_id: input.thread_id,
         workspace,
         model: resolved_model,
         answer,
+        tool_calls,
+        cached: false,
     })
 }
@@ -0,0 +1,140 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+use super::findings::fnv1a;
+use crate::backend::{FollowUpToolCall, MessageAttachmentInput};
+
+static FOLLOW_UP_ANSWERS: OnceLock<Mutex<HashMap<u64, CachedFollowUp>>> = OnceLock::new();
+static FOLLOW_UPS_IN_FLIGHT: OnceLock<Mutex<HashMap<u64, Arc<AsyncMutex<()>>>>> = OnceLock::new();
+
+fn follow_up_answers() -> &'static Mutex<HashMap<u64, CachedFollowUp>> {
+    FOLLOW_UP_ANSWERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// An answer kept for a short while so a re-submitted question does not call the model again.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedFollowUp {
+    pub(crate) model: String,
+    pub(crate) answer: String,
+    pub(crate) tool_calls: Vec<FollowUpToolCall>,
+    expires_at: Instant,
+}
+
+/// Identifies a follow-up question by the thread, the conversation history it is asked
+/// after, and everything the user submitted with it.
+pub(crate) fn follow_up_cache_key(
+    thread_id: i64,
+    history: &str,
+    question: &str,
+    attachments: &[MessageAttachmentInput],
+    use_tools: bool,
+) -> u64 {
+    let thread_id = thread_id.to_string();
+    let mut parts = vec![
+        thread_id.as_str(),
+        history,
+        question.trim(),
+        if use_tools { "tools" } else { "no-tools" },
+    ];
+    for attachment in attachments {
+        parts.push(attachment.name.trim());
+        parts.push(&attachment.content);
+    }
+    fnv1a(&parts)
+}
+
+/// Waits until no other request is answering the question with `key`, so a double submit
+/// waits for the first answer and then finds it in the cache.
+pub(crate) async fn lock_follow_up(key: u64) -> OwnedMutexGuard<()> {
+    let lock = match FOLLOW_UPS_IN_FLIGHT
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+    {
+        Ok(mut in_flight) => {
+            in_flight.retain(|_, lock| Arc::strong_count(lock) > 1);
+            in_flight
+                .entry(key)
+                .or_insert_with(|| Arc::new(AsyncMutex::new(())))
+                .clone()
+        }
+        Err(_) => Arc::new(AsyncMutex::new(())),
+    };
+    lock.lock_owned().await
+}
+
+pub(crate) fn cached_follow_up(key: u64) -> Option<CachedFollowUp> {
+    let Ok(mut answers) = follow_up_answers().lock() else {
+        return None;
+    };
+    let now = Instant::now();
+    answers.retain(|_, cached| cached.expires_at > now);
+    answers.get(&key).cloned()
+}
+
+/// Keeps an answer for `ttl` under every key in `keys`. Callers pass the key the question was
+/// asked with and the key of the same question asked again right after this answer.
+pub(crate) fn store_follow_up(
+    keys: &[u64],
+    model: &str,
+    answer: &str,
+    tool_calls: &[FollowUpToolCall],
+    ttl: Duration,
+) {
+    if ttl.is_zero() {
+        return;
+    }
+    let cached = CachedFollowUp {
+        model: model.to_string(),
+        answer: answer.to_string(),
+        tool_calls: tool_calls.to_vec(),
+        expires_at: Instant::now() + ttl,
+    };
+    let Ok(mut answers) = follow_up_answers().lock() else {
+        return;
+    };
+    for key in keys {
+        answers.insert(*key, cached.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{cached_follow_up, follow_up_cache_key, store_follow_up};
+    use crate::backend::MessageAttachmentInput;
+
+    #[test]
+    fn cached_answers_match_the_same_question_after_the_same_history() {
+        let log = MessageAttachmentInput {
+            name: "app.log".to_string(),
+            content: "error: boom".to_string(),
+            mime_type: None,
+        };
+        let key = follow_up_cache_key(41, "User: review\n\nAssistant: ok", "Why?", &[], false);
+        assert_eq!(
+            key,
+            follow_up_cache_key(41, "User: review\n\nAssistant: ok", " Why? ", &[], false)
+        );
+        for other in [
+            follow_up_cache_key(42, "User: review\n\nAssistant: ok", "Why?", &[], false),
+            follow_up_cache_key(41, "User: review", "Why?", &[], false),
+            follow_up_cache_key(41, "User: review\n\nAssistant: ok", "Why?", &[], true),
+            follow_up_cache_key(41, "User: review\n\nAssistant: ok", "Why?", &[log], false),
+        ] {
+            assert_ne!(key, other);
+        }
+
+        store_follow_up(&[key], "gpt", "Because.", &[], Duration::from_secs(60));
+        assert_eq!(cached_follow_up(key).unwrap().answer, "Because.");
+
+        let disabled = follow_up_cache_key(41, "", "Skip?", &[], false);
+        store_follow_up(&[disabled], "gpt", "No.", &[], Duration::ZERO);
+        assert!(cached_follow_up(disabled).is_none());
+    }
+}
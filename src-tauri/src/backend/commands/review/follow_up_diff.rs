@@ -0,0 +1,281 @@
+use super::super::common::truncate_chars;
+use super::diff_chunks::DiffChunk;
+use super::prompt_safety::fence_untrusted;
+use crate::backend::{AiReviewFinding, AppState};
+
+/// One file of the diff a thread was last reviewed against.
+#[derive(Debug, Clone)]
+pub(crate) struct ThreadDiffChunk {
+    pub(crate) chunk_id: String,
+    pub(crate) file_path: String,
+    pub(crate) previous_path: Option<String>,
+    pub(crate) patch: String,
+}
+
+/// Replaces the diff stored for `thread_id` with the chunks of the review that just started,
+/// so follow-up questions are answered against the change being discussed.
+pub(crate) async fn store_thread_diff_chunks(
+    state: &AppState,
+    thread_id: i64,
+    run_id: Option<&str>,
+    chunks: &[DiffChunk],
+) -> Result<(), String> {
+    let conn = state.connection()?;
+    conn.execute(
+        "DELETE FROM thread_diff_chunks WHERE thread_id = ?1",
+        [thread_id],
+    )
+    .await
+    .map_err(|error| format!("Failed to clear the stored diff of thread {thread_id}: {error}"))?;
+    for chunk in chunks {
+        conn.execute(
+            "INSERT INTO thread_diff_chunks (thread_id, chunk_id, run_id, file_path, previous_path, patch)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            libsql::params![
+                thread_id,
+                chunk.id.clone(),
+                run_id.map(ToOwned::to_owned),
+                chunk.file_path.clone(),
+                chunk.previous_path.clone(),
+                chunk.patch.clone(),
+            ],
+        )
+        .await
+        .map_err(|error| format!("Failed to store diff of {}: {error}", chunk.file_path))?;
+    }
+    Ok(())
+}
+
+pub(crate) async fn load_thread_diff_chunks(
+    state: &AppState,
+    thread_id: i64,
+) -> Result<Vec<ThreadDiffChunk>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT chunk_id, file_path, previous_path, patch
+             FROM thread_diff_chunks
+             WHERE thread_id = ?1
+             ORDER BY rowid ASC",
+            [thread_id],
+        )
+        .await
+        .map_err(|error| {
+            format!("Failed to load the stored diff of thread {thread_id}: {error}")
+        })?;
+    let mut chunks = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read stored diff row: {error}"))?
+    {
+        chunks.push(ThreadDiffChunk {
+            chunk_id: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse stored diff chunk id: {error}"))?,
+            file_path: row
+                .get(1)
+                .map_err(|error| format!("Failed to parse stored diff file path: {error}"))?,
+            previous_path: row
+                .get(2)
+                .map_err(|error| format!("Failed to parse stored diff previous path: {error}"))?,
+            patch: row
+                .get(3)
+                .map_err(|error| format!("Failed to parse stored diff patch: {error}"))?,
+        });
+    }
+    Ok(chunks)
+}
+
+/// Whether `needle` appears in `haystack` as a whole path or word, so `lib.rs` does not
+/// match `stdlib.rs`. Both are expected in lowercase.
+fn mentions(haystack: &str, needle: &str) -> bool {
+    if needle.is_empty() {
+        return false;
+    }
+    let is_word = |value: Option<char>| {
+        value.is_some_and(|value| value.is_alphanumeric() || matches!(value, '_' | '-' | '/'))
+    };
+    haystack.match_indices(needle).any(|(start, _)| {
+        !is_word(haystack[..start].chars().next_back())
+            && !is_word(haystack[start + needle.len()..].chars().next())
+    })
+}
+
+fn mentions_path(question: &str, path: &str) -> bool {
+    let path = path.to_lowercase();
+    if mentions(question, &path) {
+        return true;
+    }
+    // A bare file name only counts when it has an extension; names like `mod` or `main`
+    // are ordinary words too often.
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    file_name.contains('.') && mentions(question, file_name)
+}
+
+fn title_keywords(title: &str) -> Vec<String> {
+    title
+        .split(|value: char| !value.is_alphanumeric() && value != '_')
+        .filter(|word| word.chars().count() >= 4)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// A finding is referenced by its id, its title, or most of the longer words of its title.
+fn mentions_finding(question: &str, finding: &AiReviewFinding) -> bool {
+    let title = finding.title.trim().to_lowercase();
+    if question.contains(&finding.id.to_lowercase())
+        || (!title.is_empty() && question.contains(&title))
+    {
+        return true;
+    }
+    let keywords = title_keywords(&title);
+    let matched = keywords
+        .iter()
+        .filter(|keyword| mentions(question, keyword))
+        .count();
+    matched >= 2 && matched * 2 >= keywords.len()
+}
+
+/// Chunks the question is about: files it names, and files of findings it refers to.
+pub(crate) fn select_follow_up_chunks<'a>(
+    chunks: &'a [ThreadDiffChunk],
+    findings: &[AiReviewFinding],
+    question: &str,
+) -> Vec<&'a ThreadDiffChunk> {
+    let question = question.to_lowercase();
+    let referenced_findings = findings
+        .iter()
+        .filter(|finding| mentions_finding(&question, finding))
+        .collect::<Vec<_>>();
+    chunks
+        .iter()
+        .filter(|chunk| {
+            mentions_path(&question, &chunk.file_path)
+                || chunk
+                    .previous_path
+                    .as_deref()
+                    .is_some_and(|path| mentions_path(&question, path))
+                || referenced_findings.iter().any(|finding| {
+                    finding.chunk_id == chunk.chunk_id || finding.file_path == chunk.file_path
+                })
+        })
+        .collect()
+}
+
+/// Prompt section with the selected chunks, fenced like the diff in review prompts and cut
+/// to `max_chars` of patch text. `None` when nothing was selected.
+pub(crate) fn format_follow_up_diff(
+    chunks: &[&ThreadDiffChunk],
+    max_chars: usize,
+) -> Option<String> {
+    let mut remaining = max_chars;
+    let mut sections = Vec::new();
+    for chunk in chunks {
+        if remaining == 0 {
+            break;
+        }
+        let (patch, truncated) = truncate_chars(&chunk.patch, remaining);
+        remaining = remaining.saturating_sub(patch.chars().count());
+        sections.push(format!(
+            "File: {}{}\n{}",
+            chunk.file_path,
+            if truncated { " (diff truncated)" } else { "" },
+            fence_untrusted("diff", &patch)
+        ));
+    }
+    if sections.is_empty() {
+        return None;
+    }
+    Some(sections.join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_follow_up_diff, select_follow_up_chunks, ThreadDiffChunk};
+    use crate::backend::AiReviewFinding;
+
+    fn chunk(file_path: &str) -> ThreadDiffChunk {
+        ThreadDiffChunk {
+            chunk_id: format!("{file_path}#file-1"),
+            file_path: file_path.to_string(),
+            previous_path: None,
+            patch: format!("--- a/{file_path}\n+++ b/{file_path}\n@@ -1 +1 @@\n-old\n+new\n"),
+        }
+    }
+
+    fn finding(file_path: &str, title: &str) -> AiReviewFinding {
+        AiReviewFinding {
+            id: "finding-7".to_string(),
+            file_path: file_path.to_string(),
+            chunk_id: format!("{file_path}#file-1"),
+            chunk_index: 1,
+            hunk_header: "@@ -1 +1 @@".to_string(),
+            side: "new".to_string(),
+            line_number: 1,
+            title: title.to_string(),
+            body: "Body.".to_string(),
+            severity: "high".to_string(),
+            confidence: None,
+            category: None,
+            fingerprint: None,
+            status: None,
+            code_hash: None,
+            recurring: None,
+            suggested_patch: None,
+            language: None,
+            blame: None,
+        }
+    }
+
+    fn selected(
+        chunks: &[ThreadDiffChunk],
+        findings: &[AiReviewFinding],
+        question: &str,
+    ) -> Vec<String> {
+        select_follow_up_chunks(chunks, findings, question)
+            .into_iter()
+            .map(|chunk| chunk.file_path.clone())
+            .collect()
+    }
+
+    #[test]
+    fn chunks_are_selected_by_path_or_finding() {
+        let chunks = [
+            chunk("src/lib.rs"),
+            chunk("src/stdlib.rs"),
+            chunk("src/db/mod.rs"),
+        ];
+        let findings = [finding("src/db/mod.rs", "Connection pool leaks on timeout")];
+
+        assert_eq!(
+            selected(&chunks, &[], "Why did lib.rs change?"),
+            ["src/lib.rs"]
+        );
+        assert_eq!(
+            selected(&chunks, &[], "Explain `src/stdlib.rs`."),
+            ["src/stdlib.rs"]
+        );
+        assert!(selected(&chunks, &[], "Is the mod ready?").is_empty());
+        assert_eq!(
+            selected(&chunks, &findings, "Is the pool leak on timeout real?"),
+            ["src/db/mod.rs"]
+        );
+        assert_eq!(
+            selected(&chunks, &findings, "Fix finding-7 please"),
+            ["src/db/mod.rs"]
+        );
+        assert!(selected(&chunks, &findings, "Any timeout concerns?").is_empty());
+    }
+
+    #[test]
+    fn selected_diff_is_fenced_and_limited() {
+        let chunks = [chunk("src/lib.rs"), chunk("src/main.rs")];
+        let all = chunks.iter().collect::<Vec<_>>();
+        let block = format_follow_up_diff(&all, 30).unwrap();
+        assert!(block.contains("File: src/lib.rs (diff truncated)"));
+        assert!(block.contains("BEGIN UNTRUSTED diff-"));
+        assert!(!block.contains("src/main.rs"));
+        assert!(format_follow_up_diff(&[], 100).is_none());
+    }
+}
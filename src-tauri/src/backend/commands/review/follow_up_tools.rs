@@ -0,0 +1,697 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ignore::WalkBuilder;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::super::common::truncate_chars;
+use super::super::name_sort::compare_paths;
+use super::super::path_filter::PathFilter;
+use super::super::workspace_files::{is_git_dir, WorkspaceRoot};
+
+/// Rounds of tool calls a follow-up answer may take before the model must answer.
+pub(crate) const MAX_FOLLOW_UP_TOOL_ROUNDS: usize = 8;
+
+const MAX_TOOL_FILE_BYTES: u64 = 512 * 1024;
+const MAX_READ_LINES: usize = 400;
+const MAX_GREP_MATCHES: usize = 100;
+const MAX_GREP_LINE_CHARS: usize = 240;
+const MAX_LISTED_ENTRIES: usize = 200;
+const MAX_TOOL_OUTPUT_CHARS: usize = 24_000;
+
+const SHELL_METACHARACTERS: [char; 9] = ['|', ';', '&', '<', '>', '$', '`', '(', '\n'];
+/// Characters the shell expands into paths we never get to check, as in `~/.ssh` or `*.rs`.
+const SHELL_EXPANSIONS: [char; 7] = ['~', '*', '?', '[', ']', '{', '}'];
+
+/// Flags a read-only program accepts: single-letter flags, which may be clustered as in
+/// `-rn`, and long flags, which may carry a value after `=`.
+struct FlagPolicy {
+    short: &'static str,
+    long: &'static [&'static str],
+}
+
+/// Programs an app-server turn may run without asking twice, with the flags that keep them
+/// read-only. Anything else is declined, including flags that run other programs or write
+/// files, such as `rg --pre`, `git grep -O`, `git diff --output` and `--ext-diff`.
+const READ_ONLY_PROGRAMS: [(&str, FlagPolicy); 8] = [
+    (
+        "cat",
+        FlagPolicy {
+            short: "nbsAET",
+            long: &["--number", "--number-nonblank", "--squeeze-blank"],
+        },
+    ),
+    (
+        "head",
+        FlagPolicy {
+            short: "ncqv",
+            long: &["--lines", "--bytes", "--quiet", "--verbose"],
+        },
+    ),
+    (
+        "tail",
+        FlagPolicy {
+            short: "ncqv",
+            long: &["--lines", "--bytes", "--quiet", "--verbose"],
+        },
+    ),
+    (
+        "ls",
+        FlagPolicy {
+            short: "laAhRr1FtS",
+            long: &["--all", "--almost-all", "--recursive", "--human-readable"],
+        },
+    ),
+    (
+        "grep",
+        FlagPolicy {
+            short: "nirRlLcwxvEFHhoseABCm",
+            long: &[
+                "--include",
+                "--exclude",
+                "--exclude-dir",
+                "--line-number",
+                "--ignore-case",
+                "--recursive",
+                "--files-with-matches",
+                "--count",
+                "--word-regexp",
+                "--fixed-strings",
+                "--extended-regexp",
+                "--max-count",
+                "--context",
+                "--color",
+            ],
+        },
+    ),
+    (
+        "rg",
+        FlagPolicy {
+            short: "niSswFlcgtTeABCmu",
+            long: &[
+                "--hidden",
+                "--no-ignore",
+                "--glob",
+                "--type",
+                "--type-not",
+                "--files",
+                "--line-number",
+                "--ignore-case",
+                "--smart-case",
+                "--case-sensitive",
+                "--fixed-strings",
+                "--word-regexp",
+                "--files-with-matches",
+                "--count",
+                "--context",
+                "--max-count",
+                "--no-heading",
+                "--color",
+            ],
+        },
+    ),
+    (
+        "wc",
+        FlagPolicy {
+            short: "lwcm",
+            long: &["--lines", "--words", "--bytes", "--chars"],
+        },
+    ),
+    (
+        "nl",
+        FlagPolicy {
+            short: "ba",
+            long: &["--body-numbering"],
+        },
+    ),
+];
+
+/// Git subcommands an app-server turn may run, with their read-only flags.
+const READ_ONLY_GIT_COMMANDS: [(&str, FlagPolicy); 7] = [
+    (
+        "grep",
+        FlagPolicy {
+            short: "niwlLcveEFABChH",
+            long: &[
+                "--line-number",
+                "--ignore-case",
+                "--word-regexp",
+                "--files-with-matches",
+                "--count",
+                "--fixed-strings",
+                "--extended-regexp",
+                "--context",
+                "--cached",
+                "--untracked",
+            ],
+        },
+    ),
+    (
+        "show",
+        FlagPolicy {
+            short: "ps",
+            long: &[
+                "--stat",
+                "--name-only",
+                "--name-status",
+                "--oneline",
+                "--no-patch",
+                "--patch",
+                "--format",
+                "--pretty",
+                "--no-color",
+            ],
+        },
+    ),
+    (
+        "log",
+        FlagPolicy {
+            short: "npSG",
+            long: &[
+                "--oneline",
+                "--max-count",
+                "--patch",
+                "--stat",
+                "--name-only",
+                "--name-status",
+                "--format",
+                "--pretty",
+                "--author",
+                "--since",
+                "--until",
+                "--grep",
+                "--follow",
+                "--graph",
+                "--decorate",
+                "--reverse",
+                "--no-color",
+            ],
+        },
+    ),
+    (
+        "diff",
+        FlagPolicy {
+            short: "UMw",
+            long: &[
+                "--stat",
+                "--numstat",
+                "--shortstat",
+                "--name-only",
+                "--name-status",
+                "--cached",
+                "--staged",
+                "--unified",
+                "--word-diff",
+                "--find-renames",
+                "--ignore-all-space",
+                "--no-color",
+            ],
+        },
+    ),
+    (
+        "blame",
+        FlagPolicy {
+            short: "LwMCesl",
+            long: &["--porcelain", "--line-porcelain"],
+        },
+    ),
+    (
+        "ls-files",
+        FlagPolicy {
+            short: "comd",
+            long: &[
+                "--cached",
+                "--others",
+                "--modified",
+                "--deleted",
+                "--exclude-standard",
+            ],
+        },
+    ),
+    (
+        "status",
+        FlagPolicy {
+            short: "sb",
+            long: &["--short", "--branch", "--porcelain"],
+        },
+    ),
+];
+
+fn flag_policy<'a>(policies: &'a [(&str, FlagPolicy)], name: &str) -> Option<&'a FlagPolicy> {
+    policies
+        .iter()
+        .find(|(program, _)| *program == name)
+        .map(|(_, policy)| policy)
+}
+
+impl FlagPolicy {
+    /// Whether `flag`, given with its leading dashes, is allowed. Digits pass as counts,
+    /// as in `head -20` or `grep -C3`.
+    fn allows(&self, flag: &str) -> bool {
+        match flag.strip_prefix("--") {
+            Some(long) => {
+                let name = long.split_once('=').map_or(long, |(name, _)| name);
+                self.long.contains(&format!("--{name}").as_str())
+            }
+            None => flag
+                .trim_start_matches('-')
+                .chars()
+                .all(|flag| flag.is_ascii_digit() || self.short.contains(flag)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReadFileArguments {
+    path: String,
+    start_line: Option<usize>,
+    end_line: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GrepArguments {
+    pattern: String,
+    path: Option<String>,
+    ignore_case: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListFilesArguments {
+    path: Option<String>,
+}
+
+/// Read-only file access for follow-up answers. Every path must resolve inside the workspace,
+/// outside `.git`, and not be excluded by `.rovexignore`.
+pub(crate) struct WorkspaceTools {
+    workspace: WorkspaceRoot,
+    filter: PathFilter,
+}
+
+impl WorkspaceTools {
+    pub(crate) fn new(workspace: &str) -> Result<Self, String> {
+        let workspace = WorkspaceRoot::new(workspace)?;
+        let filter = PathFilter::new(None, None)?.with_rovexignore(workspace.path())?;
+        Ok(Self { workspace, filter })
+    }
+
+    /// The absolute path for `path` and its workspace-relative form, or why it is not allowed.
+    fn resolve(&self, path: &str) -> Result<(PathBuf, String), String> {
+        let (resolved, relative) = self.workspace.resolve(path)?;
+        // A trailing slash lets directory patterns like `secrets/` match the directory itself.
+        let filtered = if resolved.is_dir() {
+            format!("{relative}/")
+        } else {
+            relative.clone()
+        };
+        if !relative.is_empty() && !self.filter.allows(&filtered) {
+            return Err(format!("{} is excluded by .rovexignore.", path.trim()));
+        }
+        Ok((resolved, relative))
+    }
+
+    fn read_file(&self, arguments: ReadFileArguments) -> Result<String, String> {
+        if arguments.start_line == Some(0) {
+            return Err("startLine starts at 1.".to_string());
+        }
+        let (path, relative) = self.resolve(&arguments.path)?;
+        if !path.is_file() {
+            return Err(format!("{relative} is not a file."));
+        }
+        let size = fs::metadata(&path)
+            .map_err(|error| format!("Failed to read {relative}: {error}"))?
+            .len();
+        if size > MAX_TOOL_FILE_BYTES {
+            return Err(format!(
+                "{relative} is {size} bytes; files larger than {MAX_TOOL_FILE_BYTES} bytes cannot be read."
+            ));
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|_| format!("{relative} is not a UTF-8 text file."))?;
+        let lines = content.lines().collect::<Vec<_>>();
+        let start = arguments.start_line.unwrap_or(1);
+        let last = start.saturating_add(MAX_READ_LINES - 1);
+        let end = arguments
+            .end_line
+            .unwrap_or(last)
+            .min(last)
+            .min(lines.len());
+        if start > lines.len() {
+            return Ok(format!("{relative} has {} lines.", lines.len()));
+        }
+        if end < start {
+            return Err("endLine must not be before startLine.".to_string());
+        }
+        let mut output = vec![format!(
+            "{relative} (lines {start}-{end} of {}):",
+            lines.len()
+        )];
+        for (index, line) in lines[start - 1..end].iter().enumerate() {
+            output.push(format!("{:>5} | {line}", start + index));
+        }
+        Ok(output.join("\n"))
+    }
+
+    fn grep(&self, arguments: GrepArguments) -> Result<String, String> {
+        let pattern = arguments.pattern.trim();
+        if pattern.is_empty() {
+            return Err("Search pattern must not be empty.".to_string());
+        }
+        let ignore_case = arguments.ignore_case.unwrap_or(false);
+        let needle = if ignore_case {
+            pattern.to_lowercase()
+        } else {
+            pattern.to_string()
+        };
+        let (start, _) = self.resolve(arguments.path.as_deref().unwrap_or_default())?;
+
+        let mut matches = Vec::new();
+        let mut truncated = false;
+        let walker = WalkBuilder::new(&start)
+            .hidden(false)
+            .filter_entry(|entry| !is_git_dir(&entry.file_name().to_string_lossy()))
+            .build();
+        'files: for entry in walker.flatten() {
+            if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+                continue;
+            }
+            let Ok((_, relative)) = self.resolve(&entry.path().to_string_lossy()) else {
+                continue;
+            };
+            if entry
+                .metadata()
+                .map(|metadata| metadata.len() > MAX_TOOL_FILE_BYTES)
+                .unwrap_or(true)
+            {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            for (index, line) in content.lines().enumerate() {
+                let haystack = if ignore_case {
+                    line.to_lowercase()
+                } else {
+                    line.to_string()
+                };
+                if !haystack.contains(&needle) {
+                    continue;
+                }
+                if matches.len() == MAX_GREP_MATCHES {
+                    truncated = true;
+                    break 'files;
+                }
+                let (line, _) = truncate_chars(line.trim(), MAX_GREP_LINE_CHARS);
+                matches.push(format!("{relative}:{}: {line}", index + 1));
+            }
+        }
+        if matches.is_empty() {
+            return Ok(format!("No matches for {pattern}."));
+        }
+        if truncated {
+            matches.push(format!(
+                "(stopped after {MAX_GREP_MATCHES} matches; narrow the pattern or path)"
+            ));
+        }
+        Ok(matches.join("\n"))
+    }
+
+    fn list_files(&self, arguments: ListFilesArguments) -> Result<String, String> {
+        let (directory, relative) = self.resolve(arguments.path.as_deref().unwrap_or_default())?;
+        if !directory.is_dir() {
+            return Err(format!("{relative} is not a directory."));
+        }
+        let mut entries = Vec::new();
+        let walker = WalkBuilder::new(&directory)
+            .hidden(false)
+            .max_depth(Some(1))
+            .filter_entry(|entry| !is_git_dir(&entry.file_name().to_string_lossy()))
+            .build();
+        for entry in walker.flatten().filter(|entry| entry.depth() == 1) {
+            let Ok((_, relative)) = self.resolve(&entry.path().to_string_lossy()) else {
+                continue;
+            };
+            let is_dir = entry.file_type().is_some_and(|kind| kind.is_dir());
+            entries.push(if is_dir {
+                format!("{relative}/")
+            } else {
+                relative
+            });
+        }
+        entries.sort_by(|left, right| compare_paths(left, right));
+        if entries.len() > MAX_LISTED_ENTRIES {
+            let hidden = entries.len() - MAX_LISTED_ENTRIES;
+            entries.truncate(MAX_LISTED_ENTRIES);
+            entries.push(format!("({hidden} more entries)"));
+        }
+        if entries.is_empty() {
+            return Ok("The directory is empty.".to_string());
+        }
+        Ok(entries.join("\n"))
+    }
+
+    /// Runs tool `name` with its JSON `arguments` and returns the text for the model.
+    pub(crate) fn execute(&self, name: &str, arguments: &str) -> Result<String, String> {
+        let parse_error = |error: serde_json::Error| format!("Invalid {name} arguments: {error}");
+        let output = match name {
+            "read_file" => self.read_file(serde_json::from_str(arguments).map_err(parse_error)?),
+            "grep" => self.grep(serde_json::from_str(arguments).map_err(parse_error)?),
+            "list_files" => self.list_files(serde_json::from_str(arguments).map_err(parse_error)?),
+            _ => Err(format!("Unknown tool {name}.")),
+        }?;
+        Ok(truncate_chars(&output, MAX_TOOL_OUTPUT_CHARS).0)
+    }
+
+    /// Whether an app-server turn may run `command` in `cwd`: a read-only program whose
+    /// arguments all stay inside the workspace.
+    pub(crate) fn allows_command(&self, command: &[String], cwd: Option<&str>) -> bool {
+        let base = match cwd {
+            Some(cwd) => match self.resolve(cwd) {
+                Ok((base, _)) => base,
+                Err(_) => return false,
+            },
+            None => self.workspace.path().to_path_buf(),
+        };
+        let arguments = match command {
+            [shell, flag, script]
+                if matches!(shell_name(shell), "sh" | "bash" | "zsh")
+                    && matches!(flag.as_str(), "-c" | "-lc") =>
+            {
+                if script.contains(SHELL_METACHARACTERS) || script.contains(SHELL_EXPANSIONS) {
+                    return false;
+                }
+                script.split_whitespace().map(ToOwned::to_owned).collect()
+            }
+            _ => command.to_vec(),
+        };
+        let Some((program, rest)) = arguments.split_first() else {
+            return false;
+        };
+        let (policy, rest) = match shell_name(program) {
+            "git" => match rest.split_first() {
+                Some((subcommand, rest)) => {
+                    match flag_policy(&READ_ONLY_GIT_COMMANDS, subcommand) {
+                        Some(policy) => (policy, rest),
+                        None => return false,
+                    }
+                }
+                None => return false,
+            },
+            name => match flag_policy(&READ_ONLY_PROGRAMS, name) {
+                Some(policy) => (policy, rest),
+                None => return false,
+            },
+        };
+        let mut after_separator = false;
+        rest.iter().all(|argument| {
+            let value = match argument.as_str() {
+                "--" if !after_separator => {
+                    after_separator = true;
+                    return true;
+                }
+                flag if !after_separator && flag.starts_with('-') && flag.len() > 1 => {
+                    if !policy.allows(flag) {
+                        return false;
+                    }
+                    match flag.split_once('=') {
+                        Some((_, value)) => value,
+                        None => return true,
+                    }
+                }
+                value => value,
+            };
+            self.stays_inside(&base, value)
+        })
+    }
+
+    /// Whether `value`, taken as a path relative to `base`, passes the same checks as the
+    /// tools' own paths.
+    fn stays_inside(&self, base: &Path, value: &str) -> bool {
+        let path = base.join(value.trim_matches(['\'', '"']));
+        self.resolve(&path.to_string_lossy()).is_ok()
+    }
+}
+
+fn shell_name(program: &str) -> &str {
+    Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program)
+}
+
+/// Function definitions for OpenAI tool calls, matching [`WorkspaceTools::execute`].
+pub(crate) fn openai_tool_definitions() -> serde_json::Value {
+    json!([
+        {
+            "type": "function",
+            "function": {
+                "name": "read_file",
+                "description": "Read lines of a text file in the workspace. Returns at most 400 numbered lines.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "Path relative to the workspace root." },
+                        "startLine": { "type": "integer", "description": "First line to read, starting at 1." },
+                        "endLine": { "type": "integer", "description": "Last line to read." }
+                    },
+                    "required": ["path"]
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "grep",
+                "description": "Search workspace files for a literal string. Returns matching lines as path:line: text.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "pattern": { "type": "string", "description": "Literal text to find." },
+                        "path": { "type": "string", "description": "File or directory to search, relative to the workspace root. Defaults to the whole workspace." },
+                        "ignoreCase": { "type": "boolean" }
+                    },
+                    "required": ["pattern"]
+                }
+            }
+        },
+        {
+            "type": "function",
+            "function": {
+                "name": "list_files",
+                "description": "List the files and directories directly inside a workspace directory.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "Directory relative to the workspace root. Defaults to the root." }
+                    }
+                }
+            }
+        }
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::WorkspaceTools;
+
+    fn command(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn tools_read_and_search_only_inside_the_workspace() {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let workspace = std::env::temp_dir().join(format!("rovex-follow-up-tools-test-{suffix}"));
+        fs::create_dir_all(workspace.join("src")).expect("create temp workspace");
+        fs::write(
+            workspace.join("src/lib.rs"),
+            "fn helper() {}\n\nfn caller() {\n    helper();\n}\n",
+        )
+        .expect("write source");
+        fs::write(workspace.join(".rovexignore"), "secrets/\n").expect("write .rovexignore");
+        fs::create_dir_all(workspace.join("secrets")).expect("create secrets");
+        fs::write(workspace.join("secrets/key.txt"), "helper").expect("write secret");
+
+        let tools = WorkspaceTools::new(&workspace.to_string_lossy()).expect("tools");
+        let read = tools
+            .execute(
+                "read_file",
+                r#"{"path":"src/lib.rs","startLine":3,"endLine":4}"#,
+            )
+            .unwrap();
+        assert!(read.starts_with("src/lib.rs (lines 3-4 of 5):"));
+        assert!(read.contains("    4 |     helper();"));
+        assert!(tools
+            .execute(
+                "read_file",
+                &format!(r#"{{"path":"src/lib.rs","startLine":{}}}"#, usize::MAX)
+            )
+            .unwrap()
+            .contains("has 5 lines"));
+        assert!(tools
+            .execute("read_file", r#"{"path":"src/lib.rs","startLine":0}"#)
+            .is_err());
+
+        let found = tools.execute("grep", r#"{"pattern":"helper"}"#).unwrap();
+        assert!(found.contains("src/lib.rs:1: fn helper() {}"));
+        assert!(found.contains("src/lib.rs:4: helper();"));
+        assert!(!found.contains("secrets/"));
+
+        assert!(tools
+            .execute("read_file", r#"{"path":"../outside.txt"}"#)
+            .is_err());
+        assert!(tools
+            .execute("read_file", r#"{"path":"secrets/key.txt"}"#)
+            .is_err());
+        assert!(tools
+            .execute("read_file", r#"{"path":"/etc/hosts"}"#)
+            .is_err());
+        assert!(tools.execute("list_files", "{}").unwrap().contains("src/"));
+
+        assert!(tools.allows_command(&command(&["rg", "helper", "src"]), None));
+        assert!(tools.allows_command(&command(&["bash", "-lc", "git grep -n helper"]), None));
+        assert!(!tools.allows_command(&command(&["cat", "/etc/passwd"]), None));
+        assert!(!tools.allows_command(&command(&["rm", "-rf", "src"]), None));
+        assert!(!tools.allows_command(&command(&["bash", "-lc", "cat src/lib.rs | sh"]), None));
+        assert!(!tools.allows_command(&command(&["git", "push"]), None));
+        assert!(tools.allows_command(&command(&["grep", "-rn", "helper", "src"]), None));
+        assert!(tools.allows_command(&command(&["git", "log", "-5", "--oneline"]), None));
+
+        // Flags that run other programs or write files are declined.
+        assert!(!tools.allows_command(&command(&["rg", "--pre=./evil.sh", "x"]), None));
+        assert!(!tools.allows_command(&command(&["rg", "--pre", "./evil.sh", "x"]), None));
+        assert!(!tools.allows_command(&command(&["rg", "--pre-glob=*", "x"]), None));
+        assert!(!tools.allows_command(&command(&["rg", "-z", "x"]), None));
+        assert!(!tools.allows_command(
+            &command(&["git", "grep", "--open-files-in-pager=sh", "x"]),
+            None
+        ));
+        assert!(!tools.allows_command(&command(&["git", "grep", "-Osh", "x"]), None));
+        assert!(!tools.allows_command(&command(&["git", "grep", "-O", "x"]), None));
+        assert!(!tools.allows_command(&command(&["git", "diff", "--output=src/lib.rs"]), None));
+        assert!(!tools.allows_command(&command(&["git", "log", "--output=src/lib.rs"]), None));
+        assert!(!tools.allows_command(&command(&["git", "diff", "--ext-diff"]), None));
+        assert!(!tools.allows_command(&command(&["git", "show", "--textconv"]), None));
+        assert!(!tools.allows_command(&command(&["tail", "-f", "src/lib.rs"]), None));
+        assert!(!tools.allows_command(&command(&["bash", "-lc", "rg --pre=./evil.sh x"]), None));
+
+        // Paths the shell would expand, and paths the tools themselves refuse, are declined.
+        assert!(!tools.allows_command(&command(&["bash", "-lc", "cat ~/.ssh/id_rsa"]), None));
+        assert!(!tools.allows_command(&command(&["bash", "-lc", "cat src/*.rs"]), None));
+        assert!(!tools.allows_command(&command(&["bash", "-lc", "cat src/{lib,main}.rs"]), None));
+        assert!(!tools.allows_command(&command(&["cat", ".git/config"]), None));
+        assert!(!tools.allows_command(&command(&["bash", "-lc", "cat .git/config"]), None));
+        assert!(!tools.allows_command(&command(&["cat", "secrets/key.txt"]), None));
+        assert!(!tools.allows_command(&command(&["rg", "helper", "secrets"]), None));
+        assert!(!tools.allows_command(&command(&["cat", "key.txt"]), Some("secrets")));
+        assert!(tools.allows_command(&command(&["cat", "lib.rs"]), Some("src")));
+    }
+}
@@ -0,0 +1,204 @@
+use std::path::PathBuf;
+
+use tauri::State;
+
+use super::super::common::{format_path, ROVEX_CLI_PATH_ENV};
+use super::super::workspace_git::{
+    remove_review_git_hook, write_review_git_hook, REVIEW_GIT_HOOK_MARKER,
+};
+use crate::backend::settings::settings_store;
+use crate::backend::{
+    AppState, InstallReviewGitHookInput, ReviewGitHook, UninstallReviewGitHookInput,
+    UninstallReviewGitHookResult,
+};
+
+const HOOK_PRE_COMMIT: &str = "pre-commit";
+const HOOK_PRE_PUSH: &str = "pre-push";
+const DEFAULT_HOOK_FAIL_ON: &str = "critical";
+/// Exit code of rovex-cli when the review itself could not run.
+const CLI_EXIT_ERROR: i32 = 2;
+
+fn parse_workspace(value: &str) -> Result<String, String> {
+    let workspace = value.trim();
+    if workspace.is_empty() {
+        return Err("Workspace path must not be empty.".to_string());
+    }
+    Ok(workspace.to_string())
+}
+
+fn parse_hook(value: Option<&str>) -> Result<&'static str, String> {
+    match value.map(str::trim).unwrap_or(HOOK_PRE_COMMIT) {
+        HOOK_PRE_COMMIT => Ok(HOOK_PRE_COMMIT),
+        HOOK_PRE_PUSH => Ok(HOOK_PRE_PUSH),
+        other => Err(format!(
+            "Unsupported git hook '{other}'. Use '{HOOK_PRE_COMMIT}' or '{HOOK_PRE_PUSH}'."
+        )),
+    }
+}
+
+fn parse_fail_on(value: Option<&str>) -> Result<&'static str, String> {
+    match value
+        .map(|value| value.trim().to_lowercase())
+        .as_deref()
+        .unwrap_or(DEFAULT_HOOK_FAIL_ON)
+    {
+        "critical" => Ok("critical"),
+        "high" => Ok("high"),
+        "medium" => Ok("medium"),
+        "low" => Ok("low"),
+        "none" => Ok("none"),
+        other => Err(format!(
+            "Unknown severity '{other}'. Use critical, high, medium, low, or none."
+        )),
+    }
+}
+
+/// The rovex-cli binary hooks run: `ROVEX_CLI_PATH`, or the one built next to the app.
+fn rovex_cli_path() -> Result<PathBuf, String> {
+    let path = match settings_store()
+        .get_string(ROVEX_CLI_PATH_ENV)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+    {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_exe()
+            .map_err(|error| format!("Failed to locate the Rovex executable: {error}"))?
+            .with_file_name(format!("rovex-cli{}", std::env::consts::EXE_SUFFIX)),
+    };
+    if !path.is_file() {
+        return Err(format!(
+            "rovex-cli was not found at {}. Build it with `cargo build --bin rovex-cli` or set {ROVEX_CLI_PATH_ENV}.",
+            format_path(&path)
+        ));
+    }
+    Ok(path)
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Hook script that reviews the staged diff (pre-commit) or the branch diff (pre-push) and
+/// blocks on findings at `fail_on` or above. Errors running the review never block.
+fn build_review_hook_script(cli_path: &str, workspace: &str, hook: &str, fail_on: &str) -> String {
+    let (target, action) = if hook == HOOK_PRE_COMMIT {
+        ("staged", "commit")
+    } else {
+        ("branch", "push")
+    };
+    format!(
+        "#!/bin/sh\n{REVIEW_GIT_HOOK_MARKER}\n# Installed by Rovex. Skip once with --no-verify; remove with uninstall_review_git_hook.\n{} review --workspace {} --target {target} --no-fetch --fail-on {fail_on}\nstatus=$?\nif [ \"$status\" -eq {CLI_EXIT_ERROR} ]; then\n  echo \"rovex: review could not run; not blocking the {action}.\" >&2\n  exit 0\nfi\nexit \"$status\"\n",
+        shell_quote(cli_path),
+        shell_quote(workspace)
+    )
+}
+
+async fn load_review_git_hook(
+    state: &AppState,
+    workspace: &str,
+    hook: &str,
+) -> Result<ReviewGitHook, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT fail_on, path, installed_at FROM review_git_hooks
+             WHERE workspace = ?1 AND hook = ?2",
+            (workspace.to_string(), hook.to_string()),
+        )
+        .await
+        .map_err(|error| format!("Failed to load {hook} hook of {workspace}: {error}"))?;
+    let row = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read {hook} hook of {workspace}: {error}"))?
+        .ok_or_else(|| format!("No {hook} hook is installed for {workspace}."))?;
+    Ok(ReviewGitHook {
+        workspace: workspace.to_string(),
+        hook: hook.to_string(),
+        fail_on: row
+            .get(0)
+            .map_err(|error| format!("Failed to parse git hook severity: {error}"))?,
+        path: row
+            .get(1)
+            .map_err(|error| format!("Failed to parse git hook path: {error}"))?,
+        installed_at: row
+            .get(2)
+            .map_err(|error| format!("Failed to parse git hook timestamp: {error}"))?,
+    })
+}
+
+pub async fn install_review_git_hook(
+    state: State<'_, AppState>,
+    input: InstallReviewGitHookInput,
+) -> Result<ReviewGitHook, String> {
+    let workspace = parse_workspace(&input.workspace)?;
+    let hook = parse_hook(input.hook.as_deref())?;
+    let fail_on = parse_fail_on(input.fail_on.as_deref())?;
+    let cli_path = rovex_cli_path()?;
+    let script = build_review_hook_script(&cli_path.to_string_lossy(), &workspace, hook, fail_on);
+    let path = write_review_git_hook(&workspace, hook, &script)?;
+
+    let conn = state.connection()?;
+    conn.execute(
+        "INSERT INTO review_git_hooks (workspace, hook, fail_on, path) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(workspace, hook) DO UPDATE SET
+           fail_on = excluded.fail_on,
+           path = excluded.path,
+           installed_at = CURRENT_TIMESTAMP",
+        (
+            workspace.clone(),
+            hook.to_string(),
+            fail_on.to_string(),
+            path,
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to save {hook} hook of {workspace}: {error}"))?;
+    load_review_git_hook(&state, &workspace, hook).await
+}
+
+pub async fn uninstall_review_git_hook(
+    state: State<'_, AppState>,
+    input: UninstallReviewGitHookInput,
+) -> Result<UninstallReviewGitHookResult, String> {
+    let workspace = parse_workspace(&input.workspace)?;
+    let hook = parse_hook(input.hook.as_deref())?;
+    let removed = remove_review_git_hook(&workspace, hook)?;
+
+    let conn = state.connection()?;
+    conn.execute(
+        "DELETE FROM review_git_hooks WHERE workspace = ?1 AND hook = ?2",
+        (workspace.clone(), hook.to_string()),
+    )
+    .await
+    .map_err(|error| format!("Failed to delete {hook} hook of {workspace}: {error}"))?;
+    Ok(UninstallReviewGitHookResult {
+        workspace,
+        hook: hook.to_string(),
+        removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_review_hook_script, parse_fail_on, parse_hook};
+
+    #[test]
+    fn hook_script_quotes_paths_and_reviews_the_staged_diff() {
+        let script =
+            build_review_hook_script("/opt/rovex/rovex-cli", "/src/it's", "pre-commit", "high");
+        assert!(script.starts_with("#!/bin/sh\n# rovex-review-hook\n"));
+        assert!(script.contains(
+            "'/opt/rovex/rovex-cli' review --workspace '/src/it'\\''s' --target staged --no-fetch --fail-on high\n"
+        ));
+        assert!(
+            build_review_hook_script("rovex-cli", "/src", "pre-push", "critical")
+                .contains("--target branch")
+        );
+
+        assert_eq!(parse_hook(None), Ok("pre-commit"));
+        assert!(parse_hook(Some("post-merge")).is_err());
+        assert_eq!(parse_fail_on(Some("HIGH")), Ok("high"));
+        assert!(parse_fail_on(Some("blocker")).is_err());
+    }
+}
@@ -0,0 +1,684 @@
+use std::path::Path;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, State};
+
+use super::super::common::{
+    as_non_empty_trimmed, parse_bool_i64, parse_limit, parse_setting_usize, truncate_chars,
+    DEFAULT_REVIEW_MAX_DIFF_CHARS, ROVEX_REVIEW_MAX_DIFF_CHARS_ENV,
+};
+use super::super::threads::find_or_create_thread;
+use super::super::workspace_git::compare_workspace_diff;
+use super::super::workspace_trust::is_workspace_trusted;
+use super::follow_up::generate_text_with_review_provider;
+use super::prompt_safety::{fence_untrusted, UNTRUSTED_CONTENT_RULE};
+use super::run_queue::start_ai_review_run;
+use super::{store, ReviewProvider};
+use crate::backend::{
+    AiReviewRun, AppState, CompareWorkspaceDiffInput, CompareWorkspaceDiffResult,
+    ListReviewGroupsInput, ListReviewGroupsResult, ReviewGroup, ReviewGroupInput, ReviewGroupRun,
+    StartAiReviewRunInput, StartReviewGroupInput, WorkspaceDiffTarget,
+};
+
+/// Thread that collects the group runs of a workspace.
+const GROUP_THREAD_TITLE: &str = "Cross-repo reviews";
+const GROUP_WATCH_TICK: Duration = Duration::from_secs(10);
+const MAX_GROUP_REPOSITORIES: usize = 8;
+/// Findings listed per repository in the summary prompt.
+const MAX_SUMMARY_FINDINGS_PER_REPOSITORY: usize = 30;
+
+const GROUP_STATUS_RUNNING: &str = "running";
+const GROUP_STATUS_SUMMARIZING: &str = "summarizing";
+const GROUP_STATUS_COMPLETED: &str = "completed";
+const GROUP_STATUS_FAILED: &str = "failed";
+
+fn repository_name(workspace: &str) -> String {
+    Path::new(workspace)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| workspace.to_string())
+}
+
+/// Context added to each run's prompt, so per-repository reviews look at the seams too.
+fn build_group_run_prompt(
+    prompt: Option<&str>,
+    diffs: &[CompareWorkspaceDiffResult],
+    position: usize,
+) -> String {
+    let others = diffs
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != position)
+        .map(|(_, diff)| repository_name(&diff.workspace))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let context = format!(
+        "This change is part of a coordinated change that also touches: {others}. Call out changes to interfaces the other repositories may depend on, such as APIs, shared types, schemas, events, and configuration keys."
+    );
+    match prompt {
+        Some(prompt) => format!("{prompt}\n\n{context}"),
+        None => context,
+    }
+}
+
+/// Prompt for the cross-repository summary. `sections` pairs each group run with its loaded
+/// run, which is `None` when the run did not complete.
+fn build_group_summary_prompt(
+    group: &ReviewGroup,
+    sections: &[(ReviewGroupRun, Option<AiReviewRun>, String, bool)],
+) -> String {
+    let mut prompt = format!(
+        "You are reviewing one coordinated change that spans {} repositories. Each repository was reviewed on its own; your job is what those reviews cannot see.\n\n\
+         Find interface mismatches between the repositories: API routes and request or response fields, shared types and schemas, protobuf or GraphQL definitions, event and queue names, database columns, configuration keys and environment variables, and version constraints that one side changes and the other does not match. Name the file on each side of every mismatch. Do not repeat single-repository findings unless they cause a mismatch.\n\n\
+         Reply in Markdown with a \"## Interface mismatches\" section (write \"None found.\" when there are none), then a \"## Summary\" section with a short paragraph per repository and the order the changes should be deployed in, if it matters.\n\n\
+         {UNTRUSTED_CONTENT_RULE}\n\nGroup: {}",
+        sections.len(),
+        group.title
+    );
+    if let Some(goal) = group.prompt.as_deref() {
+        prompt.push_str(&format!("\nReviewer goal: {goal}"));
+    }
+
+    for (index, (group_run, run, diff, diff_truncated)) in sections.iter().enumerate() {
+        prompt.push_str(&format!(
+            "\n\n### Repository {}: {}\nWorkspace: {}\n",
+            index + 1,
+            repository_name(&group_run.workspace),
+            group_run.workspace
+        ));
+        let Some(run) = run else {
+            prompt.push_str(&format!(
+                "Review status: {} (no review available)\n",
+                group_run.status.as_deref().unwrap_or("missing")
+            ));
+            continue;
+        };
+        prompt.push_str(&format!(
+            "Base: {}\nHead: {}\nReview status: {}\n",
+            run.base_ref, run.head, run.status
+        ));
+        if let Some(review) = run
+            .review
+            .as_deref()
+            .filter(|review| !review.trim().is_empty())
+        {
+            prompt.push_str(&format!(
+                "Review:\n{}\n",
+                fence_untrusted("review", review.trim())
+            ));
+        }
+        if !run.findings.is_empty() {
+            prompt.push_str("Findings:\n");
+            for finding in run
+                .findings
+                .iter()
+                .take(MAX_SUMMARY_FINDINGS_PER_REPOSITORY)
+            {
+                prompt.push_str(&format!(
+                    "- [{}] {} ({}:{})\n",
+                    finding.severity, finding.title, finding.file_path, finding.line_number
+                ));
+            }
+        }
+        prompt.push_str(&format!(
+            "Diff{}:\n{}\n",
+            if *diff_truncated { " (truncated)" } else { "" },
+            fence_untrusted("diff", diff)
+        ));
+    }
+    prompt
+}
+
+fn parse_group_run_from_row(row: &libsql::Row) -> Result<ReviewGroupRun, String> {
+    Ok(ReviewGroupRun {
+        run_id: row
+            .get(0)
+            .map_err(|error| format!("Failed to parse group run id: {error}"))?,
+        thread_id: row
+            .get(1)
+            .map_err(|error| format!("Failed to parse group run thread id: {error}"))?,
+        workspace: row
+            .get(2)
+            .map_err(|error| format!("Failed to parse group run workspace: {error}"))?,
+        head_ref: row
+            .get(3)
+            .map_err(|error| format!("Failed to parse group run head ref: {error}"))?,
+        base_ref: row
+            .get(4)
+            .map_err(|error| format!("Failed to parse group run base ref: {error}"))?,
+        head: row
+            .get(5)
+            .map_err(|error| format!("Failed to parse group run head: {error}"))?,
+        status: row
+            .get(6)
+            .map_err(|error| format!("Failed to parse group run status: {error}"))?,
+        finding_count: row
+            .get(7)
+            .map_err(|error| format!("Failed to parse group run finding count: {error}"))?,
+        error: row
+            .get(8)
+            .map_err(|error| format!("Failed to parse group run error: {error}"))?,
+    })
+}
+
+/// Runs of a group in the order the repositories were given. The run columns are `NULL`
+/// when a run was deleted.
+async fn load_group_runs(state: &AppState, group_id: i64) -> Result<Vec<ReviewGroupRun>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT gr.run_id, r.thread_id, gr.workspace, gr.head_ref, r.base_ref, r.head,
+                    r.status, r.finding_count, r.error
+             FROM review_group_runs gr
+             LEFT JOIN ai_review_runs r ON r.run_id = gr.run_id
+             WHERE gr.group_id = ?1
+             ORDER BY gr.position ASC",
+            [group_id],
+        )
+        .await
+        .map_err(|error| format!("Failed to load runs of review group {group_id}: {error}"))?;
+    let mut runs = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read review group run row: {error}"))?
+    {
+        runs.push(parse_group_run_from_row(&row)?);
+    }
+    Ok(runs)
+}
+
+async fn query_review_groups(
+    state: &AppState,
+    group_id: Option<i64>,
+    limit: i64,
+) -> Result<Vec<ReviewGroup>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT id, title, prompt, status, summary, model, error, created_at, ended_at
+             FROM review_groups
+             WHERE ?1 IS NULL OR id = ?1
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?2",
+            (group_id, limit),
+        )
+        .await
+        .map_err(|error| format!("Failed to query review groups: {error}"))?;
+
+    let mut groups = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read review group row: {error}"))?
+    {
+        groups.push(ReviewGroup {
+            id: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse review group id: {error}"))?,
+            title: row
+                .get(1)
+                .map_err(|error| format!("Failed to parse review group title: {error}"))?,
+            prompt: row
+                .get(2)
+                .map_err(|error| format!("Failed to parse review group prompt: {error}"))?,
+            status: row
+                .get(3)
+                .map_err(|error| format!("Failed to parse review group status: {error}"))?,
+            summary: row
+                .get(4)
+                .map_err(|error| format!("Failed to parse review group summary: {error}"))?,
+            model: row
+                .get(5)
+                .map_err(|error| format!("Failed to parse review group model: {error}"))?,
+            error: row
+                .get(6)
+                .map_err(|error| format!("Failed to parse review group error: {error}"))?,
+            created_at: row
+                .get(7)
+                .map_err(|error| format!("Failed to parse review group timestamp: {error}"))?,
+            ended_at: row
+                .get(8)
+                .map_err(|error| format!("Failed to parse review group end time: {error}"))?,
+            runs: Vec::new(),
+        });
+    }
+    for group in &mut groups {
+        group.runs = load_group_runs(state, group.id).await?;
+    }
+    Ok(groups)
+}
+
+async fn load_review_group(state: &AppState, group_id: i64) -> Result<ReviewGroup, String> {
+    query_review_groups(state, Some(group_id), 1)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Review group {group_id} was not found."))
+}
+
+async fn set_group_status(
+    state: &AppState,
+    group_id: i64,
+    status: &str,
+    summary: Option<(&str, &str)>,
+    error: Option<&str>,
+) -> Result<(), String> {
+    let is_final = status == GROUP_STATUS_COMPLETED || status == GROUP_STATUS_FAILED;
+    let conn = state.connection()?;
+    conn.execute(
+        "UPDATE review_groups
+         SET status = ?1,
+             summary = COALESCE(?2, summary),
+             model = COALESCE(?3, model),
+             error = ?4,
+             ended_at = CASE WHEN ?5 = 1 THEN CURRENT_TIMESTAMP END
+         WHERE id = ?6",
+        (
+            status.to_string(),
+            summary.map(|(summary, _)| summary.to_string()),
+            summary.map(|(_, model)| model.to_string()),
+            error.map(ToOwned::to_owned),
+            parse_bool_i64(is_final),
+            group_id,
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to update review group {group_id}: {error}"))?;
+    Ok(())
+}
+
+/// Diffs of the group's runs as stored when the group started, keyed by run id.
+async fn load_group_diffs(
+    state: &AppState,
+    group_id: i64,
+) -> Result<Vec<(String, String, bool)>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT run_id, diff, diff_truncated FROM review_group_runs
+             WHERE group_id = ?1 ORDER BY position ASC",
+            [group_id],
+        )
+        .await
+        .map_err(|error| format!("Failed to load diffs of review group {group_id}: {error}"))?;
+    let mut diffs = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read review group diff row: {error}"))?
+    {
+        let truncated: i64 = row
+            .get(2)
+            .map_err(|error| format!("Failed to parse review group diff flag: {error}"))?;
+        diffs.push((
+            row.get(0)
+                .map_err(|error| format!("Failed to parse review group run id: {error}"))?,
+            row.get(1)
+                .map_err(|error| format!("Failed to parse review group diff: {error}"))?,
+            truncated != 0,
+        ));
+    }
+    Ok(diffs)
+}
+
+async fn generate_group_summary(
+    app: &AppHandle,
+    state: &AppState,
+    group: &ReviewGroup,
+) -> Result<(String, String), String> {
+    let diffs = load_group_diffs(state, group.id).await?;
+    let mut sections = Vec::new();
+    let mut reviewed = 0;
+    for group_run in &group.runs {
+        let run = match group_run.status.as_deref() {
+            Some("completed" | "completed_with_errors") => {
+                Some(store::load_ai_review_run_by_id(state, &group_run.run_id).await?)
+            }
+            _ => None,
+        };
+        reviewed += usize::from(run.is_some());
+        let (diff, diff_truncated) = diffs
+            .iter()
+            .find(|(run_id, _, _)| *run_id == group_run.run_id)
+            .map(|(_, diff, truncated)| (diff.clone(), *truncated))
+            .unwrap_or_default();
+        sections.push((group_run.clone(), run, diff, diff_truncated));
+    }
+    if reviewed == 0 {
+        return Err("None of the repository reviews completed.".to_string());
+    }
+
+    let review_provider = ReviewProvider::from_settings()?;
+    for group_run in &group.runs {
+        review_provider.ensure_allowed_for_workspace(
+            &group_run.workspace,
+            is_workspace_trusted(state, &group_run.workspace).await?,
+        )?;
+    }
+    let prompt = build_group_summary_prompt(group, &sections);
+    generate_text_with_review_provider(app, review_provider, &group.runs[0].workspace, &prompt)
+        .await
+}
+
+/// Writes the cross-repository summary of a group whose runs have all finished.
+async fn summarize_group(
+    app: &AppHandle,
+    state: &AppState,
+    group_id: i64,
+) -> Result<ReviewGroup, String> {
+    let group = load_review_group(state, group_id).await?;
+    if group.status == GROUP_STATUS_SUMMARIZING {
+        return Err(format!(
+            "Review group {group_id} is already being summarized."
+        ));
+    }
+    if group
+        .runs
+        .iter()
+        .any(|run| matches!(run.status.as_deref(), Some("queued" | "running")))
+    {
+        return Err(format!(
+            "Review group {group_id} still has queued or running reviews."
+        ));
+    }
+
+    set_group_status(state, group_id, GROUP_STATUS_SUMMARIZING, None, None).await?;
+    match generate_group_summary(app, state, &group).await {
+        Ok((summary, model)) => {
+            set_group_status(
+                state,
+                group_id,
+                GROUP_STATUS_COMPLETED,
+                Some((&summary, &model)),
+                None,
+            )
+            .await?
+        }
+        Err(error) => {
+            set_group_status(state, group_id, GROUP_STATUS_FAILED, None, Some(&error)).await?
+        }
+    }
+    load_review_group(state, group_id).await
+}
+
+async fn insert_review_group(
+    state: &AppState,
+    title: &str,
+    prompt: Option<&str>,
+) -> Result<i64, String> {
+    let conn = state.connection()?;
+    conn.execute(
+        "INSERT INTO review_groups (title, prompt) VALUES (?1, ?2)",
+        (title.to_string(), prompt.map(ToOwned::to_owned)),
+    )
+    .await
+    .map_err(|error| format!("Failed to create review group: {error}"))?;
+    let mut rows = conn
+        .query("SELECT last_insert_rowid()", ())
+        .await
+        .map_err(|error| format!("Failed to fetch new review group id: {error}"))?;
+    rows.next()
+        .await
+        .map_err(|error| format!("Failed to read review group id row: {error}"))?
+        .ok_or_else(|| {
+            "Missing last_insert_rowid result after creating a review group.".to_string()
+        })?
+        .get(0)
+        .map_err(|error| format!("Failed to parse new review group id: {error}"))
+}
+
+async fn start_group_runs(
+    app: &AppHandle,
+    state: &AppState,
+    group_id: i64,
+    title: &str,
+    prompt: Option<&str>,
+    diffs: Vec<(CompareWorkspaceDiffResult, Option<String>)>,
+) -> Result<(), String> {
+    // The summary prompt carries every diff, so they share the diff budget of one review.
+    let diff_budget = parse_setting_usize(
+        ROVEX_REVIEW_MAX_DIFF_CHARS_ENV,
+        DEFAULT_REVIEW_MAX_DIFF_CHARS,
+        1_000,
+    ) / diffs.len();
+    let results = diffs
+        .iter()
+        .map(|(diff, _)| diff.clone())
+        .collect::<Vec<_>>();
+    for (position, (diff, head_ref)) in diffs.into_iter().enumerate() {
+        let thread = find_or_create_thread(state, GROUP_THREAD_TITLE, &diff.workspace).await?;
+        let (summary_diff, summary_diff_truncated) = truncate_chars(&diff.diff, diff_budget);
+        let workspace = diff.workspace.clone();
+        let result = start_ai_review_run(
+            app.clone(),
+            app.state(),
+            StartAiReviewRunInput {
+                thread_id: thread.id,
+                workspace: diff.workspace,
+                base_ref: diff.base_ref,
+                merge_base: diff.merge_base,
+                head: diff.head,
+                files_changed: diff.files_changed,
+                insertions: diff.insertions,
+                deletions: diff.deletions,
+                diff: diff.diff,
+                prompt: Some(build_group_run_prompt(prompt, &results, position)),
+                scope_label: Some(format!("{title} ({})", repository_name(&workspace))),
+                max_parallel_chunks: None,
+                review_mode: None,
+                include_paths: None,
+                exclude_paths: None,
+                diff_target: Some(WorkspaceDiffTarget::Branch),
+                idempotency_key: None,
+                profile_id: None,
+                min_confidence: None,
+            },
+        )
+        .await?;
+
+        let conn = state.connection()?;
+        conn.execute(
+            "INSERT INTO review_group_runs
+               (group_id, run_id, position, workspace, head_ref, diff, diff_truncated)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            (
+                group_id,
+                result.run.run_id,
+                position as i64,
+                workspace,
+                head_ref,
+                summary_diff,
+                parse_bool_i64(summary_diff_truncated),
+            ),
+        )
+        .await
+        .map_err(|error| format!("Failed to link run to review group {group_id}: {error}"))?;
+    }
+    Ok(())
+}
+
+pub async fn start_review_group(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: StartReviewGroupInput,
+) -> Result<ReviewGroup, String> {
+    if input.repositories.len() < 2 {
+        return Err("A review group needs at least two repositories.".to_string());
+    }
+    if input.repositories.len() > MAX_GROUP_REPOSITORIES {
+        return Err(format!(
+            "A review group can include at most {MAX_GROUP_REPOSITORIES} repositories."
+        ));
+    }
+
+    // Every diff is computed before any run starts, so a bad repository fails the whole group.
+    let mut diffs = Vec::new();
+    for repository in &input.repositories {
+        let head_ref = as_non_empty_trimmed(repository.head_ref.as_deref());
+        let diff = compare_workspace_diff(CompareWorkspaceDiffInput {
+            workspace: repository.workspace.clone(),
+            base_ref: repository.base_ref.clone(),
+            fetch_remote: repository.fetch_remote,
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: Some(WorkspaceDiffTarget::Branch),
+            include_untracked: None,
+            head_ref: head_ref.clone(),
+            include_submodule_diffs: None,
+            force: None,
+            remote: None,
+        })
+        .await?;
+        if diff.diff.trim().is_empty() {
+            return Err(format!("{} has no changes to review.", diff.workspace));
+        }
+        diffs.push((diff, head_ref));
+    }
+
+    let title = as_non_empty_trimmed(input.title.as_deref()).unwrap_or_else(|| {
+        let names = diffs
+            .iter()
+            .map(|(diff, _)| repository_name(&diff.workspace))
+            .collect::<Vec<_>>();
+        format!("Cross-repo review of {}", names.join(", "))
+    });
+    let prompt = as_non_empty_trimmed(input.prompt.as_deref());
+    let group_id = insert_review_group(&state, &title, prompt.as_deref()).await?;
+    if let Err(error) =
+        start_group_runs(&app, &state, group_id, &title, prompt.as_deref(), diffs).await
+    {
+        // Runs that already started keep going; the group only records why it is incomplete.
+        set_group_status(&state, group_id, GROUP_STATUS_FAILED, None, Some(&error)).await?;
+        return Err(error);
+    }
+    load_review_group(&state, group_id).await
+}
+
+pub async fn get_review_group(
+    state: State<'_, AppState>,
+    input: ReviewGroupInput,
+) -> Result<ReviewGroup, String> {
+    load_review_group(&state, input.group_id).await
+}
+
+pub async fn list_review_groups(
+    state: State<'_, AppState>,
+    input: ListReviewGroupsInput,
+) -> Result<ListReviewGroupsResult, String> {
+    let groups = query_review_groups(&state, None, parse_limit(input.limit)).await?;
+    Ok(ListReviewGroupsResult { groups })
+}
+
+/// Regenerates the summary of a finished group, for example after changing the model.
+pub async fn summarize_review_group(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: ReviewGroupInput,
+) -> Result<ReviewGroup, String> {
+    summarize_group(&app, &state, input.group_id).await
+}
+
+async fn summarize_finished_review_groups(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT g.id FROM review_groups g
+             WHERE g.status = ?1 AND NOT EXISTS (
+               SELECT 1 FROM review_group_runs gr
+               JOIN ai_review_runs r ON r.run_id = gr.run_id
+               WHERE gr.group_id = g.id AND r.status IN ('queued', 'running'))",
+            [GROUP_STATUS_RUNNING],
+        )
+        .await
+        .map_err(|error| format!("Failed to query finished review groups: {error}"))?;
+    let mut group_ids = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read review group row: {error}"))?
+    {
+        let group_id: i64 = row
+            .get(0)
+            .map_err(|error| format!("Failed to parse review group id: {error}"))?;
+        group_ids.push(group_id);
+    }
+    for group_id in group_ids {
+        if let Err(error) = summarize_group(app, &state, group_id).await {
+            eprintln!("[backend] Failed to summarize review group {group_id}: {error}");
+        }
+    }
+    Ok(())
+}
+
+/// Starts the background task that writes the cross-repository summary once every run of a
+/// group has finished. Groups left running when the app exited are picked up on the next start.
+pub(crate) fn start_review_group_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(GROUP_WATCH_TICK).await;
+            if let Err(error) = summarize_finished_review_groups(&app).await {
+                eprintln!("[backend] Failed to check review groups: {error}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_group_summary_prompt, repository_name};
+    use crate::backend::{ReviewGroup, ReviewGroupRun};
+
+    fn group_run(workspace: &str, status: &str) -> ReviewGroupRun {
+        ReviewGroupRun {
+            run_id: format!("run-{workspace}"),
+            thread_id: Some(1),
+            workspace: workspace.to_string(),
+            head_ref: None,
+            base_ref: Some("origin/main".to_string()),
+            head: Some("abc".to_string()),
+            status: Some(status.to_string()),
+            finding_count: Some(0),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn summary_prompt_lists_every_repository() {
+        let group = ReviewGroup {
+            id: 1,
+            title: "Rename user id".to_string(),
+            prompt: Some("Check the API contract".to_string()),
+            status: "running".to_string(),
+            summary: None,
+            model: None,
+            error: None,
+            created_at: "2026-01-01 00:00:00".to_string(),
+            ended_at: None,
+            runs: Vec::new(),
+        };
+        let prompt = build_group_summary_prompt(
+            &group,
+            &[
+                (
+                    group_run("/src/api", "failed"),
+                    None,
+                    "+ userId".to_string(),
+                    false,
+                ),
+                (
+                    group_run("/src/web", "canceled"),
+                    None,
+                    String::new(),
+                    false,
+                ),
+            ],
+        );
+        assert!(prompt.contains("spans 2 repositories"));
+        assert!(prompt.contains("Reviewer goal: Check the API contract"));
+        assert!(prompt.contains("### Repository 1: api"));
+        assert!(prompt.contains("Review status: canceled (no review available)"));
+        assert_eq!(repository_name("/src/web"), "web");
+    }
+}
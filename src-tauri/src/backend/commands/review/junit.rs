@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+
+use super::super::name_sort::compare_paths;
+use super::findings::{FINDING_STATUS_DISMISSED, FINDING_STATUS_FIXED};
+use crate::backend::{AiReviewChunk, AiReviewFinding, AiReviewRun};
+
+const JUNIT_SUITES_NAME: &str = "rovex";
+/// Chunk outcome of a review that finished normally.
+const CHUNK_OUTCOME_REVIEWED: &str = "reviewed";
+
+/// Escapes text for XML attributes and content, dropping control characters XML 1.0 does not
+/// allow.
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(character),
+            character if character.is_control() => {}
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[derive(Default)]
+struct FileCases<'a> {
+    findings: Vec<&'a AiReviewFinding>,
+    unreviewed_chunks: Vec<&'a AiReviewChunk>,
+}
+
+fn finding_case(finding: &AiReviewFinding) -> String {
+    let mut case = format!(
+        "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\" line=\"{}\">\n",
+        escape_xml(&format!(
+            "{}:{} {}",
+            finding.file_path, finding.line_number, finding.title
+        )),
+        escape_xml(&finding.file_path),
+        escape_xml(&finding.file_path),
+        finding.line_number
+    );
+    match finding.status.as_deref() {
+        Some(FINDING_STATUS_DISMISSED) => {
+            case.push_str("      <skipped message=\"dismissed\"/>\n");
+        }
+        Some(FINDING_STATUS_FIXED) => {}
+        _ => {
+            let mut details = format!(
+                "{}\n\n{}:{} ({})",
+                finding.body, finding.file_path, finding.line_number, finding.side
+            );
+            if let Some(category) = finding.category.as_deref() {
+                details.push_str(&format!("\nCategory: {category}"));
+            }
+            case.push_str(&format!(
+                "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                escape_xml(&finding.title),
+                escape_xml(&finding.severity),
+                escape_xml(&details)
+            ));
+        }
+    }
+    case.push_str("    </testcase>\n");
+    case
+}
+
+fn unreviewed_chunk_case(chunk: &AiReviewChunk) -> String {
+    let outcome = chunk.outcome.as_deref().unwrap_or("error");
+    format!(
+        "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\">\n      <error message=\"{}\" type=\"{}\">{}</error>\n    </testcase>\n",
+        escape_xml(&format!("{} {}", chunk.file_path, chunk.hunk_header)),
+        escape_xml(&chunk.file_path),
+        escape_xml(&chunk.file_path),
+        escape_xml(&format!("Chunk {} could not be reviewed", chunk.chunk_index)),
+        escape_xml(outcome),
+        escape_xml(&chunk.summary)
+    )
+}
+
+/// One test suite per reviewed file and one failing test case per open finding. Dismissed
+/// findings are skipped cases, fixed ones pass, chunks that could not be reviewed are errors,
+/// and files without findings get a single passing case so CI still lists them.
+fn junit_report(name: &str, chunks: &[AiReviewChunk], findings: &[AiReviewFinding]) -> String {
+    let mut files: BTreeMap<&str, FileCases> = BTreeMap::new();
+    for chunk in chunks {
+        let cases = files.entry(chunk.file_path.as_str()).or_default();
+        if chunk
+            .outcome
+            .as_deref()
+            .is_some_and(|outcome| outcome != CHUNK_OUTCOME_REVIEWED)
+        {
+            cases.unreviewed_chunks.push(chunk);
+        }
+    }
+    for finding in findings {
+        files
+            .entry(finding.file_path.as_str())
+            .or_default()
+            .findings
+            .push(finding);
+    }
+    let mut files = files.into_iter().collect::<Vec<_>>();
+    files.sort_by(|(left, _), (right, _)| compare_paths(left, right));
+
+    let mut suites = String::new();
+    let (mut total_tests, mut total_failures, mut total_errors, mut total_skipped) =
+        (0usize, 0usize, 0usize, 0usize);
+    for (file_path, mut cases) in files {
+        cases.findings.sort_by_key(|finding| finding.line_number);
+        let skipped = cases
+            .findings
+            .iter()
+            .filter(|finding| finding.status.as_deref() == Some(FINDING_STATUS_DISMISSED))
+            .count();
+        let failures = cases
+            .findings
+            .iter()
+            .filter(|finding| {
+                !matches!(
+                    finding.status.as_deref(),
+                    Some(FINDING_STATUS_DISMISSED | FINDING_STATUS_FIXED)
+                )
+            })
+            .count();
+        let errors = cases.unreviewed_chunks.len();
+        let tests = (cases.findings.len() + errors).max(1);
+        let mut body = String::new();
+        for finding in &cases.findings {
+            body.push_str(&finding_case(finding));
+        }
+        for chunk in &cases.unreviewed_chunks {
+            body.push_str(&unreviewed_chunk_case(chunk));
+        }
+        if body.is_empty() {
+            body.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\"/>\n",
+                escape_xml(&format!("{file_path} has no findings")),
+                escape_xml(file_path),
+                escape_xml(file_path)
+            ));
+        }
+        suites.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\">\n{body}  </testsuite>\n",
+            escape_xml(file_path)
+        ));
+        total_tests += tests;
+        total_failures += failures;
+        total_errors += errors;
+        total_skipped += skipped;
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"{}\" tests=\"{total_tests}\" failures=\"{total_failures}\" errors=\"{total_errors}\" skipped=\"{total_skipped}\">\n{suites}</testsuites>\n",
+        escape_xml(name)
+    )
+}
+
+/// Serializes a run as a JUnit XML report, for CI systems that show test results but not
+/// SARIF.
+pub(crate) fn build_junit_report(run: &AiReviewRun) -> String {
+    junit_report(
+        &format!("{JUNIT_SUITES_NAME} {}", run.run_id),
+        &run.chunks,
+        &run.findings,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::junit_report;
+    use crate::backend::{AiReviewChunk, AiReviewFinding};
+
+    fn finding(file_path: &str, line_number: i64, status: Option<&str>) -> AiReviewFinding {
+        AiReviewFinding {
+            id: format!("{file_path}:{line_number}"),
+            file_path: file_path.to_string(),
+            chunk_id: format!("{file_path}#file-1"),
+            chunk_index: 1,
+            hunk_header: "@@ -1 +1 @@".to_string(),
+            side: "additions".to_string(),
+            line_number,
+            title: "Unchecked <unwrap> & panic".to_string(),
+            body: "Panics when the \"config\" is empty.".to_string(),
+            severity: "high".to_string(),
+            confidence: None,
+            category: Some("correctness".to_string()),
+            fingerprint: None,
+            status: status.map(ToString::to_string),
+            code_hash: None,
+            recurring: None,
+            suggested_patch: None,
+            language: None,
+            blame: None,
+        }
+    }
+
+    fn chunk(file_path: &str, outcome: &str) -> AiReviewChunk {
+        AiReviewChunk {
+            id: format!("{file_path}#file-1"),
+            file_path: file_path.to_string(),
+            chunk_index: 1,
+            hunk_header: "@@ -1 +1 @@".to_string(),
+            summary: "Summary".to_string(),
+            findings: Vec::new(),
+            token_usage: None,
+            outcome: Some(outcome.to_string()),
+            language: None,
+        }
+    }
+
+    #[test]
+    fn findings_become_failing_cases_grouped_by_file() {
+        let report = junit_report(
+            "rovex run-1",
+            &[
+                chunk("src/main.rs", "reviewed"),
+                chunk("src/lib.rs", "reviewed"),
+                chunk("src/gen.rs", "error"),
+            ],
+            &[
+                finding("src/lib.rs", 20, Some("dismissed")),
+                finding("src/lib.rs", 10, None),
+            ],
+        );
+        assert!(report.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"rovex run-1\" tests=\"4\" failures=\"1\" errors=\"1\" skipped=\"1\">"));
+        let gen = report.find("name=\"src/gen.rs\"").unwrap();
+        let lib = report.find("name=\"src/lib.rs\"").unwrap();
+        let main = report.find("name=\"src/main.rs\"").unwrap();
+        assert!(gen < lib && lib < main);
+        assert!(report.contains(
+            "<testsuite name=\"src/lib.rs\" tests=\"2\" failures=\"1\" errors=\"0\" skipped=\"1\">"
+        ));
+        assert!(report.contains(
+            "<failure message=\"Unchecked &lt;unwrap&gt; &amp; panic\" type=\"high\">Panics when the &quot;config&quot; is empty."
+        ));
+        assert!(report.find("line=\"10\"").unwrap() < report.find("line=\"20\"").unwrap());
+        assert!(report.contains("<error message=\"Chunk 1 could not be reviewed\" type=\"error\">"));
+        assert!(report.contains("name=\"src/main.rs has no findings\""));
+    }
+}
@@ -0,0 +1,252 @@
+use std::path::Path;
+
+use super::diff_chunks::parse_hunk_positions;
+
+struct Language {
+    /// Stable lowercase id stored on chunks and findings.
+    id: &'static str,
+    name: &'static str,
+    extensions: &'static [&'static str],
+    file_names: &'static [&'static str],
+}
+
+const LANGUAGES: &[Language] = &[
+    language("rust", "Rust", &["rs"], &[]),
+    language(
+        "typescript",
+        "TypeScript",
+        &["ts", "tsx", "mts", "cts"],
+        &[],
+    ),
+    language(
+        "javascript",
+        "JavaScript",
+        &["js", "jsx", "mjs", "cjs"],
+        &[],
+    ),
+    language("python", "Python", &["py", "pyi", "pyw"], &[]),
+    language("go", "Go", &["go"], &[]),
+    language("java", "Java", &["java"], &[]),
+    language("kotlin", "Kotlin", &["kt", "kts"], &[]),
+    language("swift", "Swift", &["swift"], &[]),
+    language("c", "C", &["c"], &[]),
+    language(
+        "cpp",
+        "C++",
+        &["cc", "cpp", "cxx", "c++", "hh", "hpp", "hxx"],
+        &[],
+    ),
+    language("csharp", "C#", &["cs"], &[]),
+    language("objective-c", "Objective-C", &["mm"], &[]),
+    language(
+        "ruby",
+        "Ruby",
+        &["rb", "rake", "gemspec"],
+        &["Gemfile", "Rakefile"],
+    ),
+    language("php", "PHP", &["php"], &[]),
+    language("scala", "Scala", &["scala", "sc"], &[]),
+    language("dart", "Dart", &["dart"], &[]),
+    language("elixir", "Elixir", &["ex", "exs"], &[]),
+    language("haskell", "Haskell", &["hs"], &[]),
+    language("lua", "Lua", &["lua"], &[]),
+    language("perl", "Perl", &["pl", "pm"], &[]),
+    language("r", "R", &["r"], &[]),
+    language("zig", "Zig", &["zig"], &[]),
+    language("nix", "Nix", &["nix"], &[]),
+    language("shell", "Shell", &["sh", "bash", "zsh"], &[]),
+    language("powershell", "PowerShell", &["ps1", "psm1"], &[]),
+    language("sql", "SQL", &["sql"], &[]),
+    language("html", "HTML", &["html", "htm"], &[]),
+    language("css", "CSS", &["css", "scss", "sass", "less"], &[]),
+    language("vue", "Vue", &["vue"], &[]),
+    language("svelte", "Svelte", &["svelte"], &[]),
+    language("markdown", "Markdown", &["md", "mdx"], &[]),
+    language("json", "JSON", &["json", "jsonc"], &[]),
+    language("yaml", "YAML", &["yml", "yaml"], &[]),
+    language("toml", "TOML", &["toml"], &["Cargo.lock"]),
+    language("xml", "XML", &["xml", "xsd", "plist"], &[]),
+    language("protobuf", "Protocol Buffers", &["proto"], &[]),
+    language("graphql", "GraphQL", &["graphql", "gql"], &[]),
+    language("terraform", "Terraform", &["tf", "tfvars"], &[]),
+    language("dockerfile", "Dockerfile", &["dockerfile"], &["Dockerfile"]),
+    language(
+        "makefile",
+        "Makefile",
+        &["mk"],
+        &["Makefile", "makefile", "GNUmakefile"],
+    ),
+    language("matlab", "MATLAB", &[], &[]),
+];
+
+const fn language(
+    id: &'static str,
+    name: &'static str,
+    extensions: &'static [&'static str],
+    file_names: &'static [&'static str],
+) -> Language {
+    Language {
+        id,
+        name,
+        extensions,
+        file_names,
+    }
+}
+
+/// Display name of a language id returned by [`detect_language`].
+pub(crate) fn language_name(id: &str) -> Option<&'static str> {
+    LANGUAGES
+        .iter()
+        .find(|language| language.id == id)
+        .map(|language| language.name)
+}
+
+/// Text of the lines a patch shows, without their `+`, `-` or space prefix.
+fn patch_content_lines(patch: &str) -> impl Iterator<Item = &str> {
+    patch
+        .lines()
+        .skip_while(|line| !line.starts_with("@@ "))
+        .filter(|line| !line.starts_with("@@ ") && !line.starts_with('\\'))
+        .map(|line| line.get(1..).unwrap_or_default())
+}
+
+/// The interpreter a script's `#!` line names, when the patch shows the file's first line.
+fn shebang_language(patch: &str) -> Option<&'static str> {
+    let mut lines = patch.lines().skip_while(|line| !line.starts_with("@@ "));
+    let (_, new_start) = parse_hunk_positions(lines.next()?)?;
+    if new_start > 1 {
+        return None;
+    }
+    let first_line = lines.next()?.get(1..)?;
+    let interpreter = first_line.strip_prefix("#!")?;
+    let interpreter = interpreter
+        .split_whitespace()
+        .find(|word| !word.ends_with("/env") && !word.starts_with('-'))?;
+    let interpreter = interpreter.rsplit('/').next().unwrap_or(interpreter);
+    match interpreter
+        .trim_end_matches(|character: char| character.is_ascii_digit() || character == '.')
+    {
+        "python" => Some("python"),
+        "node" | "deno" | "bun" => Some("javascript"),
+        "sh" | "bash" | "zsh" | "dash" | "ksh" => Some("shell"),
+        "ruby" => Some("ruby"),
+        "perl" => Some("perl"),
+        "php" => Some("php"),
+        "pwsh" => Some("powershell"),
+        _ => None,
+    }
+}
+
+/// Languages an extension is shared by, told apart by what the changed lines contain.
+fn disambiguate(extension: &str, patch: &str) -> Option<&'static str> {
+    let contains_any = |needles: &[&str]| {
+        patch_content_lines(patch).any(|line| needles.iter().any(|needle| line.contains(needle)))
+    };
+    match extension {
+        "h" => Some(
+            if contains_any(&["@interface", "@implementation", "#import", "@property"]) {
+                "objective-c"
+            } else if contains_any(&["class ", "namespace ", "template<", "template <", "std::"]) {
+                "cpp"
+            } else {
+                "c"
+            },
+        ),
+        "m" => Some(
+            if contains_any(&["@interface", "@implementation", "#import", "@end"]) {
+                "objective-c"
+            } else {
+                "matlab"
+            },
+        ),
+        // Qt translation files share the TypeScript extension.
+        "ts" if patch_content_lines(patch)
+            .find(|line| !line.trim().is_empty())
+            .is_some_and(|line| {
+                let line = line.trim_start();
+                line.starts_with("<?xml") || line.starts_with("<TS")
+            }) =>
+        {
+            Some("xml")
+        }
+        _ => None,
+    }
+}
+
+/// The language of a changed file, from its name and extension, the content of `patch` for
+/// extensions several languages share, and the `#!` line of scripts without an extension.
+pub(crate) fn detect_language(file_path: &str, patch: &str) -> Option<&'static str> {
+    let path = Path::new(file_path);
+    let file_name = path.file_name()?.to_str()?;
+    if let Some(language) = LANGUAGES
+        .iter()
+        .find(|language| language.file_names.contains(&file_name))
+    {
+        return Some(language.id);
+    }
+    if file_name.starts_with("Dockerfile.") {
+        return Some("dockerfile");
+    }
+    let Some(extension) = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase)
+    else {
+        return shebang_language(patch);
+    };
+    if let Some(language) = disambiguate(&extension, patch) {
+        return Some(language);
+    }
+    LANGUAGES
+        .iter()
+        .find(|language| language.extensions.contains(&extension.as_str()))
+        .map(|language| language.id)
+        .or_else(|| shebang_language(patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_language, language_name};
+
+    #[test]
+    fn detects_languages_from_names_and_content() {
+        assert_eq!(detect_language("src/lib.rs", ""), Some("rust"));
+        assert_eq!(detect_language("web/App.TSX", ""), Some("typescript"));
+        assert_eq!(
+            detect_language("build/Dockerfile.dev", ""),
+            Some("dockerfile")
+        );
+        assert_eq!(detect_language("Makefile", ""), Some("makefile"));
+        assert_eq!(detect_language("notes.txt", ""), None);
+
+        let header = |line: &str| format!("@@ -0,0 +1,2 @@\n+{line}\n+int x;\n");
+        assert_eq!(
+            detect_language("include/a.h", &header("#include <stdio.h>")),
+            Some("c")
+        );
+        assert_eq!(
+            detect_language("include/a.h", &header("namespace app {")),
+            Some("cpp")
+        );
+        assert_eq!(
+            detect_language("Sources/View.m", &header("@implementation View")),
+            Some("objective-c")
+        );
+        assert_eq!(
+            detect_language("i18n/app_de.ts", &header("<?xml version=\"1.0\"?>")),
+            Some("xml")
+        );
+        assert_eq!(
+            detect_language("bin/deploy", &header("#!/usr/bin/env python3")),
+            Some("python")
+        );
+        assert_eq!(
+            detect_language(
+                "bin/deploy",
+                "@@ -4,1 +4,1 @@\n-#!/bin/bash\n+#!/bin/bash\n"
+            ),
+            None
+        );
+        assert_eq!(language_name("cpp"), Some("C++"));
+    }
+}
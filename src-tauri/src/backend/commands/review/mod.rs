@@ -1,19 +1,55 @@
+pub(crate) mod ai_requests;
+pub(crate) mod chunk_outcome;
+pub(crate) mod commit_message;
+pub(crate) mod commit_status;
 pub(crate) mod config;
+pub(crate) mod cross_file;
+pub(crate) mod demo;
 pub(crate) mod diff_chunks;
+pub(crate) mod eta;
 pub(crate) mod executor;
 #[cfg(test)]
 mod executor_tests;
+pub(crate) mod finding_actions;
+pub(crate) mod finding_order;
+pub(crate) mod finding_quality;
+pub(crate) mod findings;
 pub(crate) mod follow_up;
+pub(crate) mod follow_up_cache;
+pub(crate) mod follow_up_diff;
+pub(crate) mod follow_up_suggestions;
+pub(crate) mod follow_up_tools;
+pub(crate) mod git_hooks;
+pub(crate) mod groups;
+pub(crate) mod junit;
+pub(crate) mod languages;
+pub(crate) mod model_stats;
+pub(crate) mod notifications;
+pub(crate) mod prompt_safety;
+pub(crate) mod queue_status;
+pub(crate) mod report;
+pub(crate) mod resource_usage;
+pub(crate) mod reviewer_profiles;
+pub(crate) mod run_comparison;
 pub(crate) mod run_queue;
+pub(crate) mod run_snapshots;
+pub(crate) mod sarif;
+pub(crate) mod schedules;
 pub(crate) mod store;
+pub(crate) mod team_stats;
+pub(crate) mod thread_title;
+pub(crate) mod token_usage;
 pub(crate) mod transports;
-
-use std::env;
+pub(crate) mod triage;
+pub(crate) mod ui_activity;
+pub(crate) mod verdict;
 
 use tauri::{AppHandle, Emitter};
 
 use self::store::append_ai_review_run_progress;
+use self::ui_activity::{wait_for_ui_idle, MAX_PROGRESS_WRITE_DEFERRAL};
 use super::common::{AI_REVIEW_PROGRESS_EVENT, DEFAULT_REVIEW_PROVIDER, ROVEX_REVIEW_PROVIDER_ENV};
+use crate::backend::settings::settings_store;
 use crate::backend::{AiReviewProgressEvent, AppState};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,11 +60,10 @@ pub(crate) enum ReviewProvider {
 }
 
 impl ReviewProvider {
-    pub(crate) fn from_env() -> Result<Self, String> {
-        let provider = env::var(ROVEX_REVIEW_PROVIDER_ENV)
-            .ok()
-            .map(|value| value.trim().to_lowercase())
-            .filter(|value| !value.is_empty())
+    pub(crate) fn from_settings() -> Result<Self, String> {
+        let provider = settings_store()
+            .get_string(ROVEX_REVIEW_PROVIDER_ENV)
+            .map(|value| value.to_lowercase())
             .unwrap_or_else(|| DEFAULT_REVIEW_PROVIDER.to_string());
         match provider.as_str() {
             "openai" => Ok(Self::OpenAi),
@@ -39,18 +74,76 @@ impl ReviewProvider {
             )),
         }
     }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::OpenAi => "openai",
+            Self::Opencode => "opencode",
+            Self::AppServer => "app-server",
+        }
+    }
+
+    /// Whether the transport runs an agent that can read files and call tools in the workspace.
+    pub(crate) fn uses_workspace_tools(self) -> bool {
+        !matches!(self, Self::OpenAi)
+    }
+
+    /// Rejects agentic transports for workspaces that are not trusted.
+    pub(crate) fn ensure_allowed_for_workspace(
+        self,
+        workspace: &str,
+        trusted: bool,
+    ) -> Result<(), String> {
+        if trusted || !self.uses_workspace_tools() {
+            return Ok(());
+        }
+        Err(format!(
+            "Workspace {workspace} is untrusted, so the '{}' review provider cannot run tools in it. Switch the review provider to 'openai' or mark the workspace as trusted.",
+            self.as_str()
+        ))
+    }
 }
-pub(crate) fn emit_ai_review_progress(app: &AppHandle, event: &AiReviewProgressEvent) {
-    let _ = app.emit(AI_REVIEW_PROGRESS_EVENT, event);
+
+/// Where a review runs: inside the desktop app, whose event bus and plugins it can use, or
+/// headless from the CLI, where progress goes to stderr.
+#[derive(Clone)]
+pub(crate) enum ReviewHost {
+    App(AppHandle),
+    Headless,
+}
+
+impl ReviewHost {
+    pub(crate) fn app_handle(&self) -> Option<&AppHandle> {
+        match self {
+            Self::App(app) => Some(app),
+            Self::Headless => None,
+        }
+    }
+}
+
+pub(crate) fn emit_ai_review_progress(host: &ReviewHost, event: &AiReviewProgressEvent) {
+    match host {
+        ReviewHost::App(app) => {
+            let _ = app.emit(AI_REVIEW_PROGRESS_EVENT, event);
+        }
+        // Streamed description tokens would drown out the progress lines.
+        ReviewHost::Headless if event.status != "description-delta" => {
+            eprintln!("[review] {}", event.message);
+        }
+        ReviewHost::Headless => {}
+    }
 }
 
 pub(crate) async fn emit_and_persist_ai_review_progress(
-    app: &AppHandle,
+    host: &ReviewHost,
     state: &AppState,
     run_id: &str,
     event: AiReviewProgressEvent,
 ) {
-    emit_ai_review_progress(app, &event);
+    emit_ai_review_progress(host, &event);
+    // Each write rewrites the run's progress columns, so it waits out a burst of typing or
+    // scrolling rather than competing with the UI for the database and CPU.
+    wait_for_ui_idle(MAX_PROGRESS_WRITE_DEFERRAL).await;
     if let Err(error) = append_ai_review_run_progress(state, run_id, &event).await {
         eprintln!("[backend] Failed to persist AI review progress for {run_id}: {error}");
     }
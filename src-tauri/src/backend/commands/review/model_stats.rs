@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use crate::backend::{AiReviewTokenUsage, ModelPerformanceStat};
+
+pub(crate) const CHUNK_REVIEW_PROMPT_PROFILE: &str = "chunk-review";
+pub(crate) const DESCRIPTION_PROMPT_PROFILE: &str = "description";
+pub(crate) const TRIAGE_PROMPT_PROFILE: &str = "triage";
+pub(crate) const CROSS_FILE_PROMPT_PROFILE: &str = "cross-file";
+pub(crate) const FOLLOW_UP_SUGGESTIONS_PROMPT_PROFILE: &str = "follow-up-suggestions";
+
+/// Models with fewer recorded requests than this are not considered for a suggested default.
+const MIN_REQUESTS_FOR_SUGGESTION: u64 = 5;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ModelStatsDelta {
+    pub(crate) request_count: u64,
+    pub(crate) failure_count: u64,
+    pub(crate) prompt_tokens: u64,
+    pub(crate) completion_tokens: u64,
+    pub(crate) estimated_cost_usd: Option<f64>,
+}
+
+/// Collects per (transport, model, prompt profile) outcomes during a run so they can be
+/// persisted with a single upsert per key once the run finishes.
+#[derive(Default)]
+pub(crate) struct ModelStatsRecorder {
+    deltas: HashMap<(String, String, String), ModelStatsDelta>,
+}
+
+impl ModelStatsRecorder {
+    pub(crate) fn record(
+        &mut self,
+        transport: &str,
+        model: &str,
+        prompt_profile: &str,
+        succeeded: bool,
+        usage: Option<&AiReviewTokenUsage>,
+    ) {
+        let model = model.trim();
+        if model.is_empty() {
+            return;
+        }
+        let delta = self
+            .deltas
+            .entry((
+                transport.to_string(),
+                model.to_string(),
+                prompt_profile.to_string(),
+            ))
+            .or_default();
+        delta.request_count += 1;
+        if !succeeded {
+            delta.failure_count += 1;
+        }
+        if let Some(usage) = usage {
+            delta.prompt_tokens = delta.prompt_tokens.saturating_add(usage.prompt_tokens);
+            delta.completion_tokens = delta
+                .completion_tokens
+                .saturating_add(usage.completion_tokens);
+            delta.estimated_cost_usd = match (delta.estimated_cost_usd, usage.estimated_cost_usd) {
+                (Some(left), Some(right)) => Some(left + right),
+                (left, right) => left.or(right),
+            };
+        }
+    }
+
+    pub(crate) fn into_deltas(self) -> Vec<((String, String, String), ModelStatsDelta)> {
+        self.deltas.into_iter().collect()
+    }
+}
+
+/// Picks the chunk-review model with the lowest failure rate for `transport`, preferring
+/// fewer tokens per request on ties. Models without enough history are ignored.
+pub(crate) fn suggest_default_model(
+    stats: &[ModelPerformanceStat],
+    transport: &str,
+) -> Option<String> {
+    stats
+        .iter()
+        .filter(|stat| {
+            stat.transport == transport
+                && stat.prompt_profile == CHUNK_REVIEW_PROMPT_PROFILE
+                && stat.request_count >= MIN_REQUESTS_FOR_SUGGESTION
+        })
+        .min_by(|left, right| {
+            let left_tokens = left.avg_prompt_tokens + left.avg_completion_tokens;
+            let right_tokens = right.avg_prompt_tokens + right.avg_completion_tokens;
+            left.failure_rate
+                .total_cmp(&right.failure_rate)
+                .then(left_tokens.total_cmp(&right_tokens))
+        })
+        .map(|stat| stat.model.clone())
+}
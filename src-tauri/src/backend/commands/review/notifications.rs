@@ -0,0 +1,247 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use super::super::common::{
+    DEFAULT_REVIEW_WEBHOOK_EVENTS, ROVEX_REVIEW_SLACK_WEBHOOK_URL_ENV,
+    ROVEX_REVIEW_WEBHOOK_EVENTS_ENV, ROVEX_REVIEW_WEBHOOK_URL_ENV,
+};
+use super::store;
+use crate::backend::settings::settings_store;
+use crate::backend::{AiReviewFinding, AiReviewRun, AppState};
+
+const WEBHOOK_TIMEOUT_MS: u64 = 10_000;
+
+/// Notification event for a final run status, or `None` for statuses that are not final.
+fn notification_event(status: &str) -> Option<&'static str> {
+    match status {
+        "completed" | "completed_with_errors" => Some("completed"),
+        "failed" => Some("failed"),
+        "canceled" => Some("canceled"),
+        _ => None,
+    }
+}
+
+fn is_event_enabled(event: &str) -> bool {
+    settings_store()
+        .get_string(ROVEX_REVIEW_WEBHOOK_EVENTS_ENV)
+        .unwrap_or_else(|| DEFAULT_REVIEW_WEBHOOK_EVENTS.to_string())
+        .split(',')
+        .any(|value| value.trim().eq_ignore_ascii_case(event))
+}
+
+fn webhook_url(name: &str) -> Option<String> {
+    settings_store()
+        .get_string(name)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn finding_counts(findings: &[AiReviewFinding]) -> Value {
+    let count = |severity: &str| {
+        findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    };
+    json!({
+        "total": findings.len(),
+        "critical": count("critical"),
+        "high": count("high"),
+        "medium": count("medium"),
+        "low": count("low"),
+        "recurring": findings.iter().filter(|finding| finding.recurring == Some(true)).count(),
+    })
+}
+
+fn run_title(run: &AiReviewRun) -> &str {
+    run.scope_label
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(&run.workspace)
+}
+
+/// JSON body posted to the generic webhook. `link` carries what a receiver needs to point
+/// back at the reviewed change.
+fn build_webhook_payload(run: &AiReviewRun, event: &str) -> Value {
+    json!({
+        "event": format!("review_run.{event}"),
+        "runId": run.run_id,
+        "threadId": run.thread_id,
+        "status": run.status,
+        "title": run_title(run),
+        "model": run.model,
+        "error": run.error,
+        "findingCounts": finding_counts(&run.findings),
+        "filesChanged": run.files_changed,
+        "failedChunks": run.failed_chunks,
+        "createdAt": run.created_at,
+        "endedAt": run.ended_at,
+        "link": {
+            "workspace": run.workspace,
+            "baseRef": run.base_ref,
+            "mergeBase": run.merge_base,
+            "head": run.head,
+            "threadId": run.thread_id,
+            "runId": run.run_id,
+        },
+    })
+}
+
+/// Message body for a Slack incoming webhook.
+fn build_slack_payload(run: &AiReviewRun, event: &str) -> Value {
+    let head = run.head.get(..12).unwrap_or(&run.head);
+    let mut text = format!(
+        "Rovex review {event}: *{}*\n`{}` at `{head}` against `{}`",
+        run_title(run),
+        run.workspace,
+        run.base_ref
+    );
+    if event == "completed" {
+        let counts = finding_counts(&run.findings);
+        text.push_str(&format!(
+            "\n{} finding(s): {} critical, {} high, {} medium, {} low",
+            counts["total"], counts["critical"], counts["high"], counts["medium"], counts["low"]
+        ));
+    }
+    if let Some(error) = run.error.as_deref().filter(|_| event != "completed") {
+        text.push_str(&format!("\n> {}", error.trim()));
+    }
+    json!({ "text": text })
+}
+
+async fn post_webhook(client: &Client, url: &str, payload: &Value) -> Result<(), String> {
+    let response = client
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .map_err(|error| format!("request failed: {}", error.without_url()))?;
+    if !response.status().is_success() {
+        return Err(format!("receiver returned {}", response.status()));
+    }
+    Ok(())
+}
+
+type Delivery = (&'static str, String, Value);
+
+async fn pending_deliveries(state: &AppState, run_id: &str) -> Vec<Delivery> {
+    let webhook = webhook_url(ROVEX_REVIEW_WEBHOOK_URL_ENV);
+    let slack_webhook = webhook_url(ROVEX_REVIEW_SLACK_WEBHOOK_URL_ENV);
+    if webhook.is_none() && slack_webhook.is_none() {
+        return Vec::new();
+    }
+    let run = match store::load_ai_review_run_by_id(state, run_id).await {
+        Ok(run) => run,
+        Err(error) => {
+            eprintln!("[backend] Failed to load AI review run {run_id} for notifications: {error}");
+            return Vec::new();
+        }
+    };
+    let Some(event) = notification_event(&run.status) else {
+        return Vec::new();
+    };
+    if !is_event_enabled(event) {
+        return Vec::new();
+    }
+
+    let mut deliveries = Vec::new();
+    if let Some(url) = webhook {
+        deliveries.push(("webhook", url, build_webhook_payload(&run, event)));
+    }
+    if let Some(url) = slack_webhook {
+        deliveries.push(("Slack webhook", url, build_slack_payload(&run, event)));
+    }
+    deliveries
+}
+
+async fn send_deliveries(run_id: &str, deliveries: Vec<Delivery>) {
+    let client = match Client::builder()
+        .timeout(Duration::from_millis(WEBHOOK_TIMEOUT_MS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("[backend] Failed to initialize HTTP client for notifications: {error}");
+            return;
+        }
+    };
+    for (label, url, payload) in deliveries {
+        // Webhook URLs embed their credentials, so errors never include them.
+        if let Err(error) = post_webhook(&client, &url, &payload).await {
+            eprintln!("[backend] Failed to notify {label} about AI review run {run_id}: {error}");
+        }
+    }
+}
+
+/// Posts the final state of `run_id` to the configured webhooks. Delivery happens in the
+/// background and failures are only logged, so a bad webhook never affects the run.
+pub(crate) async fn notify_run_finished(state: &AppState, run_id: &str) {
+    let deliveries = pending_deliveries(state, run_id).await;
+    if deliveries.is_empty() {
+        return;
+    }
+    let run_id = run_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        send_deliveries(&run_id, deliveries).await;
+    });
+}
+
+/// Like [`notify_run_finished`], but waits for delivery. Used by the CLI, which would
+/// otherwise exit before background deliveries complete.
+pub(crate) async fn notify_run_finished_and_wait(state: &AppState, run_id: &str) {
+    let deliveries = pending_deliveries(state, run_id).await;
+    send_deliveries(run_id, deliveries).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{finding_counts, notification_event};
+    use crate::backend::AiReviewFinding;
+
+    fn finding(severity: &str, recurring: bool) -> AiReviewFinding {
+        AiReviewFinding {
+            id: "src/lib.rs:1".to_string(),
+            file_path: "src/lib.rs".to_string(),
+            chunk_id: "src/lib.rs#file-1".to_string(),
+            chunk_index: 1,
+            hunk_header: "@@ -1 +1 @@".to_string(),
+            side: "additions".to_string(),
+            line_number: 1,
+            title: "Unchecked unwrap".to_string(),
+            body: "body".to_string(),
+            severity: severity.to_string(),
+            confidence: None,
+            category: None,
+            fingerprint: None,
+            status: None,
+            code_hash: None,
+            recurring: Some(recurring),
+            suggested_patch: None,
+            language: None,
+            blame: None,
+        }
+    }
+
+    #[test]
+    fn only_final_statuses_notify_and_findings_are_counted_by_severity() {
+        assert_eq!(
+            notification_event("completed_with_errors"),
+            Some("completed")
+        );
+        assert_eq!(notification_event("canceled"), Some("canceled"));
+        assert_eq!(notification_event("running"), None);
+
+        let counts = finding_counts(&[
+            finding("high", false),
+            finding("high", true),
+            finding("low", false),
+        ]);
+        assert_eq!(counts["total"], 3);
+        assert_eq!(counts["high"], 2);
+        assert_eq!(counts["critical"], 0);
+        assert_eq!(counts["recurring"], 1);
+    }
+}
@@ -0,0 +1,60 @@
+/// Finished runs sampled when estimating how long one chunk takes to review.
+pub(crate) const QUEUE_ESTIMATE_SAMPLE_RUNS: i64 = 20;
+
+/// Simulates the run queue to estimate how long each queued run waits for a slot.
+///
+/// `running_remaining_ms` holds the expected remaining time of every run that holds a slot, and
+/// `queued_duration_ms` the expected duration of each queued run in queue order. Slots are
+/// handed out first come, first served, matching the semaphore behind the queue. When more runs
+/// hold a slot than `slot_count` allows (the capacity shrank), only the last of them to finish
+/// give their slot back.
+pub(crate) fn estimate_queue_start_offsets(
+    slot_count: usize,
+    running_remaining_ms: &[u64],
+    queued_duration_ms: &[u64],
+) -> Vec<u64> {
+    let slot_count = slot_count.max(1);
+    let mut free_at = running_remaining_ms.to_vec();
+    free_at.sort_unstable();
+    if free_at.len() > slot_count {
+        free_at.drain(..free_at.len() - slot_count);
+    }
+    free_at.resize(slot_count, 0);
+
+    queued_duration_ms
+        .iter()
+        .map(|duration| {
+            let (slot, start) = free_at
+                .iter()
+                .copied()
+                .enumerate()
+                .min_by_key(|(_, free)| *free)
+                .unwrap_or((0, 0));
+            free_at[slot] = start.saturating_add(*duration);
+            start
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::estimate_queue_start_offsets;
+
+    #[test]
+    fn queued_runs_start_when_the_earliest_slot_frees_up() {
+        let offsets = estimate_queue_start_offsets(2, &[1_000, 5_000], &[3_000, 2_000, 1_000]);
+        assert_eq!(offsets, vec![1_000, 4_000, 5_000]);
+    }
+
+    #[test]
+    fn idle_slots_start_queued_runs_immediately() {
+        let offsets = estimate_queue_start_offsets(3, &[4_000], &[1_000, 1_000, 1_000]);
+        assert_eq!(offsets, vec![0, 0, 1_000]);
+    }
+
+    #[test]
+    fn shrunk_capacity_waits_for_the_last_runs_to_finish() {
+        let offsets = estimate_queue_start_offsets(1, &[1_000, 6_000], &[2_000]);
+        assert_eq!(offsets, vec![6_000]);
+    }
+}
@@ -0,0 +1,464 @@
+use super::super::name_sort::compare_paths;
+use super::finding_order::{group_findings, ordered_findings, FindingGrouping, FindingOrder};
+use crate::backend::{AiReviewChunk, AiReviewFinding, AiReviewRun};
+
+pub(crate) const REPORT_FORMAT_MARKDOWN: &str = "markdown";
+pub(crate) const REPORT_FORMAT_HTML: &str = "html";
+
+const REPORT_STYLE: &str = "body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:960px;margin:2rem auto;padding:0 1rem;color:#1f2328;line-height:1.5}
+table{border-collapse:collapse;width:100%;margin:1rem 0}th,td{border:1px solid #d0d7de;padding:.4rem .6rem;text-align:left;vertical-align:top}
+th{background:#f6f8fa}code{font-family:ui-monospace,SFMono-Regular,Menlo,monospace;font-size:.9em}
+.description{white-space:pre-wrap;background:#f6f8fa;border-radius:6px;padding:1rem}
+.badge{display:inline-block;border-radius:999px;padding:0 .5rem;font-size:.75rem;font-weight:600;text-transform:uppercase;color:#fff}
+.badge-critical{background:#8b0000}.badge-high{background:#cf222e}.badge-medium{background:#bf8700}.badge-low{background:#57606a}
+.finding{border:1px solid #d0d7de;border-radius:6px;padding:.75rem 1rem;margin:.75rem 0}.finding p{white-space:pre-wrap;margin:.5rem 0 0}
+.meta{color:#57606a;font-size:.85rem}";
+
+fn group_title(grouping: FindingGrouping, key: &str) -> String {
+    match grouping {
+        FindingGrouping::Severity => key.to_uppercase(),
+        FindingGrouping::File | FindingGrouping::Category => key.to_string(),
+    }
+}
+
+fn short_sha(value: &str) -> &str {
+    value.get(..12).unwrap_or(value)
+}
+
+fn report_title(run: &AiReviewRun) -> String {
+    run.scope_label
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(&run.workspace)
+        .to_string()
+}
+
+fn finding_status_suffix(finding: &AiReviewFinding) -> String {
+    match finding.status.as_deref() {
+        Some(status) if status != "open" => format!(" ({status})"),
+        _ => String::new(),
+    }
+}
+
+fn escape_markdown_cell(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+fn findings_markdown_table(findings: &[&AiReviewFinding]) -> String {
+    let mut table = String::from("| Severity | Location | Finding |\n| --- | --- | --- |\n");
+    for finding in findings {
+        table.push_str(&format!(
+            "| `{}` | `{}:{}` | {}{} |\n",
+            finding.severity.to_uppercase(),
+            escape_markdown_cell(&finding.file_path),
+            finding.line_number,
+            escape_markdown_cell(&finding.title),
+            finding_status_suffix(finding)
+        ));
+    }
+    table
+}
+
+/// Renders a run as a self-contained markdown document.
+fn approval_label(run: &AiReviewRun) -> String {
+    match run.approval_note.as_deref() {
+        Some(note) => format!("{}: {note}", run.approval),
+        None => run.approval.clone(),
+    }
+}
+
+pub(crate) fn build_markdown_report(run: &AiReviewRun, order: &FindingOrder) -> String {
+    let findings = ordered_findings(&run.findings, order);
+    let mut report = format!("# Review: {}\n\n", report_title(run));
+    report.push_str("| | |\n| --- | --- |\n");
+    report.push_str(&format!(
+        "| Workspace | `{}` |\n",
+        escape_markdown_cell(&run.workspace)
+    ));
+    report.push_str(&format!(
+        "| Base | `{}` at `{}` |\n",
+        escape_markdown_cell(&run.base_ref),
+        short_sha(&run.merge_base)
+    ));
+    report.push_str(&format!("| Head | `{}` |\n", short_sha(&run.head)));
+    report.push_str(&format!(
+        "| Changes | {} file(s), +{} / -{} |\n",
+        run.files_changed, run.insertions, run.deletions
+    ));
+    report.push_str(&format!("| Status | {} |\n", run.status));
+    report.push_str(&format!(
+        "| Approval | {} |\n",
+        escape_markdown_cell(&approval_label(run))
+    ));
+    if let Some(model) = run.model.as_deref() {
+        report.push_str(&format!("| Model | {} |\n", escape_markdown_cell(model)));
+    }
+    report.push_str(&format!("| Created | {} |\n", run.created_at));
+
+    report.push_str("\n## Summary\n\n");
+    report.push_str(
+        run.review
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or("No description was generated for this run."),
+    );
+    report.push('\n');
+
+    report.push_str(&format!("\n## Findings ({})\n\n", findings.len()));
+    match order.group {
+        _ if findings.is_empty() => report.push_str("No findings.\n"),
+        Some(grouping) => {
+            for (index, (key, members)) in group_findings(&findings, grouping).iter().enumerate() {
+                if index > 0 {
+                    report.push('\n');
+                }
+                report.push_str(&format!(
+                    "### {} ({})\n\n",
+                    group_title(grouping, key),
+                    members.len()
+                ));
+                push_markdown_findings(&mut report, members, "####");
+            }
+        }
+        None => push_markdown_findings(&mut report, &findings, "###"),
+    }
+
+    report.push_str("\n## Files\n");
+    push_markdown_file_summaries(&mut report, run.chunks.iter());
+    if !run.skipped_chunks.is_empty() {
+        report.push_str(&format!(
+            "\n## Not Reviewed ({})\n\n",
+            run.skipped_chunks.len()
+        ));
+        for skipped in &run.skipped_chunks {
+            report.push_str(&format!(
+                "- `{}` ({})\n",
+                skipped.file_path,
+                skipped.reason.as_str()
+            ));
+        }
+    }
+    report
+}
+
+/// Renders a run that is still in progress: the description once its generation finished
+/// (`None` while it is pending) and the findings of the files reviewed so far.
+pub(crate) fn build_partial_review_markdown(
+    description: Option<&str>,
+    chunks: &[AiReviewChunk],
+    findings: &[AiReviewFinding],
+    completed_chunks: usize,
+    total_chunks: usize,
+) -> String {
+    let findings = ordered_findings(findings, &FindingOrder::default());
+    let mut report = format!(
+        "_Review in progress: {completed_chunks} of {total_chunks} file(s) reviewed._\n\n## Summary\n\n"
+    );
+    report.push_str(match description.map(str::trim) {
+        Some("") => "No description was generated for this run.",
+        Some(description) => description,
+        None => "The description is still being generated.",
+    });
+    report.push('\n');
+
+    report.push_str(&format!("\n## Findings so far ({})\n\n", findings.len()));
+    if findings.is_empty() {
+        report.push_str("No findings yet.\n");
+    } else {
+        push_markdown_findings(&mut report, &findings, "###");
+    }
+
+    if !chunks.is_empty() {
+        let mut chunks = chunks.iter().collect::<Vec<_>>();
+        chunks.sort_by(|left, right| {
+            compare_paths(&left.file_path, &right.file_path)
+                .then(left.chunk_index.cmp(&right.chunk_index))
+        });
+        report.push_str("\n## Files reviewed so far\n");
+        push_markdown_file_summaries(&mut report, chunks.into_iter());
+    }
+    report
+}
+
+/// The findings table followed by one section per finding, titled at `heading` level.
+fn push_markdown_findings(report: &mut String, findings: &[&AiReviewFinding], heading: &str) {
+    report.push_str(&findings_markdown_table(findings));
+    for finding in findings {
+        report.push_str(&format!(
+            "\n{heading} [{}] {}{}\n\n`{}:{}` ({})",
+            finding.severity.to_uppercase(),
+            finding.title,
+            finding_status_suffix(finding),
+            finding.file_path,
+            finding.line_number,
+            finding.side
+        ));
+        if let Some(category) = finding.category.as_deref() {
+            report.push_str(&format!(" · {category}"));
+        }
+        if let Some(confidence) = finding.confidence {
+            report.push_str(&format!(" · {:.0}% confidence", confidence * 100.0));
+        }
+        report.push_str(&format!("\n\n{}\n", finding.body.trim()));
+        if let Some(patch) = finding.suggested_patch.as_deref() {
+            report.push_str(&format!(
+                "\nSuggested fix:\n\n```diff\n{}\n```\n",
+                patch.trim_end()
+            ));
+        }
+    }
+}
+
+/// Finding count of a reviewed chunk, or the outcome (such as `refusal`) of a failed one.
+fn chunk_status_text(chunk: &AiReviewChunk) -> String {
+    match chunk.outcome.as_deref() {
+        Some(outcome) if outcome != "reviewed" => format!("Not reviewed ({outcome})."),
+        _ => format!("{} finding(s).", chunk.findings.len()),
+    }
+}
+
+fn push_markdown_file_summaries<'a>(
+    report: &mut String,
+    chunks: impl Iterator<Item = &'a AiReviewChunk>,
+) {
+    for chunk in chunks {
+        report.push_str(&format!(
+            "\n### {}\n\n{}",
+            chunk.file_path,
+            chunk_status_text(chunk)
+        ));
+        if !chunk.summary.trim().is_empty() {
+            report.push_str(&format!(" {}", chunk.summary.trim()));
+        }
+        report.push('\n');
+    }
+}
+
+fn severity_badge(severity: &str) -> String {
+    let severity = escape_html(severity);
+    format!("<span class=\"badge badge-{severity}\">{severity}</span>")
+}
+
+/// Renders a run as a standalone HTML page with inline styles and no external assets.
+fn push_html_findings(body: &mut String, findings: &[&AiReviewFinding]) {
+    body.push_str("<table>\n<tr><th>Severity</th><th>Location</th><th>Finding</th></tr>\n");
+    for finding in findings {
+        body.push_str(&format!(
+            "<tr><td>{}</td><td><code>{}:{}</code></td><td>{}{}</td></tr>\n",
+            severity_badge(&finding.severity),
+            escape_html(&finding.file_path),
+            finding.line_number,
+            escape_html(&finding.title),
+            escape_html(&finding_status_suffix(finding))
+        ));
+    }
+    body.push_str("</table>\n");
+    for finding in findings {
+        let mut meta = format!(
+            "<code>{}:{}</code> ({})",
+            escape_html(&finding.file_path),
+            finding.line_number,
+            escape_html(&finding.side)
+        );
+        if let Some(category) = finding.category.as_deref() {
+            meta.push_str(&format!(" · {}", escape_html(category)));
+        }
+        if let Some(confidence) = finding.confidence {
+            meta.push_str(&format!(" · {:.0}% confidence", confidence * 100.0));
+        }
+        body.push_str(&format!(
+            "<div class=\"finding\">{} <strong>{}</strong>{}<div class=\"meta\">{meta}</div><p>{}</p>",
+            severity_badge(&finding.severity),
+            escape_html(&finding.title),
+            escape_html(&finding_status_suffix(finding)),
+            escape_html(finding.body.trim())
+        ));
+        if let Some(patch) = finding.suggested_patch.as_deref() {
+            body.push_str(&format!(
+                "<div class=\"meta\">Suggested fix</div><pre><code>{}</code></pre>",
+                escape_html(patch.trim_end())
+            ));
+        }
+        body.push_str("</div>\n");
+    }
+}
+
+pub(crate) fn build_html_report(run: &AiReviewRun, order: &FindingOrder) -> String {
+    let findings = ordered_findings(&run.findings, order);
+    let title = escape_html(&report_title(run));
+    let mut body = format!("<h1>Review: {title}</h1>\n<table>\n");
+    let mut meta_row = |label: &str, value: String| {
+        body.push_str(&format!("<tr><th>{label}</th><td>{value}</td></tr>\n"));
+    };
+    meta_row(
+        "Workspace",
+        format!("<code>{}</code>", escape_html(&run.workspace)),
+    );
+    meta_row(
+        "Base",
+        format!(
+            "<code>{}</code> at <code>{}</code>",
+            escape_html(&run.base_ref),
+            escape_html(short_sha(&run.merge_base))
+        ),
+    );
+    meta_row(
+        "Head",
+        format!("<code>{}</code>", escape_html(short_sha(&run.head))),
+    );
+    meta_row(
+        "Changes",
+        format!(
+            "{} file(s), +{} / -{}",
+            run.files_changed, run.insertions, run.deletions
+        ),
+    );
+    meta_row("Status", escape_html(&run.status));
+    meta_row("Approval", escape_html(&approval_label(run)));
+    if let Some(model) = run.model.as_deref() {
+        meta_row("Model", escape_html(model));
+    }
+    meta_row("Created", escape_html(&run.created_at));
+    body.push_str("</table>\n");
+
+    body.push_str(&format!(
+        "<h2>Summary</h2>\n<div class=\"description\">{}</div>\n",
+        escape_html(
+            run.review
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .unwrap_or("No description was generated for this run.")
+        )
+    ));
+
+    body.push_str(&format!("<h2>Findings ({})</h2>\n", findings.len()));
+    match order.group {
+        _ if findings.is_empty() => body.push_str("<p>No findings.</p>\n"),
+        Some(grouping) => {
+            for (key, members) in group_findings(&findings, grouping) {
+                body.push_str(&format!(
+                    "<h3>{} ({})</h3>\n",
+                    escape_html(&group_title(grouping, &key)),
+                    members.len()
+                ));
+                push_html_findings(&mut body, &members);
+            }
+        }
+        None => push_html_findings(&mut body, &findings),
+    }
+
+    body.push_str("<h2>Files</h2>\n");
+    for chunk in &run.chunks {
+        body.push_str(&format!(
+            "<h3><code>{}</code></h3>\n<p>{} {}</p>\n",
+            escape_html(&chunk.file_path),
+            escape_html(&chunk_status_text(chunk)),
+            escape_html(chunk.summary.trim())
+        ));
+    }
+    if !run.skipped_chunks.is_empty() {
+        body.push_str(&format!(
+            "<h2>Not Reviewed ({})</h2>\n<ul>\n",
+            run.skipped_chunks.len()
+        ));
+        for skipped in &run.skipped_chunks {
+            body.push_str(&format!(
+                "<li><code>{}</code> ({})</li>\n",
+                escape_html(&skipped.file_path),
+                skipped.reason.as_str()
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Review: {title}</title>\n<style>\n{REPORT_STYLE}\n</style>\n</head>\n<body>\n{body}</body>\n</html>\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_partial_review_markdown, escape_html, findings_markdown_table};
+    use crate::backend::commands::review::finding_order::{ordered_findings, FindingOrder};
+    use crate::backend::AiReviewFinding;
+
+    fn finding(title: &str, severity: &str, line_number: i64) -> AiReviewFinding {
+        AiReviewFinding {
+            id: format!("src/lib.rs:{line_number}"),
+            file_path: "src/lib.rs".to_string(),
+            chunk_id: "src/lib.rs#file-1".to_string(),
+            chunk_index: 1,
+            hunk_header: "@@ -1 +1 @@".to_string(),
+            side: "additions".to_string(),
+            line_number,
+            title: title.to_string(),
+            body: "body".to_string(),
+            severity: severity.to_string(),
+            confidence: None,
+            category: None,
+            fingerprint: None,
+            status: None,
+            code_hash: None,
+            recurring: None,
+            suggested_patch: None,
+            language: None,
+            blame: None,
+        }
+    }
+
+    #[test]
+    fn markdown_table_orders_by_severity_and_escapes_cells() {
+        let findings = [
+            finding("Slow loop", "low", 3),
+            finding("Pipe | in\ntitle", "critical", 9),
+        ];
+        assert_eq!(
+            findings_markdown_table(&ordered_findings(&findings, &FindingOrder::default())),
+            "| Severity | Location | Finding |\n| --- | --- | --- |\n| `CRITICAL` | `src/lib.rs:9` | Pipe \\| in title |\n| `LOW` | `src/lib.rs:3` | Slow loop |\n"
+        );
+    }
+
+    #[test]
+    fn partial_review_shows_progress_and_findings_so_far() {
+        let pending = build_partial_review_markdown(None, &[], &[], 0, 3);
+        assert!(pending.starts_with("_Review in progress: 0 of 3 file(s) reviewed._"));
+        assert!(pending.contains("The description is still being generated."));
+        assert!(pending.contains("No findings yet."));
+
+        let findings = [finding("Unchecked unwrap", "high", 4)];
+        let partial = build_partial_review_markdown(Some("Adds a parser."), &[], &findings, 1, 3);
+        assert!(partial.contains("## Summary\n\nAdds a parser.\n"));
+        assert!(partial.contains("## Findings so far (1)"));
+        assert!(partial.contains("| `HIGH` | `src/lib.rs:4` | Unchecked unwrap |"));
+    }
+
+    #[test]
+    fn escape_html_neutralizes_markup() {
+        assert_eq!(
+            escape_html("<script>alert('x') && \"y\"</script>"),
+            "&lt;script&gt;alert(&#39;x&#39;) &amp;&amp; &quot;y&quot;&lt;/script&gt;"
+        );
+    }
+}
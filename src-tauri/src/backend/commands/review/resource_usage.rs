@@ -0,0 +1,168 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tokio::task::JoinHandle;
+
+use crate::backend::AiReviewResourceUsage;
+
+const PROCESS_SAMPLE_INTERVAL_MS: u64 = 500;
+
+tokio::task_local! {
+    static RUN_RESOURCE_USAGE: Arc<RunResourceUsage>;
+}
+
+#[derive(Default)]
+struct UsageTotals {
+    process_count: usize,
+    current_memory_bytes: u64,
+    peak_memory_bytes: u64,
+    cpu_time_ms: u64,
+}
+
+/// Aggregated CPU and memory usage of the transport processes spawned for one review run.
+///
+/// Peak memory is the highest combined resident set size of all processes that were alive at
+/// the same time, since chunk reviews run their transports in parallel.
+#[derive(Default)]
+pub(crate) struct RunResourceUsage {
+    totals: Mutex<UsageTotals>,
+}
+
+impl RunResourceUsage {
+    pub(crate) fn snapshot(&self) -> Option<AiReviewResourceUsage> {
+        let totals = self.totals.lock().ok()?;
+        if totals.process_count == 0 {
+            return None;
+        }
+        Some(AiReviewResourceUsage {
+            process_count: totals.process_count,
+            peak_memory_bytes: totals.peak_memory_bytes,
+            cpu_time_ms: totals.cpu_time_ms,
+        })
+    }
+
+    fn register_process(&self) {
+        if let Ok(mut totals) = self.totals.lock() {
+            totals.process_count += 1;
+        }
+    }
+
+    fn record_sample(&self, previous_memory_bytes: u64, memory_bytes: u64, cpu_delta_ms: u64) {
+        let Ok(mut totals) = self.totals.lock() else {
+            return;
+        };
+        totals.current_memory_bytes = totals
+            .current_memory_bytes
+            .saturating_sub(previous_memory_bytes)
+            .saturating_add(memory_bytes);
+        totals.peak_memory_bytes = totals.peak_memory_bytes.max(totals.current_memory_bytes);
+        totals.cpu_time_ms = totals.cpu_time_ms.saturating_add(cpu_delta_ms);
+    }
+}
+
+/// Runs `future` with `usage` collecting the resources of any transport process it spawns.
+pub(crate) async fn with_run_resource_usage<F: Future>(
+    usage: Arc<RunResourceUsage>,
+    future: F,
+) -> F::Output {
+    RUN_RESOURCE_USAGE.scope(usage, future).await
+}
+
+struct SamplerState {
+    system: System,
+    pid: Pid,
+    memory_bytes: u64,
+    cpu_time_ms: u64,
+    usage: Arc<RunResourceUsage>,
+}
+
+impl SamplerState {
+    /// Returns `false` once the process has exited.
+    fn sample(&mut self) -> bool {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[self.pid]),
+            true,
+            ProcessRefreshKind::nothing().with_memory().with_cpu(),
+        );
+        let Some(process) = self.system.process(self.pid) else {
+            self.release();
+            return false;
+        };
+
+        let memory_bytes = process.memory();
+        let cpu_time_ms = process.accumulated_cpu_time();
+        self.usage.record_sample(
+            self.memory_bytes,
+            memory_bytes,
+            cpu_time_ms.saturating_sub(self.cpu_time_ms),
+        );
+        self.memory_bytes = memory_bytes;
+        self.cpu_time_ms = self.cpu_time_ms.max(cpu_time_ms);
+        true
+    }
+
+    fn release(&mut self) {
+        self.usage.record_sample(self.memory_bytes, 0, 0);
+        self.memory_bytes = 0;
+    }
+}
+
+/// Periodically samples a child process until it exits or the sampler is finished.
+pub(crate) struct ProcessUsageSampler {
+    state: Arc<Mutex<SamplerState>>,
+    task: JoinHandle<()>,
+}
+
+impl ProcessUsageSampler {
+    /// Takes a final sample so CPU time spent since the last tick is not lost.
+    /// Call before killing the process.
+    pub(crate) fn finish(self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.sample();
+            state.release();
+        }
+    }
+}
+
+impl Drop for ProcessUsageSampler {
+    fn drop(&mut self) {
+        self.task.abort();
+        if let Ok(mut state) = self.state.lock() {
+            state.release();
+        }
+    }
+}
+
+/// Starts sampling `pid` when called inside a review run; returns `None` otherwise.
+pub(crate) fn track_child_process(pid: Option<u32>) -> Option<ProcessUsageSampler> {
+    let usage = RUN_RESOURCE_USAGE.try_with(Arc::clone).ok()?;
+    let pid = Pid::from_u32(pid?);
+    usage.register_process();
+
+    let state = Arc::new(Mutex::new(SamplerState {
+        system: System::new(),
+        pid,
+        memory_bytes: 0,
+        cpu_time_ms: 0,
+        usage,
+    }));
+    let state_for_task = state.clone();
+    let task = tokio::spawn(async move {
+        loop {
+            let alive = match state_for_task.lock() {
+                Ok(mut state) => state.sample(),
+                Err(_) => false,
+            };
+            if !alive {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(PROCESS_SAMPLE_INTERVAL_MS)).await;
+        }
+    });
+
+    Some(ProcessUsageSampler { state, task })
+}
@@ -0,0 +1,403 @@
+use tauri::State;
+
+use super::super::common::{as_non_empty_trimmed, parse_json_vec_or_default};
+use crate::backend::{
+    AppState, CreateReviewerProfileInput, DeleteReviewerProfileInput, DeleteReviewerProfileResult,
+    ListReviewerProfilesResult, ReviewerProfile, UpdateReviewerProfileInput,
+};
+
+const MAX_PROFILE_NAME_CHARS: usize = 80;
+const MAX_FOCUS_AREAS: usize = 20;
+
+const PROFILE_COLUMNS: &str =
+    "id, name, system_prompt, min_severity, focus_areas_json, created_at, updated_at";
+
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "critical" => 0,
+        "high" => 1,
+        "medium" => 2,
+        _ => 3,
+    }
+}
+
+pub(crate) fn parse_min_severity(value: Option<&str>) -> Result<Option<&'static str>, String> {
+    match as_non_empty_trimmed(value)
+        .map(|value| value.to_lowercase())
+        .as_deref()
+    {
+        None => Ok(None),
+        Some("critical") => Ok(Some("critical")),
+        Some("high") => Ok(Some("high")),
+        Some("medium") => Ok(Some("medium")),
+        Some("low") => Ok(Some("low")),
+        Some(other) => Err(format!(
+            "Unknown minimum severity '{other}'. Use critical, high, medium, or low."
+        )),
+    }
+}
+
+/// Trimmed, non-empty focus areas in their given order, without case-insensitive repeats.
+fn parse_focus_areas(values: Option<&[String]>) -> Result<Vec<String>, String> {
+    let mut focus_areas: Vec<String> = Vec::new();
+    for value in values.unwrap_or_default() {
+        let value = value.trim();
+        if value.is_empty()
+            || focus_areas
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(value))
+        {
+            continue;
+        }
+        focus_areas.push(value.to_string());
+    }
+    if focus_areas.len() > MAX_FOCUS_AREAS {
+        return Err(format!(
+            "A reviewer profile can have at most {MAX_FOCUS_AREAS} focus areas."
+        ));
+    }
+    Ok(focus_areas)
+}
+
+struct ProfileFields {
+    name: String,
+    system_prompt: String,
+    min_severity: Option<&'static str>,
+    focus_areas_json: String,
+}
+
+fn parse_profile_fields(
+    name: &str,
+    system_prompt: &str,
+    min_severity: Option<&str>,
+    focus_areas: Option<&[String]>,
+) -> Result<ProfileFields, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Reviewer profile name must not be empty.".to_string());
+    }
+    if name.chars().count() > MAX_PROFILE_NAME_CHARS {
+        return Err(format!(
+            "Reviewer profile name must be at most {MAX_PROFILE_NAME_CHARS} characters."
+        ));
+    }
+    let system_prompt = system_prompt.trim();
+    if system_prompt.is_empty() {
+        return Err("Reviewer profile prompt must not be empty.".to_string());
+    }
+    let focus_areas_json = serde_json::to_string(&parse_focus_areas(focus_areas)?)
+        .map_err(|error| format!("Failed to serialize focus areas: {error}"))?;
+    Ok(ProfileFields {
+        name: name.to_string(),
+        system_prompt: system_prompt.to_string(),
+        min_severity: parse_min_severity(min_severity)?,
+        focus_areas_json,
+    })
+}
+
+async fn query_reviewer_profiles(
+    state: &AppState,
+    id: Option<i64>,
+    name: Option<&str>,
+) -> Result<Vec<ReviewerProfile>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {PROFILE_COLUMNS}
+                 FROM reviewer_profiles
+                 WHERE (?1 IS NULL OR id = ?1) AND (?2 IS NULL OR name = ?2 COLLATE NOCASE)
+                 ORDER BY name COLLATE NOCASE ASC, id ASC"
+            ),
+            (id, name.map(ToOwned::to_owned)),
+        )
+        .await
+        .map_err(|error| format!("Failed to query reviewer profiles: {error}"))?;
+
+    let mut profiles = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read reviewer profile row: {error}"))?
+    {
+        let focus_areas_json: String = row
+            .get(4)
+            .map_err(|error| format!("Failed to parse reviewer profile focus areas: {error}"))?;
+        profiles.push(ReviewerProfile {
+            id: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse reviewer profile id: {error}"))?,
+            name: row
+                .get(1)
+                .map_err(|error| format!("Failed to parse reviewer profile name: {error}"))?,
+            system_prompt: row
+                .get(2)
+                .map_err(|error| format!("Failed to parse reviewer profile prompt: {error}"))?,
+            min_severity: row.get(3).ok(),
+            focus_areas: parse_json_vec_or_default(&focus_areas_json),
+            created_at: row
+                .get(5)
+                .map_err(|error| format!("Failed to parse reviewer profile timestamp: {error}"))?,
+            updated_at: row
+                .get(6)
+                .map_err(|error| format!("Failed to parse reviewer profile timestamp: {error}"))?,
+        });
+    }
+    Ok(profiles)
+}
+
+pub(crate) async fn load_reviewer_profile(
+    state: &AppState,
+    id: i64,
+) -> Result<ReviewerProfile, String> {
+    query_reviewer_profiles(state, Some(id), None)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Reviewer profile {id} does not exist."))
+}
+
+/// The profile named `name`, compared without regard to case.
+pub(crate) async fn find_reviewer_profile_by_name(
+    state: &AppState,
+    name: &str,
+) -> Result<Option<ReviewerProfile>, String> {
+    Ok(query_reviewer_profiles(state, None, Some(name))
+        .await?
+        .into_iter()
+        .next())
+}
+
+/// Profile names are unique regardless of case.
+async fn ensure_profile_name_available(
+    state: &AppState,
+    name: &str,
+    id: Option<i64>,
+) -> Result<(), String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT 1 FROM reviewer_profiles
+             WHERE name = ?1 COLLATE NOCASE AND id IS NOT ?2
+             LIMIT 1",
+            (name.to_string(), id),
+        )
+        .await
+        .map_err(|error| format!("Failed to look up reviewer profile names: {error}"))?;
+    if rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read reviewer profile names: {error}"))?
+        .is_some()
+    {
+        return Err(format!("A reviewer profile named '{name}' already exists."));
+    }
+    Ok(())
+}
+
+/// Reviewer goal for prompts: the profile's prompt, its focus areas, and the run's own
+/// prompt as extra focus when one was given.
+pub(crate) fn profile_reviewer_goal(profile: &ReviewerProfile, prompt: Option<&str>) -> String {
+    let mut goal = profile.system_prompt.trim().to_string();
+    if !profile.focus_areas.is_empty() {
+        goal.push_str(&format!(
+            "\nFocus areas: {}.",
+            profile.focus_areas.join(", ")
+        ));
+    }
+    if let Some(prompt) = as_non_empty_trimmed(prompt) {
+        goal.push_str(&format!("\nAlso: {prompt}"));
+    }
+    goal
+}
+
+/// Whether a finding of `severity` is at or above `min_severity`, when one is set.
+pub(crate) fn meets_severity(severity: &str, min_severity: Option<&str>) -> bool {
+    min_severity.is_none_or(|min_severity| severity_rank(severity) <= severity_rank(min_severity))
+}
+
+/// The higher of two minimum severities, so the stricter gate wins.
+pub(crate) fn stricter_min_severity<'a>(
+    left: Option<&'a str>,
+    right: Option<&'a str>,
+) -> Option<&'a str> {
+    match (left, right) {
+        (Some(left), Some(right)) if severity_rank(right) < severity_rank(left) => Some(right),
+        (Some(left), _) => Some(left),
+        (None, right) => right,
+    }
+}
+
+pub async fn create_reviewer_profile(
+    state: State<'_, AppState>,
+    input: CreateReviewerProfileInput,
+) -> Result<ReviewerProfile, String> {
+    let fields = parse_profile_fields(
+        &input.name,
+        &input.system_prompt,
+        input.min_severity.as_deref(),
+        input.focus_areas.as_deref(),
+    )?;
+    ensure_profile_name_available(&state, &fields.name, None).await?;
+    let conn = state.connection()?;
+    conn.execute(
+        "INSERT INTO reviewer_profiles (name, system_prompt, min_severity, focus_areas_json)
+         VALUES (?1, ?2, ?3, ?4)",
+        (
+            fields.name,
+            fields.system_prompt,
+            fields.min_severity,
+            fields.focus_areas_json,
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to create reviewer profile: {error}"))?;
+    let mut rows = conn
+        .query("SELECT last_insert_rowid()", ())
+        .await
+        .map_err(|error| format!("Failed to fetch new reviewer profile id: {error}"))?;
+    let id: i64 = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read reviewer profile id row: {error}"))?
+        .ok_or_else(|| {
+            "Missing last_insert_rowid result after create_reviewer_profile.".to_string()
+        })?
+        .get(0)
+        .map_err(|error| format!("Failed to parse new reviewer profile id: {error}"))?;
+    load_reviewer_profile(&state, id).await
+}
+
+pub async fn update_reviewer_profile(
+    state: State<'_, AppState>,
+    input: UpdateReviewerProfileInput,
+) -> Result<ReviewerProfile, String> {
+    let fields = parse_profile_fields(
+        &input.name,
+        &input.system_prompt,
+        input.min_severity.as_deref(),
+        input.focus_areas.as_deref(),
+    )?;
+    ensure_profile_name_available(&state, &fields.name, Some(input.id)).await?;
+    let conn = state.connection()?;
+    let updated = conn
+        .execute(
+            "UPDATE reviewer_profiles SET
+               name = ?2,
+               system_prompt = ?3,
+               min_severity = ?4,
+               focus_areas_json = ?5,
+               updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?1",
+            (
+                input.id,
+                fields.name,
+                fields.system_prompt,
+                fields.min_severity,
+                fields.focus_areas_json,
+            ),
+        )
+        .await
+        .map_err(|error| format!("Failed to update reviewer profile {}: {error}", input.id))?;
+    if updated == 0 {
+        return Err(format!("Reviewer profile {} does not exist.", input.id));
+    }
+    load_reviewer_profile(&state, input.id).await
+}
+
+pub async fn list_reviewer_profiles(
+    state: State<'_, AppState>,
+) -> Result<ListReviewerProfilesResult, String> {
+    Ok(ListReviewerProfilesResult {
+        profiles: query_reviewer_profiles(&state, None, None).await?,
+    })
+}
+
+/// Runs that used the profile keep their `profileId`; they already stored the prompt they ran
+/// with.
+pub async fn delete_reviewer_profile(
+    state: State<'_, AppState>,
+    input: DeleteReviewerProfileInput,
+) -> Result<DeleteReviewerProfileResult, String> {
+    let conn = state.connection()?;
+    let deleted = conn
+        .execute("DELETE FROM reviewer_profiles WHERE id = ?1", [input.id])
+        .await
+        .map_err(|error| format!("Failed to delete reviewer profile {}: {error}", input.id))?;
+    Ok(DeleteReviewerProfileResult {
+        id: input.id,
+        deleted: deleted > 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        meets_severity, parse_focus_areas, parse_min_severity, profile_reviewer_goal,
+        stricter_min_severity,
+    };
+    use crate::backend::ReviewerProfile;
+
+    fn profile(min_severity: Option<&str>, focus_areas: &[&str]) -> ReviewerProfile {
+        ReviewerProfile {
+            id: 1,
+            name: "Security review".to_string(),
+            system_prompt: "Review for exploitable security bugs.".to_string(),
+            min_severity: min_severity.map(ToOwned::to_owned),
+            focus_areas: focus_areas.iter().map(|area| area.to_string()).collect(),
+            created_at: "2026-01-01 00:00:00".to_string(),
+            updated_at: "2026-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn profile_goal_combines_prompt_focus_areas_and_run_prompt() {
+        let profile = profile(None, &["authentication", "SQL injection"]);
+        assert_eq!(
+            profile_reviewer_goal(&profile, Some("  the login handler ")),
+            "Review for exploitable security bugs.\nFocus areas: authentication, SQL injection.\nAlso: the login handler"
+        );
+        assert_eq!(
+            profile_reviewer_goal(&self::profile(None, &[]), None),
+            "Review for exploitable security bugs."
+        );
+    }
+
+    #[test]
+    fn min_severity_keeps_findings_at_or_above_it() {
+        let high = profile(Some("high"), &[]);
+        let high = high.min_severity.as_deref();
+        assert!(meets_severity("critical", high));
+        assert!(meets_severity("high", high));
+        assert!(!meets_severity("medium", high));
+        assert!(meets_severity("low", None));
+
+        assert_eq!(
+            stricter_min_severity(Some("medium"), Some("high")),
+            Some("high")
+        );
+        assert_eq!(
+            stricter_min_severity(Some("high"), Some("low")),
+            Some("high")
+        );
+        assert_eq!(stricter_min_severity(None, Some("low")), Some("low"));
+
+        assert_eq!(parse_min_severity(Some(" HIGH ")), Ok(Some("high")));
+        assert_eq!(parse_min_severity(Some("")), Ok(None));
+        assert!(parse_min_severity(Some("urgent")).is_err());
+    }
+
+    #[test]
+    fn focus_areas_are_trimmed_and_deduplicated() {
+        let areas = [
+            " API design ".to_string(),
+            "".to_string(),
+            "api design".to_string(),
+            "Pagination".to_string(),
+        ];
+        assert_eq!(
+            parse_focus_areas(Some(&areas)).unwrap(),
+            ["API design", "Pagination"]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use super::chunk_outcome::ChunkOutcome;
+use super::findings::{finding_fingerprint, is_same_finding};
+use super::store::load_ai_review_run_by_id;
+use crate::backend::{
+    AiReviewFinding, AiReviewRun, AppState, CompareAiReviewRunsInput, CompareAiReviewRunsResult,
+    PersistingAiReviewFinding,
+};
+
+/// Findings of two runs sorted into those only the head run has, those it no longer has,
+/// those both have, and base findings in files the head run did not review.
+#[derive(Debug, Default)]
+struct FindingComparison {
+    new: Vec<AiReviewFinding>,
+    resolved: Vec<AiReviewFinding>,
+    persisting: Vec<PersistingAiReviewFinding>,
+    unreviewed: Vec<AiReviewFinding>,
+}
+
+/// Findings stored before fingerprints existed get one from their file and title, so they
+/// still match their counterparts.
+fn with_fingerprints(findings: &[AiReviewFinding]) -> Vec<AiReviewFinding> {
+    findings
+        .iter()
+        .cloned()
+        .map(|mut finding| {
+            if finding.fingerprint.is_none() {
+                finding.fingerprint = Some(finding_fingerprint(&finding, None));
+            }
+            finding
+        })
+        .collect()
+}
+
+/// Files at least one of the run's chunks was reviewed in. A base finding elsewhere was
+/// not looked at again, so its absence says nothing about whether it was fixed.
+fn reviewed_files(run: &AiReviewRun) -> HashSet<&str> {
+    run.chunks
+        .iter()
+        .filter(|chunk| {
+            chunk
+                .outcome
+                .as_deref()
+                .is_none_or(|outcome| outcome == ChunkOutcome::Reviewed.as_str())
+        })
+        .map(|chunk| chunk.file_path.as_str())
+        .collect()
+}
+
+/// Pairs each head finding with at most one base finding, so two head findings cannot both
+/// claim the same earlier one.
+fn compare_findings(
+    base: &[AiReviewFinding],
+    head: &[AiReviewFinding],
+    head_reviewed_files: &HashSet<&str>,
+) -> FindingComparison {
+    let base = with_fingerprints(base);
+    let head = with_fingerprints(head);
+    let mut matched = vec![false; base.len()];
+    let mut comparison = FindingComparison::default();
+    for finding in head {
+        let counterpart = (0..base.len())
+            .find(|&index| !matched[index] && is_same_finding(&finding, &base[index]));
+        match counterpart {
+            Some(index) => {
+                matched[index] = true;
+                comparison.persisting.push(PersistingAiReviewFinding {
+                    base: base[index].clone(),
+                    head: finding,
+                });
+            }
+            None => comparison.new.push(finding),
+        }
+    }
+    for (finding, matched) in base.into_iter().zip(matched) {
+        if matched {
+            continue;
+        }
+        if head_reviewed_files.contains(finding.file_path.as_str()) {
+            comparison.resolved.push(finding);
+        } else {
+            comparison.unreviewed.push(finding);
+        }
+    }
+    comparison
+}
+
+pub(crate) async fn compare_ai_review_runs(
+    state: &AppState,
+    input: CompareAiReviewRunsInput,
+) -> Result<CompareAiReviewRunsResult, String> {
+    let base_run_id = input.base_run_id.trim();
+    let head_run_id = input.head_run_id.trim();
+    if base_run_id == head_run_id {
+        return Err("Choose two different runs to compare.".to_string());
+    }
+    let base = load_ai_review_run_by_id(state, base_run_id).await?;
+    let head = load_ai_review_run_by_id(state, head_run_id).await?;
+    if base.thread_id != head.thread_id {
+        return Err(format!(
+            "Runs {base_run_id} and {head_run_id} belong to different threads."
+        ));
+    }
+
+    let comparison = compare_findings(&base.findings, &head.findings, &reviewed_files(&head));
+    Ok(CompareAiReviewRunsResult {
+        thread_id: head.thread_id,
+        base_run_id: base.run_id.clone(),
+        head_run_id: head.run_id.clone(),
+        base_head: base.head.clone(),
+        head_head: head.head.clone(),
+        new_findings: comparison.new,
+        resolved_findings: comparison.resolved,
+        persisting_findings: comparison.persisting,
+        unreviewed_findings: comparison.unreviewed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::compare_findings;
+    use crate::backend::AiReviewFinding;
+
+    fn finding(file_path: &str, title: &str, line_number: i64) -> AiReviewFinding {
+        AiReviewFinding {
+            id: format!("{file_path}:{line_number}"),
+            file_path: file_path.to_string(),
+            chunk_id: format!("{file_path}#file-1"),
+            chunk_index: 1,
+            hunk_header: "@@ -1 +1 @@".to_string(),
+            side: "additions".to_string(),
+            line_number,
+            title: title.to_string(),
+            body: "body".to_string(),
+            severity: "high".to_string(),
+            confidence: None,
+            category: None,
+            fingerprint: None,
+            status: None,
+            code_hash: None,
+            recurring: None,
+            suggested_patch: None,
+            language: None,
+            blame: None,
+        }
+    }
+
+    #[test]
+    fn findings_are_new_resolved_persisting_or_unreviewed() {
+        let base = [
+            finding("src/lib.rs", "Unchecked unwrap", 10),
+            finding("src/lib.rs", "Unchecked unwrap", 30),
+            finding("src/lib.rs", "Missing bounds check", 20),
+            finding("src/untouched.rs", "Leaked file handle", 5),
+        ];
+        let head = [
+            // Moved lines keep their fingerprint.
+            finding("src/lib.rs", "Unchecked unwrap", 14),
+            finding("src/lib.rs", "SQL built from user input", 40),
+        ];
+        let reviewed = HashSet::from(["src/lib.rs"]);
+
+        let comparison = compare_findings(&base, &head, &reviewed);
+        let ids = |findings: &[AiReviewFinding]| {
+            findings
+                .iter()
+                .map(|finding| finding.id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(comparison.persisting.len(), 1);
+        assert_eq!(comparison.persisting[0].base.id, "src/lib.rs:10");
+        assert_eq!(comparison.persisting[0].head.id, "src/lib.rs:14");
+        assert_eq!(ids(&comparison.new), ["src/lib.rs:40"]);
+        // Only one of the two identical base findings is still reported.
+        assert_eq!(
+            ids(&comparison.resolved),
+            ["src/lib.rs:30", "src/lib.rs:20"]
+        );
+        assert_eq!(ids(&comparison.unreviewed), ["src/untouched.rs:5"]);
+    }
+}
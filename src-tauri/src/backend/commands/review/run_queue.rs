@@ -1,5 +1,7 @@
 use std::{
     collections::HashMap,
+    fs,
+    path::Path,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc, Mutex, OnceLock,
@@ -7,39 +9,273 @@ use std::{
 };
 
 use tauri::{AppHandle, Manager, State};
-use tokio::sync::{Notify, Semaphore};
+use tokio::sync::{AcquireError, Notify, OwnedSemaphorePermit, Semaphore};
 
-use super::super::common::{as_non_empty_trimmed, MAX_PARALLEL_REVIEW_RUNS};
+use super::super::common::{
+    as_non_empty_trimmed, current_max_parallel_review_runs, current_max_provider_chunks,
+    format_path, parse_idempotency_key,
+};
+use super::super::path_filter::{filter_diff_by_path, PathFilter};
+use super::super::repo_config::load_repo_review_config;
 use super::super::threads::load_thread_by_id;
-use super::diff_chunks::parse_diff_file_chunks;
-use super::emit_and_persist_ai_review_progress;
+use super::super::workspace_git::{
+    apply_patch_to_workspace, blame_findings, commit_messages_between, diff_since_commit,
+};
+use super::diff_chunks::{normalize_suggested_patch, parse_workspace_diff_file_chunks};
+use super::finding_order::{organize_run_findings, FindingOrder};
+use super::findings::{
+    commit_message_references_finding, parse_fix_commit, FINDING_STATUS_DISMISSED,
+    FINDING_STATUS_FIXED, FIX_COMMIT_SCAN_COMMITS, FIX_COMMIT_SCAN_FINDINGS,
+};
+use super::junit::build_junit_report;
+use super::model_stats::suggest_default_model;
+use super::notifications::{notify_run_finished, notify_run_finished_and_wait};
+use super::queue_status::{estimate_queue_start_offsets, QUEUE_ESTIMATE_SAMPLE_RUNS};
+use super::report::{
+    build_html_report, build_markdown_report, REPORT_FORMAT_HTML, REPORT_FORMAT_MARKDOWN,
+};
+use super::reviewer_profiles::{
+    find_reviewer_profile_by_name, load_reviewer_profile, profile_reviewer_goal,
+};
+use super::sarif::build_sarif_report;
+use super::verdict::parse_approval;
+use super::{emit_and_persist_ai_review_progress, ReviewHost, ReviewProvider};
 use super::{executor, store};
+use crate::backend::paths::{long_path, normalize_path};
 use crate::backend::{
-    AiReviewProgressEvent, AiReviewRun, AppState, CancelAiReviewRunInput, CancelAiReviewRunResult,
-    CreateInlineReviewCommentInput, GetAiReviewRunInput, InlineReviewComment,
-    ListAiReviewRunsInput, ListAiReviewRunsResult, ListInlineReviewCommentsInput,
-    ListInlineReviewCommentsResult, StartAiReviewRunInput, StartAiReviewRunResult,
+    AiReviewProgressEvent, AiReviewRun, AppState, ApplyFindingFixInput, ApplyFindingFixResult,
+    CancelAiReviewRunInput, CancelAiReviewRunResult, CreateInlineReviewCommentInput,
+    ExportAiReviewRunInput, ExportAiReviewRunResult, ExportReviewReportInput,
+    ExportReviewReportResult, GenerateAiReviewResult, GetAiReviewRunInput,
+    GetModelPerformanceStatsInput, GetModelPerformanceStatsResult, GetReviewQueueStatusResult,
+    GetReviewUsageSummaryInput, GetReviewUsageSummaryResult, InlineReviewComment,
+    ListAiReviewRunsInput, ListAiReviewRunsResult, ListFindingStatesInput, ListFindingStatesResult,
+    ListInlineReviewCommentsInput, ListInlineReviewCommentsResult, ReviewFindingState,
+    SearchFindingsInput, SearchFindingsResult, SetAiReviewRunApprovalInput, StartAiReviewRunInput,
+    StartAiReviewRunResult, UpdateFindingStateInput, WorkspaceDiffTarget,
 };
 
 #[derive(Clone)]
 struct ActiveRunHandle {
     cancel_flag: Arc<AtomicBool>,
     cancel_notify: Arc<Notify>,
+    /// Order in which the run joined the queue, which is also the order slots are granted in.
+    queue_sequence: u64,
+    has_slot: Arc<AtomicBool>,
 }
 
 static REVIEW_RUN_COUNTER: AtomicU64 = AtomicU64::new(1);
-static REVIEW_RUN_SLOTS: OnceLock<Arc<Semaphore>> = OnceLock::new();
+static REVIEW_QUEUE_SEQUENCE: AtomicU64 = AtomicU64::new(1);
+static REVIEW_RUN_SLOTS: OnceLock<ReviewRunSlots> = OnceLock::new();
+static PROVIDER_CHUNK_SLOTS: OnceLock<Mutex<HashMap<&'static str, &'static ReviewRunSlots>>> =
+    OnceLock::new();
 static ACTIVE_REVIEW_RUNS: OnceLock<Mutex<HashMap<String, ActiveRunHandle>>> = OnceLock::new();
+static UNPERSISTED_RUN_OUTCOMES: OnceLock<Mutex<HashMap<String, PendingRunOutcome>>> =
+    OnceLock::new();
+
+const INTERRUPTED_RUN_ERROR: &str = "Run was interrupted before its outcome was saved.";
+
+/// Final state of a run that still has to be written to the database.
+#[derive(Clone)]
+enum PendingRunOutcome {
+    Finalized {
+        result: Box<GenerateAiReviewResult>,
+        status: &'static str,
+    },
+    Ended {
+        status: &'static str,
+        error: String,
+        mark_canceled: bool,
+    },
+}
+
+impl PendingRunOutcome {
+    fn canceled(error: &str) -> Self {
+        Self::Ended {
+            status: "canceled",
+            error: error.to_string(),
+            mark_canceled: true,
+        }
+    }
+
+    fn failed(error: &str) -> Self {
+        Self::Ended {
+            status: "failed",
+            error: error.to_string(),
+            mark_canceled: false,
+        }
+    }
+
+    async fn write(&self, state: &AppState, run_id: &str) -> Result<(), String> {
+        match self {
+            Self::Finalized { result, status } => {
+                store::retry_db_write(|| {
+                    store::finalize_ai_review_run(state, run_id, result, status, None)
+                })
+                .await
+            }
+            Self::Ended {
+                status,
+                error,
+                mark_canceled,
+            } => {
+                store::retry_db_write(|| {
+                    store::set_ai_review_run_status(
+                        state,
+                        run_id,
+                        status,
+                        Some(error.as_str()),
+                        false,
+                        true,
+                        *mark_canceled,
+                    )
+                })
+                .await
+            }
+        }
+    }
+}
+
+struct SlotCapacity {
+    total: usize,
+    /// Permits that must be retired when running reviews release them, because the
+    /// capacity shrank while they were held.
+    pending_removals: usize,
+}
+
+/// Execution slots shared by all queued review runs, or by the chunk workers of every run on
+/// one provider. The capacity can change at runtime; shrinking it never interrupts work that
+/// already holds a slot.
+struct ReviewRunSlots {
+    semaphore: Arc<Semaphore>,
+    capacity: Mutex<SlotCapacity>,
+}
+
+pub(crate) struct ReviewRunSlot {
+    slots: &'static ReviewRunSlots,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl ReviewRunSlots {
+    fn new(total: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(total)),
+            capacity: Mutex::new(SlotCapacity {
+                total,
+                pending_removals: 0,
+            }),
+        }
+    }
+
+    async fn acquire(&'static self) -> Result<ReviewRunSlot, AcquireError> {
+        let permit = self.semaphore.clone().acquire_owned().await?;
+        Ok(ReviewRunSlot {
+            slots: self,
+            permit: Some(permit),
+        })
+    }
+
+    fn total(&self) -> usize {
+        self.capacity
+            .lock()
+            .map(|capacity| capacity.total)
+            .unwrap_or_default()
+    }
+
+    fn resize(&self, target: usize) {
+        let Ok(mut capacity) = self.capacity.lock() else {
+            return;
+        };
+        if target > capacity.total {
+            let added = target - capacity.total;
+            let restored = added.min(capacity.pending_removals);
+            capacity.pending_removals -= restored;
+            self.semaphore.add_permits(added - restored);
+        } else if target < capacity.total {
+            let removed = capacity.total - target;
+            let forgotten = self.semaphore.forget_permits(removed);
+            capacity.pending_removals += removed - forgotten;
+        }
+        capacity.total = target;
+    }
+
+    fn release(&self, permit: OwnedSemaphorePermit) {
+        if let Ok(mut capacity) = self.capacity.lock() {
+            if capacity.pending_removals > 0 {
+                capacity.pending_removals -= 1;
+                permit.forget();
+            }
+        }
+    }
+}
 
-fn review_run_slots() -> &'static Arc<Semaphore> {
-    REVIEW_RUN_SLOTS.get_or_init(|| Arc::new(Semaphore::new(MAX_PARALLEL_REVIEW_RUNS)))
+impl Drop for ReviewRunSlot {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit.take() {
+            self.slots.release(permit);
+        }
+    }
+}
+
+fn review_run_slots() -> &'static ReviewRunSlots {
+    REVIEW_RUN_SLOTS.get_or_init(|| ReviewRunSlots::new(current_max_parallel_review_runs()))
+}
+
+/// Applies the current `ROVEX_REVIEW_MAX_PARALLEL_RUNS` setting to the run queue.
+pub(crate) fn sync_review_run_slots() {
+    review_run_slots().resize(current_max_parallel_review_runs());
+}
+
+/// The chunk slots of `provider`, shared by every run so concurrent runs do not multiply the
+/// requests in flight against it. Each provider's pool is created once and lives for the rest
+/// of the process.
+fn provider_chunk_slots(provider: ReviewProvider) -> Result<&'static ReviewRunSlots, String> {
+    let mut pools = PROVIDER_CHUNK_SLOTS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .map_err(|_| "Failed to access provider chunk slots.".to_string())?;
+    let limit = current_max_provider_chunks();
+    let slots = *pools
+        .entry(provider.as_str())
+        .or_insert_with(|| Box::leak(Box::new(ReviewRunSlots::new(limit))));
+    slots.resize(limit);
+    Ok(slots)
+}
+
+/// Applies the current `ROVEX_REVIEW_MAX_PROVIDER_CHUNKS` setting to every provider's pool.
+pub(crate) fn sync_provider_chunk_slots() {
+    let Some(pools) = PROVIDER_CHUNK_SLOTS.get() else {
+        return;
+    };
+    let Ok(pools) = pools.lock() else {
+        return;
+    };
+    let limit = current_max_provider_chunks();
+    for slots in pools.values() {
+        slots.resize(limit);
+    }
+}
+
+/// Waits for a chunk slot of `provider`. Chunk workers hold it while they call the provider,
+/// on top of their run's own `ROVEX_REVIEW_MAX_PARALLEL_CHUNKS` cap.
+pub(crate) async fn acquire_provider_chunk_slot(
+    provider: ReviewProvider,
+) -> Result<ReviewRunSlot, String> {
+    provider_chunk_slots(provider)?
+        .acquire()
+        .await
+        .map_err(|_| format!("The {} chunk pool was closed.", provider.as_str()))
+}
+
+fn unpersisted_run_outcomes() -> &'static Mutex<HashMap<String, PendingRunOutcome>> {
+    UNPERSISTED_RUN_OUTCOMES.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
 fn active_review_runs() -> &'static Mutex<HashMap<String, ActiveRunHandle>> {
     ACTIVE_REVIEW_RUNS.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
-fn next_review_run_id() -> String {
+pub(crate) fn next_review_run_id() -> String {
     let counter = REVIEW_RUN_COUNTER.fetch_add(1, Ordering::Relaxed);
     let millis = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -48,26 +284,300 @@ fn next_review_run_id() -> String {
     format!("run-{millis}-{counter}")
 }
 
-pub async fn start_ai_review_run(
-    app: AppHandle,
-    state: State<'_, AppState>,
-    input: StartAiReviewRunInput,
-) -> Result<StartAiReviewRunResult, String> {
-    let _ = load_thread_by_id(&state, input.thread_id).await?;
+/// Narrows an incremental run to the changes made since the last finished review of the same
+/// thread and workspace. Falls back to a full review when there is no usable previous head.
+/// Returns the commit the incremental diff starts from.
+/// Writes a run's final state, retrying transient database errors. If the write still fails,
+/// the outcome is kept in memory so a later reconciliation pass can persist it.
+async fn persist_run_outcome(state: &AppState, run_id: &str, outcome: PendingRunOutcome) {
+    if let Err(error) = outcome.write(state, run_id).await {
+        eprintln!("[backend] Failed to persist outcome of AI review run {run_id}: {error}");
+        if let Ok(mut pending) = unpersisted_run_outcomes().lock() {
+            pending.insert(run_id.to_string(), outcome);
+        }
+        return;
+    }
+    notify_run_finished(state, run_id).await;
+}
+
+/// Retries outcomes that could not be saved when their run ended. With `include_orphaned`,
+/// also fails `queued`/`running` rows that no live task owns, such as runs left behind when
+/// the app exited mid-review.
+pub(crate) async fn reconcile_review_runs(state: &AppState, include_orphaned: bool) {
+    let pending = unpersisted_run_outcomes()
+        .lock()
+        .map(|pending| pending.clone())
+        .unwrap_or_default();
+    for (run_id, outcome) in pending {
+        if outcome.write(state, &run_id).await.is_ok() {
+            if let Ok(mut pending) = unpersisted_run_outcomes().lock() {
+                pending.remove(&run_id);
+            }
+            notify_run_finished(state, &run_id).await;
+        }
+    }
+
+    if !include_orphaned {
+        return;
+    }
+    let run_ids = match store::list_unfinished_ai_review_run_ids(state).await {
+        Ok(run_ids) => run_ids,
+        Err(error) => {
+            eprintln!("[backend] Failed to reconcile AI review runs: {error}");
+            return;
+        }
+    };
+    for run_id in run_ids {
+        let is_active = active_review_runs()
+            .lock()
+            .map(|runs| runs.contains_key(&run_id))
+            .unwrap_or(true);
+        let is_pending = unpersisted_run_outcomes()
+            .lock()
+            .map(|pending| pending.contains_key(&run_id))
+            .unwrap_or(true);
+        if !is_active && !is_pending {
+            persist_run_outcome(
+                state,
+                &run_id,
+                PendingRunOutcome::failed(INTERRUPTED_RUN_ERROR),
+            )
+            .await;
+        }
+    }
+}
+
+async fn apply_review_mode(
+    state: &AppState,
+    input: &mut StartAiReviewRunInput,
+) -> Result<Option<String>, String> {
+    match input.review_mode.as_deref().map(str::trim) {
+        None | Some("") | Some("full") => return Ok(None),
+        Some("incremental") => {}
+        Some(other) => {
+            return Err(format!(
+                "Unsupported review mode '{other}'. Use 'full' or 'incremental'."
+            ))
+        }
+    }
+    if input.diff_target.unwrap_or_default() != WorkspaceDiffTarget::Branch {
+        return Err(
+            "Incremental reviews need committed changes. Use a full review for uncommitted work."
+                .to_string(),
+        );
+    }
+
+    let workspace = input.workspace.trim().to_string();
+    let Some(previous_head) =
+        store::load_last_reviewed_head(state, input.thread_id, &workspace).await?
+    else {
+        return Ok(None);
+    };
+    let Some(range) = diff_since_commit(&workspace, &previous_head)? else {
+        return Ok(None);
+    };
+    if range.diff.trim().is_empty() {
+        return Err("There are no changes since the last review.".to_string());
+    }
+
+    let short_head: String = previous_head.chars().take(8).collect();
+    input.diff = range.diff;
+    input.files_changed = range.files_changed;
+    input.insertions = range.insertions;
+    input.deletions = range.deletions;
+    if input
+        .scope_label
+        .as_deref()
+        .map(str::trim)
+        .is_none_or(str::is_empty)
+    {
+        input.scope_label = Some(format!("Changes since {short_head}"));
+    }
+    Ok(Some(previous_head))
+}
+
+/// Validates and path-filters `input`, then records it as a queued run. Returns the run id,
+/// the filtered input and the number of chunks to review.
+async fn insert_queued_review_run(
+    state: &AppState,
+    mut input: StartAiReviewRunInput,
+) -> Result<(String, StartAiReviewRunInput, usize), String> {
+    let _ = load_thread_by_id(state, input.thread_id).await?;
+    let repo_config =
+        load_repo_review_config(Path::new(input.workspace.trim()))?.unwrap_or_default();
+    if input.profile_id.is_none() {
+        if let Some(name) = repo_config.profile.as_deref() {
+            input.profile_id = find_reviewer_profile_by_name(state, name)
+                .await?
+                .map(|profile| profile.id);
+        }
+    }
+    let profile = match input.profile_id {
+        Some(profile_id) => Some(load_reviewer_profile(state, profile_id).await?),
+        None => None,
+    };
+    let incremental_base_head = apply_review_mode(state, &mut input).await?;
+    if let Err(error) = mark_findings_fixed_by_commits(state, &input).await {
+        eprintln!("[backend] Failed to match commits to fixed findings: {error}");
+    }
+    let exclude_paths = repo_config.merged_exclude_paths(input.exclude_paths.as_deref());
+    let path_filter = PathFilter::new(
+        input.include_paths.as_deref(),
+        Some(exclude_paths.as_slice()),
+    )?
+    .with_rovexignore(Path::new(input.workspace.trim()))?;
+    let mut skipped_chunks = Vec::new();
+    if !path_filter.is_empty() {
+        let filtered = filter_diff_by_path(&input.diff, &path_filter);
+        if filtered.diff.trim().is_empty() {
+            return Err(
+                "No changed files match the review path filters and .rovexignore rules."
+                    .to_string(),
+            );
+        }
+        input.diff = filtered.diff;
+        input.files_changed = filtered.files_changed;
+        input.insertions = filtered.insertions;
+        input.deletions = filtered.deletions;
+        skipped_chunks = filtered.skipped;
+    }
     let raw_diff = input.diff.trim();
     if raw_diff.is_empty() {
         return Err("There are no changes to review.".to_string());
     }
-    let total_chunks = parse_diff_file_chunks(raw_diff).len();
+    let reviewable = parse_workspace_diff_file_chunks(Path::new(input.workspace.trim()), raw_diff)?;
+    let total_chunks = reviewable.chunks.len();
+    skipped_chunks.extend(reviewable.skipped);
     if total_chunks == 0 {
         return Err("No reviewable changed files were found in this diff.".to_string());
     }
 
-    let reviewer_goal = as_non_empty_trimmed(input.prompt.as_deref())
-        .unwrap_or_else(|| "Review changed files and report actionable bugs.".to_string());
+    let reviewer_goal = match profile.as_ref() {
+        Some(profile) => profile_reviewer_goal(profile, input.prompt.as_deref()),
+        None => as_non_empty_trimmed(input.prompt.as_deref())
+            .unwrap_or_else(|| "Review changed files and report actionable bugs.".to_string()),
+    };
 
     let run_id = next_review_run_id();
-    store::insert_ai_review_run(&state, &run_id, &input, &reviewer_goal, total_chunks).await?;
+    store::insert_ai_review_run(
+        state,
+        &run_id,
+        &input,
+        &reviewer_goal,
+        total_chunks,
+        &skipped_chunks,
+        incremental_base_head.as_deref(),
+    )
+    .await?;
+    Ok((run_id, input, total_chunks))
+}
+
+/// Marks open findings of the workspace fixed when a commit in the range under review names
+/// them, so the run, and every later one, starts with them closed. The oldest naming commit
+/// is recorded as the fix.
+async fn mark_findings_fixed_by_commits(
+    state: &AppState,
+    input: &StartAiReviewRunInput,
+) -> Result<(), String> {
+    let workspace = input.workspace.trim();
+    let commits = commit_messages_between(
+        workspace,
+        input.merge_base.trim(),
+        input.head.trim(),
+        FIX_COMMIT_SCAN_COMMITS,
+    );
+    if commits.is_empty() {
+        return Ok(());
+    }
+    for (run_id, finding_id, fingerprint) in
+        store::list_open_findings(state, workspace, FIX_COMMIT_SCAN_FINDINGS).await?
+    {
+        let Some((hash, _)) = commits.iter().rev().find(|(_, message)| {
+            commit_message_references_finding(message, &fingerprint, &finding_id)
+        }) else {
+            continue;
+        };
+        let short_hash: String = hash.chars().take(8).collect();
+        store::set_review_finding_state(
+            state,
+            &run_id,
+            &finding_id,
+            FINDING_STATUS_FIXED,
+            Some(format!("Named in commit {short_hash}.")),
+            Some(hash.clone()),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Runs a review to completion on the calling task instead of the app's run queue, for the
+/// headless CLI. Progress goes to stderr.
+pub(crate) async fn run_review_headless(
+    state: &AppState,
+    input: StartAiReviewRunInput,
+) -> Result<AiReviewRun, String> {
+    let (run_id, input, _) = insert_queued_review_run(state, input).await?;
+    store::retry_db_write(|| {
+        store::set_ai_review_run_status(state, &run_id, "running", None, true, false, false)
+    })
+    .await?;
+
+    let review_input = executor::as_generate_ai_review_input(input);
+    let outcome = match executor::execute_ai_review_generation(
+        &ReviewHost::Headless,
+        state,
+        &review_input,
+        Some(&run_id),
+        None,
+        true,
+    )
+    .await
+    {
+        Ok(outcome) => PendingRunOutcome::Finalized {
+            status: if outcome.had_errors {
+                "completed_with_errors"
+            } else {
+                "completed"
+            },
+            result: Box::new(outcome.result),
+        },
+        Err(error) => PendingRunOutcome::failed(&error),
+    };
+    outcome.write(state, &run_id).await?;
+    notify_run_finished_and_wait(state, &run_id).await;
+    store::load_ai_review_run_by_id(state, &run_id).await
+}
+
+pub async fn start_ai_review_run(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    mut input: StartAiReviewRunInput,
+) -> Result<StartAiReviewRunResult, String> {
+    sync_review_run_slots();
+    input.idempotency_key = parse_idempotency_key(input.idempotency_key.as_deref())?;
+    let thread_id = input.thread_id;
+    let idempotency_key = input.idempotency_key.clone();
+    if let Some(key) = idempotency_key.as_deref() {
+        if let Some(run) =
+            store::load_ai_review_run_by_idempotency_key(&state, thread_id, key).await?
+        {
+            return Ok(StartAiReviewRunResult { run });
+        }
+    }
+    let (run_id, input, total_chunks) = match insert_queued_review_run(&state, input).await {
+        Ok(queued) => queued,
+        Err(error) => {
+            // A concurrent retry with the same key may have queued the run first.
+            let Some(key) = idempotency_key.as_deref() else {
+                return Err(error);
+            };
+            let run = store::load_ai_review_run_by_idempotency_key(&state, thread_id, key)
+                .await?
+                .ok_or(error)?;
+            return Ok(StartAiReviewRunResult { run });
+        }
+    };
     let queued_event = AiReviewProgressEvent {
         run_id: Some(run_id.clone()),
         thread_id: input.thread_id,
@@ -81,11 +591,19 @@ pub async fn start_ai_review_run(
         finding_count: None,
         chunk: None,
         finding: None,
+        eta_ms: None,
     };
-    emit_and_persist_ai_review_progress(&app, &state, &run_id, queued_event).await;
+    emit_and_persist_ai_review_progress(
+        &ReviewHost::App(app.clone()),
+        &state,
+        &run_id,
+        queued_event,
+    )
+    .await;
 
     let cancel_flag = Arc::new(AtomicBool::new(false));
     let cancel_notify = Arc::new(Notify::new());
+    let has_slot = Arc::new(AtomicBool::new(false));
     let completed_notify = Arc::new(Notify::new());
     {
         let mut runs = active_review_runs()
@@ -96,20 +614,23 @@ pub async fn start_ai_review_run(
             ActiveRunHandle {
                 cancel_flag: cancel_flag.clone(),
                 cancel_notify: cancel_notify.clone(),
+                queue_sequence: REVIEW_QUEUE_SEQUENCE.fetch_add(1, Ordering::Relaxed),
+                has_slot: has_slot.clone(),
             },
         );
     }
 
     let app_handle = app.clone();
+    let host = ReviewHost::App(app);
     let run_id_for_task = run_id.clone();
-    let review_input = executor::as_generate_ai_review_input(&input);
+    let review_input = executor::as_generate_ai_review_input(input);
     tauri::async_runtime::spawn(async move {
-        let acquire = review_run_slots().clone().acquire_owned();
+        let acquire = review_run_slots().acquire();
         tokio::pin!(acquire);
         let permit = tokio::select! {
             _ = cancel_notify.notified() => {
                 let state = app_handle.state::<AppState>();
-                let _ = store::set_ai_review_run_status(&state, &run_id_for_task, "canceled", Some("Run canceled before execution."), false, true, true).await;
+                persist_run_outcome(&state, &run_id_for_task, PendingRunOutcome::canceled("Run canceled before execution.")).await;
                 let canceled_event = AiReviewProgressEvent {
                     run_id: Some(run_id_for_task.clone()),
                     thread_id: review_input.thread_id,
@@ -123,8 +644,9 @@ pub async fn start_ai_review_run(
                     finding_count: None,
                     chunk: None,
                     finding: None,
+                    eta_ms: None,
                 };
-                emit_and_persist_ai_review_progress(&app_handle, &state, &run_id_for_task, canceled_event).await;
+                emit_and_persist_ai_review_progress(&host, &state, &run_id_for_task, canceled_event).await;
                 if let Ok(mut runs) = active_review_runs().lock() {
                     runs.remove(&run_id_for_task);
                 }
@@ -141,18 +663,15 @@ pub async fn start_ai_review_run(
             return;
         };
         let _permit = permit;
+        has_slot.store(true, Ordering::Relaxed);
 
         let state = app_handle.state::<AppState>();
 
         if cancel_flag.load(Ordering::Relaxed) {
-            let _ = store::set_ai_review_run_status(
+            persist_run_outcome(
                 &state,
                 &run_id_for_task,
-                "canceled",
-                Some("Run canceled before execution."),
-                false,
-                true,
-                true,
+                PendingRunOutcome::canceled("Run canceled before execution."),
             )
             .await;
             if let Ok(mut runs) = active_review_runs().lock() {
@@ -162,19 +681,26 @@ pub async fn start_ai_review_run(
             return;
         }
 
-        let _ = store::set_ai_review_run_status(
-            &state,
-            &run_id_for_task,
-            "running",
-            None,
-            true,
-            false,
-            false,
-        )
-        .await;
+        if let Err(error) = store::retry_db_write(|| {
+            store::set_ai_review_run_status(
+                &state,
+                &run_id_for_task,
+                "running",
+                None,
+                true,
+                false,
+                false,
+            )
+        })
+        .await
+        {
+            eprintln!(
+                "[backend] Failed to mark AI review run {run_id_for_task} as running: {error}"
+            );
+        }
 
         let outcome = executor::execute_ai_review_generation(
-            &app_handle,
+            &host,
             &state,
             &review_input,
             Some(&run_id_for_task),
@@ -190,25 +716,22 @@ pub async fn start_ai_review_run(
                 } else {
                     "completed"
                 };
-                let _ = store::finalize_ai_review_run(
+                persist_run_outcome(
                     &state,
                     &run_id_for_task,
-                    &outcome.result,
-                    status,
-                    None,
+                    PendingRunOutcome::Finalized {
+                        result: Box::new(outcome.result),
+                        status,
+                    },
                 )
                 .await;
             }
             Err(error) => {
                 if error.to_lowercase().contains("canceled") {
-                    let _ = store::set_ai_review_run_status(
+                    persist_run_outcome(
                         &state,
                         &run_id_for_task,
-                        "canceled",
-                        Some(error.as_str()),
-                        false,
-                        true,
-                        true,
+                        PendingRunOutcome::canceled(&error),
                     )
                     .await;
                     let canceled_event = AiReviewProgressEvent {
@@ -224,23 +747,20 @@ pub async fn start_ai_review_run(
                         finding_count: None,
                         chunk: None,
                         finding: None,
+                        eta_ms: None,
                     };
                     emit_and_persist_ai_review_progress(
-                        &app_handle,
+                        &host,
                         &state,
                         &run_id_for_task,
                         canceled_event,
                     )
                     .await;
                 } else {
-                    let _ = store::set_ai_review_run_status(
+                    persist_run_outcome(
                         &state,
                         &run_id_for_task,
-                        "failed",
-                        Some(error.as_str()),
-                        false,
-                        true,
-                        false,
+                        PendingRunOutcome::failed(&error),
                     )
                     .await;
                     let failed_event = AiReviewProgressEvent {
@@ -256,9 +776,10 @@ pub async fn start_ai_review_run(
                         finding_count: None,
                         chunk: None,
                         finding: None,
+                        eta_ms: None,
                     };
                     emit_and_persist_ai_review_progress(
-                        &app_handle,
+                        &host,
                         &state,
                         &run_id_for_task,
                         failed_event,
@@ -272,6 +793,7 @@ pub async fn start_ai_review_run(
             runs.remove(&run_id_for_task);
         }
         completed_notify.notify_waiters();
+        reconcile_review_runs(&state, false).await;
     });
 
     let run = store::load_ai_review_run_by_id(&state, &run_id).await?;
@@ -299,15 +821,17 @@ pub async fn cancel_ai_review_run(
         active.cancel_flag.store(true, Ordering::Relaxed);
         active.cancel_notify.notify_waiters();
         if run.status == "queued" {
-            store::set_ai_review_run_status(
-                &state,
-                run_id,
-                "canceled",
-                Some("Run canceled before execution."),
-                false,
-                true,
-                true,
-            )
+            store::retry_db_write(|| {
+                store::set_ai_review_run_status(
+                    &state,
+                    run_id,
+                    "canceled",
+                    Some("Run canceled before execution."),
+                    false,
+                    true,
+                    true,
+                )
+            })
             .await?;
             let canceled_event = AiReviewProgressEvent {
                 run_id: Some(run_id.to_string()),
@@ -322,8 +846,15 @@ pub async fn cancel_ai_review_run(
                 finding_count: Some(run.finding_count),
                 chunk: None,
                 finding: None,
+                eta_ms: None,
             };
-            emit_and_persist_ai_review_progress(&app, &state, run_id, canceled_event).await;
+            emit_and_persist_ai_review_progress(
+                &ReviewHost::App(app),
+                &state,
+                run_id,
+                canceled_event,
+            )
+            .await;
         }
         let status = if run.status == "queued" {
             "canceled".to_string()
@@ -348,8 +879,7 @@ pub async fn list_ai_review_runs(
     state: State<'_, AppState>,
     input: ListAiReviewRunsInput,
 ) -> Result<ListAiReviewRunsResult, String> {
-    let runs = store::list_ai_review_runs_internal(&state, input.thread_id, input.limit).await?;
-    Ok(ListAiReviewRunsResult { runs })
+    store::list_ai_review_runs_internal(&state, &input).await
 }
 
 pub async fn get_ai_review_run(
@@ -360,7 +890,309 @@ pub async fn get_ai_review_run(
     if run_id.is_empty() {
         return Err("Run id must not be empty.".to_string());
     }
-    store::load_ai_review_run_by_id(&state, run_id).await
+    let order = FindingOrder::parse(input.sort_by.as_deref(), input.group_by.as_deref())?;
+    let mut run = store::load_ai_review_run_by_id(&state, run_id).await?;
+    if input.sort_by.is_some() || input.group_by.is_some() {
+        organize_run_findings(&mut run, &order);
+    }
+    if input.include_blame.unwrap_or(false) {
+        blame_findings(
+            &run.workspace,
+            &run.merge_base,
+            &run.head,
+            &mut run.findings,
+        );
+    }
+    Ok(run)
+}
+
+pub async fn set_ai_review_run_approval(
+    state: &AppState,
+    input: SetAiReviewRunApprovalInput,
+) -> Result<AiReviewRun, String> {
+    let run_id = input.run_id.trim();
+    if run_id.is_empty() {
+        return Err("Run id must not be empty.".to_string());
+    }
+    let approval = parse_approval(&input.approval)?;
+    let note = as_non_empty_trimmed(input.note.as_deref());
+    store::set_ai_review_run_approval(state, run_id, approval, note).await?;
+    store::load_ai_review_run_by_id(state, run_id).await
+}
+
+pub async fn export_ai_review_run(
+    state: &AppState,
+    input: ExportAiReviewRunInput,
+) -> Result<ExportAiReviewRunResult, String> {
+    let run_id = input.run_id.trim();
+    if run_id.is_empty() {
+        return Err("Run id must not be empty.".to_string());
+    }
+    let format = as_non_empty_trimmed(input.format.as_deref())
+        .map(|value| value.to_lowercase())
+        .unwrap_or_else(|| "sarif".to_string());
+    let run = store::load_ai_review_run_by_id(state, run_id).await?;
+    let (content, extension, mime_type) = match format.as_str() {
+        "sarif" => (
+            serde_json::to_string_pretty(&build_sarif_report(&run))
+                .map_err(|error| format!("Failed to serialize SARIF report: {error}"))?,
+            "sarif",
+            "application/sarif+json",
+        ),
+        "junit" => (build_junit_report(&run), "xml", "application/xml"),
+        _ => {
+            return Err(format!(
+                "Unsupported export format '{format}'. Expected 'sarif' or 'junit'."
+            ))
+        }
+    };
+    Ok(ExportAiReviewRunResult {
+        run_id: run.run_id.clone(),
+        file_name: format!("rovex-review-{}.{extension}", run.run_id),
+        format,
+        mime_type: mime_type.to_string(),
+        content,
+    })
+}
+
+pub async fn export_review_report(
+    state: State<'_, AppState>,
+    input: ExportReviewReportInput,
+) -> Result<ExportReviewReportResult, String> {
+    let run_id = input.run_id.trim();
+    if run_id.is_empty() {
+        return Err("Run id must not be empty.".to_string());
+    }
+    let output_path = input.output_path.trim();
+    if output_path.is_empty() {
+        return Err("Output path must not be empty.".to_string());
+    }
+    let output_path = normalize_path(Path::new(output_path))?;
+    let order = FindingOrder::parse(input.sort_by.as_deref(), input.group_by.as_deref())?;
+    let format = match as_non_empty_trimmed(input.format.as_deref())
+        .map(|value| value.to_lowercase())
+        .as_deref()
+    {
+        Some("markdown" | "md") => REPORT_FORMAT_MARKDOWN,
+        Some("html" | "htm") => REPORT_FORMAT_HTML,
+        Some(other) => {
+            return Err(format!(
+                "Unsupported report format '{other}'. Expected 'markdown' or 'html'."
+            ))
+        }
+        None => match output_path
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some(extension) if extension.eq_ignore_ascii_case("html") => REPORT_FORMAT_HTML,
+            Some(extension) if extension.eq_ignore_ascii_case("htm") => REPORT_FORMAT_HTML,
+            _ => REPORT_FORMAT_MARKDOWN,
+        },
+    };
+    if let Some(parent) = output_path.parent() {
+        if !parent.is_dir() {
+            return Err(format!("Directory does not exist: {}", format_path(parent)));
+        }
+    }
+
+    let run = store::load_ai_review_run_by_id(&state, run_id).await?;
+    let content = if format == REPORT_FORMAT_HTML {
+        build_html_report(&run, &order)
+    } else {
+        build_markdown_report(&run, &order)
+    };
+    fs::write(long_path(&output_path), &content).map_err(|error| {
+        format!(
+            "Failed to write report to {}: {error}",
+            format_path(&output_path)
+        )
+    })?;
+    Ok(ExportReviewReportResult {
+        run_id: run.run_id,
+        format: format.to_string(),
+        path: format_path(&output_path),
+        bytes_written: content.len() as u64,
+    })
+}
+
+pub async fn get_review_queue_status(
+    state: State<'_, AppState>,
+) -> Result<GetReviewQueueStatusResult, String> {
+    sync_review_run_slots();
+    let handles = active_review_runs()
+        .lock()
+        .map_err(|_| "Failed to access active review runs.".to_string())?
+        .clone();
+    let avg_chunk_duration_ms =
+        store::load_average_chunk_duration_ms(&state, QUEUE_ESTIMATE_SAMPLE_RUNS).await?;
+
+    // Rows without a live task are orphans that reconciliation will fail; they hold no slot.
+    let mut runs = store::list_unfinished_ai_review_runs(&state)
+        .await?
+        .into_iter()
+        .filter_map(|mut run| {
+            let handle = handles.get(&run.run_id)?;
+            run.has_slot = handle.has_slot.load(Ordering::Relaxed);
+            Some((handle.queue_sequence, run))
+        })
+        .collect::<Vec<_>>();
+    runs.sort_by_key(|(queue_sequence, run)| (!run.has_slot, *queue_sequence));
+    let mut runs = runs.into_iter().map(|(_, run)| run).collect::<Vec<_>>();
+
+    let slots_total = review_run_slots().total();
+    let slots_in_use = runs.iter().filter(|run| run.has_slot).count();
+    let start_offsets = avg_chunk_duration_ms
+        .map(|chunk_ms| {
+            let duration_ms = |chunks: usize| (chunk_ms * chunks as f64).round() as u64;
+            let running_remaining_ms = runs
+                .iter()
+                .filter(|run| run.has_slot)
+                .map(|run| {
+                    duration_ms(
+                        run.total_chunks
+                            .saturating_sub(run.completed_chunks + run.failed_chunks),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let queued_duration_ms = runs
+                .iter()
+                .filter(|run| !run.has_slot)
+                .map(|run| duration_ms(run.total_chunks))
+                .collect::<Vec<_>>();
+            estimate_queue_start_offsets(slots_total, &running_remaining_ms, &queued_duration_ms)
+        })
+        .unwrap_or_default();
+    for (position, run) in runs.iter_mut().filter(|run| !run.has_slot).enumerate() {
+        run.queue_position = Some(position + 1);
+        run.estimated_start_ms = start_offsets.get(position).copied();
+    }
+
+    Ok(GetReviewQueueStatusResult {
+        slots_total,
+        slots_in_use,
+        avg_chunk_duration_ms: avg_chunk_duration_ms.map(|value| value.round() as u64),
+        runs,
+    })
+}
+
+pub async fn get_review_usage_summary(
+    state: State<'_, AppState>,
+    input: GetReviewUsageSummaryInput,
+) -> Result<GetReviewUsageSummaryResult, String> {
+    let months = store::load_review_usage_summary(&state, input.thread_id, input.limit).await?;
+    Ok(GetReviewUsageSummaryResult { months })
+}
+
+pub async fn search_findings(
+    state: State<'_, AppState>,
+    input: SearchFindingsInput,
+) -> Result<SearchFindingsResult, String> {
+    let file_glob = as_non_empty_trimmed(input.file_glob.as_deref());
+    let file_filter = file_glob
+        .map(|glob| PathFilter::new(Some(&[glob]), None))
+        .transpose()?;
+    let hits = store::search_ai_review_findings(&state, &input, file_filter.as_ref()).await?;
+    Ok(SearchFindingsResult { hits })
+}
+
+async fn update_finding_state(
+    state: &AppState,
+    input: UpdateFindingStateInput,
+    finding_state: &str,
+    fix_commit: Option<String>,
+) -> Result<ReviewFindingState, String> {
+    let run_id = input.run_id.trim();
+    if run_id.is_empty() {
+        return Err("Run id must not be empty.".to_string());
+    }
+    let finding_id = input.finding_id.trim();
+    if finding_id.is_empty() {
+        return Err("Finding id must not be empty.".to_string());
+    }
+    let reason = as_non_empty_trimmed(input.reason.as_deref());
+    store::set_review_finding_state(state, run_id, finding_id, finding_state, reason, fix_commit)
+        .await
+}
+
+pub async fn dismiss_finding(
+    state: State<'_, AppState>,
+    input: UpdateFindingStateInput,
+) -> Result<ReviewFindingState, String> {
+    update_finding_state(&state, input, FINDING_STATUS_DISMISSED, None).await
+}
+
+pub async fn mark_finding_fixed(
+    state: State<'_, AppState>,
+    input: UpdateFindingStateInput,
+) -> Result<ReviewFindingState, String> {
+    let fix_commit = parse_fix_commit(input.fix_commit.as_deref())?;
+    update_finding_state(&state, input, FINDING_STATUS_FIXED, fix_commit).await
+}
+
+pub async fn apply_finding_fix(
+    state: State<'_, AppState>,
+    input: ApplyFindingFixInput,
+) -> Result<ApplyFindingFixResult, String> {
+    let run_id = input.run_id.trim();
+    if run_id.is_empty() {
+        return Err("Run id must not be empty.".to_string());
+    }
+    let finding_id = input.finding_id.trim();
+    if finding_id.is_empty() {
+        return Err("Finding id must not be empty.".to_string());
+    }
+    let (workspace, head, finding) =
+        store::load_ai_review_finding(&state, run_id, finding_id).await?;
+    let patch = finding
+        .suggested_patch
+        .as_deref()
+        .and_then(|patch| normalize_suggested_patch(patch, &finding.file_path))
+        .ok_or_else(|| format!("Finding '{finding_id}' has no applicable suggested fix."))?;
+    let outcome =
+        apply_patch_to_workspace(&workspace, &finding.file_path, &patch, head.as_deref())?;
+    Ok(ApplyFindingFixResult {
+        workspace: outcome.workspace,
+        file_path: finding.file_path,
+        applied: outcome.conflicts.is_empty(),
+        three_way: outcome.three_way,
+        conflicts: outcome.conflicts,
+    })
+}
+
+pub async fn list_finding_states(
+    state: State<'_, AppState>,
+    input: ListFindingStatesInput,
+) -> Result<ListFindingStatesResult, String> {
+    let workspace = as_non_empty_trimmed(input.workspace.as_deref());
+    let finding_state =
+        as_non_empty_trimmed(input.state.as_deref()).map(|value| value.to_lowercase());
+    let states =
+        store::list_review_finding_states(&state, workspace, finding_state, input.limit).await?;
+    Ok(ListFindingStatesResult { states })
+}
+
+pub async fn get_model_performance_stats(
+    state: State<'_, AppState>,
+    input: GetModelPerformanceStatsInput,
+) -> Result<GetModelPerformanceStatsResult, String> {
+    let transport =
+        as_non_empty_trimmed(input.transport.as_deref()).map(|value| value.to_lowercase());
+    let prompt_profile = as_non_empty_trimmed(input.prompt_profile.as_deref());
+    let stats = store::load_model_performance_stats(
+        &state,
+        transport.as_deref(),
+        prompt_profile.as_deref(),
+    )
+    .await?;
+    let suggestion_transport = match transport {
+        Some(transport) => transport,
+        None => ReviewProvider::from_settings()?.as_str().to_string(),
+    };
+    let suggested_model = suggest_default_model(&stats, &suggestion_transport);
+    Ok(GetModelPerformanceStatsResult {
+        stats,
+        suggested_model,
+    })
 }
 
 pub async fn create_inline_review_comment(
@@ -379,3 +1211,25 @@ pub async fn list_inline_review_comments(
     let comments = store::list_inline_review_comments_internal(&state, &input).await?;
     Ok(ListInlineReviewCommentsResult { comments })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ReviewRunSlots;
+
+    #[test]
+    fn slots_return_to_their_own_pool_and_honor_shrinking() {
+        tauri::async_runtime::block_on(async {
+            let slots: &'static ReviewRunSlots = Box::leak(Box::new(ReviewRunSlots::new(2)));
+            let first = slots.acquire().await.unwrap();
+            let second = slots.acquire().await.unwrap();
+            assert_eq!(slots.semaphore.available_permits(), 0);
+
+            slots.resize(1);
+            drop(first);
+            assert_eq!(slots.semaphore.available_permits(), 0);
+            drop(second);
+            assert_eq!(slots.semaphore.available_permits(), 1);
+            assert_eq!(slots.total(), 1);
+        });
+    }
+}
@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use tauri::State;
+
+use super::super::common::{
+    as_non_empty_trimmed, parse_setting_usize, DEFAULT_MAX_RUN_SNAPSHOTS,
+    ROVEX_MAX_RUN_SNAPSHOTS_ENV,
+};
+use super::super::workspace_git::{add_run_snapshot_worktree, remove_run_snapshot_worktree};
+use super::store;
+use crate::backend::{
+    AppState, ListRunSnapshotsInput, ListRunSnapshotsResult, RemoveRunSnapshotResult, RunSnapshot,
+    RunSnapshotInput,
+};
+
+fn parse_run_id(value: &str) -> Result<&str, String> {
+    let run_id = value.trim();
+    if run_id.is_empty() {
+        return Err("Run id must not be empty.".to_string());
+    }
+    Ok(run_id)
+}
+
+async fn query_run_snapshots(
+    state: &AppState,
+    workspace: Option<String>,
+    run_id: Option<String>,
+) -> Result<Vec<RunSnapshot>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT run_id, workspace, head, path, opened_at
+             FROM run_snapshots
+             WHERE (?1 IS NULL OR workspace = ?1) AND (?2 IS NULL OR run_id = ?2)
+             ORDER BY opened_at DESC, rowid DESC",
+            (workspace, run_id),
+        )
+        .await
+        .map_err(|error| format!("Failed to query run snapshots: {error}"))?;
+
+    let mut snapshots = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read run snapshot row: {error}"))?
+    {
+        let path: String = row
+            .get(3)
+            .map_err(|error| format!("Failed to parse run snapshot path: {error}"))?;
+        snapshots.push(RunSnapshot {
+            run_id: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse run snapshot run id: {error}"))?,
+            workspace: row
+                .get(1)
+                .map_err(|error| format!("Failed to parse run snapshot workspace: {error}"))?,
+            head: row
+                .get(2)
+                .map_err(|error| format!("Failed to parse run snapshot head: {error}"))?,
+            exists: Path::new(&path).is_dir(),
+            path,
+            created: false,
+            opened_at: row
+                .get(4)
+                .map_err(|error| format!("Failed to parse run snapshot timestamp: {error}"))?,
+        });
+    }
+    Ok(snapshots)
+}
+
+async fn delete_run_snapshot(state: &AppState, snapshot: &RunSnapshot) -> Result<bool, String> {
+    // A workspace that was deleted or moved takes its worktrees with it, so only the record
+    // is left to clean up.
+    let removed = match remove_run_snapshot_worktree(&snapshot.workspace, &snapshot.run_id) {
+        Ok(removed) => removed,
+        Err(error) if !snapshot.exists => {
+            eprintln!(
+                "[backend] Failed to prune worktree of run snapshot {}: {error}",
+                snapshot.run_id
+            );
+            false
+        }
+        Err(error) => return Err(error),
+    };
+    let conn = state.connection()?;
+    conn.execute(
+        "DELETE FROM run_snapshots WHERE run_id = ?1",
+        [snapshot.run_id.clone()],
+    )
+    .await
+    .map_err(|error| format!("Failed to delete run snapshot {}: {error}", snapshot.run_id))?;
+    Ok(removed)
+}
+
+/// Removes the least recently opened snapshots of `workspace` beyond the configured limit.
+async fn prune_run_snapshots(state: &AppState, workspace: &str) -> Result<(), String> {
+    let max_snapshots =
+        parse_setting_usize(ROVEX_MAX_RUN_SNAPSHOTS_ENV, DEFAULT_MAX_RUN_SNAPSHOTS, 1);
+    let snapshots = query_run_snapshots(state, Some(workspace.to_string()), None).await?;
+    for snapshot in snapshots.iter().skip(max_snapshots) {
+        if let Err(error) = delete_run_snapshot(state, snapshot).await {
+            eprintln!(
+                "[backend] Failed to remove old run snapshot {}: {error}",
+                snapshot.run_id
+            );
+        }
+    }
+    Ok(())
+}
+
+pub async fn checkout_run_snapshot(
+    state: State<'_, AppState>,
+    input: RunSnapshotInput,
+) -> Result<RunSnapshot, String> {
+    let run_id = parse_run_id(&input.run_id)?;
+    let run = store::load_ai_review_run_by_id(&state, run_id).await?;
+    if run.status != "completed" {
+        return Err(format!(
+            "Run '{run_id}' is {}. Only completed runs can be checked out.",
+            run.status
+        ));
+    }
+
+    let (path, created) = add_run_snapshot_worktree(&run.workspace, run_id, &run.head)?;
+    let conn = state.connection()?;
+    conn.execute(
+        "INSERT INTO run_snapshots (run_id, workspace, head, path) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(run_id) DO UPDATE SET
+           workspace = excluded.workspace,
+           head = excluded.head,
+           path = excluded.path,
+           opened_at = CURRENT_TIMESTAMP",
+        (
+            run_id.to_string(),
+            run.workspace.clone(),
+            run.head.clone(),
+            path,
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to save run snapshot {run_id}: {error}"))?;
+    if let Err(error) = prune_run_snapshots(&state, &run.workspace).await {
+        eprintln!("[backend] Failed to prune run snapshots: {error}");
+    }
+
+    let mut snapshot = query_run_snapshots(&state, None, Some(run_id.to_string()))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Run snapshot {run_id} was not saved."))?;
+    snapshot.created = created;
+    Ok(snapshot)
+}
+
+pub async fn list_run_snapshots(
+    state: State<'_, AppState>,
+    input: ListRunSnapshotsInput,
+) -> Result<ListRunSnapshotsResult, String> {
+    let workspace = as_non_empty_trimmed(input.workspace.as_deref());
+    let snapshots = query_run_snapshots(&state, workspace, None).await?;
+    Ok(ListRunSnapshotsResult { snapshots })
+}
+
+pub async fn remove_run_snapshot(
+    state: State<'_, AppState>,
+    input: RunSnapshotInput,
+) -> Result<RemoveRunSnapshotResult, String> {
+    let run_id = parse_run_id(&input.run_id)?;
+    let snapshot = query_run_snapshots(&state, None, Some(run_id.to_string()))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Run '{run_id}' has no snapshot."))?;
+    let removed = delete_run_snapshot(&state, &snapshot).await?;
+    Ok(RemoveRunSnapshotResult {
+        run_id: run_id.to_string(),
+        removed,
+    })
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use super::findings::{normalize_finding_title, FINDING_STATUS_DISMISSED};
+use crate::backend::{AiReviewFinding, AiReviewRun};
+
+const SARIF_SCHEMA_URI: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_TOOL_NAME: &str = "rovex";
+
+/// Maps a finding severity to a SARIF result level and the `security-severity` score GitHub
+/// code scanning uses to rank alerts.
+fn sarif_level(severity: &str) -> (&'static str, &'static str) {
+    match severity {
+        "critical" => ("error", "9.0"),
+        "high" => ("error", "7.0"),
+        "medium" => ("warning", "5.0"),
+        _ => ("note", "3.0"),
+    }
+}
+
+/// Rule id shared by every finding with the same normalized title.
+fn sarif_rule_id(finding: &AiReviewFinding) -> String {
+    let slug = normalize_finding_title(&finding.title).replace(' ', "-");
+    if slug.is_empty() {
+        "rovex/finding".to_string()
+    } else {
+        format!("rovex/{slug}")
+    }
+}
+
+fn sarif_result(finding: &AiReviewFinding, rule_index: usize) -> Value {
+    let (level, _) = sarif_level(&finding.severity);
+    let mut physical_location = json!({
+        "artifactLocation": {
+            "uri": finding.file_path,
+            "uriBaseId": "%SRCROOT%",
+        },
+    });
+    // Deletion-side line numbers point into the base revision, which the scanned checkout
+    // does not have, so those findings are reported against the whole file.
+    if finding.side != "deletions" {
+        physical_location["region"] = json!({ "startLine": finding.line_number.max(1) });
+    }
+
+    let mut result = json!({
+        "ruleId": sarif_rule_id(finding),
+        "ruleIndex": rule_index,
+        "level": level,
+        "message": { "text": format!("{}\n\n{}", finding.title, finding.body) },
+        "locations": [{ "physicalLocation": physical_location }],
+        "properties": {
+            "severity": finding.severity,
+            "category": finding.category,
+            "confidence": finding.confidence,
+            "side": finding.side,
+        },
+    });
+    if let Some(fingerprint) = finding.fingerprint.as_deref() {
+        result["partialFingerprints"] = json!({ "rovexFingerprint/v1": fingerprint });
+    }
+    if finding.status.as_deref() == Some(FINDING_STATUS_DISMISSED) {
+        result["suppressions"] = json!([{ "kind": "external", "status": "accepted" }]);
+    }
+    result
+}
+
+/// Builds one rule per distinct finding title and one result per finding.
+fn sarif_rules_and_results(findings: &[AiReviewFinding]) -> (Vec<Value>, Vec<Value>) {
+    let mut rules: Vec<Value> = Vec::new();
+    let mut rule_indexes: HashMap<String, usize> = HashMap::new();
+    let mut results = Vec::with_capacity(findings.len());
+    for finding in findings {
+        let rule_id = sarif_rule_id(finding);
+        let rule_index = match rule_indexes.get(&rule_id) {
+            Some(index) => *index,
+            None => {
+                let (level, security_severity) = sarif_level(&finding.severity);
+                rules.push(json!({
+                    "id": rule_id,
+                    "name": finding.title,
+                    "shortDescription": { "text": finding.title },
+                    "defaultConfiguration": { "level": level },
+                    "properties": {
+                        "security-severity": security_severity,
+                        "tags": finding.category.iter().collect::<Vec<_>>(),
+                    },
+                }));
+                rule_indexes.insert(rule_id, rules.len() - 1);
+                rules.len() - 1
+            }
+        };
+        results.push(sarif_result(finding, rule_index));
+    }
+    (rules, results)
+}
+
+/// Serializes a run's findings as a SARIF 2.1.0 log.
+pub(crate) fn build_sarif_report(run: &AiReviewRun) -> Value {
+    let (rules, results) = sarif_rules_and_results(&run.findings);
+    json!({
+        "$schema": SARIF_SCHEMA_URI,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": SARIF_TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "automationDetails": { "id": format!("rovex/{}", run.run_id) },
+            "results": results,
+            "properties": {
+                "head": run.head,
+                "baseRef": run.base_ref,
+                "mergeBase": run.merge_base,
+                "model": run.model,
+                "status": run.status,
+                "scopeLabel": run.scope_label,
+                "verdict": run.verdict,
+                "verdictReason": run.verdict_reason,
+                "approval": run.approval,
+                "approvalNote": run.approval_note,
+            },
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sarif_rules_and_results;
+    use crate::backend::AiReviewFinding;
+
+    fn finding(title: &str, severity: &str, side: &str, line_number: i64) -> AiReviewFinding {
+        AiReviewFinding {
+            id: format!("src/lib.rs:{line_number}"),
+            file_path: "src/lib.rs".to_string(),
+            chunk_id: "src/lib.rs#file-1".to_string(),
+            chunk_index: 1,
+            hunk_header: "@@ -1 +1 @@".to_string(),
+            side: side.to_string(),
+            line_number,
+            title: title.to_string(),
+            body: "body".to_string(),
+            severity: severity.to_string(),
+            confidence: None,
+            category: Some("correctness".to_string()),
+            fingerprint: Some("0123456789abcdef".to_string()),
+            status: None,
+            code_hash: None,
+            recurring: None,
+            suggested_patch: None,
+            language: None,
+            blame: None,
+        }
+    }
+
+    #[test]
+    fn findings_with_the_same_title_share_a_rule() {
+        let (rules, results) = sarif_rules_and_results(&[
+            finding("Unchecked unwrap", "high", "additions", 10),
+            finding("unchecked UNWRAP!", "medium", "additions", 20),
+            finding("Missing bounds check", "low", "deletions", 5),
+        ]);
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "rovex/unchecked-unwrap");
+        assert_eq!(results[1]["ruleIndex"], 0);
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[1]["level"], "warning");
+        assert_eq!(results[2]["level"], "note");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            10
+        );
+        assert!(results[2]["locations"][0]["physicalLocation"]
+            .get("region")
+            .is_none());
+        assert_eq!(
+            results[0]["partialFingerprints"]["rovexFingerprint/v1"],
+            "0123456789abcdef"
+        );
+    }
+}
@@ -0,0 +1,446 @@
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, State};
+
+use super::super::common::{as_non_empty_trimmed, parse_bool_i64};
+use super::super::threads::{find_or_create_thread, load_thread_by_id};
+use super::super::workspace_git::{
+    compare_workspace_diff, current_workspace_branch, fetch_remote_branch_head,
+    verify_remote_branch,
+};
+use super::run_queue::start_ai_review_run;
+use crate::backend::{
+    AppState, CompareWorkspaceDiffInput, DeleteReviewScheduleInput, DeleteReviewScheduleResult,
+    ListReviewSchedulesInput, ListReviewSchedulesResult, ReviewSchedule, SaveReviewScheduleInput,
+    StartAiReviewRunInput, WorkspaceDiffTarget,
+};
+
+/// Thread that collects scheduled runs of a workspace when a schedule names none.
+const SCHEDULED_THREAD_TITLE: &str = "Scheduled reviews";
+const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+/// Shortest interval a schedule may use; every check fetches the remote.
+const MIN_SCHEDULE_INTERVAL_MINUTES: i64 = 5;
+
+const SCHEDULE_COLUMNS: &str = "s.id, s.workspace, s.thread_id, s.base_ref, s.branch,
+       s.interval_minutes, s.on_new_commits, s.prompt, s.enabled, s.last_seen_head,
+       s.last_run_id, r.status, s.last_run_at, s.last_error, s.created_at,
+       s.interval_minutes IS NOT NULL AND (s.last_run_at IS NULL
+         OR datetime(s.last_run_at, '+' || s.interval_minutes || ' minutes') <= CURRENT_TIMESTAMP),
+       s.remote";
+
+fn parse_interval_minutes(value: Option<i64>) -> Result<Option<i64>, String> {
+    match value {
+        Some(minutes) if minutes < MIN_SCHEDULE_INTERVAL_MINUTES => Err(format!(
+            "Schedule interval must be at least {MIN_SCHEDULE_INTERVAL_MINUTES} minutes."
+        )),
+        value => Ok(value),
+    }
+}
+
+/// Schedules with their interval state: `true` when the interval has elapsed.
+async fn query_review_schedules(
+    state: &AppState,
+    workspace: Option<String>,
+    id: Option<i64>,
+    enabled_only: bool,
+) -> Result<Vec<(ReviewSchedule, bool)>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {SCHEDULE_COLUMNS}
+                 FROM review_schedules s
+                 LEFT JOIN ai_review_runs r ON r.run_id = s.last_run_id
+                 WHERE (?1 IS NULL OR s.workspace = ?1) AND (?2 IS NULL OR s.id = ?2)
+                   AND (?3 = 0 OR s.enabled = 1)
+                 ORDER BY s.workspace ASC, s.id ASC"
+            ),
+            (workspace, id, parse_bool_i64(enabled_only)),
+        )
+        .await
+        .map_err(|error| format!("Failed to query review schedules: {error}"))?;
+
+    let mut schedules = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read review schedule row: {error}"))?
+    {
+        let schedule = ReviewSchedule {
+            id: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse review schedule id: {error}"))?,
+            workspace: row
+                .get(1)
+                .map_err(|error| format!("Failed to parse review schedule workspace: {error}"))?,
+            thread_id: row
+                .get(2)
+                .map_err(|error| format!("Failed to parse review schedule thread id: {error}"))?,
+            base_ref: row.get(3).ok(),
+            remote: row.get(16).ok(),
+            branch: row.get(4).ok(),
+            interval_minutes: row.get(5).ok(),
+            on_new_commits: row.get::<i64>(6).unwrap_or(0) != 0,
+            prompt: row.get(7).ok(),
+            enabled: row.get::<i64>(8).unwrap_or(1) != 0,
+            last_seen_head: row.get(9).ok(),
+            last_run_id: row.get(10).ok(),
+            last_run_status: row.get(11).ok(),
+            last_run_at: row.get(12).ok(),
+            last_error: row.get(13).ok(),
+            created_at: row
+                .get(14)
+                .map_err(|error| format!("Failed to parse review schedule timestamp: {error}"))?,
+        };
+        let interval_due = row.get::<i64>(15).unwrap_or(0) != 0;
+        schedules.push((schedule, interval_due));
+    }
+    Ok(schedules)
+}
+
+async fn load_review_schedule(state: &AppState, id: i64) -> Result<ReviewSchedule, String> {
+    query_review_schedules(state, None, Some(id), false)
+        .await?
+        .into_iter()
+        .next()
+        .map(|(schedule, _)| schedule)
+        .ok_or_else(|| format!("Review schedule {id} does not exist."))
+}
+
+pub async fn save_review_schedule(
+    state: State<'_, AppState>,
+    input: SaveReviewScheduleInput,
+) -> Result<ReviewSchedule, String> {
+    let workspace = as_non_empty_trimmed(Some(&input.workspace))
+        .ok_or_else(|| "Workspace path must not be empty.".to_string())?;
+    let interval_minutes = parse_interval_minutes(input.interval_minutes)?;
+    let on_new_commits = input.on_new_commits.unwrap_or(false);
+    if interval_minutes.is_none() && !on_new_commits {
+        return Err("A schedule needs an interval, new-commit triggering, or both.".to_string());
+    }
+    let remote = as_non_empty_trimmed(input.remote.as_deref());
+    let mut branch = as_non_empty_trimmed(input.branch.as_deref());
+    if branch.is_none() && on_new_commits {
+        branch = Some(current_workspace_branch(&workspace)?.ok_or_else(|| {
+            "The workspace is on a detached HEAD. Choose a branch to watch for new commits."
+                .to_string()
+        })?);
+    }
+    if let Some(branch) = branch.clone() {
+        // Checks the branch name and that the remote has it before the scheduler relies on it.
+        let workspace = workspace.clone();
+        let remote = remote.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            verify_remote_branch(&workspace, remote.as_deref(), &branch)
+        })
+        .await
+        .map_err(|error| format!("Failed to check the schedule branch: {error}"))??;
+    }
+    let thread_id = match input.thread_id {
+        Some(thread_id) => load_thread_by_id(&state, thread_id).await?.id,
+        None => {
+            find_or_create_thread(&state, SCHEDULED_THREAD_TITLE, &workspace)
+                .await?
+                .id
+        }
+    };
+    let base_ref = as_non_empty_trimmed(input.base_ref.as_deref());
+    let prompt = as_non_empty_trimmed(input.prompt.as_deref());
+    let enabled = parse_bool_i64(input.enabled.unwrap_or(true));
+
+    let conn = state.connection()?;
+    let id = match input.id {
+        Some(id) => {
+            // A changed branch or remote starts over from its current head.
+            let updated = conn
+                .execute(
+                    "UPDATE review_schedules SET
+                       workspace = ?2,
+                       thread_id = ?3,
+                       base_ref = ?4,
+                       last_seen_head = CASE WHEN branch IS ?5 AND remote IS ?10
+                         THEN last_seen_head ELSE NULL END,
+                       branch = ?5,
+                       remote = ?10,
+                       interval_minutes = ?6,
+                       on_new_commits = ?7,
+                       prompt = ?8,
+                       enabled = ?9,
+                       last_error = NULL
+                     WHERE id = ?1",
+                    (
+                        id,
+                        workspace.clone(),
+                        thread_id,
+                        base_ref,
+                        branch,
+                        interval_minutes,
+                        parse_bool_i64(on_new_commits),
+                        prompt,
+                        enabled,
+                        remote,
+                    ),
+                )
+                .await
+                .map_err(|error| format!("Failed to update review schedule {id}: {error}"))?;
+            if updated == 0 {
+                return Err(format!("Review schedule {id} does not exist."));
+            }
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO review_schedules
+                   (workspace, thread_id, base_ref, branch, interval_minutes, on_new_commits,
+                    prompt, enabled, remote)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                (
+                    workspace.clone(),
+                    thread_id,
+                    base_ref,
+                    branch,
+                    interval_minutes,
+                    parse_bool_i64(on_new_commits),
+                    prompt,
+                    enabled,
+                    remote,
+                ),
+            )
+            .await
+            .map_err(|error| format!("Failed to save review schedule: {error}"))?;
+            let mut rows = conn
+                .query("SELECT last_insert_rowid()", ())
+                .await
+                .map_err(|error| format!("Failed to fetch new review schedule id: {error}"))?;
+            rows.next()
+                .await
+                .map_err(|error| format!("Failed to read review schedule id row: {error}"))?
+                .ok_or_else(|| {
+                    "Missing last_insert_rowid result after save_review_schedule.".to_string()
+                })?
+                .get(0)
+                .map_err(|error| format!("Failed to parse new review schedule id: {error}"))?
+        }
+    };
+    load_review_schedule(&state, id).await
+}
+
+pub async fn list_review_schedules(
+    state: State<'_, AppState>,
+    input: ListReviewSchedulesInput,
+) -> Result<ListReviewSchedulesResult, String> {
+    let workspace = as_non_empty_trimmed(input.workspace.as_deref());
+    let schedules = query_review_schedules(&state, workspace, None, false)
+        .await?
+        .into_iter()
+        .map(|(schedule, _)| schedule)
+        .collect();
+    Ok(ListReviewSchedulesResult { schedules })
+}
+
+pub async fn delete_review_schedule(
+    state: State<'_, AppState>,
+    input: DeleteReviewScheduleInput,
+) -> Result<DeleteReviewScheduleResult, String> {
+    let conn = state.connection()?;
+    let deleted = conn
+        .execute("DELETE FROM review_schedules WHERE id = ?1", [input.id])
+        .await
+        .map_err(|error| format!("Failed to delete review schedule {}: {error}", input.id))?;
+    Ok(DeleteReviewScheduleResult {
+        id: input.id,
+        deleted: deleted > 0,
+    })
+}
+
+#[derive(Debug, Default)]
+struct ScheduleCheck {
+    /// Head of the watched branch at this check.
+    seen_head: Option<String>,
+    run_id: Option<String>,
+    /// The schedule was due. Stamping the check as a run restarts its interval.
+    ran: bool,
+    error: Option<String>,
+}
+
+async fn record_schedule_check(
+    state: &AppState,
+    schedule_id: i64,
+    check: ScheduleCheck,
+) -> Result<(), String> {
+    let conn = state.connection()?;
+    conn.execute(
+        "UPDATE review_schedules SET
+           last_seen_head = COALESCE(?2, last_seen_head),
+           last_run_id = COALESCE(?3, last_run_id),
+           last_run_at = CASE WHEN ?4 = 1 THEN CURRENT_TIMESTAMP ELSE last_run_at END,
+           last_error = ?5
+         WHERE id = ?1",
+        (
+            schedule_id,
+            check.seen_head,
+            check.run_id,
+            parse_bool_i64(check.ran),
+            check.error,
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to update review schedule {schedule_id}: {error}"))?;
+    Ok(())
+}
+
+/// Starts a run for `schedule` when its interval elapsed or its branch moved.
+async fn run_schedule_if_due(
+    app: &AppHandle,
+    schedule: &ReviewSchedule,
+    interval_due: bool,
+) -> Result<ScheduleCheck, String> {
+    // The watched branch as `<remote>/<branch>`, with the commit it points at.
+    let watched = match schedule.branch.clone() {
+        Some(branch) => {
+            let workspace = schedule.workspace.clone();
+            let remote = schedule.remote.clone();
+            let fetched_branch = branch.clone();
+            // A full fetch can take a while, so it stays off the async workers.
+            let (remote, head) = tauri::async_runtime::spawn_blocking(move || {
+                fetch_remote_branch_head(&workspace, remote.as_deref(), &fetched_branch)
+            })
+            .await
+            .map_err(|error| format!("Failed to fetch the schedule branch: {error}"))??;
+            Some((format!("{remote}/{branch}"), head))
+        }
+        None => None,
+    };
+    let remote_head = watched.as_ref().map(|(_, head)| head.clone());
+    // The first check only records where the branch is, so creating a schedule does not
+    // review commits that were already there.
+    let branch_moved = schedule.on_new_commits
+        && schedule.last_seen_head.is_some()
+        && remote_head.is_some()
+        && remote_head != schedule.last_seen_head;
+    if !interval_due && !branch_moved {
+        return Ok(ScheduleCheck {
+            seen_head: remote_head,
+            ..ScheduleCheck::default()
+        });
+    }
+
+    let diff = compare_workspace_diff(CompareWorkspaceDiffInput {
+        workspace: schedule.workspace.clone(),
+        base_ref: schedule.base_ref.clone(),
+        // The branch was fetched above.
+        fetch_remote: Some(remote_head.is_none()),
+        include_paths: None,
+        exclude_paths: None,
+        diff_target: Some(WorkspaceDiffTarget::Branch),
+        include_untracked: None,
+        head_ref: watched
+            .as_ref()
+            .map(|(tracking_ref, _)| format!("refs/remotes/{tracking_ref}")),
+        include_submodule_diffs: None,
+        force: None,
+        remote: schedule.remote.clone(),
+    })
+    .await?;
+    if diff.diff.trim().is_empty() {
+        return Ok(ScheduleCheck {
+            seen_head: remote_head,
+            ran: true,
+            ..ScheduleCheck::default()
+        });
+    }
+
+    let state = app.state::<AppState>();
+    let scope = watched
+        .map(|(tracking_ref, _)| tracking_ref)
+        .unwrap_or_else(|| diff.head.get(..12).unwrap_or(&diff.head).to_string());
+    let result = start_ai_review_run(
+        app.clone(),
+        state,
+        StartAiReviewRunInput {
+            thread_id: schedule.thread_id,
+            workspace: diff.workspace,
+            base_ref: diff.base_ref,
+            merge_base: diff.merge_base,
+            head: diff.head,
+            files_changed: diff.files_changed,
+            insertions: diff.insertions,
+            deletions: diff.deletions,
+            diff: diff.diff,
+            prompt: schedule.prompt.clone(),
+            scope_label: Some(format!("Scheduled review of {scope}")),
+            max_parallel_chunks: None,
+            review_mode: None,
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: Some(WorkspaceDiffTarget::Branch),
+            idempotency_key: None,
+            profile_id: None,
+            min_confidence: None,
+        },
+    )
+    .await?;
+    Ok(ScheduleCheck {
+        seen_head: remote_head,
+        run_id: Some(result.run.run_id),
+        ran: true,
+        error: None,
+    })
+}
+
+async fn run_due_review_schedules(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let schedules = query_review_schedules(&state, None, None, true).await?;
+    for (schedule, interval_due) in schedules {
+        // Overlapping runs of one schedule would review the same commits twice.
+        if matches!(
+            schedule.last_run_status.as_deref(),
+            Some("queued" | "running")
+        ) {
+            continue;
+        }
+        let check = match run_schedule_if_due(app, &schedule, interval_due).await {
+            Ok(check) => check,
+            Err(error) => {
+                eprintln!(
+                    "[backend] Scheduled review {} of {} failed: {error}",
+                    schedule.id, schedule.workspace
+                );
+                // A failing interval schedule waits for its next interval instead of
+                // retrying every tick.
+                ScheduleCheck {
+                    ran: interval_due,
+                    error: Some(error),
+                    ..ScheduleCheck::default()
+                }
+            }
+        };
+        record_schedule_check(&state, schedule.id, check).await?;
+    }
+    Ok(())
+}
+
+/// Starts the background task that checks review schedules once a minute. Scheduled runs go
+/// through the regular run queue, so they emit the same progress events as manual runs.
+pub(crate) fn start_review_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SCHEDULER_TICK).await;
+            if let Err(error) = run_due_review_schedules(&app).await {
+                eprintln!("[backend] Failed to check review schedules: {error}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_interval_minutes;
+
+    #[test]
+    fn schedule_intervals_have_a_floor() {
+        assert_eq!(parse_interval_minutes(None), Ok(None));
+        assert_eq!(parse_interval_minutes(Some(60)), Ok(Some(60)));
+        assert!(parse_interval_minutes(Some(1)).is_err());
+    }
+}
@@ -1,13 +1,28 @@
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    collections::HashSet,
+    future::Future,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
 
 use super::super::common::{
-    parse_bool_i64, parse_json_vec_or_default, parse_limit, parse_optional_json_vec,
+    as_non_empty_trimmed, parse_bool_i64, parse_json_vec_or_default, parse_limit,
+    parse_optional_json_vec, DB_WRITE_RETRY_BASE_DELAY_MS, DB_WRITE_RETRY_MAX_ATTEMPTS,
     MAX_PROGRESS_EVENTS_PER_RUN,
 };
+use super::super::name_sort::compare_paths;
+use super::super::path_filter::PathFilter;
+use super::super::thread_settings::record_thread_run;
+use super::findings::{finding_fingerprint, FINDING_STATUS_OPEN};
+use super::model_stats::ModelStatsDelta;
+use super::verdict::{approved_verdict, run_verdict, VerdictThresholds, APPROVAL_PENDING};
 use crate::backend::{
-    AiReviewChunk, AiReviewFinding, AiReviewProgressEvent, AiReviewRun, AppState,
-    CreateInlineReviewCommentInput, GenerateAiReviewResult, InlineReviewComment,
-    ListInlineReviewCommentsInput, StartAiReviewRunInput,
+    AiReviewChunk, AiReviewFinding, AiReviewProgressEvent, AiReviewResourceUsage, AiReviewRun,
+    AiReviewSkippedChunk, AiReviewTokenUsage, AppState, CreateInlineReviewCommentInput,
+    FindingSearchHit, GenerateAiReviewResult, InlineReviewComment, ListAiReviewRunsInput,
+    ListAiReviewRunsResult, ListInlineReviewCommentsInput, ModelPerformanceStat,
+    ReviewFindingState, ReviewQueueEntry, ReviewUsageMonth, SearchFindingsInput,
+    StartAiReviewRunInput,
 };
 
 static INLINE_REVIEW_COMMENT_COUNTER: AtomicU64 = AtomicU64::new(1);
@@ -21,6 +36,42 @@ fn next_inline_review_comment_id() -> String {
     format!("comment-{millis}-{counter}")
 }
 
+pub(crate) fn is_transient_db_error(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    lower.contains("database is locked")
+        || lower.contains("busy")
+        || lower.contains("timed out")
+        || lower.contains("timeout")
+        || lower.contains("connection")
+        || lower.contains("stream")
+        || lower.contains("hrana")
+        || lower.contains("503")
+        || lower.contains("502")
+}
+
+/// Runs a database write, retrying transient failures (locked database, dropped remote
+/// connection) with exponential backoff. Other errors are returned immediately.
+pub(crate) async fn retry_db_write<T, F, Fut>(mut write: F) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let mut attempt = 1usize;
+    loop {
+        match write().await {
+            Ok(value) => return Ok(value),
+            Err(error)
+                if attempt < DB_WRITE_RETRY_MAX_ATTEMPTS && is_transient_db_error(&error) =>
+            {
+                let backoff_ms = DB_WRITE_RETRY_BASE_DELAY_MS * (1_u64 << (attempt - 1));
+                tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
 fn normalize_comment_side(raw: &str) -> Option<&'static str> {
     let value = raw.trim().to_lowercase();
     match value.as_str() {
@@ -40,6 +91,30 @@ fn parse_ai_review_run_from_row(row: &libsql::Row) -> Result<AiReviewRun, String
     let progress_events_json: Option<String> = row
         .get(24)
         .map_err(|error| format!("Failed to parse run progress_events_json: {error}"))?;
+    let resource_usage_json: Option<String> = row
+        .get(29)
+        .map_err(|error| format!("Failed to parse run resource_usage_json: {error}"))?;
+    let prompt_tokens: Option<i64> = row
+        .get(30)
+        .map_err(|error| format!("Failed to parse run prompt_tokens: {error}"))?;
+    let completion_tokens: Option<i64> = row
+        .get(31)
+        .map_err(|error| format!("Failed to parse run completion_tokens: {error}"))?;
+    let estimated_cost_usd: Option<f64> = row
+        .get(32)
+        .map_err(|error| format!("Failed to parse run estimated_cost_usd: {error}"))?;
+    let skipped_chunks_json: Option<String> = row
+        .get(36)
+        .map_err(|error| format!("Failed to parse run skipped_chunks_json: {error}"))?;
+    let suppressed_findings_json: Option<String> = row
+        .get(40)
+        .map_err(|error| format!("Failed to parse run suppressed_findings_json: {error}"))?;
+    let suggested_follow_ups_json: Option<String> = row
+        .get(45)
+        .map_err(|error| format!("Failed to parse run suggested_follow_ups_json: {error}"))?;
+    let review_mode: Option<String> = row
+        .get(33)
+        .map_err(|error| format!("Failed to parse run review_mode: {error}"))?;
     let diff_truncated: i64 = row
         .get(20)
         .map_err(|error| format!("Failed to parse run diff_truncated: {error}"))?;
@@ -61,6 +136,21 @@ fn parse_ai_review_run_from_row(row: &libsql::Row) -> Result<AiReviewRun, String
     let diff_chars_total: Option<i64> = row
         .get(19)
         .map_err(|error| format!("Failed to parse run diff_chars_total: {error}"))?;
+    let approval = row
+        .get::<Option<String>>(42)
+        .map_err(|error| format!("Failed to parse run approval: {error}"))?
+        .unwrap_or_else(|| APPROVAL_PENDING.to_string());
+    let approval_note: Option<String> = row
+        .get(43)
+        .map_err(|error| format!("Failed to parse run approval_note: {error}"))?;
+    let (verdict, verdict_reason) = approved_verdict(
+        row.get(38)
+            .map_err(|error| format!("Failed to parse run verdict: {error}"))?,
+        row.get(39)
+            .map_err(|error| format!("Failed to parse run verdict_reason: {error}"))?,
+        &approval,
+        approval_note.as_deref(),
+    );
 
     Ok(AiReviewRun {
         run_id: row
@@ -116,8 +206,44 @@ fn parse_ai_review_run_from_row(row: &libsql::Row) -> Result<AiReviewRun, String
             .get(21)
             .map_err(|error| format!("Failed to parse run error: {error}"))?,
         chunks: parse_optional_json_vec(chunks_json),
+        skipped_chunks: parse_optional_json_vec(skipped_chunks_json),
         findings: parse_optional_json_vec(findings_json),
+        suppressed_findings: parse_optional_json_vec(suppressed_findings_json),
+        finding_groups: Vec::new(),
+        min_confidence: row
+            .get(41)
+            .map_err(|error| format!("Failed to parse run min_confidence: {error}"))?,
+        suggested_follow_ups: parse_optional_json_vec(suggested_follow_ups_json),
         progress_events: parse_optional_json_vec(progress_events_json),
+        resource_usage: resource_usage_json
+            .and_then(|value| serde_json::from_str::<AiReviewResourceUsage>(&value).ok()),
+        token_usage: prompt_tokens.map(|prompt_tokens| {
+            let prompt_tokens = prompt_tokens.max(0) as u64;
+            let completion_tokens = completion_tokens.unwrap_or(0).max(0) as u64;
+            AiReviewTokenUsage {
+                prompt_tokens,
+                completion_tokens,
+                total_tokens: prompt_tokens.saturating_add(completion_tokens),
+                estimated_cost_usd,
+            }
+        }),
+        review_mode: review_mode.unwrap_or_else(|| "full".to_string()),
+        incremental_base_head: row
+            .get(34)
+            .map_err(|error| format!("Failed to parse run incremental_base_head: {error}"))?,
+        partial_review: row
+            .get(35)
+            .map_err(|error| format!("Failed to parse run partial_review: {error}"))?,
+        profile_id: row
+            .get(37)
+            .map_err(|error| format!("Failed to parse run profile_id: {error}"))?,
+        verdict,
+        verdict_reason,
+        approval,
+        approval_note,
+        approval_updated_at: row
+            .get(44)
+            .map_err(|error| format!("Failed to parse run approval_updated_at: {error}"))?,
         created_at: row
             .get(25)
             .map_err(|error| format!("Failed to parse run created_at: {error}"))?,
@@ -189,15 +315,20 @@ pub(crate) async fn insert_ai_review_run(
     input: &StartAiReviewRunInput,
     reviewer_goal: &str,
     total_chunks: usize,
+    skipped_chunks: &[AiReviewSkippedChunk],
+    incremental_base_head: Option<&str>,
 ) -> Result<(), String> {
+    let skipped_chunks_json = serde_json::to_string(skipped_chunks)
+        .map_err(|error| format!("Failed to serialize skipped chunks: {error}"))?;
     let conn = state.connection()?;
     conn.execute(
         "INSERT INTO ai_review_runs (
             run_id, thread_id, workspace, base_ref, merge_base, head, files_changed, insertions, deletions,
             prompt, scope_label, status, total_chunks, completed_chunks, failed_chunks, finding_count,
-            diff_chars_total
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 'queued', ?12, 0, 0, 0, ?13)",
-        (
+            diff_chars_total, review_mode, incremental_base_head, idempotency_key, skipped_chunks_json,
+            profile_id
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 'queued', ?12, 0, 0, 0, ?13, ?14, ?15, ?16, ?17, ?18)",
+        libsql::params![
             run_id.to_string(),
             input.thread_id,
             input.workspace.trim().to_string(),
@@ -211,11 +342,82 @@ pub(crate) async fn insert_ai_review_run(
             input.scope_label.clone(),
             i64::try_from(total_chunks).unwrap_or(i64::MAX),
             i64::try_from(input.diff.chars().count()).unwrap_or(i64::MAX),
-        ),
+            if incremental_base_head.is_some() {
+                "incremental"
+            } else {
+                "full"
+            },
+            incremental_base_head.map(ToOwned::to_owned),
+            input.idempotency_key.clone(),
+            skipped_chunks_json,
+            input.profile_id,
+        ],
     )
     .await
     .map_err(|error| format!("Failed to insert AI review run: {error}"))?;
-    Ok(())
+    record_thread_run(state, run_id, input).await
+}
+
+/// The run started in `thread_id` with the client-supplied idempotency `key`, if any.
+pub(crate) async fn load_ai_review_run_by_idempotency_key(
+    state: &AppState,
+    thread_id: i64,
+    key: &str,
+) -> Result<Option<AiReviewRun>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT run_id FROM ai_review_runs
+             WHERE thread_id = ?1 AND idempotency_key = ?2
+             LIMIT 1",
+            (thread_id, key.to_string()),
+        )
+        .await
+        .map_err(|error| format!("Failed to look up AI review run by idempotency key: {error}"))?;
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read AI review run row: {error}"))?
+    else {
+        return Ok(None);
+    };
+    let run_id: String = row
+        .get(0)
+        .map_err(|error| format!("Failed to parse AI review run id: {error}"))?;
+    load_ai_review_run_by_id(state, &run_id).await.map(Some)
+}
+
+/// Returns the head of the most recent finished review of `workspace` in the thread.
+pub(crate) async fn load_last_reviewed_head(
+    state: &AppState,
+    thread_id: i64,
+    workspace: &str,
+) -> Result<Option<String>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT head
+             FROM ai_review_runs
+             WHERE thread_id = ?1
+               AND workspace = ?2
+               AND status IN ('completed', 'completed_with_errors')
+             ORDER BY created_at DESC
+             LIMIT 1",
+            (thread_id, workspace.to_string()),
+        )
+        .await
+        .map_err(|error| format!("Failed to load last reviewed head: {error}"))?;
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read last reviewed head: {error}"))?
+    else {
+        return Ok(None);
+    };
+    let head: String = row
+        .get(0)
+        .map_err(|error| format!("Failed to parse last reviewed head: {error}"))?;
+    Ok(Some(head))
 }
 
 pub(crate) async fn load_ai_review_run_by_id(
@@ -230,7 +432,11 @@ pub(crate) async fn load_ai_review_run_by_id(
               prompt, scope_label, status, total_chunks, completed_chunks, failed_chunks, finding_count,
               model, review, diff_chars_used, diff_chars_total, diff_truncated, error,
               chunks_json, findings_json, progress_events_json,
-              created_at, started_at, ended_at, canceled_at
+              created_at, started_at, ended_at, canceled_at, resource_usage_json,
+              prompt_tokens, completion_tokens, estimated_cost_usd, review_mode,
+              incremental_base_head, partial_review, skipped_chunks_json, profile_id,
+              verdict, verdict_reason, suppressed_findings_json, min_confidence,
+              approval, approval_note, approval_updated_at, suggested_follow_ups_json
              FROM ai_review_runs
              WHERE run_id = ?1
              LIMIT 1",
@@ -247,60 +453,436 @@ pub(crate) async fn load_ai_review_run_by_id(
         return Err(format!("AI review run '{run_id}' was not found."));
     };
 
-    parse_ai_review_run_from_row(&row)
+    let mut run = parse_ai_review_run_from_row(&row)?;
+    // Finished runs read findings from their normalized rows; `findings_json` only tracks
+    // findings streamed in while a run is in progress.
+    if run.ended_at.is_some() {
+        let findings = load_ai_review_run_findings(state, run_id).await?;
+        if !findings.is_empty() {
+            run.findings = findings;
+        }
+    }
+    Ok(run)
 }
 
+/// Pages through runs, newest first. `beforeRunId` continues with older runs and
+/// `afterRunId` returns the newer runs closest to the cursor. Runs are ordered by creation
+/// time with the rowid breaking ties, so cursors stay stable when runs share a timestamp.
 pub(crate) async fn list_ai_review_runs_internal(
+    state: &AppState,
+    input: &ListAiReviewRunsInput,
+) -> Result<ListAiReviewRunsResult, String> {
+    let before_run_id = as_non_empty_trimmed(input.before_run_id.as_deref());
+    let after_run_id = as_non_empty_trimmed(input.after_run_id.as_deref());
+    if before_run_id.is_some() && after_run_id.is_some() {
+        return Err("Pass either beforeRunId or afterRunId, not both.".to_string());
+    }
+    let conn = state.connection()?;
+    for run_id in before_run_id.iter().chain(after_run_id.iter()) {
+        let mut rows = conn
+            .query(
+                "SELECT 1 FROM ai_review_runs WHERE run_id = ?1",
+                [run_id.clone()],
+            )
+            .await
+            .map_err(|error| format!("Failed to look up AI review run {run_id}: {error}"))?;
+        if rows
+            .next()
+            .await
+            .map_err(|error| format!("Failed to read AI review run {run_id}: {error}"))?
+            .is_none()
+        {
+            return Err(format!("AI review run {run_id} was not found."));
+        }
+    }
+
+    let requested_limit = parse_limit(input.limit);
+    let is_forward = after_run_id.is_some();
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT
+                  run_id, thread_id, workspace, base_ref, merge_base, head, files_changed, insertions, deletions,
+                  prompt, scope_label, status, total_chunks, completed_chunks, failed_chunks, finding_count,
+                  model, review, diff_chars_used, diff_chars_total, diff_truncated, error,
+                  chunks_json, findings_json, progress_events_json,
+                  created_at, started_at, ended_at, canceled_at, resource_usage_json,
+                  prompt_tokens, completion_tokens, estimated_cost_usd, review_mode,
+                  incremental_base_head, partial_review, skipped_chunks_json, profile_id,
+                  verdict, verdict_reason, suppressed_findings_json, min_confidence,
+                  approval, approval_note, approval_updated_at, suggested_follow_ups_json
+                 FROM ai_review_runs
+                 WHERE (?1 IS NULL OR thread_id = ?1)
+                   AND (?3 IS NULL OR (created_at, rowid) <
+                     (SELECT created_at, rowid FROM ai_review_runs WHERE run_id = ?3))
+                   AND (?4 IS NULL OR (created_at, rowid) >
+                     (SELECT created_at, rowid FROM ai_review_runs WHERE run_id = ?4))
+                 ORDER BY created_at {order}, rowid {order}
+                 LIMIT ?2",
+                order = if is_forward { "ASC" } else { "DESC" }
+            ),
+            (
+                input.thread_id,
+                requested_limit + 1,
+                before_run_id,
+                after_run_id,
+            ),
+        )
+        .await
+        .map_err(|error| format!("Failed to list AI review runs: {error}"))?;
+
+    let mut runs = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read AI review run rows: {error}"))?
+    {
+        runs.push(parse_ai_review_run_from_row(&row)?);
+    }
+    let has_more = runs.len() as i64 > requested_limit;
+    runs.truncate(requested_limit as usize);
+    if is_forward {
+        runs.reverse();
+    }
+
+    let mut rows = conn
+        .query(
+            "SELECT COUNT(*) FROM ai_review_runs WHERE (?1 IS NULL OR thread_id = ?1)",
+            [input.thread_id],
+        )
+        .await
+        .map_err(|error| format!("Failed to count AI review runs: {error}"))?;
+    let total_count = match rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read AI review run count: {error}"))?
+    {
+        Some(row) => row
+            .get(0)
+            .map_err(|error| format!("Failed to parse AI review run count: {error}"))?,
+        None => 0,
+    };
+
+    Ok(ListAiReviewRunsResult {
+        runs,
+        total_count,
+        has_more,
+    })
+}
+
+pub(crate) async fn load_review_usage_summary(
     state: &AppState,
     thread_id: Option<i64>,
     limit: Option<u32>,
-) -> Result<Vec<AiReviewRun>, String> {
+) -> Result<Vec<ReviewUsageMonth>, String> {
     let conn = state.connection()?;
     let requested_limit = parse_limit(limit);
-    let mut rows = if let Some(thread_id) = thread_id {
-        conn.query(
+    let mut rows = conn
+        .query(
             "SELECT
-              run_id, thread_id, workspace, base_ref, merge_base, head, files_changed, insertions, deletions,
-              prompt, scope_label, status, total_chunks, completed_chunks, failed_chunks, finding_count,
-              model, review, diff_chars_used, diff_chars_total, diff_truncated, error,
-              chunks_json, findings_json, progress_events_json,
-              created_at, started_at, ended_at, canceled_at
+              strftime('%Y-%m', created_at) AS month,
+              COUNT(*),
+              COALESCE(SUM(prompt_tokens), 0),
+              COALESCE(SUM(completion_tokens), 0),
+              SUM(estimated_cost_usd)
              FROM ai_review_runs
-             WHERE thread_id = ?1
-             ORDER BY created_at DESC
+             WHERE prompt_tokens IS NOT NULL
+               AND (?1 IS NULL OR thread_id = ?1)
+             GROUP BY month
+             ORDER BY month DESC
              LIMIT ?2",
             (thread_id, requested_limit),
         )
         .await
-        .map_err(|error| format!("Failed to list AI review runs: {error}"))?
-    } else {
-        conn.query(
+        .map_err(|error| format!("Failed to query review usage summary: {error}"))?;
+
+    let mut months = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read review usage summary rows: {error}"))?
+    {
+        let run_count: i64 = row
+            .get(1)
+            .map_err(|error| format!("Failed to parse usage run count: {error}"))?;
+        let prompt_tokens: i64 = row
+            .get(2)
+            .map_err(|error| format!("Failed to parse usage prompt tokens: {error}"))?;
+        let completion_tokens: i64 = row
+            .get(3)
+            .map_err(|error| format!("Failed to parse usage completion tokens: {error}"))?;
+        let prompt_tokens = prompt_tokens.max(0) as u64;
+        let completion_tokens = completion_tokens.max(0) as u64;
+        months.push(ReviewUsageMonth {
+            month: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse usage month: {error}"))?,
+            run_count: run_count.max(0) as u64,
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens.saturating_add(completion_tokens),
+            estimated_cost_usd: row
+                .get(4)
+                .map_err(|error| format!("Failed to parse usage cost: {error}"))?,
+        });
+    }
+
+    Ok(months)
+}
+
+pub(crate) async fn record_model_performance_stats(
+    state: &AppState,
+    deltas: Vec<((String, String, String), ModelStatsDelta)>,
+) -> Result<(), String> {
+    if deltas.is_empty() {
+        return Ok(());
+    }
+    let conn = state.connection()?;
+    for ((transport, model, prompt_profile), delta) in deltas {
+        conn.execute(
+            "INSERT INTO model_performance_stats (
+              transport, model, prompt_profile, request_count, failure_count,
+              prompt_tokens, completion_tokens, estimated_cost_usd, updated_at
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, CURRENT_TIMESTAMP)
+             ON CONFLICT(transport, model, prompt_profile)
+             DO UPDATE SET
+               request_count = request_count + excluded.request_count,
+               failure_count = failure_count + excluded.failure_count,
+               prompt_tokens = prompt_tokens + excluded.prompt_tokens,
+               completion_tokens = completion_tokens + excluded.completion_tokens,
+               estimated_cost_usd = CASE
+                 WHEN excluded.estimated_cost_usd IS NULL THEN estimated_cost_usd
+                 ELSE COALESCE(estimated_cost_usd, 0) + excluded.estimated_cost_usd
+               END,
+               updated_at = CURRENT_TIMESTAMP",
+            (
+                transport.clone(),
+                model.clone(),
+                prompt_profile.clone(),
+                delta.request_count as i64,
+                delta.failure_count as i64,
+                delta.prompt_tokens.min(i64::MAX as u64) as i64,
+                delta.completion_tokens.min(i64::MAX as u64) as i64,
+                delta.estimated_cost_usd,
+            ),
+        )
+        .await
+        .map_err(|error| {
+            format!(
+                "Failed to record model stats for {transport}/{model}/{prompt_profile}: {error}"
+            )
+        })?;
+    }
+    Ok(())
+}
+
+pub(crate) async fn load_model_performance_stats(
+    state: &AppState,
+    transport: Option<&str>,
+    prompt_profile: Option<&str>,
+) -> Result<Vec<ModelPerformanceStat>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
             "SELECT
-              run_id, thread_id, workspace, base_ref, merge_base, head, files_changed, insertions, deletions,
-              prompt, scope_label, status, total_chunks, completed_chunks, failed_chunks, finding_count,
-              model, review, diff_chars_used, diff_chars_total, diff_truncated, error,
-              chunks_json, findings_json, progress_events_json,
-              created_at, started_at, ended_at, canceled_at
+              transport,
+              model,
+              prompt_profile,
+              request_count,
+              failure_count,
+              prompt_tokens,
+              completion_tokens,
+              estimated_cost_usd,
+              updated_at
+             FROM model_performance_stats
+             WHERE (?1 IS NULL OR transport = ?1)
+               AND (?2 IS NULL OR prompt_profile = ?2)
+             ORDER BY transport ASC, prompt_profile ASC, request_count DESC, model ASC",
+            (
+                transport.map(ToOwned::to_owned),
+                prompt_profile.map(ToOwned::to_owned),
+            ),
+        )
+        .await
+        .map_err(|error| format!("Failed to query model performance stats: {error}"))?;
+
+    let mut stats = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read model performance stats rows: {error}"))?
+    {
+        let read_count = |index: i32, label: &str| -> Result<u64, String> {
+            row.get::<i64>(index)
+                .map(|value| value.max(0) as u64)
+                .map_err(|error| format!("Failed to parse model stats {label}: {error}"))
+        };
+        let request_count = read_count(3, "request count")?;
+        let failure_count = read_count(4, "failure count")?;
+        let prompt_tokens = read_count(5, "prompt tokens")?;
+        let completion_tokens = read_count(6, "completion tokens")?;
+        let per_request = |value: u64| {
+            if request_count == 0 {
+                0.0
+            } else {
+                value as f64 / request_count as f64
+            }
+        };
+        stats.push(ModelPerformanceStat {
+            transport: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse model stats transport: {error}"))?,
+            model: row
+                .get(1)
+                .map_err(|error| format!("Failed to parse model stats model: {error}"))?,
+            prompt_profile: row
+                .get(2)
+                .map_err(|error| format!("Failed to parse model stats prompt profile: {error}"))?,
+            request_count,
+            failure_count,
+            failure_rate: per_request(failure_count),
+            prompt_tokens,
+            completion_tokens,
+            avg_prompt_tokens: per_request(prompt_tokens),
+            avg_completion_tokens: per_request(completion_tokens),
+            estimated_cost_usd: row
+                .get(7)
+                .map_err(|error| format!("Failed to parse model stats cost: {error}"))?,
+            updated_at: row
+                .get(8)
+                .map_err(|error| format!("Failed to parse model stats updated_at: {error}"))?,
+        });
+    }
+
+    Ok(stats)
+}
+
+pub(crate) async fn list_unfinished_ai_review_run_ids(
+    state: &AppState,
+) -> Result<Vec<String>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT run_id
              FROM ai_review_runs
-             ORDER BY created_at DESC
-             LIMIT ?1",
-            [requested_limit],
+             WHERE status IN ('queued', 'running')
+             ORDER BY created_at ASC",
+            (),
         )
         .await
-        .map_err(|error| format!("Failed to list AI review runs: {error}"))?
-    };
+        .map_err(|error| format!("Failed to query unfinished AI review runs: {error}"))?;
+
+    let mut run_ids = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read unfinished AI review run rows: {error}"))?
+    {
+        run_ids.push(
+            row.get(0)
+                .map_err(|error| format!("Failed to parse unfinished run id: {error}"))?,
+        );
+    }
+    Ok(run_ids)
+}
+
+/// Queued and running runs with their chunk progress, oldest first.
+pub(crate) async fn list_unfinished_ai_review_runs(
+    state: &AppState,
+) -> Result<Vec<ReviewQueueEntry>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT run_id, thread_id, workspace, status, total_chunks, completed_chunks,
+              failed_chunks, created_at, started_at
+             FROM ai_review_runs
+             WHERE status IN ('queued', 'running')
+             ORDER BY created_at ASC",
+            (),
+        )
+        .await
+        .map_err(|error| format!("Failed to query unfinished AI review runs: {error}"))?;
 
     let mut runs = Vec::new();
     while let Some(row) = rows
         .next()
         .await
-        .map_err(|error| format!("Failed to read AI review run rows: {error}"))?
+        .map_err(|error| format!("Failed to read unfinished AI review run rows: {error}"))?
     {
-        runs.push(parse_ai_review_run_from_row(&row)?);
+        let chunk_count = |index: i32, label: &str| -> Result<usize, String> {
+            let value: i64 = row
+                .get(index)
+                .map_err(|error| format!("Failed to parse queued run {label}: {error}"))?;
+            Ok(usize::try_from(value).unwrap_or_default())
+        };
+        runs.push(ReviewQueueEntry {
+            run_id: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse queued run id: {error}"))?,
+            thread_id: row
+                .get(1)
+                .map_err(|error| format!("Failed to parse queued run thread id: {error}"))?,
+            workspace: row
+                .get(2)
+                .map_err(|error| format!("Failed to parse queued run workspace: {error}"))?,
+            status: row
+                .get(3)
+                .map_err(|error| format!("Failed to parse queued run status: {error}"))?,
+            queue_position: None,
+            has_slot: false,
+            total_chunks: chunk_count(4, "total chunks")?,
+            completed_chunks: chunk_count(5, "completed chunks")?,
+            failed_chunks: chunk_count(6, "failed chunks")?,
+            created_at: row
+                .get(7)
+                .map_err(|error| format!("Failed to parse queued run created_at: {error}"))?,
+            started_at: row
+                .get(8)
+                .map_err(|error| format!("Failed to parse queued run started_at: {error}"))?,
+            estimated_start_ms: None,
+        });
     }
     Ok(runs)
 }
 
+/// Average wall-clock milliseconds the most recent finished runs spent per reviewed chunk.
+pub(crate) async fn load_average_chunk_duration_ms(
+    state: &AppState,
+    sample_runs: i64,
+) -> Result<Option<f64>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT SUM((julianday(ended_at) - julianday(started_at)) * 86400000.0)
+                    / SUM(completed_chunks)
+             FROM (
+               SELECT started_at, ended_at, completed_chunks
+               FROM ai_review_runs
+               WHERE status IN ('completed', 'completed_with_errors')
+                 AND started_at IS NOT NULL
+                 AND ended_at IS NOT NULL
+                 AND completed_chunks > 0
+               ORDER BY ended_at DESC
+               LIMIT ?1
+             )",
+            [sample_runs],
+        )
+        .await
+        .map_err(|error| format!("Failed to query review chunk throughput: {error}"))?;
+
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read review chunk throughput: {error}"))?
+    else {
+        return Ok(None);
+    };
+    let average: Option<f64> = row
+        .get(0)
+        .map_err(|error| format!("Failed to parse review chunk throughput: {error}"))?;
+    Ok(average.filter(|value| value.is_finite() && *value >= 0.0))
+}
+
 pub(crate) async fn set_ai_review_run_status(
     state: &AppState,
     run_id: &str,
@@ -371,8 +953,7 @@ pub(crate) async fn append_ai_review_run_progress(
             chunks.push(chunk.clone());
         }
         chunks.sort_by(|left, right| {
-            left.file_path
-                .cmp(&right.file_path)
+            compare_paths(&left.file_path, &right.file_path)
                 .then(left.chunk_index.cmp(&right.chunk_index))
         });
     }
@@ -424,6 +1005,92 @@ pub(crate) async fn append_ai_review_run_progress(
     Ok(())
 }
 
+pub(crate) async fn set_ai_review_run_partial_review(
+    state: &AppState,
+    run_id: &str,
+    partial_review: &str,
+) -> Result<(), String> {
+    let conn = state.connection()?;
+    conn.execute(
+        "UPDATE ai_review_runs SET partial_review = ?2 WHERE run_id = ?1",
+        (run_id.to_string(), partial_review.to_string()),
+    )
+    .await
+    .map_err(|error| format!("Failed to persist partial review: {error}"))?;
+    Ok(())
+}
+
+/// Files a run will not review, as recorded when it was queued or last updated.
+pub(crate) async fn load_ai_review_run_skipped_chunks(
+    state: &AppState,
+    run_id: &str,
+) -> Result<Vec<AiReviewSkippedChunk>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT skipped_chunks_json FROM ai_review_runs WHERE run_id = ?1",
+            [run_id.to_string()],
+        )
+        .await
+        .map_err(|error| format!("Failed to load skipped chunks of run {run_id}: {error}"))?;
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read skipped chunks of run {run_id}: {error}"))?
+    else {
+        return Ok(Vec::new());
+    };
+    let skipped_chunks_json: Option<String> = row
+        .get(0)
+        .map_err(|error| format!("Failed to parse run skipped_chunks_json: {error}"))?;
+    Ok(parse_optional_json_vec(skipped_chunks_json))
+}
+
+pub(crate) async fn set_ai_review_run_skipped_chunks(
+    state: &AppState,
+    run_id: &str,
+    skipped_chunks: &[AiReviewSkippedChunk],
+) -> Result<(), String> {
+    let skipped_chunks_json = serde_json::to_string(skipped_chunks)
+        .map_err(|error| format!("Failed to serialize skipped chunks: {error}"))?;
+    let conn = state.connection()?;
+    conn.execute(
+        "UPDATE ai_review_runs SET skipped_chunks_json = ?2 WHERE run_id = ?1",
+        (run_id.to_string(), skipped_chunks_json),
+    )
+    .await
+    .map_err(|error| format!("Failed to persist skipped chunks: {error}"))?;
+    Ok(())
+}
+
+/// Records a reviewer's decision on a finished run. Queued and running runs cannot be ruled on
+/// yet.
+pub(crate) async fn set_ai_review_run_approval(
+    state: &AppState,
+    run_id: &str,
+    approval: &str,
+    note: Option<String>,
+) -> Result<(), String> {
+    let conn = state.connection()?;
+    let updated = conn
+        .execute(
+            "UPDATE ai_review_runs
+             SET approval = ?2, approval_note = ?3, approval_updated_at = CURRENT_TIMESTAMP
+             WHERE run_id = ?1 AND status NOT IN ('queued', 'running')",
+            (run_id.to_string(), approval.to_string(), note),
+        )
+        .await
+        .map_err(|error| format!("Failed to update approval of run {run_id}: {error}"))?;
+    if updated == 0 {
+        // Tells a missing run apart from one that has not finished.
+        load_ai_review_run_by_id(state, run_id).await?;
+        return Err(format!(
+            "AI review run '{run_id}' has not finished, so it cannot be approved yet."
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) async fn finalize_ai_review_run(
     state: &AppState,
     run_id: &str,
@@ -431,9 +1098,20 @@ pub(crate) async fn finalize_ai_review_run(
     status: &str,
     error: Option<&str>,
 ) -> Result<(), String> {
+    let verdict = run_verdict(&result.findings, VerdictThresholds::from_settings());
+    let suppressed_findings_json =
+        serde_json::to_string(&result.suppressed_findings).map_err(|serialize_error| {
+            format!("Failed to serialize suppressed findings: {serialize_error}")
+        })?;
+    // The run and its findings are saved together, so a completed run never lacks findings.
     let conn = state.connection()?;
-    conn.execute(
-        "UPDATE ai_review_runs
+    let transaction = conn
+        .transaction()
+        .await
+        .map_err(|error| format!("Failed to start finalizing AI review run: {error}"))?;
+    transaction
+        .execute(
+            "UPDATE ai_review_runs
          SET status = ?2,
              model = ?3,
              review = ?4,
@@ -446,30 +1124,718 @@ pub(crate) async fn finalize_ai_review_run(
              completed_chunks = ?11,
              total_chunks = ?12,
              finding_count = ?13,
+             skipped_chunks_json = ?14,
+             verdict = ?15,
+             verdict_reason = ?16,
+             suppressed_findings_json = ?17,
+             min_confidence = ?18,
+             suggested_follow_ups_json = ?19,
+             partial_review = NULL,
              ended_at = CURRENT_TIMESTAMP
+         WHERE run_id = ?1",
+            libsql::params![
+                run_id.to_string(),
+                status.to_string(),
+                Some(result.model.clone()),
+                Some(result.review.clone()),
+                i64::try_from(result.diff_chars_used).unwrap_or(i64::MAX),
+                i64::try_from(result.diff_chars_total).unwrap_or(i64::MAX),
+                parse_bool_i64(result.diff_truncated),
+                error.map(ToOwned::to_owned),
+                serde_json::to_string(&result.chunks).map_err(|serialize_error| {
+                    format!("Failed to serialize final chunks: {serialize_error}")
+                })?,
+                serde_json::to_string(&result.findings).map_err(|serialize_error| {
+                    format!("Failed to serialize final findings: {serialize_error}")
+                })?,
+                i64::try_from(result.chunks.len()).unwrap_or(i64::MAX),
+                i64::try_from(result.chunks.len()).unwrap_or(i64::MAX),
+                i64::try_from(result.findings.len()).unwrap_or(i64::MAX),
+                serde_json::to_string(&result.skipped_chunks).map_err(|serialize_error| {
+                    format!("Failed to serialize skipped chunks: {serialize_error}")
+                })?,
+                verdict.verdict,
+                verdict.reason,
+                suppressed_findings_json,
+                result.min_confidence,
+                serde_json::to_string(&result.suggested_follow_ups).map_err(|serialize_error| {
+                    format!("Failed to serialize suggested follow-ups: {serialize_error}")
+                })?,
+            ],
+        )
+        .await
+        .map_err(|error| format!("Failed to finalize AI review run: {error}"))?;
+    save_ai_review_run_usage(&transaction, run_id, result).await?;
+    save_ai_review_run_findings(&transaction, run_id, &result.findings).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(|error| format!("Failed to commit finalized AI review run: {error}"))
+}
+
+/// Columns selected by [`parse_finding_from_row`], in order.
+const FINDING_COLUMNS: &str = "finding_id, file_path, chunk_id, chunk_index, hunk_header, side,
+  line_number, title, body, severity, confidence, category, fingerprint, status, code_hash,
+  recurring, suggested_patch, language";
+
+fn parse_finding_from_row(row: &libsql::Row, offset: i32) -> Result<AiReviewFinding, String> {
+    let column = |index: i32| offset + index;
+    let chunk_index: i64 = row
+        .get(column(3))
+        .map_err(|error| format!("Failed to parse finding chunk index: {error}"))?;
+    Ok(AiReviewFinding {
+        id: row
+            .get(column(0))
+            .map_err(|error| format!("Failed to parse finding id: {error}"))?,
+        file_path: row
+            .get(column(1))
+            .map_err(|error| format!("Failed to parse finding file path: {error}"))?,
+        chunk_id: row
+            .get(column(2))
+            .map_err(|error| format!("Failed to parse finding chunk id: {error}"))?,
+        chunk_index: usize::try_from(chunk_index).unwrap_or_default(),
+        hunk_header: row
+            .get(column(4))
+            .map_err(|error| format!("Failed to parse finding hunk header: {error}"))?,
+        side: row
+            .get(column(5))
+            .map_err(|error| format!("Failed to parse finding side: {error}"))?,
+        line_number: row
+            .get(column(6))
+            .map_err(|error| format!("Failed to parse finding line number: {error}"))?,
+        title: row
+            .get(column(7))
+            .map_err(|error| format!("Failed to parse finding title: {error}"))?,
+        body: row
+            .get(column(8))
+            .map_err(|error| format!("Failed to parse finding body: {error}"))?,
+        severity: row
+            .get(column(9))
+            .map_err(|error| format!("Failed to parse finding severity: {error}"))?,
+        confidence: row
+            .get(column(10))
+            .map_err(|error| format!("Failed to parse finding confidence: {error}"))?,
+        category: row
+            .get(column(11))
+            .map_err(|error| format!("Failed to parse finding category: {error}"))?,
+        fingerprint: row
+            .get(column(12))
+            .map_err(|error| format!("Failed to parse finding fingerprint: {error}"))?,
+        status: row
+            .get(column(13))
+            .map_err(|error| format!("Failed to parse finding status: {error}"))?,
+        code_hash: row
+            .get(column(14))
+            .map_err(|error| format!("Failed to parse finding code hash: {error}"))?,
+        recurring: row
+            .get::<Option<i64>>(column(15))
+            .map_err(|error| format!("Failed to parse finding recurring flag: {error}"))?
+            .map(|value| value != 0),
+        suggested_patch: row
+            .get(column(16))
+            .map_err(|error| format!("Failed to parse finding suggested patch: {error}"))?,
+        language: row
+            .get(column(17))
+            .map_err(|error| format!("Failed to parse finding language: {error}"))?,
+        blame: None,
+    })
+}
+
+/// Replaces the rows of `ai_review_findings` that belong to a run. Findings whose fingerprint
+/// was dismissed or fixed in the same workspace inherit that status.
+async fn save_ai_review_run_findings(
+    conn: &libsql::Connection,
+    run_id: &str,
+    findings: &[AiReviewFinding],
+) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM ai_review_findings WHERE run_id = ?1",
+        [run_id.to_string()],
+    )
+    .await
+    .map_err(|error| format!("Failed to clear findings for run {run_id}: {error}"))?;
+    for finding in findings {
+        let fingerprint = finding
+            .fingerprint
+            .clone()
+            .unwrap_or_else(|| finding_fingerprint(finding, None));
+        conn.execute(
+            &format!(
+                "INSERT OR REPLACE INTO ai_review_findings (
+                  run_id, finding_id, thread_id, workspace, fingerprint, status, category,
+                  severity, confidence, file_path, side, line_number, chunk_id, chunk_index,
+                  hunk_header, title, body, code_hash, recurring, suggested_patch, language,
+                  created_at
+                 )
+                 SELECT ?1, ?2, thread_id, workspace, ?3,
+                        COALESCE(
+                          (SELECT states.state FROM review_finding_states AS states
+                           WHERE states.workspace = runs.workspace
+                             AND states.fingerprint = ?3),
+                          '{FINDING_STATUS_OPEN}'
+                        ),
+                        ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                        ?18, created_at
+                 FROM ai_review_runs AS runs
+                 WHERE run_id = ?1"
+            ),
+            libsql::params![
+                run_id.to_string(),
+                finding.id.clone(),
+                fingerprint,
+                finding.category.clone(),
+                finding.severity.clone(),
+                finding.confidence,
+                finding.file_path.clone(),
+                finding.side.clone(),
+                finding.line_number,
+                finding.chunk_id.clone(),
+                i64::try_from(finding.chunk_index).unwrap_or(i64::MAX),
+                finding.hunk_header.clone(),
+                finding.title.clone(),
+                finding.body.clone(),
+                finding.code_hash.clone(),
+                finding.recurring.map(parse_bool_i64),
+                finding.suggested_patch.clone(),
+                finding.language.clone(),
+            ],
+        )
+        .await
+        .map_err(|error| format!("Failed to save finding {}: {error}", finding.id))?;
+    }
+    Ok(())
+}
+
+pub(crate) async fn load_ai_review_run_findings(
+    state: &AppState,
+    run_id: &str,
+) -> Result<Vec<AiReviewFinding>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {FINDING_COLUMNS}
+                 FROM ai_review_findings
+                 WHERE run_id = ?1
+                 ORDER BY file_path ASC, line_number ASC, finding_id ASC"
+            ),
+            [run_id.to_string()],
+        )
+        .await
+        .map_err(|error| format!("Failed to load findings for run {run_id}: {error}"))?;
+
+    let mut findings = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read finding rows: {error}"))?
+    {
+        findings.push(parse_finding_from_row(&row, 0)?);
+    }
+    Ok(findings)
+}
+
+/// Loads one finding together with the workspace and head commit of the run it came from.
+pub(crate) async fn load_ai_review_finding(
+    state: &AppState,
+    run_id: &str,
+    finding_id: &str,
+) -> Result<(String, Option<String>, AiReviewFinding), String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT workspace,
+                        (SELECT runs.head FROM ai_review_runs AS runs
+                         WHERE runs.run_id = ai_review_findings.run_id),
+                        {FINDING_COLUMNS}
+                 FROM ai_review_findings
+                 WHERE run_id = ?1 AND finding_id = ?2"
+            ),
+            (run_id.to_string(), finding_id.to_string()),
+        )
+        .await
+        .map_err(|error| format!("Failed to load finding {finding_id}: {error}"))?;
+    let row = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read finding {finding_id}: {error}"))?
+        .ok_or_else(|| format!("Finding '{finding_id}' was not found in run '{run_id}'."))?;
+    let workspace: String = row
+        .get(0)
+        .map_err(|error| format!("Failed to parse finding workspace: {error}"))?;
+    let head: Option<String> = row
+        .get(1)
+        .map_err(|error| format!("Failed to parse run head: {error}"))?;
+    Ok((workspace, head, parse_finding_from_row(&row, 2)?))
+}
+
+/// Findings of the latest `run_limit` finished runs in a thread, other than `exclude_run_id`.
+pub(crate) async fn load_recent_thread_findings(
+    state: &AppState,
+    thread_id: i64,
+    exclude_run_id: Option<&str>,
+    run_limit: i64,
+) -> Result<Vec<AiReviewFinding>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {FINDING_COLUMNS}
+                 FROM ai_review_findings
+                 WHERE run_id IN (
+                   SELECT run_id
+                   FROM ai_review_runs
+                   WHERE thread_id = ?1
+                     AND ended_at IS NOT NULL
+                     AND (?2 IS NULL OR run_id != ?2)
+                   ORDER BY ended_at DESC
+                   LIMIT ?3
+                 )"
+            ),
+            (thread_id, exclude_run_id.map(ToOwned::to_owned), run_limit),
+        )
+        .await
+        .map_err(|error| {
+            format!("Failed to load earlier findings for thread {thread_id}: {error}")
+        })?;
+
+    let mut findings = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read earlier finding rows: {error}"))?
+    {
+        findings.push(parse_finding_from_row(&row, 0)?);
+    }
+    Ok(findings)
+}
+
+/// Moves findings of runs finalized before `ai_review_findings` existed out of
+/// `findings_json`.
+pub(crate) async fn backfill_ai_review_findings(state: &AppState) -> Result<(), String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT run_id, findings_json
+             FROM ai_review_runs AS runs
+             WHERE finding_count > 0
+               AND NOT EXISTS (
+                 SELECT 1 FROM ai_review_findings AS findings
+                 WHERE findings.run_id = runs.run_id
+               )",
+            (),
+        )
+        .await
+        .map_err(|error| format!("Failed to query unindexed review findings: {error}"))?;
+
+    let mut pending = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read unindexed review findings: {error}"))?
+    {
+        let run_id: String = row
+            .get(0)
+            .map_err(|error| format!("Failed to parse unindexed run id: {error}"))?;
+        let findings_json: String = row
+            .get(1)
+            .map_err(|error| format!("Failed to parse unindexed findings: {error}"))?;
+        pending.push((run_id, findings_json));
+    }
+
+    for (run_id, findings_json) in pending {
+        let findings: Vec<AiReviewFinding> = parse_json_vec_or_default(&findings_json);
+        save_ai_review_run_findings(&conn, &run_id, &findings).await?;
+    }
+    Ok(())
+}
+
+/// Trigrams need three characters, so shorter queries fall back to a `LIKE` scan.
+const MIN_INDEXED_QUERY_CHARS: usize = 3;
+
+/// The query as one FTS5 phrase, so its characters are matched literally.
+fn search_phrase(query: &str) -> String {
+    format!("\"{}\"", query.replace('"', "\"\""))
+}
+
+fn like_pattern(query: &str) -> String {
+    let escaped = query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// Accepts `YYYY-MM-DD` or RFC 3339 timestamps and returns the `YYYY-MM-DD HH:MM:SS` form
+/// that `CURRENT_TIMESTAMP` columns compare against. A bare date used as an upper bound
+/// covers the whole day.
+fn normalize_timestamp_bound(value: &str, end_of_day: bool) -> String {
+    let normalized: String = value
+        .trim()
+        .trim_end_matches('Z')
+        .replacen('T', " ", 1)
+        .chars()
+        .take(19)
+        .collect();
+    if end_of_day && normalized.len() == 10 {
+        format!("{normalized} 23:59:59")
+    } else {
+        normalized
+    }
+}
+
+pub(crate) async fn search_ai_review_findings(
+    state: &AppState,
+    input: &SearchFindingsInput,
+    file_filter: Option<&PathFilter>,
+) -> Result<Vec<FindingSearchHit>, String> {
+    let conn = state.connection()?;
+    let list_json = |values: Option<&Vec<String>>| -> Result<Option<String>, String> {
+        let values = values
+            .map(|values| {
+                values
+                    .iter()
+                    .map(|value| value.trim().to_lowercase())
+                    .filter(|value| !value.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|values| !values.is_empty());
+        values
+            .map(|values| serde_json::to_string(&values))
+            .transpose()
+            .map_err(|error| format!("Failed to encode search filter: {error}"))
+    };
+    let query = as_non_empty_trimmed(input.query.as_deref());
+    let (phrase, pattern) = match query {
+        Some(query) if query.chars().count() >= MIN_INDEXED_QUERY_CHARS => {
+            (Some(search_phrase(&query)), None)
+        }
+        query => (None, query.map(|query| like_pattern(&query))),
+    };
+    let severities = list_json(input.severities.as_ref())?;
+    let categories = list_json(input.categories.as_ref())?;
+    let languages = list_json(input.languages.as_ref())?;
+    let workspace = as_non_empty_trimmed(input.workspace.as_deref());
+    let since = as_non_empty_trimmed(input.since.as_deref())
+        .map(|value| normalize_timestamp_bound(&value, false));
+    let until = as_non_empty_trimmed(input.until.as_deref())
+        .map(|value| normalize_timestamp_bound(&value, true));
+    let limit = parse_limit(input.limit) as usize;
+    // File globs are matched in Rust, so the SQL limit only applies without one.
+    let sql_limit = if file_filter.is_some() {
+        -1
+    } else {
+        limit as i64
+    };
+
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT run_id, thread_id, workspace, created_at, {FINDING_COLUMNS}
+             FROM ai_review_findings
+             WHERE (?9 IS NULL OR rowid IN (
+                      SELECT rowid FROM ai_review_findings_search
+                      WHERE ai_review_findings_search MATCH ?9))
+               AND (?1 IS NULL OR title LIKE ?1 ESCAPE '\\' OR body LIKE ?1 ESCAPE '\\'
+                    OR file_path LIKE ?1 ESCAPE '\\')
+               AND (?2 IS NULL OR severity IN (SELECT value FROM json_each(?2)))
+               AND (?3 IS NULL OR category IN (SELECT value FROM json_each(?3)))
+               AND (?4 IS NULL OR language IN (SELECT value FROM json_each(?4)))
+               AND (?5 IS NULL OR workspace = ?5)
+               AND (?6 IS NULL OR created_at >= ?6)
+               AND (?7 IS NULL OR created_at <= ?7)
+             ORDER BY created_at DESC, run_id DESC, file_path ASC
+             LIMIT ?8"
+            ),
+            libsql::params![
+                pattern, severities, categories, languages, workspace, since, until, sql_limit,
+                phrase
+            ],
+        )
+        .await
+        .map_err(|error| format!("Failed to search review findings: {error}"))?;
+
+    let mut hits = Vec::new();
+    while hits.len() < limit {
+        let Some(row) = rows
+            .next()
+            .await
+            .map_err(|error| format!("Failed to read review finding rows: {error}"))?
+        else {
+            break;
+        };
+        let finding = parse_finding_from_row(&row, 4)?;
+        if file_filter.is_some_and(|filter| !filter.allows(&finding.file_path)) {
+            continue;
+        }
+        hits.push(FindingSearchHit {
+            run_id: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse finding run id: {error}"))?,
+            thread_id: row
+                .get(1)
+                .map_err(|error| format!("Failed to parse finding thread id: {error}"))?,
+            workspace: row
+                .get(2)
+                .map_err(|error| format!("Failed to parse finding workspace: {error}"))?,
+            created_at: row
+                .get(3)
+                .map_err(|error| format!("Failed to parse finding created_at: {error}"))?,
+            finding,
+        });
+    }
+    Ok(hits)
+}
+
+/// Columns selected by [`parse_finding_state_from_row`], in order.
+const FINDING_STATE_COLUMNS: &str = "workspace, fingerprint, state, file_path, title, reason,
+  run_id, finding_id, fix_commit, created_at, updated_at";
+
+fn parse_finding_state_from_row(row: &libsql::Row) -> Result<ReviewFindingState, String> {
+    Ok(ReviewFindingState {
+        workspace: row
+            .get(0)
+            .map_err(|error| format!("Failed to parse finding state workspace: {error}"))?,
+        fingerprint: row
+            .get(1)
+            .map_err(|error| format!("Failed to parse finding state fingerprint: {error}"))?,
+        state: row
+            .get(2)
+            .map_err(|error| format!("Failed to parse finding state: {error}"))?,
+        file_path: row
+            .get(3)
+            .map_err(|error| format!("Failed to parse finding state file path: {error}"))?,
+        title: row
+            .get(4)
+            .map_err(|error| format!("Failed to parse finding state title: {error}"))?,
+        reason: row
+            .get(5)
+            .map_err(|error| format!("Failed to parse finding state reason: {error}"))?,
+        run_id: row
+            .get(6)
+            .map_err(|error| format!("Failed to parse finding state run id: {error}"))?,
+        finding_id: row
+            .get(7)
+            .map_err(|error| format!("Failed to parse finding state finding id: {error}"))?,
+        fix_commit: row
+            .get(8)
+            .map_err(|error| format!("Failed to parse finding state fix commit: {error}"))?,
+        created_at: row
+            .get(9)
+            .map_err(|error| format!("Failed to parse finding state created_at: {error}"))?,
+        updated_at: row
+            .get(10)
+            .map_err(|error| format!("Failed to parse finding state updated_at: {error}"))?,
+    })
+}
+
+/// Records `finding_state` for the fingerprint of a stored finding, so the same finding in
+/// later runs of the workspace inherits it, and applies it to every stored occurrence.
+/// `fix_commit` names the commit that fixed it, and is cleared by any other state.
+pub(crate) async fn set_review_finding_state(
+    state: &AppState,
+    run_id: &str,
+    finding_id: &str,
+    finding_state: &str,
+    reason: Option<String>,
+    fix_commit: Option<String>,
+) -> Result<ReviewFindingState, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT workspace, fingerprint, file_path, title
+             FROM ai_review_findings
+             WHERE run_id = ?1 AND finding_id = ?2",
+            (run_id.to_string(), finding_id.to_string()),
+        )
+        .await
+        .map_err(|error| format!("Failed to load finding {finding_id}: {error}"))?;
+    let row = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read finding {finding_id}: {error}"))?
+        .ok_or_else(|| format!("Finding '{finding_id}' was not found in run '{run_id}'."))?;
+    let workspace: String = row
+        .get(0)
+        .map_err(|error| format!("Failed to parse finding workspace: {error}"))?;
+    let fingerprint: String = row
+        .get(1)
+        .map_err(|error| format!("Failed to parse finding fingerprint: {error}"))?;
+    let file_path: String = row
+        .get(2)
+        .map_err(|error| format!("Failed to parse finding file path: {error}"))?;
+    let title: String = row
+        .get(3)
+        .map_err(|error| format!("Failed to parse finding title: {error}"))?;
+
+    conn.execute(
+        "INSERT INTO review_finding_states (
+          workspace, fingerprint, state, file_path, title, reason, run_id, finding_id, fix_commit
+         ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+         ON CONFLICT(workspace, fingerprint) DO UPDATE SET
+           state = excluded.state,
+           file_path = excluded.file_path,
+           title = excluded.title,
+           reason = excluded.reason,
+           run_id = excluded.run_id,
+           finding_id = excluded.finding_id,
+           fix_commit = excluded.fix_commit,
+           updated_at = CURRENT_TIMESTAMP",
+        (
+            workspace.clone(),
+            fingerprint.clone(),
+            finding_state.to_string(),
+            file_path,
+            title,
+            reason,
+            run_id.to_string(),
+            finding_id.to_string(),
+            fix_commit,
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to save state for finding {finding_id}: {error}"))?;
+    conn.execute(
+        "UPDATE ai_review_findings SET status = ?3 WHERE workspace = ?1 AND fingerprint = ?2",
+        (
+            workspace.clone(),
+            fingerprint.clone(),
+            finding_state.to_string(),
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to update status for finding {finding_id}: {error}"))?;
+
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {FINDING_STATE_COLUMNS}
+                 FROM review_finding_states
+                 WHERE workspace = ?1 AND fingerprint = ?2"
+            ),
+            (workspace, fingerprint),
+        )
+        .await
+        .map_err(|error| format!("Failed to load state for finding {finding_id}: {error}"))?;
+    let row = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read state for finding {finding_id}: {error}"))?
+        .ok_or_else(|| format!("State for finding {finding_id} was not saved."))?;
+    parse_finding_state_from_row(&row)
+}
+
+/// `(run_id, finding_id, fingerprint)` of the most recent stored finding for each open
+/// fingerprint in `workspace`, newest first.
+pub(crate) async fn list_open_findings(
+    state: &AppState,
+    workspace: &str,
+    limit: usize,
+) -> Result<Vec<(String, String, String)>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT run_id, finding_id, fingerprint
+                 FROM ai_review_findings
+                 WHERE workspace = ?1 AND status = '{FINDING_STATUS_OPEN}'
+                 ORDER BY created_at DESC, run_id DESC, finding_id ASC"
+            ),
+            [workspace.to_string()],
+        )
+        .await
+        .map_err(|error| format!("Failed to list open findings: {error}"))?;
+
+    let mut seen = HashSet::new();
+    let mut findings = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read open finding rows: {error}"))?
+    {
+        let fingerprint: String = row
+            .get(2)
+            .map_err(|error| format!("Failed to parse open finding fingerprint: {error}"))?;
+        if !seen.insert(fingerprint.clone()) {
+            continue;
+        }
+        findings.push((
+            row.get(0)
+                .map_err(|error| format!("Failed to parse open finding run id: {error}"))?,
+            row.get(1)
+                .map_err(|error| format!("Failed to parse open finding id: {error}"))?,
+            fingerprint,
+        ));
+        if findings.len() >= limit {
+            break;
+        }
+    }
+    Ok(findings)
+}
+
+pub(crate) async fn list_review_finding_states(
+    state: &AppState,
+    workspace: Option<String>,
+    finding_state: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<ReviewFindingState>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {FINDING_STATE_COLUMNS}
+                 FROM review_finding_states
+                 WHERE (?1 IS NULL OR workspace = ?1)
+                   AND (?2 IS NULL OR state = ?2)
+                 ORDER BY updated_at DESC, fingerprint ASC
+                 LIMIT ?3"
+            ),
+            (workspace, finding_state, parse_limit(limit)),
+        )
+        .await
+        .map_err(|error| format!("Failed to list finding states: {error}"))?;
+
+    let mut states = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read finding state rows: {error}"))?
+    {
+        states.push(parse_finding_state_from_row(&row)?);
+    }
+    Ok(states)
+}
+
+async fn save_ai_review_run_usage(
+    conn: &libsql::Connection,
+    run_id: &str,
+    result: &GenerateAiReviewResult,
+) -> Result<(), String> {
+    let resource_usage_json = result
+        .resource_usage
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|error| format!("Failed to serialize resource usage: {error}"))?;
+    let token_usage = result.token_usage.as_ref();
+    conn.execute(
+        "UPDATE ai_review_runs
+         SET resource_usage_json = ?2,
+             prompt_tokens = ?3,
+             completion_tokens = ?4,
+             estimated_cost_usd = ?5
          WHERE run_id = ?1",
         (
             run_id.to_string(),
-            status.to_string(),
-            Some(result.model.clone()),
-            Some(result.review.clone()),
-            i64::try_from(result.diff_chars_used).unwrap_or(i64::MAX),
-            i64::try_from(result.diff_chars_total).unwrap_or(i64::MAX),
-            parse_bool_i64(result.diff_truncated),
-            error.map(ToOwned::to_owned),
-            serde_json::to_string(&result.chunks).map_err(|serialize_error| {
-                format!("Failed to serialize final chunks: {serialize_error}")
-            })?,
-            serde_json::to_string(&result.findings).map_err(|serialize_error| {
-                format!("Failed to serialize final findings: {serialize_error}")
-            })?,
-            i64::try_from(result.chunks.len()).unwrap_or(i64::MAX),
-            i64::try_from(result.chunks.len()).unwrap_or(i64::MAX),
-            i64::try_from(result.findings.len()).unwrap_or(i64::MAX),
+            resource_usage_json,
+            token_usage.map(|usage| i64::try_from(usage.prompt_tokens).unwrap_or(i64::MAX)),
+            token_usage.map(|usage| i64::try_from(usage.completion_tokens).unwrap_or(i64::MAX)),
+            token_usage.and_then(|usage| usage.estimated_cost_usd),
         ),
     )
     .await
-    .map_err(|error| format!("Failed to finalize AI review run: {error}"))?;
+    .map_err(|error| format!("Failed to save AI review run usage: {error}"))?;
     Ok(())
 }
 
@@ -640,3 +2006,90 @@ pub(crate) async fn list_inline_review_comments_internal(
     }
     Ok(comments)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::search_ai_review_findings;
+    use crate::backend::db::{self, rebuild_findings_search_index};
+    use crate::backend::{AppState, SearchFindingsInput};
+
+    fn search(query: &str) -> SearchFindingsInput {
+        SearchFindingsInput {
+            query: Some(query.to_string()),
+            severities: None,
+            categories: None,
+            languages: None,
+            file_glob: None,
+            workspace: None,
+            since: None,
+            until: None,
+            limit: None,
+        }
+    }
+
+    #[test]
+    fn findings_are_searched_through_the_index() {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("rovex-finding-search-test-{suffix}.db"));
+        tauri::async_runtime::block_on(async {
+            let db = libsql::Builder::new_local(&path)
+                .build()
+                .await
+                .expect("open database");
+            db::initialize_schema(&db).await.expect("initialize schema");
+            let state = AppState {
+                db,
+                database_url: format!("file:{}", path.display()),
+            };
+            let conn = state.connection().unwrap();
+            conn.execute("PRAGMA foreign_keys = OFF", ()).await.unwrap();
+            for (finding_id, title, file_path) in [
+                ("f1", "Unchecked unwrap on user input", "src/parser.rs"),
+                ("f2", "Missing bounds check", "src/buffer.rs"),
+            ] {
+                conn.execute(
+                    "INSERT INTO ai_review_findings (run_id, finding_id, thread_id, workspace,
+                       fingerprint, severity, file_path, side, line_number, chunk_id, chunk_index,
+                       hunk_header, title, body, created_at)
+                     VALUES ('run-1', ?1, 1, '/repo', ?1, 'high', ?3, 'additions', 1, 'c', 0,
+                       '@@', ?2, 'Details.', '2024-01-01 00:00:00')",
+                    (finding_id, title, file_path),
+                )
+                .await
+                .unwrap();
+            }
+            let ids = |hits: Vec<crate::backend::FindingSearchHit>| {
+                hits.into_iter()
+                    .map(|hit| hit.finding.id)
+                    .collect::<Vec<_>>()
+            };
+
+            let found = search_ai_review_findings(&state, &search("UNWRAP"), None).await;
+            assert_eq!(ids(found.unwrap()), ["f1"]);
+            let found = search_ai_review_findings(&state, &search("buffer.rs"), None).await;
+            assert_eq!(ids(found.unwrap()), ["f2"]);
+            // Too short for trigrams, so matched with LIKE instead.
+            let found = search_ai_review_findings(&state, &search("nd"), None).await;
+            assert_eq!(ids(found.unwrap()), ["f2"]);
+            let found = search_ai_review_findings(&state, &search("\"unwrap OR *"), None).await;
+            assert!(found.unwrap().is_empty());
+
+            conn.execute("DELETE FROM ai_review_findings WHERE finding_id = 'f1'", ())
+                .await
+                .unwrap();
+            let found = search_ai_review_findings(&state, &search("unwrap"), None).await;
+            assert!(found.unwrap().is_empty());
+
+            conn.execute("VACUUM", ()).await.unwrap();
+            rebuild_findings_search_index(&conn).await.unwrap();
+            let found = search_ai_review_findings(&state, &search("bounds"), None).await;
+            assert_eq!(ids(found.unwrap()), ["f2"]);
+        });
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,167 @@
+use tauri::State;
+
+use super::super::common::{current_team_stats_min_runs, team_stats_enabled};
+use crate::backend::{
+    AppState, GetTeamReviewStatsInput, GetTeamReviewStatsResult, TeamReviewWeekStats,
+    TeamSeverityMix,
+};
+
+const DEFAULT_TEAM_STATS_WEEKS: u32 = 12;
+const MAX_TEAM_STATS_WEEKS: u32 = 52;
+
+/// Totals of one week's completed runs, as aggregated by the database.
+#[derive(Debug, Clone, PartialEq)]
+struct WeekAggregate {
+    week_start: String,
+    run_count: u64,
+    finding_count: u64,
+    severity_mix: TeamSeverityMix,
+}
+
+/// Turns weekly totals into reportable stats, dropping weeks with fewer than `min_runs` runs
+/// so a quiet week cannot be traced back to the few runs in it.
+fn publishable_weeks(
+    aggregates: Vec<WeekAggregate>,
+    min_runs: u64,
+) -> (Vec<TeamReviewWeekStats>, u64) {
+    let mut suppressed_weeks = 0;
+    let weeks = aggregates
+        .into_iter()
+        .filter_map(|aggregate| {
+            if aggregate.run_count < min_runs.max(1) {
+                suppressed_weeks += 1;
+                return None;
+            }
+            Some(TeamReviewWeekStats {
+                mean_findings: aggregate.finding_count as f64 / aggregate.run_count as f64,
+                week_start: aggregate.week_start,
+                run_count: aggregate.run_count,
+                severity_mix: aggregate.severity_mix,
+            })
+        })
+        .collect();
+    (weeks, suppressed_weeks)
+}
+
+/// Weekly run and finding totals across every workspace in the app database, newest week
+/// first. Only sums leave the database; individual runs are never read.
+async fn load_week_aggregates(state: &AppState, weeks: u32) -> Result<Vec<WeekAggregate>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT
+              date(runs.created_at, 'weekday 0', '-6 days') AS week_start,
+              COUNT(*),
+              COALESCE(SUM(runs.finding_count), 0),
+              COALESCE(SUM(findings.critical), 0),
+              COALESCE(SUM(findings.high), 0),
+              COALESCE(SUM(findings.medium), 0),
+              COALESCE(SUM(findings.low), 0)
+             FROM ai_review_runs runs
+             LEFT JOIN (
+               SELECT
+                 run_id,
+                 SUM(severity = 'critical') AS critical,
+                 SUM(severity = 'high') AS high,
+                 SUM(severity = 'medium') AS medium,
+                 SUM(severity = 'low') AS low
+               FROM ai_review_findings
+               GROUP BY run_id
+             ) findings ON findings.run_id = runs.run_id
+             WHERE runs.status IN ('completed', 'completed_with_errors')
+               AND runs.created_at >= date('now', 'weekday 0', '-6 days', ?1)
+             GROUP BY week_start
+             ORDER BY week_start DESC",
+            [format!("-{} days", (weeks - 1) * 7)],
+        )
+        .await
+        .map_err(|error| format!("Failed to query team review stats: {error}"))?;
+
+    let mut aggregates = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read team review stats rows: {error}"))?
+    {
+        let read_count = |index: i32, label: &str| -> Result<u64, String> {
+            row.get::<i64>(index)
+                .map(|value| value.max(0) as u64)
+                .map_err(|error| format!("Failed to parse team stats {label}: {error}"))
+        };
+        aggregates.push(WeekAggregate {
+            week_start: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse team stats week: {error}"))?,
+            run_count: read_count(1, "run count")?,
+            finding_count: read_count(2, "finding count")?,
+            severity_mix: TeamSeverityMix {
+                critical: read_count(3, "critical count")?,
+                high: read_count(4, "high count")?,
+                medium: read_count(5, "medium count")?,
+                low: read_count(6, "low count")?,
+            },
+        });
+    }
+    Ok(aggregates)
+}
+
+/// Weekly aggregates of completed runs with small weeks withheld. The published weeks are
+/// exact; nothing is randomized, so this is suppression rather than differential privacy.
+pub async fn get_team_review_stats(
+    state: State<'_, AppState>,
+    input: GetTeamReviewStatsInput,
+) -> Result<GetTeamReviewStatsResult, String> {
+    if !team_stats_enabled() {
+        return Err("Team stats are off. Set ROVEX_TEAM_STATS=true to opt in.".to_string());
+    }
+    let weeks = input.weeks.unwrap_or(DEFAULT_TEAM_STATS_WEEKS);
+    if weeks == 0 || weeks > MAX_TEAM_STATS_WEEKS {
+        return Err(format!(
+            "Weeks must be between 1 and {MAX_TEAM_STATS_WEEKS}."
+        ));
+    }
+    let min_runs_per_week = current_team_stats_min_runs();
+    let aggregates = load_week_aggregates(&state, weeks).await?;
+    let (weeks, suppressed_weeks) = publishable_weeks(aggregates, min_runs_per_week);
+    Ok(GetTeamReviewStatsResult {
+        weeks,
+        suppressed_weeks,
+        min_runs_per_week,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{publishable_weeks, WeekAggregate};
+    use crate::backend::TeamSeverityMix;
+
+    fn week(week_start: &str, run_count: u64, finding_count: u64) -> WeekAggregate {
+        WeekAggregate {
+            week_start: week_start.to_string(),
+            run_count,
+            finding_count,
+            severity_mix: TeamSeverityMix {
+                high: finding_count,
+                ..TeamSeverityMix::default()
+            },
+        }
+    }
+
+    #[test]
+    fn weeks_below_the_run_threshold_are_suppressed() {
+        let (weeks, suppressed) = publishable_weeks(
+            vec![
+                week("2026-10-12", 2, 9),
+                week("2026-10-05", 8, 12),
+                week("2026-09-28", 4, 1),
+            ],
+            5,
+        );
+        assert_eq!(suppressed, 2);
+        assert_eq!(weeks.len(), 1);
+        assert_eq!(weeks[0].week_start, "2026-10-05");
+        assert_eq!(weeks[0].run_count, 8);
+        assert_eq!(weeks[0].mean_findings, 1.5);
+        assert_eq!(weeks[0].severity_mix.high, 12);
+    }
+}
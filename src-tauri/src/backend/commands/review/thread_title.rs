@@ -0,0 +1,204 @@
+use std::path::Path;
+
+use tauri::{AppHandle, State};
+
+use super::super::common::{as_non_empty_trimmed, truncate_chars};
+use super::super::git_repo::GitRepo;
+use super::super::thread_settings::get_thread_settings;
+use super::super::threads::set_thread_title;
+use super::super::workspace_git::compare_workspace_diff;
+use super::super::workspace_trust::is_workspace_trusted;
+use super::follow_up::generate_text_with_review_provider;
+use super::prompt_safety::{fence_untrusted, UNTRUSTED_CONTENT_RULE};
+use super::ReviewProvider;
+use crate::backend::{
+    AppState, CompareWorkspaceDiffInput, DiffFile, DiffFileStatus, GetThreadSettingsInput,
+    SuggestThreadTitleInput, SuggestThreadTitleResult,
+};
+
+/// Characters of the diff the model sees. A title needs the gist, not every hunk.
+const MAX_THREAD_TITLE_DIFF_CHARS: usize = 20_000;
+const MAX_THREAD_TITLE_CHARS: usize = 60;
+const MAX_LISTED_TITLE_FILES: usize = 100;
+
+fn status_label(status: DiffFileStatus) -> &'static str {
+    match status {
+        DiffFileStatus::Added => "added",
+        DiffFileStatus::Modified => "modified",
+        DiffFileStatus::Deleted => "deleted",
+        DiffFileStatus::Renamed => "renamed",
+        DiffFileStatus::Copied => "copied",
+    }
+}
+
+fn build_thread_title_prompt(
+    branch: Option<&str>,
+    base_ref: &str,
+    files: &[DiffFile],
+    diff: &str,
+    diff_truncated: bool,
+) -> String {
+    let mut prompt = format!(
+        "Write a title for a code review thread about the changes below.\n\nRules:\n- At most {MAX_THREAD_TITLE_CHARS} characters, in sentence case, without a trailing period.\n- Say what the change does, such as \"Retry failed uploads with backoff\"; do not start with \"Review\" or repeat the branch name verbatim.\n- Reply with the title only, without quotes or commentary.\n- {UNTRUSTED_CONTENT_RULE}\n"
+    );
+    match branch {
+        Some(branch) => {
+            prompt.push_str(&format!("\nBranch: {branch} (compared with {base_ref})\n"))
+        }
+        None => prompt.push_str(&format!("\nDetached HEAD, compared with {base_ref}\n")),
+    }
+    prompt.push_str(&format!("\nChanged files ({}):\n", files.len()));
+    for file in files.iter().take(MAX_LISTED_TITLE_FILES) {
+        prompt.push_str(&format!(
+            "- {} ({}, +{} -{})\n",
+            file.path,
+            status_label(file.status),
+            file.insertions,
+            file.deletions
+        ));
+    }
+    if files.len() > MAX_LISTED_TITLE_FILES {
+        prompt.push_str(&format!(
+            "- ... and {} more\n",
+            files.len() - MAX_LISTED_TITLE_FILES
+        ));
+    }
+    prompt.push_str(&format!(
+        "\nDiff{}:\n{}",
+        if diff_truncated { " (truncated)" } else { "" },
+        fence_untrusted("diff", diff.trim_end())
+    ));
+    prompt
+}
+
+/// The first line of the model's reply, without a `Title:` label, quotes or a trailing
+/// period, shortened at a word boundary when the model ran over.
+fn clean_thread_title(raw: &str) -> String {
+    let line = raw
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("```"))
+        .unwrap_or_default();
+    let mut title = line
+        .strip_prefix("Title:")
+        .or_else(|| line.strip_prefix("title:"))
+        .unwrap_or(line)
+        .trim();
+    for quote in ['"', '\'', '`', '*'] {
+        title = title.trim_matches(quote).trim();
+    }
+    let title = title.trim_end_matches('.').trim_end();
+
+    let (shortened, truncated) = truncate_chars(title, MAX_THREAD_TITLE_CHARS);
+    if !truncated {
+        return title.to_string();
+    }
+    match shortened.rsplit_once(' ') {
+        Some((words, _)) if !words.is_empty() => {
+            words.trim_end_matches([',', ';', ':']).to_string()
+        }
+        _ => shortened,
+    }
+}
+
+pub async fn suggest_thread_title(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    input: SuggestThreadTitleInput,
+) -> Result<SuggestThreadTitleResult, String> {
+    let settings = match input.thread_id {
+        Some(thread_id) => {
+            Some(get_thread_settings(&state, GetThreadSettingsInput { thread_id }).await?)
+        }
+        None => None,
+    };
+    let workspace = as_non_empty_trimmed(input.workspace.as_deref())
+        .or_else(|| {
+            settings
+                .as_ref()
+                .and_then(|settings| settings.workspace.clone())
+        })
+        .ok_or_else(|| "Choose a workspace to title the thread from.".to_string())?;
+    let base_ref = as_non_empty_trimmed(input.base_ref.as_deref()).or_else(|| {
+        settings
+            .as_ref()
+            .and_then(|settings| settings.base_ref.clone())
+    });
+
+    let comparison = compare_workspace_diff(CompareWorkspaceDiffInput {
+        workspace,
+        base_ref,
+        fetch_remote: Some(false),
+        include_paths: None,
+        exclude_paths: None,
+        diff_target: None,
+        include_untracked: None,
+        head_ref: None,
+        include_submodule_diffs: None,
+        force: None,
+        remote: None,
+    })
+    .await?;
+    if comparison.files.is_empty() {
+        return Err(format!(
+            "The workspace has no changes against {} to title the thread from.",
+            comparison.base_ref
+        ));
+    }
+    let review_provider = ReviewProvider::from_settings()?;
+    review_provider.ensure_allowed_for_workspace(
+        &comparison.workspace,
+        is_workspace_trusted(&state, &comparison.workspace).await?,
+    )?;
+
+    let branch = GitRepo::open(Path::new(&comparison.workspace)).current_branch()?;
+    let (diff, diff_truncated) = truncate_chars(&comparison.diff, MAX_THREAD_TITLE_DIFF_CHARS);
+    let prompt = build_thread_title_prompt(
+        branch.as_deref(),
+        &comparison.base_ref,
+        &comparison.files,
+        &diff,
+        diff_truncated || comparison.diff_truncated,
+    );
+    let (reply, model) =
+        generate_text_with_review_provider(&app, review_provider, &comparison.workspace, &prompt)
+            .await?;
+    let title = clean_thread_title(&reply);
+    if title.is_empty() {
+        return Err("The review provider returned an empty title.".to_string());
+    }
+
+    let thread = match input.thread_id {
+        Some(thread_id) => Some(set_thread_title(&state, thread_id, &title).await?),
+        None => None,
+    };
+    Ok(SuggestThreadTitleResult {
+        title,
+        model,
+        branch,
+        thread,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::clean_thread_title;
+
+    #[test]
+    fn replies_are_cleaned_to_a_short_title() {
+        assert_eq!(
+            clean_thread_title("Title: \"Retry failed uploads with backoff.\"\n\nExplanation"),
+            "Retry failed uploads with backoff"
+        );
+        assert_eq!(
+            clean_thread_title("```\nAdd dark mode\n```"),
+            "Add dark mode"
+        );
+        let long = clean_thread_title(
+            "Move workspace comparisons onto libgit2 and cache them until HEAD, the base ref or the tree moves",
+        );
+        assert!(long.chars().count() <= 60, "{long}");
+        assert!(long.starts_with("Move workspace comparisons onto libgit2"));
+        assert!(!long.ends_with(' '));
+    }
+}
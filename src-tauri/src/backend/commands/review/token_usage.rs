@@ -0,0 +1,106 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use crate::backend::AiReviewTokenUsage;
+
+/// USD per million prompt / completion tokens, matched by model id prefix.
+const MODEL_PRICING_PER_MILLION: &[(&str, f64, f64)] = &[
+    ("gpt-5-nano", 0.05, 0.40),
+    ("gpt-5-mini", 0.25, 2.00),
+    ("gpt-5", 1.25, 10.00),
+    ("gpt-4.1-nano", 0.10, 0.40),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gpt-4.1", 2.00, 8.00),
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4o", 2.50, 10.00),
+    ("o4-mini", 1.10, 4.40),
+    ("o3-mini", 1.10, 4.40),
+    ("o3", 2.00, 8.00),
+];
+
+tokio::task_local! {
+    static TOKEN_USAGE: Arc<TokenUsageCollector>;
+}
+
+/// Accumulates the token usage reported by transports while a review unit (a chunk or the
+/// description stream) is running, including retried attempts.
+#[derive(Default)]
+pub(crate) struct TokenUsageCollector {
+    usage: Mutex<Option<AiReviewTokenUsage>>,
+}
+
+impl TokenUsageCollector {
+    pub(crate) fn snapshot(&self) -> Option<AiReviewTokenUsage> {
+        self.usage.lock().ok()?.clone()
+    }
+
+    fn add(&self, usage: &AiReviewTokenUsage) {
+        if let Ok(mut current) = self.usage.lock() {
+            merge_token_usage(&mut current, usage);
+        }
+    }
+}
+
+pub(crate) async fn with_token_usage<F: Future>(
+    collector: Arc<TokenUsageCollector>,
+    future: F,
+) -> F::Output {
+    TOKEN_USAGE.scope(collector, future).await
+}
+
+pub(crate) fn merge_token_usage(
+    total: &mut Option<AiReviewTokenUsage>,
+    usage: &AiReviewTokenUsage,
+) {
+    let Some(total) = total.as_mut() else {
+        *total = Some(usage.clone());
+        return;
+    };
+    total.prompt_tokens = total.prompt_tokens.saturating_add(usage.prompt_tokens);
+    total.completion_tokens = total
+        .completion_tokens
+        .saturating_add(usage.completion_tokens);
+    total.total_tokens = total.total_tokens.saturating_add(usage.total_tokens);
+    total.estimated_cost_usd = match (total.estimated_cost_usd, usage.estimated_cost_usd) {
+        (Some(left), Some(right)) => Some(left + right),
+        (left, right) => left.or(right),
+    };
+}
+
+fn estimate_cost_usd(model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
+    let model_id = model
+        .rsplit('/')
+        .next()
+        .unwrap_or(model)
+        .trim()
+        .to_lowercase();
+    let (_, prompt_price, completion_price) = MODEL_PRICING_PER_MILLION
+        .iter()
+        .filter(|(prefix, _, _)| model_id.starts_with(prefix))
+        .max_by_key(|(prefix, _, _)| prefix.len())?;
+    Some(
+        (prompt_tokens as f64 * prompt_price + completion_tokens as f64 * completion_price)
+            / 1_000_000.0,
+    )
+}
+
+/// Records usage reported by a provider for the current review unit. Providers that report
+/// their own cost pass it through; otherwise the cost is estimated from the pricing table.
+pub(crate) fn record_token_usage(
+    model: &str,
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    reported_cost_usd: Option<f64>,
+) {
+    let usage = AiReviewTokenUsage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens.saturating_add(completion_tokens),
+        estimated_cost_usd: reported_cost_usd
+            .filter(|cost| *cost > 0.0)
+            .or_else(|| estimate_cost_usd(model, prompt_tokens, completion_tokens)),
+    };
+    let _ = TOKEN_USAGE.try_with(|collector| collector.add(&usage));
+}
@@ -1,17 +1,50 @@
-use std::{collections::HashSet, env, process::Stdio, time::Duration};
+use std::{collections::HashSet, process::Stdio, time::Duration};
 
 use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command as TokioCommand;
 
 use super::super::super::common::{
-    parse_env_u64, snippet, DEFAULT_APP_SERVER_COMMAND, DEFAULT_APP_SERVER_STATUS_TIMEOUT_MS,
+    parse_setting_u64, snippet, DEFAULT_APP_SERVER_COMMAND, DEFAULT_APP_SERVER_STATUS_TIMEOUT_MS,
     ROVEX_APP_SERVER_COMMAND_ENV, ROVEX_REVIEW_TIMEOUT_MS_ENV,
 };
+use super::super::diff_chunks::chunk_review_json_schema;
+use super::super::resource_usage::track_child_process;
+use super::super::token_usage::record_token_usage;
+use super::rate_limit::provider_rate_limiter;
+use super::session_health::watch_session;
+use crate::backend::settings::settings_store;
 use crate::backend::{
     AppServerAccountStatus, AppServerCredits, AppServerModel, AppServerRateLimitWindow,
     AppServerRateLimits,
 };
 
+/// Error prefixes the executor matches on to tell account problems apart from other failures.
+pub(crate) const APP_SERVER_AUTH_EXPIRED_ERROR: &str = "Codex app-server session expired";
+pub(crate) const APP_SERVER_RATE_LIMITED_ERROR: &str = "Codex app-server rate limited";
+/// Consecutive stdout lines that are not JSON-RPC messages, such as warnings printed by the
+/// app-server's runtime, tolerated before the output is treated as broken.
+const MAX_SKIPPED_APP_SERVER_LINES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AppServerAccountError {
+    AuthExpired(String),
+    RateLimited {
+        detail: String,
+        resets_at: Option<i64>,
+    },
+}
+
+impl AppServerAccountError {
+    fn into_message(self) -> String {
+        match self {
+            Self::AuthExpired(detail) => format!("{APP_SERVER_AUTH_EXPIRED_ERROR}: {detail}"),
+            Self::RateLimited { detail, .. } => {
+                format!("{APP_SERVER_RATE_LIMITED_ERROR}: {detail}")
+            }
+        }
+    }
+}
+
 fn resolve_app_server_model(review_model: &str) -> String {
     review_model.trim().to_string()
 }
@@ -269,6 +302,165 @@ fn is_assistant_item(item: &serde_json::Value) -> bool {
     )
 }
 
+/// Reads cumulative thread usage from a `thread/tokenUsage/updated` notification.
+fn extract_app_server_token_usage(message: &serde_json::Value) -> Option<(u64, u64)> {
+    let total = message.pointer("/params/tokenUsage/total")?;
+    let count = |key: &str| total.get(key).and_then(|value| value.as_u64()).unwrap_or(0);
+    let prompt_tokens = count("inputTokens");
+    let completion_tokens = count("outputTokens");
+    if prompt_tokens == 0 && completion_tokens == 0 {
+        return None;
+    }
+    Some((prompt_tokens, completion_tokens))
+}
+
+/// Maps a `codexErrorInfo` payload (a bare variant name, or an object keyed by it) and its
+/// message onto the account errors the executor handles specially.
+fn classify_app_server_error(error: &serde_json::Value) -> Option<AppServerAccountError> {
+    let detail = error
+        .get("message")
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or("request failed")
+        .to_string();
+    let info = error
+        .get("codexErrorInfo")
+        .map(|value| value.to_string().to_lowercase())
+        .unwrap_or_default();
+    let normalized_detail = detail.to_lowercase();
+
+    if info.contains("unauthorized")
+        || info.contains("401")
+        || normalized_detail.contains("unauthorized")
+        || normalized_detail.contains("token expired")
+        || normalized_detail.contains("log in again")
+    {
+        return Some(AppServerAccountError::AuthExpired(detail));
+    }
+    if info.contains("usagelimitexceeded")
+        || info.contains("429")
+        || normalized_detail.contains("usage limit")
+        || normalized_detail.contains("rate limit")
+    {
+        return Some(AppServerAccountError::RateLimited {
+            detail,
+            resets_at: None,
+        });
+    }
+    None
+}
+
+/// Recognizes notifications that mean the turn can no longer finish: the account was signed
+/// out, a usage window is exhausted, or the server reported a non-retryable auth/limit error.
+fn extract_app_server_account_error(message: &serde_json::Value) -> Option<AppServerAccountError> {
+    match message.get("method").and_then(|value| value.as_str())? {
+        "error" => {
+            let will_retry = message
+                .pointer("/params/willRetry")
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false);
+            if will_retry {
+                return None;
+            }
+            classify_app_server_error(message.pointer("/params/error")?)
+        }
+        "account/updated" => {
+            let params = message.get("params")?;
+            match params.get("authMode") {
+                Some(serde_json::Value::Null) => Some(AppServerAccountError::AuthExpired(
+                    "the Codex account was signed out".to_string(),
+                )),
+                _ => None,
+            }
+        }
+        "account/rateLimits/updated" => {
+            let limits = message
+                .pointer("/params/rateLimits")
+                .and_then(parse_app_server_rate_limits)?;
+            [limits.primary, limits.secondary]
+                .into_iter()
+                .flatten()
+                .find(|window| window.used_percent >= 100)
+                .map(|window| AppServerAccountError::RateLimited {
+                    detail: "usage limit reached".to_string(),
+                    resets_at: window.resets_at,
+                })
+        }
+        "turn/completed" => classify_app_server_error(message.pointer("/params/turn/error")?),
+        _ => None,
+    }
+}
+
+/// Decides whether an app-server turn may run a command, given its argv and working directory.
+pub(crate) type CommandApproval<'a> = &'a mut (dyn FnMut(&[String], Option<&str>) -> bool + Send);
+
+fn approval_request_command(params: &serde_json::Value) -> Vec<String> {
+    match params.get("command") {
+        Some(serde_json::Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| part.as_str().map(ToOwned::to_owned))
+            .collect(),
+        // A plain string is a shell command line, so it is checked like `sh -c <line>`.
+        Some(serde_json::Value::String(line)) => {
+            vec!["sh".to_string(), "-c".to_string(), line.to_string()]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The reply to an approval request the app-server sent during a turn, or `None` when
+/// `message` is not one. Commands are accepted only when `approve` allows them; file changes
+/// are always declined.
+fn approval_response(
+    message: &serde_json::Value,
+    approve: CommandApproval<'_>,
+) -> Option<serde_json::Value> {
+    let id = message.get("id")?.clone();
+    let method = message.get("method")?.as_str()?;
+    let params = message.get("params").cloned().unwrap_or_default();
+    let mut command_allowed = || {
+        approve(
+            &approval_request_command(&params),
+            params.get("cwd").and_then(|value| value.as_str()),
+        )
+    };
+    let decision = match method {
+        "item/commandExecution/requestApproval" if command_allowed() => "accept",
+        "item/commandExecution/requestApproval" | "item/fileChange/requestApproval" => "decline",
+        "execCommandApproval" if command_allowed() => "approved",
+        "execCommandApproval" | "applyPatchApproval" => "denied",
+        _ => return None,
+    };
+    Some(serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "result": { "decision": decision },
+    }))
+}
+
+/// The command line of a command the app-server started without asking, when `approve`
+/// would have declined it. Even the strictest approval policy runs commands Codex deems
+/// safe, like `cat`, without a request, so every started command is checked here too.
+fn unapproved_command(message: &serde_json::Value, approve: CommandApproval<'_>) -> Option<String> {
+    if message.get("method")?.as_str()? != "item/started" {
+        return None;
+    }
+    let item = message.pointer("/params/item")?;
+    if item.get("type")?.as_str()? != "commandExecution" {
+        return None;
+    }
+    let command = approval_request_command(item);
+    let cwd = item.get("cwd").and_then(|value| value.as_str());
+    if approve(&command, cwd) {
+        return None;
+    }
+    Some(match item.get("command") {
+        Some(serde_json::Value::String(line)) => line.clone(),
+        _ => command.join(" "),
+    })
+}
+
 fn json_value_id(value: &serde_json::Value) -> Option<String> {
     value
         .as_str()
@@ -296,6 +488,7 @@ async fn read_json_rpc_message<R: AsyncBufRead + Unpin>(
     lines: &mut tokio::io::Lines<R>,
     deadline: tokio::time::Instant,
 ) -> Result<serde_json::Value, String> {
+    let mut skipped_lines = 0;
     loop {
         let remaining = remaining_until(deadline)?;
         let next_line = tokio::time::timeout(remaining, lines.next_line())
@@ -309,13 +502,18 @@ async fn read_json_rpc_message<R: AsyncBufRead + Unpin>(
             continue;
         }
 
-        let message = serde_json::from_str::<serde_json::Value>(trimmed).map_err(|error| {
-            format!(
+        let error = match serde_json::from_str::<serde_json::Value>(trimmed) {
+            Ok(message) if message.is_object() => return Ok(message),
+            Ok(_) => "not a JSON-RPC message".to_string(),
+            Err(error) => error.to_string(),
+        };
+        skipped_lines += 1;
+        if skipped_lines >= MAX_SKIPPED_APP_SERVER_LINES {
+            return Err(format!(
                 "Received invalid JSON from Codex app-server: {error}. Payload: {}",
                 snippet(trimmed, 200)
-            )
-        })?;
-        return Ok(message);
+            ));
+        }
     }
 }
 
@@ -368,6 +566,49 @@ pub(crate) async fn generate_review_with_app_server(
         timeout_ms,
         review_model,
         None,
+        None,
+        None,
+    )
+    .await
+}
+
+/// Reviews one chunk with the reply constrained to the chunk review JSON schema through the
+/// turn's `outputSchema`.
+pub(crate) async fn generate_chunk_review_with_app_server(
+    workspace: &str,
+    prompt: &str,
+    timeout_ms: u64,
+    review_model: &str,
+) -> Result<(String, String), String> {
+    generate_review_with_app_server_internal::<fn(&str)>(
+        workspace,
+        prompt,
+        timeout_ms,
+        review_model,
+        None,
+        None,
+        Some(&chunk_review_json_schema()),
+    )
+    .await
+}
+
+/// Runs a follow-up turn in a read-only sandbox. Commands the app-server asks to run go
+/// through `approve`, and file changes are declined.
+pub(crate) async fn generate_follow_up_with_app_server(
+    workspace: &str,
+    prompt: &str,
+    timeout_ms: u64,
+    review_model: &str,
+    approve: CommandApproval<'_>,
+) -> Result<(String, String), String> {
+    generate_review_with_app_server_internal::<fn(&str)>(
+        workspace,
+        prompt,
+        timeout_ms,
+        review_model,
+        None,
+        Some(approve),
+        None,
     )
     .await
 }
@@ -388,6 +629,8 @@ where
         timeout_ms,
         review_model,
         Some(on_delta),
+        None,
+        None,
     )
     .await
 }
@@ -398,14 +641,14 @@ async fn generate_review_with_app_server_internal<F>(
     timeout_ms: u64,
     review_model: &str,
     mut on_delta: Option<&mut F>,
+    mut command_approval: Option<CommandApproval<'_>>,
+    output_schema: Option<&serde_json::Value>,
 ) -> Result<(String, String), String>
 where
     F: FnMut(&str),
 {
-    let command_name = env::var(ROVEX_APP_SERVER_COMMAND_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
+    let command_name = settings_store()
+        .get_string(ROVEX_APP_SERVER_COMMAND_ENV)
         .unwrap_or_else(|| DEFAULT_APP_SERVER_COMMAND.to_string());
     let resolved_model = resolve_app_server_model(review_model);
 
@@ -414,6 +657,8 @@ where
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
+        // A canceled review or follow-up drops this future; the process goes with it.
+        .kill_on_drop(true)
         .spawn()
         .map_err(|error| {
             format!(
@@ -421,6 +666,7 @@ where
                 command_name
             )
         })?;
+    let usage_sampler = track_child_process(child.id());
     let mut stdin = child
         .stdin
         .take()
@@ -432,7 +678,7 @@ where
     let mut lines = BufReader::new(stdout).lines();
 
     let deadline = tokio::time::Instant::now() + Duration::from_millis(timeout_ms);
-    let review_result: Result<(String, String), String> = async {
+    let review = async {
         let initialize_request_id = 1i64;
         write_json_rpc_message(
             &mut stdin,
@@ -464,16 +710,23 @@ where
         .await?;
 
         let thread_start_request_id = 2i64;
+        let mut thread_params = serde_json::json!({
+            "cwd": workspace,
+            "model": resolved_model,
+        });
+        // "untrusted" asks before every command except the ones Codex considers safe reads;
+        // those are vetted as they start, see `unapproved_command`.
+        if command_approval.is_some() {
+            thread_params["sandbox"] = serde_json::json!("read-only");
+            thread_params["approvalPolicy"] = serde_json::json!("untrusted");
+        }
         write_json_rpc_message(
             &mut stdin,
             &serde_json::json!({
                 "jsonrpc": "2.0",
                 "id": thread_start_request_id,
                 "method": "thread/start",
-                "params": {
-                    "cwd": workspace,
-                    "model": resolved_model,
-                }
+                "params": thread_params,
             }),
         )
         .await?;
@@ -491,22 +744,26 @@ where
             .ok_or_else(|| "Codex app-server did not return a thread id.".to_string())?;
 
         let turn_start_request_id = 3i64;
+        let mut turn_params = serde_json::json!({
+            "threadId": thread_id,
+            "cwd": workspace,
+            "input": [
+                {
+                    "type": "text",
+                    "text": prompt,
+                }
+            ],
+        });
+        if let Some(output_schema) = output_schema {
+            turn_params["outputSchema"] = output_schema.clone();
+        }
         write_json_rpc_message(
             &mut stdin,
             &serde_json::json!({
                 "jsonrpc": "2.0",
                 "id": turn_start_request_id,
                 "method": "turn/start",
-                "params": {
-                    "threadId": thread_id,
-                    "cwd": workspace,
-                    "input": [
-                        {
-                            "type": "text",
-                            "text": prompt,
-                        }
-                    ],
-                }
+                "params": turn_params,
             }),
         )
         .await?;
@@ -520,11 +777,40 @@ where
 
         let mut latest_text: Option<String> = None;
         let mut streamed_text = String::new();
+        let mut token_usage: Option<(u64, u64)> = None;
         loop {
             let message = read_json_rpc_message(&mut lines, deadline).await?;
             if let Some(error) = extract_json_rpc_error_message(&message) {
                 return Err(error);
             }
+            if let Some(error) = extract_app_server_account_error(&message) {
+                if let AppServerAccountError::RateLimited {
+                    resets_at: Some(resets_at),
+                    ..
+                } = &error
+                {
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|value| value.as_secs() as i64)
+                        .unwrap_or(0);
+                    if *resets_at > now {
+                        provider_rate_limiter("app-server", review_model)
+                            .pause_for(Duration::from_secs((*resets_at - now) as u64));
+                    }
+                }
+                return Err(error.into_message());
+            }
+            if let Some(approve) = command_approval.as_deref_mut() {
+                if let Some(response) = approval_response(&message, approve) {
+                    write_json_rpc_message(&mut stdin, &response).await?;
+                    continue;
+                }
+                if let Some(command) = unapproved_command(&message, approve) {
+                    return Err(format!(
+                        "Codex app-server ran a command outside the follow-up allow-list: {command}"
+                    ));
+                }
+            }
 
             let method = message.get("method").and_then(|value| value.as_str());
             match method {
@@ -558,6 +844,11 @@ where
                         latest_text = Some(text);
                     }
                 }
+                Some("thread/tokenUsage/updated") => {
+                    if let Some(usage) = extract_app_server_token_usage(&message) {
+                        token_usage = Some(usage);
+                    }
+                }
                 Some("turn/completed") => {
                     if let Some(expected_turn_id) = expected_turn_id.as_ref() {
                         if let Some(actual_turn_id) = message.pointer("/params/turn/id") {
@@ -586,6 +877,9 @@ where
             }
         }
 
+        if let Some((prompt_tokens, completion_tokens)) = token_usage {
+            record_token_usage(&resolved_model, prompt_tokens, completion_tokens, None);
+        }
         let review = latest_text
             .map(|value| value.trim().to_string())
             .filter(|value| !value.is_empty())
@@ -593,11 +887,23 @@ where
                 "Codex app-server completed without returning assistant output.".to_string()
             })?;
         Ok((review, resolved_model.clone()))
-    }
+    };
+    // A crashed app-server fails the turn right away, instead of at the deadline when it
+    // leaves a pipe open behind it.
+    let review_result = watch_session("Codex app-server", review, async {
+        match child.wait().await {
+            Ok(status) => format!("exited with {status}"),
+            Err(_) => std::future::pending().await,
+        }
+    })
     .await;
 
+    if let Some(usage_sampler) = usage_sampler {
+        usage_sampler.finish();
+    }
     let _ = child.kill().await;
     let _ = child.wait().await;
+
     review_result
 }
 
@@ -613,10 +919,8 @@ pub async fn get_app_server_account_status() -> Result<AppServerAccountStatus, S
         detail: Some(detail),
     };
 
-    let command_name = env::var(ROVEX_APP_SERVER_COMMAND_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
+    let command_name = settings_store()
+        .get_string(ROVEX_APP_SERVER_COMMAND_ENV)
         .unwrap_or_else(|| DEFAULT_APP_SERVER_COMMAND.to_string());
 
     let mut child = match TokioCommand::new(&command_name)
@@ -657,7 +961,7 @@ pub async fn get_app_server_account_status() -> Result<AppServerAccountStatus, S
     };
     let mut lines = BufReader::new(stdout).lines();
 
-    let timeout_ms = parse_env_u64(
+    let timeout_ms = parse_setting_u64(
         ROVEX_REVIEW_TIMEOUT_MS_ENV,
         DEFAULT_APP_SERVER_STATUS_TIMEOUT_MS,
         500,
@@ -800,7 +1104,22 @@ pub async fn get_app_server_account_status() -> Result<AppServerAccountStatus, S
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_app_server_models_result, parse_app_server_rate_limits_result};
+    use std::time::Duration;
+
+    use proptest::prelude::*;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    use super::{
+        approval_response, extract_app_server_account_error, parse_app_server_models_result,
+        parse_app_server_rate_limits_result, read_json_rpc_message, unapproved_command,
+        AppServerAccountError, MAX_SKIPPED_APP_SERVER_LINES,
+    };
+
+    fn read_first_message(output: &str) -> Result<serde_json::Value, String> {
+        let mut lines = BufReader::new(output.as_bytes()).lines();
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        tauri::async_runtime::block_on(read_json_rpc_message(&mut lines, deadline))
+    }
 
     #[test]
     fn parse_app_server_rate_limits_prefers_codex_bucket() {
@@ -851,4 +1170,134 @@ mod tests {
         assert_eq!(parsed[1].id, "gpt-5.2-codex");
         assert_eq!(parsed[1].upgrade.as_deref(), Some("gpt-5.3-codex"));
     }
+
+    #[test]
+    fn extract_app_server_account_error_classifies_notifications() {
+        let unauthorized = serde_json::json!({
+            "method": "error",
+            "params": {
+                "error": { "message": "Your session expired", "codexErrorInfo": "unauthorized" },
+                "willRetry": false
+            }
+        });
+        assert_eq!(
+            extract_app_server_account_error(&unauthorized),
+            Some(AppServerAccountError::AuthExpired(
+                "Your session expired".to_string()
+            ))
+        );
+
+        let retrying = serde_json::json!({
+            "method": "error",
+            "params": {
+                "error": { "message": "Too many requests", "codexErrorInfo": "usageLimitExceeded" },
+                "willRetry": true
+            }
+        });
+        assert_eq!(extract_app_server_account_error(&retrying), None);
+
+        let exhausted = serde_json::json!({
+            "method": "account/rateLimits/updated",
+            "params": {
+                "rateLimits": {
+                    "primary": { "usedPercent": 100, "resetsAt": 1700000000 }
+                }
+            }
+        });
+        assert_eq!(
+            extract_app_server_account_error(&exhausted),
+            Some(AppServerAccountError::RateLimited {
+                detail: "usage limit reached".to_string(),
+                resets_at: Some(1_700_000_000),
+            })
+        );
+    }
+
+    #[test]
+    fn approval_response_accepts_only_allowed_commands() {
+        let mut approve = |command: &[String], _cwd: Option<&str>| command[0] == "rg";
+        let request = serde_json::json!({
+            "id": 7,
+            "method": "item/commandExecution/requestApproval",
+            "params": { "command": ["rg", "helper"], "cwd": "/repo" }
+        });
+        assert_eq!(
+            approval_response(&request, &mut approve),
+            Some(
+                serde_json::json!({ "jsonrpc": "2.0", "id": 7, "result": { "decision": "accept" } })
+            )
+        );
+
+        let legacy = serde_json::json!({
+            "id": "8",
+            "method": "execCommandApproval",
+            "params": { "command": ["rm", "-rf", "src"] }
+        });
+        assert_eq!(
+            approval_response(&legacy, &mut approve).unwrap()["result"]["decision"],
+            "denied"
+        );
+
+        let patch = serde_json::json!({ "id": 9, "method": "item/fileChange/requestApproval" });
+        assert_eq!(
+            approval_response(&patch, &mut approve).unwrap()["result"]["decision"],
+            "decline"
+        );
+
+        let notification = serde_json::json!({ "method": "item/completed", "params": {} });
+        assert_eq!(approval_response(&notification, &mut approve), None);
+    }
+
+    #[test]
+    fn unapproved_command_catches_commands_started_without_a_request() {
+        let mut approve = |command: &[String], _cwd: Option<&str>| {
+            command.last().is_some_and(|line| !line.contains(".ssh"))
+        };
+        let started = |command: &str| {
+            serde_json::json!({
+                "method": "item/started",
+                "params": { "item": { "type": "commandExecution", "command": command, "cwd": "/repo" } }
+            })
+        };
+        assert_eq!(
+            unapproved_command(&started("cat ~/.ssh/id_rsa"), &mut approve),
+            Some("cat ~/.ssh/id_rsa".to_string())
+        );
+        assert_eq!(
+            unapproved_command(&started("cat src/lib.rs"), &mut approve),
+            None
+        );
+
+        let message = serde_json::json!({
+            "method": "item/started",
+            "params": { "item": { "type": "agentMessage", "command": "cat ~/.ssh/id_rsa" } }
+        });
+        assert_eq!(unapproved_command(&message, &mut approve), None);
+    }
+
+    #[test]
+    fn json_rpc_reader_gives_up_on_endless_noise() {
+        let noise = "warning: not json\n".repeat(MAX_SKIPPED_APP_SERVER_LINES);
+        let error = read_first_message(&format!("{noise}{{\"id\":1}}\n")).unwrap_err();
+        assert!(error.contains("Received invalid JSON"));
+        assert!(read_first_message("")
+            .unwrap_err()
+            .contains("exited before returning"));
+    }
+
+    proptest! {
+        #[test]
+        fn json_rpc_reader_skips_noise_before_a_message(
+            noise in prop::collection::vec("[^\r\n]{0,80}", 0..16),
+            id in any::<i64>(),
+        ) {
+            let message = serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": {} });
+            let output = format!("{}\n{message}\n", noise.join("\n"));
+            let read = read_first_message(&output).unwrap();
+            // A noise line may itself be a JSON object; otherwise the message comes first.
+            if !noise.iter().any(|line| line.trim_start().starts_with('{')) {
+                prop_assert_eq!(read, message);
+            }
+        }
+    }
 }
@@ -1,22 +1,23 @@
-use std::{env, process::Stdio, time::Duration};
+use std::{process::Stdio, time::Duration};
 
+use tauri_plugin_opener::OpenerExt;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command as TokioCommand;
 
 use super::super::super::common::{
-    parse_env_u64, DEFAULT_APP_SERVER_COMMAND, DEFAULT_APP_SERVER_STATUS_TIMEOUT_MS,
+    parse_setting_u64, DEFAULT_APP_SERVER_COMMAND, DEFAULT_APP_SERVER_STATUS_TIMEOUT_MS,
     ROVEX_APP_SERVER_COMMAND_ENV, ROVEX_REVIEW_TIMEOUT_MS_ENV,
 };
+use super::super::ReviewHost;
 use super::app_server::{
     parse_app_server_optional_string, wait_for_json_rpc_result, write_json_rpc_message,
 };
+use crate::backend::settings::settings_store;
 use crate::backend::AppServerLoginStartResult;
 
 pub async fn start_app_server_account_login() -> Result<AppServerLoginStartResult, String> {
-    let command_name = env::var(ROVEX_APP_SERVER_COMMAND_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
+    let command_name = settings_store()
+        .get_string(ROVEX_APP_SERVER_COMMAND_ENV)
         .unwrap_or_else(|| DEFAULT_APP_SERVER_COMMAND.to_string());
 
     let mut child = TokioCommand::new(&command_name)
@@ -42,7 +43,7 @@ pub async fn start_app_server_account_login() -> Result<AppServerLoginStartResul
         .ok_or_else(|| "Failed to open Codex app-server stdout.".to_string())?;
     let mut lines = BufReader::new(stdout).lines();
 
-    let timeout_ms = parse_env_u64(
+    let timeout_ms = parse_setting_u64(
         ROVEX_REVIEW_TIMEOUT_MS_ENV,
         DEFAULT_APP_SERVER_STATUS_TIMEOUT_MS,
         500,
@@ -107,3 +108,32 @@ pub async fn start_app_server_account_login() -> Result<AppServerLoginStartResul
 
     login_result
 }
+
+/// Starts a ChatGPT login for the Codex app-server and opens the sign-in page in the browser.
+/// Returns a message describing what the user needs to do next.
+pub(crate) async fn request_app_server_relogin(host: &ReviewHost) -> String {
+    let login = match start_app_server_account_login().await {
+        Ok(login) => login,
+        Err(error) => {
+            return format!(
+                "Codex session expired and sign-in could not be started ({error}). Sign in again from Settings."
+            )
+        }
+    };
+    let Some(app) = host.app_handle() else {
+        return format!(
+            "Codex session expired. Sign in at {} and rerun the review.",
+            login.auth_url
+        );
+    };
+    match app.opener().open_url(&login.auth_url, None::<&str>) {
+        Ok(()) => {
+            "Codex session expired. Opened sign-in in your browser; rerun the review after logging in."
+                .to_string()
+        }
+        Err(_) => format!(
+            "Codex session expired. Sign in at {} and rerun the review.",
+            login.auth_url
+        ),
+    }
+}
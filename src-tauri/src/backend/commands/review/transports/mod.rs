@@ -2,3 +2,6 @@ pub(crate) mod app_server;
 pub(crate) mod app_server_login;
 pub(crate) mod openai;
 pub(crate) mod opencode;
+pub(crate) mod opencode_sidecar;
+pub(crate) mod rate_limit;
+pub(crate) mod session_health;
@@ -1,9 +1,41 @@
-use std::time::Duration;
+use std::{
+    collections::HashSet,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
 
 use reqwest::{Client, StatusCode};
 use serde::Serialize;
 
 use super::super::super::common::{snippet, OPENAI_API_KEY_ENV};
+use super::super::chunk_outcome::{CONTENT_FILTER_ERROR, EMPTY_OUTPUT_ERROR, MODEL_REFUSAL_ERROR};
+use super::super::diff_chunks::{chunk_review_json_schema, CHUNK_REVIEW_SCHEMA_NAME};
+use super::super::token_usage::record_token_usage;
+use super::rate_limit::{parse_retry_after, provider_rate_limiter};
+
+fn pause_on_rate_limit(model: &str, response: &reqwest::Response) {
+    if response.status() != StatusCode::TOO_MANY_REQUESTS {
+        return;
+    }
+    if let Some(delay) = parse_retry_after(response.headers()) {
+        provider_rate_limiter("openai", model).pause_for(delay);
+    }
+}
+
+/// Refusals and content-filter stops arrive as successful completions. They are returned as
+/// errors so they are not mistaken for empty output.
+fn chat_completion_block(body: &serde_json::Value) -> Option<String> {
+    let choice = body.get("choices")?.as_array()?.first()?;
+    if choice
+        .get("finish_reason")
+        .and_then(serde_json::Value::as_str)
+        == Some("content_filter")
+    {
+        return Some(CONTENT_FILTER_ERROR.to_string());
+    }
+    let refusal = choice.get("message")?.get("refusal")?.as_str()?.trim();
+    (!refusal.is_empty()).then(|| format!("{MODEL_REFUSAL_ERROR}: {}", snippet(refusal, 300)))
+}
 
 fn extract_chat_response_text(body: &serde_json::Value) -> Option<String> {
     let content = body
@@ -55,6 +87,14 @@ fn extract_chat_response_text(body: &serde_json::Value) -> Option<String> {
     }
 }
 
+fn extract_chat_usage(body: &serde_json::Value) -> Option<(u64, u64)> {
+    let usage = body.get("usage")?;
+    Some((
+        usage.get("prompt_tokens")?.as_u64()?,
+        usage.get("completion_tokens")?.as_u64()?,
+    ))
+}
+
 #[derive(Debug, Serialize)]
 struct OpenAiChatMessage<'a> {
     role: &'a str,
@@ -68,32 +108,26 @@ struct OpenAiChatRequest<'a> {
     messages: Vec<OpenAiChatMessage<'a>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<OpenAiStreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
-async fn generate_openai_chat_completion(
+#[derive(Debug, Serialize)]
+struct OpenAiStreamOptions {
+    include_usage: bool,
+}
+
+/// Sends one non-streaming chat completion request and returns the response body, recording
+/// its token usage.
+async fn post_chat_completion<T: Serialize>(
     model: &str,
     base_url: &str,
     timeout_ms: u64,
     api_key: &str,
-    system_prompt: &str,
-    prompt: &str,
-) -> Result<String, String> {
-    let request = OpenAiChatRequest {
-        model,
-        temperature: 0.2,
-        messages: vec![
-            OpenAiChatMessage {
-                role: "system",
-                content: system_prompt,
-            },
-            OpenAiChatMessage {
-                role: "user",
-                content: prompt,
-            },
-        ],
-        stream: None,
-    };
-
+    request: &T,
+) -> Result<serde_json::Value, String> {
     let endpoint = format!("{}/chat/completions", base_url.trim_end_matches('/'));
     let client = Client::builder()
         .timeout(Duration::from_millis(timeout_ms))
@@ -104,7 +138,7 @@ async fn generate_openai_chat_completion(
         .post(&endpoint)
         .header("Authorization", format!("Bearer {api_key}"))
         .header("Content-Type", "application/json")
-        .json(&request)
+        .json(request)
         .send()
         .await
         .map_err(|error| format!("Failed to reach AI provider: {error}"))?;
@@ -116,6 +150,7 @@ async fn generate_openai_chat_completion(
     }
 
     if !response.status().is_success() {
+        pause_on_rate_limit(model, &response);
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         return Err(format!(
@@ -128,8 +163,44 @@ async fn generate_openai_chat_completion(
         .json()
         .await
         .map_err(|error| format!("Failed to parse AI provider response: {error}"))?;
-    let review = extract_chat_response_text(&body)
-        .ok_or_else(|| "AI provider returned an empty response.".to_string())?;
+    if let Some((prompt_tokens, completion_tokens)) = extract_chat_usage(&body) {
+        record_token_usage(model, prompt_tokens, completion_tokens, None);
+    }
+    Ok(body)
+}
+
+async fn generate_openai_chat_completion(
+    model: &str,
+    base_url: &str,
+    timeout_ms: u64,
+    api_key: &str,
+    system_prompt: &str,
+    prompt: &str,
+    response_format: Option<serde_json::Value>,
+) -> Result<String, String> {
+    let request = OpenAiChatRequest {
+        model,
+        temperature: 0.2,
+        messages: vec![
+            OpenAiChatMessage {
+                role: "system",
+                content: system_prompt,
+            },
+            OpenAiChatMessage {
+                role: "user",
+                content: prompt,
+            },
+        ],
+        stream: None,
+        stream_options: None,
+        response_format,
+    };
+
+    let body = post_chat_completion(model, base_url, timeout_ms, api_key, &request).await?;
+    if let Some(error) = chat_completion_block(&body) {
+        return Err(error);
+    }
+    let review = extract_chat_response_text(&body).ok_or_else(|| EMPTY_OUTPUT_ERROR.to_string())?;
     Ok(review)
 }
 
@@ -206,6 +277,10 @@ where
             },
         ],
         stream: Some(true),
+        stream_options: Some(OpenAiStreamOptions {
+            include_usage: true,
+        }),
+        response_format: None,
     };
 
     let endpoint = format!("{}/chat/completions", base_url.trim_end_matches('/'));
@@ -229,6 +304,7 @@ where
         ));
     }
     if !response.status().is_success() {
+        pause_on_rate_limit(model, &response);
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
         return Err(format!(
@@ -267,12 +343,15 @@ where
                 aggregate.push_str(&delta);
                 on_delta(&delta);
             }
+            if let Some((prompt_tokens, completion_tokens)) = extract_chat_usage(&event) {
+                record_token_usage(model, prompt_tokens, completion_tokens, None);
+            }
         }
     }
 
     let review = aggregate.trim().to_string();
     if review.is_empty() {
-        return Err("AI provider returned an empty response.".to_string());
+        return Err(EMPTY_OUTPUT_ERROR.to_string());
     }
     Ok(review)
 }
@@ -285,8 +364,53 @@ pub(crate) async fn generate_review_with_openai(
     prompt: &str,
 ) -> Result<String, String> {
     let system_prompt = "You are a senior code reviewer. Review the diff and provide concise, high-signal findings. Prioritize functional bugs, regressions, security risks, and missing tests. Use markdown with sections: Summary, Findings, Suggested Tests. If no issues, say that clearly.";
-    generate_openai_chat_completion(model, base_url, timeout_ms, api_key, system_prompt, prompt)
-        .await
+    generate_openai_chat_completion(
+        model,
+        base_url,
+        timeout_ms,
+        api_key,
+        system_prompt,
+        prompt,
+        None,
+    )
+    .await
+}
+
+/// A completion for passes that ask for their own JSON shape, such as triage and the
+/// cross-file pass, so no response format is forced.
+pub(crate) async fn generate_json_with_openai(
+    model: &str,
+    base_url: &str,
+    timeout_ms: u64,
+    api_key: &str,
+    system_prompt: &str,
+    prompt: &str,
+) -> Result<String, String> {
+    generate_openai_chat_completion(
+        model,
+        base_url,
+        timeout_ms,
+        api_key,
+        system_prompt,
+        prompt,
+        None,
+    )
+    .await
+}
+
+/// `base_url` and model pairs that rejected the `json_schema` response format, such as older
+/// models or OpenAI-compatible servers without structured output. Their chunk reviews rely on
+/// the prompt and the JSON repair retry instead.
+fn json_schema_unsupported() -> &'static Mutex<HashSet<String>> {
+    static UNSUPPORTED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    UNSUPPORTED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+fn is_response_format_rejection(error: &str) -> bool {
+    error.starts_with("AI provider returned 400")
+        && ["response_format", "json_schema"]
+            .iter()
+            .any(|needle| error.contains(needle))
 }
 
 pub(crate) async fn generate_chunk_with_openai(
@@ -297,6 +421,115 @@ pub(crate) async fn generate_chunk_with_openai(
     prompt: &str,
 ) -> Result<String, String> {
     let system_prompt = "You are a senior code reviewer focused on bug detection for a single diff chunk. Inspect context carefully, avoid style nits, and return strict JSON only.";
-    generate_openai_chat_completion(model, base_url, timeout_ms, api_key, system_prompt, prompt)
+    let key = format!("{}|{model}", base_url.trim_end_matches('/'));
+    let schema_supported = !json_schema_unsupported()
+        .lock()
+        .map(|unsupported| unsupported.contains(&key))
+        .unwrap_or(false);
+    if schema_supported {
+        let response_format = serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": CHUNK_REVIEW_SCHEMA_NAME,
+                "strict": true,
+                "schema": chunk_review_json_schema(),
+            },
+        });
+        match generate_openai_chat_completion(
+            model,
+            base_url,
+            timeout_ms,
+            api_key,
+            system_prompt,
+            prompt,
+            Some(response_format),
+        )
         .await
+        {
+            Err(error) if is_response_format_rejection(&error) => {
+                if let Ok(mut unsupported) = json_schema_unsupported().lock() {
+                    unsupported.insert(key);
+                }
+            }
+            result => return result,
+        }
+    }
+    generate_openai_chat_completion(
+        model,
+        base_url,
+        timeout_ms,
+        api_key,
+        system_prompt,
+        prompt,
+        None,
+    )
+    .await
+}
+
+/// Answers `prompt` with `tools` available. Each round of tool calls goes through
+/// `run_tool(name, arguments)`; after `max_rounds` rounds the model has to answer without them.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn generate_follow_up_with_openai_tools<F>(
+    model: &str,
+    base_url: &str,
+    timeout_ms: u64,
+    api_key: &str,
+    prompt: &str,
+    tools: &serde_json::Value,
+    max_rounds: usize,
+    mut run_tool: F,
+) -> Result<String, String>
+where
+    F: FnMut(&str, &str) -> String,
+{
+    let system_prompt = "You are a senior code reviewer answering follow-up questions about a change. Use the tools to read and search workspace files instead of guessing, then answer concisely and cite the files you looked at.";
+    let mut messages = vec![
+        serde_json::json!({ "role": "system", "content": system_prompt }),
+        serde_json::json!({ "role": "user", "content": prompt }),
+    ];
+
+    for round in 0..=max_rounds {
+        let request = serde_json::json!({
+            "model": model,
+            "temperature": 0.2,
+            "messages": messages,
+            "tools": tools,
+            "tool_choice": if round == max_rounds { "none" } else { "auto" },
+        });
+        let body = post_chat_completion(model, base_url, timeout_ms, api_key, &request).await?;
+        if let Some(error) = chat_completion_block(&body) {
+            return Err(error);
+        }
+        let message = body
+            .pointer("/choices/0/message")
+            .cloned()
+            .ok_or_else(|| EMPTY_OUTPUT_ERROR.to_string())?;
+        let tool_calls = message
+            .get("tool_calls")
+            .and_then(serde_json::Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        if tool_calls.is_empty() {
+            return extract_chat_response_text(&body).ok_or_else(|| EMPTY_OUTPUT_ERROR.to_string());
+        }
+
+        messages.push(message);
+        for call in tool_calls {
+            let name = call
+                .pointer("/function/name")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default();
+            let arguments = call
+                .pointer("/function/arguments")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("{}");
+            let output = run_tool(name, arguments);
+            messages.push(serde_json::json!({
+                "role": "tool",
+                "tool_call_id": call.get("id").cloned().unwrap_or_default(),
+                "content": output,
+            }));
+        }
+    }
+    Err(EMPTY_OUTPUT_ERROR.to_string())
 }
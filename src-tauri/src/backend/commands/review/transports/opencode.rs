@@ -1,17 +1,24 @@
-use std::{env, time::Duration};
+use std::time::Duration;
 
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use tauri::async_runtime::Receiver;
 use tauri::AppHandle;
 use tauri_plugin_shell::{process::CommandEvent, ShellExt};
 
 use super::super::super::common::{
-    parse_env_u16, parse_env_u64, snippet, DEFAULT_OPENCODE_AGENT, DEFAULT_OPENCODE_HOSTNAME,
-    DEFAULT_OPENCODE_MODEL, DEFAULT_OPENCODE_PORT, DEFAULT_OPENCODE_PROVIDER,
-    DEFAULT_OPENCODE_SERVER_TIMEOUT_MS, DEFAULT_REVIEW_MODEL, OPENCODE_SIDECAR_NAME,
-    ROVEX_OPENCODE_AGENT_ENV, ROVEX_OPENCODE_HOSTNAME_ENV, ROVEX_OPENCODE_MODEL_ENV,
-    ROVEX_OPENCODE_PORT_ENV, ROVEX_OPENCODE_PROVIDER_ENV, ROVEX_OPENCODE_SERVER_TIMEOUT_MS_ENV,
+    parse_setting_u16, parse_setting_u64, snippet, DEFAULT_OPENCODE_AGENT,
+    DEFAULT_OPENCODE_HOSTNAME, DEFAULT_OPENCODE_MODEL, DEFAULT_OPENCODE_PORT,
+    DEFAULT_OPENCODE_PROVIDER, DEFAULT_OPENCODE_SERVER_TIMEOUT_MS, DEFAULT_REVIEW_MODEL,
+    OPENCODE_SIDECAR_NAME, ROVEX_OPENCODE_AGENT_ENV, ROVEX_OPENCODE_HOSTNAME_ENV,
+    ROVEX_OPENCODE_MODEL_ENV, ROVEX_OPENCODE_PORT_ENV, ROVEX_OPENCODE_PROVIDER_ENV,
+    ROVEX_OPENCODE_SERVER_TIMEOUT_MS_ENV,
 };
+use super::super::resource_usage::track_child_process;
+use super::super::token_usage::record_token_usage;
+use super::opencode_sidecar::{verify_opencode_sidecar_async, SidecarCheck};
+use super::session_health::watch_session;
+use crate::backend::settings::settings_store;
 use crate::backend::OpencodeSidecarStatus;
 
 struct ResolvedOpencodeModel {
@@ -48,20 +55,16 @@ struct OpencodePromptRequest<'a> {
 }
 
 fn resolve_opencode_model(review_model: &str) -> Result<ResolvedOpencodeModel, String> {
-    let configured_model = env::var(ROVEX_OPENCODE_MODEL_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
+    let configured_model = settings_store()
+        .get_string(ROVEX_OPENCODE_MODEL_ENV)
         .unwrap_or_else(|| {
             if review_model.contains('/') {
                 review_model.to_string()
             } else if review_model == DEFAULT_REVIEW_MODEL {
                 DEFAULT_OPENCODE_MODEL.to_string()
             } else {
-                let provider = env::var(ROVEX_OPENCODE_PROVIDER_ENV)
-                    .ok()
-                    .map(|value| value.trim().to_string())
-                    .filter(|value| !value.is_empty())
+                let provider = settings_store()
+                    .get_string(ROVEX_OPENCODE_PROVIDER_ENV)
                     .unwrap_or_else(|| DEFAULT_OPENCODE_PROVIDER.to_string());
                 format!("{provider}/{review_model}")
             }
@@ -231,6 +234,42 @@ fn extract_opencode_review_from_body(body: &str) -> Option<String> {
     }
 }
 
+/// Reads token counts and the provider-reported cost from the latest assistant message info.
+fn record_opencode_token_usage(body: &str, model_id: &str) {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body.trim()) else {
+        return;
+    };
+    let items: Vec<&serde_json::Value> = if let Some(array) = value.as_array() {
+        array.iter().collect()
+    } else {
+        vec![&value]
+    };
+    let Some(info) = items
+        .into_iter()
+        .rev()
+        .filter_map(|item| item.get("info"))
+        .find(|info| info.get("role").and_then(|role| role.as_str()) == Some("assistant"))
+    else {
+        return;
+    };
+    let Some(tokens) = info.get("tokens") else {
+        return;
+    };
+    let count = |key: &str| {
+        tokens
+            .get(key)
+            .and_then(|value| value.as_u64())
+            .unwrap_or(0)
+    };
+    let prompt_tokens = count("input");
+    let completion_tokens = count("output").saturating_add(count("reasoning"));
+    if prompt_tokens == 0 && completion_tokens == 0 {
+        return;
+    }
+    let cost = info.get("cost").and_then(|value| value.as_f64());
+    record_token_usage(model_id, prompt_tokens, completion_tokens, cost);
+}
+
 fn extract_latest_assistant_review_from_messages_body(body: &str) -> Option<String> {
     let trimmed = body.trim();
     if trimmed.is_empty() {
@@ -346,7 +385,17 @@ async fn wait_for_opencode_server(
     hostname: &str,
     port: u16,
     startup_timeout_ms: u64,
-) -> Result<(String, tauri_plugin_shell::process::CommandChild), String> {
+) -> Result<
+    (
+        String,
+        tauri_plugin_shell::process::CommandChild,
+        Receiver<CommandEvent>,
+    ),
+    String,
+> {
+    verify_opencode_sidecar_async()
+        .await
+        .map_err(|problem| problem.to_string())?;
     let command = app
         .shell()
         .sidecar(OPENCODE_SIDECAR_NAME)
@@ -414,7 +463,7 @@ async fn wait_for_opencode_server(
                     let child = child.take().ok_or_else(|| {
                         "Internal error: missing OpenCode sidecar handle.".to_string()
                     })?;
-                    return Ok((url, child));
+                    return Ok((url, child, events));
                 }
                 output_lines.push(line);
             }
@@ -451,25 +500,22 @@ pub(crate) async fn generate_review_with_opencode(
     review_model: &str,
 ) -> Result<(String, String), String> {
     let resolved_model = resolve_opencode_model(review_model)?;
-    let hostname = env::var(ROVEX_OPENCODE_HOSTNAME_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
+    let hostname = settings_store()
+        .get_string(ROVEX_OPENCODE_HOSTNAME_ENV)
         .unwrap_or_else(|| DEFAULT_OPENCODE_HOSTNAME.to_string());
-    let port = parse_env_u16(ROVEX_OPENCODE_PORT_ENV, DEFAULT_OPENCODE_PORT, 1);
-    let server_timeout_ms = parse_env_u64(
+    let port = parse_setting_u16(ROVEX_OPENCODE_PORT_ENV, DEFAULT_OPENCODE_PORT, 1);
+    let server_timeout_ms = parse_setting_u64(
         ROVEX_OPENCODE_SERVER_TIMEOUT_MS_ENV,
         DEFAULT_OPENCODE_SERVER_TIMEOUT_MS,
         1_000,
     );
-    let agent = env::var(ROVEX_OPENCODE_AGENT_ENV)
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty())
+    let agent = settings_store()
+        .get_string(ROVEX_OPENCODE_AGENT_ENV)
         .unwrap_or_else(|| DEFAULT_OPENCODE_AGENT.to_string());
 
-    let (server_url, sidecar_child) =
+    let (server_url, sidecar_child, mut sidecar_events) =
         wait_for_opencode_server(app, &hostname, port, server_timeout_ms).await?;
+    let usage_sampler = track_child_process(Some(sidecar_child.pid()));
     let base_url = server_url.trim_end_matches('/').to_string();
     let client = Client::builder()
         .timeout(Duration::from_millis(timeout_ms))
@@ -478,7 +524,7 @@ pub(crate) async fn generate_review_with_opencode(
     validate_opencode_model_available(&client, &base_url, workspace, &resolved_model).await?;
     let mut session_id: Option<String> = None;
 
-    let review_result: Result<(String, String), String> = async {
+    let review = async {
         let session_endpoint = format!("{base_url}/session");
         let session_response = client
             .post(&session_endpoint)
@@ -528,6 +574,7 @@ pub(crate) async fn generate_review_with_opencode(
         }
         let prompt_body = prompt_response.text().await.unwrap_or_default();
         let review = if let Some(review) = extract_opencode_review_from_body(&prompt_body) {
+            record_opencode_token_usage(&prompt_body, &resolved_model.model_id);
             review
         } else {
             let messages_endpoint = format!("{base_url}/session/{}/message", session.id);
@@ -552,6 +599,7 @@ pub(crate) async fn generate_review_with_opencode(
                 }
 
                 if let Some(review) = extract_latest_assistant_review_from_messages_body(&body) {
+                    record_opencode_token_usage(&body, &resolved_model.model_id);
                     break review;
                 }
 
@@ -568,7 +616,17 @@ pub(crate) async fn generate_review_with_opencode(
         };
 
         Ok((review, resolved_model.display.clone()))
-    }
+    };
+    // Sidecar output is drained until it terminates, so a crash mid-review fails the request
+    // right away instead of as a refused connection or a poll timeout.
+    let review_result = watch_session("OpenCode sidecar", review, async {
+        while let Some(event) = sidecar_events.recv().await {
+            if let CommandEvent::Terminated(payload) = event {
+                return format!("terminated (code: {:?})", payload.code);
+            }
+        }
+        "closed its output".to_string()
+    })
     .await;
 
     if let Some(session_id) = &session_id {
@@ -579,45 +637,65 @@ pub(crate) async fn generate_review_with_opencode(
             .send()
             .await;
     }
+    if let Some(usage_sampler) = usage_sampler {
+        usage_sampler.finish();
+    }
     let _ = sidecar_child.kill();
 
     review_result
 }
 
+fn unavailable_sidecar_status(detail: String) -> OpencodeSidecarStatus {
+    OpencodeSidecarStatus {
+        available: false,
+        version: None,
+        detail: Some(detail),
+        architecture: None,
+        checksum_verified: false,
+        problem: None,
+        remediation: None,
+    }
+}
+
 pub async fn get_opencode_sidecar_status(app: AppHandle) -> Result<OpencodeSidecarStatus, String> {
+    let SidecarCheck {
+        architecture,
+        checksum_verified,
+    } = match verify_opencode_sidecar_async().await {
+        Ok(check) => check,
+        Err(problem) => {
+            return Ok(OpencodeSidecarStatus {
+                problem: Some(problem.code().to_string()),
+                remediation: Some(problem.remediation()),
+                ..unavailable_sidecar_status(problem.message())
+            });
+        }
+    };
     let command = match app.shell().sidecar(OPENCODE_SIDECAR_NAME) {
         Ok(command) => command,
         Err(error) => {
-            return Ok(OpencodeSidecarStatus {
-                available: false,
-                version: None,
-                detail: Some(format!("Bundled sidecar is unavailable: {error}")),
-            });
+            return Ok(unavailable_sidecar_status(format!(
+                "Bundled sidecar is unavailable: {error}"
+            )));
         }
     };
 
     let output = match command.arg("--version").output().await {
         Ok(output) => output,
         Err(error) => {
-            return Ok(OpencodeSidecarStatus {
-                available: false,
-                version: None,
-                detail: Some(format!("Failed to run bundled OpenCode sidecar: {error}")),
-            });
+            return Ok(unavailable_sidecar_status(format!(
+                "Failed to run bundled OpenCode sidecar: {error}"
+            )));
         }
     };
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         let detail = stderr.trim();
-        return Ok(OpencodeSidecarStatus {
-            available: false,
-            version: None,
-            detail: Some(if detail.is_empty() {
-                "Bundled OpenCode sidecar exited with a non-zero status.".to_string()
-            } else {
-                snippet(detail, 300)
-            }),
-        });
+        return Ok(unavailable_sidecar_status(if detail.is_empty() {
+            "Bundled OpenCode sidecar exited with a non-zero status.".to_string()
+        } else {
+            snippet(detail, 300)
+        }));
     }
 
     let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -629,6 +707,10 @@ pub async fn get_opencode_sidecar_status(app: AppHandle) -> Result<OpencodeSidec
             Some(version)
         },
         detail: None,
+        architecture: Some(architecture),
+        checksum_verified,
+        problem: None,
+        remediation: None,
     })
 }
 
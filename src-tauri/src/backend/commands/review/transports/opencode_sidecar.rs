@@ -0,0 +1,387 @@
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::SystemTime,
+};
+
+use sha2::{Digest, Sha256};
+
+use super::super::super::common::OPENCODE_SIDECAR_NAME;
+
+/// SHA-256 of the sidecar that `build.rs` bundled. Unset when the build had no sidecar.
+const EXPECTED_SIDECAR_SHA256: Option<&str> = option_env!("ROVEX_OPENCODE_SIDECAR_SHA256");
+/// Bytes read to identify the executable format; PE headers sit well inside this.
+const HEADER_BYTES: u64 = 4096;
+
+/// Why the bundled sidecar must not be spawned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SidecarProblem {
+    Missing {
+        path: PathBuf,
+    },
+    Unreadable {
+        path: PathBuf,
+        error: String,
+    },
+    UnrecognizedFormat {
+        path: PathBuf,
+    },
+    ArchitectureMismatch {
+        path: PathBuf,
+        found: Vec<&'static str>,
+    },
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl SidecarProblem {
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            Self::Missing { .. } => "missing",
+            Self::Unreadable { .. } => "unreadable",
+            Self::UnrecognizedFormat { .. } => "unrecognized-format",
+            Self::ArchitectureMismatch { .. } => "architecture-mismatch",
+            Self::ChecksumMismatch { .. } => "checksum-mismatch",
+        }
+    }
+
+    pub(crate) fn message(&self) -> String {
+        match self {
+            Self::Missing { path } => {
+                format!("Bundled OpenCode sidecar is missing at {}.", path.display())
+            }
+            Self::Unreadable { path, error } => format!(
+                "Bundled OpenCode sidecar at {} could not be read: {error}",
+                path.display()
+            ),
+            Self::UnrecognizedFormat { path } => format!(
+                "Bundled OpenCode sidecar at {} is not an executable for this platform.",
+                path.display()
+            ),
+            Self::ArchitectureMismatch { path, found } => format!(
+                "Bundled OpenCode sidecar at {} is built for {}, but this app runs on {}.",
+                path.display(),
+                found.join(", "),
+                host_architecture()
+            ),
+            Self::ChecksumMismatch {
+                path,
+                expected,
+                actual,
+            } => format!(
+                "Bundled OpenCode sidecar at {} does not match the build (expected SHA-256 {expected}, found {actual}).",
+                path.display()
+            ),
+        }
+    }
+
+    pub(crate) fn remediation(&self) -> String {
+        let other_provider =
+            "Or set ROVEX_REVIEW_PROVIDER to `openai` or `app-server` to review without the sidecar.";
+        match self {
+            Self::Missing { .. } | Self::Unreadable { .. } | Self::ChecksumMismatch { .. } => {
+                format!(
+                    "Reinstall Rovex. For source builds, point ROVEX_OPENCODE_BIN at an opencode binary, delete src-tauri/binaries/opencode-*, and rebuild. {other_provider}"
+                )
+            }
+            Self::UnrecognizedFormat { .. } | Self::ArchitectureMismatch { .. } => {
+                let arch = host_architecture();
+                format!(
+                    "Install the {arch} build of Rovex. For source builds, set ROVEX_OPENCODE_BIN_{} to an {arch} opencode binary, delete src-tauri/binaries/opencode-*, and rebuild. {other_provider}",
+                    arch.to_uppercase()
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for SidecarProblem {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "OpenCode sidecar check failed ({}): {} Fix: {}",
+            self.code(),
+            self.message(),
+            self.remediation()
+        )
+    }
+}
+
+/// A sidecar that passed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SidecarCheck {
+    pub(crate) architecture: String,
+    /// False when the build recorded no checksum to compare against.
+    pub(crate) checksum_verified: bool,
+}
+
+type CheckKey = (PathBuf, u64, Option<SystemTime>);
+
+/// The last successful check, so each review does not re-hash an unchanged sidecar.
+static LAST_SIDECAR_CHECK: OnceLock<Mutex<Option<(CheckKey, SidecarCheck)>>> = OnceLock::new();
+
+fn last_sidecar_check() -> &'static Mutex<Option<(CheckKey, SidecarCheck)>> {
+    LAST_SIDECAR_CHECK.get_or_init(|| Mutex::new(None))
+}
+
+fn host_architecture() -> &'static str {
+    std::env::consts::ARCH
+}
+
+/// Where the shell plugin resolves the sidecar: next to the app executable.
+fn opencode_sidecar_path() -> Result<PathBuf, String> {
+    let exe = std::env::current_exe()
+        .map_err(|error| format!("Failed to resolve the app executable: {error}"))?;
+    let mut dir = exe
+        .parent()
+        .ok_or_else(|| "App executable has no parent directory.".to_string())?;
+    // Test binaries run from `target/<profile>/deps`.
+    if dir.ends_with("deps") {
+        dir = dir.parent().unwrap_or(dir);
+    }
+    Ok(dir.join(format!(
+        "{OPENCODE_SIDECAR_NAME}{}",
+        std::env::consts::EXE_SUFFIX
+    )))
+}
+
+fn read_u16(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u16> {
+    let value: [u8; 2] = bytes.get(offset..offset + 2)?.try_into().ok()?;
+    Some(if big_endian {
+        u16::from_be_bytes(value)
+    } else {
+        u16::from_le_bytes(value)
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let value: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if big_endian {
+        u32::from_be_bytes(value)
+    } else {
+        u32::from_le_bytes(value)
+    })
+}
+
+fn elf_architecture(machine: u16) -> Option<&'static str> {
+    match machine {
+        0x03 => Some("x86"),
+        0x28 => Some("arm"),
+        0x3E => Some("x86_64"),
+        0xB7 => Some("aarch64"),
+        0xF3 => Some("riscv64"),
+        _ => None,
+    }
+}
+
+fn mach_o_architecture(cpu_type: u32) -> Option<&'static str> {
+    match cpu_type {
+        0x0000_0007 => Some("x86"),
+        0x0100_0007 => Some("x86_64"),
+        0x0000_000C => Some("arm"),
+        0x0100_000C => Some("aarch64"),
+        _ => None,
+    }
+}
+
+fn pe_architecture(machine: u16) -> Option<&'static str> {
+    match machine {
+        0x014C => Some("x86"),
+        0x8664 => Some("x86_64"),
+        0xAA64 => Some("aarch64"),
+        _ => None,
+    }
+}
+
+/// Architectures an ELF, Mach-O (including universal) or PE executable was built for, in
+/// `std::env::consts::ARCH` naming. `None` when the header is not recognized.
+pub(crate) fn executable_architectures(header: &[u8]) -> Option<Vec<&'static str>> {
+    if header.starts_with(b"\x7FELF") {
+        let big_endian = *header.get(5)? == 2;
+        return elf_architecture(read_u16(header, 18, big_endian)?).map(|arch| vec![arch]);
+    }
+    if header.starts_with(b"MZ") {
+        let pe_offset = read_u32(header, 0x3C, false)? as usize;
+        if header.get(pe_offset..pe_offset + 4)? != b"PE\0\0" {
+            return None;
+        }
+        return pe_architecture(read_u16(header, pe_offset + 4, false)?).map(|arch| vec![arch]);
+    }
+    match read_u32(header, 0, true)? {
+        // Thin Mach-O, stored little-endian on every platform Rovex ships for.
+        0xCFFA_EDFE | 0xCEFA_EDFE => {
+            mach_o_architecture(read_u32(header, 4, false)?).map(|arch| vec![arch])
+        }
+        // Universal binary. Java class files share this magic but have far larger counts.
+        magic @ (0xCAFE_BABE | 0xCAFE_BABF) => {
+            let count = read_u32(header, 4, true)? as usize;
+            if count == 0 || count > 16 {
+                return None;
+            }
+            let entry_size = if magic == 0xCAFE_BABF { 32 } else { 20 };
+            let architectures = (0..count)
+                .filter_map(|index| read_u32(header, 8 + index * entry_size, true))
+                .filter_map(mach_o_architecture)
+                .collect::<Vec<_>>();
+            (!architectures.is_empty()).then_some(architectures)
+        }
+        _ => None,
+    }
+}
+
+fn sha256_hex(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+fn check_sidecar(path: &Path) -> Result<SidecarCheck, SidecarProblem> {
+    let unreadable = |error: io::Error| SidecarProblem::Unreadable {
+        path: path.to_path_buf(),
+        error: error.to_string(),
+    };
+    let mut header = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(HEADER_BYTES).read_to_end(&mut header))
+        .map_err(unreadable)?;
+    let architectures =
+        executable_architectures(&header).ok_or_else(|| SidecarProblem::UnrecognizedFormat {
+            path: path.to_path_buf(),
+        })?;
+    if !architectures.contains(&host_architecture()) {
+        return Err(SidecarProblem::ArchitectureMismatch {
+            path: path.to_path_buf(),
+            found: architectures,
+        });
+    }
+
+    let checksum_verified = match EXPECTED_SIDECAR_SHA256 {
+        Some(expected) => {
+            let actual = sha256_hex(path).map_err(unreadable)?;
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(SidecarProblem::ChecksumMismatch {
+                    path: path.to_path_buf(),
+                    expected: expected.to_string(),
+                    actual,
+                });
+            }
+            true
+        }
+        None => false,
+    };
+    Ok(SidecarCheck {
+        architecture: host_architecture().to_string(),
+        checksum_verified,
+    })
+}
+
+/// Checks that the bundled sidecar exists, targets this machine's architecture, and matches
+/// the checksum recorded at build time. Blocking; hashes the whole binary on first use.
+pub(crate) fn verify_opencode_sidecar() -> Result<SidecarCheck, SidecarProblem> {
+    let path = opencode_sidecar_path().map_err(|error| SidecarProblem::Unreadable {
+        path: PathBuf::from(OPENCODE_SIDECAR_NAME),
+        error,
+    })?;
+    let metadata = match fs::metadata(&path) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        _ => return Err(SidecarProblem::Missing { path }),
+    };
+    let key = (path.clone(), metadata.len(), metadata.modified().ok());
+    if let Ok(last) = last_sidecar_check().lock() {
+        if let Some((last_key, check)) = last.as_ref() {
+            if *last_key == key {
+                return Ok(check.clone());
+            }
+        }
+    }
+
+    let check = check_sidecar(&path)?;
+    if let Ok(mut last) = last_sidecar_check().lock() {
+        *last = Some((key, check.clone()));
+    }
+    Ok(check)
+}
+
+pub(crate) async fn verify_opencode_sidecar_async() -> Result<SidecarCheck, SidecarProblem> {
+    tauri::async_runtime::spawn_blocking(verify_opencode_sidecar)
+        .await
+        .unwrap_or_else(|error| {
+            Err(SidecarProblem::Unreadable {
+                path: PathBuf::from(OPENCODE_SIDECAR_NAME),
+                error: format!("Sidecar check stopped unexpectedly: {error}"),
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{executable_architectures, SidecarProblem};
+
+    fn elf(machine: u16) -> Vec<u8> {
+        let mut header = vec![0u8; 64];
+        header[..4].copy_from_slice(b"\x7FELF");
+        header[5] = 1;
+        header[18..20].copy_from_slice(&machine.to_le_bytes());
+        header
+    }
+
+    #[test]
+    fn recognizes_executable_architectures() {
+        assert_eq!(executable_architectures(&elf(0x3E)), Some(vec!["x86_64"]));
+        assert_eq!(executable_architectures(&elf(0xB7)), Some(vec!["aarch64"]));
+
+        let mut mach_o = vec![0xCF, 0xFA, 0xED, 0xFE];
+        mach_o.extend(0x0100_000Cu32.to_le_bytes());
+        assert_eq!(executable_architectures(&mach_o), Some(vec!["aarch64"]));
+
+        let mut universal = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 2];
+        for cpu_type in [0x0100_0007u32, 0x0100_000C] {
+            universal.extend(cpu_type.to_be_bytes());
+            universal.extend([0u8; 16]);
+        }
+        assert_eq!(
+            executable_architectures(&universal),
+            Some(vec!["x86_64", "aarch64"])
+        );
+
+        let mut pe = vec![0u8; 0x90];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3C..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+        pe[0x84..0x86].copy_from_slice(&0xAA64u16.to_le_bytes());
+        assert_eq!(executable_architectures(&pe), Some(vec!["aarch64"]));
+
+        assert_eq!(executable_architectures(b"#!/bin/sh\n"), None);
+        // A Java class file shares the universal binary magic.
+        assert_eq!(
+            executable_architectures(&[0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 52]),
+            None
+        );
+    }
+
+    #[test]
+    fn problems_name_a_code_and_a_fix() {
+        let problem = SidecarProblem::ArchitectureMismatch {
+            path: PathBuf::from("/Applications/Rovex.app/Contents/MacOS/opencode"),
+            found: vec!["riscv64"],
+        };
+        let message = problem.to_string();
+        assert!(message.contains("(architecture-mismatch)"));
+        assert!(message.contains("built for riscv64"));
+        assert!(message.contains("Fix: Install the"));
+        assert!(message.contains("ROVEX_REVIEW_PROVIDER"));
+    }
+}
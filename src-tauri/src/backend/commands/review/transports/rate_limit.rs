@@ -0,0 +1,237 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant, SystemTime},
+};
+
+use reqwest::header::HeaderMap;
+
+use super::super::super::common::{
+    parse_setting_u64, DEFAULT_REVIEW_REQUESTS_PER_MINUTE, ROVEX_REVIEW_REQUESTS_PER_MINUTE_ENV,
+};
+
+/// Requests that may be issued back to back before the bucket starts pacing them.
+const RATE_LIMIT_BURST: f64 = 4.0;
+const MAX_RETRY_AFTER_MS: u64 = 120_000;
+
+static PROVIDER_RATE_LIMITERS: OnceLock<Mutex<HashMap<String, Arc<ProviderRateLimiter>>>> =
+    OnceLock::new();
+
+struct BucketState {
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+/// Token bucket shared by every chunk worker that talks to the same provider and model.
+///
+/// A rate-limit response pauses the whole bucket, so parallel workers wait together instead
+/// of retrying independently and tripping the limit again.
+pub(crate) struct ProviderRateLimiter {
+    state: Mutex<BucketState>,
+}
+
+impl ProviderRateLimiter {
+    fn new(requests_per_minute: u64) -> Self {
+        Self {
+            state: Mutex::new(BucketState {
+                tokens: RATE_LIMIT_BURST,
+                refill_per_sec: requests_per_minute as f64 / 60.0,
+                last_refill: Instant::now(),
+                paused_until: None,
+            }),
+        }
+    }
+
+    fn set_requests_per_minute(&self, requests_per_minute: u64) {
+        if let Ok(mut state) = self.state.lock() {
+            state.refill_per_sec = requests_per_minute as f64 / 60.0;
+        }
+    }
+
+    /// Waits until the provider is not paused and a request token is available.
+    pub(crate) async fn acquire(&self) {
+        loop {
+            let wait = {
+                let Ok(mut state) = self.state.lock() else {
+                    return;
+                };
+                let now = Instant::now();
+                match state.paused_until {
+                    Some(until) if until > now => until - now,
+                    _ => {
+                        state.paused_until = None;
+                        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+                        state.tokens =
+                            (state.tokens + elapsed * state.refill_per_sec).min(RATE_LIMIT_BURST);
+                        state.last_refill = now;
+                        if state.tokens >= 1.0 {
+                            state.tokens -= 1.0;
+                            return;
+                        }
+                        Duration::from_secs_f64((1.0 - state.tokens) / state.refill_per_sec)
+                    }
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Blocks new requests for at least `delay`. Overlapping pauses keep the later deadline.
+    pub(crate) fn pause_for(&self, delay: Duration) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let until = Instant::now() + delay;
+        if state.paused_until.is_none_or(|current| current < until) {
+            state.paused_until = Some(until);
+        }
+        state.tokens = 0.0;
+    }
+}
+
+/// Returns the limiter shared by all requests to `provider` with `model`.
+pub(crate) fn provider_rate_limiter(provider: &str, model: &str) -> Arc<ProviderRateLimiter> {
+    let requests_per_minute = parse_setting_u64(
+        ROVEX_REVIEW_REQUESTS_PER_MINUTE_ENV,
+        DEFAULT_REVIEW_REQUESTS_PER_MINUTE,
+        1,
+    );
+    let key = format!("{provider}:{model}");
+    let limiters = PROVIDER_RATE_LIMITERS.get_or_init(|| Mutex::new(HashMap::new()));
+    let Ok(mut limiters) = limiters.lock() else {
+        return Arc::new(ProviderRateLimiter::new(requests_per_minute));
+    };
+    let limiter = limiters
+        .entry(key)
+        .or_insert_with(|| Arc::new(ProviderRateLimiter::new(requests_per_minute)))
+        .clone();
+    limiter.set_requests_per_minute(requests_per_minute);
+    limiter
+}
+
+/// Reads `retry-after-ms` or `Retry-After` (in seconds or as an HTTP date) from a
+/// rate-limited response.
+pub(crate) fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    parse_retry_after_at(headers, SystemTime::now())
+}
+
+fn parse_retry_after_at(headers: &HeaderMap, now: SystemTime) -> Option<Duration> {
+    let header_text = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+    };
+    let header_number = |name: &str| {
+        header_text(name)
+            .and_then(|value| value.parse::<f64>().ok())
+            .filter(|value| value.is_finite() && *value >= 0.0)
+    };
+    // A date already in the past means the limit has lifted.
+    let header_date = |name: &str| {
+        header_text(name)
+            .and_then(|value| httpdate::parse_http_date(value).ok())
+            .map(|date| date.duration_since(now).unwrap_or_default().as_millis() as f64)
+    };
+    let delay_ms = header_number("retry-after-ms")
+        .or_else(|| header_number("retry-after").map(|seconds| seconds * 1000.0))
+        .or_else(|| header_date("retry-after"))?;
+    Some(Duration::from_millis(
+        (delay_ms.ceil() as u64).min(MAX_RETRY_AFTER_MS),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    use super::{parse_retry_after_at, ProviderRateLimiter, MAX_RETRY_AFTER_MS, RATE_LIMIT_BURST};
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).expect("header value"));
+        headers
+    }
+
+    /// Runs `acquire` and returns how long it waited.
+    fn timed_acquire(limiter: &ProviderRateLimiter) -> Duration {
+        let started_at = Instant::now();
+        tauri::async_runtime::block_on(limiter.acquire());
+        started_at.elapsed()
+    }
+
+    #[test]
+    fn the_bucket_spends_its_burst_then_paces_requests() {
+        // Ten requests a second: a token every 100ms once the burst is spent.
+        let limiter = ProviderRateLimiter::new(600);
+        for _ in 0..RATE_LIMIT_BURST as usize {
+            assert!(timed_acquire(&limiter) < Duration::from_millis(50));
+        }
+        let paced = timed_acquire(&limiter);
+        assert!(paced >= Duration::from_millis(50), "waited {paced:?}");
+        assert!(paced < Duration::from_millis(1_000), "waited {paced:?}");
+    }
+
+    #[test]
+    fn idle_time_refills_the_bucket_up_to_the_burst() {
+        // One request a minute, so only the moved clock refills the bucket.
+        let limiter = ProviderRateLimiter::new(1);
+        for _ in 0..RATE_LIMIT_BURST as usize {
+            timed_acquire(&limiter);
+        }
+        limiter.state.lock().unwrap().last_refill -= Duration::from_secs(60);
+        assert!(timed_acquire(&limiter) < Duration::from_millis(50));
+        assert!(limiter.state.lock().unwrap().tokens < 1.0);
+
+        limiter.state.lock().unwrap().last_refill -= Duration::from_secs(3_600);
+        timed_acquire(&limiter);
+        let tokens = limiter.state.lock().unwrap().tokens;
+        assert!(
+            (tokens - (RATE_LIMIT_BURST - 1.0)).abs() < 0.01,
+            "{tokens} tokens"
+        );
+    }
+
+    #[test]
+    fn a_pause_holds_back_every_request_until_it_ends() {
+        let limiter = ProviderRateLimiter::new(6_000);
+        limiter.pause_for(Duration::from_millis(100));
+        limiter.pause_for(Duration::from_millis(10));
+        let paused = timed_acquire(&limiter);
+        assert!(paused >= Duration::from_millis(90), "waited {paused:?}");
+    }
+
+    #[test]
+    fn retry_after_reads_seconds_and_http_dates() {
+        let now = httpdate::parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").expect("date");
+        let at = |headers: &HeaderMap| parse_retry_after_at(headers, now);
+
+        assert_eq!(
+            at(&headers("retry-after", "7")),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(
+            at(&headers("retry-after-ms", "250.5")),
+            Some(Duration::from_millis(251))
+        );
+        assert_eq!(
+            at(&headers("retry-after", "Sun, 06 Nov 1994 08:50:07 GMT")),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            at(&headers("retry-after", "Sun, 06 Nov 1994 08:00:00 GMT")),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            at(&headers("retry-after", "Mon, 07 Nov 1994 08:49:37 GMT")),
+            Some(Duration::from_millis(MAX_RETRY_AFTER_MS))
+        );
+        assert_eq!(at(&headers("retry-after", "soon")), None);
+        assert_eq!(at(&headers("retry-after", "-3")), None);
+        assert_eq!(at(&HeaderMap::new()), None);
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    future::Future,
+    pin::pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+pub(crate) const SESSION_LOST_ERROR: &str = "Review transport session ended unexpectedly";
+
+/// How long a transport's reply may keep arriving after its process exits, so output that was
+/// already written when a session finished is not mistaken for a crash.
+const SESSION_EXIT_GRACE: Duration = Duration::from_millis(500);
+
+pub(crate) fn session_lost_error(transport: &str, detail: &str) -> String {
+    format!("{SESSION_LOST_ERROR}: {transport} {detail}.")
+}
+
+pub(crate) fn is_session_lost_error(message: &str) -> bool {
+    message.starts_with(SESSION_LOST_ERROR)
+}
+
+/// Waits for `request` unless `exited`, which resolves with a description of how the
+/// transport's process ended, finishes first. A request that then fails or does not finish
+/// within a short grace period fails with a [`SESSION_LOST_ERROR`], instead of the connection
+/// error or timeout it would otherwise end with.
+pub(crate) async fn watch_session<T>(
+    transport: &str,
+    request: impl Future<Output = Result<T, String>>,
+    exited: impl Future<Output = String>,
+) -> Result<T, String> {
+    let mut request = pin!(request);
+    tokio::select! {
+        biased;
+        result = &mut request => result,
+        detail = exited => match tokio::time::timeout(SESSION_EXIT_GRACE, request).await {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(_)) | Err(_) => Err(session_lost_error(transport, &detail)),
+        },
+    }
+}
+
+/// Transport sessions a run may restart after they die mid-chunk. Chunk workers share it, so
+/// a transport that keeps crashing fails the run's chunks instead of restarting forever.
+pub(crate) struct SessionRestartBudget {
+    remaining: AtomicUsize,
+}
+
+impl SessionRestartBudget {
+    pub(crate) fn new(restarts: usize) -> Self {
+        Self {
+            remaining: AtomicUsize::new(restarts),
+        }
+    }
+
+    /// Uses up one restart, or returns `false` once the run has none left.
+    pub(crate) fn try_take(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |remaining| {
+                remaining.checked_sub(1)
+            })
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_session_lost_error, watch_session, SessionRestartBudget};
+
+    #[test]
+    fn a_dead_process_fails_the_request_it_was_serving() {
+        tauri::async_runtime::block_on(async {
+            let lost = watch_session(
+                "Codex app-server",
+                std::future::pending::<Result<(), String>>(),
+                async { "exited with signal 9".to_string() },
+            )
+            .await
+            .unwrap_err();
+            assert!(is_session_lost_error(&lost));
+            assert!(lost.contains("Codex app-server exited with signal 9"));
+
+            let finished = watch_session(
+                "Codex app-server",
+                async { Ok::<_, String>("review") },
+                async { "exited with status 0".to_string() },
+            )
+            .await;
+            assert_eq!(finished.unwrap(), "review");
+        });
+
+        let budget = SessionRestartBudget::new(2);
+        assert!(budget.try_take());
+        assert!(budget.try_take());
+        assert!(!budget.try_take());
+    }
+}
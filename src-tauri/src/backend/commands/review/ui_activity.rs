@@ -0,0 +1,106 @@
+use std::{
+    sync::{Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use super::super::common::current_interactive_parallel_chunks_per_run;
+use crate::backend::SignalUiActivityInput;
+
+/// How long the app counts as interactive after a signal that names no duration.
+const DEFAULT_UI_ACTIVITY_WINDOW: Duration = Duration::from_millis(1_500);
+const MAX_UI_ACTIVITY_WINDOW: Duration = Duration::from_secs(10);
+/// Longest a progress write waits for the user to stop interacting, so someone who keeps
+/// scrolling delays persistence but never stops it.
+pub(crate) const MAX_PROGRESS_WRITE_DEFERRAL: Duration = Duration::from_secs(2);
+const UI_IDLE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// When the frontend last reported the user typing or scrolling, as the moment that activity
+/// stops counting.
+#[derive(Default)]
+struct UiActivity {
+    interactive_until: Mutex<Option<Instant>>,
+}
+
+impl UiActivity {
+    fn signal(&self, now: Instant, active: bool, window: Duration) {
+        let mut interactive_until = self
+            .interactive_until
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *interactive_until = if active {
+            let until = now + window.min(MAX_UI_ACTIVITY_WINDOW);
+            Some(interactive_until.map_or(until, |current| current.max(until)))
+        } else {
+            None
+        };
+    }
+
+    fn is_interactive(&self, now: Instant) -> bool {
+        self.interactive_until
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .is_some_and(|until| now < until)
+    }
+}
+
+static UI_ACTIVITY: OnceLock<UiActivity> = OnceLock::new();
+
+fn ui_activity() -> &'static UiActivity {
+    UI_ACTIVITY.get_or_init(UiActivity::default)
+}
+
+pub(crate) fn ui_is_interactive() -> bool {
+    ui_activity().is_interactive(Instant::now())
+}
+
+/// Chunks a run may have in flight: its own limit, lowered while the user is interacting so
+/// review workers leave the app's CPU share to the UI.
+pub(crate) fn chunk_parallelism(max_parallel_chunks: usize) -> usize {
+    if ui_is_interactive() {
+        max_parallel_chunks.min(current_interactive_parallel_chunks_per_run())
+    } else {
+        max_parallel_chunks
+    }
+}
+
+/// Waits until the user stops interacting, or at most `max_wait`.
+pub(crate) async fn wait_for_ui_idle(max_wait: Duration) {
+    let deadline = Instant::now() + max_wait;
+    while ui_is_interactive() && Instant::now() < deadline {
+        tokio::time::sleep(UI_IDLE_POLL_INTERVAL).await;
+    }
+}
+
+pub async fn signal_ui_activity(input: SignalUiActivityInput) -> Result<(), String> {
+    let window = input
+        .duration_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_UI_ACTIVITY_WINDOW);
+    ui_activity().signal(Instant::now(), input.active.unwrap_or(true), window);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{UiActivity, MAX_UI_ACTIVITY_WINDOW};
+
+    #[test]
+    fn activity_counts_until_its_window_ends() {
+        let activity = UiActivity::default();
+        let now = Instant::now();
+        assert!(!activity.is_interactive(now));
+
+        activity.signal(now, true, Duration::from_secs(2));
+        activity.signal(now, true, Duration::from_secs(1));
+        assert!(activity.is_interactive(now + Duration::from_millis(1_500)));
+        assert!(!activity.is_interactive(now + Duration::from_secs(2)));
+
+        activity.signal(now, true, Duration::from_secs(600));
+        assert!(!activity.is_interactive(now + MAX_UI_ACTIVITY_WINDOW));
+
+        activity.signal(now, false, Duration::ZERO);
+        assert!(!activity.is_interactive(now));
+    }
+}
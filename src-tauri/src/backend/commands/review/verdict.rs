@@ -0,0 +1,221 @@
+use super::super::common::{
+    parse_setting_usize, DEFAULT_VERDICT_MAX_CRITICAL, DEFAULT_VERDICT_MAX_HIGH,
+    ROVEX_REVIEW_VERDICT_MAX_CRITICAL_ENV, ROVEX_REVIEW_VERDICT_MAX_HIGH_ENV,
+};
+use crate::backend::AiReviewFinding;
+
+pub(crate) const VERDICT_PASS: &str = "pass";
+pub(crate) const VERDICT_FAIL: &str = "fail";
+
+pub(crate) const APPROVAL_PENDING: &str = "pending";
+pub(crate) const APPROVAL_APPROVED: &str = "approved";
+pub(crate) const APPROVAL_CHANGES_REQUESTED: &str = "changes-requested";
+
+pub(crate) fn parse_approval(value: &str) -> Result<&'static str, String> {
+    match value.trim().to_lowercase().as_str() {
+        "pending" => Ok(APPROVAL_PENDING),
+        "approved" | "approve" => Ok(APPROVAL_APPROVED),
+        "changes-requested" | "changes_requested" | "request-changes" => {
+            Ok(APPROVAL_CHANGES_REQUESTED)
+        }
+        _ => Err("Approval must be 'pending', 'approved', or 'changes-requested'.".to_string()),
+    }
+}
+
+/// Most critical and high findings a run may have and still pass. Medium and low findings
+/// never fail a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct VerdictThresholds {
+    pub(crate) max_critical: usize,
+    pub(crate) max_high: usize,
+}
+
+impl VerdictThresholds {
+    pub(crate) fn from_settings() -> Self {
+        Self {
+            max_critical: parse_setting_usize(
+                ROVEX_REVIEW_VERDICT_MAX_CRITICAL_ENV,
+                DEFAULT_VERDICT_MAX_CRITICAL,
+                0,
+            ),
+            max_high: parse_setting_usize(
+                ROVEX_REVIEW_VERDICT_MAX_HIGH_ENV,
+                DEFAULT_VERDICT_MAX_HIGH,
+                0,
+            ),
+        }
+    }
+}
+
+/// A finished run's verdict and, when it fails, which threshold was exceeded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RunVerdict {
+    pub(crate) verdict: &'static str,
+    pub(crate) reason: Option<String>,
+}
+
+fn describe_excess(count: usize, severity: &str, max: usize) -> String {
+    let noun = if count == 1 { "finding" } else { "findings" };
+    format!("{count} {severity} {noun} (at most {max} allowed)")
+}
+
+pub(crate) fn run_verdict(
+    findings: &[AiReviewFinding],
+    thresholds: VerdictThresholds,
+) -> RunVerdict {
+    let count = |severity: &str| {
+        findings
+            .iter()
+            .filter(|finding| finding.severity.trim().eq_ignore_ascii_case(severity))
+            .count()
+    };
+    let critical = count("critical");
+    let high = count("high");
+    let mut reasons = Vec::new();
+    if critical > thresholds.max_critical {
+        reasons.push(describe_excess(
+            critical,
+            "critical",
+            thresholds.max_critical,
+        ));
+    }
+    if high > thresholds.max_high {
+        reasons.push(describe_excess(high, "high", thresholds.max_high));
+    }
+    if reasons.is_empty() {
+        RunVerdict {
+            verdict: VERDICT_PASS,
+            reason: None,
+        }
+    } else {
+        RunVerdict {
+            verdict: VERDICT_FAIL,
+            reason: Some(reasons.join("; ")),
+        }
+    }
+}
+
+/// The verdict a run reports once a reviewer has ruled on it. A pending approval keeps the
+/// severity verdict; a decision replaces it, noting a severity failure it overrode.
+pub(crate) fn approved_verdict(
+    verdict: Option<String>,
+    reason: Option<String>,
+    approval: &str,
+    note: Option<&str>,
+) -> (Option<String>, Option<String>) {
+    let note = note.map(|note| format!(": {note}")).unwrap_or_default();
+    match approval {
+        APPROVAL_APPROVED => {
+            let reason = reason
+                .filter(|_| verdict.as_deref() == Some(VERDICT_FAIL))
+                .map(|reason| format!("Approved over {reason}{note}"));
+            (Some(VERDICT_PASS.to_string()), reason)
+        }
+        APPROVAL_CHANGES_REQUESTED => (
+            Some(VERDICT_FAIL.to_string()),
+            Some(format!("Changes requested{note}")),
+        ),
+        _ => (verdict, reason),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        approved_verdict, parse_approval, run_verdict, VerdictThresholds, APPROVAL_APPROVED,
+        APPROVAL_CHANGES_REQUESTED, APPROVAL_PENDING, VERDICT_FAIL, VERDICT_PASS,
+    };
+    use crate::backend::AiReviewFinding;
+
+    fn finding(severity: &str) -> AiReviewFinding {
+        AiReviewFinding {
+            id: "src/lib.rs#1:new:1:1".to_string(),
+            file_path: "src/lib.rs".to_string(),
+            chunk_id: "src/lib.rs#1".to_string(),
+            chunk_index: 1,
+            hunk_header: "@@ -1 +1 @@".to_string(),
+            side: "new".to_string(),
+            line_number: 1,
+            title: "Issue".to_string(),
+            body: "Body".to_string(),
+            severity: severity.to_string(),
+            confidence: None,
+            category: None,
+            fingerprint: None,
+            status: None,
+            code_hash: None,
+            recurring: None,
+            suggested_patch: None,
+            language: None,
+            blame: None,
+        }
+    }
+
+    #[test]
+    fn critical_or_too_many_high_findings_fail_the_run() {
+        let thresholds = VerdictThresholds {
+            max_critical: 0,
+            max_high: 2,
+        };
+        let passing = [
+            finding("high"),
+            finding("High"),
+            finding("medium"),
+            finding("low"),
+        ];
+        assert_eq!(run_verdict(&passing, thresholds).verdict, VERDICT_PASS);
+        assert_eq!(run_verdict(&[], thresholds).reason, None);
+
+        let failing = [
+            finding("critical"),
+            finding("high"),
+            finding("high"),
+            finding("high"),
+        ];
+        let verdict = run_verdict(&failing, thresholds);
+        assert_eq!(verdict.verdict, VERDICT_FAIL);
+        assert_eq!(
+            verdict.reason.as_deref(),
+            Some("1 critical finding (at most 0 allowed); 3 high findings (at most 2 allowed)")
+        );
+    }
+    #[test]
+    fn a_reviewer_decision_overrides_the_severity_verdict() {
+        let failed = || {
+            (
+                Some(VERDICT_FAIL.to_string()),
+                Some("1 critical finding (at most 0 allowed)".to_string()),
+            )
+        };
+        let (verdict, reason) = failed();
+        assert_eq!(
+            approved_verdict(verdict, reason, APPROVAL_PENDING, None),
+            failed()
+        );
+        let (verdict, reason) = failed();
+        assert_eq!(
+            approved_verdict(verdict, reason, APPROVAL_APPROVED, Some("false positive")),
+            (
+                Some(VERDICT_PASS.to_string()),
+                Some(
+                    "Approved over 1 critical finding (at most 0 allowed): false positive"
+                        .to_string()
+                )
+            )
+        );
+        assert_eq!(
+            approved_verdict(
+                Some(VERDICT_PASS.to_string()),
+                None,
+                APPROVAL_CHANGES_REQUESTED,
+                None
+            ),
+            (
+                Some(VERDICT_FAIL.to_string()),
+                Some("Changes requested".to_string())
+            )
+        );
+        assert_eq!(parse_approval(" Approved"), Ok(APPROVAL_APPROVED));
+        assert!(parse_approval("lgtm").is_err());
+    }
+}
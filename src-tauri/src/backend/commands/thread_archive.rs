@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+
+use libsql::Connection;
+use serde_json::{json, Map, Value};
+use tauri::State;
+
+use super::common::as_non_empty_trimmed;
+use super::threads::load_thread_by_id;
+use super::workspace_data::{current_timestamp, export_file_name, export_table_rows};
+use crate::backend::{
+    AppState, ExportThreadInput, ExportThreadResult, ImportThreadInput, ImportThreadResult,
+};
+
+/// Identifies thread archives, bumped when their layout changes.
+const THREAD_EXPORT_FORMAT: &str = "rovex-thread-export";
+const THREAD_EXPORT_VERSION: u64 = 1;
+
+/// Tables in a thread archive, parents first, with the rows that belong to thread `?1`.
+const THREAD_ARCHIVE_TABLES: [(&str, &str); 9] = [
+    ("threads", "id = ?1"),
+    ("messages", "thread_id = ?1"),
+    ("message_attachments", "thread_id = ?1"),
+    ("pull_request_comments", "thread_id = ?1"),
+    ("ai_review_runs", "thread_id = ?1"),
+    ("ai_review_findings", "thread_id = ?1"),
+    ("inline_review_comments", "thread_id = ?1"),
+    ("thread_diff_chunks", "thread_id = ?1"),
+    ("thread_settings", "thread_id = ?1"),
+];
+
+/// Inverse of `column_to_json`: embedded JSON goes back to text. Blob columns are not part
+/// of thread archives, so base64 strings stay text.
+fn json_to_column(value: &Value) -> libsql::Value {
+    match value {
+        Value::Null => libsql::Value::Null,
+        Value::Bool(value) => libsql::Value::Integer(i64::from(*value)),
+        Value::Number(number) => number
+            .as_i64()
+            .map(libsql::Value::Integer)
+            .unwrap_or_else(|| libsql::Value::Real(number.as_f64().unwrap_or_default())),
+        Value::String(text) => libsql::Value::Text(text.clone()),
+        Value::Array(_) | Value::Object(_) => libsql::Value::Text(value.to_string()),
+    }
+}
+
+async fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>, String> {
+    let mut rows = conn
+        .query(&format!("PRAGMA table_info({table})"), ())
+        .await
+        .map_err(|error| format!("Failed to read columns of {table}: {error}"))?;
+    let mut columns = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read column of {table}: {error}"))?
+    {
+        columns.push(
+            row.get::<String>(1)
+                .map_err(|error| format!("Failed to parse column of {table}: {error}"))?,
+        );
+    }
+    Ok(columns)
+}
+
+/// Inserts `row` into `table`, skipping fields this version has no column for, so archives
+/// from a newer rovex still import. Returns the new rowid.
+async fn insert_archived_row(
+    conn: &Connection,
+    table: &str,
+    columns: &[String],
+    row: &Map<String, Value>,
+) -> Result<i64, String> {
+    let (names, values): (Vec<&str>, Vec<libsql::Value>) = row
+        .iter()
+        .filter(|(name, _)| columns.contains(name))
+        .map(|(name, value)| (name.as_str(), json_to_column(value)))
+        .unzip();
+    let placeholders = (1..=names.len())
+        .map(|index| format!("?{index}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    conn.execute(
+        &format!(
+            "INSERT INTO {table} ({}) VALUES ({placeholders})",
+            names.join(", ")
+        ),
+        values,
+    )
+    .await
+    .map_err(|error| format!("Failed to import {table} row: {error}"))?;
+    let mut rows = conn
+        .query("SELECT last_insert_rowid()", ())
+        .await
+        .map_err(|error| format!("Failed to fetch imported {table} id: {error}"))?;
+    rows.next()
+        .await
+        .map_err(|error| format!("Failed to read imported {table} id row: {error}"))?
+        .ok_or_else(|| format!("Missing last_insert_rowid result after importing {table}."))?
+        .get(0)
+        .map_err(|error| format!("Failed to parse imported {table} id: {error}"))
+}
+
+/// Rows of `table` in the archive, each with the imported thread's id and, when given, the
+/// workspace on this machine.
+fn archived_rows(
+    tables: &Map<String, Value>,
+    table: &str,
+    thread_id: i64,
+    workspace: Option<&str>,
+) -> Result<Vec<Map<String, Value>>, String> {
+    let Some(rows) = tables.get(table) else {
+        return Ok(Vec::new());
+    };
+    let rows = rows
+        .as_array()
+        .ok_or_else(|| format!("Thread archive table {table} is not a list of rows."))?;
+    rows.iter()
+        .map(|row| {
+            let mut row = row
+                .as_object()
+                .cloned()
+                .ok_or_else(|| format!("Thread archive table {table} has a malformed row."))?;
+            row.insert("thread_id".to_string(), json!(thread_id));
+            // Keys are unique per database, so an imported copy must not claim the
+            // original's retries.
+            row.remove("idempotency_key");
+            if let Some(workspace) = workspace {
+                if row.contains_key("workspace") {
+                    row.insert("workspace".to_string(), json!(workspace));
+                }
+            }
+            Ok(row)
+        })
+        .collect()
+}
+
+pub async fn export_thread(
+    state: State<'_, AppState>,
+    input: ExportThreadInput,
+) -> Result<ExportThreadResult, String> {
+    let thread = load_thread_by_id(&state, input.thread_id).await?;
+    let conn = state.connection()?;
+    let exported_at = current_timestamp(&conn).await?;
+
+    let mut tables = Map::new();
+    let mut total_rows = 0;
+    for (table, filter) in THREAD_ARCHIVE_TABLES {
+        let rows = export_table_rows(&conn, table, filter, [thread.id]).await?;
+        total_rows += rows.len();
+        tables.insert(table.to_string(), Value::Array(rows));
+    }
+
+    let document = json!({
+        "format": THREAD_EXPORT_FORMAT,
+        "version": THREAD_EXPORT_VERSION,
+        "exportedAt": exported_at,
+        "tables": tables,
+    });
+    let content = serde_json::to_string_pretty(&document)
+        .map_err(|error| format!("Failed to serialize thread archive: {error}"))?;
+    Ok(ExportThreadResult {
+        thread_id: thread.id,
+        file_name: export_file_name("thread", &thread.title),
+        mime_type: "application/json".to_string(),
+        total_rows,
+        content,
+    })
+}
+
+/// Restores a thread archive as a new thread. Message ids and the thread id are assigned by
+/// this database; run ids are kept, so importing the same archive twice is rejected.
+pub async fn import_thread(
+    state: State<'_, AppState>,
+    input: ImportThreadInput,
+) -> Result<ImportThreadResult, String> {
+    let document: Value = serde_json::from_str(&input.content)
+        .map_err(|error| format!("Thread archive is not valid JSON: {error}"))?;
+    if document.get("format").and_then(Value::as_str) != Some(THREAD_EXPORT_FORMAT) {
+        return Err("The file is not a rovex thread archive.".to_string());
+    }
+    let version = document
+        .get("version")
+        .and_then(Value::as_u64)
+        .unwrap_or_default();
+    if version == 0 || version > THREAD_EXPORT_VERSION {
+        return Err(format!(
+            "Thread archive version {version} is not supported. Update rovex to import it."
+        ));
+    }
+    let tables = document
+        .get("tables")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "Thread archive has no tables.".to_string())?;
+    let workspace = as_non_empty_trimmed(input.workspace.as_deref());
+
+    let mut thread_row = match tables.get("threads").and_then(Value::as_array) {
+        Some(rows) if rows.len() == 1 => rows[0]
+            .as_object()
+            .cloned()
+            .ok_or_else(|| "Thread archive has a malformed thread row.".to_string())?,
+        _ => return Err("Thread archive must contain exactly one thread.".to_string()),
+    };
+    thread_row.remove("id");
+    if workspace.is_some() {
+        thread_row.insert("workspace".to_string(), json!(workspace));
+    }
+
+    let conn = state.connection()?;
+    let mut columns = HashMap::new();
+    for (table, _) in THREAD_ARCHIVE_TABLES {
+        columns.insert(table, table_columns(&conn, table).await?);
+    }
+
+    let transaction = conn
+        .transaction()
+        .await
+        .map_err(|error| format!("Failed to start the import transaction: {error}"))?;
+    let thread_id =
+        insert_archived_row(&transaction, "threads", &columns["threads"], &thread_row).await?;
+
+    // Messages get new ids here, so attachments and imported pull request comments are
+    // pointed at the imported copies.
+    let mut message_ids = HashMap::new();
+    let mut counts = HashMap::new();
+    for (table, _) in &THREAD_ARCHIVE_TABLES[1..] {
+        let rows = archived_rows(tables, table, thread_id, workspace.as_deref())?;
+        counts.insert(*table, rows.len());
+        for mut row in rows {
+            let archived_message_id = row.get("id").and_then(Value::as_i64);
+            match *table {
+                "messages" => {
+                    row.remove("id");
+                }
+                "message_attachments" | "pull_request_comments" => {
+                    row.remove("id");
+                    let message_id = row
+                        .get("message_id")
+                        .and_then(Value::as_i64)
+                        .and_then(|message_id| message_ids.get(&message_id))
+                        .ok_or_else(|| {
+                            format!("Thread archive has a {table} row without its message.")
+                        })?;
+                    row.insert("message_id".to_string(), json!(message_id));
+                }
+                "ai_review_runs" => {
+                    let run_id = row
+                        .get("run_id")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
+                    let mut existing = transaction
+                        .query(
+                            "SELECT 1 FROM ai_review_runs WHERE run_id = ?1",
+                            [run_id.to_string()],
+                        )
+                        .await
+                        .map_err(|error| format!("Failed to check run {run_id}: {error}"))?;
+                    if existing
+                        .next()
+                        .await
+                        .map_err(|error| format!("Failed to check run {run_id}: {error}"))?
+                        .is_some()
+                    {
+                        return Err(format!(
+                            "Review run {run_id} already exists, so this thread was already imported."
+                        ));
+                    }
+                    // Nothing would resume a run that was still going when it was exported.
+                    if matches!(
+                        row.get("status").and_then(Value::as_str),
+                        Some("queued" | "running")
+                    ) {
+                        row.insert("status".to_string(), json!("failed"));
+                        row.insert(
+                            "error".to_string(),
+                            json!("The run had not finished when the thread was exported."),
+                        );
+                    }
+                }
+                // Reviewer profiles are numbered per database.
+                "thread_settings" => {
+                    row.remove("profile_id");
+                }
+                _ => {}
+            }
+            let rowid = insert_archived_row(&transaction, table, &columns[table], &row).await?;
+            if let ("messages", Some(archived_message_id)) = (*table, archived_message_id) {
+                message_ids.insert(archived_message_id, rowid);
+            }
+        }
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(|error| format!("Failed to commit the import transaction: {error}"))?;
+
+    Ok(ImportThreadResult {
+        thread: load_thread_by_id(&state, thread_id).await?,
+        message_count: counts["messages"],
+        attachment_count: counts["message_attachments"],
+        run_count: counts["ai_review_runs"],
+        finding_count: counts["ai_review_findings"],
+        comment_count: counts["inline_review_comments"],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::workspace_data::column_to_json;
+    use super::{archived_rows, json_to_column};
+    use serde_json::json;
+
+    #[test]
+    fn archived_columns_round_trip_and_rows_move_to_the_new_thread() {
+        for (name, value) in [
+            (
+                "findings_json",
+                libsql::Value::Text("[{\"id\":1}]".to_string()),
+            ),
+            ("line_number", libsql::Value::Integer(12)),
+            ("estimated_cost_usd", libsql::Value::Real(0.5)),
+            ("error", libsql::Value::Null),
+        ] {
+            assert_eq!(json_to_column(&column_to_json(name, value.clone())), value);
+        }
+
+        let tables = json!({
+            "messages": [{ "id": 4, "thread_id": 9, "content": "hi" }],
+            "ai_review_runs": [{ "run_id": "r1", "thread_id": 9, "workspace": "/old" }],
+        });
+        let tables = tables.as_object().unwrap();
+        let runs = archived_rows(tables, "ai_review_runs", 2, Some("/new")).unwrap();
+        assert_eq!(runs[0]["thread_id"], json!(2));
+        assert_eq!(runs[0]["workspace"], json!("/new"));
+        let messages = archived_rows(tables, "messages", 2, Some("/new")).unwrap();
+        assert!(!messages[0].contains_key("workspace"));
+        assert!(archived_rows(tables, "inline_review_comments", 2, None)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+use super::common::as_non_empty_trimmed;
+use super::review::reviewer_profiles::load_reviewer_profile;
+use super::threads::load_thread_by_id;
+use crate::backend::{
+    AppState, GetThreadSettingsInput, SetThreadSettingsInput, StartAiReviewRunInput,
+    ThreadSettings, WorkspaceDiffTarget,
+};
+
+async fn load_thread_settings(state: &AppState, thread_id: i64) -> Result<ThreadSettings, String> {
+    let thread = load_thread_by_id(state, thread_id).await?;
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT base_ref, profile_id, last_run_id, updated_at
+             FROM thread_settings WHERE thread_id = ?1",
+            [thread_id],
+        )
+        .await
+        .map_err(|error| format!("Failed to load settings for thread {thread_id}: {error}"))?;
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read settings for thread {thread_id}: {error}"))?
+    else {
+        return Ok(ThreadSettings {
+            thread_id,
+            workspace: thread.workspace,
+            base_ref: None,
+            profile_id: None,
+            last_run_id: None,
+            updated_at: None,
+        });
+    };
+    Ok(ThreadSettings {
+        thread_id,
+        workspace: thread.workspace,
+        base_ref: row
+            .get(0)
+            .map_err(|error| format!("Failed to parse thread base ref: {error}"))?,
+        profile_id: row
+            .get(1)
+            .map_err(|error| format!("Failed to parse thread reviewer profile: {error}"))?,
+        last_run_id: row
+            .get(2)
+            .map_err(|error| format!("Failed to parse thread last run id: {error}"))?,
+        updated_at: row
+            .get(3)
+            .map_err(|error| format!("Failed to parse thread settings timestamp: {error}"))?,
+    })
+}
+
+/// Remembers `run_id` as the thread's last run. The thread's first run also fills in the
+/// defaults that were not set beforehand, and its workspace when the thread has none.
+pub(crate) async fn record_thread_run(
+    state: &AppState,
+    run_id: &str,
+    input: &StartAiReviewRunInput,
+) -> Result<(), String> {
+    // Uncommitted targets compare against HEAD, which is no default for a branch review.
+    let base_ref = match input.diff_target.unwrap_or_default() {
+        WorkspaceDiffTarget::Branch => as_non_empty_trimmed(Some(&input.base_ref)),
+        _ => None,
+    };
+    let conn = state.connection()?;
+    conn.execute(
+        "INSERT INTO thread_settings (thread_id, base_ref, profile_id, last_run_id)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(thread_id) DO UPDATE SET
+           base_ref = CASE WHEN thread_settings.last_run_id IS NULL
+             THEN COALESCE(thread_settings.base_ref, excluded.base_ref)
+             ELSE thread_settings.base_ref END,
+           profile_id = CASE WHEN thread_settings.last_run_id IS NULL
+             THEN COALESCE(thread_settings.profile_id, excluded.profile_id)
+             ELSE thread_settings.profile_id END,
+           last_run_id = excluded.last_run_id,
+           updated_at = CURRENT_TIMESTAMP",
+        libsql::params![
+            input.thread_id,
+            base_ref,
+            input.profile_id,
+            run_id.to_string()
+        ],
+    )
+    .await
+    .map_err(|error| {
+        format!(
+            "Failed to save settings for thread {}: {error}",
+            input.thread_id
+        )
+    })?;
+    conn.execute(
+        "UPDATE threads SET workspace = ?2
+         WHERE id = ?1 AND (workspace IS NULL OR TRIM(workspace) = '')",
+        (input.thread_id, input.workspace.trim().to_string()),
+    )
+    .await
+    .map_err(|error| {
+        format!(
+            "Failed to link thread {} to its workspace: {error}",
+            input.thread_id
+        )
+    })?;
+    Ok(())
+}
+
+pub async fn get_thread_settings(
+    state: &AppState,
+    input: GetThreadSettingsInput,
+) -> Result<ThreadSettings, String> {
+    load_thread_settings(state, input.thread_id).await
+}
+
+/// Replaces the thread's default base ref and reviewer profile; `None` clears either.
+pub async fn set_thread_settings(
+    state: &AppState,
+    input: SetThreadSettingsInput,
+) -> Result<ThreadSettings, String> {
+    load_thread_by_id(state, input.thread_id).await?;
+    let base_ref = as_non_empty_trimmed(input.base_ref.as_deref());
+    if base_ref
+        .as_deref()
+        .is_some_and(|value| value.starts_with('-'))
+    {
+        return Err("Base ref must not start with '-'.".to_string());
+    }
+    if let Some(profile_id) = input.profile_id {
+        load_reviewer_profile(state, profile_id).await?;
+    }
+
+    let conn = state.connection()?;
+    conn.execute(
+        "INSERT INTO thread_settings (thread_id, base_ref, profile_id) VALUES (?1, ?2, ?3)
+         ON CONFLICT(thread_id) DO UPDATE SET
+           base_ref = excluded.base_ref,
+           profile_id = excluded.profile_id,
+           updated_at = CURRENT_TIMESTAMP",
+        libsql::params![input.thread_id, base_ref, input.profile_id],
+    )
+    .await
+    .map_err(|error| {
+        format!(
+            "Failed to save settings for thread {}: {error}",
+            input.thread_id
+        )
+    })?;
+    load_thread_settings(state, input.thread_id).await
+}
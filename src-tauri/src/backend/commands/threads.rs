@@ -1,19 +1,49 @@
 use tauri::State;
 
-use super::common::{parse_limit, parse_message_role};
+use super::common::{parse_bool_i64, parse_idempotency_key, parse_limit, parse_message_role};
 use crate::backend::{
-    AddThreadMessageInput, AppState, BackendHealth, CreateThreadInput, Message, MessageRole, Thread,
+    AddThreadMessageInput, AppState, ArchiveThreadInput, BackendHealth, CreateThreadInput,
+    ListThreadMessagesInput, ListThreadMessagesResult, Message, MessageRole, PinThreadInput,
+    RenameThreadInput, Thread,
 };
 
+const THREAD_COLUMNS: &str = "id, title, workspace, created_at, pinned, archived_at";
+
+fn parse_thread_from_row(row: &libsql::Row) -> Result<Thread, String> {
+    let pinned: i64 = row
+        .get(4)
+        .map_err(|error| format!("Failed to parse thread pinned: {error}"))?;
+    Ok(Thread {
+        id: row
+            .get(0)
+            .map_err(|error| format!("Failed to parse thread id: {error}"))?,
+        title: row
+            .get(1)
+            .map_err(|error| format!("Failed to parse thread title: {error}"))?,
+        workspace: row
+            .get(2)
+            .map_err(|error| format!("Failed to parse thread workspace: {error}"))?,
+        created_at: row
+            .get(3)
+            .map_err(|error| format!("Failed to parse thread created_at: {error}"))?,
+        pinned: pinned != 0,
+        archived_at: row
+            .get(5)
+            .map_err(|error| format!("Failed to parse thread archived_at: {error}"))?,
+    })
+}
+
+/// Stores a message and returns its id, or `None` when `content` is blank and nothing was
+/// stored.
 pub(crate) async fn persist_thread_message(
     state: &AppState,
     thread_id: i64,
     role: MessageRole,
     content: &str,
-) -> Result<(), String> {
+) -> Result<Option<i64>, String> {
     let normalized = content.trim();
     if normalized.is_empty() {
-        return Ok(());
+        return Ok(None);
     }
 
     let conn = state.connection()?;
@@ -23,6 +53,26 @@ pub(crate) async fn persist_thread_message(
     )
     .await
     .map_err(|error| format!("Failed to persist thread message: {error}"))?;
+    let mut rows = conn
+        .query("SELECT last_insert_rowid()", ())
+        .await
+        .map_err(|error| format!("Failed to fetch persisted message id: {error}"))?;
+    let message_id = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read persisted message id row: {error}"))?
+        .ok_or_else(|| "Missing last_insert_rowid result after persisting a message.".to_string())?
+        .get(0)
+        .map_err(|error| format!("Failed to parse persisted message id: {error}"))?;
+    Ok(Some(message_id))
+}
+
+/// Removes one message. Its attachments are removed with it.
+pub(crate) async fn delete_thread_message(state: &AppState, message_id: i64) -> Result<(), String> {
+    let conn = state.connection()?;
+    conn.execute("DELETE FROM messages WHERE id = ?1", [message_id])
+        .await
+        .map_err(|error| format!("Failed to delete message {message_id}: {error}"))?;
     Ok(())
 }
 
@@ -30,7 +80,7 @@ pub(crate) async fn load_thread_by_id(state: &AppState, thread_id: i64) -> Resul
     let conn = state.connection()?;
     let mut rows = conn
         .query(
-            "SELECT id, title, workspace, created_at FROM threads WHERE id = ?1 LIMIT 1",
+            &format!("SELECT {THREAD_COLUMNS} FROM threads WHERE id = ?1 LIMIT 1"),
             [thread_id],
         )
         .await
@@ -44,20 +94,7 @@ pub(crate) async fn load_thread_by_id(state: &AppState, thread_id: i64) -> Resul
         return Err(format!("Thread {thread_id} was not found."));
     };
 
-    Ok(Thread {
-        id: row
-            .get(0)
-            .map_err(|error| format!("Failed to parse thread id: {error}"))?,
-        title: row
-            .get(1)
-            .map_err(|error| format!("Failed to parse thread title: {error}"))?,
-        workspace: row
-            .get(2)
-            .map_err(|error| format!("Failed to parse thread workspace: {error}"))?,
-        created_at: row
-            .get(3)
-            .map_err(|error| format!("Failed to parse thread created_at: {error}"))?,
-    })
+    parse_thread_from_row(&row)
 }
 
 pub(crate) async fn load_message_by_id(
@@ -81,6 +118,10 @@ pub(crate) async fn load_message_by_id(
         return Err(format!("Message {message_id} was not found."));
     };
 
+    parse_message_from_row(&row)
+}
+
+fn parse_message_from_row(row: &libsql::Row) -> Result<Message, String> {
     let role: String = row
         .get(2)
         .map_err(|error| format!("Failed to parse message role: {error}"))?;
@@ -171,26 +212,16 @@ pub async fn backend_health(state: State<'_, AppState>) -> Result<BackendHealth,
     })
 }
 
-pub async fn create_thread(
-    state: State<'_, AppState>,
-    input: CreateThreadInput,
+async fn insert_thread(
+    state: &AppState,
+    title: &str,
+    workspace: Option<String>,
+    idempotency_key: Option<String>,
 ) -> Result<Thread, String> {
-    let title = input.title.trim();
-    if title.is_empty() {
-        return Err("Thread title must not be empty.".to_string());
-    }
-
-    let workspace = input
-        .workspace
-        .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(ToOwned::to_owned);
-
     let conn = state.connection()?;
     conn.execute(
-        "INSERT INTO threads (title, workspace) VALUES (?1, ?2)",
-        (title.to_owned(), workspace),
+        "INSERT INTO threads (title, workspace, idempotency_key) VALUES (?1, ?2, ?3)",
+        (title.to_owned(), workspace, idempotency_key),
     )
     .await
     .map_err(|error| format!("Failed to create thread: {error}"))?;
@@ -207,18 +238,128 @@ pub async fn create_thread(
         .get(0)
         .map_err(|error| format!("Failed to parse new thread id: {error}"))?;
 
-    load_thread_by_id(&state, thread_id).await
+    load_thread_by_id(state, thread_id).await
+}
+
+/// Returns the most recent thread with `title` in `workspace`, creating it when missing.
+pub(crate) async fn find_or_create_thread(
+    state: &AppState,
+    title: &str,
+    workspace: &str,
+) -> Result<Thread, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT id FROM threads WHERE title = ?1 AND workspace = ?2 ORDER BY id DESC LIMIT 1",
+            (title.to_string(), workspace.to_string()),
+        )
+        .await
+        .map_err(|error| format!("Failed to look up thread: {error}"))?;
+    if let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read thread row: {error}"))?
+    {
+        let thread_id = row
+            .get(0)
+            .map_err(|error| format!("Failed to parse thread id: {error}"))?;
+        return load_thread_by_id(state, thread_id).await;
+    }
+    insert_thread(state, title, Some(workspace.to_string()), None).await
 }
 
+fn parse_thread_title(value: &str) -> Result<&str, String> {
+    let title = value.trim();
+    if title.is_empty() {
+        return Err("Thread title must not be empty.".to_string());
+    }
+    Ok(title)
+}
+
+pub async fn create_thread(
+    state: State<'_, AppState>,
+    input: CreateThreadInput,
+) -> Result<Thread, String> {
+    let title = parse_thread_title(&input.title)?;
+
+    let workspace = input
+        .workspace
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned);
+
+    let Some(key) = parse_idempotency_key(input.idempotency_key.as_deref())? else {
+        return insert_thread(&state, title, workspace, None).await;
+    };
+    if let Some(thread) = load_thread_by_idempotency_key(&state, &key).await? {
+        return Ok(thread);
+    }
+    match insert_thread(&state, title, workspace, Some(key.clone())).await {
+        Ok(thread) => Ok(thread),
+        // A concurrent retry with the same key inserted first.
+        Err(error) => load_thread_by_idempotency_key(&state, &key)
+            .await?
+            .ok_or(error),
+    }
+}
+
+async fn load_thread_by_idempotency_key(
+    state: &AppState,
+    key: &str,
+) -> Result<Option<Thread>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!("SELECT {THREAD_COLUMNS} FROM threads WHERE idempotency_key = ?1 LIMIT 1"),
+            [key.to_string()],
+        )
+        .await
+        .map_err(|error| format!("Failed to look up thread by idempotency key: {error}"))?;
+    rows.next()
+        .await
+        .map_err(|error| format!("Failed to read thread row: {error}"))?
+        .map(|row| parse_thread_from_row(&row))
+        .transpose()
+}
+
+async fn load_message_by_idempotency_key(
+    state: &AppState,
+    thread_id: i64,
+    key: &str,
+) -> Result<Option<Message>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT id, thread_id, role, content, created_at FROM messages
+             WHERE thread_id = ?1 AND idempotency_key = ?2 LIMIT 1",
+            (thread_id, key.to_string()),
+        )
+        .await
+        .map_err(|error| format!("Failed to look up message by idempotency key: {error}"))?;
+    rows.next()
+        .await
+        .map_err(|error| format!("Failed to read message row: {error}"))?
+        .map(|row| parse_message_from_row(&row))
+        .transpose()
+}
+
+/// Pinned threads first, then newest first. Archived threads are left out unless requested.
 pub async fn list_threads(
     state: State<'_, AppState>,
     limit: Option<u32>,
+    include_archived: Option<bool>,
 ) -> Result<Vec<Thread>, String> {
     let conn = state.connection()?;
     let mut rows = conn
         .query(
-            "SELECT id, title, workspace, created_at FROM threads ORDER BY created_at DESC LIMIT ?1",
-            [parse_limit(limit)],
+            &format!(
+                "SELECT {THREAD_COLUMNS} FROM threads
+                 WHERE ?2 OR archived_at IS NULL
+                 ORDER BY pinned DESC, created_at DESC, id DESC
+                 LIMIT ?1"
+            ),
+            (parse_limit(limit), include_archived.unwrap_or(false)),
         )
         .await
         .map_err(|error| format!("Failed to list threads: {error}"))?;
@@ -229,25 +370,73 @@ pub async fn list_threads(
         .await
         .map_err(|error| format!("Failed to read thread rows: {error}"))?
     {
-        threads.push(Thread {
-            id: row
-                .get(0)
-                .map_err(|error| format!("Failed to parse thread id: {error}"))?,
-            title: row
-                .get(1)
-                .map_err(|error| format!("Failed to parse thread title: {error}"))?,
-            workspace: row
-                .get(2)
-                .map_err(|error| format!("Failed to parse thread workspace: {error}"))?,
-            created_at: row
-                .get(3)
-                .map_err(|error| format!("Failed to parse thread created_at: {error}"))?,
-        });
+        threads.push(parse_thread_from_row(&row)?);
     }
 
     Ok(threads)
 }
 
+pub(crate) async fn set_thread_title(
+    state: &AppState,
+    thread_id: i64,
+    title: &str,
+) -> Result<Thread, String> {
+    let title = parse_thread_title(title)?;
+    let _ = load_thread_by_id(state, thread_id).await?;
+    let conn = state.connection()?;
+    conn.execute(
+        "UPDATE threads SET title = ?1 WHERE id = ?2",
+        (title.to_string(), thread_id),
+    )
+    .await
+    .map_err(|error| format!("Failed to rename thread: {error}"))?;
+    load_thread_by_id(state, thread_id).await
+}
+
+pub async fn rename_thread(
+    state: State<'_, AppState>,
+    input: RenameThreadInput,
+) -> Result<Thread, String> {
+    set_thread_title(&state, input.thread_id, &input.title).await
+}
+
+/// Archives the thread, or restores it with `archived: false`. Archiving keeps the
+/// original archive time when repeated.
+pub async fn archive_thread(
+    state: State<'_, AppState>,
+    input: ArchiveThreadInput,
+) -> Result<Thread, String> {
+    let _ = load_thread_by_id(&state, input.thread_id).await?;
+    let conn = state.connection()?;
+    conn.execute(
+        "UPDATE threads
+         SET archived_at = CASE WHEN ?1 THEN COALESCE(archived_at, CURRENT_TIMESTAMP) END
+         WHERE id = ?2",
+        (input.archived.unwrap_or(true), input.thread_id),
+    )
+    .await
+    .map_err(|error| format!("Failed to archive thread: {error}"))?;
+    load_thread_by_id(&state, input.thread_id).await
+}
+
+pub async fn pin_thread(
+    state: State<'_, AppState>,
+    input: PinThreadInput,
+) -> Result<Thread, String> {
+    let _ = load_thread_by_id(&state, input.thread_id).await?;
+    let conn = state.connection()?;
+    conn.execute(
+        "UPDATE threads SET pinned = ?1 WHERE id = ?2",
+        (
+            parse_bool_i64(input.pinned.unwrap_or(true)),
+            input.thread_id,
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to pin thread: {error}"))?;
+    load_thread_by_id(&state, input.thread_id).await
+}
+
 pub async fn delete_thread(state: State<'_, AppState>, thread_id: i64) -> Result<bool, String> {
     let _ = load_thread_by_id(&state, thread_id).await?;
     let conn = state.connection()?;
@@ -269,14 +458,36 @@ pub async fn add_thread_message(
     }
 
     let _ = load_thread_by_id(&state, input.thread_id).await?;
+    let idempotency_key = parse_idempotency_key(input.idempotency_key.as_deref())?;
+    if let Some(key) = idempotency_key.as_deref() {
+        if let Some(message) = load_message_by_idempotency_key(&state, input.thread_id, key).await?
+        {
+            return Ok(message);
+        }
+    }
 
     let conn = state.connection()?;
-    conn.execute(
-        "INSERT INTO messages (thread_id, role, content) VALUES (?1, ?2, ?3)",
-        (input.thread_id, input.role.as_str(), content.to_owned()),
-    )
-    .await
-    .map_err(|error| format!("Failed to add thread message: {error}"))?;
+    let inserted = conn
+        .execute(
+            "INSERT INTO messages (thread_id, role, content, idempotency_key) VALUES (?1, ?2, ?3, ?4)",
+            (
+                input.thread_id,
+                input.role.as_str(),
+                content.to_owned(),
+                idempotency_key.clone(),
+            ),
+        )
+        .await
+        .map_err(|error| format!("Failed to add thread message: {error}"));
+    if let Err(error) = inserted {
+        // A concurrent retry with the same key inserted first.
+        let Some(key) = idempotency_key.as_deref() else {
+            return Err(error);
+        };
+        return load_message_by_idempotency_key(&state, input.thread_id, key)
+            .await?
+            .ok_or(error);
+    }
 
     let mut rows = conn
         .query("SELECT last_insert_rowid()", ())
@@ -293,18 +504,31 @@ pub async fn add_thread_message(
     load_message_by_id(&state, message_id).await
 }
 
+/// Pages through a thread's messages, oldest first within a page. Without a cursor the
+/// newest `limit` messages are returned; `beforeId` pages back to older messages and `afterId`
+/// forward to newer ones. `hasMore` tells whether the paging direction has further messages.
 pub async fn list_thread_messages(
     state: State<'_, AppState>,
-    thread_id: i64,
-    limit: Option<u32>,
-) -> Result<Vec<Message>, String> {
-    let _ = load_thread_by_id(&state, thread_id).await?;
+    input: ListThreadMessagesInput,
+) -> Result<ListThreadMessagesResult, String> {
+    if input.before_id.is_some() && input.after_id.is_some() {
+        return Err("Pass either beforeId or afterId, not both.".to_string());
+    }
+    let _ = load_thread_by_id(&state, input.thread_id).await?;
     let conn = state.connection()?;
+    let limit = parse_limit(input.limit);
+    let is_forward = input.after_id.is_some();
 
     let mut rows = conn
         .query(
-            "SELECT id, thread_id, role, content, created_at FROM messages WHERE thread_id = ?1 ORDER BY created_at ASC LIMIT ?2",
-            (thread_id, parse_limit(limit)),
+            &format!(
+                "SELECT id, thread_id, role, content, created_at FROM messages
+                 WHERE thread_id = ?1 AND (?3 IS NULL OR id < ?3) AND (?4 IS NULL OR id > ?4)
+                 ORDER BY id {}
+                 LIMIT ?2",
+                if is_forward { "ASC" } else { "DESC" }
+            ),
+            (input.thread_id, limit + 1, input.before_id, input.after_id),
         )
         .await
         .map_err(|error| format!("Failed to list thread messages: {error}"))?;
@@ -315,26 +539,35 @@ pub async fn list_thread_messages(
         .await
         .map_err(|error| format!("Failed to read message rows: {error}"))?
     {
-        let role: String = row
-            .get(2)
-            .map_err(|error| format!("Failed to parse message role: {error}"))?;
-
-        messages.push(Message {
-            id: row
-                .get(0)
-                .map_err(|error| format!("Failed to parse message id: {error}"))?,
-            thread_id: row
-                .get(1)
-                .map_err(|error| format!("Failed to parse message thread_id: {error}"))?,
-            role: parse_message_role(role)?,
-            content: row
-                .get(3)
-                .map_err(|error| format!("Failed to parse message content: {error}"))?,
-            created_at: row
-                .get(4)
-                .map_err(|error| format!("Failed to parse message created_at: {error}"))?,
-        });
+        messages.push(parse_message_from_row(&row)?);
+    }
+    let has_more = messages.len() as i64 > limit;
+    messages.truncate(limit as usize);
+    if !is_forward {
+        messages.reverse();
     }
 
-    Ok(messages)
+    let mut rows = conn
+        .query(
+            "SELECT COUNT(*) FROM messages WHERE thread_id = ?1",
+            [input.thread_id],
+        )
+        .await
+        .map_err(|error| format!("Failed to count thread messages: {error}"))?;
+    let total_count = match rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read thread message count: {error}"))?
+    {
+        Some(row) => row
+            .get(0)
+            .map_err(|error| format!("Failed to parse thread message count: {error}"))?,
+        None => 0,
+    };
+
+    Ok(ListThreadMessagesResult {
+        messages,
+        total_count,
+        has_more,
+    })
 }
@@ -0,0 +1,430 @@
+use std::path::Path;
+
+use base64::Engine as _;
+use libsql::params::IntoParams;
+use libsql::Connection;
+use serde_json::{json, Map, Value};
+use tauri::State;
+
+use super::common::format_path;
+use super::workspace_git::{remove_review_git_hook, remove_run_snapshot_worktree};
+use crate::backend::paths::normalize_path;
+use crate::backend::{
+    AppState, ExportWorkspaceDataInput, ExportWorkspaceDataResult, PurgeWorkspaceDataInput,
+    PurgeWorkspaceDataResult, PurgedWorkspaceTable,
+};
+
+/// Identifies export documents, bumped when their layout changes.
+const WORKSPACE_EXPORT_FORMAT: &str = "rovex-workspace-export";
+const WORKSPACE_EXPORT_VERSION: u32 = 1;
+
+/// Tables holding workspace data with the rows that belong to the workspace. Rows that
+/// reference threads come before `threads`, so the purge subqueries still see them.
+const WORKSPACE_DATA_TABLES: [(&str, &str); 17] = [
+    (
+        "thread_settings",
+        "thread_id IN (SELECT id FROM threads WHERE workspace IN (?1, ?2))",
+    ),
+    (
+        "thread_diff_chunks",
+        "thread_id IN (SELECT id FROM threads WHERE workspace IN (?1, ?2))",
+    ),
+    (
+        "pull_request_comments",
+        "thread_id IN (SELECT id FROM threads WHERE workspace IN (?1, ?2))",
+    ),
+    (
+        "message_attachments",
+        "thread_id IN (SELECT id FROM threads WHERE workspace IN (?1, ?2))",
+    ),
+    (
+        "messages",
+        "thread_id IN (SELECT id FROM threads WHERE workspace IN (?1, ?2))",
+    ),
+    (
+        "ai_review_findings",
+        "workspace IN (?1, ?2) OR run_id IN (
+           SELECT run_id FROM ai_review_runs
+           WHERE workspace IN (?1, ?2)
+              OR thread_id IN (SELECT id FROM threads WHERE workspace IN (?1, ?2)))",
+    ),
+    (
+        "inline_review_comments",
+        "workspace IN (?1, ?2) OR thread_id IN (SELECT id FROM threads WHERE workspace IN (?1, ?2))",
+    ),
+    (
+        "ai_review_runs",
+        "workspace IN (?1, ?2) OR thread_id IN (SELECT id FROM threads WHERE workspace IN (?1, ?2))",
+    ),
+    ("review_finding_states", "workspace IN (?1, ?2)"),
+    ("run_snapshots", "workspace IN (?1, ?2)"),
+    ("review_git_hooks", "workspace IN (?1, ?2)"),
+    (
+        "review_schedules",
+        "workspace IN (?1, ?2) OR thread_id IN (SELECT id FROM threads WHERE workspace IN (?1, ?2))",
+    ),
+    ("review_group_runs", "workspace IN (?1, ?2)"),
+    ("workspace_sync_status", "workspace IN (?1, ?2)"),
+    ("workspace_trust", "workspace IN (?1, ?2)"),
+    ("code_intel_configs", "workspace IN (?1, ?2)"),
+    ("threads", "workspace IN (?1, ?2)"),
+];
+
+/// Code-intel tables, keyed by project root. They only exist once a sync has written to the
+/// app database.
+const CODE_INTEL_TABLES: [&str; 3] = [
+    "code_embedding_chunks",
+    "code_graph_nodes",
+    "code_intel_file_states",
+];
+
+/// The workspace as typed and as the app stores it, since rows may carry either form.
+fn workspace_keys(value: &str) -> Result<(String, String), String> {
+    let workspace = value.trim();
+    if workspace.is_empty() {
+        return Err("Workspace path must not be empty.".to_string());
+    }
+    let normalized = format_path(&normalize_path(Path::new(workspace))?);
+    Ok((workspace.to_string(), normalized))
+}
+
+async fn table_exists(conn: &Connection, table: &str) -> Result<bool, String> {
+    let mut rows = conn
+        .query(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [table.to_string()],
+        )
+        .await
+        .map_err(|error| format!("Failed to look up table {table}: {error}"))?;
+    let row = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read table {table}: {error}"))?;
+    Ok(row.is_some())
+}
+
+/// `(table, filter)` pairs of workspace data, including code-intel tables when they exist.
+async fn workspace_data_targets(
+    conn: &Connection,
+) -> Result<Vec<(&'static str, &'static str)>, String> {
+    let mut targets = WORKSPACE_DATA_TABLES.to_vec();
+    for table in CODE_INTEL_TABLES {
+        if table_exists(conn, table).await? {
+            targets.push((table, "project_root IN (?1, ?2)"));
+        }
+    }
+    Ok(targets)
+}
+
+async fn count_rows(
+    conn: &Connection,
+    table: &str,
+    filter: &str,
+    keys: &(String, String),
+) -> Result<i64, String> {
+    let mut rows = conn
+        .query(
+            &format!("SELECT COUNT(*) FROM {table} WHERE {filter}"),
+            keys.clone(),
+        )
+        .await
+        .map_err(|error| format!("Failed to count {table} rows: {error}"))?;
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read {table} row count: {error}"))?
+    else {
+        return Ok(0);
+    };
+    row.get(0)
+        .map_err(|error| format!("Failed to parse {table} row count: {error}"))
+}
+
+async fn count_active_runs(conn: &Connection, keys: &(String, String)) -> Result<i64, String> {
+    count_rows(
+        conn,
+        "ai_review_runs",
+        "status IN ('queued', 'running') AND (workspace IN (?1, ?2)
+           OR thread_id IN (SELECT id FROM threads WHERE workspace IN (?1, ?2)))",
+        keys,
+    )
+    .await
+}
+
+/// `(workspace, name)` pairs of a table that names files Rovex created inside the workspace.
+async fn load_workspace_files(
+    conn: &Connection,
+    table: &str,
+    name_column: &str,
+    keys: &(String, String),
+) -> Result<Vec<(String, String)>, String> {
+    let mut rows = conn
+        .query(
+            &format!("SELECT workspace, {name_column} FROM {table} WHERE workspace IN (?1, ?2)"),
+            keys.clone(),
+        )
+        .await
+        .map_err(|error| format!("Failed to load {table}: {error}"))?;
+    let mut files = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read {table} row: {error}"))?
+    {
+        files.push((
+            row.get(0)
+                .map_err(|error| format!("Failed to parse {table} workspace: {error}"))?,
+            row.get(1)
+                .map_err(|error| format!("Failed to parse {table} {name_column}: {error}"))?,
+        ));
+    }
+    Ok(files)
+}
+
+/// Deletes every row of `targets` for the workspace in one transaction and returns the
+/// deleted row count per table.
+async fn delete_workspace_rows(
+    conn: &Connection,
+    targets: &[(&'static str, &'static str)],
+    keys: &(String, String),
+) -> Result<Vec<PurgedWorkspaceTable>, String> {
+    let transaction = conn
+        .transaction()
+        .await
+        .map_err(|error| format!("Failed to start the purge transaction: {error}"))?;
+    let mut tables = Vec::with_capacity(targets.len());
+    for (table, filter) in targets {
+        let rows = transaction
+            .execute(&format!("DELETE FROM {table} WHERE {filter}"), keys.clone())
+            .await
+            .map_err(|error| format!("Failed to delete {table} rows: {error}"))?;
+        tables.push(PurgedWorkspaceTable {
+            table: table.to_string(),
+            rows: rows as i64,
+        });
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(|error| format!("Failed to commit the purge transaction: {error}"))?;
+    Ok(tables)
+}
+
+pub async fn purge_workspace_data(
+    state: State<'_, AppState>,
+    input: PurgeWorkspaceDataInput,
+) -> Result<PurgeWorkspaceDataResult, String> {
+    let keys = workspace_keys(&input.workspace)?;
+    let dry_run = input.dry_run.unwrap_or(false);
+    let conn = state.connection()?;
+
+    let active_runs = count_active_runs(&conn, &keys).await?;
+    if active_runs > 0 && !dry_run {
+        return Err(format!(
+            "{} has {active_runs} queued or running review run(s). Cancel them before purging its data.",
+            keys.1
+        ));
+    }
+
+    let targets = workspace_data_targets(&conn).await?;
+    let snapshots = load_workspace_files(&conn, "run_snapshots", "run_id", &keys).await?;
+    let hooks = load_workspace_files(&conn, "review_git_hooks", "hook", &keys).await?;
+
+    let tables = if dry_run {
+        let mut tables = Vec::with_capacity(targets.len());
+        for (table, filter) in &targets {
+            tables.push(PurgedWorkspaceTable {
+                table: table.to_string(),
+                rows: count_rows(&conn, table, filter, &keys).await?,
+            });
+        }
+        tables
+    } else {
+        delete_workspace_rows(&conn, &targets, &keys).await?
+    };
+
+    // Worktrees and hook scripts live on disk, so they are removed once the rows are gone.
+    // A workspace that was already deleted takes them along, which is not an error.
+    let mut files_removed = 0;
+    if !dry_run {
+        for (workspace, run_id) in &snapshots {
+            match remove_run_snapshot_worktree(workspace, run_id) {
+                Ok(removed) => files_removed += usize::from(removed),
+                Err(error) => eprintln!(
+                    "[backend] Failed to remove worktree of run snapshot {run_id}: {error}"
+                ),
+            }
+        }
+        for (workspace, hook) in &hooks {
+            match remove_review_git_hook(workspace, hook) {
+                Ok(removed) => files_removed += usize::from(removed),
+                Err(error) => {
+                    eprintln!("[backend] Failed to remove {hook} hook of {workspace}: {error}")
+                }
+            }
+        }
+    }
+
+    Ok(PurgeWorkspaceDataResult {
+        workspace: keys.1,
+        dry_run,
+        active_runs,
+        total_rows: tables.iter().map(|table| table.rows).sum(),
+        tables,
+        snapshot_worktrees: snapshots.len(),
+        git_hooks: hooks.len(),
+        files_removed,
+    })
+}
+
+/// Converts a column to JSON. `*_json` columns are embedded as parsed JSON and blobs as base64.
+pub(crate) fn column_to_json(name: &str, value: libsql::Value) -> Value {
+    match value {
+        libsql::Value::Null => Value::Null,
+        libsql::Value::Integer(value) => json!(value),
+        libsql::Value::Real(value) => json!(value),
+        libsql::Value::Text(text) if name.ends_with("_json") => {
+            serde_json::from_str(&text).unwrap_or(Value::String(text))
+        }
+        libsql::Value::Text(text) => Value::String(text),
+        libsql::Value::Blob(bytes) => {
+            Value::String(base64::engine::general_purpose::STANDARD.encode(bytes))
+        }
+    }
+}
+
+pub(crate) async fn export_table_rows(
+    conn: &Connection,
+    table: &str,
+    filter: &str,
+    params: impl IntoParams,
+) -> Result<Vec<Value>, String> {
+    let mut rows = conn
+        .query(
+            &format!("SELECT * FROM {table} WHERE {filter} ORDER BY rowid"),
+            params,
+        )
+        .await
+        .map_err(|error| format!("Failed to export {table} rows: {error}"))?;
+    let mut exported = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read {table} row: {error}"))?
+    {
+        let mut object = Map::new();
+        for index in 0..row.column_count() {
+            let name = row.column_name(index).unwrap_or_default().to_string();
+            let value = row
+                .get_value(index)
+                .map_err(|error| format!("Failed to parse {table}.{name}: {error}"))?;
+            object.insert(name.clone(), column_to_json(&name, value));
+        }
+        exported.push(Value::Object(object));
+    }
+    Ok(exported)
+}
+
+/// `rovex-<kind>-<name>.json`, with `name` reduced to safe characters.
+pub(crate) fn export_file_name(kind: &str, name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() || character == '-' || character == '_' {
+                character
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    let name = name.trim_matches('-');
+    if name.is_empty() {
+        format!("rovex-{kind}.json")
+    } else {
+        format!("rovex-{kind}-{name}.json")
+    }
+}
+
+/// The database clock, so export timestamps match the `created_at` columns they sit next to.
+pub(crate) async fn current_timestamp(conn: &Connection) -> Result<String, String> {
+    let mut rows = conn
+        .query("SELECT CURRENT_TIMESTAMP", ())
+        .await
+        .map_err(|error| format!("Failed to read the export timestamp: {error}"))?;
+    rows.next()
+        .await
+        .map_err(|error| format!("Failed to read the export timestamp: {error}"))?
+        .ok_or_else(|| "Failed to read the export timestamp.".to_string())?
+        .get(0)
+        .map_err(|error| format!("Failed to parse the export timestamp: {error}"))
+}
+
+pub async fn export_workspace_data(
+    state: State<'_, AppState>,
+    input: ExportWorkspaceDataInput,
+) -> Result<ExportWorkspaceDataResult, String> {
+    let keys = workspace_keys(&input.workspace)?;
+    let conn = state.connection()?;
+
+    let exported_at = current_timestamp(&conn).await?;
+
+    let mut tables = Map::new();
+    let mut total_rows = 0;
+    for (table, filter) in workspace_data_targets(&conn).await? {
+        let rows = export_table_rows(&conn, table, filter, keys.clone()).await?;
+        total_rows += rows.len();
+        tables.insert(table.to_string(), Value::Array(rows));
+    }
+
+    let document = json!({
+        "format": WORKSPACE_EXPORT_FORMAT,
+        "version": WORKSPACE_EXPORT_VERSION,
+        "workspace": keys.1,
+        "exportedAt": exported_at,
+        "tables": tables,
+    });
+    let content = serde_json::to_string_pretty(&document)
+        .map_err(|error| format!("Failed to serialize workspace export: {error}"))?;
+    let name = Path::new(&keys.1)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(ExportWorkspaceDataResult {
+        file_name: export_file_name("workspace", &name),
+        workspace: keys.1,
+        mime_type: "application/json".to_string(),
+        total_rows,
+        content,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{column_to_json, export_file_name};
+    use serde_json::json;
+
+    #[test]
+    fn exports_json_columns_parsed_and_names_files_after_the_workspace() {
+        assert_eq!(
+            column_to_json(
+                "findings_json",
+                libsql::Value::Text("[{\"id\":1}]".to_string())
+            ),
+            json!([{ "id": 1 }])
+        );
+        assert_eq!(
+            column_to_json("body", libsql::Value::Text("[not json".to_string())),
+            json!("[not json")
+        );
+        assert_eq!(
+            column_to_json("data", libsql::Value::Blob(vec![1, 2, 3])),
+            json!("AQID")
+        );
+
+        assert_eq!(
+            export_file_name("workspace", "Client Repo"),
+            "rovex-workspace-Client-Repo.json"
+        );
+        assert_eq!(export_file_name("workspace", ""), "rovex-workspace.json");
+    }
+}
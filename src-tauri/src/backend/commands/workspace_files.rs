@@ -0,0 +1,286 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ignore::WalkBuilder;
+
+use super::common::format_path;
+use super::name_sort::compare_paths;
+use crate::backend::paths::{normalize_path, path_starts_with};
+use crate::backend::{
+    ListWorkspaceFilesInput, ListWorkspaceFilesResult, ReadWorkspaceFileInput,
+    ReadWorkspaceFileResult, WorkspaceFileEntry,
+};
+
+const DEFAULT_LISTED_FILES: usize = 200;
+const MAX_LISTED_FILES: usize = 1_000;
+/// Entries a listing walks before it stops, so a recursive listing of a huge tree stays bounded.
+const MAX_WALKED_FILES: usize = 100_000;
+const MAX_READ_FILE_BYTES: u64 = 2 * 1024 * 1024;
+const MAX_READ_FILE_LINES: usize = 2_000;
+
+/// A workspace directory that paths from the frontend, the model or the code-intel graph are
+/// resolved against. Resolved paths stay inside the root, through `..` and symlinks alike, and
+/// never point into `.git`.
+pub(crate) struct WorkspaceRoot {
+    root: PathBuf,
+}
+
+impl WorkspaceRoot {
+    pub(crate) fn new(workspace: &str) -> Result<Self, String> {
+        let workspace = workspace.trim();
+        if workspace.is_empty() {
+            return Err("Workspace path must not be empty.".to_string());
+        }
+        let root = normalize_path(Path::new(workspace))?;
+        if !root.is_dir() {
+            return Err(format!("Workspace {workspace} is not a directory."));
+        }
+        Ok(Self { root })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.root
+    }
+
+    /// The absolute path for `path` and its `/`-separated workspace-relative form, or why it
+    /// is not allowed. An empty path or `.` is the root itself.
+    pub(crate) fn resolve(&self, path: &str) -> Result<(PathBuf, String), String> {
+        let trimmed = path.trim();
+        let candidate = if trimmed.is_empty() || trimmed == "." {
+            self.root.clone()
+        } else {
+            self.root.join(trimmed)
+        };
+        let resolved = normalize_path(&candidate)?;
+        if !path_starts_with(&resolved, &self.root) {
+            return Err(format!("{trimmed} is outside the workspace."));
+        }
+        let relative = self.relative(&resolved);
+        if relative.split('/').any(is_git_dir) {
+            return Err(format!("{trimmed} is inside .git."));
+        }
+        Ok((resolved, relative))
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .map(|relative| {
+                relative
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Whether a path component names the git directory. Compared without case, since `.GIT`
+/// is the same directory on case-insensitive file systems.
+pub(crate) fn is_git_dir(name: &str) -> bool {
+    name.eq_ignore_ascii_case(".git")
+}
+
+fn entry_kind(file_type: Option<fs::FileType>) -> &'static str {
+    match file_type {
+        Some(kind) if kind.is_symlink() => "symlink",
+        Some(kind) if kind.is_dir() => "directory",
+        _ => "file",
+    }
+}
+
+pub async fn list_workspace_files(
+    input: ListWorkspaceFilesInput,
+) -> Result<ListWorkspaceFilesResult, String> {
+    let workspace = WorkspaceRoot::new(&input.workspace)?;
+    let (directory, relative) = workspace.resolve(input.path.as_deref().unwrap_or_default())?;
+    if !directory.is_dir() {
+        return Err(format!("{relative} is not a directory."));
+    }
+    let respect_gitignore = input.respect_gitignore.unwrap_or(true);
+    let max_depth = (!input.recursive.unwrap_or(false)).then_some(1);
+
+    let walker = WalkBuilder::new(&directory)
+        .hidden(false)
+        .git_ignore(respect_gitignore)
+        .git_global(respect_gitignore)
+        .git_exclude(respect_gitignore)
+        .ignore(respect_gitignore)
+        .parents(respect_gitignore)
+        .max_depth(max_depth)
+        .filter_entry(|entry| !is_git_dir(&entry.file_name().to_string_lossy()))
+        .build();
+    let mut entries = Vec::new();
+    let mut truncated = false;
+    for entry in walker.flatten().filter(|entry| entry.depth() > 0) {
+        if entries.len() == MAX_WALKED_FILES {
+            truncated = true;
+            break;
+        }
+        let kind = entry_kind(entry.file_type());
+        entries.push(WorkspaceFileEntry {
+            path: workspace.relative(entry.path()),
+            name: entry.file_name().to_string_lossy().to_string(),
+            kind: kind.to_string(),
+            size_bytes: (kind == "file")
+                .then(|| entry.metadata().ok().map(|metadata| metadata.len()))
+                .flatten(),
+        });
+    }
+    entries.sort_by(|left, right| compare_paths(&left.path, &right.path));
+
+    let total_count = entries.len();
+    let offset = input.offset.unwrap_or(0) as usize;
+    let limit = input
+        .limit
+        .map(|limit| (limit as usize).clamp(1, MAX_LISTED_FILES))
+        .unwrap_or(DEFAULT_LISTED_FILES);
+    let entries = entries
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .collect::<Vec<_>>();
+    Ok(ListWorkspaceFilesResult {
+        workspace: format_path(workspace.path()),
+        path: relative,
+        has_more: offset + entries.len() < total_count,
+        entries,
+        total_count,
+        truncated,
+    })
+}
+
+pub async fn read_workspace_file(
+    input: ReadWorkspaceFileInput,
+) -> Result<ReadWorkspaceFileResult, String> {
+    let workspace = WorkspaceRoot::new(&input.workspace)?;
+    let (path, relative) = workspace.resolve(&input.path)?;
+    if !path.is_file() {
+        return Err(format!("{relative} is not a file."));
+    }
+    let size_bytes = fs::metadata(&path)
+        .map_err(|error| format!("Failed to read {relative}: {error}"))?
+        .len();
+    if size_bytes > MAX_READ_FILE_BYTES {
+        return Err(format!(
+            "{relative} is {size_bytes} bytes; files larger than {MAX_READ_FILE_BYTES} bytes cannot be read."
+        ));
+    }
+    let bytes = fs::read(&path).map_err(|error| format!("Failed to read {relative}: {error}"))?;
+    let content =
+        String::from_utf8(bytes).map_err(|_| format!("{relative} is not a UTF-8 text file."))?;
+
+    let lines = content.split_inclusive('\n').collect::<Vec<_>>();
+    let start_line = input.start_line.unwrap_or(1).max(1);
+    let last_allowed = start_line.saturating_add(MAX_READ_FILE_LINES - 1);
+    let requested_end = input.end_line.unwrap_or(last_allowed);
+    if requested_end < start_line {
+        return Err("endLine must not be before startLine.".to_string());
+    }
+    if start_line > lines.len().max(1) {
+        return Err(format!("{relative} has {} lines.", lines.len()));
+    }
+    let end_line = requested_end.min(last_allowed).min(lines.len());
+    Ok(ReadWorkspaceFileResult {
+        path: relative,
+        content: lines
+            .get(start_line - 1..end_line)
+            .unwrap_or_default()
+            .concat(),
+        start_line,
+        end_line,
+        total_lines: lines.len(),
+        truncated: requested_end.min(lines.len()) > end_line,
+        size_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::{list_workspace_files, read_workspace_file, WorkspaceRoot};
+    use crate::backend::{ListWorkspaceFilesInput, ReadWorkspaceFileInput};
+
+    #[test]
+    fn file_access_stays_inside_the_workspace() {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let parent = std::env::temp_dir().join(format!("rovex-workspace-files-test-{suffix}"));
+        let root = parent.join("repo");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("src/lib.rs"), "one\ntwo\nthree\n").unwrap();
+        fs::write(root.join("README.md"), "readme\n").unwrap();
+        fs::write(root.join("binary.bin"), [0xff, 0xfe, 0x00]).unwrap();
+        fs::write(parent.join("secret.txt"), "secret\n").unwrap();
+        let workspace = root.to_string_lossy().to_string();
+
+        let workspace_root = WorkspaceRoot::new(&workspace).unwrap();
+        assert!(workspace_root.resolve("../secret.txt").is_err());
+        assert!(workspace_root.resolve("src/../../secret.txt").is_err());
+        assert!(workspace_root.resolve(".git/config").is_err());
+        assert!(workspace_root.resolve(".GIT/config").is_err());
+        assert!(workspace_root.resolve("src/.Git/HEAD").is_err());
+        assert_eq!(
+            workspace_root.resolve("./src/lib.rs").unwrap().1,
+            "src/lib.rs"
+        );
+
+        tauri::async_runtime::block_on(async {
+            let listed = list_workspace_files(ListWorkspaceFilesInput {
+                workspace: workspace.clone(),
+                path: None,
+                recursive: Some(true),
+                respect_gitignore: None,
+                offset: Some(1),
+                limit: Some(2),
+            })
+            .await
+            .unwrap();
+            let paths = listed
+                .entries
+                .iter()
+                .map(|entry| entry.path.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(listed.total_count, 4);
+            assert!(listed.has_more);
+            assert_eq!(paths, ["README.md", "src"]);
+
+            let read = read_workspace_file(ReadWorkspaceFileInput {
+                workspace: workspace.clone(),
+                path: "src/lib.rs".to_string(),
+                start_line: Some(2),
+                end_line: Some(9),
+            })
+            .await
+            .unwrap();
+            assert_eq!(read.content, "two\nthree\n");
+            assert_eq!((read.start_line, read.end_line), (2, 3));
+            assert_eq!(read.total_lines, 3);
+            assert!(!read.truncated);
+
+            let past_end = read_workspace_file(ReadWorkspaceFileInput {
+                workspace: workspace.clone(),
+                path: "src/lib.rs".to_string(),
+                start_line: Some(usize::MAX),
+                end_line: None,
+            })
+            .await;
+            assert!(past_end.unwrap_err().contains("has 3 lines"));
+
+            let binary = read_workspace_file(ReadWorkspaceFileInput {
+                workspace,
+                path: "binary.bin".to_string(),
+                start_line: None,
+                end_line: None,
+            })
+            .await;
+            assert!(binary.unwrap_err().contains("not a UTF-8 text file"));
+        });
+        let _ = fs::remove_dir_all(&parent);
+    }
+}
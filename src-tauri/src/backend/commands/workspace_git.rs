@@ -1,25 +1,84 @@
 use std::{
-    env, fs,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    io::{Read, Write},
     path::{Path, PathBuf},
-    process::{Command, Output},
-    time::Instant,
+    process::{Command, Output, Stdio},
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use tauri::State;
+use libsql::Connection;
+use tauri::{AppHandle, Emitter, State};
 
 use super::super::providers::provider_client;
+use super::binary_decoders::{binary_decoder_for, BinaryDecoder};
 use super::common::{
-    format_path, truncate_utf8_by_bytes, COMPARE_ENABLE_RENAMES, DEFAULT_REPOSITORIES_DIR,
-    MAX_COMPARE_DIFF_BYTES,
+    as_non_empty_trimmed, format_path, truncate_utf8_by_bytes, COMPARE_ENABLE_RENAMES,
+    DEFAULT_REPOSITORIES_DIR, MAX_COMPARE_DIFF_BYTES, ROVEX_REPOSITORIES_DIR_ENV,
+    WORKSPACE_FETCH_PROGRESS_EVENT,
+};
+use super::git_repo::GitRepo;
+use super::name_sort::NameCollator;
+use super::path_filter::{
+    filter_diff_by_path, filter_numstat_by_path, rovexignore_fingerprint, PathFilter,
 };
 use super::providers::load_provider_connection_row;
+use super::repo_config::load_repo_review_config;
+use super::review::diff_chunks::{parse_truncated_diff_files, reviewable_diff_files};
+use super::workspace_files::WorkspaceRoot;
+use super::workspace_sync::registered_workspaces;
+use crate::backend::paths::{home_dir, long_path, normalize_path, path_starts_with, paths_equal};
+use crate::backend::settings::settings_store;
 use crate::backend::{
-    AppState, CheckoutWorkspaceBranchInput, CheckoutWorkspaceBranchResult, CloneRepositoryInput,
-    CloneRepositoryResult, CompareWorkspaceDiffInput, CompareWorkspaceDiffProfile,
-    CompareWorkspaceDiffResult, CreateWorkspaceBranchInput, ListWorkspaceBranchesInput,
-    ListWorkspaceBranchesResult, WorkspaceBranch,
+    AiReviewFinding, AppState, BranchSwitchStash, CheckoutWorkspaceBranchInput,
+    CheckoutWorkspaceBranchResult, CloneRepositoryInput, CloneRepositoryResult, CommitDetail,
+    CommitFileChange, CommitWorkspaceChangesInput, CommitWorkspaceChangesResult,
+    CompareWorkspaceDiffInput, CompareWorkspaceDiffProfile, CompareWorkspaceDiffResult,
+    CreateWorkspaceBranchInput, DiffFile, FetchWorkspaceRemoteInput, FetchWorkspaceRemoteResult,
+    FindingBlame, GetCommitDetailInput, GetLineBlameInput, GetLineBlameResult, LineBlame,
+    ListWorkspaceBranchesInput, ListWorkspaceBranchesResult, ListWorkspaceCommitsInput,
+    ListWorkspaceCommitsResult, MigrateRepositoriesDirInput, MigrateRepositoriesDirResult,
+    ProviderKind, PullWorkspaceBranchInput, PullWorkspaceBranchResult, PushWorkspaceBranchInput,
+    PushWorkspaceBranchResult, RepositoriesDirResult, UnshallowWorkspaceInput,
+    UnshallowWorkspaceResult, WorkspaceBranch, WorkspaceCommit, WorkspaceDiffTarget,
+    WorkspaceFetchProgressEvent, WorkspaceRemote, WorkspaceStatus, WorkspaceStatusInput,
+    WorkspaceSubmoduleChange,
 };
 
+/// Tables with a `workspace` column, rewritten when repositories move. The first three are
+/// counted in [`MigrateRepositoriesDirResult`].
+pub(crate) const WORKSPACE_TABLES: [&str; 12] = [
+    "threads",
+    "ai_review_runs",
+    "inline_review_comments",
+    "ai_review_findings",
+    "review_finding_states",
+    "workspace_trust",
+    "code_intel_configs",
+    "run_snapshots",
+    "review_git_hooks",
+    "review_schedules",
+    "review_group_runs",
+    "workspace_sync_status",
+];
+/// Upper bound on untracked files synthesized into a single comparison diff.
+const MAX_UNTRACKED_DIFF_FILES: usize = 500;
+/// Binary files larger than this are left as git's "Binary files differ" line.
+const MAX_DECODED_BINARY_FILE_BYTES: u64 = 16 * 1024 * 1024;
+const MAX_BLAME_LINES: usize = 500;
+const DEFAULT_LISTED_COMMITS: usize = 50;
+const MAX_LISTED_COMMITS: usize = 500;
+/// Mode git records a submodule's commit with.
+const GITLINK_MODE: &str = "160000";
+/// Commits fetched in turn when a shallow clone's history does not reach the merge base.
+const SHALLOW_DEEPEN_STEPS: [u32; 3] = [50, 200, 1000];
+/// Bytes of comparisons [`compare_workspace_diff`] keeps; the least recently used are dropped
+/// past this.
+const MAX_CACHED_COMPARISON_BYTES: usize = 32 * 1024 * 1024;
+/// How long after fetching the remote a repeated comparison skips the fetch.
+const COMPARE_FETCH_INTERVAL: Duration = Duration::from_secs(60);
+
 fn parse_clone_directory_name(
     explicit_name: Option<&str>,
     repository_name: &str,
@@ -48,27 +107,124 @@ fn parse_clone_directory_name(
     Ok(raw_value.to_string())
 }
 
-fn resolve_repository_root(explicit_root: Option<&str>) -> Result<PathBuf, String> {
+pub(crate) fn resolve_repository_root(explicit_root: Option<&str>) -> Result<PathBuf, String> {
     if let Some(root) = explicit_root
         .map(str::trim)
         .filter(|value| !value.is_empty())
     {
-        return Ok(PathBuf::from(root));
+        return normalize_path(Path::new(root));
     }
 
-    if let Ok(custom_root) = env::var("ROVEX_REPOSITORIES_DIR") {
-        let trimmed = custom_root.trim();
-        if !trimmed.is_empty() {
-            return Ok(PathBuf::from(trimmed));
-        }
+    if let Some(custom_root) = settings_store().get_string(ROVEX_REPOSITORIES_DIR_ENV) {
+        return normalize_path(Path::new(&custom_root));
     }
 
-    let home = env::var("HOME")
-        .or_else(|_| env::var("USERPROFILE"))
-        .map_err(|_| {
-            "Unable to determine a home directory. Provide destinationRoot.".to_string()
+    let home = home_dir().ok_or_else(|| {
+        "Unable to determine a home directory. Provide destinationRoot.".to_string()
+    })?;
+    normalize_path(&home.join(DEFAULT_REPOSITORIES_DIR))
+}
+
+fn copy_directory(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to)
+        .map_err(|error| format!("Failed to create {}: {error}", format_path(to)))?;
+    let entries = fs::read_dir(from)
+        .map_err(|error| format!("Failed to read {}: {error}", format_path(from)))?;
+    for entry in entries {
+        let entry =
+            entry.map_err(|error| format!("Failed to read {}: {error}", format_path(from)))?;
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+        let file_type = entry
+            .file_type()
+            .map_err(|error| format!("Failed to inspect {}: {error}", format_path(&source)))?;
+        if file_type.is_dir() {
+            copy_directory(&source, &target)?;
+            continue;
+        }
+        #[cfg(unix)]
+        if file_type.is_symlink() {
+            let link = fs::read_link(&source)
+                .map_err(|error| format!("Failed to read {}: {error}", format_path(&source)))?;
+            std::os::unix::fs::symlink(link, &target)
+                .map_err(|error| format!("Failed to create {}: {error}", format_path(&target)))?;
+            continue;
+        }
+        fs::copy(&source, &target).map_err(|error| {
+            format!(
+                "Failed to copy {} to {}: {error}",
+                format_path(&source),
+                format_path(&target)
+            )
         })?;
-    Ok(PathBuf::from(home).join(DEFAULT_REPOSITORIES_DIR))
+    }
+    Ok(())
+}
+
+/// Moves a directory, falling back to copy-and-delete when the destination is on another
+/// filesystem.
+fn move_directory(from: &Path, to: &Path) -> Result<(), String> {
+    let (from, to) = (&long_path(from), &long_path(to));
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if let Err(error) = copy_directory(from, to) {
+        let _ = fs::remove_dir_all(to);
+        return Err(error);
+    }
+    fs::remove_dir_all(from)
+        .map_err(|error| format!("Failed to remove {}: {error}", format_path(from)))
+}
+
+async fn rewrite_workspace_paths(
+    conn: &libsql::Transaction,
+    table: &str,
+    from: &str,
+    to: &str,
+) -> Result<u64, String> {
+    let from_prefix = format!("{from}{}", std::path::MAIN_SEPARATOR);
+    let sql = format!(
+        "UPDATE {table}
+         SET workspace = ?2 || substr(workspace, ?3)
+         WHERE workspace = ?1 OR substr(workspace, 1, ?4) = ?5"
+    );
+    conn.execute(
+        &sql,
+        (
+            from.to_string(),
+            to.to_string(),
+            from.chars().count() as i64 + 1,
+            from_prefix.chars().count() as i64,
+            from_prefix,
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to update workspace paths in {table}: {error}"))
+}
+
+/// Points every workspace row of the `moved` repositories at their new paths in one
+/// transaction, returning the updated row count per table of [`WORKSPACE_TABLES`].
+async fn rewrite_moved_workspaces(
+    conn: &Connection,
+    moved: &[(PathBuf, PathBuf)],
+) -> Result<[u64; WORKSPACE_TABLES.len()], String> {
+    let transaction = conn
+        .transaction()
+        .await
+        .map_err(|error| format!("Failed to start the migration transaction: {error}"))?;
+    let mut updated = [0u64; WORKSPACE_TABLES.len()];
+    for (from, to) in moved {
+        let from = format_path(from);
+        let to = format_path(to);
+        for (index, table) in WORKSPACE_TABLES.iter().enumerate() {
+            updated[index] += rewrite_workspace_paths(&transaction, table, &from, &to).await?;
+        }
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(|error| format!("Failed to commit the migration transaction: {error}"))?;
+    Ok(updated)
 }
 
 fn summarize_process_output(output: &Output) -> String {
@@ -83,7 +239,7 @@ fn summarize_process_output(output: &Output) -> String {
     }
 }
 
-fn run_git(repo_path: &Path, args: &[&str], context: &str) -> Result<Output, String> {
+pub(crate) fn run_git(repo_path: &Path, args: &[&str], context: &str) -> Result<Output, String> {
     let output = Command::new("git")
         .env("GIT_TERMINAL_PROMPT", "0")
         .arg("-C")
@@ -102,18 +258,245 @@ fn run_git(repo_path: &Path, args: &[&str], context: &str) -> Result<Output, Str
     }
 }
 
-fn run_git_trimmed(repo_path: &Path, args: &[&str], context: &str) -> Result<String, String> {
+pub(crate) fn run_git_trimmed(
+    repo_path: &Path,
+    args: &[&str],
+    context: &str,
+) -> Result<String, String> {
     let output = run_git(repo_path, args, context)?;
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
+/// Builds add-diffs for untracked files that `.gitignore` does not exclude, so files that were
+/// never `git add`-ed still show up in a comparison.
+fn untracked_files_diff(
+    repo_path: &Path,
+    pathspecs: &[String],
+    path_filter: &PathFilter,
+) -> Result<String, String> {
+    let mut ls_files_args = vec!["ls-files", "--others", "--exclude-standard", "-z"];
+    if !pathspecs.is_empty() {
+        ls_files_args.push("--");
+        ls_files_args.extend(pathspecs.iter().map(String::as_str));
+    }
+    let listing = run_git(repo_path, &ls_files_args, "ls-files --others")?;
+    let listing = String::from_utf8_lossy(&listing.stdout);
+
+    let mut diff = String::new();
+    for path in listing
+        .split('\0')
+        .filter(|path| !path.is_empty() && path_filter.allows(path))
+        .take(MAX_UNTRACKED_DIFF_FILES)
+    {
+        let output = Command::new("git")
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .arg("-C")
+            .arg(repo_path)
+            .args([
+                "diff",
+                "--no-index",
+                "--no-color",
+                "--no-ext-diff",
+                "--patch",
+                "--",
+                "/dev/null",
+                path,
+            ])
+            .output()
+            .map_err(|error| format!("Failed to run git diff --no-index: {error}"))?;
+        // `--no-index` exits with 1 whenever the inputs differ, which is the expected case here.
+        if !output.status.success() && output.status.code() != Some(1) {
+            return Err(format!(
+                "git diff --no-index failed for '{path}': {}",
+                summarize_process_output(&output)
+            ));
+        }
+        diff.push_str(&String::from_utf8_lossy(&output.stdout));
+    }
+    Ok(diff)
+}
+
+/// Where the new side of a comparison reads file contents from.
+enum BinaryDiffSide<'a> {
+    /// A revision, or the index when empty.
+    Object(&'a str),
+    WorkingTree,
+}
+
+fn read_binary_side(
+    repo_path: &Path,
+    side: &BinaryDiffSide<'_>,
+    path: &str,
+) -> Result<Vec<u8>, String> {
+    match side {
+        BinaryDiffSide::Object(revision) => {
+            let object = format!("{revision}:{path}");
+            let size = run_git_trimmed(repo_path, &["cat-file", "-s", &object], "cat-file -s")?;
+            if size.parse::<u64>().unwrap_or(u64::MAX) > MAX_DECODED_BINARY_FILE_BYTES {
+                return Err(format!("{path} is too large to decode."));
+            }
+            Ok(run_git(repo_path, &["cat-file", "blob", &object], "cat-file blob")?.stdout)
+        }
+        BinaryDiffSide::WorkingTree => {
+            let file_path = repo_path.join(path);
+            let size = fs::metadata(long_path(&file_path))
+                .map_err(|error| format!("Failed to read {path}: {error}"))?
+                .len();
+            if size > MAX_DECODED_BINARY_FILE_BYTES {
+                return Err(format!("{path} is too large to decode."));
+            }
+            fs::read(long_path(&file_path))
+                .map_err(|error| format!("Failed to read {path}: {error}"))
+        }
+    }
+}
+
+/// Hunks of a unified diff between two decoded texts, labeled with the decoder that made them.
+fn decoded_text_hunks(
+    repo_path: &Path,
+    old_text: &str,
+    new_text: &str,
+    decoder: &dyn BinaryDecoder,
+) -> Result<String, String> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    let temp_path = |side: &str| {
+        std::env::temp_dir().join(format!(
+            "rovex-decoded-{}-{nanos}-{side}",
+            std::process::id()
+        ))
+    };
+    let (old_path, new_path) = (temp_path("old"), temp_path("new"));
+    let written = fs::write(&old_path, old_text).and_then(|_| fs::write(&new_path, new_text));
+    let output = written
+        .map_err(|error| format!("Failed to write decoded text: {error}"))
+        .and_then(|_| {
+            Command::new("git")
+                .env("GIT_TERMINAL_PROMPT", "0")
+                .arg("-C")
+                .arg(repo_path)
+                .args(["diff", "--no-index", "--no-color", "--no-ext-diff", "--"])
+                .arg(&old_path)
+                .arg(&new_path)
+                .output()
+                .map_err(|error| format!("Failed to run git diff --no-index: {error}"))
+        });
+    let _ = fs::remove_file(&old_path);
+    let _ = fs::remove_file(&new_path);
+    let output = output?;
+    if !output.status.success() && output.status.code() != Some(1) {
+        return Err(format!(
+            "git diff --no-index failed: {}",
+            summarize_process_output(&output)
+        ));
+    }
+
+    let mut hunks = String::new();
+    for line in String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip_while(|line| !line.starts_with("@@ "))
+    {
+        // The hunk header context names the decoder so reviewers know the text is derived.
+        match line
+            .strip_prefix("@@ ")
+            .and_then(|rest| rest.split_once(" @@"))
+        {
+            Some((ranges, _)) => {
+                hunks.push_str(&format!("@@ {ranges} @@ decoded {}\n", decoder.name()))
+            }
+            None => {
+                hunks.push_str(line);
+                hunks.push('\n');
+            }
+        }
+    }
+    Ok(hunks)
+}
+
+/// Old and new paths of a `Binary files <old> and <new> differ` line, with `None` for
+/// `/dev/null`. Quoted paths are not handled.
+fn parse_binary_files_line(line: &str) -> Option<(Option<&str>, Option<&str>)> {
+    let sides = line
+        .strip_prefix("Binary files ")?
+        .strip_suffix(" differ")?;
+    if let Some(new_side) = sides.strip_prefix("/dev/null and ") {
+        return Some((None, Some(new_side.strip_prefix("b/")?)));
+    }
+    if let Some(old_side) = sides.strip_suffix(" and /dev/null") {
+        return Some((Some(old_side.strip_prefix("a/")?), None));
+    }
+    let (old_side, new_side) = sides.split_once(" and b/")?;
+    Some((Some(old_side.strip_prefix("a/")?), Some(new_side)))
+}
+
+fn decode_binary_file_diff(
+    repo_path: &Path,
+    old_path: Option<&str>,
+    new_path: Option<&str>,
+    old_revision: &str,
+    new_side: &BinaryDiffSide<'_>,
+) -> Result<Option<String>, String> {
+    let Some(decoder) = new_path.or(old_path).and_then(binary_decoder_for) else {
+        return Ok(None);
+    };
+    let decode_side = |path: Option<&str>, side: &BinaryDiffSide<'_>| match path {
+        Some(path) => decoder.decode(&read_binary_side(repo_path, side, path)?),
+        None => Ok(String::new()),
+    };
+    let old_text = decode_side(old_path, &BinaryDiffSide::Object(old_revision))?;
+    let new_text = decode_side(new_path, new_side)?;
+    if old_text == new_text {
+        return Ok(None);
+    }
+    let hunks = decoded_text_hunks(repo_path, &old_text, &new_text, decoder)?;
+    let header_path = |prefix: &str, path: Option<&str>| {
+        path.map(|path| format!("{prefix}{path}"))
+            .unwrap_or_else(|| "/dev/null".to_string())
+    };
+    Ok(Some(format!(
+        "--- {}\n+++ {}\n{hunks}",
+        header_path("a/", old_path),
+        header_path("b/", new_path)
+    )))
+}
+
+/// Replaces `Binary files ... differ` lines for formats with a [`BinaryDecoder`] by a diff of
+/// their decoded text, so those changes get reviewed instead of silently skipped. Files that
+/// fail to decode keep the original line.
+fn decode_binary_diffs(
+    repo_path: &Path,
+    diff: &str,
+    old_revision: &str,
+    new_side: &BinaryDiffSide<'_>,
+) -> String {
+    if !diff.contains("\nBinary files ") {
+        return diff.to_string();
+    }
+    let mut decoded = String::with_capacity(diff.len());
+    for line in diff.split_inclusive('\n') {
+        let replacement = parse_binary_files_line(line.trim_end_matches('\n')).and_then(
+            |(old_path, new_path)| {
+                decode_binary_file_diff(repo_path, old_path, new_path, old_revision, new_side)
+                    .unwrap_or_else(|error| {
+                        eprintln!("[backend] Failed to decode binary diff: {error}");
+                        None
+                    })
+            },
+        );
+        decoded.push_str(replacement.as_deref().unwrap_or(line));
+    }
+    decoded
+}
+
 fn resolve_workspace_repo_path(workspace: &str) -> Result<PathBuf, String> {
     let workspace = workspace.trim();
     if workspace.is_empty() {
         return Err("Workspace path must not be empty.".to_string());
     }
 
-    let repo_path = PathBuf::from(workspace);
+    let repo_path = normalize_path(Path::new(workspace))?;
     if !repo_path.exists() {
         return Err(format!(
             "Workspace does not exist: {}",
@@ -131,12 +514,7 @@ fn resolve_workspace_repo_path(workspace: &str) -> Result<PathBuf, String> {
 }
 
 fn ensure_git_repository(repo_path: &Path) -> Result<(), String> {
-    let is_git_repo = run_git_trimmed(
-        repo_path,
-        &["rev-parse", "--is-inside-work-tree"],
-        "rev-parse",
-    )?;
-    if is_git_repo != "true" {
+    if !GitRepo::open(repo_path).is_work_tree()? {
         return Err(format!(
             "Workspace is not a git repository: {}",
             format_path(repo_path)
@@ -180,7 +558,7 @@ fn ref_sort_priority(name: &str) -> i32 {
     branch_sort_priority(normalized)
 }
 
-fn read_git_trimmed_if_success(repo_path: &Path, args: &[&str]) -> Option<String> {
+pub(crate) fn read_git_trimmed_if_success(repo_path: &Path, args: &[&str]) -> Option<String> {
     let output = Command::new("git")
         .env("GIT_TERMINAL_PROMPT", "0")
         .arg("-C")
@@ -198,83 +576,81 @@ fn read_git_trimmed_if_success(repo_path: &Path, args: &[&str]) -> Option<String
     Some(value)
 }
 
-fn git_ref_exists(repo_path: &Path, reference: &str) -> bool {
-    let commit_reference = format!("{reference}^{{commit}}");
-    Command::new("git")
-        .env("GIT_TERMINAL_PROMPT", "0")
-        .arg("-C")
-        .arg(repo_path)
-        .arg("rev-parse")
-        .arg("--verify")
-        .arg("--quiet")
-        .arg(commit_reference)
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
-}
-
+/// Suggests the default branch of `default_remote`, then of `origin`, then a local default
+/// branch, before settling for any branch that exists.
 fn resolve_suggested_base_ref(
-    repo_path: &Path,
+    repo: &GitRepo<'_>,
+    default_remote: &str,
     upstream_branch: Option<&str>,
     remote_branch_names: &[String],
     local_branch_names: &[String],
 ) -> String {
-    if let Some(origin_head) = read_git_trimmed_if_success(
-        repo_path,
-        &[
-            "symbolic-ref",
-            "--quiet",
-            "--short",
-            "refs/remotes/origin/HEAD",
-        ],
-    ) {
-        if origin_head != "origin/HEAD" && git_ref_exists(repo_path, &origin_head) {
-            return origin_head;
+    let exists = |reference: &str| repo.commit_id(reference).is_some();
+    let mut remotes = vec![default_remote];
+    if default_remote != "origin" {
+        remotes.push("origin");
+    }
+    for remote in remotes {
+        if let Some(remote_head) = repo.remote_head(remote) {
+            if remote_head != format!("{remote}/HEAD") && exists(&remote_head) {
+                return remote_head;
+            }
+        }
+        for branch in ["main", "master"] {
+            let candidate = format!("{remote}/{branch}");
+            if exists(&candidate) {
+                return candidate;
+            }
         }
     }
 
-    for candidate in ["origin/main", "origin/master", "main", "master"] {
-        if git_ref_exists(repo_path, candidate) {
+    for candidate in ["main", "master"] {
+        if exists(candidate) {
             return candidate.to_string();
         }
     }
 
     if let Some(upstream) = upstream_branch {
-        if git_ref_exists(repo_path, upstream) {
+        if exists(upstream) {
             return upstream.to_string();
         }
     }
 
     if let Some(remote_branch) = remote_branch_names
         .iter()
-        .find(|candidate| git_ref_exists(repo_path, candidate))
+        .find(|candidate| exists(candidate))
     {
         return remote_branch.clone();
     }
 
     if let Some(local_branch) = local_branch_names
         .iter()
-        .find(|candidate| git_ref_exists(repo_path, candidate))
+        .find(|candidate| exists(candidate))
     {
         return local_branch.clone();
     }
 
-    "origin/main".to_string()
+    format!("{default_remote}/main")
 }
 
+/// Resolves the base ref, falling back from a remote's `main` to its `master` and then to
+/// the local `main` or `master`.
 pub(crate) fn resolve_base_ref(
     repo_path: &Path,
     requested_base_ref: &str,
 ) -> Result<String, String> {
+    let repo = GitRepo::open(repo_path);
     let mut candidates = vec![requested_base_ref.to_string()];
-    if requested_base_ref == "origin/main" {
-        candidates.push("origin/master".to_string());
+    if let Some(remote) = requested_base_ref.strip_suffix("/main").filter(|remote| {
+        *remote == "origin" || repo.remote_names().iter().any(|name| name == remote)
+    }) {
+        candidates.push(format!("{remote}/master"));
         candidates.push("main".to_string());
         candidates.push("master".to_string());
     }
 
     for candidate in candidates {
-        if git_ref_exists(repo_path, &candidate) {
+        if repo.commit_id(&candidate).is_some() {
             return Ok(candidate);
         }
     }
@@ -284,6 +660,140 @@ pub(crate) fn resolve_base_ref(
     ))
 }
 
+fn is_shallow_repository(repo_path: &Path) -> bool {
+    GitRepo::open(repo_path).is_shallow()
+}
+
+/// The remote a remote-tracking ref such as `upstream/main` belongs to, or `None` for a
+/// local ref.
+fn ref_remote(repo: &GitRepo<'_>, reference: &str) -> Option<String> {
+    let remotes = repo.remote_names();
+    // Remote names may contain `/`, so the longest matching name wins.
+    remotes
+        .into_iter()
+        .filter(|remote| {
+            reference
+                .strip_prefix(remote.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .max_by_key(String::len)
+}
+
+/// The remote a remote-tracking ref such as `upstream/main` belongs to, or `origin`.
+fn remote_for_ref(repo: &GitRepo<'_>, reference: &str) -> String {
+    ref_remote(repo, reference).unwrap_or_else(|| "origin".to_string())
+}
+
+/// Rejects remote names git would read as an option.
+fn parse_remote_name(value: Option<&str>) -> Result<Option<String>, String> {
+    let remote = as_non_empty_trimmed(value);
+    if remote
+        .as_deref()
+        .is_some_and(|remote| remote.starts_with('-'))
+    {
+        return Err("Remote must not start with '-'.".to_string());
+    }
+    Ok(remote)
+}
+
+/// Merge base of `head` and `base_ref`. In a shallow clone whose history stops short of it,
+/// fetches more history in [`SHALLOW_DEEPEN_STEPS`] and tries again; also returns how many
+/// commits were added.
+fn resolve_merge_base_deepening(
+    repo_path: &Path,
+    head: &str,
+    base_ref: &str,
+) -> Result<(String, Option<u32>), String> {
+    let mut deepened_by = 0;
+    let mut steps = SHALLOW_DEEPEN_STEPS.iter();
+    loop {
+        // Opened on every pass so libgit2 sees the history each fetch adds.
+        let repo = GitRepo::open(repo_path);
+        let error = match repo.merge_base(head, base_ref) {
+            Ok(merge_base) => return Ok((merge_base, (deepened_by > 0).then_some(deepened_by))),
+            Err(error) => error,
+        };
+        if !repo.is_shallow() {
+            return Err(error);
+        }
+        let Some(step) = steps.next() else {
+            return Err(format!(
+                "No merge base between HEAD and {base_ref} was found in this shallow clone, even after fetching {deepened_by} more commits. Fetch the full history with unshallow_workspace and compare again."
+            ));
+        };
+        let remote = remote_for_ref(&repo, base_ref);
+        run_git(
+            repo_path,
+            &["fetch", "--quiet", &format!("--deepen={step}"), &remote],
+            "fetch --deepen",
+        )?;
+        deepened_by += step;
+    }
+}
+
+fn is_null_object_id(value: &str) -> bool {
+    value.bytes().all(|byte| byte == b'0')
+}
+
+/// Submodules whose recorded commit differs between the two sides of the comparison, from
+/// `git diff --raw` entries with the gitlink mode.
+fn submodule_changes(
+    repo_path: &Path,
+    comparison_args: &[&str],
+    pathspecs: &[String],
+) -> Result<Vec<WorkspaceSubmoduleChange>, String> {
+    let mut args = vec!["diff"];
+    args.extend(comparison_args.iter().copied());
+    args.extend(["--raw", "-z", "--no-abbrev", "--no-renames"]);
+    if !pathspecs.is_empty() {
+        args.push("--");
+        args.extend(pathspecs.iter().map(String::as_str));
+    }
+    let output = run_git(repo_path, &args, "diff --raw")?;
+    let raw = String::from_utf8_lossy(&output.stdout);
+    let mut fields = raw.split('\0');
+    let mut changes = Vec::new();
+    while let (Some(meta), Some(path)) = (fields.next(), fields.next()) {
+        let Some(meta) = meta.strip_prefix(':') else {
+            continue;
+        };
+        let parts = meta.split_whitespace().collect::<Vec<_>>();
+        let [old_mode, new_mode, old_id, new_id, status] = parts[..] else {
+            continue;
+        };
+        if old_mode != GITLINK_MODE && new_mode != GITLINK_MODE {
+            continue;
+        }
+        let old_commit =
+            (old_mode == GITLINK_MODE && !is_null_object_id(old_id)).then(|| old_id.to_string());
+        // Working-tree sides leave the id out; the submodule's checkout has it.
+        let new_commit = if new_mode != GITLINK_MODE {
+            None
+        } else if is_null_object_id(new_id) {
+            read_git_trimmed_if_success(&repo_path.join(path), &["rev-parse", "HEAD"])
+        } else {
+            Some(new_id.to_string())
+        };
+        if old_commit.is_some() && old_commit == new_commit {
+            continue;
+        }
+        changes.push(WorkspaceSubmoduleChange {
+            path: path.to_string(),
+            status: match (old_commit.is_some(), new_commit.is_some(), status) {
+                (false, true, _) | (_, _, "A") => "added",
+                (true, false, _) | (_, _, "D") => "removed",
+                _ => "modified",
+            }
+            .to_string(),
+            old_commit,
+            new_commit,
+            workspace: None,
+            diff: None,
+        });
+    }
+    Ok(changes)
+}
+
 fn parse_numstat(diff_numstat: &str) -> (i64, i64, i64) {
     let mut files_changed = 0i64;
     let mut insertions = 0i64;
@@ -313,115 +823,35 @@ fn parse_numstat(diff_numstat: &str) -> (i64, i64, i64) {
     (files_changed, insertions, deletions)
 }
 
-pub async fn clone_repository(
-    state: State<'_, AppState>,
-    input: CloneRepositoryInput,
-) -> Result<CloneRepositoryResult, String> {
-    let connection = load_provider_connection_row(&state, input.provider)
-        .await?
-        .ok_or_else(|| format!("{} is not connected.", input.provider.as_str()))?;
-    let client = provider_client(input.provider);
-    let repository = client.parse_repository(&input.repository)?;
-
-    let destination_root = resolve_repository_root(input.destination_root.as_deref())?;
-    fs::create_dir_all(&destination_root).map_err(|error| {
-        format!(
-            "Failed to create clone destination {}: {error}",
-            format_path(&destination_root)
-        )
-    })?;
-
-    let directory_name =
-        parse_clone_directory_name(input.directory_name.as_deref(), &repository.name)?;
-    let destination_path = destination_root.join(directory_name);
-    if destination_path.exists() {
-        return Err(format!(
-            "Destination already exists: {}",
-            format_path(&destination_path)
-        ));
-    }
-
-    let auth_header = client.clone_auth_header(&connection.access_token)?;
-    let clone_url = client.clone_url(&repository);
-    let mut command = Command::new("git");
-    command
-        .env("GIT_TERMINAL_PROMPT", "0")
-        .arg("-c")
-        .arg(format!("http.extraHeader={auth_header}"))
-        .arg("clone");
-
-    if input.shallow.unwrap_or(true) {
-        command.arg("--depth").arg("1");
-    }
-
-    let output = command
-        .arg(&clone_url)
-        .arg(&destination_path)
-        .output()
-        .map_err(|error| format!("Failed to run git clone: {error}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        let summary = if !stderr.is_empty() { stderr } else { stdout };
-        let detail = if summary.is_empty() {
-            "Unknown git error.".to_string()
-        } else {
-            summary
-        };
-        return Err(format!("git clone failed: {detail}"));
-    }
-
-    Ok(CloneRepositoryResult {
-        provider: input.provider,
-        repository: repository.slug(),
-        workspace: format_path(&destination_path),
-    })
+pub(crate) struct CommitRangeDiff {
+    pub(crate) diff: String,
+    pub(crate) files_changed: i64,
+    pub(crate) insertions: i64,
+    pub(crate) deletions: i64,
 }
 
-pub async fn compare_workspace_diff(
-    input: CompareWorkspaceDiffInput,
-) -> Result<CompareWorkspaceDiffResult, String> {
-    let started_at = Instant::now();
-    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+/// Diffs the working tree of `workspace` against `since_commit`. Returns `None` when the
+/// commit is no longer present (for example after a rebase and gc).
+pub(crate) fn diff_since_commit(
+    workspace: &str,
+    since_commit: &str,
+) -> Result<Option<CommitRangeDiff>, String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
     ensure_git_repository(&repo_path)?;
 
-    let requested_base_ref = input
-        .base_ref
-        .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .unwrap_or("origin/main");
-
-    let should_fetch_origin =
-        input.fetch_remote.unwrap_or(true) && requested_base_ref.starts_with("origin/");
-    let mut fetch_origin_ms = None;
-    if should_fetch_origin {
-        let fetch_started_at = Instant::now();
-        run_git(&repo_path, &["fetch", "--quiet", "origin"], "fetch origin")?;
-        fetch_origin_ms = Some(fetch_started_at.elapsed().as_millis() as u64);
-    }
-
-    let resolve_base_ref_started_at = Instant::now();
-    let base_ref = resolve_base_ref(&repo_path, requested_base_ref)?;
-    let resolve_base_ref_ms = resolve_base_ref_started_at.elapsed().as_millis() as u64;
-
-    let resolve_head_started_at = Instant::now();
-    let head = run_git_trimmed(&repo_path, &["rev-parse", "HEAD"], "resolve HEAD")?;
-    let resolve_head_ms = resolve_head_started_at.elapsed().as_millis() as u64;
-
-    let resolve_merge_base_started_at = Instant::now();
-    let merge_base = run_git_trimmed(
+    let commit_ref = format!("{since_commit}^{{commit}}");
+    if read_git_trimmed_if_success(
         &repo_path,
-        &["merge-base", "HEAD", base_ref.as_str()],
-        "resolve merge-base",
-    )?;
-    let resolve_merge_base_ms = resolve_merge_base_started_at.elapsed().as_millis() as u64;
+        &["rev-parse", "--verify", "--quiet", &commit_ref],
+    )
+    .is_none()
+    {
+        return Ok(None);
+    }
 
     let mut diff_args = vec![
         "diff",
-        "--merge-base",
-        base_ref.as_str(),
+        since_commit,
         "--no-color",
         "--no-ext-diff",
         "--patch",
@@ -431,163 +861,1769 @@ pub async fn compare_workspace_diff(
     } else {
         diff_args.push("--no-renames");
     }
-
-    let diff_started_at = Instant::now();
     let diff_output = run_git(&repo_path, &diff_args, "diff")?;
-    let diff_ms = diff_started_at.elapsed().as_millis() as u64;
     let raw_diff = String::from_utf8_lossy(&diff_output.stdout).to_string();
-    let diff_bytes_total = raw_diff.len();
-    let (diff, diff_truncated) = truncate_utf8_by_bytes(&raw_diff, MAX_COMPARE_DIFF_BYTES);
-    let diff_bytes_used = diff.len();
+    let (diff, _) = truncate_utf8_by_bytes(&raw_diff, MAX_COMPARE_DIFF_BYTES);
 
-    let numstat_started_at = Instant::now();
     let numstat_output = run_git(
         &repo_path,
-        &["diff", "--merge-base", base_ref.as_str(), "--numstat"],
+        &["diff", since_commit, "--numstat"],
         "diff --numstat",
     )?;
-    let numstat_ms = numstat_started_at.elapsed().as_millis() as u64;
     let numstat = String::from_utf8_lossy(&numstat_output.stdout);
     let (files_changed, insertions, deletions) = parse_numstat(&numstat);
-    let total_ms = started_at.elapsed().as_millis() as u64;
-
-    let profile = CompareWorkspaceDiffProfile {
-        fetch_origin_ms,
-        resolve_base_ref_ms,
-        resolve_head_ms,
-        resolve_merge_base_ms,
-        diff_ms,
-        numstat_ms,
-        total_ms,
-    };
 
-    Ok(CompareWorkspaceDiffResult {
-        workspace: format_path(&repo_path),
-        base_ref,
-        merge_base,
-        head,
+    Ok(Some(CommitRangeDiff {
         diff,
         files_changed,
         insertions,
         deletions,
-        diff_truncated,
-        diff_bytes_used,
-        diff_bytes_total,
-        profile,
-    })
+    }))
 }
 
-pub async fn list_workspace_branches(
-    input: ListWorkspaceBranchesInput,
-) -> Result<ListWorkspaceBranchesResult, String> {
-    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
-    ensure_git_repository(&repo_path)?;
-
-    if input.fetch_remote.unwrap_or(false) {
-        run_git(&repo_path, &["fetch", "--quiet", "origin"], "fetch origin")?;
-    }
+pub(crate) struct PatchApplyOutcome {
+    pub(crate) workspace: String,
+    pub(crate) three_way: bool,
+    pub(crate) conflicts: Vec<String>,
+}
 
-    let current_branch = run_git_trimmed(
+fn git_apply(repo_path: &Path, args: &[&str], patch: &str) -> Result<Output, String> {
+    let mut child = Command::new("git")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("apply")
+        .args(args)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("Failed to run git apply: {error}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(patch.as_bytes())
+            .map_err(|error| format!("Failed to pass patch to git apply: {error}"))?;
+    }
+    child
+        .wait_with_output()
+        .map_err(|error| format!("Failed to run git apply: {error}"))
+}
+
+/// Repository path of `workspace` and the branch checked out there, if any.
+pub(crate) fn workspace_checkout(workspace: &str) -> Result<(PathBuf, Option<String>), String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let branch = read_git_trimmed_if_success(&repo_path, &["branch", "--show-current"]);
+    Ok((repo_path, branch))
+}
+
+/// Applies a single-file unified diff to the working tree of `workspace`. A patch that no
+/// longer applies cleanly is retried with `git apply --3way` against the file as of
+/// `base_commit`, which stages the result and leaves conflict markers in the files listed in
+/// the outcome.
+pub(crate) fn apply_patch_to_workspace(
+    workspace: &str,
+    file_path: &str,
+    patch: &str,
+    base_commit: Option<&str>,
+) -> Result<PatchApplyOutcome, String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+
+    let check = git_apply(&repo_path, &["--check", "--recount"], patch)?;
+    if check.status.success() {
+        let output = git_apply(&repo_path, &["--recount"], patch)?;
+        if !output.status.success() {
+            return Err(format!(
+                "git apply failed: {}",
+                summarize_process_output(&output)
+            ));
+        }
+        return Ok(PatchApplyOutcome {
+            workspace: format_path(&repo_path),
+            three_way: false,
+            conflicts: Vec::new(),
+        });
+    }
+
+    // Model-written patches carry no blob ids, so point `--3way` at the reviewed version of the
+    // file to give it a merge base.
+    let base_blob = base_commit.and_then(|commit| {
+        read_git_trimmed_if_success(&repo_path, &["rev-parse", &format!("{commit}:{file_path}")])
+    });
+    let Some(base_blob) = base_blob else {
+        return Err(format!(
+            "Suggested fix no longer applies to {file_path}: {}",
+            summarize_process_output(&check)
+        ));
+    };
+    let three_way_patch =
+        format!("diff --git a/{file_path} b/{file_path}\nindex {base_blob}..0000000\n{patch}");
+    let output = git_apply(&repo_path, &["--3way", "--recount"], &three_way_patch)?;
+    let conflicts = run_git_trimmed(
+        &repo_path,
+        &["diff", "--name-only", "--diff-filter=U", "--", file_path],
+        "diff --diff-filter=U",
+    )?
+    .lines()
+    .map(ToOwned::to_owned)
+    .collect::<Vec<_>>();
+    if !output.status.success() && conflicts.is_empty() {
+        return Err(format!(
+            "git apply --3way failed: {}",
+            summarize_process_output(&output)
+        ));
+    }
+
+    Ok(PatchApplyOutcome {
+        workspace: format_path(&repo_path),
+        three_way: true,
+        conflicts,
+    })
+}
+
+/// Directory under the repository's shared git dir that holds run snapshot worktrees.
+const RUN_SNAPSHOTS_DIR: &str = "rovex-snapshots";
+
+fn run_snapshot_path(repo_path: &Path, run_id: &str) -> Result<PathBuf, String> {
+    let is_safe = !run_id.is_empty()
+        && run_id.chars().all(|character| {
+            character.is_ascii_alphanumeric() || character == '-' || character == '_'
+        });
+    if !is_safe {
+        return Err(format!(
+            "Run id '{run_id}' cannot be used as a snapshot name."
+        ));
+    }
+    let common_dir = run_git_trimmed(
+        repo_path,
+        &["rev-parse", "--git-common-dir"],
+        "rev-parse --git-common-dir",
+    )?;
+    let common_dir = normalize_path(&repo_path.join(common_dir))?;
+    Ok(common_dir.join(RUN_SNAPSHOTS_DIR).join(run_id))
+}
+
+/// Checks out `head` of `workspace` as a detached worktree dedicated to `run_id`, reusing an
+/// existing snapshot at the same commit. Returns the worktree path and whether it was created.
+pub(crate) fn add_run_snapshot_worktree(
+    workspace: &str,
+    run_id: &str,
+    head: &str,
+) -> Result<(String, bool), String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let snapshot_path = run_snapshot_path(&repo_path, run_id)?;
+
+    if snapshot_path.exists() {
+        let snapshot_head = read_git_trimmed_if_success(&snapshot_path, &["rev-parse", "HEAD"]);
+        if snapshot_head.as_deref() == Some(head) {
+            return Ok((format_path(&snapshot_path), false));
+        }
+        remove_run_snapshot_worktree(workspace, run_id)?;
+    }
+
+    if GitRepo::open(&repo_path).commit_id(head).is_none() {
+        return Err(format!(
+            "Commit {head} is no longer in {}. It may have been garbage-collected after a rebase.",
+            format_path(&repo_path)
+        ));
+    }
+    if let Some(parent) = snapshot_path.parent() {
+        fs::create_dir_all(long_path(parent))
+            .map_err(|error| format!("Failed to create {}: {error}", format_path(parent)))?;
+    }
+    let snapshot_arg = snapshot_path.to_string_lossy().to_string();
+    run_git(
+        &repo_path,
+        &["worktree", "add", "--detach", snapshot_arg.as_str(), head],
+        "worktree add",
+    )?;
+    Ok((format_path(&snapshot_path), true))
+}
+
+/// Removes the snapshot worktree of `run_id`, discarding any edits made in it. Returns `false`
+/// when there was nothing to remove.
+pub(crate) fn remove_run_snapshot_worktree(workspace: &str, run_id: &str) -> Result<bool, String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let snapshot_path = run_snapshot_path(&repo_path, run_id)?;
+    let existed = snapshot_path.exists();
+    if existed {
+        let snapshot_arg = snapshot_path.to_string_lossy().to_string();
+        let removed = run_git(
+            &repo_path,
+            &["worktree", "remove", "--force", snapshot_arg.as_str()],
+            "worktree remove",
+        );
+        // A directory git no longer tracks as a worktree is deleted by hand.
+        if removed.is_err() {
+            fs::remove_dir_all(long_path(&snapshot_path)).map_err(|error| {
+                format!("Failed to remove {}: {error}", format_path(&snapshot_path))
+            })?;
+        }
+    }
+    run_git(&repo_path, &["worktree", "prune"], "worktree prune")?;
+    Ok(existed)
+}
+
+/// First line after the shebang of every hook script Rovex writes, so user hooks are never
+/// overwritten or removed.
+pub(crate) const REVIEW_GIT_HOOK_MARKER: &str = "# rovex-review-hook";
+
+/// Path of `hook` in the hooks directory git runs for the repository, honoring
+/// `core.hooksPath`.
+fn git_hook_path(repo_path: &Path, hook: &str) -> Result<PathBuf, String> {
+    let hook_path = run_git_trimmed(
+        repo_path,
+        &["rev-parse", "--git-path", &format!("hooks/{hook}")],
+        "rev-parse --git-path",
+    )?;
+    normalize_path(&repo_path.join(hook_path))
+}
+
+fn is_review_git_hook(hook_path: &Path) -> bool {
+    fs::read_to_string(long_path(hook_path))
+        .map(|script| script.contains(REVIEW_GIT_HOOK_MARKER))
+        .unwrap_or(false)
+}
+
+/// Writes `script` as the `hook` of `workspace` and makes it executable. Refuses to replace a
+/// hook that Rovex did not write. Returns the hook path.
+pub(crate) fn write_review_git_hook(
+    workspace: &str,
+    hook: &str,
+    script: &str,
+) -> Result<String, String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let hook_path = git_hook_path(&repo_path, hook)?;
+    if hook_path.exists() && !is_review_git_hook(&hook_path) {
+        return Err(format!(
+            "{} already exists and was not installed by Rovex. Remove it, or call rovex-cli from it yourself.",
+            format_path(&hook_path)
+        ));
+    }
+    if let Some(parent) = hook_path.parent() {
+        fs::create_dir_all(long_path(parent))
+            .map_err(|error| format!("Failed to create {}: {error}", format_path(parent)))?;
+    }
+    fs::write(long_path(&hook_path), script)
+        .map_err(|error| format!("Failed to write {}: {error}", format_path(&hook_path)))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(long_path(&hook_path), fs::Permissions::from_mode(0o755)).map_err(
+            |error| {
+                format!(
+                    "Failed to make {} executable: {error}",
+                    format_path(&hook_path)
+                )
+            },
+        )?;
+    }
+    Ok(format_path(&hook_path))
+}
+
+/// Removes the `hook` of `workspace` if Rovex wrote it. Returns `false` when there was no hook.
+pub(crate) fn remove_review_git_hook(workspace: &str, hook: &str) -> Result<bool, String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let hook_path = git_hook_path(&repo_path, hook)?;
+    if !hook_path.exists() {
+        return Ok(false);
+    }
+    if !is_review_git_hook(&hook_path) {
+        return Err(format!(
+            "{} was not installed by Rovex and was left in place.",
+            format_path(&hook_path)
+        ));
+    }
+    fs::remove_file(long_path(&hook_path))
+        .map_err(|error| format!("Failed to remove {}: {error}", format_path(&hook_path)))?;
+    Ok(true)
+}
+
+/// Current branch of `workspace`, or `None` on a detached HEAD.
+pub(crate) fn current_workspace_branch(workspace: &str) -> Result<Option<String>, String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let branch = run_git_trimmed(
         &repo_path,
         &["branch", "--show-current"],
         "branch --show-current",
     )?;
-    let current_branch = if current_branch.is_empty() {
-        None
-    } else {
-        Some(current_branch)
+    Ok((!branch.is_empty()).then_some(branch))
+}
+
+/// `remote` when given, after checking it, or the workspace's default remote.
+fn resolve_remote(repo_path: &Path, remote: Option<&str>) -> Result<String, String> {
+    Ok(parse_remote_name(remote)?.unwrap_or_else(|| GitRepo::open(repo_path).default_remote()))
+}
+
+/// Fetches `remote`, or the default remote when it is `None`, and returns the remote it used
+/// with the commit `<remote>/<branch>` points at.
+pub(crate) fn fetch_remote_branch_head(
+    workspace: &str,
+    remote: Option<&str>,
+    branch: &str,
+) -> Result<(String, String), String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let branch = parse_branch_name(branch)?;
+    validate_branch_name(&repo_path, &branch)?;
+    let remote = resolve_remote(&repo_path, remote)?;
+    run_git(
+        &repo_path,
+        &["fetch", "--quiet", &remote],
+        &format!("fetch {remote}"),
+    )?;
+    let head = run_git_trimmed(
+        &repo_path,
+        &[
+            "rev-parse",
+            "--verify",
+            &format!("refs/remotes/{remote}/{branch}^{{commit}}"),
+        ],
+        "resolve remote branch",
+    )?;
+    Ok((remote, head))
+}
+
+/// Checks that `remote`, or the default remote when it is `None`, has `branch`: from the
+/// remote-tracking ref when there is one and with `git ls-remote` otherwise, so nothing is
+/// fetched.
+pub(crate) fn verify_remote_branch(
+    workspace: &str,
+    remote: Option<&str>,
+    branch: &str,
+) -> Result<(), String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let branch = parse_branch_name(branch)?;
+    validate_branch_name(&repo_path, &branch)?;
+    let remote = resolve_remote(&repo_path, remote)?;
+    let tracking_ref = format!("refs/remotes/{remote}/{branch}");
+    if read_git_trimmed_if_success(
+        &repo_path,
+        &["rev-parse", "--verify", "--quiet", &tracking_ref],
+    )
+    .is_some()
+    {
+        return Ok(());
+    }
+    let remote_head = run_git_trimmed(
+        &repo_path,
+        &[
+            "ls-remote",
+            "--quiet",
+            &remote,
+            &format!("refs/heads/{branch}"),
+        ],
+        &format!("ls-remote {remote}"),
+    )?;
+    if remote_head.is_empty() {
+        return Err(format!("{remote} has no branch named {branch}."));
+    }
+    Ok(())
+}
+
+/// How far a clone lags behind its remotes. `stale_refs` are remote-tracking refs that their
+/// remote has moved past since the last fetch; `behind_upstream` counts commits the checkout
+/// is missing from its already-fetched upstream.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RemoteSyncCheck {
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub base_ref: Option<String>,
+    pub stale_refs: Vec<String>,
+    pub behind_upstream: i64,
+}
+
+/// Compares the base ref of the default remote and the current branch's upstream with the
+/// remotes they track using `git ls-remote`, so checking never fetches or touches the working
+/// tree.
+pub(crate) fn check_remote_sync(workspace: &str) -> Result<RemoteSyncCheck, String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let repo = GitRepo::open(&repo_path);
+
+    let branch = read_git_trimmed_if_success(&repo_path, &["branch", "--show-current"]);
+    let upstream = read_git_trimmed_if_success(
+        &repo_path,
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ],
+    );
+    let remote = repo.default_remote();
+    let base_ref = repo
+        .remote_head(&remote)
+        .filter(|remote_head| *remote_head != format!("{remote}/HEAD"))
+        .or_else(|| resolve_base_ref(&repo_path, &format!("{remote}/main")).ok())
+        .filter(|base_ref| ref_remote(&repo, base_ref).as_deref() == Some(remote.as_str()));
+
+    // Each tracked ref with the remote it belongs to; local upstreams have nothing to check.
+    let mut tracked_refs: Vec<(String, String)> = Vec::new();
+    for reference in [base_ref.as_ref(), upstream.as_ref()].into_iter().flatten() {
+        if tracked_refs.iter().any(|(tracked, _)| tracked == reference) {
+            continue;
+        }
+        if let Some(remote) = ref_remote(&repo, reference) {
+            tracked_refs.push((reference.clone(), remote));
+        }
+    }
+
+    let mut stale_refs = Vec::new();
+    let mut remotes = tracked_refs
+        .iter()
+        .map(|(_, remote)| remote.as_str())
+        .collect::<Vec<_>>();
+    remotes.sort_unstable();
+    remotes.dedup();
+    for remote in remotes {
+        let refs = tracked_refs
+            .iter()
+            .filter(|(_, tracked_remote)| tracked_remote == remote)
+            .map(|(reference, _)| reference)
+            .collect::<Vec<_>>();
+        let heads = refs
+            .iter()
+            .map(|reference| format!("refs/heads/{}", &reference[remote.len() + 1..]))
+            .collect::<Vec<_>>();
+        let mut args = vec!["ls-remote", "--quiet", remote];
+        args.extend(heads.iter().map(String::as_str));
+        let remote_heads = run_git_trimmed(&repo_path, &args, &format!("ls-remote {remote}"))?;
+        for (reference, head) in refs.into_iter().zip(&heads) {
+            // A branch deleted on the remote is not something a fetch would bring in.
+            let Some(remote_commit) = remote_heads.lines().find_map(|line| {
+                let (commit, name) = line.split_once('\t')?;
+                (name == head).then_some(commit)
+            }) else {
+                continue;
+            };
+            let local_commit = read_git_trimmed_if_success(
+                &repo_path,
+                &[
+                    "rev-parse",
+                    "--verify",
+                    &format!("refs/remotes/{reference}"),
+                ],
+            );
+            if local_commit.as_deref() != Some(remote_commit) {
+                stale_refs.push(reference.clone());
+            }
+        }
+    }
+
+    let behind_upstream = upstream
+        .as_ref()
+        .and_then(|_| {
+            read_git_trimmed_if_success(&repo_path, &["rev-list", "--count", "HEAD..@{upstream}"])
+        })
+        .and_then(|count| count.parse::<i64>().ok())
+        .unwrap_or(0);
+
+    Ok(RemoteSyncCheck {
+        branch,
+        upstream,
+        base_ref,
+        stale_refs,
+        behind_upstream,
+    })
+}
+
+/// The checkout of a workspace at one point in time: its commit and branch, and for every
+/// changed or untracked path its git status with the size and modification time on disk.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct WorkspaceSnapshot {
+    pub head: Option<String>,
+    pub branch: Option<String>,
+    pub paths: BTreeMap<String, String>,
+}
+
+/// A file's size and modification time, which change whenever it is written.
+fn disk_state(path: &Path) -> Option<String> {
+    let metadata = fs::metadata(long_path(path)).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|modified| modified.as_nanos())
+        .unwrap_or_default();
+    Some(format!("{} {modified}", metadata.len()))
+}
+
+/// Parses `git status --porcelain=v1 -z` into `(status, path)` pairs. Renames and copies are
+/// reported under their new path.
+pub(crate) fn parse_porcelain_status(output: &str) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    let mut records = output.split('\0').filter(|record| !record.is_empty());
+    while let Some(record) = records.next() {
+        let (Some(status), Some(path)) = (record.get(..2), record.get(3..)) else {
+            continue;
+        };
+        if status.contains(['R', 'C']) {
+            // The original path follows as its own record.
+            records.next();
+        }
+        entries.push((status.to_string(), path.to_string()));
+    }
+    entries
+}
+
+/// Reads what a workspace watch compares between checks. Only git plumbing and file
+/// metadata are read, so nothing in the workspace changes.
+pub(crate) fn read_workspace_snapshot(workspace: &str) -> Result<WorkspaceSnapshot, String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    let status = run_git(
+        &repo_path,
+        &["status", "--porcelain=v1", "-z", "--untracked-files=all"],
+        "status",
+    )?;
+    let mut paths = BTreeMap::new();
+    for (status, path) in parse_porcelain_status(&String::from_utf8_lossy(&status.stdout)) {
+        let disk_state =
+            disk_state(&repo_path.join(&path)).unwrap_or_else(|| "missing".to_string());
+        paths.insert(path, format!("{status} {disk_state}"));
+    }
+    Ok(WorkspaceSnapshot {
+        head: read_git_trimmed_if_success(&repo_path, &["rev-parse", "--verify", "HEAD"]),
+        branch: read_git_trimmed_if_success(&repo_path, &["branch", "--show-current"]),
+        paths,
+    })
+}
+
+fn is_ancestor(repo_path: &Path, from: &str, to: &str) -> bool {
+    Command::new("git")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["merge-base", "--is-ancestor", from, to])
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// `<short hash> <subject>` of up to `limit` commits reachable from `to` but not `from`,
+/// newest first. Empty when `from` is not an ancestor, as after a reset or checkout.
+pub(crate) fn commits_between(workspace: &str, from: &str, to: &str, limit: usize) -> Vec<String> {
+    let Ok(repo_path) = resolve_workspace_repo_path(workspace) else {
+        return Vec::new();
+    };
+    if !is_ancestor(&repo_path, from, to) {
+        return Vec::new();
+    }
+    read_git_trimmed_if_success(
+        &repo_path,
+        &[
+            "log",
+            "--format=%h %s",
+            &format!("--max-count={limit}"),
+            &format!("{from}..{to}"),
+        ],
+    )
+    .map(|log| log.lines().map(ToOwned::to_owned).collect())
+    .unwrap_or_default()
+}
+
+/// Full hash and message of up to `limit` commits reachable from `to` but not `from`, newest
+/// first. Empty under the same conditions as [`commits_between`].
+pub(crate) fn commit_messages_between(
+    workspace: &str,
+    from: &str,
+    to: &str,
+    limit: usize,
+) -> Vec<(String, String)> {
+    let Ok(repo_path) = resolve_workspace_repo_path(workspace) else {
+        return Vec::new();
+    };
+    if !is_ancestor(&repo_path, from, to) {
+        return Vec::new();
+    }
+    read_git_trimmed_if_success(
+        &repo_path,
+        &[
+            "log",
+            "--format=%H%x1f%B%x1e",
+            &format!("--max-count={limit}"),
+            &format!("{from}..{to}"),
+        ],
+    )
+    .map(|log| {
+        log.split('\u{1e}')
+            .filter_map(|entry| {
+                let (hash, message) = entry.trim_start().split_once('\u{1f}')?;
+                Some((hash.to_string(), message.trim().to_string()))
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// The commit that last touched each line in `ranges` (1-based, inclusive) of `file_path`,
+/// blamed at `rev` or in the working tree. Lines that are not committed yet map to `None`.
+fn blame_line_commits(
+    repo_path: &Path,
+    file_path: &str,
+    ranges: &BTreeSet<(usize, usize)>,
+    rev: Option<&str>,
+) -> Result<BTreeMap<usize, Option<String>>, String> {
+    let mut args = vec!["blame".to_string(), "--porcelain".to_string()];
+    for (start, end) in ranges {
+        args.push("-L".to_string());
+        args.push(format!("{start},{end}"));
+    }
+    args.extend(rev.map(ToOwned::to_owned));
+    args.extend(["--".to_string(), file_path.to_string()]);
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    let output = run_git(repo_path, &args, "blame")?;
+
+    // Each blamed line starts with `<commit> <original line> <final line>[ <group size>]`;
+    // the header and content lines in between never start with a full hash.
+    let mut commits = BTreeMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let mut fields = line.split(' ');
+        let (Some(commit), Some(_), Some(final_line)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if !matches!(commit.len(), 40 | 64) || !commit.bytes().all(|byte| byte.is_ascii_hexdigit())
+        {
+            continue;
+        }
+        let Ok(final_line) = final_line.parse::<usize>() else {
+            continue;
+        };
+        let uncommitted = commit.bytes().all(|byte| byte == b'0');
+        commits.insert(final_line, (!uncommitted).then(|| commit.to_string()));
+    }
+    Ok(commits)
+}
+
+struct BlameCommit {
+    author: String,
+    author_email: String,
+    authored_at: String,
+    summary: String,
+}
+
+fn read_blame_commits<'a>(
+    repo_path: &Path,
+    commits: impl IntoIterator<Item = &'a String>,
+) -> HashMap<String, BlameCommit> {
+    let commits = commits.into_iter().collect::<BTreeSet<_>>();
+    if commits.is_empty() {
+        return HashMap::new();
+    }
+    let mut args = vec![
+        "log",
+        "--no-walk=unsorted",
+        "--format=%H%x1f%an%x1f%ae%x1f%aI%x1f%s",
+    ];
+    args.extend(commits.iter().map(|commit| commit.as_str()));
+    read_git_trimmed_if_success(repo_path, &args)
+        .map(|log| {
+            log.lines()
+                .filter_map(|line| {
+                    let mut fields = line.split('\u{1f}');
+                    let hash = fields.next()?.to_string();
+                    let commit = BlameCommit {
+                        author: fields.next()?.to_string(),
+                        author_email: fields.next()?.to_string(),
+                        authored_at: fields.next()?.to_string(),
+                        summary: fields.next()?.to_string(),
+                    };
+                    Some((hash, commit))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Annotates findings with the blame of their lines. Added lines are blamed at `head`, in the
+/// working tree while `head` is still checked out so uncommitted edits count as introduced;
+/// removed lines are blamed at `merge_base` and are never introduced by the change. Findings
+/// git cannot blame, such as those in deleted or renamed files, are left without blame.
+pub(crate) fn blame_findings(
+    workspace: &str,
+    merge_base: &str,
+    head: &str,
+    findings: &mut [AiReviewFinding],
+) {
+    // Finding paths are relative to the repository root, not to the workspace.
+    let Some(repo_path) = resolve_workspace_repo_path(workspace)
+        .ok()
+        .and_then(|workspace| {
+            read_git_trimmed_if_success(&workspace, &["rev-parse", "--show-toplevel"])
+        })
+        .map(PathBuf::from)
+    else {
+        return;
+    };
+    let head_checked_out = read_git_trimmed_if_success(&repo_path, &["rev-parse", "HEAD"])
+        .is_some_and(|current| current == head);
+
+    let mut by_file: BTreeMap<(String, bool), Vec<usize>> = BTreeMap::new();
+    for (index, finding) in findings.iter().enumerate() {
+        if finding.line_number > 0 {
+            let added = finding.side != "deletions";
+            by_file
+                .entry((finding.file_path.clone(), added))
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let mut introduced_commits = HashMap::new();
+    for ((file_path, added), indices) in by_file {
+        let rev = match (added, head_checked_out) {
+            (false, _) => Some(merge_base),
+            (true, true) => None,
+            (true, false) => Some(head),
+        };
+        let ranges = indices
+            .iter()
+            .map(|&index| {
+                let line = findings[index].line_number as usize;
+                (line, line)
+            })
+            .collect();
+        let Ok(line_commits) = blame_line_commits(&repo_path, &file_path, &ranges, rev) else {
+            continue;
+        };
+        let details = read_blame_commits(&repo_path, line_commits.values().flatten());
+        for index in indices {
+            let line = findings[index].line_number as usize;
+            let Some(commit) = line_commits.get(&line) else {
+                continue;
+            };
+            let introduced = added
+                && commit.as_ref().is_none_or(|commit| {
+                    *introduced_commits
+                        .entry(commit.clone())
+                        .or_insert_with(|| !is_ancestor(&repo_path, commit, merge_base))
+                });
+            let detail = commit.as_ref().and_then(|commit| details.get(commit));
+            findings[index].blame = Some(FindingBlame {
+                commit: commit.clone(),
+                author: detail.map(|detail| detail.author.clone()),
+                authored_at: detail.map(|detail| detail.authored_at.clone()),
+                summary: detail.map(|detail| detail.summary.clone()),
+                introduced,
+            });
+        }
+    }
+}
+
+pub async fn clone_repository(
+    state: &AppState,
+    input: CloneRepositoryInput,
+) -> Result<CloneRepositoryResult, String> {
+    let connection = load_provider_connection_row(state, input.provider)
+        .await?
+        .ok_or_else(|| format!("{} is not connected.", input.provider.as_str()))?;
+    let client = provider_client(input.provider);
+    let repository = client.parse_repository(&input.repository)?;
+
+    let destination_root = resolve_repository_root(input.destination_root.as_deref())?;
+    fs::create_dir_all(long_path(&destination_root)).map_err(|error| {
+        format!(
+            "Failed to create clone destination {}: {error}",
+            format_path(&destination_root)
+        )
+    })?;
+
+    let directory_name =
+        parse_clone_directory_name(input.directory_name.as_deref(), &repository.name)?;
+    let destination_path = destination_root.join(directory_name);
+    if long_path(&destination_path).exists() {
+        return Err(format!(
+            "Destination already exists: {}",
+            format_path(&destination_path)
+        ));
+    }
+
+    let auth_header = client.clone_auth_header(&connection.access_token)?;
+    let clone_url = client.clone_url(&repository);
+    let mut command = Command::new("git");
+    command
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .arg("-c")
+        .arg(format!("http.extraHeader={auth_header}"))
+        .arg("-c")
+        .arg("core.longpaths=true")
+        .arg("clone");
+
+    let shallow = input.shallow.unwrap_or(true);
+    if shallow {
+        command.arg("--depth").arg("1");
+    }
+    if input.recurse_submodules.unwrap_or(false) {
+        command.arg("--recurse-submodules");
+        if shallow {
+            command.arg("--shallow-submodules");
+        }
+    }
+
+    let output = command
+        .arg(&clone_url)
+        .arg(&destination_path)
+        .output()
+        .map_err(|error| format!("Failed to run git clone: {error}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let summary = if !stderr.is_empty() { stderr } else { stdout };
+        let detail = if summary.is_empty() {
+            "Unknown git error.".to_string()
+        } else {
+            summary
+        };
+        return Err(format!("git clone failed: {detail}"));
+    }
+
+    Ok(CloneRepositoryResult {
+        provider: input.provider,
+        repository: repository.slug(),
+        workspace: format_path(&destination_path),
+    })
+}
+
+pub async fn get_repositories_dir() -> Result<RepositoriesDirResult, String> {
+    let root = resolve_repository_root(None)?;
+    Ok(RepositoriesDirResult {
+        repositories_dir: format_path(&root),
+    })
+}
+
+pub async fn migrate_repositories_dir(
+    state: State<'_, AppState>,
+    input: MigrateRepositoriesDirInput,
+) -> Result<MigrateRepositoriesDirResult, String> {
+    let destination = input.destination.trim();
+    if destination.is_empty() {
+        return Err("Repositories directory must not be empty.".to_string());
+    }
+    if !Path::new(destination).is_absolute() {
+        return Err("Repositories directory must be an absolute path.".to_string());
+    }
+    let destination_root = normalize_path(Path::new(destination))?;
+
+    let previous_root = resolve_repository_root(None)?;
+    if paths_equal(&destination_root, &previous_root) {
+        return Err("Repositories directory is already set to this path.".to_string());
+    }
+    if path_starts_with(&destination_root, &previous_root) {
+        return Err(
+            "Repositories directory cannot be moved inside the current directory.".to_string(),
+        );
+    }
+
+    let mut repositories = Vec::new();
+    if previous_root.is_dir() {
+        let entries = fs::read_dir(&previous_root)
+            .map_err(|error| format!("Failed to read {}: {error}", format_path(&previous_root)))?;
+        for entry in entries.flatten() {
+            if entry.file_type().map(|kind| kind.is_dir()).unwrap_or(false) {
+                repositories.push(entry.file_name());
+            }
+        }
+    }
+    repositories.sort();
+
+    let conflicts = repositories
+        .iter()
+        .filter(|name| long_path(&destination_root.join(name)).exists())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    if !conflicts.is_empty() {
+        return Err(format!(
+            "Destination already contains: {}",
+            conflicts.join(", ")
+        ));
+    }
+
+    fs::create_dir_all(long_path(&destination_root)).map_err(|error| {
+        format!(
+            "Failed to create repositories directory {}: {error}",
+            format_path(&destination_root)
+        )
+    })?;
+
+    let mut moved: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for name in &repositories {
+        let from = previous_root.join(name);
+        let to = destination_root.join(name);
+        if let Err(error) = move_directory(&from, &to) {
+            for (moved_from, moved_to) in moved.iter().rev() {
+                let _ = move_directory(moved_to, moved_from);
+            }
+            return Err(error);
+        }
+        moved.push((from, to));
+    }
+
+    // The paths are rewritten in one transaction, and the repositories move back when it
+    // fails, so the database never points at directories that are not there.
+    let conn = state.connection()?;
+    let updated = match rewrite_moved_workspaces(&conn, &moved).await {
+        Ok(updated) => updated,
+        Err(error) => {
+            for (moved_from, moved_to) in moved.iter().rev() {
+                let _ = move_directory(moved_to, moved_from);
+            }
+            return Err(error);
+        }
+    };
+    settings_store()
+        .set(
+            &conn,
+            ROVEX_REPOSITORIES_DIR_ENV,
+            &format_path(&destination_root),
+        )
+        .await?;
+
+    Ok(MigrateRepositoriesDirResult {
+        previous_dir: format_path(&previous_root),
+        repositories_dir: format_path(&destination_root),
+        moved_repositories: moved.iter().map(|(_, to)| format_path(to)).collect(),
+        updated_threads: updated[0],
+        updated_review_runs: updated[1],
+        updated_inline_comments: updated[2],
+    })
+}
+
+/// Fills in each submodule change's own diff when the submodule is checked out as one of
+/// the `registered` workspaces. Commits the checkout does not have leave the diff out.
+pub(crate) fn attach_submodule_diffs(
+    repo_path: &Path,
+    submodules: &mut [WorkspaceSubmoduleChange],
+    registered: &[String],
+) {
+    for submodule in submodules {
+        let submodule_path = repo_path.join(&submodule.path);
+        let Some(workspace) = registered
+            .iter()
+            .find(|workspace| paths_equal(Path::new(workspace), &submodule_path))
+        else {
+            continue;
+        };
+        submodule.workspace = Some(workspace.clone());
+        let (Some(old_commit), Some(new_commit)) = (&submodule.old_commit, &submodule.new_commit)
+        else {
+            continue;
+        };
+        submodule.diff = run_git(
+            Path::new(workspace),
+            &[
+                "diff",
+                "--no-color",
+                "--no-ext-diff",
+                "--patch",
+                old_commit,
+                new_commit,
+            ],
+            "diff in submodule",
+        )
+        .ok()
+        .map(|output| {
+            truncate_utf8_by_bytes(
+                &String::from_utf8_lossy(&output.stdout),
+                MAX_COMPARE_DIFF_BYTES,
+            )
+            .0
+        });
+    }
+}
+
+/// The options of a comparison that shape its result.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ComparisonKey {
+    workspace: String,
+    base_ref: String,
+    diff_target: WorkspaceDiffTarget,
+    head_ref: Option<String>,
+    include_paths: Option<Vec<String>>,
+    exclude_paths: Vec<String>,
+    include_untracked: bool,
+    /// Remote fetched before comparing, if any.
+    remote: Option<String>,
+}
+
+/// The state of the repository a comparison was computed from. The result is reused only
+/// while none of it changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ComparisonFingerprint {
+    base_commit: Option<String>,
+    merge_base: String,
+    head: String,
+    /// Status, size and modification time of every changed path, and what is staged, unless
+    /// the comparison reads neither.
+    working_tree: Option<(BTreeMap<String, String>, Option<u64>)>,
+    /// Hash of the `.rovexignore` the diff was filtered through.
+    rovexignore: Option<u64>,
+}
+
+struct CachedComparison {
+    fingerprint: ComparisonFingerprint,
+    fetched_at: Option<Instant>,
+    used_at: Instant,
+    /// [`comparison_bytes`] of `result`.
+    bytes: usize,
+    result: CompareWorkspaceDiffResult,
+}
+
+/// Roughly how much memory `result` holds: its diffs and the hunks of its files.
+fn comparison_bytes(result: &CompareWorkspaceDiffResult) -> usize {
+    let files = result
+        .files
+        .iter()
+        .map(|file| {
+            let hunks = file
+                .hunks
+                .iter()
+                .map(|hunk| {
+                    hunk.header.len()
+                        + (hunk.addition_lines.len() + hunk.deletion_lines.len())
+                            * std::mem::size_of::<i64>()
+                })
+                .sum::<usize>();
+            file.path.len() + file.patch.len() + hunks
+        })
+        .sum::<usize>();
+    let submodules = result
+        .submodules
+        .iter()
+        .filter_map(|submodule| submodule.diff.as_ref())
+        .map(String::len)
+        .sum::<usize>();
+    result.diff.len() + files + submodules
+}
+
+static CACHED_COMPARISONS: OnceLock<Mutex<HashMap<ComparisonKey, CachedComparison>>> =
+    OnceLock::new();
+
+fn cached_comparisons() -> &'static Mutex<HashMap<ComparisonKey, CachedComparison>> {
+    CACHED_COMPARISONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Whether the remote was fetched for this comparison recently enough to skip fetching again.
+fn fetched_recently(key: &ComparisonKey) -> bool {
+    cached_comparisons()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(key)?.fetched_at)
+        .is_some_and(|fetched_at| fetched_at.elapsed() < COMPARE_FETCH_INTERVAL)
+}
+
+/// The cached result for `key`, if it was computed from the same repository state.
+fn cached_comparison(
+    key: &ComparisonKey,
+    fingerprint: &ComparisonFingerprint,
+    fetched_at: Option<Instant>,
+) -> Option<CompareWorkspaceDiffResult> {
+    let mut cache = cached_comparisons().lock().ok()?;
+    let cached = cache.get_mut(key)?;
+    if fetched_at.is_some() {
+        cached.fetched_at = fetched_at;
+    }
+    if cached.fingerprint != *fingerprint {
+        return None;
+    }
+    cached.used_at = Instant::now();
+    Some(cached.result.clone())
+}
+
+fn cache_comparison(
+    key: ComparisonKey,
+    fingerprint: ComparisonFingerprint,
+    fetched_at: Option<Instant>,
+    result: &CompareWorkspaceDiffResult,
+) {
+    let Ok(mut cache) = cached_comparisons().lock() else {
+        return;
+    };
+    let fetched_at = fetched_at.or_else(|| cache.remove(&key).and_then(|cached| cached.fetched_at));
+    let bytes = comparison_bytes(result);
+    if bytes > MAX_CACHED_COMPARISON_BYTES {
+        return;
+    }
+    let mut cached_bytes = cache.values().map(|cached| cached.bytes).sum::<usize>();
+    while cached_bytes + bytes > MAX_CACHED_COMPARISON_BYTES {
+        let Some(oldest) = cache
+            .iter()
+            .min_by_key(|(_, cached)| cached.used_at)
+            .map(|(key, _)| key.clone())
+        else {
+            break;
+        };
+        cached_bytes -= cache.remove(&oldest).map_or(0, |cached| cached.bytes);
+    }
+    cache.insert(
+        key,
+        CachedComparison {
+            fingerprint,
+            fetched_at,
+            used_at: Instant::now(),
+            bytes,
+            result: result.clone(),
+        },
+    );
+}
+
+pub async fn compare_workspace_diff(
+    input: CompareWorkspaceDiffInput,
+) -> Result<CompareWorkspaceDiffResult, String> {
+    let started_at = Instant::now();
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let repo_config = load_repo_review_config(&repo_path)?.unwrap_or_default();
+    let exclude_paths = repo_config.merged_exclude_paths(input.exclude_paths.as_deref());
+    let path_filter = PathFilter::new(
+        input.include_paths.as_deref(),
+        Some(exclude_paths.as_slice()),
+    )?
+    .with_rovexignore(&repo_path)?;
+    let pathspecs = path_filter.git_pathspecs();
+    let diff_target = input.diff_target.unwrap_or_default();
+    let compares_branch = diff_target == WorkspaceDiffTarget::Branch;
+    let head_ref = as_non_empty_trimmed(input.head_ref.as_deref());
+    if head_ref.is_some() && !compares_branch {
+        return Err("A head ref can only be compared with the branch target.".to_string());
+    }
+
+    let repo = GitRepo::open(&repo_path);
+    let requested_base_ref = input
+        .base_ref
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .or(repo_config.base_ref.as_deref())
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| format!("{}/main", repo.default_remote()));
+    let requested_base_ref = requested_base_ref.as_str();
+    let fetch_remote = match parse_remote_name(input.remote.as_deref())? {
+        Some(remote) => Some(remote),
+        None => ref_remote(&repo, requested_base_ref),
+    };
+
+    let force = input.force.unwrap_or(false);
+    let cache_key = ComparisonKey {
+        workspace: format_path(&repo_path),
+        base_ref: requested_base_ref.to_string(),
+        diff_target,
+        head_ref: head_ref.clone(),
+        include_paths: input.include_paths.clone(),
+        exclude_paths: exclude_paths.clone(),
+        include_untracked: input.include_untracked.unwrap_or(false),
+        remote: fetch_remote.clone(),
     };
 
-    let upstream_branch = if let Some(current_branch_name) = current_branch.as_deref() {
-        let current_branch_ref = format!("refs/heads/{current_branch_name}");
-        let upstream = run_git_trimmed(
+    let should_fetch_remote = compares_branch
+        && input.fetch_remote.unwrap_or(true)
+        && (force || !fetched_recently(&cache_key));
+    let mut fetch_origin_ms = None;
+    let mut fetched_at = None;
+    if let Some(remote) = fetch_remote.as_deref().filter(|_| should_fetch_remote) {
+        let fetch_started_at = Instant::now();
+        run_git(
             &repo_path,
-            &[
-                "for-each-ref",
-                "--format=%(upstream:short)",
-                current_branch_ref.as_str(),
-            ],
-            "resolve branch upstream",
+            &["fetch", "--quiet", remote],
+            &format!("fetch {remote}"),
         )?;
-        if upstream.is_empty() {
-            None
-        } else {
-            Some(upstream)
+        fetch_origin_ms = Some(fetch_started_at.elapsed().as_millis() as u64);
+        fetched_at = Some(fetch_started_at);
+    }
+
+    // Uncommitted targets compare against HEAD itself, so HEAD doubles as the base ref and
+    // merge base.
+    let resolve_base_ref_started_at = Instant::now();
+    let base_ref = if compares_branch {
+        resolve_base_ref(&repo_path, requested_base_ref)?
+    } else {
+        "HEAD".to_string()
+    };
+    let resolve_base_ref_ms = resolve_base_ref_started_at.elapsed().as_millis() as u64;
+
+    let resolve_head_started_at = Instant::now();
+    let head = match head_ref.as_deref() {
+        Some(head_ref) => repo.resolve_commit(head_ref, "resolve head ref")?,
+        None => repo.resolve_commit("HEAD", "resolve HEAD")?,
+    };
+    let resolve_head_ms = resolve_head_started_at.elapsed().as_millis() as u64;
+
+    let resolve_merge_base_started_at = Instant::now();
+    let (merge_base, history_deepened_by) = if compares_branch {
+        resolve_merge_base_deepening(&repo_path, &head, &base_ref)?
+    } else {
+        (head.clone(), None)
+    };
+    let resolve_merge_base_ms = resolve_merge_base_started_at.elapsed().as_millis() as u64;
+
+    // A comparison against another head ref never reads the working tree or the index.
+    let fingerprint = ComparisonFingerprint {
+        base_commit: repo.commit_id(&base_ref),
+        merge_base: merge_base.clone(),
+        head: head.clone(),
+        working_tree: match head_ref {
+            Some(_) => None,
+            None => Some((
+                read_workspace_snapshot(&input.workspace)?.paths,
+                repo.index_fingerprint(),
+            )),
+        },
+        rovexignore: rovexignore_fingerprint(&repo_path),
+    };
+    if !force {
+        if let Some(mut result) = cached_comparison(&cache_key, &fingerprint, fetched_at) {
+            result.cached = true;
+            result.last_fetched_at = repo.last_fetched_at();
+            result.profile = CompareWorkspaceDiffProfile {
+                fetch_origin_ms,
+                resolve_base_ref_ms,
+                resolve_head_ms,
+                resolve_merge_base_ms,
+                diff_ms: 0,
+                numstat_ms: 0,
+                total_ms: started_at.elapsed().as_millis() as u64,
+            };
+            return Ok(result);
         }
+    }
+
+    let mut comparison_args = match diff_target {
+        WorkspaceDiffTarget::Branch => vec!["--merge-base", base_ref.as_str()],
+        WorkspaceDiffTarget::WorkingTree => vec!["HEAD"],
+        WorkspaceDiffTarget::Staged => vec!["--cached", "HEAD"],
+    };
+    if head_ref.is_some() {
+        comparison_args.push(head.as_str());
+    }
+
+    let mut diff_args = vec!["diff"];
+    diff_args.extend(comparison_args.iter().copied());
+    diff_args.extend(["--no-color", "--no-ext-diff", "--patch"]);
+    if COMPARE_ENABLE_RENAMES {
+        diff_args.push("--find-renames");
+    } else {
+        diff_args.push("--no-renames");
+    }
+    if !pathspecs.is_empty() {
+        diff_args.push("--");
+        diff_args.extend(pathspecs.iter().map(String::as_str));
+    }
+
+    let diff_started_at = Instant::now();
+    let diff_output = run_git(&repo_path, &diff_args, "diff")?;
+    let mut raw_diff = String::from_utf8_lossy(&diff_output.stdout).to_string();
+    if path_filter.needs_diff_filtering() {
+        raw_diff = filter_diff_by_path(&raw_diff, &path_filter).diff;
+    }
+    // Staged comparisons only cover the index, and another head ref never sees the working
+    // tree, so untracked files belong to neither.
+    let include_untracked = input.include_untracked.unwrap_or(false)
+        && diff_target != WorkspaceDiffTarget::Staged
+        && head_ref.is_none();
+    let untracked = if include_untracked {
+        let untracked_diff = untracked_files_diff(&repo_path, &pathspecs, &path_filter)?;
+        Some(filter_diff_by_path(
+            &untracked_diff,
+            &PathFilter::new(None, None)?,
+        ))
     } else {
         None
     };
+    if let Some(untracked) = &untracked {
+        raw_diff.push_str(&untracked.diff);
+    }
+    let new_side = match (diff_target, head_ref.is_some()) {
+        (WorkspaceDiffTarget::Staged, _) => BinaryDiffSide::Object(""),
+        (_, true) => BinaryDiffSide::Object(&head),
+        _ => BinaryDiffSide::WorkingTree,
+    };
+    let old_revision = if compares_branch {
+        merge_base.as_str()
+    } else {
+        "HEAD"
+    };
+    raw_diff = decode_binary_diffs(&repo_path, &raw_diff, old_revision, &new_side);
+    let diff_ms = diff_started_at.elapsed().as_millis() as u64;
+    let diff_bytes_total = raw_diff.len();
+    let (diff, diff_truncated) = truncate_utf8_by_bytes(&raw_diff, MAX_COMPARE_DIFF_BYTES);
+    let diff_bytes_used = diff.len();
+    let files = parse_truncated_diff_files(&raw_diff, diff_bytes_used);
+    let skipped_files = reviewable_diff_files(&repo_path, &files)?.skipped;
+    // The patches were only needed to find the skipped files, and would double the memory a
+    // cached comparison takes.
+    let files = files
+        .into_iter()
+        .map(|file| DiffFile {
+            patch: Arc::from(""),
+            ..file
+        })
+        .collect::<Vec<_>>();
+
+    let numstat_started_at = Instant::now();
+    let mut numstat_args = vec!["diff"];
+    numstat_args.extend(comparison_args.iter().copied());
+    numstat_args.push("--numstat");
+    if !pathspecs.is_empty() {
+        numstat_args.push("--");
+        numstat_args.extend(pathspecs.iter().map(String::as_str));
+    }
+    let numstat_output = run_git(&repo_path, &numstat_args, "diff --numstat")?;
+    let numstat_ms = numstat_started_at.elapsed().as_millis() as u64;
+    let mut numstat = String::from_utf8_lossy(&numstat_output.stdout).to_string();
+    if path_filter.needs_diff_filtering() {
+        numstat = filter_numstat_by_path(&numstat, &path_filter);
+    }
+    let (mut files_changed, mut insertions, deletions) = parse_numstat(&numstat);
+    let submodules = submodule_changes(&repo_path, &comparison_args, &pathspecs)?;
+    if let Some(untracked) = &untracked {
+        files_changed += untracked.files_changed;
+        insertions += untracked.insertions;
+    }
+    let total_ms = started_at.elapsed().as_millis() as u64;
+
+    let profile = CompareWorkspaceDiffProfile {
+        fetch_origin_ms,
+        resolve_base_ref_ms,
+        resolve_head_ms,
+        resolve_merge_base_ms,
+        diff_ms,
+        numstat_ms,
+        total_ms,
+    };
 
-    let branch_output = run_git(
-        &repo_path,
-        &["for-each-ref", "--format=%(refname:short)", "refs/heads"],
-        "for-each-ref",
-    )?;
-    let raw_branches = String::from_utf8_lossy(&branch_output.stdout);
-    let mut branch_names: Vec<String> = raw_branches
-        .lines()
-        .map(str::trim)
-        .filter(|line| !line.is_empty())
-        .map(ToOwned::to_owned)
-        .collect();
+    let result = CompareWorkspaceDiffResult {
+        workspace: format_path(&repo_path),
+        base_ref,
+        merge_base,
+        head,
+        diff,
+        files,
+        files_changed,
+        insertions,
+        deletions,
+        diff_truncated,
+        diff_bytes_used,
+        diff_bytes_total,
+        diff_target,
+        profile,
+        repo_config_path: (!repo_config.path.is_empty()).then_some(repo_config.path),
+        history_deepened_by,
+        submodules,
+        skipped_files,
+        cached: false,
+        last_fetched_at: repo.last_fetched_at(),
+        remote: fetch_remote,
+    };
+    cache_comparison(cache_key, fingerprint, fetched_at, &result);
+    Ok(result)
+}
+
+/// [`compare_workspace_diff`], adding submodule diffs from registered workspaces when the
+/// input asks for them.
+pub async fn compare_workspace_diff_with_submodules(
+    state: &AppState,
+    input: CompareWorkspaceDiffInput,
+) -> Result<CompareWorkspaceDiffResult, String> {
+    let include_submodule_diffs = input.include_submodule_diffs.unwrap_or(false);
+    let mut result = compare_workspace_diff(input).await?;
+    if include_submodule_diffs && !result.submodules.is_empty() {
+        let registered = registered_workspaces(state).await?;
+        attach_submodule_diffs(
+            Path::new(&result.workspace),
+            &mut result.submodules,
+            &registered,
+        );
+    }
+    Ok(result)
+}
 
+pub async fn list_workspace_branches(
+    input: ListWorkspaceBranchesInput,
+) -> Result<ListWorkspaceBranchesResult, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let collator = NameCollator::from(input.sort.as_ref());
+
+    let repo = GitRepo::open(&repo_path);
+    let default_remote = repo.default_remote();
+    if input.fetch_remote.unwrap_or(false) {
+        let remote =
+            parse_remote_name(input.remote.as_deref())?.unwrap_or_else(|| default_remote.clone());
+        run_git(
+            &repo_path,
+            &["fetch", "--quiet", &remote],
+            &format!("fetch {remote}"),
+        )?;
+    }
+
+    let current_branch = repo.current_branch()?;
+    let upstream_branch = match current_branch.as_deref() {
+        Some(current_branch_name) => repo.upstream_of(current_branch_name)?,
+        None => None,
+    };
+
+    let mut branch_names = repo.ref_names("refs/heads")?;
     branch_names.sort_by(|left, right| {
         ref_sort_priority(left)
             .cmp(&ref_sort_priority(right))
-            .then_with(|| left.to_lowercase().cmp(&right.to_lowercase()))
+            .then_with(|| collator.compare(left, right))
     });
     branch_names.dedup();
 
-    let remote_branch_output = run_git(
-        &repo_path,
-        &["for-each-ref", "--format=%(refname:short)", "refs/remotes"],
-        "for-each-ref remotes",
-    )?;
-    let raw_remote_branches = String::from_utf8_lossy(&remote_branch_output.stdout);
-    let mut remote_branch_names: Vec<String> = raw_remote_branches
-        .lines()
-        .map(str::trim)
-        .filter(|line| !line.is_empty())
-        .filter(|line| !line.ends_with("/HEAD"))
-        .map(ToOwned::to_owned)
-        .collect();
+    let mut remote_branch_names = repo.ref_names("refs/remotes")?;
+    remote_branch_names.retain(|name| !name.ends_with("/HEAD"));
     remote_branch_names.sort_by(|left, right| {
         ref_sort_priority(left)
             .cmp(&ref_sort_priority(right))
-            .then_with(|| left.to_lowercase().cmp(&right.to_lowercase()))
+            .then_with(|| collator.compare(left, right))
     });
     remote_branch_names.dedup();
 
-    let suggested_base_ref = resolve_suggested_base_ref(
-        &repo_path,
-        upstream_branch.as_deref(),
-        &remote_branch_names,
-        &branch_names,
-    );
+    let suggested_base_ref = resolve_suggested_base_ref(
+        &repo,
+        &default_remote,
+        upstream_branch.as_deref(),
+        &remote_branch_names,
+        &branch_names,
+    );
+
+    let mut remotes = repo
+        .remote_names()
+        .into_iter()
+        .map(|name| WorkspaceRemote {
+            url: repo.remote_url(&name),
+            name,
+        })
+        .collect::<Vec<_>>();
+    remotes.sort_by(|left, right| collator.compare(&left.name, &right.name));
+
+    let branches = branch_names
+        .into_iter()
+        .map(|name| WorkspaceBranch {
+            is_current: current_branch.as_deref() == Some(name.as_str()),
+            name,
+        })
+        .collect();
+
+    let remote_branches = remote_branch_names
+        .into_iter()
+        .map(|name| WorkspaceBranch {
+            is_current: upstream_branch.as_deref() == Some(name.as_str()),
+            name,
+        })
+        .collect();
+
+    Ok(ListWorkspaceBranchesResult {
+        workspace: format_path(&repo_path),
+        current_branch,
+        branches,
+        upstream_branch,
+        remote_branches,
+        suggested_base_ref,
+        remotes,
+        default_remote,
+    })
+}
+
+/// Rejects revisions git would read as an option.
+fn parse_revision(value: Option<&str>, label: &str) -> Result<Option<String>, String> {
+    let revision = as_non_empty_trimmed(value);
+    if revision
+        .as_deref()
+        .is_some_and(|value| value.starts_with('-'))
+    {
+        return Err(format!("{label} must not start with '-'."));
+    }
+    Ok(revision)
+}
+
+/// Commits printed with `--format=%H%x1f%h%x1f%P%x1f%an%x1f%ae%x1f%aI%x1f%s%x1e`.
+fn parse_commit_log(log: &str) -> Vec<WorkspaceCommit> {
+    log.split('\u{1e}')
+        .filter_map(|entry| {
+            let mut fields = entry.trim_start().split('\u{1f}');
+            let hash = fields.next().filter(|hash| !hash.is_empty())?;
+            Some(WorkspaceCommit {
+                hash: hash.to_string(),
+                short_hash: fields.next()?.to_string(),
+                parents: fields
+                    .next()?
+                    .split_whitespace()
+                    .map(ToOwned::to_owned)
+                    .collect(),
+                author: fields.next()?.to_string(),
+                author_email: fields.next()?.to_string(),
+                authored_at: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// `(status, previous path, path)` of each file in `git diff-tree --name-status -z` output.
+fn parse_name_status(output: &str) -> Vec<(String, Option<String>, String)> {
+    let mut fields = output.split('\0').filter(|field| !field.is_empty());
+    let mut files = Vec::new();
+    while let Some(code) = fields.next() {
+        let status = match code.chars().next() {
+            Some('A') => "added",
+            Some('D') => "deleted",
+            Some('R') => "renamed",
+            Some('C') => "copied",
+            Some('T') => "type-changed",
+            _ => "modified",
+        };
+        let previous_path = if matches!(status, "renamed" | "copied") {
+            fields.next().map(ToOwned::to_owned)
+        } else {
+            None
+        };
+        let Some(path) = fields.next() else {
+            break;
+        };
+        files.push((status.to_string(), previous_path, path.to_string()));
+    }
+    files
+}
+
+/// Splits a patch into its files, in the order git printed them.
+fn split_patch_files(patch: &str) -> Vec<&str> {
+    let mut starts = patch
+        .match_indices("diff --git ")
+        .map(|(index, _)| index)
+        .filter(|&index| index == 0 || patch.as_bytes()[index - 1] == b'\n')
+        .collect::<Vec<_>>();
+    starts.push(patch.len());
+    starts
+        .windows(2)
+        .map(|window| &patch[window[0]..window[1]])
+        .collect()
+}
+
+/// Added and removed lines of one file's patch, or `None` when git did not diff it as text.
+fn count_patch_lines(patch: &str) -> Option<(i64, i64)> {
+    let mut lines = patch.lines().skip_while(|line| !line.starts_with("@@ "));
+    lines.next()?;
+    Some(lines.fold((0, 0), |(insertions, deletions), line| {
+        if line.starts_with('+') {
+            (insertions + 1, deletions)
+        } else if line.starts_with('-') {
+            (insertions, deletions + 1)
+        } else {
+            (insertions, deletions)
+        }
+    }))
+}
+
+pub async fn list_workspace_commits(
+    input: ListWorkspaceCommitsInput,
+) -> Result<ListWorkspaceCommitsResult, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let range = parse_revision(input.range.as_deref(), "Commit range")?
+        .unwrap_or_else(|| "HEAD".to_string());
+    let offset = input.offset.unwrap_or(0) as usize;
+    let limit = input
+        .limit
+        .map(|limit| (limit as usize).clamp(1, MAX_LISTED_COMMITS))
+        .unwrap_or(DEFAULT_LISTED_COMMITS);
+
+    let mut args = vec![
+        "log".to_string(),
+        "--format=%H%x1f%h%x1f%P%x1f%an%x1f%ae%x1f%aI%x1f%s%x1e".to_string(),
+        format!("--skip={offset}"),
+        // One more than the page, to tell whether another page follows.
+        format!("--max-count={}", limit + 1),
+    ];
+    if let Some(author) = as_non_empty_trimmed(input.author.as_deref()) {
+        args.extend([
+            format!("--author={author}"),
+            "--regexp-ignore-case".to_string(),
+            "--fixed-strings".to_string(),
+        ]);
+    }
+    args.extend([range.clone(), "--".to_string()]);
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    let log = run_git(&repo_path, &args, "log")?;
+
+    let mut commits = parse_commit_log(&String::from_utf8_lossy(&log.stdout));
+    let has_more = commits.len() > limit;
+    commits.truncate(limit);
+    Ok(ListWorkspaceCommitsResult {
+        workspace: format_path(&repo_path),
+        range,
+        commits,
+        has_more,
+    })
+}
+
+pub async fn get_commit_detail(input: GetCommitDetailInput) -> Result<CommitDetail, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let commit = parse_revision(Some(&input.commit), "Commit")?
+        .ok_or_else(|| "Commit must not be empty.".to_string())?;
+    let hash = read_git_trimmed_if_success(
+        &repo_path,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{commit}^{{commit}}"),
+        ],
+    )
+    .ok_or_else(|| format!("Commit {commit} was not found."))?;
+
+    let header = run_git(
+        &repo_path,
+        &[
+            "show",
+            "-s",
+            "--format=%h%x1f%P%x1f%an%x1f%ae%x1f%aI%x1f%cn%x1f%cI%x1f%B",
+            &hash,
+        ],
+        "show",
+    )?;
+    let header = String::from_utf8_lossy(&header.stdout);
+    let fields = header.splitn(8, '\u{1f}').collect::<Vec<_>>();
+    let [short_hash, parents, author, author_email, authored_at, committer, committed_at, message] =
+        fields[..]
+    else {
+        return Err(format!("Failed to parse commit {hash}."));
+    };
+    let parents = parents
+        .split_whitespace()
+        .map(ToOwned::to_owned)
+        .collect::<Vec<_>>();
+    let message = message.trim().to_string();
+
+    let mut diff_args = vec!["diff-tree", "-r", "--no-commit-id", "--no-ext-diff"];
+    diff_args.push(if COMPARE_ENABLE_RENAMES {
+        "--find-renames"
+    } else {
+        "--no-renames"
+    });
+    let revisions = match parents.first() {
+        Some(parent) => [parent.as_str(), hash.as_str()],
+        None => ["--root", hash.as_str()],
+    };
+    let name_status = run_git(
+        &repo_path,
+        &[&diff_args[..], &["--name-status", "-z"], &revisions[..]].concat(),
+        "diff-tree --name-status",
+    )?;
+    let patch = run_git(
+        &repo_path,
+        &[&diff_args[..], &["--patch"], &revisions[..]].concat(),
+        "diff-tree --patch",
+    )?;
+    let patch = String::from_utf8_lossy(&patch.stdout);
+    let patches = split_patch_files(&patch);
+
+    let mut files = Vec::new();
+    let (mut insertions, mut deletions) = (0, 0);
+    let mut diff_bytes = 0;
+    let mut diff_truncated = false;
+    for (index, (status, previous_path, path)) in
+        parse_name_status(&String::from_utf8_lossy(&name_status.stdout))
+            .into_iter()
+            .enumerate()
+    {
+        let file_patch = patches.get(index).copied().unwrap_or_default();
+        let counts = count_patch_lines(file_patch);
+        if let Some((file_insertions, file_deletions)) = counts {
+            insertions += file_insertions;
+            deletions += file_deletions;
+        }
+        diff_bytes += file_patch.len();
+        let diff = if diff_bytes <= MAX_COMPARE_DIFF_BYTES {
+            Some(file_patch.to_string())
+        } else {
+            diff_truncated = true;
+            None
+        };
+        files.push(CommitFileChange {
+            path,
+            previous_path,
+            status,
+            insertions: counts.map(|(file_insertions, _)| file_insertions),
+            deletions: counts.map(|(_, file_deletions)| file_deletions),
+            diff,
+        });
+    }
+
+    Ok(CommitDetail {
+        short_hash: short_hash.to_string(),
+        parents,
+        author: author.to_string(),
+        author_email: author_email.to_string(),
+        authored_at: authored_at.to_string(),
+        committer: committer.to_string(),
+        committed_at: committed_at.to_string(),
+        subject: message.lines().next().unwrap_or_default().to_string(),
+        message,
+        files,
+        insertions,
+        deletions,
+        diff_truncated,
+        hash,
+    })
+}
 
-    let branches = branch_names
-        .into_iter()
-        .map(|name| WorkspaceBranch {
-            is_current: current_branch.as_deref() == Some(name.as_str()),
-            name,
-        })
-        .collect();
+pub async fn get_line_blame(input: GetLineBlameInput) -> Result<GetLineBlameResult, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let (_, file_path) = WorkspaceRoot::new(&input.workspace)?.resolve(&input.file_path)?;
+    if file_path.is_empty() {
+        return Err("File path must not be empty.".to_string());
+    }
+    if input.start_line == 0 {
+        return Err("startLine must be at least 1.".to_string());
+    }
+    let end_line = input.end_line.unwrap_or(input.start_line);
+    if end_line < input.start_line {
+        return Err("endLine must not be before startLine.".to_string());
+    }
+    if end_line - input.start_line >= MAX_BLAME_LINES {
+        return Err(format!(
+            "At most {MAX_BLAME_LINES} lines can be blamed at once."
+        ));
+    }
+    let rev = parse_revision(input.rev.as_deref(), "Revision")?;
 
-    let remote_branches = remote_branch_names
+    let line_commits = blame_line_commits(
+        &repo_path,
+        &file_path,
+        &BTreeSet::from([(input.start_line, end_line)]),
+        rev.as_deref(),
+    )?;
+    let details = read_blame_commits(&repo_path, line_commits.values().flatten());
+    let lines = line_commits
         .into_iter()
-        .map(|name| WorkspaceBranch {
-            is_current: upstream_branch.as_deref() == Some(name.as_str()),
-            name,
+        .map(|(line_number, commit)| {
+            let detail = commit.as_ref().and_then(|commit| details.get(commit));
+            LineBlame {
+                line_number,
+                author: detail.map(|detail| detail.author.clone()),
+                author_email: detail.map(|detail| detail.author_email.clone()),
+                authored_at: detail.map(|detail| detail.authored_at.clone()),
+                summary: detail.map(|detail| detail.summary.clone()),
+                commit,
+            }
         })
         .collect();
-
-    Ok(ListWorkspaceBranchesResult {
-        workspace: format_path(&repo_path),
-        current_branch,
-        branches,
-        upstream_branch,
-        remote_branches,
-        suggested_base_ref,
+    Ok(GetLineBlameResult {
+        file_path,
+        rev,
+        lines,
     })
 }
 
@@ -599,15 +2635,17 @@ pub async fn checkout_workspace_branch(
 
     let branch_name = parse_branch_name(&input.branch_name)?;
     validate_branch_name(&repo_path, &branch_name)?;
-    run_git(
+    let stash = switch_branch(
         &repo_path,
+        &branch_name,
+        input.auto_stash.unwrap_or(false),
         &["checkout", branch_name.as_str()],
-        "checkout branch",
     )?;
 
     Ok(CheckoutWorkspaceBranchResult {
         workspace: format_path(&repo_path),
         branch_name,
+        stash,
     })
 }
 
@@ -626,22 +2664,829 @@ pub async fn create_workspace_branch(
         .map(str::trim)
         .filter(|value| !value.is_empty());
 
-    if let Some(from_ref) = from_ref {
+    let mut args = vec!["checkout", "-b", branch_name.as_str()];
+    args.extend(from_ref);
+    let stash = switch_branch(
+        &repo_path,
+        &branch_name,
+        input.auto_stash.unwrap_or(false),
+        &args,
+    )?;
+
+    Ok(CheckoutWorkspaceBranchResult {
+        workspace: format_path(&repo_path),
+        branch_name,
+        stash,
+    })
+}
+
+/// What `git status --porcelain=v2 --branch -z` reports about the branch and working tree.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct StatusSummary {
+    pub branch: Option<String>,
+    pub head: Option<String>,
+    pub upstream: Option<String>,
+    pub ahead_behind: Option<(u64, u64)>,
+    pub staged: usize,
+    pub unstaged: usize,
+    pub untracked: usize,
+    pub conflicted: usize,
+}
+
+impl StatusSummary {
+    fn has_changes(&self) -> bool {
+        self.staged + self.unstaged + self.untracked + self.conflicted > 0
+    }
+
+    /// Non-zero counts, such as `2 staged, 1 untracked`.
+    fn describe_changes(&self) -> String {
+        [
+            (self.staged, "staged"),
+            (self.unstaged, "unstaged"),
+            (self.untracked, "untracked"),
+            (self.conflicted, "conflicted"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+/// Parses `git status --porcelain=v2 --branch -z`, which unlike the v1 format read by
+/// [`parse_porcelain_status`] also reports the branch, its upstream and how far apart they are.
+pub(crate) fn parse_status_summary(output: &str) -> StatusSummary {
+    let mut summary = StatusSummary::default();
+    let mut records = output.split('\0');
+    while let Some(record) = records.next() {
+        if let Some(header) = record.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.oid" if value != "(initial)" => summary.head = Some(value.to_string()),
+                "branch.head" if value != "(detached)" => summary.branch = Some(value.to_string()),
+                "branch.upstream" => summary.upstream = Some(value.to_string()),
+                "branch.ab" => {
+                    let mut counts = value
+                        .split(' ')
+                        .map(|count| count.trim_start_matches(['+', '-']).parse::<u64>().ok());
+                    if let (Some(Some(ahead)), Some(Some(behind))) = (counts.next(), counts.next())
+                    {
+                        summary.ahead_behind = Some((ahead, behind));
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        let mut fields = record.split(' ');
+        match fields.next() {
+            Some(kind @ ("1" | "2")) => {
+                let xy = fields.next().unwrap_or("..").as_bytes();
+                if xy.first().is_some_and(|state| *state != b'.') {
+                    summary.staged += 1;
+                }
+                if xy.get(1).is_some_and(|state| *state != b'.') {
+                    summary.unstaged += 1;
+                }
+                // A rename or copy is followed by its original path.
+                if kind == "2" {
+                    records.next();
+                }
+            }
+            Some("u") => summary.conflicted += 1,
+            Some("?") => summary.untracked += 1,
+            _ => {}
+        }
+    }
+    summary
+}
+
+fn read_status(repo_path: &Path) -> Result<StatusSummary, String> {
+    GitRepo::open(repo_path).status()
+}
+
+fn conflicted_files(repo_path: &Path) -> Result<Vec<String>, String> {
+    Ok(run_git_trimmed(
+        repo_path,
+        &["diff", "--name-only", "--diff-filter=U"],
+        "diff --diff-filter=U",
+    )?
+    .lines()
+    .map(ToOwned::to_owned)
+    .collect())
+}
+
+/// Runs the checkout in `args`. With `auto_stash`, uncommitted changes are stashed first and
+/// reapplied on the new branch; without it, a checkout git refuses because of them fails with
+/// an error that says so.
+fn switch_branch(
+    repo_path: &Path,
+    branch_name: &str,
+    auto_stash: bool,
+    args: &[&str],
+) -> Result<Option<BranchSwitchStash>, String> {
+    let status = read_status(repo_path)?;
+    if status.conflicted > 0 {
+        return Err(format!(
+            "Cannot switch to {branch_name}: {} file(s) have unresolved merge conflicts.",
+            status.conflicted
+        ));
+    }
+    if !auto_stash || !status.has_changes() {
+        return match run_git(repo_path, args, "checkout") {
+            Ok(_) => Ok(None),
+            Err(error) if error.contains("would be overwritten") => Err(format!(
+                "Cannot switch to {branch_name} with uncommitted changes ({}). Commit or stash them, or retry with autoStash. {error}",
+                status.describe_changes()
+            )),
+            Err(error) => Err(error),
+        };
+    }
+
+    let message = format!("rovex: auto-stash before switching to {branch_name}");
+    run_git(
+        repo_path,
+        &["stash", "push", "--include-untracked", "-m", &message],
+        "stash push",
+    )?;
+    let stash_commit = run_git_trimmed(
+        repo_path,
+        &["rev-parse", "--verify", "refs/stash"],
+        "rev-parse refs/stash",
+    )?;
+    if let Err(error) = run_git(repo_path, args, "checkout") {
+        // Nothing was switched, so the changes go back where they came from.
+        return match run_git(repo_path, &["stash", "pop", "--index"], "stash pop") {
+            Ok(_) => Err(error),
+            Err(pop_error) => Err(format!(
+                "{error} Restoring the stashed changes also failed, so they are kept in stash {stash_commit}: {pop_error}"
+            )),
+        };
+    }
+
+    // `--index` keeps staged changes staged, but gives up without touching anything when the
+    // new branch's index cannot take them; a plain pop then restores them unstaged.
+    let mut popped = run_git(repo_path, &["stash", "pop", "--index"], "stash pop --index");
+    let mut conflicts = conflicted_files(repo_path)?;
+    if popped.is_err() && conflicts.is_empty() {
+        popped = run_git(repo_path, &["stash", "pop"], "stash pop");
+        conflicts = conflicted_files(repo_path)?;
+    }
+    match popped {
+        Ok(_) => Ok(Some(BranchSwitchStash {
+            stash_commit,
+            restored: true,
+            conflicts,
+        })),
+        // A pop that conflicts leaves the stash entry in place.
+        Err(_) if !conflicts.is_empty() => Ok(Some(BranchSwitchStash {
+            stash_commit,
+            restored: false,
+            conflicts,
+        })),
+        Err(error) => Err(format!(
+            "Switched to {branch_name}, but the stashed changes could not be reapplied and are kept in stash {stash_commit}: {error}"
+        )),
+    }
+}
+
+pub async fn workspace_status(input: WorkspaceStatusInput) -> Result<WorkspaceStatus, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let status = read_status(&repo_path)?;
+    let stash_count = read_git_trimmed_if_success(
+        &repo_path,
+        &["rev-list", "--walk-reflogs", "--count", "refs/stash"],
+    )
+    .and_then(|count| count.parse().ok())
+    .unwrap_or(0);
+
+    Ok(WorkspaceStatus {
+        workspace: format_path(&repo_path),
+        clean: !status.has_changes(),
+        branch: status.branch,
+        head: status.head,
+        upstream: status.upstream,
+        ahead: status.ahead_behind.map(|(ahead, _)| ahead),
+        behind: status.ahead_behind.map(|(_, behind)| behind),
+        staged: status.staged,
+        unstaged: status.unstaged,
+        untracked: status.untracked,
+        conflicted: status.conflicted,
+        stash_count,
+    })
+}
+
+/// Remote and branch that `branch` tracks, from its `branch.<name>.remote` and
+/// `branch.<name>.merge` config.
+fn branch_upstream(repo_path: &Path, branch: &str) -> Option<(String, String)> {
+    let remote =
+        read_git_trimmed_if_success(repo_path, &["config", &format!("branch.{branch}.remote")])?;
+    let merge =
+        read_git_trimmed_if_success(repo_path, &["config", &format!("branch.{branch}.merge")])?;
+    let merge = merge
+        .strip_prefix("refs/heads/")
+        .unwrap_or(&merge)
+        .to_string();
+    Some((remote, merge))
+}
+
+fn require_current_branch(repo_path: &Path) -> Result<String, String> {
+    read_git_trimmed_if_success(repo_path, &["branch", "--show-current"])
+        .ok_or_else(|| "The workspace is on a detached HEAD; check out a branch first.".to_string())
+}
+
+fn same_remote_url(left: &str, right: &str) -> bool {
+    let normalize = |url: &str| {
+        let url = url.trim().trim_end_matches('/').to_ascii_lowercase();
+        url.strip_suffix(".git").map(str::to_string).unwrap_or(url)
+    };
+    normalize(left) == normalize(right)
+}
+
+/// `-c http.extraHeader=...` arguments that authenticate git against `remote` with the token
+/// of the connected provider hosting it, the same way clones are authenticated. SSH remotes
+/// and remotes no connected provider hosts get none, so git falls back to the user's own
+/// credentials.
+async fn remote_auth_args(
+    state: &AppState,
+    repo_path: &Path,
+    remote: &str,
+    provider: Option<ProviderKind>,
+) -> Result<Vec<String>, String> {
+    let url = run_git_trimmed(repo_path, &["remote", "get-url", remote], "remote get-url")?;
+    if !url.to_ascii_lowercase().starts_with("https://") {
+        return Ok(Vec::new());
+    }
+    let candidates = match provider {
+        Some(provider) => vec![provider],
+        None => vec![ProviderKind::Github, ProviderKind::Gitlab],
+    };
+    for kind in candidates {
+        let client = provider_client(kind);
+        let hosted = client
+            .parse_repository(&url)
+            .is_ok_and(|repository| same_remote_url(&client.clone_url(&repository), &url));
+        if !hosted {
+            if provider.is_some() {
+                return Err(format!(
+                    "Remote {remote} ({url}) is not a {} repository.",
+                    kind.as_str()
+                ));
+            }
+            continue;
+        }
+        let Some(connection) = load_provider_connection_row(state, kind).await? else {
+            if provider.is_some() {
+                return Err(format!("{} is not connected.", kind.as_str()));
+            }
+            continue;
+        };
+        let auth_header = client.clone_auth_header(&connection.access_token)?;
+        return Ok(vec![
+            "-c".to_string(),
+            format!("http.extraHeader={auth_header}"),
+        ]);
+    }
+    Ok(Vec::new())
+}
+
+fn run_git_with_auth(
+    repo_path: &Path,
+    auth_args: &[String],
+    args: &[&str],
+    context: &str,
+) -> Result<Output, String> {
+    let mut full_args = auth_args.iter().map(String::as_str).collect::<Vec<_>>();
+    full_args.extend_from_slice(args);
+    run_git(repo_path, &full_args, context)
+}
+
+/// Fetches the upstream of the current branch and fast-forwards to it. A branch that has
+/// diverged from its upstream is reported and left as it was, since catching up would take
+/// a merge or rebase.
+pub(crate) fn pull_workspace_branch_with_auth(
+    workspace: &str,
+    auth_args: &[String],
+) -> Result<PullWorkspaceBranchResult, String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let branch = require_current_branch(&repo_path)?;
+    let (remote, _) = branch_upstream(&repo_path, &branch).ok_or_else(|| {
+        format!("Branch {branch} has no upstream to pull from; push it with an upstream first.")
+    })?;
+    run_git_with_auth(
+        &repo_path,
+        auth_args,
+        &["fetch", "--quiet", &remote],
+        "fetch",
+    )?;
+    let upstream = run_git_trimmed(
+        &repo_path,
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ],
+        "resolve upstream",
+    )?;
+    let counts = run_git_trimmed(
+        &repo_path,
+        &["rev-list", "--left-right", "--count", "HEAD...@{upstream}"],
+        "rev-list --left-right",
+    )?;
+    let (ahead, behind) = counts
+        .split_once('\t')
+        .and_then(|(ahead, behind)| Some((ahead.parse().ok()?, behind.parse().ok()?)))
+        .ok_or_else(|| format!("Failed to parse ahead/behind counts '{counts}'."))?;
+    let previous_head = run_git_trimmed(&repo_path, &["rev-parse", "HEAD"], "rev-parse HEAD")?;
+
+    let status = if behind == 0 {
+        "up-to-date"
+    } else if ahead > 0 {
+        "diverged"
+    } else {
         run_git(
             &repo_path,
-            &["checkout", "-b", branch_name.as_str(), from_ref],
-            "checkout -b",
+            &["merge", "--ff-only", "--quiet", "@{upstream}"],
+            "merge --ff-only",
         )?;
-    } else {
-        run_git(
+        "fast-forwarded"
+    };
+    let head = run_git_trimmed(&repo_path, &["rev-parse", "HEAD"], "rev-parse HEAD")?;
+
+    Ok(PullWorkspaceBranchResult {
+        workspace: format_path(&repo_path),
+        branch,
+        upstream,
+        status: status.to_string(),
+        previous_head,
+        head,
+        ahead,
+        behind,
+    })
+}
+
+/// Where a push goes: the branch, the remote, and the branch name on that remote.
+struct PushTarget {
+    branch: String,
+    remote: String,
+    remote_branch: String,
+    has_upstream: bool,
+}
+
+fn resolve_push_target(
+    repo_path: &Path,
+    branch: Option<&str>,
+    remote: Option<&str>,
+) -> Result<PushTarget, String> {
+    let branch = match as_non_empty_trimmed(branch) {
+        Some(branch) => {
+            let branch = parse_branch_name(&branch)?;
+            validate_branch_name(repo_path, &branch)?;
+            branch
+        }
+        None => require_current_branch(repo_path)?,
+    };
+    let upstream = branch_upstream(repo_path, &branch);
+    let remote = as_non_empty_trimmed(remote)
+        .or_else(|| upstream.as_ref().map(|(remote, _)| remote.clone()))
+        .unwrap_or_else(|| "origin".to_string());
+    // Pushing to the upstream remote keeps the upstream's branch name; other remotes get
+    // the local name.
+    let remote_branch = upstream
+        .as_ref()
+        .filter(|(upstream_remote, _)| *upstream_remote == remote)
+        .map(|(_, remote_branch)| remote_branch.clone())
+        .unwrap_or_else(|| branch.clone());
+    Ok(PushTarget {
+        branch,
+        remote,
+        remote_branch,
+        has_upstream: upstream.is_some(),
+    })
+}
+
+/// Pushes a branch, setting its upstream when asked to or when it has none yet. A push the
+/// remote rejects, such as one that is not a fast-forward, is an error.
+pub(crate) fn push_workspace_branch_with_auth(
+    input: &PushWorkspaceBranchInput,
+    auth_args: &[String],
+) -> Result<PushWorkspaceBranchResult, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let target = resolve_push_target(&repo_path, input.branch.as_deref(), input.remote.as_deref())?;
+    run_git_trimmed(
+        &repo_path,
+        &["remote", "get-url", &target.remote],
+        "remote get-url",
+    )?;
+    let set_upstream = input.set_upstream.unwrap_or(!target.has_upstream);
+    let refspec = format!(
+        "refs/heads/{}:refs/heads/{}",
+        target.branch, target.remote_branch
+    );
+
+    let mut args = auth_args.iter().map(String::as_str).collect::<Vec<_>>();
+    args.extend(["push", "--porcelain"]);
+    if set_upstream {
+        args.push("--set-upstream");
+    }
+    args.extend([target.remote.as_str(), refspec.as_str()]);
+    // A rejected push exits non-zero, so the ref's status line is read before the exit code.
+    let output = Command::new("git")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .arg("-C")
+        .arg(&repo_path)
+        .args(&args)
+        .output()
+        .map_err(|error| format!("Failed to run git push: {error}"))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let destination = format!(":refs/heads/{}", target.remote_branch);
+    let ref_status = stdout.lines().find_map(|line| {
+        let mut fields = line.splitn(3, '\t');
+        let flag = fields.next()?;
+        let refs = fields.next()?;
+        refs.ends_with(&destination)
+            .then(|| (flag.trim(), fields.next().unwrap_or_default().trim()))
+    });
+    let status = match ref_status {
+        Some(("!", summary)) => {
+            return Err(format!(
+                "Pushing {} to {}/{} was rejected {summary}; pull the remote changes first.",
+                target.branch, target.remote, target.remote_branch
+            ))
+        }
+        Some(_) if !output.status.success() => {
+            return Err(format!(
+                "git push failed: {}",
+                summarize_process_output(&output)
+            ))
+        }
+        Some(("=", _)) => "up-to-date",
+        Some(("*", _)) => "created",
+        Some(_) => "pushed",
+        None => {
+            return Err(format!(
+                "git push failed: {}",
+                summarize_process_output(&output)
+            ))
+        }
+    };
+    let head = run_git_trimmed(
+        &repo_path,
+        &["rev-parse", &format!("refs/heads/{}", target.branch)],
+        "rev-parse",
+    )?;
+
+    Ok(PushWorkspaceBranchResult {
+        workspace: format_path(&repo_path),
+        branch: target.branch,
+        remote: target.remote,
+        remote_branch: target.remote_branch,
+        status: status.to_string(),
+        head,
+        upstream_set: set_upstream,
+    })
+}
+
+pub async fn pull_workspace_branch(
+    state: &AppState,
+    input: PullWorkspaceBranchInput,
+) -> Result<PullWorkspaceBranchResult, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let branch = require_current_branch(&repo_path)?;
+    let auth_args = match branch_upstream(&repo_path, &branch) {
+        Some((remote, _)) => remote_auth_args(state, &repo_path, &remote, input.provider).await?,
+        None => Vec::new(),
+    };
+    pull_workspace_branch_with_auth(&input.workspace, &auth_args)
+}
+
+pub async fn push_workspace_branch(
+    state: &AppState,
+    input: PushWorkspaceBranchInput,
+) -> Result<PushWorkspaceBranchResult, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let target = resolve_push_target(&repo_path, input.branch.as_deref(), input.remote.as_deref())?;
+    let auth_args = remote_auth_args(state, &repo_path, &target.remote, input.provider).await?;
+    push_workspace_branch_with_auth(&input, &auth_args)
+}
+
+fn staged_files(repo_path: &Path) -> Result<Vec<String>, String> {
+    let output = run_git(
+        repo_path,
+        &["diff", "--cached", "--name-only", "-z"],
+        "diff --cached --name-only",
+    )?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(ToOwned::to_owned)
+        .collect())
+}
+
+/// Repository path of `workspace`, its staged diff, and the staged files.
+pub(crate) fn read_staged_diff(workspace: &str) -> Result<(PathBuf, String, Vec<String>), String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let files = staged_files(&repo_path)?;
+    let diff = run_git(
+        &repo_path,
+        &["diff", "--cached", "--no-color", "--no-ext-diff"],
+        "diff --cached",
+    )?;
+    Ok((
+        repo_path,
+        String::from_utf8_lossy(&diff.stdout).into_owned(),
+        files,
+    ))
+}
+
+pub async fn commit_workspace_changes(
+    input: CommitWorkspaceChangesInput,
+) -> Result<CommitWorkspaceChangesResult, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let message = input.message.trim();
+    if message.is_empty() {
+        return Err("Commit message must not be empty.".to_string());
+    }
+
+    let paths = input
+        .paths
+        .iter()
+        .flatten()
+        .map(|path| path.trim())
+        .filter(|path| !path.is_empty())
+        .collect::<Vec<_>>();
+    if !paths.is_empty() {
+        // Literal pathspecs, so a file named like a glob stages only itself.
+        let mut args = vec!["--literal-pathspecs", "add", "--all", "--"];
+        args.extend(paths.iter().copied());
+        run_git(&repo_path, &args, "add")?;
+    }
+    let files = staged_files(&repo_path)?;
+    if files.is_empty() {
+        return Err("Nothing is staged to commit.".to_string());
+    }
+
+    run_git(&repo_path, &["commit", "--quiet", "-m", message], "commit")?;
+    let commit = run_git_trimmed(&repo_path, &["rev-parse", "HEAD"], "rev-parse HEAD")?;
+
+    Ok(CommitWorkspaceChangesResult {
+        workspace: format_path(&repo_path),
+        branch: read_git_trimmed_if_success(&repo_path, &["branch", "--show-current"]),
+        commit,
+        summary: message
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        files,
+    })
+}
+
+pub(crate) fn unshallow_workspace_with_auth(
+    workspace: &str,
+    depth: Option<u32>,
+    auth_args: &[String],
+) -> Result<UnshallowWorkspaceResult, String> {
+    let repo_path = resolve_workspace_repo_path(workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let remote = workspace_fetch_remote(&repo_path);
+    let was_shallow = is_shallow_repository(&repo_path);
+    if was_shallow {
+        let depth_arg = match depth {
+            Some(0) => return Err("Depth must be at least 1.".to_string()),
+            Some(depth) => format!("--deepen={depth}"),
+            None => "--unshallow".to_string(),
+        };
+        run_git_with_auth(
             &repo_path,
-            &["checkout", "-b", branch_name.as_str()],
-            "checkout -b",
+            auth_args,
+            &["fetch", "--quiet", &depth_arg, &remote],
+            "fetch",
         )?;
     }
+    let commit_count = run_git_trimmed(&repo_path, &["rev-list", "--count", "HEAD"], "rev-list")?
+        .parse::<i64>()
+        .unwrap_or_default();
 
-    Ok(CheckoutWorkspaceBranchResult {
+    Ok(UnshallowWorkspaceResult {
         workspace: format_path(&repo_path),
-        branch_name,
+        remote,
+        was_shallow,
+        shallow: is_shallow_repository(&repo_path),
+        commit_count,
+    })
+}
+
+/// The current branch's upstream remote, or `origin`.
+fn workspace_fetch_remote(repo_path: &Path) -> String {
+    GitRepo::open(repo_path).default_remote()
+}
+
+pub async fn unshallow_workspace(
+    state: &AppState,
+    input: UnshallowWorkspaceInput,
+) -> Result<UnshallowWorkspaceResult, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let auth_args = if is_shallow_repository(&repo_path) {
+        let remote = workspace_fetch_remote(&repo_path);
+        remote_auth_args(state, &repo_path, &remote, input.provider).await?
+    } else {
+        Vec::new()
+    };
+    unshallow_workspace_with_auth(&input.workspace, input.depth, &auth_args)
+}
+
+/// One `git fetch --progress` status line, such as
+/// `Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FetchProgress {
+    pub phase: String,
+    pub percent: Option<u8>,
+    pub current: Option<u64>,
+    pub total: Option<u64>,
+    pub transfer: Option<String>,
+}
+
+/// Reads a progress line of `git fetch`, with or without the `remote: ` prefix of the
+/// server's phases. Other lines, such as the updated refs, are not progress.
+pub(crate) fn parse_fetch_progress(line: &str) -> Option<FetchProgress> {
+    let line = line.trim();
+    let line = line.strip_prefix("remote:").map_or(line, str::trim_start);
+    let (phase, rest) = line.split_once(':')?;
+    if phase.is_empty()
+        || !phase
+            .chars()
+            .all(|character| character.is_ascii_alphabetic() || character == ' ')
+    {
+        return None;
+    }
+    let rest = rest.trim();
+    let rest = rest
+        .strip_suffix(", done.")
+        .or_else(|| rest.strip_suffix(", done"))
+        .unwrap_or(rest);
+    let (counts, transfer) = match rest.split_once(", ") {
+        Some((counts, transfer)) => (counts, Some(transfer.trim())),
+        None => (rest, None),
+    };
+    let mut progress = FetchProgress {
+        phase: phase.trim().to_string(),
+        percent: None,
+        current: None,
+        total: None,
+        transfer: transfer
+            .filter(|transfer| !transfer.is_empty())
+            .map(ToOwned::to_owned),
+    };
+    for part in counts.split_whitespace() {
+        if let Some(percent) = part.strip_suffix('%') {
+            progress.percent = Some(percent.parse().ok()?);
+        } else if let Some(fraction) = part
+            .strip_prefix('(')
+            .and_then(|part| part.strip_suffix(')'))
+        {
+            let (current, total) = fraction.split_once('/')?;
+            progress.current = Some(current.parse().ok()?);
+            progress.total = Some(total.parse().ok()?);
+        } else {
+            progress.current = Some(part.parse().ok()?);
+        }
+    }
+    (progress.percent.is_some() || progress.current.is_some()).then_some(progress)
+}
+
+/// Runs `git fetch --progress` from `remote` and hands each progress line that moved on to
+/// `on_progress` as git writes it.
+pub(crate) fn fetch_remote_with_progress(
+    repo_path: &Path,
+    remote: &str,
+    auth_args: &[String],
+    mut on_progress: impl FnMut(FetchProgress),
+) -> Result<(), String> {
+    let mut child = Command::new("git")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .arg("-C")
+        .arg(repo_path)
+        .args(auth_args)
+        .args(["fetch", "--progress", remote])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("Failed to run git fetch: {error}"))?;
+    let mut stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| "Failed to read git fetch output.".to_string())?;
+
+    // Git redraws a progress line by ending it with `\r` instead of `\n`.
+    let mut messages = Vec::new();
+    let mut last_reported = None;
+    let mut handle_line = |line: &[u8]| {
+        let line = String::from_utf8_lossy(line);
+        match parse_fetch_progress(&line) {
+            Some(progress) => {
+                let key = (progress.phase.clone(), progress.percent, progress.current);
+                if last_reported.as_ref() != Some(&key) {
+                    last_reported = Some(key);
+                    on_progress(progress);
+                }
+            }
+            None if !line.trim().is_empty() => messages.push(line.trim().to_string()),
+            None => {}
+        }
+    };
+    let mut line = Vec::new();
+    let mut buffer = [0u8; 4096];
+    loop {
+        let read = stderr
+            .read(&mut buffer)
+            .map_err(|error| format!("Failed to read git fetch output: {error}"))?;
+        if read == 0 {
+            break;
+        }
+        for &byte in &buffer[..read] {
+            if byte == b'\r' || byte == b'\n' {
+                handle_line(&line);
+                line.clear();
+            } else {
+                line.push(byte);
+            }
+        }
+    }
+    handle_line(&line);
+
+    let status = child
+        .wait()
+        .map_err(|error| format!("Failed to run git fetch: {error}"))?;
+    if status.success() {
+        return Ok(());
+    }
+    Err(format!(
+        "git fetch {remote} failed: {}",
+        if messages.is_empty() {
+            "Unknown process failure.".to_string()
+        } else {
+            messages.join("\n")
+        }
+    ))
+}
+
+/// Fetches a remote off the async runtime, emitting [`WORKSPACE_FETCH_PROGRESS_EVENT`] as
+/// objects arrive, so large remotes can be fetched ahead of a comparison that then passes
+/// `fetchRemote: false`.
+pub async fn fetch_workspace_remote(
+    app: AppHandle,
+    state: &AppState,
+    input: FetchWorkspaceRemoteInput,
+) -> Result<FetchWorkspaceRemoteResult, String> {
+    let repo_path = resolve_workspace_repo_path(&input.workspace)?;
+    ensure_git_repository(&repo_path)?;
+    let remote = parse_remote_name(input.remote.as_deref())?
+        .unwrap_or_else(|| workspace_fetch_remote(&repo_path));
+    let auth_args = remote_auth_args(state, &repo_path, &remote, input.provider).await?;
+    let workspace = format_path(&repo_path);
+
+    let started_at = Instant::now();
+    let (event_workspace, event_remote) = (workspace.clone(), remote.clone());
+    let fetch_remote = remote.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        fetch_remote_with_progress(&repo_path, &fetch_remote, &auth_args, |progress| {
+            let _ = app.emit(
+                WORKSPACE_FETCH_PROGRESS_EVENT,
+                WorkspaceFetchProgressEvent {
+                    workspace: event_workspace.clone(),
+                    remote: event_remote.clone(),
+                    phase: progress.phase,
+                    percent: progress.percent,
+                    current: progress.current,
+                    total: progress.total,
+                    transfer: progress.transfer,
+                },
+            );
+        })
+    })
+    .await
+    .map_err(|error| format!("Failed to fetch {remote}: {error}"))??;
+
+    Ok(FetchWorkspaceRemoteResult {
+        workspace,
+        remote,
+        fetched_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default(),
+        duration_ms: started_at.elapsed().as_millis() as u64,
     })
 }
@@ -1,13 +1,33 @@
 use std::{
     fs,
-    path::Path,
+    path::{Path, PathBuf},
     process::Command,
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use super::workspace_git::resolve_base_ref;
+use super::git_repo::GitRepo;
+use super::workspace_git::{
+    add_run_snapshot_worktree, apply_patch_to_workspace, attach_submodule_diffs, blame_findings,
+    check_remote_sync, checkout_workspace_branch, commit_workspace_changes, compare_workspace_diff,
+    fetch_remote_with_progress, get_commit_detail, get_line_blame, list_workspace_branches,
+    list_workspace_commits, parse_fetch_progress, parse_status_summary,
+    pull_workspace_branch_with_auth, push_workspace_branch_with_auth, read_staged_diff,
+    remove_review_git_hook, remove_run_snapshot_worktree, resolve_base_ref,
+    unshallow_workspace_with_auth, verify_remote_branch, workspace_status, write_review_git_hook,
+    REVIEW_GIT_HOOK_MARKER, WORKSPACE_TABLES,
+};
+use crate::backend::db;
+use crate::backend::{
+    AiReviewFinding, CheckoutWorkspaceBranchInput, CommitWorkspaceChangesInput,
+    CompareWorkspaceDiffInput, GetCommitDetailInput, GetLineBlameInput, ListWorkspaceBranchesInput,
+    ListWorkspaceCommitsInput, PushWorkspaceBranchInput, WorkspaceDiffTarget, WorkspaceStatusInput,
+};
 
 fn run_ok(repo_path: &Path, args: &[&str]) {
+    git_stdout(repo_path, args);
+}
+
+fn git_stdout(repo_path: &Path, args: &[&str]) -> String {
     let output = Command::new("git")
         .arg("-C")
         .arg(repo_path)
@@ -20,6 +40,96 @@ fn run_ok(repo_path: &Path, args: &[&str]) {
         args,
         String::from_utf8_lossy(&output.stderr)
     );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+/// Runs `git init` on `branch` in `repo_path`, creating it, with a committer identity set.
+fn init_repo(repo_path: &Path, branch: &str) {
+    fs::create_dir_all(repo_path).expect("create temp repo dir");
+    run_ok(repo_path, &["init", "-b", branch]);
+    run_ok(repo_path, &["config", "user.email", "test@example.com"]);
+    run_ok(repo_path, &["config", "user.name", "Test"]);
+}
+
+/// Stages everything and commits it. The identity is passed along so clones can commit too.
+fn commit_all(repo_path: &Path, message: &str) {
+    run_ok(repo_path, &["add", "-A"]);
+    run_ok(
+        repo_path,
+        &[
+            "-c",
+            "user.email=test@example.com",
+            "-c",
+            "user.name=Test",
+            "commit",
+            "-m",
+            message,
+        ],
+    );
+}
+
+/// A scratch directory under the temp dir, removed on drop. [`TestRepo::init`] makes it a
+/// repository; tests with several repositories create them inside [`TestRepo::new`].
+struct TestRepo {
+    path: PathBuf,
+}
+
+impl TestRepo {
+    fn new(name: &str) -> Self {
+        let suffix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("rovex-{name}-test-{suffix}"));
+        fs::create_dir_all(&path).expect("create temp dir");
+        Self { path }
+    }
+
+    fn init(name: &str, branch: &str) -> Self {
+        let repo = Self::new(name);
+        init_repo(&repo.path, branch);
+        repo
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn join(&self, relative_path: &str) -> PathBuf {
+        self.path.join(relative_path)
+    }
+
+    fn workspace(&self) -> String {
+        self.path.to_string_lossy().to_string()
+    }
+
+    fn git(&self, args: &[&str]) {
+        run_ok(&self.path, args);
+    }
+
+    fn write(&self, relative_path: &str, contents: impl AsRef<[u8]>) {
+        fs::write(self.join(relative_path), contents).expect("write file");
+    }
+
+    fn read(&self, relative_path: &str) -> String {
+        fs::read_to_string(self.join(relative_path)).expect("read file")
+    }
+
+    fn commit(&self, message: &str) {
+        commit_all(&self.path, message);
+    }
+
+    fn head(&self) -> String {
+        git_stdout(&self.path, &["rev-parse", "HEAD"])
+            .trim()
+            .to_string()
+    }
+}
+
+impl Drop for TestRepo {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
 }
 
 #[test]
@@ -52,3 +162,1103 @@ fn resolve_base_ref_falls_back_to_master_when_origin_main_missing() {
 
     let _ = fs::remove_dir_all(&repo_path);
 }
+
+#[test]
+fn compare_workspace_diff_targets_staged_and_working_tree_changes() {
+    let repo = TestRepo::init("diff-target", "master");
+    repo.write("staged.txt", "one\n");
+    repo.write("unstaged.txt", "one\n");
+    repo.commit("init");
+    repo.write("staged.txt", "two\n");
+    repo.git(&["add", "staged.txt"]);
+    repo.write("unstaged.txt", "two\n");
+
+    let compare = |diff_target| {
+        tauri::async_runtime::block_on(compare_workspace_diff(CompareWorkspaceDiffInput {
+            workspace: repo.workspace(),
+            base_ref: None,
+            fetch_remote: Some(false),
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: Some(diff_target),
+            include_untracked: None,
+            head_ref: None,
+            include_submodule_diffs: None,
+            force: None,
+            remote: None,
+        }))
+        .expect("compare workspace diff")
+    };
+
+    let staged = compare(WorkspaceDiffTarget::Staged);
+    assert_eq!(staged.base_ref, "HEAD");
+    assert_eq!(staged.merge_base, staged.head);
+    assert_eq!(staged.files_changed, 1);
+    assert!(staged.diff.contains("staged.txt"));
+    assert!(!staged.diff.contains("unstaged.txt"));
+
+    let working_tree = compare(WorkspaceDiffTarget::WorkingTree);
+    assert_eq!(working_tree.files_changed, 2);
+    assert!(working_tree.diff.contains("unstaged.txt"));
+}
+
+#[test]
+fn compare_workspace_diff_includes_untracked_files_when_requested() {
+    let repo = TestRepo::init("untracked", "master");
+    repo.write(".gitignore", "ignored.txt\n");
+    repo.commit("init");
+    repo.write("new.txt", "one\ntwo\n");
+    repo.write("ignored.txt", "secret\n");
+
+    let compare = |include_untracked| {
+        tauri::async_runtime::block_on(compare_workspace_diff(CompareWorkspaceDiffInput {
+            workspace: repo.workspace(),
+            base_ref: None,
+            fetch_remote: Some(false),
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: Some(WorkspaceDiffTarget::WorkingTree),
+            include_untracked: Some(include_untracked),
+            head_ref: None,
+            include_submodule_diffs: None,
+            force: None,
+            remote: None,
+        }))
+        .expect("compare workspace diff")
+    };
+
+    let without = compare(false);
+    assert_eq!(without.files_changed, 0);
+    assert!(without.diff.is_empty());
+
+    let with = compare(true);
+    assert_eq!(with.files_changed, 1);
+    assert_eq!(with.insertions, 2);
+    assert!(with.diff.contains("+++ b/new.txt"));
+    assert!(!with.diff.contains("ignored.txt"));
+}
+
+#[test]
+fn apply_patch_to_workspace_falls_back_to_three_way_merge() {
+    let repo = TestRepo::init("apply-patch", "master");
+    repo.write("lib.txt", "one\ntwo\nthree\n");
+    repo.commit("init");
+    let reviewed_head = repo.head();
+    let workspace = repo.workspace();
+    let patch = "--- a/lib.txt\n+++ b/lib.txt\n@@ -1,3 +1,3 @@\n one\n-two\n+TWO\n three\n";
+
+    let clean = apply_patch_to_workspace(&workspace, "lib.txt", patch, Some(&reviewed_head))
+        .expect("apply clean patch");
+    assert!(!clean.three_way);
+    assert!(clean.conflicts.is_empty());
+    assert_eq!(repo.read("lib.txt"), "one\nTWO\nthree\n");
+
+    repo.write("lib.txt", "one\n2\nthree\n");
+    repo.commit("edit");
+    let merged = apply_patch_to_workspace(&workspace, "lib.txt", patch, Some(&reviewed_head))
+        .expect("apply patch with three-way merge");
+    assert!(merged.three_way);
+    assert_eq!(merged.conflicts, vec!["lib.txt".to_string()]);
+    assert!(repo.read("lib.txt").contains("<<<<<<<"));
+}
+
+#[test]
+fn run_snapshot_worktree_stays_at_the_reviewed_head() {
+    let repo = TestRepo::init("run-snapshot", "master");
+    repo.write("lib.txt", "reviewed\n");
+    repo.commit("init");
+    let reviewed_head = repo.head();
+    repo.write("lib.txt", "advanced\n");
+    repo.commit("advance");
+    let workspace = repo.workspace();
+
+    let (snapshot_path, created) =
+        add_run_snapshot_worktree(&workspace, "run-1", &reviewed_head).expect("add snapshot");
+    assert!(created);
+    assert_eq!(
+        fs::read_to_string(Path::new(&snapshot_path).join("lib.txt")).expect("read snapshot"),
+        "reviewed\n"
+    );
+    let (reused_path, created) =
+        add_run_snapshot_worktree(&workspace, "run-1", &reviewed_head).expect("reuse snapshot");
+    assert!(!created);
+    assert_eq!(reused_path, snapshot_path);
+    assert!(add_run_snapshot_worktree(&workspace, "../run-1", &reviewed_head).is_err());
+
+    assert!(remove_run_snapshot_worktree(&workspace, "run-1").expect("remove snapshot"));
+    assert!(!Path::new(&snapshot_path).exists());
+    assert!(!remove_run_snapshot_worktree(&workspace, "run-1").expect("remove again"));
+}
+
+#[test]
+fn review_git_hook_never_replaces_user_hooks() {
+    let repo = TestRepo::init("hook", "master");
+    let workspace = repo.workspace();
+    let script = format!("#!/bin/sh\n{REVIEW_GIT_HOOK_MARKER}\nexit 0\n");
+
+    let hook_path = write_review_git_hook(&workspace, "pre-commit", &script).expect("write hook");
+    assert_eq!(fs::read_to_string(&hook_path).expect("read hook"), script);
+    write_review_git_hook(&workspace, "pre-commit", &script).expect("rewrite own hook");
+    assert!(remove_review_git_hook(&workspace, "pre-commit").expect("remove hook"));
+    assert!(!remove_review_git_hook(&workspace, "pre-commit").expect("remove again"));
+
+    fs::write(&hook_path, "#!/bin/sh\nmake lint\n").expect("write user hook");
+    assert!(write_review_git_hook(&workspace, "pre-commit", &script).is_err());
+    assert!(remove_review_git_hook(&workspace, "pre-commit").is_err());
+    assert_eq!(
+        fs::read_to_string(&hook_path).expect("read user hook"),
+        "#!/bin/sh\nmake lint\n"
+    );
+}
+
+#[test]
+fn compare_workspace_diff_reviews_a_head_ref_instead_of_the_checkout() {
+    let repo = TestRepo::init("head-ref", "master");
+    repo.write("README.md", "hello\n");
+    repo.commit("init");
+    repo.git(&["checkout", "-b", "feature"]);
+    repo.write("feature.txt", "new\n");
+    repo.commit("feature");
+    repo.git(&["checkout", "master"]);
+    repo.write("README.md", "local edit\n");
+
+    let compare = |diff_target| {
+        tauri::async_runtime::block_on(compare_workspace_diff(CompareWorkspaceDiffInput {
+            workspace: repo.workspace(),
+            base_ref: Some("master".to_string()),
+            fetch_remote: Some(false),
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: Some(diff_target),
+            include_untracked: None,
+            head_ref: Some("feature".to_string()),
+            include_submodule_diffs: None,
+            force: None,
+            remote: None,
+        }))
+    };
+
+    let result = compare(WorkspaceDiffTarget::Branch).expect("compare head ref");
+    assert_eq!(result.files_changed, 1);
+    assert!(result.diff.contains("feature.txt"));
+    assert!(!result.diff.contains("README.md"));
+    assert_ne!(result.head, result.merge_base);
+    assert!(compare(WorkspaceDiffTarget::Staged).is_err());
+}
+
+#[test]
+fn compare_workspace_diff_decodes_binary_formats_with_a_decoder() {
+    let repo = TestRepo::init("binary-decode", "master");
+    // Protobuf messages with a zero varint, which makes git treat the files as binary.
+    repo.write("api.desc", b"\x0a\x03abc\x10\x00");
+    repo.write("logo.png", b"\x89PNG\x00\x01");
+    repo.commit("init");
+    repo.write("api.desc", b"\x0a\x03abd\x10\x00");
+    repo.write("logo.png", b"\x89PNG\x00\x02");
+
+    let result =
+        tauri::async_runtime::block_on(compare_workspace_diff(CompareWorkspaceDiffInput {
+            workspace: repo.workspace(),
+            base_ref: None,
+            fetch_remote: Some(false),
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: Some(WorkspaceDiffTarget::WorkingTree),
+            include_untracked: None,
+            head_ref: None,
+            include_submodule_diffs: None,
+            force: None,
+            remote: None,
+        }))
+        .expect("compare workspace diff");
+    assert!(result.diff.contains(
+        "--- a/api.desc\n+++ b/api.desc\n@@ -1,2 +1,2 @@ decoded protobuf\n-1: \"abc\"\n+1: \"abd\"\n 2: 0\n"
+    ));
+    assert!(result
+        .diff
+        .contains("Binary files a/logo.png and b/logo.png differ"));
+}
+
+#[test]
+fn check_remote_sync_reports_refs_that_origin_moved_past() {
+    let root = TestRepo::new("remote-sync");
+    let origin_path = root.join("origin");
+    let clone_path = root.join("clone");
+    let commit = |message: &str| {
+        fs::write(origin_path.join("README.md"), message).expect("write file");
+        commit_all(&origin_path, message);
+    };
+
+    init_repo(&origin_path, "main");
+    commit("init");
+    root.git(&[
+        "clone",
+        &origin_path.to_string_lossy(),
+        &clone_path.to_string_lossy(),
+    ]);
+    let workspace = clone_path.to_string_lossy().to_string();
+
+    let check = check_remote_sync(&workspace).expect("check fresh clone");
+    assert_eq!(check.branch.as_deref(), Some("main"));
+    assert_eq!(check.base_ref.as_deref(), Some("origin/main"));
+    assert!(check.stale_refs.is_empty());
+
+    commit("upstream change");
+    let check = check_remote_sync(&workspace).expect("check after upstream commit");
+    assert_eq!(check.stale_refs, vec!["origin/main".to_string()]);
+    assert_eq!(check.behind_upstream, 0);
+
+    run_ok(&clone_path, &["fetch", "--quiet", "origin"]);
+    let check = check_remote_sync(&workspace).expect("check after fetch");
+    assert!(check.stale_refs.is_empty());
+    assert_eq!(check.behind_upstream, 1);
+}
+
+#[test]
+fn remote_branch_checks_follow_the_default_remote_without_fetching() {
+    let root = TestRepo::new("verify-remote-branch");
+    let origin_path = root.join("origin");
+    let clone_path = root.join("clone");
+    init_repo(&origin_path, "main");
+    fs::write(origin_path.join("README.md"), "init").expect("write file");
+    commit_all(&origin_path, "init");
+    root.git(&[
+        "clone",
+        &origin_path.to_string_lossy(),
+        &clone_path.to_string_lossy(),
+    ]);
+    run_ok(&origin_path, &["branch", "feature"]);
+    let workspace = clone_path.to_string_lossy().to_string();
+
+    verify_remote_branch(&workspace, None, "main").expect("tracked branch");
+    verify_remote_branch(&workspace, None, "feature").expect("branch only on origin");
+    assert!(verify_remote_branch(&workspace, None, "missing").is_err());
+    assert!(!git_stdout(&clone_path, &["branch", "-r"]).contains("feature"));
+
+    // A clone whose remote is not called origin checks that remote instead.
+    run_ok(&clone_path, &["remote", "rename", "origin", "upstream"]);
+    verify_remote_branch(&workspace, None, "feature").expect("default remote");
+    verify_remote_branch(&workspace, Some("upstream"), "feature").expect("named remote");
+    assert!(verify_remote_branch(&workspace, Some("origin"), "feature").is_err());
+    let check = check_remote_sync(&workspace).expect("check renamed remote");
+    assert_eq!(check.upstream.as_deref(), Some("upstream/main"));
+    assert!(check.stale_refs.is_empty());
+    fs::write(origin_path.join("README.md"), "change").expect("write file");
+    commit_all(&origin_path, "upstream change");
+    let check = check_remote_sync(&workspace).expect("check after upstream commit");
+    assert_eq!(check.stale_refs, vec!["upstream/main".to_string()]);
+}
+
+#[test]
+fn blame_tells_introduced_lines_from_pre_existing_ones() {
+    let repo = TestRepo::init("blame", "master");
+    repo.write("lib.txt", "one\ntwo\nthree\n");
+    repo.commit("init");
+    let merge_base = repo.head();
+    repo.write("lib.txt", "one\nTWO\nthree\n");
+    repo.commit("shout two");
+    let head = repo.head();
+    repo.write("lib.txt", "one\nTWO\nTHREE\n");
+    let workspace = repo.workspace();
+
+    let blame = tauri::async_runtime::block_on(get_line_blame(GetLineBlameInput {
+        workspace: workspace.clone(),
+        file_path: "./lib.txt".to_string(),
+        start_line: 1,
+        end_line: Some(3),
+        rev: None,
+    }))
+    .expect("blame lines");
+    assert_eq!(blame.file_path, "lib.txt");
+    let commits = blame
+        .lines
+        .iter()
+        .map(|line| line.commit.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        commits,
+        [Some(merge_base.clone()), Some(head.clone()), None]
+    );
+    assert_eq!(blame.lines[1].author.as_deref(), Some("Test"));
+    assert_eq!(blame.lines[1].summary.as_deref(), Some("shout two"));
+    assert!(
+        tauri::async_runtime::block_on(get_line_blame(GetLineBlameInput {
+            workspace: workspace.clone(),
+            file_path: "../lib.txt".to_string(),
+            start_line: 1,
+            end_line: None,
+            rev: None,
+        }))
+        .is_err()
+    );
+
+    let finding = |side: &str, line_number: i64| AiReviewFinding {
+        id: format!("{side}:{line_number}"),
+        file_path: "lib.txt".to_string(),
+        chunk_id: "lib.txt#file-1".to_string(),
+        chunk_index: 1,
+        hunk_header: "@@ -1,3 +1,3 @@".to_string(),
+        side: side.to_string(),
+        line_number,
+        title: "title".to_string(),
+        body: "body".to_string(),
+        severity: "medium".to_string(),
+        confidence: None,
+        category: None,
+        fingerprint: None,
+        status: None,
+        code_hash: None,
+        recurring: None,
+        suggested_patch: None,
+        language: None,
+        blame: None,
+    };
+    let mut findings = vec![
+        finding("additions", 1),
+        finding("additions", 2),
+        finding("additions", 3),
+        finding("deletions", 2),
+    ];
+    blame_findings(&workspace, &merge_base, &head, &mut findings);
+    let introduced = findings
+        .iter()
+        .map(|finding| finding.blame.as_ref().map(|blame| blame.introduced))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        introduced,
+        [Some(false), Some(true), Some(true), Some(false)]
+    );
+    let deleted = findings[3].blame.as_ref().expect("deleted line blame");
+    assert_eq!(deleted.summary.as_deref(), Some("init"));
+}
+
+#[test]
+fn commit_history_pages_filters_and_details_commits() {
+    let repo = TestRepo::init("commit-history", "master");
+    let commit = |author: &str, message: &str| {
+        repo.git(&["add", "-A"]);
+        repo.git(&[
+            "-c",
+            &format!("user.email={}@example.com", author.to_lowercase()),
+            "-c",
+            &format!("user.name={author}"),
+            "commit",
+            "-m",
+            message,
+        ]);
+    };
+
+    repo.write("old name.txt", "one\ntwo\nthree\nfour\n");
+    repo.write("image.bin", [0u8, 1, 2]);
+    commit("Ada", "init");
+    repo.write("lib.txt", "lib\n");
+    commit("Grace", "add lib");
+    fs::rename(repo.join("old name.txt"), repo.join("new name.txt")).expect("rename");
+    repo.write("new name.txt", "one\ntwo\nthree\n4\n");
+    repo.write("lib.txt", "lib\nmore\n");
+    commit("Ada", "rename and extend\n\nLonger explanation.");
+    let workspace = repo.workspace();
+
+    tauri::async_runtime::block_on(async {
+        let page = list_workspace_commits(ListWorkspaceCommitsInput {
+            workspace: workspace.clone(),
+            range: None,
+            author: None,
+            offset: Some(1),
+            limit: Some(1),
+        })
+        .await
+        .expect("list commits");
+        assert_eq!(page.range, "HEAD");
+        assert_eq!(page.commits.len(), 1);
+        assert_eq!(page.commits[0].subject, "add lib");
+        assert_eq!(page.commits[0].author, "Grace");
+        assert!(page.has_more);
+
+        let by_ada = list_workspace_commits(ListWorkspaceCommitsInput {
+            workspace: workspace.clone(),
+            range: Some("HEAD~2..HEAD".to_string()),
+            author: Some("ada@EXAMPLE".to_string()),
+            offset: None,
+            limit: None,
+        })
+        .await
+        .expect("list commits by author");
+        let subjects = by_ada
+            .commits
+            .iter()
+            .map(|commit| commit.subject.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(subjects, ["rename and extend"]);
+        assert!(!by_ada.has_more);
+
+        let detail = get_commit_detail(GetCommitDetailInput {
+            workspace: workspace.clone(),
+            commit: "HEAD".to_string(),
+        })
+        .await
+        .expect("commit detail");
+        assert_eq!(detail.subject, "rename and extend");
+        assert_eq!(detail.message, "rename and extend\n\nLonger explanation.");
+        assert_eq!(detail.parents.len(), 1);
+        assert_eq!((detail.insertions, detail.deletions), (2, 1));
+        let files = detail
+            .files
+            .iter()
+            .map(|file| {
+                (
+                    file.status.as_str(),
+                    file.previous_path.as_deref(),
+                    file.path.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            [
+                ("modified", None, "lib.txt"),
+                ("renamed", Some("old name.txt"), "new name.txt"),
+            ]
+        );
+        assert!(detail.files[0]
+            .diff
+            .as_deref()
+            .is_some_and(|diff| diff.contains("+more")));
+
+        let root = get_commit_detail(GetCommitDetailInput {
+            workspace: workspace.clone(),
+            commit: "HEAD~2".to_string(),
+        })
+        .await
+        .expect("root commit detail");
+        assert!(root.parents.is_empty());
+        let binary = root
+            .files
+            .iter()
+            .find(|file| file.path == "image.bin")
+            .expect("binary file");
+        assert_eq!((binary.status.as_str(), binary.insertions), ("added", None));
+
+        assert!(get_commit_detail(GetCommitDetailInput {
+            workspace,
+            commit: "--output=/tmp/x".to_string(),
+        })
+        .await
+        .is_err());
+    });
+}
+
+#[test]
+fn auto_stash_carries_changes_across_branch_switches() {
+    let repo = TestRepo::init("auto-stash", "master");
+    repo.write("notes.txt", "base\n");
+    repo.write("shared.txt", "one\n");
+    repo.commit("init");
+    repo.git(&["checkout", "-b", "feature"]);
+    repo.write("shared.txt", "feature\n");
+    repo.commit("feature");
+    repo.git(&["checkout", "master"]);
+
+    repo.write("notes.txt", "edited\n");
+    repo.write("staged.txt", "staged\n");
+    repo.git(&["add", "staged.txt"]);
+    repo.write("scratch.txt", "scratch\n");
+    let workspace = repo.workspace();
+    let checkout = |branch_name: &str, auto_stash: bool| {
+        tauri::async_runtime::block_on(checkout_workspace_branch(CheckoutWorkspaceBranchInput {
+            workspace: workspace.clone(),
+            branch_name: branch_name.to_string(),
+            auto_stash: Some(auto_stash),
+        }))
+    };
+    let status = || {
+        tauri::async_runtime::block_on(workspace_status(WorkspaceStatusInput {
+            workspace: workspace.clone(),
+        }))
+        .expect("workspace status")
+    };
+
+    let before = status();
+    assert_eq!(before.branch.as_deref(), Some("master"));
+    assert_eq!(
+        (before.staged, before.unstaged, before.untracked),
+        (1, 1, 1)
+    );
+    assert!(!before.clean);
+    assert_eq!(before.upstream, None);
+
+    let switched = checkout("feature", true).expect("switch with auto-stash");
+    let stash = switched.stash.expect("changes were stashed");
+    assert!(stash.restored);
+    assert!(stash.conflicts.is_empty());
+    let after = status();
+    assert_eq!(after.branch.as_deref(), Some("feature"));
+    assert_eq!((after.staged, after.unstaged, after.untracked), (1, 1, 1));
+    assert_eq!(after.stash_count, 0);
+
+    repo.write("shared.txt", "mine\n");
+    let refused = checkout("master", false).expect_err("dirty checkout is refused");
+    assert!(refused.contains("autoStash"));
+
+    let conflicted = checkout("master", true).expect("switch despite conflicts");
+    let stash = conflicted.stash.expect("changes were stashed");
+    assert!(!stash.restored);
+    assert_eq!(stash.conflicts, ["shared.txt"]);
+    let after_conflict = status();
+    assert_eq!(after_conflict.conflicted, 1);
+    assert_eq!(after_conflict.stash_count, 1);
+}
+
+#[test]
+fn pull_fast_forwards_and_push_sets_upstreams() {
+    let root = TestRepo::new("pull-push");
+    let seed_path = root.join("seed");
+    let alice_path = root.join("alice");
+    let bob_path = root.join("bob");
+    let commit = |repo_path: &Path, message: &str| {
+        fs::write(repo_path.join(format!("{message}.txt")), message).expect("write file");
+        commit_all(repo_path, message);
+    };
+    let push = |repo_path: &Path, branch: Option<&str>| {
+        push_workspace_branch_with_auth(
+            &PushWorkspaceBranchInput {
+                workspace: repo_path.to_string_lossy().to_string(),
+                branch: branch.map(ToOwned::to_owned),
+                remote: None,
+                set_upstream: None,
+                provider: None,
+            },
+            &[],
+        )
+    };
+
+    init_repo(&seed_path, "main");
+    commit(&seed_path, "init");
+    for args in [
+        &["clone", "--bare", "seed", "origin.git"][..],
+        &["clone", "origin.git", "alice"],
+        &["clone", "origin.git", "bob"],
+    ] {
+        root.git(args);
+    }
+    let bob = bob_path.to_string_lossy().to_string();
+
+    commit(&alice_path, "alice-1");
+    let pushed = push(&alice_path, None).expect("push main");
+    assert_eq!(pushed.status, "pushed");
+    assert_eq!(
+        (pushed.remote.as_str(), pushed.remote_branch.as_str()),
+        ("origin", "main")
+    );
+    assert_eq!(
+        push(&alice_path, None).expect("push again").status,
+        "up-to-date"
+    );
+
+    let pulled = pull_workspace_branch_with_auth(&bob, &[]).expect("pull main");
+    assert_eq!(pulled.status, "fast-forwarded");
+    assert_eq!(pulled.head, pushed.head);
+    assert_eq!(
+        pull_workspace_branch_with_auth(&bob, &[])
+            .expect("pull again")
+            .status,
+        "up-to-date"
+    );
+
+    commit(&alice_path, "alice-2");
+    push(&alice_path, None).expect("push second commit");
+    commit(&bob_path, "bob-1");
+    let diverged = pull_workspace_branch_with_auth(&bob, &[]).expect("pull diverged");
+    assert_eq!(diverged.status, "diverged");
+    assert_eq!((diverged.ahead, diverged.behind), (1, 1));
+    assert_eq!(diverged.head, diverged.previous_head);
+    let rejected = push(&bob_path, None).expect_err("non-fast-forward push");
+    assert!(rejected.contains("was rejected"), "{rejected}");
+
+    run_ok(&bob_path, &["checkout", "-b", "feature"]);
+    let created = push(&bob_path, None).expect("push new branch");
+    assert_eq!(created.status, "created");
+    assert!(created.upstream_set);
+    assert_eq!(
+        pull_workspace_branch_with_auth(&bob, &[])
+            .expect("pull feature")
+            .upstream,
+        "origin/feature"
+    );
+}
+
+#[test]
+fn commit_stages_only_the_selected_paths() {
+    let repo = TestRepo::init("commit", "master");
+    repo.write("a.txt", "a\n");
+    repo.commit("init");
+
+    repo.write("a.txt", "changed\n");
+    repo.write("b.txt", "b\n");
+    repo.write("[c].txt", "c\n");
+    let workspace = repo.workspace();
+    let commit = |message: &str, paths: Option<Vec<String>>| {
+        tauri::async_runtime::block_on(commit_workspace_changes(CommitWorkspaceChangesInput {
+            workspace: workspace.clone(),
+            message: message.to_string(),
+            paths,
+        }))
+    };
+
+    let empty = commit("  ", None).expect_err("empty messages are refused");
+    assert!(empty.contains("must not be empty"));
+    let nothing = commit("Update", None).expect_err("nothing is staged yet");
+    assert!(nothing.contains("Nothing is staged"));
+
+    let committed = commit(
+        "Update a and c\n\nBody text.",
+        Some(vec!["a.txt".to_string(), "[c].txt".to_string()]),
+    )
+    .expect("commit selected paths");
+    assert_eq!(committed.branch.as_deref(), Some("master"));
+    assert_eq!(committed.summary, "Update a and c");
+    assert_eq!(committed.files, vec!["[c].txt", "a.txt"]);
+    let (_, diff, files) = read_staged_diff(&workspace).expect("staged diff");
+    assert!(diff.is_empty() && files.is_empty());
+    assert_eq!(committed.commit, repo.head());
+    assert_eq!(
+        git_stdout(repo.path(), &["status", "--porcelain"]),
+        "?? b.txt\n"
+    );
+}
+
+#[test]
+fn shallow_clones_are_deepened_to_reach_the_merge_base() {
+    let root = TestRepo::new("shallow");
+    let origin_path = root.join("origin");
+    let commit = |message: &str| {
+        fs::write(origin_path.join("log.txt"), message).expect("write file");
+        commit_all(&origin_path, message);
+    };
+
+    init_repo(&origin_path, "master");
+    commit("init");
+    run_ok(&origin_path, &["checkout", "-b", "feature"]);
+    commit("feature");
+    run_ok(&origin_path, &["checkout", "master"]);
+    for message in ["one", "two", "three"] {
+        commit(message);
+    }
+    let origin_url = format!("file://{}", origin_path.to_string_lossy());
+    let clone = |name: &str| {
+        let clone_path = root.join(name);
+        root.git(&[
+            "clone",
+            "--quiet",
+            "--depth",
+            "1",
+            "--no-single-branch",
+            &origin_url,
+            name,
+        ]);
+        clone_path
+    };
+
+    let clone_path = clone("compare");
+    run_ok(&clone_path, &["checkout", "--quiet", "feature"]);
+    let result =
+        tauri::async_runtime::block_on(compare_workspace_diff(CompareWorkspaceDiffInput {
+            workspace: clone_path.to_string_lossy().to_string(),
+            base_ref: Some("origin/master".to_string()),
+            fetch_remote: Some(false),
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: Some(WorkspaceDiffTarget::Branch),
+            include_untracked: None,
+            head_ref: None,
+            include_submodule_diffs: None,
+            force: None,
+            remote: None,
+        }))
+        .expect("compare deepens the shallow clone");
+    assert_eq!(result.history_deepened_by, Some(50));
+    assert_eq!(result.files_changed, 1);
+    let init = git_stdout(&origin_path, &["rev-list", "--max-parents=0", "HEAD"]);
+    assert_eq!(result.merge_base, init.trim());
+
+    let clone_path = clone("unshallow");
+    let workspace = clone_path.to_string_lossy().to_string();
+    let deepened = unshallow_workspace_with_auth(&workspace, Some(1), &[]).expect("deepen");
+    assert!(deepened.was_shallow && deepened.shallow);
+    assert_eq!(deepened.remote, "origin");
+    assert_eq!(deepened.commit_count, 2);
+    let full = unshallow_workspace_with_auth(&workspace, None, &[]).expect("unshallow");
+    assert!(full.was_shallow && !full.shallow);
+    assert_eq!(full.commit_count, 4);
+    let again = unshallow_workspace_with_auth(&workspace, None, &[]).expect("no-op");
+    assert!(!again.was_shallow);
+}
+
+#[test]
+fn submodule_pointer_changes_are_listed_with_their_own_diff() {
+    let root = TestRepo::new("submodule");
+    let library_path = root.join("library");
+    let app_path = root.join("app");
+
+    init_repo(&library_path, "master");
+    fs::write(library_path.join("lib.txt"), "v1\n").expect("write file");
+    commit_all(&library_path, "v1");
+    init_repo(&app_path, "master");
+    fs::write(app_path.join("README.md"), "app\n").expect("write file");
+    run_ok(
+        &app_path,
+        &[
+            "-c",
+            "protocol.file.allow=always",
+            "submodule",
+            "add",
+            "--quiet",
+            &library_path.to_string_lossy(),
+            "vendor/library",
+        ],
+    );
+    commit_all(&app_path, "init");
+
+    let submodule_path = app_path.join("vendor/library");
+    run_ok(&app_path, &["checkout", "-b", "feature"]);
+    fs::write(submodule_path.join("lib.txt"), "v2\n").expect("write file");
+    commit_all(&submodule_path, "v2");
+    commit_all(&app_path, "bump library");
+
+    let mut result =
+        tauri::async_runtime::block_on(compare_workspace_diff(CompareWorkspaceDiffInput {
+            workspace: app_path.to_string_lossy().to_string(),
+            base_ref: Some("master".to_string()),
+            fetch_remote: Some(false),
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: Some(WorkspaceDiffTarget::Branch),
+            include_untracked: None,
+            head_ref: None,
+            include_submodule_diffs: None,
+            force: None,
+            remote: None,
+        }))
+        .expect("compare submodule bump");
+    assert_eq!(result.submodules.len(), 1);
+    let submodule = &result.submodules[0];
+    assert_eq!(submodule.path, "vendor/library");
+    assert_eq!(submodule.status, "modified");
+    assert!(submodule.old_commit.is_some() && submodule.new_commit.is_some());
+    assert_ne!(submodule.old_commit, submodule.new_commit);
+
+    attach_submodule_diffs(&app_path, &mut result.submodules, &[]);
+    assert_eq!(result.submodules[0].diff, None);
+    let registered = [submodule_path.to_string_lossy().to_string()];
+    attach_submodule_diffs(&app_path, &mut result.submodules, &registered);
+    let submodule = &result.submodules[0];
+    assert_eq!(submodule.workspace.as_deref(), Some(registered[0].as_str()));
+    let diff = submodule.diff.as_deref().expect("submodule diff");
+    assert!(diff.contains("-v1") && diff.contains("+v2"));
+}
+
+#[test]
+fn libgit2_reads_match_the_git_cli() {
+    let root = TestRepo::new("libgit2");
+    let seed_path = root.join("seed");
+    let repo_path = root.join("clone");
+    let commit = |repo_path: &Path, message: &str| {
+        fs::write(repo_path.join(format!("{message}.txt")), message).expect("write file");
+        commit_all(repo_path, message);
+    };
+
+    init_repo(&seed_path, "main");
+    commit(&seed_path, "init");
+    run_ok(&seed_path, &["branch", "feature/nested"]);
+    root.git(&["clone", "seed", "clone"]);
+    run_ok(
+        &repo_path,
+        &["checkout", "-b", "topic", "--track", "origin/main"],
+    );
+    commit(&repo_path, "topic-1");
+    commit(&seed_path, "main-1");
+    run_ok(&repo_path, &["fetch", "--quiet", "origin"]);
+
+    run_ok(&repo_path, &["mv", "init.txt", "renamed.txt"]);
+    fs::write(repo_path.join("topic-1.txt"), "edited").expect("write file");
+    fs::write(repo_path.join("untracked.txt"), "new").expect("write file");
+
+    let repo = GitRepo::open(&repo_path);
+    let cli_refs = |namespace: &str| {
+        let mut names = git_stdout(
+            &repo_path,
+            &["for-each-ref", "--format=%(refname:short)", namespace],
+        )
+        .lines()
+        .map(ToOwned::to_owned)
+        .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+    for namespace in ["refs/heads", "refs/remotes"] {
+        let mut names = repo.ref_names(namespace).expect("list refs");
+        names.sort();
+        assert_eq!(names, cli_refs(namespace));
+    }
+    assert!(cli_refs("refs/remotes").contains(&"origin/feature/nested".to_string()));
+
+    assert_eq!(
+        repo.current_branch().expect("branch").as_deref(),
+        Some("topic")
+    );
+    assert_eq!(
+        repo.upstream_of("topic").expect("upstream").as_deref(),
+        Some("origin/main")
+    );
+    assert_eq!(repo.remote_head("origin").as_deref(), Some("origin/main"));
+    assert_eq!(
+        repo.merge_base("HEAD", "origin/main").expect("merge base"),
+        git_stdout(&repo_path, &["merge-base", "HEAD", "origin/main"]).trim()
+    );
+    assert_eq!(repo.commit_id("origin/missing"), None);
+
+    let status = repo.status().expect("status");
+    assert_eq!(
+        status,
+        parse_status_summary(&git_stdout(
+            &repo_path,
+            &["status", "--porcelain=v2", "--branch", "-z"]
+        ))
+    );
+    assert_eq!(status.ahead_behind, Some((1, 1)));
+    assert_eq!(
+        (status.staged, status.unstaged, status.untracked),
+        (1, 1, 1)
+    );
+}
+
+#[test]
+fn repeated_comparisons_reuse_the_cached_diff_until_the_workspace_moves() {
+    let repo = TestRepo::init("diff-cache", "master");
+    repo.write("file.txt", "one\n");
+    repo.commit("init");
+    repo.git(&["checkout", "-b", "feature"]);
+    repo.write("file.txt", "two\n");
+    repo.commit("change");
+
+    let compare = |diff_target, force| {
+        tauri::async_runtime::block_on(compare_workspace_diff(CompareWorkspaceDiffInput {
+            workspace: repo.workspace(),
+            base_ref: Some("master".to_string()),
+            fetch_remote: Some(false),
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: Some(diff_target),
+            include_untracked: None,
+            head_ref: None,
+            include_submodule_diffs: None,
+            force: Some(force),
+            remote: None,
+        }))
+        .expect("compare workspace diff")
+    };
+
+    let first = compare(WorkspaceDiffTarget::Branch, false);
+    assert!(!first.cached);
+    let repeated = compare(WorkspaceDiffTarget::Branch, false);
+    assert!(repeated.cached);
+    assert_eq!(repeated.diff, first.diff);
+    assert!(!compare(WorkspaceDiffTarget::Branch, true).cached);
+
+    repo.write("file.txt", "three, uncommitted\n");
+    let edited = compare(WorkspaceDiffTarget::Branch, false);
+    assert!(!edited.cached);
+    assert!(edited.diff.contains("+three, uncommitted"));
+
+    repo.commit("commit the edit");
+    let committed = compare(WorkspaceDiffTarget::Branch, false);
+    assert!(!committed.cached);
+    assert_ne!(committed.head, edited.head);
+
+    repo.write("file.txt", "four\n");
+    assert!(!compare(WorkspaceDiffTarget::Staged, false).cached);
+    assert!(compare(WorkspaceDiffTarget::Staged, false).diff.is_empty());
+    repo.git(&["add", "file.txt"]);
+    let staged = compare(WorkspaceDiffTarget::Staged, false);
+    assert!(!staged.cached);
+    assert!(staged.diff.contains("+four"));
+
+    // Kept out of the status snapshot, so only its own fingerprint notices the edit.
+    repo.write(".git/info/exclude", ".rovexignore\n");
+    assert!(compare(WorkspaceDiffTarget::Branch, false)
+        .diff
+        .contains("file.txt"));
+    assert!(compare(WorkspaceDiffTarget::Branch, false).cached);
+    repo.write(".rovexignore", "file.txt\n");
+    let ignored = compare(WorkspaceDiffTarget::Branch, false);
+    assert!(!ignored.cached);
+    assert!(!ignored.diff.contains("file.txt"));
+}
+
+#[test]
+fn fetch_progress_lines_are_parsed_and_streamed() {
+    let receiving =
+        parse_fetch_progress("Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s  ")
+            .expect("receiving progress");
+    assert_eq!(receiving.phase, "Receiving objects");
+    assert_eq!(
+        (receiving.percent, receiving.current, receiving.total),
+        (Some(45), Some(450), Some(1000))
+    );
+    assert_eq!(receiving.transfer.as_deref(), Some("1.20 MiB | 2.00 MiB/s"));
+    let enumerating =
+        parse_fetch_progress("remote: Enumerating objects: 20, done.").expect("count progress");
+    assert_eq!(
+        (
+            enumerating.phase.as_str(),
+            enumerating.current,
+            enumerating.percent
+        ),
+        ("Enumerating objects", Some(20), None)
+    );
+    for line in [
+        "From github.com:owner/repo",
+        "   1111111..2222222  main       -> origin/main",
+        "remote: Total 4 (delta 0), reused 0 (delta 0), pack-reused 0",
+    ] {
+        assert_eq!(parse_fetch_progress(line), None, "{line}");
+    }
+
+    let root = TestRepo::new("fetch-progress");
+    let seed_path = root.join("seed");
+    let clone_path = root.join("clone");
+    let commit = |message: &str| {
+        fs::write(seed_path.join(format!("{message}.txt")), message).expect("write file");
+        commit_all(&seed_path, message);
+    };
+    init_repo(&seed_path, "main");
+    commit("init");
+    root.git(&["clone", "seed", "clone"]);
+    commit("second");
+
+    let mut phases = Vec::new();
+    fetch_remote_with_progress(&clone_path, "origin", &[], |progress| {
+        phases.push(progress.phase)
+    })
+    .expect("fetch origin");
+    assert!(!phases.is_empty());
+    assert!(GitRepo::open(&clone_path).last_fetched_at().is_some());
+    assert!(GitRepo::open(&clone_path)
+        .commit_id("origin/main")
+        .is_some());
+
+    let error = fetch_remote_with_progress(&clone_path, "missing", &[], |_| {})
+        .expect_err("unknown remote");
+    assert!(error.contains("git fetch missing failed"), "{error}");
+}
+
+#[test]
+fn branches_and_base_refs_follow_the_upstream_remote() {
+    let root = TestRepo::new("upstream-remote");
+    let parent_path = root.join("parent");
+    let repo_path = root.join("clone");
+    let commit = |repo_path: &Path, message: &str| {
+        fs::write(repo_path.join(format!("{message}.txt")), message).expect("write file");
+        commit_all(repo_path, message);
+    };
+
+    // A fork workflow: `origin` is the fork, and the topic branch tracks `upstream/main`.
+    init_repo(&parent_path, "main");
+    commit(&parent_path, "init");
+    root.git(&["clone", "parent", "fork"]);
+    commit(&root.join("fork"), "fork-only");
+    root.git(&["clone", "fork", "clone"]);
+    run_ok(
+        &repo_path,
+        &["remote", "add", "upstream", &parent_path.to_string_lossy()],
+    );
+    run_ok(&repo_path, &["fetch", "--quiet", "upstream"]);
+    run_ok(
+        &repo_path,
+        &["checkout", "-b", "topic", "--track", "upstream/main"],
+    );
+    commit(&repo_path, "topic-1");
+    commit(&parent_path, "main-1");
+
+    let branches =
+        tauri::async_runtime::block_on(list_workspace_branches(ListWorkspaceBranchesInput {
+            workspace: repo_path.to_string_lossy().to_string(),
+            fetch_remote: Some(true),
+            remote: None,
+            sort: None,
+        }))
+        .expect("list workspace branches");
+    assert_eq!(branches.default_remote, "upstream");
+    assert_eq!(branches.suggested_base_ref, "upstream/main");
+    assert_eq!(
+        branches
+            .remotes
+            .iter()
+            .map(|remote| remote.name.as_str())
+            .collect::<Vec<_>>(),
+        ["origin", "upstream"]
+    );
+    assert!(branches.remotes.iter().all(|remote| remote.url.is_some()));
+
+    // Listing fetched `upstream`, and comparing without a base ref fetches it again.
+    let repo = GitRepo::open(&repo_path);
+    let parent = GitRepo::open(&parent_path);
+    assert_eq!(repo.commit_id("upstream/main"), parent.commit_id("main"));
+    commit(&parent_path, "main-2");
+    let compare =
+        tauri::async_runtime::block_on(compare_workspace_diff(CompareWorkspaceDiffInput {
+            workspace: repo_path.to_string_lossy().to_string(),
+            base_ref: None,
+            fetch_remote: Some(true),
+            include_paths: None,
+            exclude_paths: None,
+            diff_target: None,
+            include_untracked: None,
+            head_ref: None,
+            include_submodule_diffs: None,
+            force: None,
+            remote: None,
+        }))
+        .expect("compare workspace diff");
+    assert_eq!(compare.base_ref, "upstream/main");
+    assert_eq!(compare.remote.as_deref(), Some("upstream"));
+    assert_eq!(repo.commit_id("upstream/main"), parent.commit_id("main"));
+    assert!(compare.diff.contains("topic-1.txt"));
+    assert!(!compare.diff.contains("fork-only.txt"));
+}
+
+#[test]
+fn every_table_with_a_workspace_column_is_migrated() {
+    let dir = TestRepo::new("schema");
+    let path = dir.join("app.db");
+    let tables = tauri::async_runtime::block_on(async {
+        let database = libsql::Builder::new_local(&path)
+            .build()
+            .await
+            .expect("open database");
+        db::initialize_schema(&database)
+            .await
+            .expect("initialize schema");
+        let conn = database.connect().expect("connect");
+        let mut rows = conn
+            .query(
+                "SELECT m.name FROM sqlite_master m, pragma_table_info(m.name) c
+                 WHERE m.type = 'table' AND c.name = 'workspace' ORDER BY m.name",
+                (),
+            )
+            .await
+            .expect("list tables");
+        let mut tables = Vec::new();
+        while let Some(row) = rows.next().await.expect("read table") {
+            tables.push(row.get::<String>(0).expect("table name"));
+        }
+        tables
+    });
+
+    let mut migrated = WORKSPACE_TABLES.to_vec();
+    migrated.sort();
+    assert_eq!(
+        tables, migrated,
+        "add new workspace tables to WORKSPACE_TABLES so migrate_repositories_dir rewrites them"
+    );
+}
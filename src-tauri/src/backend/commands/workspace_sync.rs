@@ -0,0 +1,213 @@
+use std::path::Path;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, State};
+
+use super::common::{
+    as_non_empty_trimmed, parse_json_vec_or_default, parse_setting_u64,
+    DEFAULT_WORKSPACE_SYNC_INTERVAL_MINUTES, ROVEX_WORKSPACE_SYNC_INTERVAL_MINUTES_ENV,
+};
+use super::workspace_git::{check_remote_sync, RemoteSyncCheck};
+use crate::backend::{
+    AppState, ListStaleWorkspacesInput, ListStaleWorkspacesResult, WorkspaceSyncStatus,
+};
+
+const SYNC_WATCH_TICK: Duration = Duration::from_secs(60);
+
+const SYNC_STATUS_COLUMNS: &str = "workspace, branch, upstream, base_ref, stale_refs_json,
+    behind_upstream, error, checked_at";
+
+/// Workspaces that have a thread which is not archived, skipping folders that no longer exist.
+pub(crate) async fn registered_workspaces(state: &AppState) -> Result<Vec<String>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT DISTINCT workspace FROM threads
+             WHERE workspace IS NOT NULL AND workspace != '' AND archived_at IS NULL
+             ORDER BY workspace",
+            (),
+        )
+        .await
+        .map_err(|error| format!("Failed to list workspaces: {error}"))?;
+    let mut workspaces = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read workspace row: {error}"))?
+    {
+        let workspace: String = row
+            .get(0)
+            .map_err(|error| format!("Failed to parse workspace: {error}"))?;
+        if Path::new(&workspace).is_dir() {
+            workspaces.push(workspace);
+        }
+    }
+    Ok(workspaces)
+}
+
+async fn record_sync_check(
+    state: &AppState,
+    workspace: &str,
+    check: Result<RemoteSyncCheck, String>,
+) -> Result<(), String> {
+    // A failed check, usually because the remote is unreachable, keeps the last known refs so an
+    // offline laptop does not hide a workspace that was already known to be stale.
+    let (check, error) = match check {
+        Ok(check) => (Some(check), None),
+        Err(error) => (None, Some(error)),
+    };
+    let stale_refs_json = check
+        .as_ref()
+        .map(|check| serde_json::to_string(&check.stale_refs))
+        .transpose()
+        .map_err(|error| format!("Failed to serialize stale refs: {error}"))?;
+    let conn = state.connection()?;
+    conn.execute(
+        "INSERT INTO workspace_sync_status
+           (workspace, branch, upstream, base_ref, stale_refs_json, behind_upstream, error,
+            checked_at)
+         VALUES (?1, ?2, ?3, ?4, COALESCE(?5, '[]'), COALESCE(?6, 0), ?7, CURRENT_TIMESTAMP)
+         ON CONFLICT(workspace) DO UPDATE SET
+           branch = CASE WHEN ?7 IS NULL THEN excluded.branch ELSE branch END,
+           upstream = CASE WHEN ?7 IS NULL THEN excluded.upstream ELSE upstream END,
+           base_ref = CASE WHEN ?7 IS NULL THEN excluded.base_ref ELSE base_ref END,
+           stale_refs_json = COALESCE(?5, stale_refs_json),
+           behind_upstream = COALESCE(?6, behind_upstream),
+           error = excluded.error,
+           checked_at = CURRENT_TIMESTAMP",
+        (
+            workspace.to_string(),
+            check.as_ref().and_then(|check| check.branch.clone()),
+            check.as_ref().and_then(|check| check.upstream.clone()),
+            check.as_ref().and_then(|check| check.base_ref.clone()),
+            stale_refs_json,
+            check.as_ref().map(|check| check.behind_upstream),
+            error,
+        ),
+    )
+    .await
+    .map_err(|error| format!("Failed to save sync status for {workspace}: {error}"))?;
+    Ok(())
+}
+
+async fn check_workspaces(state: &AppState, workspaces: &[String]) -> Result<(), String> {
+    for workspace in workspaces {
+        let remote_workspace = workspace.clone();
+        // `git ls-remote` blocks on the network, so it stays off the async workers.
+        let check =
+            tauri::async_runtime::spawn_blocking(move || check_remote_sync(&remote_workspace))
+                .await
+                .map_err(|error| format!("Failed to check {workspace} against its remote: {error}"))
+                .and_then(|check| check);
+        record_sync_check(state, workspace, check).await?;
+    }
+    Ok(())
+}
+
+async fn load_sync_statuses(
+    state: &AppState,
+    workspace: Option<&str>,
+) -> Result<Vec<WorkspaceSyncStatus>, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT {SYNC_STATUS_COLUMNS} FROM workspace_sync_status
+                 WHERE ?1 IS NULL OR workspace = ?1
+                 ORDER BY workspace"
+            ),
+            [workspace.map(ToOwned::to_owned)],
+        )
+        .await
+        .map_err(|error| format!("Failed to load workspace sync status: {error}"))?;
+    let mut statuses = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read workspace sync status row: {error}"))?
+    {
+        let stale_refs_json: String = row
+            .get(4)
+            .map_err(|error| format!("Failed to parse stale refs: {error}"))?;
+        let stale_refs = parse_json_vec_or_default::<String>(&stale_refs_json);
+        let behind_upstream: i64 = row
+            .get(5)
+            .map_err(|error| format!("Failed to parse commits behind upstream: {error}"))?;
+        statuses.push(WorkspaceSyncStatus {
+            workspace: row
+                .get(0)
+                .map_err(|error| format!("Failed to parse sync status workspace: {error}"))?,
+            branch: row
+                .get(1)
+                .map_err(|error| format!("Failed to parse sync status branch: {error}"))?,
+            upstream: row
+                .get(2)
+                .map_err(|error| format!("Failed to parse sync status upstream: {error}"))?,
+            base_ref: row
+                .get(3)
+                .map_err(|error| format!("Failed to parse sync status base ref: {error}"))?,
+            stale: !stale_refs.is_empty() || behind_upstream > 0,
+            stale_refs,
+            behind_upstream,
+            error: row
+                .get(6)
+                .map_err(|error| format!("Failed to parse sync status error: {error}"))?,
+            checked_at: row
+                .get(7)
+                .map_err(|error| format!("Failed to parse sync status timestamp: {error}"))?,
+        });
+    }
+    Ok(statuses)
+}
+
+/// Workspaces whose clone is behind its remote, so the UI can offer a fetch before a review
+/// compares against an outdated merge base. Pass `workspace` to get its status even when it
+/// is up to date.
+pub async fn list_stale_workspaces(
+    state: State<'_, AppState>,
+    input: ListStaleWorkspacesInput,
+) -> Result<ListStaleWorkspacesResult, String> {
+    let workspace = as_non_empty_trimmed(input.workspace.as_deref());
+    if input.refresh.unwrap_or(false) {
+        let workspaces = match workspace.as_ref() {
+            Some(workspace) => vec![workspace.clone()],
+            None => registered_workspaces(&state).await?,
+        };
+        check_workspaces(&state, &workspaces).await?;
+    }
+    let mut workspaces = load_sync_statuses(&state, workspace.as_deref()).await?;
+    if workspace.is_none() {
+        workspaces.retain(|status| status.stale);
+    }
+    Ok(ListStaleWorkspacesResult { workspaces })
+}
+
+/// Starts the background task that checks registered workspaces against their remotes every
+/// `ROVEX_WORKSPACE_SYNC_INTERVAL_MINUTES`. Setting it to `0` turns the checks off.
+pub(crate) fn start_workspace_sync_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut minutes_since_check = u64::MAX;
+        loop {
+            tokio::time::sleep(SYNC_WATCH_TICK).await;
+            let interval_minutes = parse_setting_u64(
+                ROVEX_WORKSPACE_SYNC_INTERVAL_MINUTES_ENV,
+                DEFAULT_WORKSPACE_SYNC_INTERVAL_MINUTES,
+                0,
+            );
+            minutes_since_check = minutes_since_check.saturating_add(1);
+            if interval_minutes == 0 || minutes_since_check < interval_minutes {
+                continue;
+            }
+            minutes_since_check = 0;
+
+            let state = app.state::<AppState>();
+            let result = match registered_workspaces(&state).await {
+                Ok(workspaces) => check_workspaces(&state, &workspaces).await,
+                Err(error) => Err(error),
+            };
+            if let Err(error) = result {
+                eprintln!("[backend] Failed to check workspace sync status: {error}");
+            }
+        }
+    });
+}
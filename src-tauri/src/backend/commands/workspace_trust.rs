@@ -0,0 +1,91 @@
+use tauri::State;
+
+use crate::backend::{AppState, GetWorkspaceTrustInput, SetWorkspaceTrustInput, WorkspaceTrust};
+
+pub(crate) const TRUST_LEVEL_TRUSTED: &str = "trusted";
+pub(crate) const TRUST_LEVEL_UNTRUSTED: &str = "untrusted";
+
+fn parse_workspace(value: &str) -> Result<String, String> {
+    let workspace = value.trim();
+    if workspace.is_empty() {
+        return Err("Workspace path must not be empty.".to_string());
+    }
+    Ok(workspace.to_string())
+}
+
+async fn load_workspace_trust(state: &AppState, workspace: &str) -> Result<WorkspaceTrust, String> {
+    let conn = state.connection()?;
+    let mut rows = conn
+        .query(
+            "SELECT trust_level, updated_at FROM workspace_trust WHERE workspace = ?1",
+            [workspace.to_string()],
+        )
+        .await
+        .map_err(|error| format!("Failed to load trust for workspace {workspace}: {error}"))?;
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read trust for workspace {workspace}: {error}"))?
+    else {
+        // Workspaces were always trusted before trust levels existed, so that stays the default.
+        return Ok(WorkspaceTrust {
+            workspace: workspace.to_string(),
+            trust_level: TRUST_LEVEL_TRUSTED.to_string(),
+            updated_at: None,
+        });
+    };
+    Ok(WorkspaceTrust {
+        workspace: workspace.to_string(),
+        trust_level: row
+            .get(0)
+            .map_err(|error| format!("Failed to parse workspace trust level: {error}"))?,
+        updated_at: row
+            .get(1)
+            .map_err(|error| format!("Failed to parse workspace trust timestamp: {error}"))?,
+    })
+}
+
+/// Whether agentic transports and workspace file reads are allowed for `workspace`.
+pub(crate) async fn is_workspace_trusted(
+    state: &AppState,
+    workspace: &str,
+) -> Result<bool, String> {
+    let trust = load_workspace_trust(state, workspace.trim()).await?;
+    Ok(trust.trust_level != TRUST_LEVEL_UNTRUSTED)
+}
+
+pub async fn get_workspace_trust(
+    state: State<'_, AppState>,
+    input: GetWorkspaceTrustInput,
+) -> Result<WorkspaceTrust, String> {
+    let workspace = parse_workspace(&input.workspace)?;
+    load_workspace_trust(&state, &workspace).await
+}
+
+pub async fn set_workspace_trust(
+    state: State<'_, AppState>,
+    input: SetWorkspaceTrustInput,
+) -> Result<WorkspaceTrust, String> {
+    let workspace = parse_workspace(&input.workspace)?;
+    let trust_level = match input.trust_level.trim().to_lowercase().as_str() {
+        TRUST_LEVEL_TRUSTED => TRUST_LEVEL_TRUSTED,
+        TRUST_LEVEL_UNTRUSTED => TRUST_LEVEL_UNTRUSTED,
+        _ => {
+            return Err(format!(
+                "Trust level must be '{TRUST_LEVEL_TRUSTED}' or '{TRUST_LEVEL_UNTRUSTED}'."
+            ))
+        }
+    };
+
+    let conn = state.connection()?;
+    conn.execute(
+        "INSERT INTO workspace_trust (workspace, trust_level) VALUES (?1, ?2)
+         ON CONFLICT(workspace) DO UPDATE SET
+           trust_level = excluded.trust_level,
+           updated_at = CURRENT_TIMESTAMP",
+        (workspace.clone(), trust_level.to_string()),
+    )
+    .await
+    .map_err(|error| format!("Failed to save trust for workspace {workspace}: {error}"))?;
+    load_workspace_trust(&state, &workspace).await
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+
+use super::common::{
+    format_path, parse_setting_u64, DEFAULT_WORKSPACE_WATCH_INTERVAL_MS,
+    ROVEX_WORKSPACE_WATCH_INTERVAL_MS_ENV, WORKSPACE_CHANGED_EVENT,
+};
+use super::name_sort::compare_paths;
+use super::workspace_git::{commits_between, read_workspace_snapshot, WorkspaceSnapshot};
+use crate::backend::paths::normalize_path;
+use crate::backend::{WatchWorkspaceInput, WorkspaceChangedEvent, WorkspaceWatch};
+
+/// Commits listed in one change event; more only means the diff is even further behind.
+const MAX_EVENT_COMMITS: usize = 20;
+/// Paths listed in one change event. `changedPathCount` still counts all of them.
+const MAX_EVENT_PATHS: usize = 200;
+
+struct WatchedWorkspace {
+    /// Open comparisons of this workspace. The watch ends when the last one closes.
+    watchers: usize,
+    snapshot: WorkspaceSnapshot,
+}
+
+static WATCHED_WORKSPACES: OnceLock<Mutex<HashMap<String, WatchedWorkspace>>> = OnceLock::new();
+
+fn watched_workspaces() -> &'static Mutex<HashMap<String, WatchedWorkspace>> {
+    WATCHED_WORKSPACES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn watch_key(workspace: &str) -> Result<String, String> {
+    let workspace = workspace.trim();
+    if workspace.is_empty() {
+        return Err("Workspace path must not be empty.".to_string());
+    }
+    Ok(format_path(&normalize_path(Path::new(workspace))?))
+}
+
+async fn read_snapshot(workspace: String) -> Result<WorkspaceSnapshot, String> {
+    tauri::async_runtime::spawn_blocking(move || read_workspace_snapshot(&workspace))
+        .await
+        .map_err(|error| format!("Failed to read workspace state: {error}"))?
+}
+
+/// Paths whose status, size or modification time differ between two snapshots.
+fn changed_paths(previous: &WorkspaceSnapshot, current: &WorkspaceSnapshot) -> Vec<String> {
+    let mut paths = previous
+        .paths
+        .iter()
+        .filter(|(path, state)| current.paths.get(*path) != Some(*state))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+    paths.extend(
+        current
+            .paths
+            .keys()
+            .filter(|path| !previous.paths.contains_key(*path))
+            .cloned(),
+    );
+    paths.sort_by(|left, right| compare_paths(left, right));
+    paths
+}
+
+fn change_event(
+    workspace: &str,
+    previous: &WorkspaceSnapshot,
+    current: &WorkspaceSnapshot,
+) -> Option<WorkspaceChangedEvent> {
+    let mut changed_paths = changed_paths(previous, current);
+    if previous.head == current.head
+        && previous.branch == current.branch
+        && changed_paths.is_empty()
+    {
+        return None;
+    }
+    let new_commits = match (&previous.head, &current.head) {
+        (Some(from), Some(to)) if from != to => {
+            commits_between(workspace, from, to, MAX_EVENT_COMMITS)
+        }
+        _ => Vec::new(),
+    };
+    let changed_path_count = changed_paths.len();
+    changed_paths.truncate(MAX_EVENT_PATHS);
+    Some(WorkspaceChangedEvent {
+        workspace: workspace.to_string(),
+        previous_head: previous.head.clone(),
+        head: current.head.clone(),
+        previous_branch: previous.branch.clone(),
+        branch: current.branch.clone(),
+        new_commits,
+        changed_paths,
+        changed_path_count,
+    })
+}
+
+/// Starts watching `workspace` for new commits, branch switches, and working-tree edits while
+/// a comparison of it is open. Each call needs a matching `unwatch_workspace`.
+pub async fn watch_workspace(input: WatchWorkspaceInput) -> Result<WorkspaceWatch, String> {
+    let workspace = watch_key(&input.workspace)?;
+    let snapshot = read_snapshot(workspace.clone()).await?;
+    let mut watched = watched_workspaces()
+        .lock()
+        .map_err(|_| "Failed to access watched workspaces.".to_string())?;
+    // A workspace that is already watched keeps its last snapshot, so changes the earlier
+    // comparisons have not heard about yet are still reported.
+    let entry = watched
+        .entry(workspace.clone())
+        .or_insert_with(|| WatchedWorkspace {
+            watchers: 0,
+            snapshot,
+        });
+    entry.watchers += 1;
+    Ok(WorkspaceWatch {
+        workspace,
+        head: entry.snapshot.head.clone(),
+        branch: entry.snapshot.branch.clone(),
+    })
+}
+
+/// Returns whether the workspace was being watched.
+pub fn unwatch_workspace(input: WatchWorkspaceInput) -> Result<bool, String> {
+    let workspace = watch_key(&input.workspace)?;
+    let mut watched = watched_workspaces()
+        .lock()
+        .map_err(|_| "Failed to access watched workspaces.".to_string())?;
+    let Some(entry) = watched.get_mut(&workspace) else {
+        return Ok(false);
+    };
+    entry.watchers = entry.watchers.saturating_sub(1);
+    if entry.watchers == 0 {
+        watched.remove(&workspace);
+    }
+    Ok(true)
+}
+
+/// Starts the background task that checks watched workspaces every
+/// `ROVEX_WORKSPACE_WATCH_INTERVAL_MS` and emits `rovex://workspace-changed` when one changed.
+/// Setting it to `0` turns the checks off.
+pub(crate) fn start_workspace_change_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let interval_ms = parse_setting_u64(
+                ROVEX_WORKSPACE_WATCH_INTERVAL_MS_ENV,
+                DEFAULT_WORKSPACE_WATCH_INTERVAL_MS,
+                0,
+            );
+            if interval_ms == 0 {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                continue;
+            }
+            tokio::time::sleep(Duration::from_millis(interval_ms.max(250))).await;
+
+            let workspaces = match watched_workspaces().lock() {
+                Ok(watched) => watched.keys().cloned().collect::<Vec<_>>(),
+                Err(_) => continue,
+            };
+            for workspace in workspaces {
+                let current = match read_snapshot(workspace.clone()).await {
+                    Ok(current) => current,
+                    Err(error) => {
+                        eprintln!(
+                            "[backend] Failed to check watched workspace {workspace}: {error}"
+                        );
+                        continue;
+                    }
+                };
+                let previous = {
+                    let Ok(mut watched) = watched_workspaces().lock() else {
+                        continue;
+                    };
+                    // Unwatched while its state was being read.
+                    let Some(entry) = watched.get_mut(&workspace) else {
+                        continue;
+                    };
+                    std::mem::replace(&mut entry.snapshot, current.clone())
+                };
+                let event = tauri::async_runtime::spawn_blocking(move || {
+                    change_event(&workspace, &previous, &current)
+                })
+                .await;
+                if let Ok(Some(event)) = event {
+                    let _ = app.emit(WORKSPACE_CHANGED_EVENT, event);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::super::workspace_git::{parse_porcelain_status, WorkspaceSnapshot};
+    use super::{change_event, changed_paths};
+
+    fn snapshot(head: &str, paths: &[(&str, &str)]) -> WorkspaceSnapshot {
+        WorkspaceSnapshot {
+            head: Some(head.to_string()),
+            branch: Some("feature".to_string()),
+            paths: paths
+                .iter()
+                .map(|(path, state)| (path.to_string(), state.to_string()))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn working_tree_changes_list_added_removed_and_edited_paths() {
+        let previous = snapshot(
+            "abc",
+            &[
+                ("src/lib.rs", " M 10 1"),
+                ("notes.md", "?? 4 1"),
+                ("tmp.txt", "?? 1 1"),
+            ],
+        );
+        let current = snapshot(
+            "abc",
+            &[
+                ("src/lib.rs", " M 12 2"),
+                ("notes.md", "?? 4 1"),
+                ("old.rs", " D missing"),
+            ],
+        );
+        assert_eq!(
+            changed_paths(&previous, &current),
+            ["old.rs", "src/lib.rs", "tmp.txt"]
+        );
+        assert!(change_event("/tmp/repo", &previous, &previous).is_none());
+
+        let event = change_event("/tmp/repo", &previous, &current).unwrap();
+        assert_eq!(event.changed_path_count, 3);
+        assert!(event.new_commits.is_empty());
+    }
+
+    #[test]
+    fn porcelain_status_reports_renames_under_their_new_path() {
+        let output = " M src/lib.rs\0R  src/new.rs\0src/old.rs\0?? notes.md\0";
+        assert_eq!(
+            parse_porcelain_status(output),
+            [
+                (" M".to_string(), "src/lib.rs".to_string()),
+                ("R ".to_string(), "src/new.rs".to_string()),
+                ("??".to_string(), "notes.md".to_string()),
+            ]
+        );
+    }
+}
@@ -12,7 +12,9 @@ CREATE TABLE IF NOT EXISTS threads (
   id INTEGER PRIMARY KEY AUTOINCREMENT,
   title TEXT NOT NULL,
   workspace TEXT,
-  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  pinned INTEGER NOT NULL DEFAULT 0,
+  archived_at TEXT
 );
 
 CREATE TABLE IF NOT EXISTS messages (
@@ -37,6 +39,12 @@ CREATE TABLE IF NOT EXISTS provider_connections (
   updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
 
+CREATE TABLE IF NOT EXISTS settings (
+  key TEXT PRIMARY KEY,
+  value_json TEXT NOT NULL,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
 CREATE TABLE IF NOT EXISTS ai_review_runs (
   run_id TEXT PRIMARY KEY,
   thread_id INTEGER NOT NULL,
@@ -67,6 +75,23 @@ CREATE TABLE IF NOT EXISTS ai_review_runs (
   started_at TEXT,
   ended_at TEXT,
   canceled_at TEXT,
+  resource_usage_json TEXT,
+  prompt_tokens INTEGER,
+  completion_tokens INTEGER,
+  estimated_cost_usd REAL,
+  review_mode TEXT,
+  incremental_base_head TEXT,
+  partial_review TEXT,
+  skipped_chunks_json TEXT,
+  profile_id INTEGER,
+  verdict TEXT,
+  verdict_reason TEXT,
+  suppressed_findings_json TEXT,
+  min_confidence REAL,
+  approval TEXT,
+  approval_note TEXT,
+  approval_updated_at TEXT,
+  suggested_follow_ups_json TEXT,
   FOREIGN KEY (thread_id) REFERENCES threads(id) ON DELETE CASCADE
 );
 
@@ -118,6 +143,242 @@ ON inline_review_comments(
   end_side,
   end_line_number
 );
+
+CREATE TABLE IF NOT EXISTS model_performance_stats (
+  transport TEXT NOT NULL,
+  model TEXT NOT NULL,
+  prompt_profile TEXT NOT NULL,
+  request_count INTEGER NOT NULL DEFAULT 0,
+  failure_count INTEGER NOT NULL DEFAULT 0,
+  prompt_tokens INTEGER NOT NULL DEFAULT 0,
+  completion_tokens INTEGER NOT NULL DEFAULT 0,
+  estimated_cost_usd REAL,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (transport, model, prompt_profile)
+);
+
+CREATE TABLE IF NOT EXISTS ai_review_findings (
+  run_id TEXT NOT NULL,
+  finding_id TEXT NOT NULL,
+  thread_id INTEGER NOT NULL,
+  workspace TEXT NOT NULL,
+  fingerprint TEXT NOT NULL,
+  status TEXT NOT NULL DEFAULT 'open',
+  category TEXT,
+  severity TEXT NOT NULL,
+  confidence REAL,
+  file_path TEXT NOT NULL,
+  side TEXT NOT NULL,
+  line_number INTEGER NOT NULL,
+  chunk_id TEXT NOT NULL,
+  chunk_index INTEGER NOT NULL,
+  hunk_header TEXT NOT NULL,
+  title TEXT NOT NULL,
+  body TEXT NOT NULL,
+  code_hash TEXT,
+  recurring INTEGER,
+  suggested_patch TEXT,
+  language TEXT,
+  created_at TEXT NOT NULL,
+  PRIMARY KEY (run_id, finding_id),
+  FOREIGN KEY (run_id) REFERENCES ai_review_runs(run_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_review_findings_created
+ON ai_review_findings(created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_ai_review_findings_workspace_created
+ON ai_review_findings(workspace, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_ai_review_findings_severity_created
+ON ai_review_findings(severity, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_ai_review_findings_category_created
+ON ai_review_findings(category, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_ai_review_findings_fingerprint
+ON ai_review_findings(workspace, fingerprint);
+
+CREATE INDEX IF NOT EXISTS idx_ai_review_findings_status_created
+ON ai_review_findings(status, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS review_finding_states (
+  workspace TEXT NOT NULL,
+  fingerprint TEXT NOT NULL,
+  state TEXT NOT NULL,
+  file_path TEXT NOT NULL,
+  title TEXT NOT NULL,
+  reason TEXT,
+  run_id TEXT,
+  finding_id TEXT,
+  fix_commit TEXT,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (workspace, fingerprint)
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_finding_states_state_updated
+ON review_finding_states(state, updated_at DESC);
+
+CREATE TABLE IF NOT EXISTS workspace_trust (
+  workspace TEXT PRIMARY KEY,
+  trust_level TEXT NOT NULL,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS code_intel_configs (
+  workspace TEXT PRIMARY KEY,
+  options_json TEXT NOT NULL,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS run_snapshots (
+  run_id TEXT PRIMARY KEY,
+  workspace TEXT NOT NULL,
+  head TEXT NOT NULL,
+  path TEXT NOT NULL,
+  opened_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_run_snapshots_workspace_opened
+ON run_snapshots(workspace, opened_at DESC);
+
+CREATE TABLE IF NOT EXISTS review_git_hooks (
+  workspace TEXT NOT NULL,
+  hook TEXT NOT NULL,
+  fail_on TEXT NOT NULL,
+  path TEXT NOT NULL,
+  installed_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (workspace, hook)
+);
+
+CREATE TABLE IF NOT EXISTS review_schedules (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  workspace TEXT NOT NULL,
+  thread_id INTEGER NOT NULL,
+  base_ref TEXT,
+  branch TEXT,
+  interval_minutes INTEGER,
+  on_new_commits INTEGER NOT NULL DEFAULT 0,
+  prompt TEXT,
+  enabled INTEGER NOT NULL DEFAULT 1,
+  last_seen_head TEXT,
+  last_run_id TEXT,
+  last_run_at TEXT,
+  last_error TEXT,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_schedules_workspace
+ON review_schedules(workspace);
+
+CREATE TABLE IF NOT EXISTS reviewer_profiles (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+  system_prompt TEXT NOT NULL,
+  min_severity TEXT,
+  focus_areas_json TEXT NOT NULL DEFAULT '[]',
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS review_groups (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  title TEXT NOT NULL,
+  prompt TEXT,
+  status TEXT NOT NULL DEFAULT 'running',
+  summary TEXT,
+  model TEXT,
+  error TEXT,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  ended_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_groups_status
+ON review_groups(status);
+
+CREATE TABLE IF NOT EXISTS review_group_runs (
+  group_id INTEGER NOT NULL,
+  run_id TEXT NOT NULL,
+  position INTEGER NOT NULL,
+  workspace TEXT NOT NULL,
+  head_ref TEXT,
+  diff TEXT NOT NULL,
+  diff_truncated INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (group_id, run_id),
+  FOREIGN KEY (group_id) REFERENCES review_groups(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_group_runs_workspace
+ON review_group_runs(workspace);
+
+CREATE TABLE IF NOT EXISTS message_attachments (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  message_id INTEGER NOT NULL,
+  thread_id INTEGER NOT NULL,
+  name TEXT NOT NULL,
+  mime_type TEXT NOT NULL,
+  size_bytes INTEGER NOT NULL,
+  content_hash TEXT NOT NULL,
+  content TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  UNIQUE (message_id, content_hash),
+  FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_message_attachments_thread
+ON message_attachments(thread_id, message_id);
+
+CREATE TABLE IF NOT EXISTS pull_request_comments (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  thread_id INTEGER NOT NULL,
+  message_id INTEGER NOT NULL,
+  provider TEXT NOT NULL,
+  repository TEXT NOT NULL,
+  pull_request_number INTEGER NOT NULL,
+  external_id TEXT NOT NULL,
+  author TEXT NOT NULL,
+  body TEXT NOT NULL,
+  file_path TEXT,
+  line_number INTEGER,
+  url TEXT,
+  commented_at TEXT NOT NULL,
+  imported_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  UNIQUE (thread_id, provider, repository, external_id),
+  FOREIGN KEY (thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS thread_diff_chunks (
+  thread_id INTEGER NOT NULL,
+  chunk_id TEXT NOT NULL,
+  run_id TEXT,
+  file_path TEXT NOT NULL,
+  previous_path TEXT,
+  patch TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (thread_id, chunk_id),
+  FOREIGN KEY (thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS thread_settings (
+  thread_id INTEGER PRIMARY KEY,
+  base_ref TEXT,
+  profile_id INTEGER,
+  last_run_id TEXT,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS workspace_sync_status (
+  workspace TEXT PRIMARY KEY,
+  branch TEXT,
+  upstream TEXT,
+  base_ref TEXT,
+  stale_refs_json TEXT NOT NULL DEFAULT '[]',
+  behind_upstream INTEGER NOT NULL DEFAULT 0,
+  error TEXT,
+  checked_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
 "#;
 
 pub async fn open_database_from_env() -> Result<(String, Database), String> {
@@ -181,6 +442,185 @@ pub async fn initialize_schema(db: &Database) -> Result<(), String> {
         .await
         .map_err(|error| format!("Failed to initialize schema: {error}"))?;
     ensure_inline_comment_range_columns(&conn).await?;
+    ensure_added_columns(&conn, "threads", THREAD_ADDED_COLUMNS).await?;
+    ensure_added_columns(&conn, "messages", MESSAGE_ADDED_COLUMNS).await?;
+    ensure_added_columns(&conn, "ai_review_runs", AI_REVIEW_RUN_ADDED_COLUMNS).await?;
+    ensure_added_columns(&conn, "ai_review_findings", AI_REVIEW_FINDING_ADDED_COLUMNS).await?;
+    ensure_added_columns(
+        &conn,
+        "review_finding_states",
+        REVIEW_FINDING_STATE_ADDED_COLUMNS,
+    )
+    .await?;
+    ensure_added_columns(&conn, "review_schedules", REVIEW_SCHEDULE_ADDED_COLUMNS).await?;
+    conn.execute_batch(IDEMPOTENCY_KEY_INDEXES_SQL)
+        .await
+        .map_err(|error| format!("Failed to create idempotency key indexes: {error}"))?;
+    ensure_findings_search_index(&conn).await?;
+
+    Ok(())
+}
+
+/// Columns added to `threads` after the table was first created.
+const THREAD_ADDED_COLUMNS: &[(&str, &str)] = &[
+    ("pinned", "INTEGER NOT NULL DEFAULT 0"),
+    ("archived_at", "TEXT"),
+    ("idempotency_key", "TEXT"),
+];
+
+/// Columns added to `messages` after the table was first created.
+const MESSAGE_ADDED_COLUMNS: &[(&str, &str)] = &[("idempotency_key", "TEXT")];
+
+/// Columns added to `ai_review_runs` after the table was first created.
+const AI_REVIEW_RUN_ADDED_COLUMNS: &[(&str, &str)] = &[
+    ("resource_usage_json", "TEXT"),
+    ("prompt_tokens", "INTEGER"),
+    ("completion_tokens", "INTEGER"),
+    ("estimated_cost_usd", "REAL"),
+    ("review_mode", "TEXT"),
+    ("incremental_base_head", "TEXT"),
+    ("partial_review", "TEXT"),
+    ("idempotency_key", "TEXT"),
+    ("skipped_chunks_json", "TEXT"),
+    ("profile_id", "INTEGER"),
+    ("verdict", "TEXT"),
+    ("verdict_reason", "TEXT"),
+    ("suppressed_findings_json", "TEXT"),
+    ("min_confidence", "REAL"),
+    ("approval", "TEXT"),
+    ("approval_note", "TEXT"),
+    ("approval_updated_at", "TEXT"),
+    ("suggested_follow_ups_json", "TEXT"),
+];
+
+/// Client-supplied idempotency keys are unique, so a retried create returns the row the
+/// first attempt made. Created after the columns, which older databases gain on startup.
+const IDEMPOTENCY_KEY_INDEXES_SQL: &str = r#"
+CREATE UNIQUE INDEX IF NOT EXISTS idx_threads_idempotency_key
+ON threads(idempotency_key) WHERE idempotency_key IS NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_messages_idempotency_key
+ON messages(thread_id, idempotency_key) WHERE idempotency_key IS NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ai_review_runs_idempotency_key
+ON ai_review_runs(thread_id, idempotency_key) WHERE idempotency_key IS NOT NULL;
+"#;
+
+/// Full-text index over finding titles, bodies and paths for `search_findings`. Trigrams
+/// match any substring of three or more characters, as the `LIKE '%q%'` scan it replaces
+/// did. Its rowids are those of `ai_review_findings`, kept in sync by the triggers.
+const FINDINGS_SEARCH_SQL: &str = r#"
+CREATE VIRTUAL TABLE IF NOT EXISTS ai_review_findings_search USING fts5(
+  title,
+  body,
+  file_path,
+  content = 'ai_review_findings',
+  tokenize = 'trigram'
+);
+
+CREATE TRIGGER IF NOT EXISTS ai_review_findings_search_insert
+AFTER INSERT ON ai_review_findings BEGIN
+  INSERT INTO ai_review_findings_search (rowid, title, body, file_path)
+  VALUES (new.rowid, new.title, new.body, new.file_path);
+END;
+
+CREATE TRIGGER IF NOT EXISTS ai_review_findings_search_delete
+AFTER DELETE ON ai_review_findings BEGIN
+  INSERT INTO ai_review_findings_search (ai_review_findings_search, rowid, title, body, file_path)
+  VALUES ('delete', old.rowid, old.title, old.body, old.file_path);
+END;
+
+CREATE TRIGGER IF NOT EXISTS ai_review_findings_search_update
+AFTER UPDATE OF title, body, file_path ON ai_review_findings BEGIN
+  INSERT INTO ai_review_findings_search (ai_review_findings_search, rowid, title, body, file_path)
+  VALUES ('delete', old.rowid, old.title, old.body, old.file_path);
+  INSERT INTO ai_review_findings_search (rowid, title, body, file_path)
+  VALUES (new.rowid, new.title, new.body, new.file_path);
+END;
+"#;
+
+/// Creates the finding search index, indexing the findings stored before it existed.
+async fn ensure_findings_search_index(conn: &libsql::Connection) -> Result<(), String> {
+    let mut rows = conn
+        .query(
+            "SELECT 1 FROM sqlite_master WHERE name = 'ai_review_findings_search'",
+            (),
+        )
+        .await
+        .map_err(|error| format!("Failed to look up the finding search index: {error}"))?;
+    let exists = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to look up the finding search index: {error}"))?
+        .is_some();
+    conn.execute_batch(FINDINGS_SEARCH_SQL)
+        .await
+        .map_err(|error| format!("Failed to create the finding search index: {error}"))?;
+    if !exists {
+        rebuild_findings_search_index(conn).await?;
+    }
+    Ok(())
+}
+
+/// Reindexes every finding. Needed after `VACUUM`, which may renumber the rowids the index
+/// refers to.
+pub(crate) async fn rebuild_findings_search_index(conn: &libsql::Connection) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO ai_review_findings_search (ai_review_findings_search) VALUES ('rebuild')",
+        (),
+    )
+    .await
+    .map_err(|error| format!("Failed to rebuild the finding search index: {error}"))?;
+    Ok(())
+}
+
+/// Columns added to `ai_review_findings` after the table was first created.
+const AI_REVIEW_FINDING_ADDED_COLUMNS: &[(&str, &str)] = &[
+    ("code_hash", "TEXT"),
+    ("recurring", "INTEGER"),
+    ("suggested_patch", "TEXT"),
+    ("language", "TEXT"),
+];
+
+/// Columns added to `review_schedules` after the table was first created.
+const REVIEW_SCHEDULE_ADDED_COLUMNS: &[(&str, &str)] = &[("remote", "TEXT")];
+
+/// Columns added to `review_finding_states` after the table was first created.
+const REVIEW_FINDING_STATE_ADDED_COLUMNS: &[(&str, &str)] = &[("fix_commit", "TEXT")];
+
+async fn ensure_added_columns(
+    conn: &libsql::Connection,
+    table: &str,
+    columns: &[(&str, &str)],
+) -> Result<(), String> {
+    let mut rows = conn
+        .query(&format!("PRAGMA table_info({table})"), ())
+        .await
+        .map_err(|error| format!("Failed to inspect {table} schema: {error}"))?;
+
+    let mut existing_columns = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|error| format!("Failed to read {table} schema rows: {error}"))?
+    {
+        let name: String = row
+            .get(1)
+            .map_err(|error| format!("Failed to parse {table} column name: {error}"))?;
+        existing_columns.push(name);
+    }
+
+    for (column, column_type) in columns {
+        if existing_columns.iter().any(|name| name == column) {
+            continue;
+        }
+        conn.execute(
+            &format!("ALTER TABLE {table} ADD COLUMN {column} {column_type}"),
+            (),
+        )
+        .await
+        .map_err(|error| format!("Failed to migrate {table}.{column}: {error}"))?;
+    }
 
     Ok(())
 }
@@ -189,9 +629,7 @@ async fn ensure_inline_comment_range_columns(conn: &libsql::Connection) -> Resul
     let mut rows = conn
         .query("PRAGMA table_info(inline_review_comments)", ())
         .await
-        .map_err(|error| {
-            format!("Failed to inspect inline_review_comments schema: {error}")
-        })?;
+        .map_err(|error| format!("Failed to inspect inline_review_comments schema: {error}"))?;
 
     let mut has_end_side = false;
     let mut has_end_line_number = false;
@@ -200,9 +638,9 @@ async fn ensure_inline_comment_range_columns(conn: &libsql::Connection) -> Resul
         .await
         .map_err(|error| format!("Failed to read inline_review_comments schema rows: {error}"))?
     {
-        let name: String = row
-            .get(1)
-            .map_err(|error| format!("Failed to parse inline_review_comments column name: {error}"))?;
+        let name: String = row.get(1).map_err(|error| {
+            format!("Failed to parse inline_review_comments column name: {error}")
+        })?;
         if name == "end_side" {
             has_end_side = true;
         } else if name == "end_line_number" {
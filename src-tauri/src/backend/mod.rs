@@ -1,28 +1,77 @@
-#[path = "code-intel.rs"]
+#[cfg_attr(feature = "code-intel", path = "code-intel.rs")]
+#[cfg_attr(not(feature = "code-intel"), path = "code-intel-disabled.rs")]
 mod code_intel;
 pub mod commands;
 mod db;
 mod models;
+mod paths;
 mod providers;
+mod secrets;
+mod settings;
 
 pub use models::{
-    AddThreadMessageInput, AiReviewChunk, AiReviewConfig, AiReviewFinding, AiReviewProgressEvent,
-    AiReviewRun, AppServerAccountStatus, AppServerCredits, AppServerLoginStartResult,
-    AppServerModel, AppServerRateLimitWindow, AppServerRateLimits, BackendHealth,
-    CancelAiReviewRunInput, CancelAiReviewRunResult, CheckoutWorkspaceBranchInput,
-    CheckoutWorkspaceBranchResult, CloneRepositoryInput, CloneRepositoryResult, CodeIntelSyncInput,
-    CodeIntelSyncResult, CompareWorkspaceDiffInput, CompareWorkspaceDiffProfile,
-    CompareWorkspaceDiffResult, ConnectProviderInput, CreateInlineReviewCommentInput,
-    CreateThreadInput,
-    CreateWorkspaceBranchInput, GenerateAiFollowUpInput, GenerateAiFollowUpResult,
-    GenerateAiReviewInput, GenerateAiReviewResult, GetAiReviewRunInput, ListAiReviewRunsInput,
-    ListAiReviewRunsResult, ListInlineReviewCommentsInput, ListInlineReviewCommentsResult,
-    ListWorkspaceBranchesInput, ListWorkspaceBranchesResult, Message, MessageRole,
-    OpenFileInEditorInput, OpencodeSidecarStatus, PollProviderDeviceAuthInput,
+    AddMessageAttachmentInput, AddThreadMessageInput, AiReviewChunk, AiReviewConfig,
+    AiReviewFinding, AiReviewFindingGroup, AiReviewProgressEvent, AiReviewResourceUsage,
+    AiReviewRun, AiReviewSkipReason, AiReviewSkippedChunk, AiReviewTokenUsage,
+    AppServerAccountStatus, AppServerCredits, AppServerLoginStartResult, AppServerModel,
+    AppServerRateLimitWindow, AppServerRateLimits, ApplyFindingFixInput, ApplyFindingFixResult,
+    ArchiveThreadInput, BackendHealth, BootstrapDemoWorkspaceInput, BootstrapDemoWorkspaceResult,
+    BranchSwitchStash, CancelAiRequestInput, CancelAiRequestResult, CancelAiReviewRunInput,
+    CancelAiReviewRunResult, CheckoutWorkspaceBranchInput, CheckoutWorkspaceBranchResult,
+    CloneRepositoryInput, CloneRepositoryResult, CodeIntelOptions, CodeIntelSyncInput,
+    CodeIntelSyncResult, CodeIntelSyncStatus, CodeIntelSyncStatusInput, CodeIntelWorkspaceConfig,
+    CommitDetail, CommitFileChange, CommitWorkspaceChangesInput, CommitWorkspaceChangesResult,
+    CompareAiReviewRunsInput, CompareAiReviewRunsResult, CompareWorkspaceDiffInput,
+    CompareWorkspaceDiffProfile, CompareWorkspaceDiffResult, ConnectProviderInput,
+    CreateInlineReviewCommentInput, CreateReviewerProfileInput, CreateThreadInput,
+    CreateWorkspaceBranchInput, DatabaseMaintenanceInput, DatabaseMaintenanceResult, DatabaseStats,
+    DeleteReviewScheduleInput, DeleteReviewScheduleResult, DeleteReviewerProfileInput,
+    DeleteReviewerProfileResult, DiffFile, DiffFileStatus, DiffHunk, ExportAiReviewRunInput,
+    ExportAiReviewRunResult, ExportReviewReportInput, ExportReviewReportResult, ExportThreadInput,
+    ExportThreadResult, ExportWorkspaceDataInput, ExportWorkspaceDataResult,
+    FetchWorkspaceRemoteInput, FetchWorkspaceRemoteResult, FindingAction, FindingBlame,
+    FindingSearchHit, FollowUpToolCall, GenerateAiFollowUpInput, GenerateAiFollowUpResult,
+    GenerateAiReviewInput, GenerateAiReviewResult, GenerateCommitMessageInput,
+    GenerateCommitMessageResult, GetAiReviewRunInput, GetCodeIntelConfigInput,
+    GetCommitDetailInput, GetLineBlameInput, GetLineBlameResult, GetModelPerformanceStatsInput,
+    GetModelPerformanceStatsResult, GetReviewQueueStatusResult, GetReviewUsageSummaryInput,
+    GetReviewUsageSummaryResult, GetSymbolDefinitionResult, GetTeamReviewStatsInput,
+    GetTeamReviewStatsResult, GetThreadSettingsInput, GetWorkspaceTrustInput,
+    ImportPullRequestCommentsInput, ImportPullRequestCommentsResult, ImportThreadInput,
+    ImportThreadResult, InlineReviewComment, InstallReviewGitHookInput, LineBlame,
+    ListAiReviewRunsInput, ListAiReviewRunsResult, ListFindingActionsResult,
+    ListFindingStatesInput, ListFindingStatesResult, ListInlineReviewCommentsInput,
+    ListInlineReviewCommentsResult, ListMessageAttachmentsInput, ListMessageAttachmentsResult,
+    ListPullRequestCommentsInput, ListPullRequestCommentsResult, ListReviewGroupsInput,
+    ListReviewGroupsResult, ListReviewSchedulesInput, ListReviewSchedulesResult,
+    ListReviewerProfilesResult, ListRunSnapshotsInput, ListRunSnapshotsResult,
+    ListStaleWorkspacesInput, ListStaleWorkspacesResult, ListSymbolReferencesResult,
+    ListThreadMessagesInput, ListThreadMessagesResult, ListWorkspaceBranchesInput,
+    ListWorkspaceBranchesResult, ListWorkspaceCommitsInput, ListWorkspaceCommitsResult,
+    ListWorkspaceFilesInput, ListWorkspaceFilesResult, Message, MessageAttachment,
+    MessageAttachmentInput, MessageRole, MigrateRepositoriesDirInput, MigrateRepositoriesDirResult,
+    ModelPerformanceStat, NameSortOptions, OpenFileInEditorInput, OpencodeSidecarStatus,
+    OrphanedRows, PersistingAiReviewFinding, PinThreadInput, PollProviderDeviceAuthInput,
     PollProviderDeviceAuthResult, ProviderConnection, ProviderDeviceAuthStatus, ProviderKind,
-    SetAiReviewApiKeyInput, SetAiReviewSettingsInput, StartAiReviewRunInput,
-    StartAiReviewRunResult, StartProviderDeviceAuthInput, StartProviderDeviceAuthResult, Thread,
-    WorkspaceBranch, InlineReviewComment,
+    PublishReviewCommitStatusInput, PublishReviewCommitStatusResult, PullRequestComment,
+    PullWorkspaceBranchInput, PullWorkspaceBranchResult, PurgeWorkspaceDataInput,
+    PurgeWorkspaceDataResult, PurgedWorkspaceTable, PushWorkspaceBranchInput,
+    PushWorkspaceBranchResult, ReadWorkspaceFileInput, ReadWorkspaceFileResult,
+    RemoveRunSnapshotResult, RenameThreadInput, RepositoriesDirResult, ReviewFindingState,
+    ReviewGitHook, ReviewGroup, ReviewGroupInput, ReviewGroupRun, ReviewQueueEntry, ReviewSchedule,
+    ReviewUsageMonth, ReviewerProfile, RunFindingActionInput, RunFindingActionResult, RunSnapshot,
+    RunSnapshotInput, SaveReviewScheduleInput, SearchFindingsInput, SearchFindingsResult,
+    SetAiReviewApiKeyInput, SetAiReviewRunApprovalInput, SetAiReviewSettingsInput,
+    SetCodeIntelConfigInput, SetFindingActionsInput, SetThreadSettingsInput,
+    SetWorkspaceTrustInput, SignalUiActivityInput, StartAiReviewRunInput, StartAiReviewRunResult,
+    StartProviderDeviceAuthInput, StartProviderDeviceAuthResult, StartReviewGroupInput,
+    SuggestThreadTitleInput, SuggestThreadTitleResult, SymbolLookupInput, TeamReviewWeekStats,
+    TeamSeverityMix, Thread, ThreadSettings, UninstallReviewGitHookInput,
+    UninstallReviewGitHookResult, UnshallowWorkspaceInput, UnshallowWorkspaceResult,
+    UpdateFindingStateInput, UpdateReviewerProfileInput, WatchWorkspaceInput, WorkspaceBranch,
+    WorkspaceChangedEvent, WorkspaceCommit, WorkspaceDiffTarget, WorkspaceFetchProgressEvent,
+    WorkspaceFileEntry, WorkspaceRemote, WorkspaceStatus, WorkspaceStatusInput,
+    WorkspaceSubmoduleChange, WorkspaceSyncStatus, WorkspaceTrust, WorkspaceWatch,
 };
 
 use libsql::{Connection, Database};
@@ -36,17 +85,60 @@ impl AppState {
     pub async fn initialize() -> Result<Self, String> {
         let (database_url, db) = db::open_database_from_env().await?;
         db::initialize_schema(&db).await?;
+        Self::load_settings(&db, true).await?;
 
-        Ok(Self { db, database_url })
+        let state = Self { db, database_url };
+        commands::reconcile_review_runs(&state, true).await;
+        if let Err(error) = commands::backfill_ai_review_findings(&state).await {
+            eprintln!("[backend] Failed to index existing review findings: {error}");
+        }
+        Ok(state)
     }
 
     pub async fn initialize_local_fallback() -> Result<Self, String> {
         let (database_url, db) = db::open_local_database().await?;
         db::initialize_schema(&db).await?;
+        Self::load_settings(&db, true).await?;
+
+        let state = Self { db, database_url };
+        commands::reconcile_review_runs(&state, true).await;
+        if let Err(error) = commands::backfill_ai_review_findings(&state).await {
+            eprintln!("[backend] Failed to index existing review findings: {error}");
+        }
+        Ok(state)
+    }
 
+    /// Opens the app database for the headless CLI. Review runs are not reconciled, since
+    /// queued or running rows may belong to a desktop app that is still open.
+    pub(crate) async fn initialize_headless() -> Result<Self, String> {
+        let (database_url, db) = match db::open_database_from_env().await {
+            Ok(opened) => opened,
+            Err(error) => {
+                eprintln!("[backend] Failed to initialize Turso from env: {error}");
+                db::open_local_database().await?
+            }
+        };
+        db::initialize_schema(&db).await?;
+        Self::load_settings(&db, false).await?;
         Ok(Self { db, database_url })
     }
 
+    /// Loads the settings cache. The desktop app also moves plaintext secrets into the
+    /// keychain; the CLI leaves them where they are.
+    async fn load_settings(db: &Database, migrate_secrets: bool) -> Result<(), String> {
+        let conn = db
+            .connect()
+            .map_err(|error| format!("Failed to open database connection: {error}"))?;
+        settings::settings_store().reload(&conn).await?;
+        if !migrate_secrets {
+            return Ok(());
+        }
+        if let Err(error) = secrets::migrate_plaintext_secrets(&conn).await {
+            eprintln!("[backend] Failed to migrate secrets to keychain: {error}");
+        }
+        Ok(())
+    }
+
     pub fn connection(&self) -> Result<Connection, String> {
         self.db
             .connect()
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize)]
@@ -13,6 +15,8 @@ pub struct BackendHealth {
 pub struct CreateThreadInput {
     pub title: String,
     pub workspace: Option<String>,
+    /// Client-chosen key; creating a thread again with the same key returns the first one.
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -22,6 +26,29 @@ pub struct Thread {
     pub title: String,
     pub workspace: Option<String>,
     pub created_at: String,
+    pub pinned: bool,
+    pub archived_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameThreadInput {
+    pub thread_id: i64,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveThreadInput {
+    pub thread_id: i64,
+    pub archived: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinThreadInput {
+    pub thread_id: i64,
+    pub pinned: Option<bool>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
@@ -48,6 +75,9 @@ pub struct AddThreadMessageInput {
     pub thread_id: i64,
     pub role: MessageRole,
     pub content: String,
+    /// Client-chosen key, unique within the thread; adding a message again with the same key
+    /// returns the first one.
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -60,13 +90,80 @@ pub struct Message {
     pub created_at: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListThreadMessagesInput {
+    pub thread_id: i64,
+    pub limit: Option<u32>,
+    pub before_id: Option<i64>,
+    pub after_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListThreadMessagesResult {
+    pub messages: Vec<Message>,
+    pub total_count: i64,
+    pub has_more: bool,
+}
+
+/// Code-intel settings that can be given per sync or saved per workspace. Unset fields fall
+/// back to the saved workspace config, then to the `CODE_INTEL_*` environment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeIntelOptions {
+    /// Directories or files to index, relative to the project root.
+    pub paths: Option<Vec<String>>,
+    pub include: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+    pub respect_gitignore: Option<bool>,
+    pub use_scip: Option<bool>,
+    pub kitedb_store_path: Option<String>,
+    pub vector_base_url: Option<String>,
+    pub vector_model: Option<String>,
+    pub vector_dimension: Option<usize>,
+    pub vector_timeout_ms: Option<u64>,
+    pub vector_batch_size: Option<usize>,
+    pub vector_concurrency: Option<usize>,
+}
+
 #[derive(Debug, Clone, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CodeIntelSyncInput {
     pub project_root: Option<String>,
-    pub use_scip: Option<bool>,
+    /// Syncs the workspace of this thread when `project_root` is not given.
+    pub thread_id: Option<i64>,
     pub clear_kitedb: Option<bool>,
     pub clear_turso_project: Option<bool>,
+    /// Only index files whose content changed since the last sync. Defaults to
+    /// `CODE_INTEL_INCREMENTAL`, which is on unless set to false.
+    pub incremental: Option<bool>,
+    #[serde(flatten)]
+    pub options: CodeIntelOptions,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCodeIntelConfigInput {
+    pub workspace: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCodeIntelConfigInput {
+    pub workspace: String,
+    /// Replaces the saved config; unset fields fall back to the environment.
+    #[serde(flatten)]
+    pub options: CodeIntelOptions,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeIntelWorkspaceConfig {
+    pub workspace: String,
+    #[serde(flatten)]
+    pub options: CodeIntelOptions,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -75,12 +172,83 @@ pub struct CodeIntelSyncResult {
     pub run_id: String,
     pub project_root: String,
     pub kitedb_store_path: String,
+    /// Whether only changed files were indexed. The first sync of a project is always full.
+    pub incremental: bool,
     pub syntax_nodes_upserted: u64,
     pub semantic_nodes_upserted: u64,
     pub vectors_upserted: u64,
     pub files_parsed: u64,
     pub files_skipped: u64,
     pub chunks_emitted: u64,
+    pub files_unchanged: u64,
+    pub files_removed: u64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeIntelSyncStatusInput {
+    pub project_root: Option<String>,
+    pub thread_id: Option<i64>,
+    pub respect_gitignore: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeIntelSyncStatus {
+    pub project_root: String,
+    pub last_run_id: Option<String>,
+    pub last_synced_at: Option<String>,
+    /// True when the project was never synced or any file changed since its last sync.
+    pub stale: bool,
+    pub files_synced: u64,
+    pub files_unchanged: u64,
+    pub files_added: u64,
+    pub files_modified: u64,
+    pub files_removed: u64,
+    /// Added, modified and removed paths, relative to the project root; capped at 100.
+    pub stale_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(feature = "code-intel"), allow(dead_code))]
+pub struct SymbolLookupInput {
+    pub workspace: String,
+    /// Symbol to look up. When absent, the symbol declared around `file_path` and `line`.
+    pub symbol: Option<String>,
+    pub file_path: Option<String>,
+    pub line: Option<i64>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeSymbolLocation {
+    pub symbol_name: String,
+    pub node_kind: String,
+    /// `syntax` or `semantic`.
+    pub graph_layer: String,
+    /// Relative to the workspace when the node lies inside it.
+    pub file_path: String,
+    /// Lines as recorded by the indexer.
+    pub start_line: Option<i64>,
+    pub end_line: Option<i64>,
+    pub scip_symbol: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSymbolDefinitionResult {
+    /// `None` when no named node covers the requested position.
+    pub symbol: Option<String>,
+    pub definitions: Vec<CodeSymbolLocation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSymbolReferencesResult {
+    pub symbol: Option<String>,
+    pub references: Vec<CodeSymbolLocation>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
@@ -172,6 +340,9 @@ pub struct CloneRepositoryInput {
     pub destination_root: Option<String>,
     pub directory_name: Option<String>,
     pub shallow: Option<bool>,
+    /// Also clones submodules; shallow clones keep them shallow too.
+    #[serde(default)]
+    pub recurse_submodules: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -182,12 +353,127 @@ pub struct CloneRepositoryResult {
     pub workspace: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoriesDirResult {
+    pub repositories_dir: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrateRepositoriesDirInput {
+    pub destination: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrateRepositoriesDirResult {
+    pub previous_dir: String,
+    pub repositories_dir: String,
+    pub moved_repositories: Vec<String>,
+    pub updated_threads: u64,
+    pub updated_review_runs: u64,
+    pub updated_inline_comments: u64,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CompareWorkspaceDiffInput {
     pub workspace: String,
     pub base_ref: Option<String>,
     pub fetch_remote: Option<bool>,
+    pub include_paths: Option<Vec<String>>,
+    pub exclude_paths: Option<Vec<String>>,
+    pub diff_target: Option<WorkspaceDiffTarget>,
+    pub include_untracked: Option<bool>,
+    /// Commit-ish to review instead of HEAD, such as `origin/feature`. Branch target only.
+    #[serde(default)]
+    pub head_ref: Option<String>,
+    /// Adds each changed submodule's own diff when its checkout is a registered workspace.
+    #[serde(default)]
+    pub include_submodule_diffs: Option<bool>,
+    /// Fetches and recomputes even when nothing changed since the last identical comparison.
+    #[serde(default)]
+    pub force: Option<bool>,
+    /// Remote to fetch before comparing; defaults to the remote the base ref belongs to.
+    #[serde(default)]
+    pub remote: Option<String>,
+}
+
+/// A submodule whose recorded commit changed, which the patch shows only as a
+/// `Subproject commit` line.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceSubmoduleChange {
+    pub path: String,
+    /// `added`, `removed` or `modified`.
+    pub status: String,
+    pub old_commit: Option<String>,
+    pub new_commit: Option<String>,
+    /// The registered workspace the submodule is checked out as, when
+    /// `includeSubmoduleDiffs` is set.
+    pub workspace: Option<String>,
+    /// Changes between the two commits inside the submodule, when `workspace` is set and has
+    /// both commits.
+    pub diff: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiffFileStatus {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Copied,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    pub header: String,
+    pub old_start: i64,
+    pub old_lines: i64,
+    pub new_start: i64,
+    pub new_lines: i64,
+    /// New-side line numbers of added lines, numbered the same way as review findings.
+    pub addition_lines: Vec<i64>,
+    /// Old-side line numbers of removed lines.
+    pub deletion_lines: Vec<i64>,
+}
+
+/// One file of a comparison's diff, parsed once so the UI does not have to parse the patch.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffFile {
+    pub path: String,
+    /// The path before a rename or copy.
+    pub previous_path: Option<String>,
+    pub status: DiffFileStatus,
+    /// How similar a renamed or copied file is to its original, in percent.
+    pub similarity: Option<u8>,
+    /// Git reported the file as binary, so it has no hunks.
+    pub binary: bool,
+    /// Language id detected from the path and the changed lines.
+    pub language: Option<&'static str>,
+    pub insertions: i64,
+    pub deletions: i64,
+    pub hunks: Vec<DiffHunk>,
+    /// The file does not fit whole in a truncated `diff`, which shows only part or none of it.
+    pub truncated: bool,
+    /// The file's headers and hunks, which review chunks are cut from. Left out of the
+    /// payload, since the UI has the same text in `diff`.
+    #[serde(skip)]
+    pub patch: Arc<str>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum WorkspaceDiffTarget {
+    #[default]
+    Branch,
+    WorkingTree,
+    Staged,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -210,13 +496,203 @@ pub struct CompareWorkspaceDiffResult {
     pub merge_base: String,
     pub head: String,
     pub diff: String,
+    /// Every changed file, including the ones a truncated `diff` cuts off.
+    pub files: Vec<DiffFile>,
     pub files_changed: i64,
     pub insertions: i64,
     pub deletions: i64,
     pub diff_truncated: bool,
     pub diff_bytes_used: usize,
     pub diff_bytes_total: usize,
+    pub diff_target: WorkspaceDiffTarget,
     pub profile: CompareWorkspaceDiffProfile,
+    /// The workspace's `rovex.toml` or `.rovex/config.toml`, when one shaped this comparison.
+    pub repo_config_path: Option<String>,
+    /// Commits of history fetched because the merge base lay beyond a shallow clone.
+    pub history_deepened_by: Option<u32>,
+    pub submodules: Vec<WorkspaceSubmoduleChange>,
+    /// Changed files a review of this diff leaves out: binary, minified, too large, or
+    /// without text changes.
+    pub skipped_files: Vec<AiReviewSkippedChunk>,
+    /// Returned from the previous identical comparison because neither HEAD, the base ref
+    /// nor the working tree moved since.
+    pub cached: bool,
+    /// When the workspace last fetched from any remote, in milliseconds since the Unix epoch,
+    /// so a comparison with `fetchRemote: false` can show how fresh its remote refs are.
+    pub last_fetched_at: Option<u64>,
+    /// The requested remote, or the one the base ref belongs to; `None` for a local base ref.
+    pub remote: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWorkspaceFilesInput {
+    pub workspace: String,
+    /// Directory to list, relative to the workspace. Defaults to the workspace root.
+    pub path: Option<String>,
+    /// Lists the whole subtree instead of only the directory's direct entries.
+    pub recursive: Option<bool>,
+    /// Skips files ignored by `.gitignore`/`.ignore`. Defaults to true.
+    pub respect_gitignore: Option<bool>,
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceFileEntry {
+    /// `/`-separated path relative to the workspace.
+    pub path: String,
+    pub name: String,
+    /// `file`, `directory` or `symlink`.
+    pub kind: String,
+    pub size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWorkspaceFilesResult {
+    pub workspace: String,
+    pub path: String,
+    pub entries: Vec<WorkspaceFileEntry>,
+    pub total_count: usize,
+    pub has_more: bool,
+    /// The walk stopped early on a very large tree, so `total_count` is a lower bound.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadWorkspaceFileInput {
+    pub workspace: String,
+    pub path: String,
+    /// First line to return, 1-based. Defaults to 1.
+    pub start_line: Option<usize>,
+    /// Last line to return, inclusive. At most 2000 lines are returned per call.
+    pub end_line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadWorkspaceFileResult {
+    pub path: String,
+    /// The requested lines with their original line endings.
+    pub content: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub total_lines: usize,
+    /// The range was cut short at the per-call line limit.
+    pub truncated: bool,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLineBlameInput {
+    pub workspace: String,
+    pub file_path: String,
+    pub start_line: usize,
+    /// Last line to blame, inclusive. Defaults to `start_line`; at most 500 lines per call.
+    pub end_line: Option<usize>,
+    /// Commit to blame at. Defaults to the working tree.
+    pub rev: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineBlame {
+    pub line_number: usize,
+    /// `None` for lines that are not committed yet.
+    pub commit: Option<String>,
+    pub author: Option<String>,
+    pub author_email: Option<String>,
+    /// Author date in ISO 8601.
+    pub authored_at: Option<String>,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLineBlameResult {
+    pub file_path: String,
+    pub rev: Option<String>,
+    pub lines: Vec<LineBlame>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWorkspaceCommitsInput {
+    pub workspace: String,
+    /// Revision or range to list, such as `main..HEAD`. Defaults to `HEAD`.
+    pub range: Option<String>,
+    /// Matches author names and emails, ignoring case.
+    pub author: Option<String>,
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub parents: Vec<String>,
+    pub author: String,
+    pub author_email: String,
+    /// Author date in ISO 8601.
+    pub authored_at: String,
+    pub subject: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWorkspaceCommitsResult {
+    pub workspace: String,
+    pub range: String,
+    pub commits: Vec<WorkspaceCommit>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCommitDetailInput {
+    pub workspace: String,
+    pub commit: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitFileChange {
+    pub path: String,
+    /// Path before a rename or copy.
+    pub previous_path: Option<String>,
+    /// `added`, `modified`, `deleted`, `renamed`, `copied` or `type-changed`.
+    pub status: String,
+    /// `None` for binary files.
+    pub insertions: Option<i64>,
+    pub deletions: Option<i64>,
+    /// The file's patch; `None` once the commit's diff is past the size limit.
+    pub diff: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitDetail {
+    pub hash: String,
+    pub short_hash: String,
+    pub parents: Vec<String>,
+    pub author: String,
+    pub author_email: String,
+    pub authored_at: String,
+    pub committer: String,
+    pub committed_at: String,
+    pub subject: String,
+    pub message: String,
+    /// Changes against the first parent, so a merge shows what it brought in.
+    pub files: Vec<CommitFileChange>,
+    pub insertions: i64,
+    pub deletions: i64,
+    pub diff_truncated: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -224,6 +700,25 @@ pub struct CompareWorkspaceDiffResult {
 pub struct ListWorkspaceBranchesInput {
     pub workspace: String,
     pub fetch_remote: Option<bool>,
+    /// Remote to fetch when `fetch_remote` is set; defaults to the current branch's upstream
+    /// remote, or `origin`.
+    #[serde(default)]
+    pub remote: Option<String>,
+    #[serde(default)]
+    pub sort: Option<NameSortOptions>,
+}
+
+/// How names are ordered. Unset fields keep the defaults: natural number order, ignoring case
+/// and accents, with case and accents only breaking ties.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameSortOptions {
+    /// Compare digit runs as numbers, so `v2` sorts before `v10`. Default `true`.
+    pub numeric: Option<bool>,
+    /// Default `false`.
+    pub case_sensitive: Option<bool>,
+    /// Default `false`.
+    pub accent_sensitive: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -242,6 +737,16 @@ pub struct ListWorkspaceBranchesResult {
     pub upstream_branch: Option<String>,
     pub remote_branches: Vec<WorkspaceBranch>,
     pub suggested_base_ref: String,
+    pub remotes: Vec<WorkspaceRemote>,
+    /// The current branch's upstream remote, or `origin`, which base refs are suggested from.
+    pub default_remote: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRemote {
+    pub name: String,
+    pub url: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -249,6 +754,8 @@ pub struct ListWorkspaceBranchesResult {
 pub struct CheckoutWorkspaceBranchInput {
     pub workspace: String,
     pub branch_name: String,
+    /// Stash uncommitted changes before switching and reapply them afterwards.
+    pub auto_stash: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -256,6 +763,18 @@ pub struct CheckoutWorkspaceBranchInput {
 pub struct CheckoutWorkspaceBranchResult {
     pub workspace: String,
     pub branch_name: String,
+    /// Set when uncommitted changes were stashed for the switch.
+    pub stash: Option<BranchSwitchStash>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchSwitchStash {
+    /// Commit of the stash entry, which stays in the stash list while `restored` is false.
+    pub stash_commit: String,
+    pub restored: bool,
+    /// Files left with conflict markers by reapplying the stash on the new branch.
+    pub conflicts: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -264,74 +783,402 @@ pub struct CreateWorkspaceBranchInput {
     pub workspace: String,
     pub branch_name: String,
     pub from_ref: Option<String>,
+    pub auto_stash: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct OpenFileInEditorInput {
+pub struct WorkspaceStatusInput {
     pub workspace: String,
-    pub file_path: String,
-    pub launcher: String,
-    pub ghostty_command_template: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceStatus {
+    pub workspace: String,
+    /// `None` on a detached HEAD.
+    pub branch: Option<String>,
+    /// `None` before the first commit.
+    pub head: Option<String>,
+    pub upstream: Option<String>,
+    /// Commits ahead of and behind the upstream; `None` without one.
+    pub ahead: Option<u64>,
+    pub behind: Option<u64>,
+    pub staged: usize,
+    pub unstaged: usize,
+    pub untracked: usize,
+    pub conflicted: usize,
+    pub stash_count: usize,
+    /// No staged, unstaged, untracked or conflicted files.
+    pub clean: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct GenerateAiReviewInput {
-    pub thread_id: i64,
+pub struct PullWorkspaceBranchInput {
     pub workspace: String,
-    pub base_ref: String,
-    pub merge_base: String,
-    pub head: String,
-    pub files_changed: i64,
-    pub insertions: i64,
-    pub deletions: i64,
-    pub diff: String,
-    pub prompt: Option<String>,
+    /// Connected provider whose token authenticates the fetch; detected from the remote URL
+    /// when omitted.
+    pub provider: Option<ProviderKind>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AiReviewFinding {
-    pub id: String,
-    pub file_path: String,
-    pub chunk_id: String,
-    pub chunk_index: usize,
-    pub hunk_header: String,
-    pub side: String,
-    pub line_number: i64,
-    pub title: String,
-    pub body: String,
-    pub severity: String,
-    pub confidence: Option<f64>,
+pub struct PullWorkspaceBranchResult {
+    pub workspace: String,
+    pub branch: String,
+    pub upstream: String,
+    /// `up-to-date`, `fast-forwarded` or `diverged`. A diverged branch is left untouched.
+    pub status: String,
+    pub previous_head: String,
+    pub head: String,
+    /// Commits only on the local branch and only on the upstream, after fetching.
+    pub ahead: u64,
+    pub behind: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AiReviewChunk {
-    pub id: String,
-    pub file_path: String,
-    pub chunk_index: usize,
-    pub hunk_header: String,
-    pub summary: String,
-    pub findings: Vec<AiReviewFinding>,
+pub struct PushWorkspaceBranchInput {
+    pub workspace: String,
+    /// Defaults to the current branch.
+    pub branch: Option<String>,
+    /// Defaults to the branch's upstream remote, then `origin`.
+    pub remote: Option<String>,
+    /// Records the pushed branch as upstream; defaults to on for branches without one.
+    pub set_upstream: Option<bool>,
+    pub provider: Option<ProviderKind>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AiReviewProgressEvent {
-    pub run_id: Option<String>,
-    pub thread_id: i64,
+pub struct PushWorkspaceBranchResult {
+    pub workspace: String,
+    pub branch: String,
+    pub remote: String,
+    pub remote_branch: String,
+    /// `pushed`, `created` for a branch new to the remote, or `up-to-date`.
     pub status: String,
-    pub message: String,
-    pub total_chunks: usize,
-    pub completed_chunks: usize,
-    pub chunk_id: Option<String>,
+    pub head: String,
+    pub upstream_set: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitWorkspaceChangesInput {
+    pub workspace: String,
+    pub message: String,
+    /// Paths to stage before committing, relative to the workspace, including deletions.
+    /// Without paths only what is already staged is committed.
+    pub paths: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitWorkspaceChangesResult {
+    pub workspace: String,
+    /// `None` when committing on a detached HEAD.
+    pub branch: Option<String>,
+    pub commit: String,
+    /// First line of the commit message.
+    pub summary: String,
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateCommitMessageInput {
+    pub workspace: String,
+    /// Extra guidance for the message, such as a ticket id or a conventional commit type.
+    pub instructions: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateCommitMessageResult {
+    pub message: String,
+    pub model: String,
+    /// The staged diff was longer than the model was shown.
+    pub diff_truncated: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestThreadTitleInput {
+    /// Thread to rename with the suggestion; its workspace and base ref are the defaults.
+    pub thread_id: Option<i64>,
+    pub workspace: Option<String>,
+    pub base_ref: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestThreadTitleResult {
+    pub title: String,
+    pub model: String,
+    /// Branch the title was written for; `None` on a detached HEAD.
+    pub branch: Option<String>,
+    /// The renamed thread, when `thread_id` was given.
+    pub thread: Option<Thread>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnshallowWorkspaceInput {
+    pub workspace: String,
+    /// Commits of history to add; fetches the full history when omitted.
+    pub depth: Option<u32>,
+    /// Connected provider whose token authenticates the fetch; detected from the remote URL
+    /// when omitted.
+    pub provider: Option<ProviderKind>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnshallowWorkspaceResult {
+    pub workspace: String,
+    pub remote: String,
+    pub was_shallow: bool,
+    /// Still shallow after the fetch, as when only `depth` more commits were fetched.
+    pub shallow: bool,
+    /// Commits reachable from HEAD after the fetch.
+    pub commit_count: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchWorkspaceRemoteInput {
+    pub workspace: String,
+    /// Defaults to the current branch's upstream remote, or `origin`.
+    pub remote: Option<String>,
+    /// Connected provider whose token authenticates the fetch; detected from the remote URL
+    /// when omitted.
+    pub provider: Option<ProviderKind>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchWorkspaceRemoteResult {
+    pub workspace: String,
+    pub remote: String,
+    /// When the fetch finished, in milliseconds since the Unix epoch.
+    pub fetched_at: u64,
+    pub duration_ms: u64,
+}
+
+/// A progress line of a running `fetch_workspace_remote`, such as `Receiving objects: 45%
+/// (450/1000)`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceFetchProgressEvent {
+    pub workspace: String,
+    pub remote: String,
+    /// Git's name for the phase, such as `Counting objects`, `Receiving objects` or
+    /// `Resolving deltas`.
+    pub phase: String,
+    pub percent: Option<u8>,
+    /// Objects or deltas handled so far in this phase.
+    pub current: Option<u64>,
+    pub total: Option<u64>,
+    /// Transfer progress git appends while receiving, such as `1.20 MiB | 2.00 MiB/s`.
+    pub transfer: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapDemoWorkspaceInput {
+    /// Directory the sample repository is cloned into; defaults to the repositories directory.
+    pub destination_root: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapDemoWorkspaceResult {
+    pub workspace: String,
+    /// False when an earlier demo checkout was reused.
+    pub cloned: bool,
+    pub thread: Thread,
+    pub run: AiReviewRun,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenFileInEditorInput {
+    pub workspace: String,
+    pub file_path: String,
+    pub launcher: String,
+    pub ghostty_command_template: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateAiReviewInput {
+    pub thread_id: i64,
+    pub workspace: String,
+    pub base_ref: String,
+    pub merge_base: String,
+    pub head: String,
+    pub files_changed: i64,
+    pub insertions: i64,
+    pub deletions: i64,
+    pub diff: String,
+    pub prompt: Option<String>,
+    pub max_parallel_chunks: Option<usize>,
+    pub diff_target: Option<WorkspaceDiffTarget>,
+    /// Client-chosen id that `cancel_ai_request` can cancel the review with.
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// Reviewer profile whose prompt, focus areas and minimum severity the review uses.
+    #[serde(default)]
+    pub profile_id: Option<i64>,
+    /// Findings less confident than this are suppressed; overrides the
+    /// `ROVEX_REVIEW_MIN_CONFIDENCE` setting.
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiReviewFinding {
+    pub id: String,
+    pub file_path: String,
+    pub chunk_id: String,
+    pub chunk_index: usize,
+    pub hunk_header: String,
+    pub side: String,
+    pub line_number: i64,
+    pub title: String,
+    pub body: String,
+    pub severity: String,
+    pub confidence: Option<f64>,
+    pub category: Option<String>,
+    pub fingerprint: Option<String>,
+    pub status: Option<String>,
+    pub code_hash: Option<String>,
+    pub recurring: Option<bool>,
+    pub suggested_patch: Option<String>,
+    /// Language id of the file, such as `rust` or `typescript`, detected when the diff was
+    /// chunked. Missing on findings stored before languages were recorded.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Who last changed the flagged line; only set on runs loaded with `include_blame`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame: Option<FindingBlame>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindingBlame {
+    /// `None` for lines that are not committed yet.
+    pub commit: Option<String>,
+    pub author: Option<String>,
+    pub authored_at: Option<String>,
+    pub summary: Option<String>,
+    /// The line was committed after the run's merge base, or not committed at all, so the
+    /// reviewed change introduced it.
+    pub introduced: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiReviewTokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    pub estimated_cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiReviewChunk {
+    pub id: String,
+    pub file_path: String,
+    pub chunk_index: usize,
+    pub hunk_header: String,
+    pub summary: String,
+    pub findings: Vec<AiReviewFinding>,
+    pub token_usage: Option<AiReviewTokenUsage>,
+    /// How the review ended: `reviewed`, `refusal`, `content-filter`, `empty-output`,
+    /// `invalid-json` or `error`. Missing on chunks stored before outcomes were recorded.
+    #[serde(default)]
+    pub outcome: Option<String>,
+    /// Language id of the file, as on the chunk's findings.
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// Why a changed file in the diff was not reviewed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AiReviewSkipReason {
+    /// Git reported the file as binary and it could not be decoded to text.
+    Binary,
+    /// A rename, mode change or empty file with no changed lines.
+    NoTextChanges,
+    /// Left out by the run's include or exclude globs.
+    ExcludedPath,
+    /// Matched by the workspace's `.rovexignore`.
+    Rovexignore,
+    /// Not started because model sign-in expired during the run.
+    AuthExpired,
+    /// Not finished because the run was canceled.
+    Canceled,
+    /// Judged trivial by the triage model, so the review model never saw it.
+    TriagedTrivial,
+    /// Minified or generated output, such as a `.min.js` bundle or a source map.
+    Minified,
+    /// The file's patch is longer than `ROVEX_REVIEW_MAX_FILE_CHARS`.
+    TooLarge,
+}
+
+impl AiReviewSkipReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Binary => "binary",
+            Self::NoTextChanges => "no-text-changes",
+            Self::ExcludedPath => "excluded-path",
+            Self::Rovexignore => "rovexignore",
+            Self::AuthExpired => "auth-expired",
+            Self::Canceled => "canceled",
+            Self::TriagedTrivial => "triaged-trivial",
+            Self::Minified => "minified",
+            Self::TooLarge => "too-large",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiReviewSkippedChunk {
+    pub file_path: String,
+    pub previous_path: Option<String>,
+    pub reason: AiReviewSkipReason,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiReviewProgressEvent {
+    pub run_id: Option<String>,
+    pub thread_id: i64,
+    pub status: String,
+    pub message: String,
+    pub total_chunks: usize,
+    pub completed_chunks: usize,
+    pub chunk_id: Option<String>,
     pub file_path: Option<String>,
     pub chunk_index: Option<usize>,
     pub finding_count: Option<usize>,
     pub chunk: Option<AiReviewChunk>,
     pub finding: Option<AiReviewFinding>,
+    pub eta_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiReviewResourceUsage {
+    pub process_count: usize,
+    pub peak_memory_bytes: u64,
+    pub cpu_time_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -351,7 +1198,16 @@ pub struct GenerateAiReviewResult {
     pub diff_chars_total: usize,
     pub diff_truncated: bool,
     pub chunks: Vec<AiReviewChunk>,
+    /// Changed files that were not reviewed, so `chunks` plus these cover the whole diff.
+    pub skipped_chunks: Vec<AiReviewSkippedChunk>,
     pub findings: Vec<AiReviewFinding>,
+    /// Findings left out of `findings` for being below `min_confidence`, kept for auditing.
+    pub suppressed_findings: Vec<AiReviewFinding>,
+    pub min_confidence: Option<f64>,
+    /// Questions to start the follow-up chat with, drawn from the description and findings.
+    pub suggested_follow_ups: Vec<String>,
+    pub resource_usage: Option<AiReviewResourceUsage>,
+    pub token_usage: Option<AiReviewTokenUsage>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -368,6 +1224,65 @@ pub struct StartAiReviewRunInput {
     pub diff: String,
     pub prompt: Option<String>,
     pub scope_label: Option<String>,
+    pub max_parallel_chunks: Option<usize>,
+    pub review_mode: Option<String>,
+    pub include_paths: Option<Vec<String>>,
+    pub exclude_paths: Option<Vec<String>>,
+    pub diff_target: Option<WorkspaceDiffTarget>,
+    /// Client-chosen key, unique within the thread; starting a run again with the same key
+    /// returns the first run instead of queueing another.
+    pub idempotency_key: Option<String>,
+    /// Reviewer profile whose prompt, focus areas and minimum severity the run uses.
+    #[serde(default)]
+    pub profile_id: Option<i64>,
+    /// Findings less confident than this are suppressed; overrides the
+    /// `ROVEX_REVIEW_MIN_CONFIDENCE` setting.
+    #[serde(default)]
+    pub min_confidence: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareAiReviewRunsInput {
+    /// The earlier run, whose findings the head run is checked against.
+    pub base_run_id: String,
+    pub head_run_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistingAiReviewFinding {
+    pub base: AiReviewFinding,
+    pub head: AiReviewFinding,
+}
+
+/// Findings of two runs on the same thread, matched by fingerprint, or by code and a
+/// similar title.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareAiReviewRunsResult {
+    pub thread_id: i64,
+    pub base_run_id: String,
+    pub head_run_id: String,
+    /// Commits the two runs reviewed.
+    pub base_head: String,
+    pub head_head: String,
+    /// Reported by the head run only.
+    pub new_findings: Vec<AiReviewFinding>,
+    /// Reported by the base run only, in files the head run reviewed again.
+    pub resolved_findings: Vec<AiReviewFinding>,
+    pub persisting_findings: Vec<PersistingAiReviewFinding>,
+    /// Reported by the base run in files the head run did not review, such as files an
+    /// incremental run skipped, so whether they were fixed is unknown.
+    pub unreviewed_findings: Vec<AiReviewFinding>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiReviewFindingGroup {
+    /// The severity, file path or category the group's findings share.
+    pub key: String,
+    pub finding_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -396,8 +1311,33 @@ pub struct AiReviewRun {
     pub diff_truncated: bool,
     pub error: Option<String>,
     pub chunks: Vec<AiReviewChunk>,
+    pub skipped_chunks: Vec<AiReviewSkippedChunk>,
     pub findings: Vec<AiReviewFinding>,
+    /// Findings left out of `findings` for being below `min_confidence`, kept for auditing.
+    pub suppressed_findings: Vec<AiReviewFinding>,
+    /// Groups of `findings`, in order, when the run was loaded with a grouping.
+    pub finding_groups: Vec<AiReviewFindingGroup>,
+    /// Confidence threshold the run was reviewed with, if any.
+    pub min_confidence: Option<f64>,
+    /// Questions to start the follow-up chat with, set when the run finishes.
+    pub suggested_follow_ups: Vec<String>,
     pub progress_events: Vec<AiReviewProgressEvent>,
+    pub resource_usage: Option<AiReviewResourceUsage>,
+    pub token_usage: Option<AiReviewTokenUsage>,
+    pub review_mode: String,
+    pub incremental_base_head: Option<String>,
+    /// Markdown assembled from the description and findings while the run is in progress.
+    pub partial_review: Option<String>,
+    pub profile_id: Option<i64>,
+    /// `pass` or `fail` against the severity thresholds, set when a run completes.
+    pub verdict: Option<String>,
+    /// Which thresholds a failing run exceeded.
+    pub verdict_reason: Option<String>,
+    /// `pending`, `approved` or `changes-requested`. A reviewer's approval decides the
+    /// `verdict`: `approved` passes the run and `changes-requested` fails it.
+    pub approval: String,
+    pub approval_note: Option<String>,
+    pub approval_updated_at: Option<String>,
     pub created_at: String,
     pub started_at: Option<String>,
     pub ended_at: Option<String>,
@@ -469,6 +1409,19 @@ pub struct CancelAiReviewRunInput {
     pub run_id: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelAiRequestInput {
+    pub request_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelAiRequestResult {
+    pub request_id: String,
+    pub canceled: bool,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelAiReviewRunResult {
@@ -482,26 +1435,965 @@ pub struct CancelAiReviewRunResult {
 pub struct ListAiReviewRunsInput {
     pub thread_id: Option<i64>,
     pub limit: Option<u32>,
+    pub before_run_id: Option<String>,
+    pub after_run_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ListAiReviewRunsResult {
     pub runs: Vec<AiReviewRun>,
+    pub total_count: i64,
+    pub has_more: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetAiReviewRunInput {
     pub run_id: String,
+    /// `severity` (most severe first), `location` or `confidence`. Findings keep the order
+    /// they were stored in unless this or `group_by` is set.
+    pub sort_by: Option<String>,
+    /// `severity`, `file` or `category`; fills in `findingGroups` on the run.
+    pub group_by: Option<String>,
+    /// Annotates each finding with the blame of its line.
+    pub include_blame: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct GenerateAiFollowUpInput {
+pub struct SetAiReviewRunApprovalInput {
+    pub run_id: String,
+    /// `pending`, `approved` or `changes-requested`.
+    pub approval: String,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishReviewCommitStatusInput {
+    pub run_id: String,
+    pub provider: ProviderKind,
+    pub repository: String,
+    /// Page the status links to, such as a published report.
+    pub details_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishReviewCommitStatusResult {
+    pub run_id: String,
+    pub provider: ProviderKind,
+    pub repository: String,
+    pub sha: String,
+    /// `pending`, `success` or `failure`.
+    pub state: String,
+    pub context: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetReviewUsageSummaryInput {
+    pub thread_id: Option<i64>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewUsageMonth {
+    pub month: String,
+    pub run_count: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    pub estimated_cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetReviewUsageSummaryResult {
+    pub months: Vec<ReviewUsageMonth>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFindingsInput {
+    pub query: Option<String>,
+    pub severities: Option<Vec<String>>,
+    pub categories: Option<Vec<String>>,
+    pub languages: Option<Vec<String>>,
+    pub file_glob: Option<String>,
+    pub workspace: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindingSearchHit {
+    pub run_id: String,
     pub thread_id: i64,
     pub workspace: String,
-    pub question: String,
+    pub created_at: String,
+    pub finding: AiReviewFinding,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFindingsResult {
+    pub hits: Vec<FindingSearchHit>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewQueueEntry {
+    pub run_id: String,
+    pub thread_id: i64,
+    pub workspace: String,
+    pub status: String,
+    pub queue_position: Option<usize>,
+    pub has_slot: bool,
+    pub total_chunks: usize,
+    pub completed_chunks: usize,
+    pub failed_chunks: usize,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub estimated_start_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetReviewQueueStatusResult {
+    pub slots_total: usize,
+    pub slots_in_use: usize,
+    pub avg_chunk_duration_ms: Option<u64>,
+    pub runs: Vec<ReviewQueueEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalUiActivityInput {
+    /// `false` ends the interactive period early. Defaults to `true`.
+    pub active: Option<bool>,
+    /// How long the app counts as interactive after this signal.
+    pub duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFindingStateInput {
+    pub run_id: String,
+    pub finding_id: String,
+    pub reason: Option<String>,
+    /// Commit that fixed the finding. Only used by `mark_finding_fixed`.
+    pub fix_commit: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewFindingState {
+    pub workspace: String,
+    pub fingerprint: String,
+    pub state: String,
+    pub file_path: String,
+    pub title: String,
+    pub reason: Option<String>,
+    pub run_id: Option<String>,
+    pub finding_id: Option<String>,
+    pub fix_commit: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFindingStatesInput {
+    pub workspace: Option<String>,
+    pub state: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFindingStatesResult {
+    pub states: Vec<ReviewFindingState>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWorkspaceTrustInput {
+    pub workspace: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetWorkspaceTrustInput {
+    pub workspace: String,
+    pub trust_level: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceTrust {
+    pub workspace: String,
+    pub trust_level: String,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeWorkspaceDataInput {
+    pub workspace: String,
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgedWorkspaceTable {
+    pub table: String,
+    pub rows: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeWorkspaceDataResult {
+    pub workspace: String,
+    pub dry_run: bool,
+    pub active_runs: i64,
+    pub total_rows: i64,
+    pub tables: Vec<PurgedWorkspaceTable>,
+    pub snapshot_worktrees: usize,
+    pub git_hooks: usize,
+    pub files_removed: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportWorkspaceDataInput {
+    pub workspace: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportWorkspaceDataResult {
+    pub workspace: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub total_rows: usize,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseMaintenanceInput {
+    /// Runs `PRAGMA integrity_check`; defaults to true.
+    pub integrity_check: Option<bool>,
+    /// Deletes the orphaned rows instead of only counting them.
+    pub cleanup_orphans: Option<bool>,
+    /// Rebuilds the database file to return free pages; local databases only.
+    pub vacuum: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStats {
+    pub page_size: i64,
+    pub page_count: i64,
+    /// Pages freed by deletes that only a vacuum returns to the file system.
+    pub freelist_count: i64,
+    pub size_bytes: i64,
+    pub free_bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedRows {
+    pub table: String,
+    pub rows: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseMaintenanceResult {
+    pub database_url: String,
+    pub remote: bool,
+    pub stats: DatabaseStats,
+    /// `None` when the integrity check was skipped.
+    pub integrity_ok: Option<bool>,
+    pub integrity_problems: Vec<String>,
+    /// Rows whose thread, message, run or review group no longer exists, per table. They
+    /// were deleted when `orphans_removed` is set.
+    pub orphans: Vec<OrphanedRows>,
+    pub orphaned_rows: i64,
+    pub orphans_removed: bool,
+    pub vacuumed: bool,
+    /// Stats after the cleanup or vacuum, when either ran.
+    pub stats_after: Option<DatabaseStats>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageAttachmentInput {
+    pub name: String,
+    pub content: String,
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddMessageAttachmentInput {
+    pub message_id: i64,
+    pub name: String,
+    pub content: String,
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageAttachment {
+    pub id: i64,
+    pub message_id: i64,
+    pub thread_id: i64,
+    pub name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub content_hash: String,
+    pub content: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListMessageAttachmentsInput {
+    pub message_id: Option<i64>,
+    pub thread_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListMessageAttachmentsResult {
+    pub attachments: Vec<MessageAttachment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportThreadInput {
+    pub thread_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportThreadResult {
+    pub thread_id: i64,
+    pub file_name: String,
+    pub mime_type: String,
+    pub total_rows: usize,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportThreadInput {
+    pub content: String,
+    /// Workspace path on this machine, replacing the one the archive was exported from.
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportThreadResult {
+    pub thread: Thread,
+    pub message_count: usize,
+    pub attachment_count: usize,
+    pub run_count: usize,
+    pub finding_count: usize,
+    pub comment_count: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetThreadSettingsInput {
+    pub thread_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetThreadSettingsInput {
+    pub thread_id: i64,
+    pub base_ref: Option<String>,
+    pub profile_id: Option<i64>,
+}
+
+/// What a new review started from the thread defaults to. The first run in the thread fills
+/// these in; later runs only update `last_run_id`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadSettings {
+    pub thread_id: i64,
+    /// The thread's workspace, linked to the first run's when the thread had none.
+    pub workspace: Option<String>,
+    pub base_ref: Option<String>,
+    pub profile_id: Option<i64>,
+    pub last_run_id: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPullRequestCommentsInput {
+    pub thread_id: i64,
+    pub provider: ProviderKind,
+    pub repository: String,
+    /// Pull request number on GitHub, merge request IID on GitLab.
+    pub number: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestComment {
+    pub id: i64,
+    pub thread_id: i64,
+    pub message_id: i64,
+    pub provider: ProviderKind,
+    pub repository: String,
+    pub pull_request_number: i64,
+    pub external_id: String,
+    pub author: String,
+    pub body: String,
+    pub file_path: Option<String>,
+    pub line_number: Option<i64>,
+    pub url: Option<String>,
+    pub commented_at: String,
+    pub imported_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPullRequestCommentsResult {
+    pub thread_id: i64,
+    /// System message that records the import, absent when every comment was already imported.
+    pub message: Option<Message>,
+    pub imported: Vec<PullRequestComment>,
+    pub skipped_count: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPullRequestCommentsInput {
+    pub thread_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPullRequestCommentsResult {
+    pub comments: Vec<PullRequestComment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAiReviewRunInput {
+    pub run_id: String,
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAiReviewRunResult {
+    pub run_id: String,
+    pub format: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportReviewReportInput {
+    pub run_id: String,
+    pub output_path: String,
+    pub format: Option<String>,
+    /// Same as on `GetAiReviewRunInput`; findings are sorted by severity by default.
+    pub sort_by: Option<String>,
+    pub group_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportReviewReportResult {
+    pub run_id: String,
+    pub format: String,
+    pub path: String,
+    pub bytes_written: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyFindingFixInput {
+    pub run_id: String,
+    pub finding_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyFindingFixResult {
+    pub workspace: String,
+    pub file_path: String,
+    pub applied: bool,
+    pub three_way: bool,
+    pub conflicts: Vec<String>,
+}
+
+/// A user-defined quick action on findings: a shell command run in the workspace or a URL
+/// opened in the browser, with `{file}`, `{line}`, `{branch}` and similar placeholders
+/// filled in from the finding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindingAction {
+    pub id: String,
+    pub label: String,
+    /// `command` or `url`.
+    pub kind: String,
+    pub template: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFindingActionsResult {
+    pub actions: Vec<FindingAction>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFindingActionsInput {
+    pub actions: Vec<FindingAction>,
+    pub persist: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunFindingActionInput {
+    pub run_id: String,
+    pub finding_id: String,
+    pub action_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunFindingActionResult {
+    pub action_id: String,
+    pub kind: String,
+    /// The command line that ran or the URL that was opened.
+    pub target: String,
+    /// `None` for URLs and for commands that were killed by a signal or timed out.
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub timed_out: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunSnapshotInput {
+    pub run_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunSnapshot {
+    pub run_id: String,
+    pub workspace: String,
+    pub head: String,
+    pub path: String,
+    pub exists: bool,
+    pub created: bool,
+    pub opened_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRunSnapshotsInput {
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRunSnapshotsResult {
+    pub snapshots: Vec<RunSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveRunSnapshotResult {
+    pub run_id: String,
+    pub removed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallReviewGitHookInput {
+    pub workspace: String,
+    pub hook: Option<String>,
+    pub fail_on: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewGitHook {
+    pub workspace: String,
+    pub hook: String,
+    pub fail_on: String,
+    pub path: String,
+    pub installed_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UninstallReviewGitHookInput {
+    pub workspace: String,
+    pub hook: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UninstallReviewGitHookResult {
+    pub workspace: String,
+    pub hook: String,
+    pub removed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveReviewScheduleInput {
+    /// Updates this schedule; a new schedule is created when missing.
+    pub id: Option<i64>,
+    pub workspace: String,
+    pub thread_id: Option<i64>,
+    pub base_ref: Option<String>,
+    /// Remote that is fetched and whose `branch` is watched. Defaults to the workspace's
+    /// default remote: the current branch's upstream remote, else `origin`.
+    pub remote: Option<String>,
+    /// Branch whose `<remote>/<branch>` head is reviewed. Reviews HEAD of the workspace when
+    /// missing, except with `on_new_commits`, which defaults to the current branch.
+    pub branch: Option<String>,
+    pub interval_minutes: Option<i64>,
+    pub on_new_commits: Option<bool>,
+    pub prompt: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewSchedule {
+    pub id: i64,
+    pub workspace: String,
+    pub thread_id: i64,
+    pub base_ref: Option<String>,
+    pub remote: Option<String>,
+    pub branch: Option<String>,
+    pub interval_minutes: Option<i64>,
+    pub on_new_commits: bool,
+    pub prompt: Option<String>,
+    pub enabled: bool,
+    pub last_seen_head: Option<String>,
+    pub last_run_id: Option<String>,
+    pub last_run_status: Option<String>,
+    pub last_run_at: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewGroupRepositoryInput {
+    pub workspace: String,
+    pub base_ref: Option<String>,
+    /// Branch or commit to review instead of the checked-out branch.
+    pub head_ref: Option<String>,
+    pub fetch_remote: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartReviewGroupInput {
+    pub title: Option<String>,
+    pub prompt: Option<String>,
+    pub repositories: Vec<ReviewGroupRepositoryInput>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewGroupRun {
+    pub run_id: String,
+    pub thread_id: Option<i64>,
+    pub workspace: String,
+    pub head_ref: Option<String>,
+    pub base_ref: Option<String>,
+    pub head: Option<String>,
+    pub status: Option<String>,
+    pub finding_count: Option<i64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewGroup {
+    pub id: i64,
+    pub title: String,
+    pub prompt: Option<String>,
+    pub status: String,
+    pub summary: Option<String>,
+    pub model: Option<String>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub ended_at: Option<String>,
+    pub runs: Vec<ReviewGroupRun>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewGroupInput {
+    pub group_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListReviewGroupsInput {
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListReviewGroupsResult {
+    pub groups: Vec<ReviewGroup>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceSyncStatus {
+    pub workspace: String,
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub base_ref: Option<String>,
+    /// Remote-tracking refs, such as `origin/main`, that their remote has moved past.
+    pub stale_refs: Vec<String>,
+    /// Commits on the fetched upstream that the checkout does not have yet.
+    pub behind_upstream: i64,
+    pub stale: bool,
+    pub error: Option<String>,
+    pub checked_at: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListStaleWorkspacesInput {
+    pub workspace: Option<String>,
+    /// Checks the remotes now instead of returning the last background check.
+    pub refresh: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListStaleWorkspacesResult {
+    pub workspaces: Vec<WorkspaceSyncStatus>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchWorkspaceInput {
+    pub workspace: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceWatch {
+    pub workspace: String,
+    pub head: Option<String>,
+    pub branch: Option<String>,
+}
+
+/// Payload of `rovex://workspace-changed`, sent when a watched workspace gets new commits,
+/// switches branches, or its working tree changes.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceChangedEvent {
+    pub workspace: String,
+    pub previous_head: Option<String>,
+    pub head: Option<String>,
+    pub previous_branch: Option<String>,
+    pub branch: Option<String>,
+    /// `<short hash> <subject>` of commits added on top of the previous head, newest first.
+    pub new_commits: Vec<String>,
+    /// Modified, added, deleted, or untracked paths whose state changed since the last check.
+    pub changed_paths: Vec<String>,
+    pub changed_path_count: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListReviewSchedulesInput {
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListReviewSchedulesResult {
+    pub schedules: Vec<ReviewSchedule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteReviewScheduleInput {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteReviewScheduleResult {
+    pub id: i64,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReviewerProfileInput {
+    pub name: String,
+    /// Replaces the default reviewer goal in review prompts.
+    pub system_prompt: String,
+    /// `critical`, `high`, `medium` or `low`; findings below it are left out of runs.
+    pub min_severity: Option<String>,
+    pub focus_areas: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReviewerProfileInput {
+    pub id: i64,
+    pub name: String,
+    pub system_prompt: String,
+    pub min_severity: Option<String>,
+    pub focus_areas: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewerProfile {
+    pub id: i64,
+    pub name: String,
+    pub system_prompt: String,
+    pub min_severity: Option<String>,
+    pub focus_areas: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListReviewerProfilesResult {
+    pub profiles: Vec<ReviewerProfile>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteReviewerProfileInput {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteReviewerProfileResult {
+    pub id: i64,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetModelPerformanceStatsInput {
+    pub transport: Option<String>,
+    pub prompt_profile: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPerformanceStat {
+    pub transport: String,
+    pub model: String,
+    pub prompt_profile: String,
+    pub request_count: u64,
+    pub failure_count: u64,
+    pub failure_rate: f64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub avg_prompt_tokens: f64,
+    pub avg_completion_tokens: f64,
+    pub estimated_cost_usd: Option<f64>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetModelPerformanceStatsResult {
+    pub stats: Vec<ModelPerformanceStat>,
+    pub suggested_model: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTeamReviewStatsInput {
+    /// Most recent weeks to report, 1 to 52. Defaults to 12.
+    pub weeks: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamSeverityMix {
+    pub critical: u64,
+    pub high: u64,
+    pub medium: u64,
+    pub low: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamReviewWeekStats {
+    /// First day of the week (Monday), as `YYYY-MM-DD`.
+    pub week_start: String,
+    pub run_count: u64,
+    pub mean_findings: f64,
+    pub severity_mix: TeamSeverityMix,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTeamReviewStatsResult {
+    pub weeks: Vec<TeamReviewWeekStats>,
+    /// Weeks left out because they had fewer completed runs than `minRunsPerWeek`.
+    pub suppressed_weeks: u64,
+    pub min_runs_per_week: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateAiFollowUpInput {
+    pub thread_id: i64,
+    pub workspace: String,
+    pub question: String,
+    /// Files or snippets stored with the question and shown to the model.
+    pub attachments: Option<Vec<MessageAttachmentInput>>,
+    /// Lets the model read and search workspace files before answering.
+    pub use_tools: Option<bool>,
+    /// Client-chosen id that `cancel_ai_request` can cancel the question with.
+    pub request_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowUpToolCall {
+    /// `read_file`, `grep`, or `list_files`, or `command` for app-server shell commands.
+    pub tool: String,
+    pub input: String,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -511,6 +2403,10 @@ pub struct GenerateAiFollowUpResult {
     pub workspace: String,
     pub model: String,
     pub answer: String,
+    pub tool_calls: Vec<FollowUpToolCall>,
+    /// Whether the answer was reused from an identical question asked moments ago. Cached
+    /// answers add no messages to the thread.
+    pub cached: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -518,18 +2414,23 @@ pub struct GenerateAiFollowUpResult {
 pub struct AiReviewConfig {
     pub has_api_key: bool,
     pub api_key_preview: Option<String>,
-    pub env_file_path: Option<String>,
     pub review_provider: String,
     pub review_model: String,
     pub opencode_provider: String,
     pub opencode_model: Option<String>,
+    pub triage_model: Option<String>,
+    pub triage_min_files: usize,
+    pub max_parallel_review_runs: usize,
+    pub max_parallel_chunks_per_run: usize,
+    pub max_provider_chunks: usize,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SetAiReviewApiKeyInput {
     pub api_key: String,
-    pub persist_to_env: Option<bool>,
+    #[serde(alias = "persistToEnv")]
+    pub persist: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -539,7 +2440,14 @@ pub struct SetAiReviewSettingsInput {
     pub review_model: String,
     pub opencode_provider: Option<String>,
     pub opencode_model: Option<String>,
-    pub persist_to_env: Option<bool>,
+    /// Empty turns triage off.
+    pub triage_model: Option<String>,
+    pub max_parallel_review_runs: Option<usize>,
+    pub max_parallel_chunks_per_run: Option<usize>,
+    /// Chunks reviewed at once with the same provider, shared by all runs.
+    pub max_provider_chunks: Option<usize>,
+    #[serde(alias = "persistToEnv")]
+    pub persist: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -548,6 +2456,14 @@ pub struct OpencodeSidecarStatus {
     pub available: bool,
     pub version: Option<String>,
     pub detail: Option<String>,
+    /// Architecture the sidecar was verified for; `None` until verification passes.
+    pub architecture: Option<String>,
+    /// False when the build recorded no checksum, so only the architecture was checked.
+    pub checksum_verified: bool,
+    /// `missing`, `unreadable`, `unrecognized-format`, `architecture-mismatch` or
+    /// `checksum-mismatch` when the sidecar failed verification.
+    pub problem: Option<String>,
+    pub remediation: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
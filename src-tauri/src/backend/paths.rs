@@ -0,0 +1,181 @@
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+};
+
+/// Windows rejects paths longer than `MAX_PATH` (260) unless they carry the `\\?\` prefix.
+#[cfg(windows)]
+const WINDOWS_MAX_PATH: usize = 248;
+
+pub(crate) fn home_dir() -> Option<PathBuf> {
+    dirs::home_dir().filter(|path| !path.as_os_str().is_empty())
+}
+
+/// Resolves `.` and `..` components without touching the filesystem.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Makes `path` absolute and canonical when it exists, otherwise lexically normalized.
+///
+/// Canonical paths on Windows are returned without the verbatim `\\?\` prefix whenever the
+/// plain form is equivalent, so they can be stored, displayed, and compared as usual.
+pub(crate) fn normalize_path(path: &Path) -> Result<PathBuf, String> {
+    if let Ok(canonical) = dunce::canonicalize(path) {
+        return Ok(canonical);
+    }
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir()
+            .map_err(|error| format!("Failed to resolve current directory: {error}"))?
+            .join(path)
+    };
+    Ok(dunce::simplified(&normalize_lexically(&absolute)).to_path_buf())
+}
+
+/// Returns a form of `path` that filesystem calls accept even beyond `MAX_PATH` on Windows.
+/// Other platforms get the path back unchanged.
+#[cfg(windows)]
+pub(crate) fn long_path(path: &Path) -> PathBuf {
+    let raw = path.as_os_str().to_string_lossy();
+    if raw.len() < WINDOWS_MAX_PATH || raw.starts_with(r"\\?\") || !path.is_absolute() {
+        return path.to_path_buf();
+    }
+    let normalized = normalize_lexically(path);
+    let normalized = normalized.to_string_lossy().replace('/', "\\");
+    match normalized.strip_prefix(r"\\") {
+        Some(unc) => PathBuf::from(format!(r"\\?\UNC\{unc}")),
+        None => PathBuf::from(format!(r"\\?\{normalized}")),
+    }
+}
+
+#[cfg(not(windows))]
+pub(crate) fn long_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+/// How a filesystem compares paths: whether case is folded, and the separator `/` is
+/// rewritten to.
+#[derive(Clone, Copy)]
+struct PathRules {
+    fold_case: bool,
+    separator: char,
+}
+
+/// Windows and the default case-insensitive volumes on macOS treat `Repo` and `repo` as the
+/// same directory.
+const HOST_PATH_RULES: PathRules = PathRules {
+    fold_case: cfg!(any(windows, target_os = "macos")),
+    separator: if cfg!(windows) { '\\' } else { '/' },
+};
+
+fn comparable(path: &str, rules: PathRules) -> String {
+    let trimmed = path.trim_end_matches(['/', '\\']);
+    let trimmed = if trimmed.is_empty() { path } else { trimmed };
+    let separated = if rules.separator == '/' {
+        trimmed.to_string()
+    } else {
+        trimmed.replace('/', &rules.separator.to_string())
+    };
+    if rules.fold_case {
+        separated.to_lowercase()
+    } else {
+        separated
+    }
+}
+
+fn host_comparable(path: &Path) -> String {
+    comparable(&dunce::simplified(path).to_string_lossy(), HOST_PATH_RULES)
+}
+
+fn starts_with_comparable(path: &str, base: &str, separator: char) -> bool {
+    path == base
+        || path
+            .strip_prefix(base)
+            .is_some_and(|rest| rest.starts_with(separator) || base.ends_with(separator))
+}
+
+/// Compares paths the way the host filesystem does (case-insensitively on Windows and macOS).
+pub(crate) fn paths_equal(left: &Path, right: &Path) -> bool {
+    host_comparable(left) == host_comparable(right)
+}
+
+/// Returns whether `path` is `base` or lies inside it, using host path comparison rules.
+pub(crate) fn path_starts_with(path: &Path, base: &Path) -> bool {
+    starts_with_comparable(
+        &host_comparable(path),
+        &host_comparable(base),
+        HOST_PATH_RULES.separator,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{comparable, starts_with_comparable, PathRules};
+
+    const WINDOWS: PathRules = PathRules {
+        fold_case: true,
+        separator: '\\',
+    };
+    const MACOS: PathRules = PathRules {
+        fold_case: true,
+        separator: '/',
+    };
+    const LINUX: PathRules = PathRules {
+        fold_case: false,
+        separator: '/',
+    };
+
+    fn starts_with(path: &str, base: &str, rules: PathRules) -> bool {
+        starts_with_comparable(
+            &comparable(path, rules),
+            &comparable(base, rules),
+            rules.separator,
+        )
+    }
+
+    #[test]
+    fn case_folds_on_windows_and_macos_only() {
+        assert_eq!(
+            comparable(r"C:\Users\Dev/Repo\", WINDOWS),
+            comparable("c:/users/dev/repo", WINDOWS)
+        );
+        assert_eq!(
+            comparable("/Users/dev/Repo/", MACOS),
+            comparable("/users/dev/repo", MACOS)
+        );
+        assert_ne!(
+            comparable("/home/dev/Repo", LINUX),
+            comparable("/home/dev/repo", LINUX)
+        );
+        assert!(starts_with("/Users/Dev/Repo/src", "/users/dev/repo", MACOS));
+        assert!(starts_with(r"C:\Repo\src", "c:/repo", WINDOWS));
+        assert!(!starts_with("/home/dev/Repo/src", "/home/dev/repo", LINUX));
+    }
+
+    #[test]
+    fn prefixes_only_match_whole_components() {
+        for rules in [WINDOWS, MACOS, LINUX] {
+            assert!(starts_with("/a/b", "/a/b", rules));
+            assert!(starts_with("/a/b/c", "/a/b", rules));
+            assert!(starts_with("/a/b/c", "/a/b/", rules));
+            assert!(starts_with("/a/b", "/", rules));
+            assert!(!starts_with("/a/bc", "/a/b", rules));
+            assert!(!starts_with("/a/b", "/a/bc", rules));
+            assert!(!starts_with("/a", "/a/b", rules));
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use async_trait::async_trait;
+
+use super::{ProviderClient, ProviderCommitStatus, ProviderIdentity, RepositoryRef};
+use crate::backend::ProviderKind;
+
+/// A provider whose repositories are local git repositories under `origins_root`, at
+/// `<owner>/<name>`, and which accepts a single access token. Built only with the
+/// `integration-test` feature.
+#[derive(Debug, Clone)]
+pub(crate) struct FakeProviderClient {
+    pub(crate) origins_root: PathBuf,
+    pub(crate) access_token: String,
+    pub(crate) account_login: String,
+    /// Statuses published through this client, as `(repository slug, sha, status)`.
+    pub(crate) commit_statuses: Arc<Mutex<Vec<(String, String, ProviderCommitStatus)>>>,
+}
+
+fn fake_providers() -> &'static Mutex<HashMap<ProviderKind, FakeProviderClient>> {
+    static FAKE_PROVIDERS: OnceLock<Mutex<HashMap<ProviderKind, FakeProviderClient>>> =
+        OnceLock::new();
+    FAKE_PROVIDERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Makes [`super::provider_client`] return `client` for `kind` from now on.
+pub(crate) fn install_fake_provider(kind: ProviderKind, client: FakeProviderClient) {
+    if let Ok(mut providers) = fake_providers().lock() {
+        providers.insert(kind, client);
+    }
+}
+
+pub(crate) fn installed_fake_provider(kind: ProviderKind) -> Option<FakeProviderClient> {
+    fake_providers()
+        .lock()
+        .ok()
+        .and_then(|providers| providers.get(&kind).cloned())
+}
+
+#[async_trait]
+impl ProviderClient for FakeProviderClient {
+    fn parse_repository(&self, value: &str) -> Result<RepositoryRef, String> {
+        let trimmed = value.trim().trim_end_matches(".git");
+        match trimmed.split_once('/') {
+            Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => {
+                Ok(RepositoryRef {
+                    owner: owner.to_string(),
+                    name: name.to_string(),
+                })
+            }
+            _ => Err(format!(
+                "Expected a repository like owner/name, got '{value}'."
+            )),
+        }
+    }
+
+    fn clone_url(&self, repository: &RepositoryRef) -> String {
+        self.origins_root
+            .join(&repository.owner)
+            .join(&repository.name)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn clone_auth_header(&self, access_token: &str) -> Result<String, String> {
+        Ok(format!("Authorization: Bearer {access_token}"))
+    }
+
+    async fn validate_access_token(&self, access_token: &str) -> Result<ProviderIdentity, String> {
+        if access_token != self.access_token {
+            return Err("Provider rejected the access token.".to_string());
+        }
+        Ok(ProviderIdentity {
+            account_login: self.account_login.clone(),
+            avatar_url: None,
+        })
+    }
+
+    async fn publish_commit_status(
+        &self,
+        access_token: &str,
+        repository: &RepositoryRef,
+        sha: &str,
+        status: &ProviderCommitStatus,
+    ) -> Result<(), String> {
+        if access_token != self.access_token {
+            return Err("Provider rejected the access token.".to_string());
+        }
+        self.commit_statuses
+            .lock()
+            .map_err(|_| "Fake provider statuses are unavailable.".to_string())?
+            .push((repository.slug(), sha.to_string(), status.clone()));
+        Ok(())
+    }
+}
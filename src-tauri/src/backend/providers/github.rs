@@ -3,11 +3,12 @@ use std::env;
 use async_trait::async_trait;
 use base64::Engine as _;
 use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 
 use super::{
-    ProviderClient, ProviderDeviceAuthorizationPoll, ProviderDeviceAuthorizationStart,
-    ProviderIdentity, RepositoryRef,
+    ProviderClient, ProviderCommitStatus, ProviderDeviceAuthorizationPoll,
+    ProviderDeviceAuthorizationStart, ProviderIdentity, ProviderReviewComment, RepositoryRef,
 };
 
 const GITHUB_HTTPS_PREFIX: &str = "https://github.com/";
@@ -19,6 +20,10 @@ const GITHUB_OAUTH_CLIENT_ID_ENV: &str = "GITHUB_OAUTH_CLIENT_ID";
 const ROVEX_GITHUB_OAUTH_CLIENT_ID_ENV: &str = "ROVEX_GITHUB_OAUTH_CLIENT_ID";
 const GITHUB_OAUTH_SCOPE_ENV: &str = "GITHUB_OAUTH_SCOPE";
 const ROVEX_GITHUB_OAUTH_SCOPE_ENV: &str = "ROVEX_GITHUB_OAUTH_SCOPE";
+const GITHUB_PAGE_SIZE: usize = 100;
+const GITHUB_MAX_COMMENT_PAGES: usize = 10;
+/// Longest description GitHub accepts on a commit status.
+const GITHUB_STATUS_DESCRIPTION_CHARS: usize = 140;
 
 pub struct GitHubProviderClient;
 
@@ -28,6 +33,43 @@ struct GitHubUserResponse {
     avatar_url: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct GitHubCommentAuthor {
+    login: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubReviewCommentResponse {
+    id: u64,
+    user: Option<GitHubCommentAuthor>,
+    body: Option<String>,
+    path: Option<String>,
+    line: Option<i64>,
+    original_line: Option<i64>,
+    html_url: Option<String>,
+    created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubReviewResponse {
+    id: u64,
+    user: Option<GitHubCommentAuthor>,
+    body: Option<String>,
+    html_url: Option<String>,
+    submitted_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubIssueCommentResponse {
+    id: u64,
+    user: Option<GitHubCommentAuthor>,
+    body: Option<String>,
+    html_url: Option<String>,
+    created_at: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct GitHubDeviceCodeResponse {
     device_code: String,
@@ -73,6 +115,71 @@ fn github_oauth_scope() -> String {
         .unwrap_or_else(|| GITHUB_DEFAULT_OAUTH_SCOPE.to_string())
 }
 
+/// Fetches every page of a GitHub list endpoint, up to `GITHUB_MAX_COMMENT_PAGES`.
+async fn github_get_pages<T: DeserializeOwned>(
+    client: &Client,
+    token: &str,
+    endpoint: &str,
+) -> Result<Vec<T>, String> {
+    let mut items = Vec::new();
+    for page in 1..=GITHUB_MAX_COMMENT_PAGES {
+        let response = client
+            .get(endpoint)
+            .query(&[("per_page", GITHUB_PAGE_SIZE), ("page", page)])
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "rovex-provider")
+            .send()
+            .await
+            .map_err(|error| format!("Failed to reach GitHub API: {error}"))?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(
+                "GitHub rejected the token. Verify token scopes and try again.".to_string(),
+            );
+        }
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(
+                "GitHub could not find the pull request, or the token cannot read it.".to_string(),
+            );
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let snippet: String = body.chars().take(200).collect();
+            return Err(format!(
+                "GitHub API returned {status}. Response: {}",
+                snippet.trim()
+            ));
+        }
+
+        let page_items: Vec<T> = response
+            .json()
+            .await
+            .map_err(|error| format!("Failed to parse GitHub API response: {error}"))?;
+        let last_page = page_items.len() < GITHUB_PAGE_SIZE;
+        items.extend(page_items);
+        if last_page {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+/// The login of a person, or `None` for bots and deleted accounts.
+fn human_login(user: Option<GitHubCommentAuthor>) -> Option<String> {
+    user.filter(|user| user.kind.as_deref() != Some("Bot"))
+        .map(|user| user.login)
+}
+
+fn non_empty_body(body: Option<String>) -> Option<String> {
+    body.map(|body| body.trim().to_string())
+        .filter(|body| !body.is_empty())
+}
+
 #[async_trait]
 impl ProviderClient for GitHubProviderClient {
     fn parse_repository(&self, value: &str) -> Result<RepositoryRef, String> {
@@ -173,6 +280,146 @@ impl ProviderClient for GitHubProviderClient {
         })
     }
 
+    async fn list_pull_request_comments(
+        &self,
+        access_token: &str,
+        repository: &RepositoryRef,
+        number: u64,
+    ) -> Result<Vec<ProviderReviewComment>, String> {
+        let token = access_token.trim();
+        if token.is_empty() {
+            return Err("Provider access token must not be empty.".to_string());
+        }
+
+        let client = Client::new();
+        let base = format!("https://api.github.com/repos/{}", repository.slug());
+        let review_comments: Vec<GitHubReviewCommentResponse> =
+            github_get_pages(&client, token, &format!("{base}/pulls/{number}/comments")).await?;
+        let reviews: Vec<GitHubReviewResponse> =
+            github_get_pages(&client, token, &format!("{base}/pulls/{number}/reviews")).await?;
+        let issue_comments: Vec<GitHubIssueCommentResponse> =
+            github_get_pages(&client, token, &format!("{base}/issues/{number}/comments")).await?;
+
+        let mut comments = Vec::new();
+        for comment in review_comments {
+            let (Some(author), Some(body)) =
+                (human_login(comment.user), non_empty_body(comment.body))
+            else {
+                continue;
+            };
+            comments.push(ProviderReviewComment {
+                id: format!("review-comment:{}", comment.id),
+                author,
+                body,
+                file_path: comment.path,
+                line: comment.line.or(comment.original_line),
+                url: comment.html_url,
+                created_at: comment.created_at,
+            });
+        }
+        // Reviews without a summary only carry their line comments, which are listed above.
+        for review in reviews {
+            let (Some(author), Some(body), Some(created_at)) = (
+                human_login(review.user),
+                non_empty_body(review.body),
+                review.submitted_at,
+            ) else {
+                continue;
+            };
+            comments.push(ProviderReviewComment {
+                id: format!("review:{}", review.id),
+                author,
+                body,
+                file_path: None,
+                line: None,
+                url: review.html_url,
+                created_at,
+            });
+        }
+        for comment in issue_comments {
+            let (Some(author), Some(body)) =
+                (human_login(comment.user), non_empty_body(comment.body))
+            else {
+                continue;
+            };
+            comments.push(ProviderReviewComment {
+                id: format!("issue-comment:{}", comment.id),
+                author,
+                body,
+                file_path: None,
+                line: None,
+                url: comment.html_url,
+                created_at: comment.created_at,
+            });
+        }
+        comments.sort_by(|left, right| left.created_at.cmp(&right.created_at));
+        Ok(comments)
+    }
+
+    async fn publish_commit_status(
+        &self,
+        access_token: &str,
+        repository: &RepositoryRef,
+        sha: &str,
+        status: &ProviderCommitStatus,
+    ) -> Result<(), String> {
+        let token = access_token.trim();
+        if token.is_empty() {
+            return Err("Provider access token must not be empty.".to_string());
+        }
+
+        let description = status
+            .description
+            .chars()
+            .take(GITHUB_STATUS_DESCRIPTION_CHARS)
+            .collect::<String>();
+        let response = Client::new()
+            .post(format!(
+                "https://api.github.com/repos/{}/statuses/{sha}",
+                repository.slug()
+            ))
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "rovex-provider")
+            .json(&serde_json::json!({
+                "state": status.state.as_str(),
+                "context": status.context,
+                "description": description,
+                "target_url": status.target_url,
+            }))
+            .send()
+            .await
+            .map_err(|error| format!("Failed to reach GitHub API: {error}"))?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(
+                "GitHub rejected the token. Verify token scopes and try again.".to_string(),
+            );
+        }
+
+        if matches!(
+            response.status(),
+            StatusCode::NOT_FOUND | StatusCode::FORBIDDEN
+        ) {
+            return Err(
+                "GitHub could not find the commit, or the token cannot write commit statuses."
+                    .to_string(),
+            );
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let snippet: String = body.chars().take(200).collect();
+            return Err(format!(
+                "GitHub API returned {status}. Response: {}",
+                snippet.trim()
+            ));
+        }
+        Ok(())
+    }
+
     async fn start_device_authorization(&self) -> Result<ProviderDeviceAuthorizationStart, String> {
         let client_id = github_oauth_client_id()?;
         let scope = github_oauth_scope();
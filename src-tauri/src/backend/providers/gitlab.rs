@@ -6,12 +6,12 @@ use reqwest::{Client, StatusCode};
 use serde::Deserialize;
 
 use super::{
-    ProviderClient, ProviderDeviceAuthorizationPoll, ProviderDeviceAuthorizationStart,
-    ProviderIdentity, RepositoryRef,
+    CommitStatusState, ProviderClient, ProviderCommitStatus, ProviderDeviceAuthorizationPoll,
+    ProviderDeviceAuthorizationStart, ProviderIdentity, ProviderReviewComment, RepositoryRef,
 };
 
 const GITLAB_DEFAULT_BASE_URL: &str = "https://gitlab.com";
-const GITLAB_DEFAULT_OAUTH_SCOPE: &str = "read_user read_repository";
+const GITLAB_DEFAULT_OAUTH_SCOPE: &str = "read_user read_repository read_api";
 const GITLAB_OAUTH_CLIENT_ID_ENV: &str = "GITLAB_OAUTH_CLIENT_ID";
 const ROVEX_GITLAB_OAUTH_CLIENT_ID_ENV: &str = "ROVEX_GITLAB_OAUTH_CLIENT_ID";
 const GITLAB_OAUTH_SCOPE_ENV: &str = "GITLAB_OAUTH_SCOPE";
@@ -19,6 +19,8 @@ const ROVEX_GITLAB_OAUTH_SCOPE_ENV: &str = "ROVEX_GITLAB_OAUTH_SCOPE";
 const GITLAB_BASE_URL_ENV: &str = "GITLAB_BASE_URL";
 const ROVEX_GITLAB_BASE_URL_ENV: &str = "ROVEX_GITLAB_BASE_URL";
 const USER_AGENT: &str = "rovex-provider";
+const GITLAB_PAGE_SIZE: usize = 100;
+const GITLAB_MAX_NOTE_PAGES: usize = 10;
 
 pub struct GitLabProviderClient;
 
@@ -28,6 +30,31 @@ struct GitLabUserResponse {
     avatar_url: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct GitLabNoteAuthor {
+    username: String,
+    bot: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabNotePosition {
+    new_path: Option<String>,
+    new_line: Option<i64>,
+    old_path: Option<String>,
+    old_line: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabNoteResponse {
+    id: u64,
+    body: String,
+    author: GitLabNoteAuthor,
+    created_at: String,
+    #[serde(default)]
+    system: bool,
+    position: Option<GitLabNotePosition>,
+}
+
 #[derive(Debug, Deserialize)]
 struct GitLabDeviceCodeResponse {
     device_code: String,
@@ -119,6 +146,65 @@ async fn parse_gitlab_user_response(
     })
 }
 
+/// Sends a GET with the token as an OAuth bearer token, retrying it as a personal access
+/// token when GitLab rejects the first form.
+async fn gitlab_get(
+    client: &Client,
+    endpoint: &str,
+    token: &str,
+    query: &[(&str, String)],
+) -> Result<reqwest::Response, String> {
+    let bearer_response = client
+        .get(endpoint)
+        .query(query)
+        .header("Authorization", format!("Bearer {token}"))
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .await
+        .map_err(|error| format!("Failed to reach GitLab API: {error}"))?;
+    if bearer_response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(bearer_response);
+    }
+
+    client
+        .get(endpoint)
+        .query(query)
+        .header("PRIVATE-TOKEN", token)
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .await
+        .map_err(|error| format!("Failed to reach GitLab API: {error}"))
+}
+
+/// Sends a POST with a JSON body, retrying it as a personal access token like [`gitlab_get`].
+async fn gitlab_post(
+    client: &Client,
+    endpoint: &str,
+    token: &str,
+    body: &serde_json::Value,
+) -> Result<reqwest::Response, String> {
+    let bearer_response = client
+        .post(endpoint)
+        .json(body)
+        .header("Authorization", format!("Bearer {token}"))
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .await
+        .map_err(|error| format!("Failed to reach GitLab API: {error}"))?;
+    if bearer_response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(bearer_response);
+    }
+
+    client
+        .post(endpoint)
+        .json(body)
+        .header("PRIVATE-TOKEN", token)
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .await
+        .map_err(|error| format!("Failed to reach GitLab API: {error}"))
+}
+
 #[async_trait]
 impl ProviderClient for GitLabProviderClient {
     fn parse_repository(&self, value: &str) -> Result<RepositoryRef, String> {
@@ -243,6 +329,150 @@ impl ProviderClient for GitLabProviderClient {
         parse_gitlab_user_response(private_token_response, "GitLab API response").await
     }
 
+    async fn list_pull_request_comments(
+        &self,
+        access_token: &str,
+        repository: &RepositoryRef,
+        number: u64,
+    ) -> Result<Vec<ProviderReviewComment>, String> {
+        let token = access_token.trim();
+        if token.is_empty() {
+            return Err("Provider access token must not be empty.".to_string());
+        }
+
+        let base_url = gitlab_base_url();
+        let project = repository.slug().replace('/', "%2F");
+        let endpoint =
+            format!("{base_url}/api/v4/projects/{project}/merge_requests/{number}/notes");
+        let client = Client::new();
+
+        let mut notes = Vec::new();
+        for page in 1..=GITLAB_MAX_NOTE_PAGES {
+            let query = [
+                ("per_page", GITLAB_PAGE_SIZE.to_string()),
+                ("page", page.to_string()),
+                ("sort", "asc".to_string()),
+                ("order_by", "created_at".to_string()),
+            ];
+            let response = gitlab_get(&client, &endpoint, token, &query).await?;
+
+            if response.status() == StatusCode::UNAUTHORIZED {
+                return Err(
+                    "GitLab rejected the token. Verify token scopes and try again.".to_string(),
+                );
+            }
+
+            if response.status() == StatusCode::NOT_FOUND {
+                return Err(
+                    "GitLab could not find the merge request, or the token cannot read it."
+                        .to_string(),
+                );
+            }
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let snippet: String = body.chars().take(200).collect();
+                return Err(format!(
+                    "GitLab API returned {status}. Response: {}",
+                    snippet.trim()
+                ));
+            }
+
+            let page_notes: Vec<GitLabNoteResponse> = response
+                .json()
+                .await
+                .map_err(|error| format!("Failed to parse GitLab API response: {error}"))?;
+            let last_page = page_notes.len() < GITLAB_PAGE_SIZE;
+            notes.extend(page_notes);
+            if last_page {
+                break;
+            }
+        }
+
+        let merge_request_url =
+            format!("{base_url}/{}/-/merge_requests/{number}", repository.slug());
+        Ok(notes
+            .into_iter()
+            .filter(|note| !note.system && note.author.bot != Some(true))
+            .filter(|note| !note.body.trim().is_empty())
+            .map(|note| {
+                let (file_path, line) = match note.position {
+                    Some(position) => (
+                        position.new_path.or(position.old_path),
+                        position.new_line.or(position.old_line),
+                    ),
+                    None => (None, None),
+                };
+                ProviderReviewComment {
+                    id: format!("note:{}", note.id),
+                    author: note.author.username,
+                    body: note.body.trim().to_string(),
+                    file_path,
+                    line,
+                    url: Some(format!("{merge_request_url}#note_{}", note.id)),
+                    created_at: note.created_at,
+                }
+            })
+            .collect())
+    }
+
+    async fn publish_commit_status(
+        &self,
+        access_token: &str,
+        repository: &RepositoryRef,
+        sha: &str,
+        status: &ProviderCommitStatus,
+    ) -> Result<(), String> {
+        let token = access_token.trim();
+        if token.is_empty() {
+            return Err("Provider access token must not be empty.".to_string());
+        }
+
+        let state = match status.state {
+            CommitStatusState::Pending => "pending",
+            CommitStatusState::Success => "success",
+            CommitStatusState::Failure => "failed",
+        };
+        let base_url = gitlab_base_url();
+        let project = repository.slug().replace('/', "%2F");
+        let endpoint = format!("{base_url}/api/v4/projects/{project}/statuses/{sha}");
+        let body = serde_json::json!({
+            "state": state,
+            "name": status.context,
+            "description": status.description,
+            "target_url": status.target_url,
+        });
+        let response = gitlab_post(&Client::new(), &endpoint, token, &body).await?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(
+                "GitLab rejected the token. Verify token scopes and try again.".to_string(),
+            );
+        }
+
+        if matches!(
+            response.status(),
+            StatusCode::NOT_FOUND | StatusCode::FORBIDDEN
+        ) {
+            return Err(
+                "GitLab could not find the commit, or the token cannot write commit statuses (it needs the api scope)."
+                    .to_string(),
+            );
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let snippet: String = body.chars().take(200).collect();
+            return Err(format!(
+                "GitLab API returned {status}. Response: {}",
+                snippet.trim()
+            ));
+        }
+        Ok(())
+    }
+
     async fn start_device_authorization(&self) -> Result<ProviderDeviceAuthorizationStart, String> {
         let client_id = gitlab_oauth_client_id()?;
         let scope = gitlab_oauth_scope();
@@ -1,3 +1,5 @@
+#[cfg(feature = "integration-test")]
+pub(crate) mod fake;
 mod github;
 mod gitlab;
 
@@ -36,6 +38,47 @@ pub struct RepositoryRef {
     pub name: String,
 }
 
+/// A comment a person left on a pull request, either on a line of the diff or on the
+/// conversation.
+#[derive(Debug, Clone)]
+pub struct ProviderReviewComment {
+    /// Provider id, prefixed by the kind of comment so ids from different endpoints differ.
+    pub id: String,
+    pub author: String,
+    pub body: String,
+    pub file_path: Option<String>,
+    pub line: Option<i64>,
+    pub url: Option<String>,
+    pub created_at: String,
+}
+
+/// Outcome shown next to a commit, alongside its CI checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitStatusState {
+    Pending,
+    Success,
+    Failure,
+}
+
+impl CommitStatusState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Success => "success",
+            Self::Failure => "failure",
+        }
+    }
+}
+
+/// A status posted on a commit. A later status with the same `context` replaces it.
+#[derive(Debug, Clone)]
+pub struct ProviderCommitStatus {
+    pub state: CommitStatusState,
+    pub context: String,
+    pub description: String,
+    pub target_url: Option<String>,
+}
+
 impl RepositoryRef {
     pub fn slug(&self) -> String {
         format!("{}/{}", self.owner, self.name)
@@ -59,9 +102,35 @@ pub trait ProviderClient: Send + Sync {
     ) -> Result<ProviderDeviceAuthorizationPoll, String> {
         Err("Device authorization is not supported for this provider.".to_string())
     }
+
+    /// Comments people left on pull request `number`, oldest first. Bot comments and
+    /// provider system notes are left out.
+    async fn list_pull_request_comments(
+        &self,
+        _access_token: &str,
+        _repository: &RepositoryRef,
+        _number: u64,
+    ) -> Result<Vec<ProviderReviewComment>, String> {
+        Err("Importing pull request comments is not supported for this provider.".to_string())
+    }
+
+    /// Posts `status` on commit `sha`, replacing an earlier status with the same context.
+    async fn publish_commit_status(
+        &self,
+        _access_token: &str,
+        _repository: &RepositoryRef,
+        _sha: &str,
+        _status: &ProviderCommitStatus,
+    ) -> Result<(), String> {
+        Err("Publishing commit statuses is not supported for this provider.".to_string())
+    }
 }
 
 pub fn provider_client(kind: ProviderKind) -> Box<dyn ProviderClient> {
+    #[cfg(feature = "integration-test")]
+    if let Some(client) = fake::installed_fake_provider(kind) {
+        return Box::new(client);
+    }
     match kind {
         ProviderKind::Github => Box::new(github::GitHubProviderClient),
         ProviderKind::Gitlab => Box::new(gitlab::GitLabProviderClient),
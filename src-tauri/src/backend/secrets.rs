@@ -0,0 +1,130 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use keyring::Entry;
+use libsql::Connection;
+
+use super::settings::settings_store;
+
+const KEYRING_SERVICE: &str = "rovex";
+/// Secrets that older builds kept in `.env` or the settings table.
+const PLAINTEXT_SECRET_KEYS: &[&str] = &["OPENAI_API_KEY"];
+
+/// Keychain lookups already made, so reading the config does not hit the keychain each time.
+static SECRET_CACHE: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+
+fn secret_cache() -> &'static Mutex<HashMap<String, Option<String>>> {
+    SECRET_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The `.env` at the root of the Rovex checkout, the one the setup steps have users create.
+fn app_env_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../.env")
+}
+
+fn keyring_entry(key: &str) -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, key)
+        .map_err(|error| format!("Failed to open keychain entry for {key}: {error}"))
+}
+
+pub(crate) fn get_secret(key: &str) -> Result<Option<String>, String> {
+    if let Some(cached) = secret_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(key).cloned())
+    {
+        return Ok(cached);
+    }
+    let value = match keyring_entry(key)?.get_password() {
+        Ok(value) => Some(value.trim().to_string()).filter(|value| !value.is_empty()),
+        Err(keyring::Error::NoEntry) => None,
+        Err(error) => return Err(format!("Failed to read {key} from keychain: {error}")),
+    };
+    if let Ok(mut cache) = secret_cache().lock() {
+        cache.insert(key.to_string(), value.clone());
+    }
+    Ok(value)
+}
+
+pub(crate) fn set_secret(key: &str, value: &str) -> Result<(), String> {
+    keyring_entry(key)?
+        .set_password(value)
+        .map_err(|error| format!("Failed to save {key} to keychain: {error}"))?;
+    if let Ok(mut cache) = secret_cache().lock() {
+        let value = Some(value.trim().to_string()).filter(|value| !value.is_empty());
+        cache.insert(key.to_string(), value);
+    }
+    Ok(())
+}
+
+/// Moves plaintext secrets into the OS keychain.
+///
+/// A secret is migrated only when the keychain has no entry for it yet, and only from the
+/// settings table or the app's own `.env`; a key that is merely set in the process
+/// environment is left alone. Once it is stored, the plaintext copy is removed from both.
+pub(crate) async fn migrate_plaintext_secrets(conn: &Connection) -> Result<(), String> {
+    let env_path = app_env_path();
+    for key in PLAINTEXT_SECRET_KEYS {
+        if get_secret(key)?.is_some() {
+            continue;
+        }
+
+        let settings = settings_store();
+        let Some(value) = settings
+            .get::<String>(key)
+            .or_else(|| read_env_key(&env_path, key))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+        else {
+            continue;
+        };
+
+        set_secret(key, &value)?;
+        settings.remove(conn, key).await?;
+        remove_env_key(&env_path, key)?;
+    }
+    Ok(())
+}
+
+fn read_env_key(path: &Path, key: &str) -> Option<String> {
+    dotenvy::from_path_iter(path)
+        .ok()?
+        .flatten()
+        .find_map(|(name, value)| (name == key).then_some(value))
+}
+
+fn remove_env_key(path: &Path, key: &str) -> Result<(), String> {
+    let existing = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(format!("Failed to read {}: {error}", path.display())),
+    };
+
+    let lines: Vec<&str> = existing
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('#') {
+                return true;
+            }
+            let trimmed = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+            trimmed
+                .split_once('=')
+                .map(|(name, _)| name.trim() != key)
+                .unwrap_or(true)
+        })
+        .collect();
+    if lines.len() == existing.lines().count() {
+        return Ok(());
+    }
+
+    let mut next = lines.join("\n");
+    if !next.is_empty() {
+        next.push('\n');
+    }
+    fs::write(path, next).map_err(|error| format!("Failed to write {}: {error}", path.display()))
+}
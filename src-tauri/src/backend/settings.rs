@@ -0,0 +1,126 @@
+use std::{
+    collections::HashMap,
+    env,
+    sync::{OnceLock, RwLock},
+};
+
+use libsql::Connection;
+use serde::{de::DeserializeOwned, Serialize};
+
+static SETTINGS_STORE: OnceLock<SettingsStore> = OnceLock::new();
+
+/// Key/value user settings persisted in the `settings` table.
+///
+/// Values are cached in memory so synchronous call sites (provider resolution, transports)
+/// can read them without a database round trip. Keys that were never saved fall back to the
+/// process environment, which keeps `.env`-based development setups working.
+pub(crate) struct SettingsStore {
+    values: RwLock<HashMap<String, serde_json::Value>>,
+}
+
+pub(crate) fn settings_store() -> &'static SettingsStore {
+    SETTINGS_STORE.get_or_init(|| SettingsStore {
+        values: RwLock::new(HashMap::new()),
+    })
+}
+
+impl SettingsStore {
+    pub(crate) async fn reload(&self, conn: &Connection) -> Result<(), String> {
+        let mut rows = conn
+            .query("SELECT key, value_json FROM settings", ())
+            .await
+            .map_err(|error| format!("Failed to load settings: {error}"))?;
+
+        let mut values = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|error| format!("Failed to read settings rows: {error}"))?
+        {
+            let key: String = row
+                .get(0)
+                .map_err(|error| format!("Failed to parse setting key: {error}"))?;
+            let raw: String = row
+                .get(1)
+                .map_err(|error| format!("Failed to parse setting value for {key}: {error}"))?;
+            match serde_json::from_str::<serde_json::Value>(&raw) {
+                Ok(value) => {
+                    values.insert(key, value);
+                }
+                Err(error) => {
+                    eprintln!("[backend] Ignoring malformed setting {key}: {error}");
+                }
+            }
+        }
+
+        let mut cache = self
+            .values
+            .write()
+            .map_err(|_| "Failed to access settings cache.".to_string())?;
+        *cache = values;
+        Ok(())
+    }
+
+    pub(crate) fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let cache = self.values.read().ok()?;
+        let value = cache.get(key)?.clone();
+        serde_json::from_value(value).ok()
+    }
+
+    /// Returns the trimmed, non-empty string stored under `key`, falling back to the
+    /// environment variable of the same name.
+    pub(crate) fn get_string(&self, key: &str) -> Option<String> {
+        self.get::<String>(key)
+            .or_else(|| env::var(key).ok())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    pub(crate) async fn set<T: Serialize>(
+        &self,
+        conn: &Connection,
+        key: &str,
+        value: &T,
+    ) -> Result<(), String> {
+        let value = serde_json::to_value(value)
+            .map_err(|error| format!("Failed to serialize setting {key}: {error}"))?;
+        let raw = serde_json::to_string(&value)
+            .map_err(|error| format!("Failed to serialize setting {key}: {error}"))?;
+        conn.execute(
+            "INSERT INTO settings (key, value_json, updated_at)
+             VALUES (?1, ?2, CURRENT_TIMESTAMP)
+             ON CONFLICT(key)
+             DO UPDATE SET
+               value_json = excluded.value_json,
+               updated_at = CURRENT_TIMESTAMP",
+            (key.to_string(), raw),
+        )
+        .await
+        .map_err(|error| format!("Failed to save setting {key}: {error}"))?;
+
+        self.set_in_memory(key, value)
+    }
+
+    pub(crate) async fn remove(&self, conn: &Connection, key: &str) -> Result<(), String> {
+        conn.execute("DELETE FROM settings WHERE key = ?1", [key.to_string()])
+            .await
+            .map_err(|error| format!("Failed to delete setting {key}: {error}"))?;
+
+        let mut cache = self
+            .values
+            .write()
+            .map_err(|_| "Failed to access settings cache.".to_string())?;
+        cache.remove(key);
+        Ok(())
+    }
+
+    /// Overrides a setting for the lifetime of the process without persisting it.
+    pub(crate) fn set_in_memory(&self, key: &str, value: serde_json::Value) -> Result<(), String> {
+        let mut cache = self
+            .values
+            .write()
+            .map_err(|_| "Failed to access settings cache.".to_string())?;
+        cache.insert(key.to_string(), value);
+        Ok(())
+    }
+}
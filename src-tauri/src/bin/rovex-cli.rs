@@ -0,0 +1,3 @@
+fn main() {
+    std::process::exit(rovex_lib::run_cli())
+}
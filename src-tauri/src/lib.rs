@@ -1,6 +1,13 @@
 mod backend;
 use tauri::Manager;
 
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub use backend::commands::bench_support;
+#[cfg(feature = "integration-test")]
+#[doc(hidden)]
+pub use backend::commands::integration_support;
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -22,15 +29,30 @@ pub fn run() {
             })
             .map_err(std::io::Error::other)?;
             app.manage(state);
+            backend::commands::start_review_scheduler(app.handle().clone());
+            backend::commands::start_review_group_watcher(app.handle().clone());
+            backend::commands::start_workspace_sync_watcher(app.handle().clone());
+            backend::commands::start_workspace_change_watcher(app.handle().clone());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             backend::commands::backend_health,
             backend::commands::create_thread,
             backend::commands::list_threads,
+            backend::commands::rename_thread,
+            backend::commands::archive_thread,
+            backend::commands::pin_thread,
             backend::commands::delete_thread,
             backend::commands::add_thread_message,
             backend::commands::list_thread_messages,
+            backend::commands::export_thread,
+            backend::commands::import_thread,
+            backend::commands::get_thread_settings,
+            backend::commands::set_thread_settings,
+            backend::commands::add_message_attachment,
+            backend::commands::list_message_attachments,
+            backend::commands::import_pull_request_comments,
+            backend::commands::list_pull_request_comments,
             backend::commands::connect_provider,
             backend::commands::start_provider_device_auth,
             backend::commands::poll_provider_device_auth,
@@ -38,10 +60,34 @@ pub fn run() {
             backend::commands::list_provider_connections,
             backend::commands::disconnect_provider,
             backend::commands::clone_repository,
+            backend::commands::get_repositories_dir,
+            backend::commands::migrate_repositories_dir,
             backend::commands::compare_workspace_diff,
+            backend::commands::list_workspace_files,
+            backend::commands::read_workspace_file,
             backend::commands::list_workspace_branches,
             backend::commands::checkout_workspace_branch,
             backend::commands::create_workspace_branch,
+            backend::commands::workspace_status,
+            backend::commands::pull_workspace_branch,
+            backend::commands::push_workspace_branch,
+            backend::commands::commit_workspace_changes,
+            backend::commands::generate_commit_message,
+            backend::commands::suggest_thread_title,
+            backend::commands::unshallow_workspace,
+            backend::commands::fetch_workspace_remote,
+            backend::commands::bootstrap_demo_workspace,
+            backend::commands::get_line_blame,
+            backend::commands::list_workspace_commits,
+            backend::commands::get_commit_detail,
+            backend::commands::list_stale_workspaces,
+            backend::commands::watch_workspace,
+            backend::commands::unwatch_workspace,
+            backend::commands::get_workspace_trust,
+            backend::commands::set_workspace_trust,
+            backend::commands::purge_workspace_data,
+            backend::commands::export_workspace_data,
+            backend::commands::database_maintenance,
             backend::commands::open_file_in_editor,
             backend::commands::get_ai_review_config,
             backend::commands::set_ai_review_api_key,
@@ -51,14 +97,60 @@ pub fn run() {
             backend::commands::get_opencode_sidecar_status,
             backend::commands::start_ai_review_run,
             backend::commands::cancel_ai_review_run,
+            backend::commands::cancel_ai_request,
             backend::commands::list_ai_review_runs,
             backend::commands::get_ai_review_run,
+            backend::commands::set_ai_review_run_approval,
+            backend::commands::publish_review_commit_status,
+            backend::commands::export_ai_review_run,
+            backend::commands::compare_ai_review_runs,
+            backend::commands::export_review_report,
+            backend::commands::checkout_run_snapshot,
+            backend::commands::list_run_snapshots,
+            backend::commands::remove_run_snapshot,
+            backend::commands::install_review_git_hook,
+            backend::commands::uninstall_review_git_hook,
+            backend::commands::save_review_schedule,
+            backend::commands::list_review_schedules,
+            backend::commands::delete_review_schedule,
+            backend::commands::create_reviewer_profile,
+            backend::commands::update_reviewer_profile,
+            backend::commands::list_reviewer_profiles,
+            backend::commands::delete_reviewer_profile,
+            backend::commands::start_review_group,
+            backend::commands::get_review_group,
+            backend::commands::list_review_groups,
+            backend::commands::summarize_review_group,
+            backend::commands::get_review_queue_status,
+            backend::commands::signal_ui_activity,
+            backend::commands::get_review_usage_summary,
+            backend::commands::get_model_performance_stats,
+            backend::commands::get_team_review_stats,
+            backend::commands::search_findings,
+            backend::commands::dismiss_finding,
+            backend::commands::mark_finding_fixed,
+            backend::commands::list_finding_states,
+            backend::commands::apply_finding_fix,
+            backend::commands::list_finding_actions,
+            backend::commands::set_finding_actions,
+            backend::commands::run_finding_action,
             backend::commands::create_inline_review_comment,
             backend::commands::list_inline_review_comments,
             backend::commands::generate_ai_review,
             backend::commands::generate_ai_follow_up,
-            backend::commands::run_code_intel_sync
+            backend::commands::run_code_intel_sync,
+            backend::commands::code_intel_sync_status,
+            backend::commands::get_code_intel_config,
+            backend::commands::set_code_intel_config,
+            backend::commands::get_symbol_definition,
+            backend::commands::list_symbol_references
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+/// Runs the headless review CLI with the process arguments and returns its exit code.
+pub fn run_cli() -> i32 {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    backend::commands::cli::run(&args)
+}
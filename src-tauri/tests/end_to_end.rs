@@ -0,0 +1,158 @@
+//! Connect, clone, diff, review and export against a fake git provider and a fake OpenAI
+//! server, with no network access or credentials.
+//!
+//! Run with `cargo test --features integration-test --test end_to_end`.
+
+use rovex_lib::integration_support::{
+    FakeChatRequest, FakeOpenAiServer, GitRepoFixture, IntegrationApp, FAKE_ACCOUNT_LOGIN,
+};
+use serde_json::json;
+
+const ACCESS_TOKEN: &str = "integration-token";
+const FINDING_TITLE: &str = "Empty config input panics";
+
+fn reply(request: &FakeChatRequest) -> String {
+    if request.stream {
+        return "Adds a config parser that reads the first line without checking for empty input."
+            .to_string();
+    }
+    json!({
+        "summary": "The parser indexes into its input without a length check.",
+        "findings": [{
+            "title": FINDING_TITLE,
+            "body": "`parse_config` reads `lines[0]` before checking that the input has any lines, so an empty config file panics instead of returning an error.",
+            "severity": "high",
+            "category": "correctness",
+            "confidence": 0.9,
+            "side": "additions",
+            "lineNumber": 3,
+            "suggestedPatch": null
+        }]
+    })
+    .to_string()
+}
+
+#[test]
+fn connect_clone_diff_review_and_export() {
+    tauri::async_runtime::block_on(async {
+        let server = FakeOpenAiServer::start(reply).unwrap();
+        let app = IntegrationApp::new(&server.base_url()).await.unwrap();
+        app.install_fake_provider("github", ACCESS_TOKEN).unwrap();
+
+        let origin = GitRepoFixture::init(app.origins_dir().join("acme").join("widgets")).unwrap();
+        origin
+            .write("README.md", "# Widgets\n")
+            .unwrap()
+            .commit("Initial commit")
+            .unwrap()
+            .checkout_new_branch("feature/config")
+            .unwrap()
+            .write(
+                "src/config.rs",
+                "pub fn parse_config(input: &str) -> String {\n    let lines: Vec<&str> = input.lines().collect();\n    lines[0].to_string()\n}\n",
+            )
+            .unwrap()
+            .commit("Add config parser")
+            .unwrap();
+
+        assert!(app.connect_provider("github", "wrong-token").await.is_err());
+        let connection = app.connect_provider("github", ACCESS_TOKEN).await.unwrap();
+        assert_eq!(connection["accountLogin"], FAKE_ACCOUNT_LOGIN);
+
+        let clone = app
+            .clone_repository("github", "acme/widgets")
+            .await
+            .unwrap();
+        assert_eq!(clone["repository"], "acme/widgets");
+        let workspace = clone["workspace"].as_str().unwrap().to_string();
+
+        let diff = app.compare_diff(&workspace, "origin/main").await.unwrap();
+        assert_eq!(diff["filesChanged"], 1);
+        assert!(diff["diff"].as_str().unwrap().contains("src/config.rs"));
+
+        let run = app.review(&workspace, "origin/main").await.unwrap();
+        assert_eq!(run["status"], "completed", "run: {run:#}");
+        let findings = run["findings"].as_array().unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0]["filePath"], "src/config.rs");
+        assert_eq!(findings[0]["lineNumber"], 3);
+        assert_eq!(findings[0]["title"], FINDING_TITLE);
+        assert!(server
+            .requests()
+            .iter()
+            .any(|request| !request.stream && request.prompt.contains("src/config.rs")));
+
+        let run_id = run["runId"].as_str().unwrap();
+        let settings = app
+            .thread_settings(run["threadId"].as_i64().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(settings["baseRef"], "origin/main");
+        assert_eq!(settings["lastRunId"], run_id);
+        assert_eq!(settings["workspace"], workspace.as_str());
+        assert_eq!(run["approval"], "pending");
+        assert_eq!(run["verdict"], "pass");
+        let run = app
+            .set_approval(run_id, "changes-requested", Some("Handle empty input"))
+            .await
+            .unwrap();
+        assert_eq!(run["approval"], "changes-requested");
+        assert_eq!(run["verdict"], "fail");
+        assert_eq!(
+            run["verdictReason"],
+            "Changes requested: Handle empty input"
+        );
+
+        let status = app
+            .publish_commit_status(run_id, "github", "acme/widgets")
+            .await
+            .unwrap();
+        assert_eq!(status["state"], "failure");
+        assert_eq!(status["sha"], run["head"]);
+        let published = app.published_commit_statuses();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0]["repository"], "acme/widgets");
+        assert_eq!(published[0]["context"], "rovex/review");
+        assert_eq!(
+            published[0]["description"],
+            "Rovex review failed: Changes requested: Handle empty input"
+        );
+
+        let sarif = app.export_sarif(run_id).await.unwrap();
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0]["message"]["text"]
+            .as_str()
+            .unwrap()
+            .contains(FINDING_TITLE));
+        assert_eq!(
+            sarif["runs"][0]["properties"]["approval"],
+            "changes-requested"
+        );
+
+        app.delete_thread_without_cascade(run["threadId"].as_i64().unwrap())
+            .await
+            .unwrap();
+        let report = app.database_maintenance(false).await.unwrap();
+        assert_eq!(report["integrityOk"], true);
+        assert!(report["orphanedRows"].as_i64().unwrap() > 0);
+        let orphaned = |report: &serde_json::Value, table: &str| {
+            report["orphans"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|orphan| orphan["table"] == table)
+                .unwrap()["rows"]
+                .as_i64()
+                .unwrap()
+        };
+        assert_eq!(orphaned(&report, "ai_review_runs"), 1);
+        assert_eq!(orphaned(&report, "ai_review_findings"), 1);
+        let repaired = app.database_maintenance(true).await.unwrap();
+        assert_eq!(repaired["orphansRemoved"], true);
+        assert_eq!(repaired["vacuumed"], true);
+        assert_eq!(repaired["statsAfter"]["freelistCount"], 0);
+        let report = app.database_maintenance(false).await.unwrap();
+        assert_eq!(report["orphanedRows"], 0);
+    });
+}
@@ -41,6 +41,8 @@ describe("buildReviewBranchSuggestionsFromWorkspaceBranches", () => {
           { name: "origin/feature/live", isCurrent: false },
         ],
         suggestedBaseRef: "origin/main",
+        remotes: [{ name: "origin", url: "https://github.com/acme/repo.git" }],
+        defaultRemote: "origin",
       },
       "origin/main",
       "feature/manual"
@@ -60,4 +62,34 @@ describe("buildReviewBranchSuggestionsFromWorkspaceBranches", () => {
       "feature/new",
     ]);
   });
+
+  it("offers the default remote's branches before origin's", () => {
+    const suggestions = buildReviewBranchSuggestionsFromWorkspaceBranches(
+      {
+        workspace: "/tmp/fork",
+        currentBranch: "fix",
+        branches: [{ name: "fix", isCurrent: true }],
+        upstreamBranch: "upstream/main",
+        remoteBranches: [
+          { name: "origin/main", isCurrent: false },
+          { name: "upstream/main", isCurrent: true },
+        ],
+        suggestedBaseRef: "upstream/main",
+        remotes: [
+          { name: "origin", url: "https://github.com/me/repo.git" },
+          { name: "upstream", url: "https://github.com/acme/repo.git" },
+        ],
+        defaultRemote: "upstream",
+      },
+      "",
+      ""
+    );
+
+    expect(suggestions.suggestedBaseRef).toBe("upstream/main");
+    expect(suggestions.baseRefTargets.slice(0, 3)).toEqual([
+      "upstream/main",
+      "upstream/master",
+      "origin/main",
+    ]);
+  });
 });
@@ -52,10 +52,13 @@ export function buildReviewBranchSuggestionsFromWorkspaceBranches(
   const remoteTargets = result.remoteBranches.map((branch) => branch.name);
   const localTargets = result.branches.map((branch) => branch.name);
   const branchTargets = dedupeRefTargets([currentBranch, fallbackReviewBranch, ...localTargets]);
+  const defaultRemote = result.defaultRemote?.trim() || "origin";
   const baseRefTargets = dedupeRefTargets([
     result.suggestedBaseRef,
     result.upstreamBranch,
     fallbackBaseRef,
+    `${defaultRemote}/main`,
+    `${defaultRemote}/master`,
     ...DEFAULT_BASE_REF_TARGETS,
     ...remoteTargets,
     ...localTargets,
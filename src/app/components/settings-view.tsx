@@ -316,7 +316,7 @@ export function SettingsView(props: SettingsViewProps) {
                               AI Review Provider
                             </p>
                             <p class="mt-1.5 text-[14px] leading-relaxed text-neutral-500">
-                              Configure which backend provider and model power reviews. Settings are applied immediately and persisted to <span class="font-mono text-neutral-300">.env</span>.
+                              Configure which backend provider and model power reviews. Settings are applied immediately and saved to the local database.
                             </p>
 
                             <div class="mt-4 rounded-xl border border-white/[0.06] bg-white/[0.015] p-4">
@@ -333,13 +333,6 @@ export function SettingsView(props: SettingsViewProps) {
                               <p class="mt-2 text-[13px] text-neutral-400">
                                 Model: <span class="font-mono text-neutral-300">{aiReviewConfig()?.reviewModel ?? "gpt-4.1-mini"}</span>
                               </p>
-                              <Show when={aiReviewConfig()?.envFilePath}>
-                                {(envPath) => (
-                                  <p class="mt-2 text-[12px] leading-relaxed text-neutral-500">
-                                    Saved to <span class="font-mono text-neutral-400">{envPath()}</span>
-                                  </p>
-                                )}
-                              </Show>
                             </div>
 
                             <form class="mt-4 max-w-xl space-y-3" onSubmit={(event) => void handleSaveAiSettings(event)}>
@@ -448,11 +441,24 @@ export function SettingsView(props: SettingsViewProps) {
                                         </p>
                                       )}
                                     </Show>
+                                    <Show when={opencodeSidecarStatus()?.architecture}>
+                                      {(architecture) => (
+                                        <p class="mt-2 text-[12px] text-neutral-400">
+                                          Architecture: <span class="font-mono text-neutral-300">{architecture()}</span>
+                                          {opencodeSidecarStatus()?.checksumVerified ? " (checksum verified)" : ""}
+                                        </p>
+                                      )}
+                                    </Show>
                                     <Show when={opencodeSidecarStatus()?.detail}>
                                       {(detail) => (
                                         <p class="mt-2 text-[12px] text-neutral-500">{detail()}</p>
                                       )}
                                     </Show>
+                                    <Show when={opencodeSidecarStatus()?.remediation}>
+                                      {(remediation) => (
+                                        <p class="mt-2 text-[12px] text-amber-300/90">{remediation()}</p>
+                                      )}
+                                    </Show>
                                     <Show when={opencodeSidecarLoadError()}>
                                       {(message) => (
                                         <p class="mt-2 text-[12px] text-rose-300/90">{message()}</p>
@@ -615,7 +621,7 @@ export function SettingsView(props: SettingsViewProps) {
                                   {aiApiKeyBusy() ? "Saving..." : "Save API key"}
                                 </Button>
                                 <span class="text-[12px] text-neutral-500">
-                                  Applied immediately for this running app and persisted to <span class="font-mono">.env</span>.
+                                  Applied immediately and saved to the OS keychain.
                                 </span>
                               </div>
                             </form>
@@ -3,6 +3,7 @@ import type { AiReviewFinding } from "@/lib/backend";
 import type { ReviewRun } from "@/app/review-types";
 import {
   buildIssuesEmptyMessage,
+  formatEtaLabel,
   resolveSelectedRun,
   sortFindingsBySeverity,
 } from "@/app/components/workspace-review-sidebar-view-model";
@@ -52,6 +53,12 @@ describe("sortFindingsBySeverity", () => {
         body: "body",
         severity: "low",
         confidence: null,
+        category: null,
+        fingerprint: null,
+        status: null,
+        codeHash: null,
+        recurring: null,
+        suggestedPatch: null,
       },
       {
         id: "1",
@@ -65,6 +72,12 @@ describe("sortFindingsBySeverity", () => {
         body: "body",
         severity: "high",
         confidence: null,
+        category: null,
+        fingerprint: null,
+        status: null,
+        codeHash: null,
+        recurring: null,
+        suggestedPatch: null,
       },
       {
         id: "2",
@@ -78,11 +91,42 @@ describe("sortFindingsBySeverity", () => {
         body: "body",
         severity: "high",
         confidence: null,
+        category: null,
+        fingerprint: null,
+        status: null,
+        codeHash: null,
+        recurring: null,
+        suggestedPatch: null,
       },
     ];
 
     expect(sortFindingsBySeverity(findings).map((finding) => finding.id)).toEqual(["2", "1", "3"]);
   });
+
+  it("lists new findings before recurring ones", () => {
+    const base: AiReviewFinding = {
+      id: "new",
+      filePath: "a.ts",
+      chunkId: "x",
+      chunkIndex: 0,
+      hunkHeader: "@@",
+      side: "additions",
+      lineNumber: 1,
+      title: "title",
+      body: "body",
+      severity: "low",
+      confidence: null,
+      category: null,
+      fingerprint: null,
+      status: null,
+      codeHash: null,
+      recurring: false,
+      suggestedPatch: null,
+    };
+    const findings = [{ ...base, id: "repeat", severity: "critical", recurring: true }, base];
+
+    expect(sortFindingsBySeverity(findings).map((finding) => finding.id)).toEqual(["new", "repeat"]);
+  });
 });
 
 describe("buildIssuesEmptyMessage", () => {
@@ -106,3 +150,16 @@ describe("buildIssuesEmptyMessage", () => {
     ).toContain("Scanning files");
   });
 });
+
+describe("formatEtaLabel", () => {
+  it("hides the label until an estimate is available", () => {
+    expect(formatEtaLabel(null)).toBeNull();
+    expect(formatEtaLabel(0)).toBeNull();
+  });
+
+  it("formats seconds and minutes", () => {
+    expect(formatEtaLabel(12_400)).toBe("~12s left");
+    expect(formatEtaLabel(120_000)).toBe("~2m left");
+    expect(formatEtaLabel(130_000)).toBe("~2m 10s left");
+  });
+});
@@ -6,6 +6,7 @@ import type {
   AiReviewProgressEvent,
 } from "@/lib/backend";
 import type { ReviewRun } from "@/app/review-types";
+import { comparePaths } from "@/lib/name-sort";
 
 export type WorkspaceReviewSidebarDerivedModel = {
   reviewRuns: Accessor<ReviewRun[]>;
@@ -43,9 +44,11 @@ export function resolveSelectedRun(runs: ReviewRun[], selectedRunId: string | nu
 
 export function sortFindingsBySeverity(findings: AiReviewFinding[]) {
   return [...findings].sort((left, right) => {
+    const byRecurring = Number(left.recurring === true) - Number(right.recurring === true);
+    if (byRecurring !== 0) return byRecurring;
     const bySeverity = severityRank(left.severity) - severityRank(right.severity);
     if (bySeverity !== 0) return bySeverity;
-    const byPath = left.filePath.localeCompare(right.filePath);
+    const byPath = comparePaths(left.filePath, right.filePath);
     if (byPath !== 0) return byPath;
     return left.lineNumber - right.lineNumber;
   });
@@ -123,13 +126,16 @@ function buildIssueFileCards(
     });
     card.summary = chunk.summary;
     card.findings = chunk.findings;
-    if (card.status !== "failed") {
+    if (chunk.outcome && chunk.outcome !== "reviewed") {
+      card.status = "failed";
+      card.errorMessage = card.errorMessage ?? chunk.summary;
+    } else if (card.status !== "failed") {
       card.status = chunk.findings.length > 0 ? "issues" : "clean";
     }
   }
 
   return [...byId.values()].sort((left, right) => {
-    const byPath = left.filePath.localeCompare(right.filePath);
+    const byPath = comparePaths(left.filePath, right.filePath);
     if (byPath !== 0) return byPath;
     return left.chunkIndex - right.chunkIndex;
   });
@@ -152,6 +158,15 @@ export function buildIssuesEmptyMessage(args: {
   return "No issues found for this run.";
 }
 
+export function formatEtaLabel(etaMs: number | null) {
+  if (etaMs === null || etaMs <= 0) return null;
+  const totalSeconds = Math.max(1, Math.round(etaMs / 1000));
+  const minutes = Math.floor(totalSeconds / 60);
+  const seconds = totalSeconds % 60;
+  if (minutes === 0) return `~${seconds}s left`;
+  return seconds === 0 ? `~${minutes}m left` : `~${minutes}m ${seconds}s left`;
+}
+
 export function useWorkspaceReviewSidebarViewModel(model: WorkspaceReviewSidebarDerivedModel) {
   const selectedRun = createMemo<ReviewRun | null>(() =>
     resolveSelectedRun(model.reviewRuns(), model.selectedRunId())
@@ -180,6 +195,15 @@ export function useWorkspaceReviewSidebarViewModel(model: WorkspaceReviewSidebar
     if (!progress || progress.totalChunks <= 0) return 0;
     return Math.round((progress.completedChunks / progress.totalChunks) * 100);
   });
+  const etaLabel = createMemo(() => {
+    if (selectedRun()?.status !== "running") return null;
+    const events = visibleProgressEvents();
+    for (let index = events.length - 1; index >= 0; index -= 1) {
+      const etaMs = events[index].etaMs;
+      if (typeof etaMs === "number") return formatEtaLabel(etaMs);
+    }
+    return null;
+  });
   const issuesEmptyMessage = createMemo(() =>
     buildIssuesEmptyMessage({
       chunkCount: visibleChunkReviews().length,
@@ -214,6 +238,7 @@ export function useWorkspaceReviewSidebarViewModel(model: WorkspaceReviewSidebar
     cleanIssueFileCards,
     latestProgress,
     progressRatio,
+    etaLabel,
     issuesEmptyMessage,
   };
 }
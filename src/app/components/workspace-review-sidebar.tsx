@@ -210,6 +210,7 @@ export function WorkspaceReviewSidebar(props: WorkspaceReviewSidebarProps) {
   const cleanIssueFileCards = derived.cleanIssueFileCards;
   const latestProgress = derived.latestProgress;
   const progressRatio = derived.progressRatio;
+  const etaLabel = derived.etaLabel;
   const issuesEmptyMessage = derived.issuesEmptyMessage;
   const activeCancelableRun = createMemo<ReviewRun | null>(() => {
     const selected = selectedRun();
@@ -237,6 +238,8 @@ export function WorkspaceReviewSidebar(props: WorkspaceReviewSidebarProps) {
   const branchCreateInputId = "workspace-branch-create-input";
   const [chatPromptCursorPosition, setChatPromptCursorPosition] = createSignal(0);
   const diffFilePaths = createMemo(() => {
+    const files = model.compareResult()?.files;
+    if (files) return [...new Set(files.map((file) => normalizeDiffPath(file.path)).filter(Boolean))];
     const patch = model.compareResult()?.diff ?? "";
     if (!patch.trim()) return [];
     return collectDiffFilePathsFromPatch(patch);
@@ -368,10 +371,14 @@ export function WorkspaceReviewSidebar(props: WorkspaceReviewSidebarProps) {
           </Show>
           <For each={card.findings}>
             {(finding) => (
-              <div class="rounded-md border border-white/[0.08] bg-black/20 px-2.5 py-2">
+              <div
+                class="rounded-md border border-white/[0.08] bg-black/20 px-2.5 py-2"
+                classList={{ "opacity-60": finding.recurring === true }}
+              >
                 <div class="mb-1 flex items-center justify-between gap-2">
                   <p class="truncate text-[11px] font-semibold uppercase tracking-[0.08em] text-amber-200/90">
                     {finding.severity}
+                    {finding.recurring ? " • recurring" : ""}
                   </p>
                   <span class="text-[11px] text-neutral-400">{finding.lineNumber}</span>
                 </div>
@@ -534,6 +541,20 @@ export function WorkspaceReviewSidebar(props: WorkspaceReviewSidebarProps) {
                           >
                             {run().status}
                           </span>
+                          <Show when={run().verdict}>
+                            {(verdict) => (
+                              <span
+                                class={`rounded-full px-2 py-0.5 text-[10px] font-semibold uppercase tracking-[0.08em] ${
+                                  verdict() === "pass"
+                                    ? "bg-emerald-500/15 text-emerald-300"
+                                    : "bg-rose-500/15 text-rose-300"
+                                }`}
+                                title={run().verdictReason ?? undefined}
+                              >
+                                {verdict()}
+                              </span>
+                            )}
+                          </Show>
                           <Show when={run().status === "queued" || run().status === "running"}>
                             <button
                               type="button"
@@ -563,6 +584,7 @@ export function WorkspaceReviewSidebar(props: WorkspaceReviewSidebarProps) {
                             </div>
                             <p class="mt-1 text-[10px] uppercase tracking-[0.08em] text-neutral-500">
                               {progress().completedChunks}/{progress().totalChunks} chunks analyzed
+                              {etaLabel() ? ` • ${etaLabel()}` : ""}
                             </p>
                           </div>
                         )}
@@ -767,6 +789,30 @@ export function WorkspaceReviewSidebar(props: WorkspaceReviewSidebarProps) {
                 </div>
               </Show>
 
+              <Show
+                when={
+                  model.aiPrompt().trim().length === 0 &&
+                  (selectedRun()?.suggestedFollowUps ?? []).length > 0
+                }
+              >
+                <div class="mb-2 flex flex-wrap gap-1.5">
+                  <For each={selectedRun()?.suggestedFollowUps ?? []}>
+                    {(question) => (
+                      <button
+                        type="button"
+                        class="rounded-full border border-white/[0.09] bg-white/[0.035] px-2.5 py-1 text-left text-[12px] text-neutral-300 transition-colors hover:border-amber-300/30 hover:text-amber-100"
+                        onClick={() => {
+                          model.setAiPrompt(question);
+                          chatPromptInputRef?.focus();
+                        }}
+                      >
+                        {question}
+                      </button>
+                    )}
+                  </For>
+                </div>
+              </Show>
+
               <form
                 class="review-chat-composer"
                 onSubmit={(event) => void model.handleAskAiFollowUp(event)}
@@ -27,7 +27,8 @@ export function useWorkspaceResources(args: UseWorkspaceResourcesArgs) {
     args.selectedThreadId,
     async (threadId): Promise<ThreadMessage[]> => {
       if (threadId == null) return [];
-      return listThreadMessages(threadId, 100);
+      const response = await listThreadMessages({ threadId, limit: 100 });
+      return response.messages;
     }
   );
   const [persistedReviewRuns, { refetch: refetchAiReviewRuns }] = createResource(
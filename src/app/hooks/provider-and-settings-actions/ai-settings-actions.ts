@@ -72,7 +72,7 @@ export function createAiSettingsActions(args: AiSettingsActionsArgs) {
         reviewModel: model,
         opencodeProvider: opencodeProvider || "openai",
         opencodeModel: opencodeModel || null,
-        persistToEnv: true,
+        persist: true,
       });
       await aiSettingsState.refetchAiReviewConfig();
       if (provider === "opencode") {
@@ -135,17 +135,13 @@ export function createAiSettingsActions(args: AiSettingsActionsArgs) {
 
     apiKeyState.setAiApiKeyBusy(true);
     try {
-      const config = await setAiReviewApiKey({
+      await setAiReviewApiKey({
         apiKey,
-        persistToEnv: true,
+        persist: true,
       });
       apiKeyState.setAiApiKeyInput("");
       await aiSettingsState.refetchAiReviewConfig();
-      apiKeyState.setAiApiKeyStatus(
-        config.envFilePath
-          ? `Saved OPENAI_API_KEY to ${config.envFilePath}.`
-          : "Saved OPENAI_API_KEY."
-      );
+      apiKeyState.setAiApiKeyStatus("Saved OPENAI_API_KEY.");
     } catch (error) {
       apiKeyState.setAiApiKeyError(toErrorMessage(error));
     } finally {
@@ -1,4 +1,10 @@
-import { checkoutWorkspaceBranch, createThread, deleteThread } from "@/lib/backend";
+import {
+  checkoutWorkspaceBranch,
+  createThread,
+  deleteThread,
+  setThreadSettings,
+  suggestThreadTitle,
+} from "@/lib/backend";
 import { toErrorMessage } from "@/app/hooks/error-utils";
 import type { RepoGroup, RepoReview, RepoReviewDefaults } from "@/app/types";
 import type { UseProviderAndSettingsActionsArgs } from "@/app/hooks/provider-and-settings-action-types";
@@ -94,6 +100,15 @@ export function createRepoManagementActions(args: RepoManagementActionsArgs) {
         workspace,
       });
       await repoState.refetchThreads();
+      await setThreadSettings({ threadId: thread.id, baseRef });
+      if (!draft?.goal?.trim() && !savedDefaults?.goal?.trim()) {
+        // Without a goal the title is generic, so name the thread after its changes.
+        void suggestThreadTitle({ threadId: thread.id, workspace, baseRef })
+          .then(() => repoState.refetchThreads())
+          .catch((error) => {
+            console.error("[rovex review] Failed to suggest a thread title:", error);
+          });
+      }
       repoState.setSelectedThreadId(thread.id);
       repoState.setSelectedBaseRef(baseRef);
       repoState.setReviewDefaultsByRepo((current) => ({
@@ -2,6 +2,7 @@ import {
   checkoutWorkspaceBranch,
   compareWorkspaceDiff,
   createWorkspaceBranch,
+  fetchWorkspaceRemote,
   listInlineReviewComments,
 } from "@/lib/backend";
 import { toErrorMessage } from "@/app/hooks/error-utils";
@@ -12,6 +13,9 @@ type BranchAndCompareActionsArgs = Pick<
   "selection" | "compare" | "branch" | "comments"
 >;
 
+/** How old the last fetch may be before a comparison fetches the remote again in the background. */
+const REMOTE_FETCH_STALE_MS = 60_000;
+
 export function createBranchAndCompareActions(args: BranchAndCompareActionsArgs) {
   const { selection, compare, branch, comments } = args;
   let compareRequestSequence = 0;
@@ -88,11 +92,13 @@ export function createBranchAndCompareActions(args: BranchAndCompareActionsArgs)
     }
   };
 
-  const handleCompareSelectedReview = async (target: { baseRef?: string; fetchRemote?: boolean } = {}) => {
+  const handleCompareSelectedReview = async (
+    target: { baseRef?: string; fetchRemote?: boolean; force?: boolean } = {}
+  ) => {
     const requestSequence = ++compareRequestSequence;
     const threadIdAtStart = selection.selectedThreadId();
     const baseRef = target.baseRef?.trim() || selection.selectedBaseRef().trim() || "origin/main";
-    const fetchRemote = target.fetchRemote ?? baseRef.startsWith("origin/");
+    const fetchRemote = target.fetchRemote ?? true;
     compare.setCompareError(null);
 
     const workspace = selection.selectedWorkspace().trim();
@@ -109,10 +115,13 @@ export function createBranchAndCompareActions(args: BranchAndCompareActionsArgs)
 
     compare.setCompareBusy(true);
     try {
+      // The diff shows local refs right away; the base ref's remote is fetched afterwards if
+      // it is stale.
       const result = await compareWorkspaceDiff({
         workspace,
         baseRef,
-        fetchRemote,
+        fetchRemote: false,
+        force: target.force,
       });
       if (requestSequence !== compareRequestSequence) {
         return;
@@ -142,6 +151,10 @@ export function createBranchAndCompareActions(args: BranchAndCompareActionsArgs)
         console.error("[rovex review] Failed to load inline review comments:", error);
         comments.setInlineReviewComments([]);
       }
+      const fetchedAt = result.lastFetchedAt ?? 0;
+      if (fetchRemote && result.remote && Date.now() - fetchedAt > REMOTE_FETCH_STALE_MS) {
+        void refreshRemoteInBackground(workspace, result.remote, baseRef, requestSequence);
+      }
     } catch (error) {
       if (requestSequence !== compareRequestSequence) {
         return;
@@ -155,6 +168,24 @@ export function createBranchAndCompareActions(args: BranchAndCompareActionsArgs)
     }
   };
 
+  const refreshRemoteInBackground = async (
+    workspace: string,
+    remote: string,
+    baseRef: string,
+    requestSequence: number
+  ) => {
+    try {
+      await fetchWorkspaceRemote({ workspace, remote });
+    } catch (error) {
+      console.error(`[rovex review] Failed to fetch ${remote}:`, error);
+      return;
+    }
+    if (requestSequence !== compareRequestSequence) {
+      return;
+    }
+    await handleCompareSelectedReview({ baseRef, fetchRemote: false });
+  };
+
   const handleOpenDiffViewer = async () => {
     if (compare.compareResult()) {
       compare.setShowDiffViewer((current) => !current);
@@ -46,6 +46,10 @@ function makePersistedRun(args: {
     startedAt: args.startedAt ?? "2026-02-20T00:00:01.000Z",
     endedAt: args.endedAt ?? null,
     canceledAt: null,
+    resourceUsage: null,
+    tokenUsage: null,
+    reviewMode: "full",
+    incrementalBaseHead: null,
   };
 }
 
@@ -94,6 +98,7 @@ describe("mapPersistedReviewRun", () => {
         findingCount: null,
         chunk: null,
         finding: null,
+        etaMs: null,
       },
     ];
     const mapped = mapPersistedReviewRun(persisted);
@@ -159,6 +164,7 @@ describe("progress-based status resolution", () => {
         findingCount: null,
         chunk: null,
         finding: null,
+        etaMs: null,
       },
     ]);
     expect(status).toBe("completed_with_errors");
@@ -179,6 +185,7 @@ describe("progress-based status resolution", () => {
         findingCount: null,
         chunk: null,
         finding: null,
+        etaMs: null,
       },
     ]);
     expect(status).toBe("running");
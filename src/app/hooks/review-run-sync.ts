@@ -49,7 +49,10 @@ export function deriveTerminalStatusFromProgressEvents(
   if (lastMeaningfulEvent.completedChunks < lastMeaningfulEvent.totalChunks) return null;
 
   const hadErrors = progressEvents.some(
-    (event) => event.status === "chunk-failed" || event.status === "description-failed"
+    (event) =>
+      event.status === "chunk-failed" ||
+      event.status === "description-failed" ||
+      event.status === "auth-required"
   );
   return hadErrors ? "completed_with_errors" : "completed";
 }
@@ -83,6 +86,9 @@ export function mapPersistedReviewRun(run: PersistedAiReviewRun): ReviewRun {
     progressEvents: run.progressEvents,
     chunks: run.chunks,
     findings: run.findings,
+    verdict: run.verdict,
+    verdictReason: run.verdictReason,
+    suggestedFollowUps: run.suggestedFollowUps,
   };
 }
 
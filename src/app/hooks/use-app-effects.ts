@@ -20,6 +20,8 @@ import type {
   CompareWorkspaceDiffResult,
   InlineReviewComment,
 } from "@/lib/backend";
+import { getThreadSettings, signalUiActivity } from "@/lib/backend";
+import { comparePaths } from "@/lib/name-sort";
 import { createFullReviewScope, type ReviewScope } from "@/app/review-scope";
 import {
   hasActiveReviewRuns,
@@ -88,10 +90,11 @@ type UseAppEffectsArgs = {
   fileOpenWith: Accessor<string>;
   ghosttyOpenCommand: Accessor<string>;
   reviewDefaultsByRepo: Accessor<Record<string, RepoReviewDefaults>>;
-  handleCompareSelectedReview: () => void | Promise<void>;
+  handleCompareSelectedReview: (target?: { baseRef?: string }) => void | Promise<void>;
 };
 
 const WORKSPACE_BRANCHES_STALE_MS = 30_000;
+const UI_ACTIVITY_SIGNAL_INTERVAL_MS = 1_000;
 
 export function useAppEffects(args: UseAppEffectsArgs) {
   createEffect(() => {
@@ -267,7 +270,17 @@ export function useAppEffects(args: UseAppEffectsArgs) {
     const workspace = args.selectedWorkspace().trim();
     if (!workspace) return;
 
-    void args.handleCompareSelectedReview();
+    void (async () => {
+      // A thread compares against the base ref its reviews used, or the one set for it.
+      const baseRef = await getThreadSettings({ threadId })
+        .then((settings) => settings.baseRef ?? undefined)
+        .catch((error) => {
+          console.error("[rovex review] Failed to load thread settings:", error);
+          return undefined;
+        });
+      if (args.selectedThreadId() !== threadId) return;
+      await args.handleCompareSelectedReview(baseRef ? { baseRef } : undefined);
+    })();
   });
 
   createEffect(() => {
@@ -325,7 +338,7 @@ export function useAppEffects(args: UseAppEffectsArgs) {
             next.push(chunk);
           }
           return next.sort((left, right) =>
-            left.filePath.localeCompare(right.filePath) || left.chunkIndex - right.chunkIndex
+            comparePaths(left.filePath, right.filePath) || left.chunkIndex - right.chunkIndex
           );
         });
       }
@@ -398,7 +411,7 @@ export function useAppEffects(args: UseAppEffectsArgs) {
             ...nextRun,
             chunks: nextChunks.sort(
               (left, right) =>
-                left.filePath.localeCompare(right.filePath) || left.chunkIndex - right.chunkIndex
+                comparePaths(left.filePath, right.filePath) || left.chunkIndex - right.chunkIndex
             ),
           };
         }
@@ -449,7 +462,10 @@ export function useAppEffects(args: UseAppEffectsArgs) {
             status: "completed",
             endedAt: Date.now(),
           };
-        } else if (payload.status === "description-failed") {
+        } else if (
+          payload.status === "description-failed" ||
+          payload.status === "auth-required"
+        ) {
           nextRun = {
             ...nextRun,
             error: nextRun.error ?? payload.message,
@@ -522,4 +538,26 @@ export function useAppEffects(args: UseAppEffectsArgs) {
       window.clearInterval(interval);
     });
   });
+
+  createEffect(() => {
+    if (typeof window === "undefined" || !args.aiReviewBusy()) return;
+    // Lets running reviews back off while the user types or scrolls.
+    let lastSignalAt = 0;
+    const handleActivity = () => {
+      const now = Date.now();
+      if (now - lastSignalAt < UI_ACTIVITY_SIGNAL_INTERVAL_MS) return;
+      lastSignalAt = now;
+      void signalUiActivity().catch(() => undefined);
+    };
+    const activityEvents = ["keydown", "wheel", "scroll", "pointerdown"] as const;
+    for (const eventName of activityEvents) {
+      window.addEventListener(eventName, handleActivity, { capture: true, passive: true });
+    }
+    onCleanup(() => {
+      for (const eventName of activityEvents) {
+        window.removeEventListener(eventName, handleActivity, { capture: true });
+      }
+      void signalUiActivity({ active: false }).catch(() => undefined);
+    });
+  });
 }
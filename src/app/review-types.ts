@@ -2,6 +2,7 @@ import type {
   AiReviewChunk,
   AiReviewFinding,
   AiReviewProgressEvent,
+  AiReviewVerdict,
 } from "@/lib/backend";
 import type { ReviewScope } from "@/app/review-scope";
 
@@ -36,6 +37,9 @@ export type ReviewRun = {
   progressEvents: AiReviewProgressEvent[];
   chunks: AiReviewChunk[];
   findings: AiReviewFinding[];
+  verdict?: AiReviewVerdict | null;
+  verdictReason?: string | null;
+  suggestedFollowUps?: string[];
 };
 
 export type ReviewChatSharedDiffContext = {
@@ -13,6 +13,36 @@ export type Thread = {
   title: string;
   workspace: string | null;
   createdAt: string;
+  pinned: boolean;
+  archivedAt: string | null;
+};
+
+export type ListThreadMessagesInput = {
+  threadId: number;
+  limit?: number | null;
+  beforeId?: number | null;
+  afterId?: number | null;
+};
+
+export type ListThreadMessagesResult = {
+  messages: Message[];
+  totalCount: number;
+  hasMore: boolean;
+};
+
+export type RenameThreadInput = {
+  threadId: number;
+  title: string;
+};
+
+export type ArchiveThreadInput = {
+  threadId: number;
+  archived?: boolean | null;
+};
+
+export type PinThreadInput = {
+  threadId: number;
+  pinned?: boolean | null;
 };
 
 export type Message = {
@@ -26,16 +56,93 @@ export type Message = {
 export type CreateThreadInput = {
   title: string;
   workspace?: string | null;
+  idempotencyKey?: string | null;
 };
 
 export type AddThreadMessageInput = {
   threadId: number;
   role: MessageRole;
   content: string;
+  idempotencyKey?: string | null;
 };
 
 export type ProviderKind = "github" | "gitlab";
 
+export type CodeIntelSyncStatusInput = {
+  projectRoot?: string | null;
+  threadId?: number | null;
+  respectGitignore?: boolean | null;
+};
+
+export type CodeIntelOptions = {
+  paths?: string[] | null;
+  include?: string[] | null;
+  exclude?: string[] | null;
+  respectGitignore?: boolean | null;
+  useScip?: boolean | null;
+  kitedbStorePath?: string | null;
+  vectorBaseUrl?: string | null;
+  vectorModel?: string | null;
+  vectorDimension?: number | null;
+  vectorTimeoutMs?: number | null;
+  vectorBatchSize?: number | null;
+  vectorConcurrency?: number | null;
+};
+
+export type GetCodeIntelConfigInput = {
+  workspace: string;
+};
+
+export type SetCodeIntelConfigInput = CodeIntelOptions & {
+  workspace: string;
+};
+
+export type CodeIntelWorkspaceConfig = CodeIntelOptions & {
+  workspace: string;
+  updatedAt: string | null;
+};
+
+export type CodeIntelSyncStatus = {
+  projectRoot: string;
+  lastRunId: string | null;
+  lastSyncedAt: string | null;
+  stale: boolean;
+  filesSynced: number;
+  filesUnchanged: number;
+  filesAdded: number;
+  filesModified: number;
+  filesRemoved: number;
+  stalePaths: string[];
+};
+
+export type SymbolLookupInput = {
+  workspace: string;
+  symbol?: string | null;
+  filePath?: string | null;
+  line?: number | null;
+  limit?: number | null;
+};
+
+export type CodeSymbolLocation = {
+  symbolName: string;
+  nodeKind: string;
+  graphLayer: "syntax" | "semantic";
+  filePath: string;
+  startLine: number | null;
+  endLine: number | null;
+  scipSymbol: string | null;
+};
+
+export type GetSymbolDefinitionResult = {
+  symbol: string | null;
+  definitions: CodeSymbolLocation[];
+};
+
+export type ListSymbolReferencesResult = {
+  symbol: string | null;
+  references: CodeSymbolLocation[];
+};
+
 export type ConnectProviderInput = {
   provider: ProviderKind;
   accessToken: string;
@@ -81,6 +188,7 @@ export type CloneRepositoryInput = {
   destinationRoot?: string | null;
   directoryName?: string | null;
   shallow?: boolean;
+  recurseSubmodules?: boolean | null;
 };
 
 export type CloneRepositoryResult = {
@@ -89,12 +197,75 @@ export type CloneRepositoryResult = {
   workspace: string;
 };
 
+export type RepositoriesDirResult = {
+  repositoriesDir: string;
+};
+
+export type MigrateRepositoriesDirInput = {
+  destination: string;
+};
+
+export type MigrateRepositoriesDirResult = {
+  previousDir: string;
+  repositoriesDir: string;
+  movedRepositories: string[];
+  updatedThreads: number;
+  updatedReviewRuns: number;
+  updatedInlineComments: number;
+};
+
 export type CompareWorkspaceDiffInput = {
   workspace: string;
   baseRef?: string | null;
   fetchRemote?: boolean;
+  includePaths?: string[] | null;
+  excludePaths?: string[] | null;
+  diffTarget?: WorkspaceDiffTarget | null;
+  includeUntracked?: boolean;
+  headRef?: string | null;
+  includeSubmoduleDiffs?: boolean | null;
+  force?: boolean | null;
+  remote?: string | null;
+};
+
+export type WorkspaceSubmoduleChangeStatus = "added" | "removed" | "modified";
+
+export type WorkspaceSubmoduleChange = {
+  path: string;
+  status: WorkspaceSubmoduleChangeStatus;
+  oldCommit: string | null;
+  newCommit: string | null;
+  workspace: string | null;
+  diff: string | null;
+};
+
+export type DiffFileStatus = "added" | "modified" | "deleted" | "renamed" | "copied";
+
+export type DiffHunk = {
+  header: string;
+  oldStart: number;
+  oldLines: number;
+  newStart: number;
+  newLines: number;
+  additionLines: number[];
+  deletionLines: number[];
 };
 
+export type DiffFile = {
+  path: string;
+  previousPath: string | null;
+  status: DiffFileStatus;
+  similarity: number | null;
+  binary: boolean;
+  language: string | null;
+  insertions: number;
+  deletions: number;
+  hunks: DiffHunk[];
+  truncated: boolean;
+};
+
+export type WorkspaceDiffTarget = "branch" | "working-tree" | "staged";
+
 export type CompareWorkspaceDiffProfile = {
   fetchOriginMs: number | null;
   resolveBaseRefMs: number;
@@ -111,18 +282,79 @@ export type CompareWorkspaceDiffResult = {
   mergeBase: string;
   head: string;
   diff: string;
+  files: DiffFile[];
   filesChanged: number;
   insertions: number;
   deletions: number;
   diffTruncated: boolean;
   diffBytesUsed: number;
   diffBytesTotal: number;
+  diffTarget: WorkspaceDiffTarget;
   profile: CompareWorkspaceDiffProfile;
+  repoConfigPath: string | null;
+  historyDeepenedBy: number | null;
+  submodules: WorkspaceSubmoduleChange[];
+  skippedFiles: AiReviewSkippedChunk[];
+  cached: boolean;
+  lastFetchedAt: number | null;
+  remote: string | null;
+};
+
+export type NameSortOptions = {
+  numeric?: boolean | null;
+  caseSensitive?: boolean | null;
+  accentSensitive?: boolean | null;
+};
+
+export type ListWorkspaceFilesInput = {
+  workspace: string;
+  path?: string | null;
+  recursive?: boolean | null;
+  respectGitignore?: boolean | null;
+  offset?: number | null;
+  limit?: number | null;
+};
+
+export type WorkspaceFileKind = "file" | "directory" | "symlink";
+
+export type WorkspaceFileEntry = {
+  path: string;
+  name: string;
+  kind: WorkspaceFileKind;
+  sizeBytes: number | null;
+};
+
+export type ListWorkspaceFilesResult = {
+  workspace: string;
+  path: string;
+  entries: WorkspaceFileEntry[];
+  totalCount: number;
+  hasMore: boolean;
+  truncated: boolean;
+};
+
+export type ReadWorkspaceFileInput = {
+  workspace: string;
+  path: string;
+  startLine?: number | null;
+  endLine?: number | null;
+};
+
+export type ReadWorkspaceFileResult = {
+  path: string;
+  content: string;
+  startLine: number;
+  endLine: number;
+  totalLines: number;
+  truncated: boolean;
+  sizeBytes: number;
 };
 
 export type ListWorkspaceBranchesInput = {
   workspace: string;
   fetchRemote?: boolean;
+  remote?: string | null;
+  sort?: NameSortOptions | null;
 };
 
 export type WorkspaceBranch = {
@@ -137,236 +369,1313 @@ export type ListWorkspaceBranchesResult = {
   upstreamBranch: string | null;
   remoteBranches: WorkspaceBranch[];
   suggestedBaseRef: string;
+  remotes: WorkspaceRemote[];
+  defaultRemote: string;
+};
+
+export type WorkspaceRemote = {
+  name: string;
+  url: string | null;
 };
 
 export type CheckoutWorkspaceBranchInput = {
   workspace: string;
   branchName: string;
+  autoStash?: boolean;
+};
+
+export type BranchSwitchStash = {
+  stashCommit: string;
+  restored: boolean;
+  conflicts: string[];
 };
 
 export type CheckoutWorkspaceBranchResult = {
   workspace: string;
   branchName: string;
+  stash: BranchSwitchStash | null;
 };
 
 export type CreateWorkspaceBranchInput = {
   workspace: string;
   branchName: string;
   fromRef?: string | null;
+  autoStash?: boolean;
 };
 
-export type OpenFileInEditorInput = {
+export type WorkspaceStatusInput = {
   workspace: string;
-  filePath: string;
-  launcher: "vscode" | "cursor" | "ghostty";
-  ghosttyCommandTemplate?: string | null;
 };
 
-export type GenerateAiReviewInput = {
-  threadId: number;
+export type WorkspaceStatus = {
   workspace: string;
-  baseRef: string;
-  mergeBase: string;
+  branch: string | null;
+  head: string | null;
+  upstream: string | null;
+  ahead: number | null;
+  behind: number | null;
+  staged: number;
+  unstaged: number;
+  untracked: number;
+  conflicted: number;
+  stashCount: number;
+  clean: boolean;
+};
+
+export type PullWorkspaceBranchInput = {
+  workspace: string;
+  provider?: ProviderKind | null;
+};
+
+export type PullWorkspaceBranchStatus = "up-to-date" | "fast-forwarded" | "diverged";
+
+export type PullWorkspaceBranchResult = {
+  workspace: string;
+  branch: string;
+  upstream: string;
+  status: PullWorkspaceBranchStatus;
+  previousHead: string;
   head: string;
-  filesChanged: number;
-  insertions: number;
-  deletions: number;
-  diff: string;
-  prompt?: string | null;
+  ahead: number;
+  behind: number;
 };
 
-export type AiReviewFinding = {
-  id: string;
+export type PushWorkspaceBranchInput = {
+  workspace: string;
+  branch?: string | null;
+  remote?: string | null;
+  setUpstream?: boolean | null;
+  provider?: ProviderKind | null;
+};
+
+export type PushWorkspaceBranchStatus = "pushed" | "created" | "up-to-date";
+
+export type PushWorkspaceBranchResult = {
+  workspace: string;
+  branch: string;
+  remote: string;
+  remoteBranch: string;
+  status: PushWorkspaceBranchStatus;
+  head: string;
+  upstreamSet: boolean;
+};
+
+export type CommitWorkspaceChangesInput = {
+  workspace: string;
+  message: string;
+  paths?: string[] | null;
+};
+
+export type CommitWorkspaceChangesResult = {
+  workspace: string;
+  branch: string | null;
+  commit: string;
+  summary: string;
+  files: string[];
+};
+
+export type GenerateCommitMessageInput = {
+  workspace: string;
+  instructions?: string | null;
+};
+
+export type GenerateCommitMessageResult = {
+  message: string;
+  model: string;
+  diffTruncated: boolean;
+};
+
+export type SuggestThreadTitleInput = {
+  threadId?: number | null;
+  workspace?: string | null;
+  baseRef?: string | null;
+};
+
+export type SuggestThreadTitleResult = {
+  title: string;
+  model: string;
+  branch: string | null;
+  thread: Thread | null;
+};
+
+export type UnshallowWorkspaceInput = {
+  workspace: string;
+  depth?: number | null;
+  provider?: ProviderKind | null;
+};
+
+export type UnshallowWorkspaceResult = {
+  workspace: string;
+  remote: string;
+  wasShallow: boolean;
+  shallow: boolean;
+  commitCount: number;
+};
+
+export type FetchWorkspaceRemoteInput = {
+  workspace: string;
+  remote?: string | null;
+  provider?: ProviderKind | null;
+};
+
+export type FetchWorkspaceRemoteResult = {
+  workspace: string;
+  remote: string;
+  fetchedAt: number;
+  durationMs: number;
+};
+
+export type WorkspaceFetchProgressEvent = {
+  workspace: string;
+  remote: string;
+  phase: string;
+  percent: number | null;
+  current: number;
+  total: number | null;
+  transfer: string | null;
+};
+
+export type BootstrapDemoWorkspaceInput = {
+  destinationRoot?: string | null;
+};
+
+export type BootstrapDemoWorkspaceResult = {
+  workspace: string;
+  cloned: boolean;
+  thread: Thread;
+  run: AiReviewRun;
+};
+
+export type GetLineBlameInput = {
+  workspace: string;
   filePath: string;
-  chunkId: string;
-  chunkIndex: number;
-  hunkHeader: string;
-  side: "additions" | "deletions" | string;
+  startLine: number;
+  endLine?: number | null;
+  rev?: string | null;
+};
+
+export type LineBlame = {
   lineNumber: number;
-  title: string;
-  body: string;
-  severity: "critical" | "high" | "medium" | "low" | string;
-  confidence: number | null;
+  commit: string | null;
+  author: string | null;
+  authorEmail: string | null;
+  authoredAt: string | null;
+  summary: string | null;
 };
 
-export type AiReviewChunk = {
-  id: string;
+export type GetLineBlameResult = {
   filePath: string;
-  chunkIndex: number;
-  hunkHeader: string;
-  summary: string;
-  findings: AiReviewFinding[];
+  rev: string | null;
+  lines: LineBlame[];
 };
 
-export type AiReviewProgressEvent = {
-  runId: string | null;
-  threadId: number;
-  status:
-    | "queued"
-    | "started"
-    | "description-start"
-    | "description-delta"
-    | "description-complete"
-    | "description-failed"
-    | "chunk-start"
-    | "chunk-complete"
-    | "chunk-failed"
-    | "finding"
-    | "completed"
-    | "completed_with_errors"
-    | "canceled"
-    | "failed"
-    | string;
-  message: string;
-  totalChunks: number;
-  completedChunks: number;
-  chunkId: string | null;
-  filePath: string | null;
-  chunkIndex: number | null;
-  findingCount: number | null;
-  chunk: AiReviewChunk | null;
-  finding: AiReviewFinding | null;
+export type ListWorkspaceCommitsInput = {
+  workspace: string;
+  range?: string | null;
+  author?: string | null;
+  offset?: number | null;
+  limit?: number | null;
 };
 
-export type AiReviewRunStatus =
-  | "queued"
-  | "running"
-  | "completed"
-  | "completed_with_errors"
-  | "failed"
-  | "canceled"
-  | string;
+export type WorkspaceCommit = {
+  hash: string;
+  shortHash: string;
+  parents: string[];
+  author: string;
+  authorEmail: string;
+  authoredAt: string;
+  subject: string;
+};
 
-export type AiReviewRun = {
-  runId: string;
-  threadId: number;
+export type ListWorkspaceCommitsResult = {
   workspace: string;
-  baseRef: string;
-  mergeBase: string;
-  head: string;
-  filesChanged: number;
+  range: string;
+  commits: WorkspaceCommit[];
+  hasMore: boolean;
+};
+
+export type GetCommitDetailInput = {
+  workspace: string;
+  commit: string;
+};
+
+export type CommitFileStatus =
+  | "added"
+  | "modified"
+  | "deleted"
+  | "renamed"
+  | "copied"
+  | "type-changed";
+
+export type CommitFileChange = {
+  path: string;
+  previousPath: string | null;
+  status: CommitFileStatus;
+  insertions: number | null;
+  deletions: number | null;
+  diff: string | null;
+};
+
+export type CommitDetail = {
+  hash: string;
+  shortHash: string;
+  parents: string[];
+  author: string;
+  authorEmail: string;
+  authoredAt: string;
+  committer: string;
+  committedAt: string;
+  subject: string;
+  message: string;
+  files: CommitFileChange[];
   insertions: number;
   deletions: number;
-  prompt: string | null;
-  scopeLabel: string | null;
-  status: AiReviewRunStatus;
-  totalChunks: number;
-  completedChunks: number;
-  failedChunks: number;
-  findingCount: number;
-  model: string | null;
-  review: string | null;
-  diffCharsUsed: number | null;
-  diffCharsTotal: number | null;
   diffTruncated: boolean;
+};
+
+export type WorkspaceSyncStatus = {
+  workspace: string;
+  branch: string | null;
+  upstream: string | null;
+  baseRef: string | null;
+  staleRefs: string[];
+  behindUpstream: number;
+  stale: boolean;
   error: string | null;
-  chunks: AiReviewChunk[];
-  findings: AiReviewFinding[];
-  progressEvents: AiReviewProgressEvent[];
-  createdAt: string;
-  startedAt: string | null;
-  endedAt: string | null;
+  checkedAt: string;
+};
+
+export type ListStaleWorkspacesInput = {
+  workspace?: string | null;
+  refresh?: boolean | null;
+};
+
+export type ListStaleWorkspacesResult = {
+  workspaces: WorkspaceSyncStatus[];
+};
+
+export type WatchWorkspaceInput = {
+  workspace: string;
+};
+
+export type WorkspaceWatch = {
+  workspace: string;
+  head: string | null;
+  branch: string | null;
+};
+
+export type WorkspaceChangedEvent = {
+  workspace: string;
+  previousHead: string | null;
+  head: string | null;
+  previousBranch: string | null;
+  branch: string | null;
+  newCommits: string[];
+  changedPaths: string[];
+  changedPathCount: number;
+};
+
+export type WorkspaceTrustLevel = "trusted" | "untrusted";
+
+export type GetWorkspaceTrustInput = {
+  workspace: string;
+};
+
+export type SetWorkspaceTrustInput = {
+  workspace: string;
+  trustLevel: WorkspaceTrustLevel;
+};
+
+export type WorkspaceTrust = {
+  workspace: string;
+  trustLevel: WorkspaceTrustLevel;
+  updatedAt: string | null;
+};
+
+export type PurgeWorkspaceDataInput = {
+  workspace: string;
+  dryRun?: boolean | null;
+};
+
+export type PurgedWorkspaceTable = {
+  table: string;
+  rows: number;
+};
+
+export type PurgeWorkspaceDataResult = {
+  workspace: string;
+  dryRun: boolean;
+  activeRuns: number;
+  totalRows: number;
+  tables: PurgedWorkspaceTable[];
+  snapshotWorktrees: number;
+  gitHooks: number;
+  filesRemoved: number;
+};
+
+export type ExportWorkspaceDataInput = {
+  workspace: string;
+};
+
+export type ExportWorkspaceDataResult = {
+  workspace: string;
+  fileName: string;
+  mimeType: string;
+  totalRows: number;
+  content: string;
+};
+
+export type DatabaseMaintenanceInput = {
+  integrityCheck?: boolean;
+  cleanupOrphans?: boolean;
+  vacuum?: boolean;
+};
+
+export type DatabaseStats = {
+  pageSize: number;
+  pageCount: number;
+  freelistCount: number;
+  sizeBytes: number;
+  freeBytes: number;
+};
+
+export type OrphanedRows = {
+  table: string;
+  rows: number;
+};
+
+export type DatabaseMaintenanceResult = {
+  databaseUrl: string;
+  remote: boolean;
+  stats: DatabaseStats;
+  integrityOk: boolean | null;
+  integrityProblems: string[];
+  orphans: OrphanedRows[];
+  orphanedRows: number;
+  orphansRemoved: boolean;
+  vacuumed: boolean;
+  statsAfter: DatabaseStats | null;
+};
+
+export type ExportThreadResult = {
+  threadId: number;
+  fileName: string;
+  mimeType: string;
+  totalRows: number;
+  content: string;
+};
+
+export type ImportThreadInput = {
+  content: string;
+  workspace?: string | null;
+};
+
+export type ImportThreadResult = {
+  thread: Thread;
+  messageCount: number;
+  attachmentCount: number;
+  runCount: number;
+  findingCount: number;
+  commentCount: number;
+};
+
+export type GetThreadSettingsInput = {
+  threadId: number;
+};
+
+export type SetThreadSettingsInput = {
+  threadId: number;
+  baseRef?: string | null;
+  profileId?: number | null;
+};
+
+export type ThreadSettings = {
+  threadId: number;
+  workspace: string | null;
+  baseRef: string | null;
+  profileId: number | null;
+  lastRunId: string | null;
+  updatedAt: string | null;
+};
+
+export type OpenFileInEditorInput = {
+  workspace: string;
+  filePath: string;
+  launcher: "vscode" | "cursor" | "ghostty";
+  ghosttyCommandTemplate?: string | null;
+};
+
+export type GenerateAiReviewInput = {
+  threadId: number;
+  workspace: string;
+  baseRef: string;
+  mergeBase: string;
+  head: string;
+  filesChanged: number;
+  insertions: number;
+  deletions: number;
+  diff: string;
+  prompt?: string | null;
+  maxParallelChunks?: number | null;
+  diffTarget?: WorkspaceDiffTarget | null;
+  requestId?: string | null;
+  profileId?: number | null;
+  minConfidence?: number | null;
+};
+
+export type AiReviewFinding = {
+  id: string;
+  filePath: string;
+  chunkId: string;
+  chunkIndex: number;
+  hunkHeader: string;
+  side: "additions" | "deletions" | string;
+  lineNumber: number;
+  title: string;
+  body: string;
+  severity: "critical" | "high" | "medium" | "low" | string;
+  confidence: number | null;
+  category: AiReviewFindingCategory | null;
+  fingerprint: string | null;
+  status: FindingStatus | null;
+  codeHash: string | null;
+  recurring: boolean | null;
+  suggestedPatch: string | null;
+  language?: string | null;
+  blame?: FindingBlame;
+};
+
+export type FindingBlame = {
+  commit: string | null;
+  author: string | null;
+  authoredAt: string | null;
+  summary: string | null;
+  introduced: boolean;
+};
+
+export type AiReviewFindingCategory =
+  | "correctness"
+  | "security"
+  | "performance"
+  | "concurrency"
+  | "error-handling"
+  | "testing"
+  | "prompt-injection"
+  | string;
+
+export type AiReviewTokenUsage = {
+  promptTokens: number;
+  completionTokens: number;
+  totalTokens: number;
+  estimatedCostUsd: number | null;
+};
+
+export type AiReviewChunkOutcome =
+  | "reviewed"
+  | "refusal"
+  | "content-filter"
+  | "empty-output"
+  | "invalid-json"
+  | "error";
+
+export type AiReviewChunk = {
+  id: string;
+  filePath: string;
+  chunkIndex: number;
+  hunkHeader: string;
+  summary: string;
+  findings: AiReviewFinding[];
+  tokenUsage: AiReviewTokenUsage | null;
+  outcome?: AiReviewChunkOutcome | null;
+  language?: string | null;
+};
+
+export type AiReviewSkipReason =
+  | "binary"
+  | "no-text-changes"
+  | "excluded-path"
+  | "rovexignore"
+  | "auth-expired"
+  | "canceled"
+  | "triaged-trivial"
+  | "minified"
+  | "too-large";
+
+export type AiReviewSkippedChunk = {
+  filePath: string;
+  previousPath: string | null;
+  reason: AiReviewSkipReason;
+};
+
+export type AiReviewProgressEvent = {
+  runId: string | null;
+  threadId: number;
+  status:
+    | "queued"
+    | "started"
+    | "description-start"
+    | "description-delta"
+    | "description-complete"
+    | "description-failed"
+    | "chunk-start"
+    | "chunk-complete"
+    | "chunk-failed"
+    | "auth-required"
+    | "finding"
+    | "completed"
+    | "completed_with_errors"
+    | "canceled"
+    | "failed"
+    | string;
+  message: string;
+  totalChunks: number;
+  completedChunks: number;
+  chunkId: string | null;
+  filePath: string | null;
+  chunkIndex: number | null;
+  findingCount: number | null;
+  chunk: AiReviewChunk | null;
+  finding: AiReviewFinding | null;
+  etaMs: number | null;
+};
+
+export type AiReviewResourceUsage = {
+  processCount: number;
+  peakMemoryBytes: number;
+  cpuTimeMs: number;
+};
+
+export type AiReviewRunStatus =
+  | "queued"
+  | "running"
+  | "completed"
+  | "completed_with_errors"
+  | "failed"
+  | "canceled"
+  | string;
+
+export type AiReviewVerdict = "pass" | "fail";
+
+export type AiReviewApproval = "pending" | "approved" | "changes-requested";
+
+export type AiReviewRun = {
+  runId: string;
+  threadId: number;
+  workspace: string;
+  baseRef: string;
+  mergeBase: string;
+  head: string;
+  filesChanged: number;
+  insertions: number;
+  deletions: number;
+  prompt: string | null;
+  scopeLabel: string | null;
+  status: AiReviewRunStatus;
+  totalChunks: number;
+  completedChunks: number;
+  failedChunks: number;
+  findingCount: number;
+  model: string | null;
+  review: string | null;
+  diffCharsUsed: number | null;
+  diffCharsTotal: number | null;
+  diffTruncated: boolean;
+  error: string | null;
+  chunks: AiReviewChunk[];
+  skippedChunks: AiReviewSkippedChunk[];
+  findings: AiReviewFinding[];
+  suppressedFindings: AiReviewFinding[];
+  findingGroups: AiReviewFindingGroup[];
+  minConfidence: number | null;
+  suggestedFollowUps: string[];
+  progressEvents: AiReviewProgressEvent[];
+  resourceUsage: AiReviewResourceUsage | null;
+  tokenUsage: AiReviewTokenUsage | null;
+  reviewMode: AiReviewMode;
+  incrementalBaseHead: string | null;
+  partialReview: string | null;
+  profileId: number | null;
+  verdict: AiReviewVerdict | null;
+  verdictReason: string | null;
+  approval: AiReviewApproval;
+  approvalNote: string | null;
+  approvalUpdatedAt: string | null;
+  createdAt: string;
+  startedAt: string | null;
+  endedAt: string | null;
   canceledAt: string | null;
 };
 
-export type InlineReviewComment = {
-  id: string;
-  threadId: number;
-  workspace: string;
-  baseRef: string;
-  mergeBase: string;
-  head: string;
-  filePath: string;
-  side: "additions" | "deletions";
-  lineNumber: number;
-  endSide: "additions" | "deletions" | null;
-  endLineNumber: number | null;
-  body: string;
-  author: string;
-  createdAt: string;
+export type InlineReviewComment = {
+  id: string;
+  threadId: number;
+  workspace: string;
+  baseRef: string;
+  mergeBase: string;
+  head: string;
+  filePath: string;
+  side: "additions" | "deletions";
+  lineNumber: number;
+  endSide: "additions" | "deletions" | null;
+  endLineNumber: number | null;
+  body: string;
+  author: string;
+  createdAt: string;
+  updatedAt: string;
+};
+
+export type CreateInlineReviewCommentInput = {
+  threadId: number;
+  workspace: string;
+  baseRef: string;
+  mergeBase: string;
+  head: string;
+  filePath: string;
+  side: "additions" | "deletions";
+  lineNumber: number;
+  endSide?: "additions" | "deletions" | null;
+  endLineNumber?: number | null;
+  body: string;
+  author?: string | null;
+};
+
+export type ListInlineReviewCommentsInput = {
+  threadId: number;
+  workspace: string;
+  baseRef: string;
+  mergeBase: string;
+  head: string;
+};
+
+export type ListInlineReviewCommentsResult = {
+  comments: InlineReviewComment[];
+};
+
+export type AiReviewMode = "full" | "incremental";
+
+export type StartAiReviewRunInput = Omit<GenerateAiReviewInput, "requestId"> & {
+  scopeLabel?: string | null;
+  reviewMode?: AiReviewMode | null;
+  includePaths?: string[] | null;
+  excludePaths?: string[] | null;
+  idempotencyKey?: string | null;
+};
+
+export type StartAiReviewRunResult = {
+  run: AiReviewRun;
+};
+
+export type CancelAiReviewRunInput = {
+  runId: string;
+};
+
+export type CancelAiReviewRunResult = {
+  runId: string;
+  canceled: boolean;
+  status: string;
+};
+
+export type CancelAiRequestInput = {
+  requestId: string;
+};
+
+export type CancelAiRequestResult = {
+  requestId: string;
+  canceled: boolean;
+};
+
+export type ListAiReviewRunsInput = {
+  threadId?: number | null;
+  limit?: number | null;
+  beforeRunId?: string | null;
+  afterRunId?: string | null;
+};
+
+export type ListAiReviewRunsResult = {
+  runs: AiReviewRun[];
+  totalCount: number;
+  hasMore: boolean;
+};
+
+export type FindingSort = "severity" | "location" | "confidence";
+
+export type FindingGrouping = "severity" | "file" | "category";
+
+export type GetAiReviewRunInput = {
+  runId: string;
+  sortBy?: FindingSort | null;
+  groupBy?: FindingGrouping | null;
+  includeBlame?: boolean | null;
+};
+
+export type AiReviewFindingGroup = {
+  key: string;
+  findingIds: string[];
+};
+
+export type SetAiReviewRunApprovalInput = {
+  runId: string;
+  approval: AiReviewApproval;
+  note?: string | null;
+};
+
+export type PublishReviewCommitStatusInput = {
+  runId: string;
+  provider: ProviderKind;
+  repository: string;
+  detailsUrl?: string | null;
+};
+
+export type PublishReviewCommitStatusResult = {
+  runId: string;
+  provider: ProviderKind;
+  repository: string;
+  sha: string;
+  state: "pending" | "success" | "failure";
+  context: string;
+  description: string;
+};
+
+export type AiReviewExportFormat = "sarif" | "junit";
+
+export type ExportAiReviewRunInput = {
+  runId: string;
+  format?: AiReviewExportFormat;
+};
+
+export type CompareAiReviewRunsInput = {
+  baseRunId: string;
+  headRunId: string;
+};
+
+export type PersistingAiReviewFinding = {
+  base: AiReviewFinding;
+  head: AiReviewFinding;
+};
+
+export type CompareAiReviewRunsResult = {
+  threadId: number;
+  baseRunId: string;
+  headRunId: string;
+  baseHead: string;
+  headHead: string;
+  newFindings: AiReviewFinding[];
+  resolvedFindings: AiReviewFinding[];
+  persistingFindings: PersistingAiReviewFinding[];
+  unreviewedFindings: AiReviewFinding[];
+};
+
+export type ReviewReportFormat = "markdown" | "html";
+
+export type ExportReviewReportInput = {
+  runId: string;
+  outputPath: string;
+  format?: ReviewReportFormat;
+  sortBy?: FindingSort | null;
+  groupBy?: FindingGrouping | null;
+};
+
+export type ExportReviewReportResult = {
+  runId: string;
+  format: ReviewReportFormat;
+  path: string;
+  bytesWritten: number;
+};
+
+export type RunSnapshotInput = {
+  runId: string;
+};
+
+export type RunSnapshot = {
+  runId: string;
+  workspace: string;
+  head: string;
+  path: string;
+  exists: boolean;
+  created: boolean;
+  openedAt: string | null;
+};
+
+export type ListRunSnapshotsInput = {
+  workspace?: string | null;
+};
+
+export type ListRunSnapshotsResult = {
+  snapshots: RunSnapshot[];
+};
+
+export type RemoveRunSnapshotResult = {
+  runId: string;
+  removed: boolean;
+};
+
+export type ReviewGitHookKind = "pre-commit" | "pre-push";
+
+export type ReviewGitHookFailOn = "critical" | "high" | "medium" | "low" | "none";
+
+export type InstallReviewGitHookInput = {
+  workspace: string;
+  hook?: ReviewGitHookKind | null;
+  failOn?: ReviewGitHookFailOn | null;
+};
+
+export type ReviewGitHook = {
+  workspace: string;
+  hook: ReviewGitHookKind;
+  failOn: ReviewGitHookFailOn;
+  path: string;
+  installedAt: string;
+};
+
+export type UninstallReviewGitHookInput = {
+  workspace: string;
+  hook?: ReviewGitHookKind | null;
+};
+
+export type UninstallReviewGitHookResult = {
+  workspace: string;
+  hook: ReviewGitHookKind;
+  removed: boolean;
+};
+
+export type SaveReviewScheduleInput = {
+  id?: number | null;
+  workspace: string;
+  threadId?: number | null;
+  baseRef?: string | null;
+  remote?: string | null;
+  branch?: string | null;
+  intervalMinutes?: number | null;
+  onNewCommits?: boolean | null;
+  prompt?: string | null;
+  enabled?: boolean | null;
+};
+
+export type ReviewSchedule = {
+  id: number;
+  workspace: string;
+  threadId: number;
+  baseRef: string | null;
+  remote: string | null;
+  branch: string | null;
+  intervalMinutes: number | null;
+  onNewCommits: boolean;
+  prompt: string | null;
+  enabled: boolean;
+  lastSeenHead: string | null;
+  lastRunId: string | null;
+  lastRunStatus: string | null;
+  lastRunAt: string | null;
+  lastError: string | null;
+  createdAt: string;
+};
+
+export type ListReviewSchedulesInput = {
+  workspace?: string | null;
+};
+
+export type ListReviewSchedulesResult = {
+  schedules: ReviewSchedule[];
+};
+
+export type DeleteReviewScheduleResult = {
+  id: number;
+  deleted: boolean;
+};
+
+export type ReviewerProfileSeverity = "critical" | "high" | "medium" | "low";
+
+export type CreateReviewerProfileInput = {
+  name: string;
+  systemPrompt: string;
+  minSeverity?: ReviewerProfileSeverity | null;
+  focusAreas?: string[] | null;
+};
+
+export type UpdateReviewerProfileInput = CreateReviewerProfileInput & {
+  id: number;
+};
+
+export type ReviewerProfile = {
+  id: number;
+  name: string;
+  systemPrompt: string;
+  minSeverity: ReviewerProfileSeverity | null;
+  focusAreas: string[];
+  createdAt: string;
+  updatedAt: string;
+};
+
+export type ListReviewerProfilesResult = {
+  profiles: ReviewerProfile[];
+};
+
+export type DeleteReviewerProfileResult = {
+  id: number;
+  deleted: boolean;
+};
+
+export type ReviewGroupRepositoryInput = {
+  workspace: string;
+  baseRef?: string | null;
+  headRef?: string | null;
+  fetchRemote?: boolean | null;
+};
+
+export type StartReviewGroupInput = {
+  title?: string | null;
+  prompt?: string | null;
+  repositories: ReviewGroupRepositoryInput[];
+};
+
+export type ReviewGroupRun = {
+  runId: string;
+  threadId: number | null;
+  workspace: string;
+  headRef: string | null;
+  baseRef: string | null;
+  head: string | null;
+  status: string | null;
+  findingCount: number | null;
+  error: string | null;
+};
+
+export type ReviewGroup = {
+  id: number;
+  title: string;
+  prompt: string | null;
+  status: "running" | "summarizing" | "completed" | "failed" | string;
+  summary: string | null;
+  model: string | null;
+  error: string | null;
+  createdAt: string;
+  endedAt: string | null;
+  runs: ReviewGroupRun[];
+};
+
+export type ListReviewGroupsInput = {
+  limit?: number | null;
+};
+
+export type ListReviewGroupsResult = {
+  groups: ReviewGroup[];
+};
+
+export type ExportAiReviewRunResult = {
+  runId: string;
+  format: AiReviewExportFormat;
+  fileName: string;
+  mimeType: string;
+  content: string;
+};
+
+export type GenerateAiReviewResult = {
+  threadId: number;
+  workspace: string;
+  baseRef: string;
+  mergeBase: string;
+  head: string;
+  filesChanged: number;
+  insertions: number;
+  deletions: number;
+  model: string;
+  review: string;
+  diffCharsUsed: number;
+  diffCharsTotal: number;
+  diffTruncated: boolean;
+  chunks: AiReviewChunk[];
+  skippedChunks: AiReviewSkippedChunk[];
+  findings: AiReviewFinding[];
+  suppressedFindings: AiReviewFinding[];
+  minConfidence: number | null;
+  suggestedFollowUps: string[];
+  resourceUsage: AiReviewResourceUsage | null;
+  tokenUsage: AiReviewTokenUsage | null;
+};
+
+export type GetReviewUsageSummaryInput = {
+  threadId?: number | null;
+  limit?: number;
+};
+
+export type ReviewUsageMonth = {
+  month: string;
+  runCount: number;
+  promptTokens: number;
+  completionTokens: number;
+  totalTokens: number;
+  estimatedCostUsd: number | null;
+};
+
+export type GetReviewUsageSummaryResult = {
+  months: ReviewUsageMonth[];
+};
+
+export type ReviewQueueEntry = {
+  runId: string;
+  threadId: number;
+  workspace: string;
+  status: string;
+  queuePosition: number | null;
+  hasSlot: boolean;
+  totalChunks: number;
+  completedChunks: number;
+  failedChunks: number;
+  createdAt: string;
+  startedAt: string | null;
+  estimatedStartMs: number | null;
+};
+
+export type GetReviewQueueStatusResult = {
+  slotsTotal: number;
+  slotsInUse: number;
+  avgChunkDurationMs: number | null;
+  runs: ReviewQueueEntry[];
+};
+
+export type SignalUiActivityInput = {
+  active?: boolean;
+  durationMs?: number;
+};
+
+export type SearchFindingsInput = {
+  query?: string | null;
+  severities?: string[] | null;
+  categories?: AiReviewFindingCategory[] | null;
+  languages?: string[] | null;
+  fileGlob?: string | null;
+  workspace?: string | null;
+  since?: string | null;
+  until?: string | null;
+  limit?: number;
+};
+
+export type FindingSearchHit = {
+  runId: string;
+  threadId: number;
+  workspace: string;
+  createdAt: string;
+  finding: AiReviewFinding;
+};
+
+export type SearchFindingsResult = {
+  hits: FindingSearchHit[];
+};
+
+export type FindingStatus = "open" | "dismissed" | "fixed";
+
+export type UpdateFindingStateInput = {
+  runId: string;
+  findingId: string;
+  reason?: string | null;
+  fixCommit?: string | null;
+};
+
+export type ReviewFindingState = {
+  workspace: string;
+  fingerprint: string;
+  state: Exclude<FindingStatus, "open">;
+  filePath: string;
+  title: string;
+  reason: string | null;
+  runId: string | null;
+  findingId: string | null;
+  fixCommit: string | null;
+  createdAt: string;
+  updatedAt: string;
+};
+
+export type ListFindingStatesInput = {
+  workspace?: string | null;
+  state?: Exclude<FindingStatus, "open"> | null;
+  limit?: number;
+};
+
+export type ListFindingStatesResult = {
+  states: ReviewFindingState[];
+};
+
+export type ApplyFindingFixInput = {
+  runId: string;
+  findingId: string;
+};
+
+export type ApplyFindingFixResult = {
+  workspace: string;
+  filePath: string;
+  applied: boolean;
+  threeWay: boolean;
+  conflicts: string[];
+};
+
+export type FindingActionKind = "command" | "url";
+
+export type FindingAction = {
+  id: string;
+  label: string;
+  kind: FindingActionKind;
+  template: string;
+};
+
+export type ListFindingActionsResult = {
+  actions: FindingAction[];
+};
+
+export type SetFindingActionsInput = {
+  actions: FindingAction[];
+  persist?: boolean;
+};
+
+export type RunFindingActionInput = {
+  runId: string;
+  findingId: string;
+  actionId: string;
+};
+
+export type RunFindingActionResult = {
+  actionId: string;
+  kind: FindingActionKind;
+  target: string;
+  exitCode: number | null;
+  stdout: string;
+  stderr: string;
+  timedOut: boolean;
+};
+
+export type ModelPromptProfile = "chunk-review" | "description";
+
+export type GetModelPerformanceStatsInput = {
+  transport?: string | null;
+  promptProfile?: ModelPromptProfile | null;
+};
+
+export type ModelPerformanceStat = {
+  transport: string;
+  model: string;
+  promptProfile: ModelPromptProfile;
+  requestCount: number;
+  failureCount: number;
+  failureRate: number;
+  promptTokens: number;
+  completionTokens: number;
+  avgPromptTokens: number;
+  avgCompletionTokens: number;
+  estimatedCostUsd: number | null;
   updatedAt: string;
 };
 
-export type CreateInlineReviewCommentInput = {
-  threadId: number;
-  workspace: string;
-  baseRef: string;
-  mergeBase: string;
-  head: string;
-  filePath: string;
-  side: "additions" | "deletions";
-  lineNumber: number;
-  endSide?: "additions" | "deletions" | null;
-  endLineNumber?: number | null;
-  body: string;
-  author?: string | null;
+export type GetModelPerformanceStatsResult = {
+  stats: ModelPerformanceStat[];
+  suggestedModel: string | null;
 };
 
-export type ListInlineReviewCommentsInput = {
-  threadId: number;
-  workspace: string;
-  baseRef: string;
-  mergeBase: string;
-  head: string;
+export type GetTeamReviewStatsInput = {
+  weeks?: number | null;
 };
 
-export type ListInlineReviewCommentsResult = {
-  comments: InlineReviewComment[];
+export type TeamSeverityMix = {
+  critical: number;
+  high: number;
+  medium: number;
+  low: number;
 };
 
-export type StartAiReviewRunInput = GenerateAiReviewInput & {
-  scopeLabel?: string | null;
+export type TeamReviewWeekStats = {
+  weekStart: string;
+  runCount: number;
+  meanFindings: number;
+  severityMix: TeamSeverityMix;
 };
 
-export type StartAiReviewRunResult = {
-  run: AiReviewRun;
+export type GetTeamReviewStatsResult = {
+  weeks: TeamReviewWeekStats[];
+  suppressedWeeks: number;
+  minRunsPerWeek: number;
 };
 
-export type CancelAiReviewRunInput = {
-  runId: string;
+export type MessageAttachmentInput = {
+  name: string;
+  content: string;
+  mimeType?: string | null;
 };
 
-export type CancelAiReviewRunResult = {
-  runId: string;
-  canceled: boolean;
-  status: string;
+export type AddMessageAttachmentInput = MessageAttachmentInput & {
+  messageId: number;
 };
 
-export type ListAiReviewRunsInput = {
+export type MessageAttachment = {
+  id: number;
+  messageId: number;
+  threadId: number;
+  name: string;
+  mimeType: string;
+  sizeBytes: number;
+  contentHash: string;
+  content: string;
+  createdAt: string;
+};
+
+export type ListMessageAttachmentsInput = {
+  messageId?: number | null;
   threadId?: number | null;
-  limit?: number | null;
 };
 
-export type ListAiReviewRunsResult = {
-  runs: AiReviewRun[];
+export type ListMessageAttachmentsResult = {
+  attachments: MessageAttachment[];
 };
 
-export type GetAiReviewRunInput = {
-  runId: string;
+export type ImportPullRequestCommentsInput = {
+  threadId: number;
+  provider: ProviderKind;
+  repository: string;
+  number: number;
 };
 
-export type GenerateAiReviewResult = {
+export type PullRequestComment = {
+  id: number;
+  threadId: number;
+  messageId: number;
+  provider: ProviderKind;
+  repository: string;
+  pullRequestNumber: number;
+  externalId: string;
+  author: string;
+  body: string;
+  filePath: string | null;
+  lineNumber: number | null;
+  url: string | null;
+  commentedAt: string;
+  importedAt: string;
+};
+
+export type ImportPullRequestCommentsResult = {
+  threadId: number;
+  message: Message | null;
+  imported: PullRequestComment[];
+  skippedCount: number;
+};
+
+export type ListPullRequestCommentsInput = {
   threadId: number;
-  workspace: string;
-  baseRef: string;
-  mergeBase: string;
-  head: string;
-  filesChanged: number;
-  insertions: number;
-  deletions: number;
-  model: string;
-  review: string;
-  diffCharsUsed: number;
-  diffCharsTotal: number;
-  diffTruncated: boolean;
-  chunks: AiReviewChunk[];
-  findings: AiReviewFinding[];
+};
+
+export type ListPullRequestCommentsResult = {
+  comments: PullRequestComment[];
 };
 
 export type GenerateAiFollowUpInput = {
   threadId: number;
   workspace: string;
   question: string;
+  attachments?: MessageAttachmentInput[] | null;
+  useTools?: boolean | null;
+  requestId?: string | null;
+};
+
+export type FollowUpToolCall = {
+  tool: string;
+  input: string;
+  error: string | null;
 };
 
 export type GenerateAiFollowUpResult = {
@@ -374,21 +1683,27 @@ export type GenerateAiFollowUpResult = {
   workspace: string;
   model: string;
   answer: string;
+  toolCalls: FollowUpToolCall[];
+  cached: boolean;
 };
 
 export type AiReviewConfig = {
   hasApiKey: boolean;
   apiKeyPreview: string | null;
-  envFilePath: string | null;
   reviewProvider: string;
   reviewModel: string;
   opencodeProvider: string;
   opencodeModel: string | null;
+  triageModel: string | null;
+  triageMinFiles: number;
+  maxParallelReviewRuns: number;
+  maxParallelChunksPerRun: number;
+  maxProviderChunks: number;
 };
 
 export type SetAiReviewApiKeyInput = {
   apiKey: string;
-  persistToEnv?: boolean;
+  persist?: boolean;
 };
 
 export type SetAiReviewSettingsInput = {
@@ -396,13 +1711,28 @@ export type SetAiReviewSettingsInput = {
   reviewModel: string;
   opencodeProvider?: string | null;
   opencodeModel?: string | null;
-  persistToEnv?: boolean;
+  triageModel?: string | null;
+  maxParallelReviewRuns?: number | null;
+  maxParallelChunksPerRun?: number | null;
+  maxProviderChunks?: number | null;
+  persist?: boolean;
 };
 
+export type OpencodeSidecarProblem =
+  | "missing"
+  | "unreadable"
+  | "unrecognized-format"
+  | "architecture-mismatch"
+  | "checksum-mismatch";
+
 export type OpencodeSidecarStatus = {
   available: boolean;
   version: string | null;
   detail: string | null;
+  architecture: string | null;
+  checksumVerified: boolean;
+  problem: OpencodeSidecarProblem | null;
+  remediation: string | null;
 };
 
 export type AppServerRateLimitWindow = {
@@ -458,8 +1788,20 @@ export function createThread(input: CreateThreadInput) {
   return invoke<Thread>("create_thread", { input });
 }
 
-export function listThreads(limit?: number) {
-  return invoke<Thread[]>("list_threads", { limit });
+export function listThreads(limit?: number, includeArchived?: boolean) {
+  return invoke<Thread[]>("list_threads", { limit, includeArchived });
+}
+
+export function renameThread(input: RenameThreadInput) {
+  return invoke<Thread>("rename_thread", { input });
+}
+
+export function archiveThread(input: ArchiveThreadInput) {
+  return invoke<Thread>("archive_thread", { input });
+}
+
+export function pinThread(input: PinThreadInput) {
+  return invoke<Thread>("pin_thread", { input });
 }
 
 export function deleteThread(threadId: number) {
@@ -470,8 +1812,40 @@ export function addThreadMessage(input: AddThreadMessageInput) {
   return invoke<Message>("add_thread_message", { input });
 }
 
-export function listThreadMessages(threadId: number, limit?: number) {
-  return invoke<Message[]>("list_thread_messages", { threadId, limit });
+export function listThreadMessages(input: ListThreadMessagesInput) {
+  return invoke<ListThreadMessagesResult>("list_thread_messages", { input });
+}
+
+export function exportThread(threadId: number) {
+  return invoke<ExportThreadResult>("export_thread", { input: { threadId } });
+}
+
+export function importThread(input: ImportThreadInput) {
+  return invoke<ImportThreadResult>("import_thread", { input });
+}
+
+export function getThreadSettings(input: GetThreadSettingsInput) {
+  return invoke<ThreadSettings>("get_thread_settings", { input });
+}
+
+export function setThreadSettings(input: SetThreadSettingsInput) {
+  return invoke<ThreadSettings>("set_thread_settings", { input });
+}
+
+export function addMessageAttachment(input: AddMessageAttachmentInput) {
+  return invoke<MessageAttachment>("add_message_attachment", { input });
+}
+
+export function listMessageAttachments(input: ListMessageAttachmentsInput) {
+  return invoke<ListMessageAttachmentsResult>("list_message_attachments", { input });
+}
+
+export function importPullRequestComments(input: ImportPullRequestCommentsInput) {
+  return invoke<ImportPullRequestCommentsResult>("import_pull_request_comments", { input });
+}
+
+export function listPullRequestComments(input: ListPullRequestCommentsInput) {
+  return invoke<ListPullRequestCommentsResult>("list_pull_request_comments", { input });
 }
 
 export function connectProvider(input: ConnectProviderInput) {
@@ -502,10 +1876,26 @@ export function cloneRepository(input: CloneRepositoryInput) {
   return invoke<CloneRepositoryResult>("clone_repository", { input });
 }
 
+export function getRepositoriesDir() {
+  return invoke<RepositoriesDirResult>("get_repositories_dir");
+}
+
+export function migrateRepositoriesDir(input: MigrateRepositoriesDirInput) {
+  return invoke<MigrateRepositoriesDirResult>("migrate_repositories_dir", { input });
+}
+
 export function compareWorkspaceDiff(input: CompareWorkspaceDiffInput) {
   return invoke<CompareWorkspaceDiffResult>("compare_workspace_diff", { input });
 }
 
+export function listWorkspaceFiles(input: ListWorkspaceFilesInput) {
+  return invoke<ListWorkspaceFilesResult>("list_workspace_files", { input });
+}
+
+export function readWorkspaceFile(input: ReadWorkspaceFileInput) {
+  return invoke<ReadWorkspaceFileResult>("read_workspace_file", { input });
+}
+
 export function listWorkspaceBranches(input: ListWorkspaceBranchesInput) {
   return invoke<ListWorkspaceBranchesResult>("list_workspace_branches", { input });
 }
@@ -518,6 +1908,86 @@ export function createWorkspaceBranch(input: CreateWorkspaceBranchInput) {
   return invoke<CheckoutWorkspaceBranchResult>("create_workspace_branch", { input });
 }
 
+export function workspaceStatus(input: WorkspaceStatusInput) {
+  return invoke<WorkspaceStatus>("workspace_status", { input });
+}
+
+export function pullWorkspaceBranch(input: PullWorkspaceBranchInput) {
+  return invoke<PullWorkspaceBranchResult>("pull_workspace_branch", { input });
+}
+
+export function pushWorkspaceBranch(input: PushWorkspaceBranchInput) {
+  return invoke<PushWorkspaceBranchResult>("push_workspace_branch", { input });
+}
+
+export function commitWorkspaceChanges(input: CommitWorkspaceChangesInput) {
+  return invoke<CommitWorkspaceChangesResult>("commit_workspace_changes", { input });
+}
+
+export function generateCommitMessage(input: GenerateCommitMessageInput) {
+  return invoke<GenerateCommitMessageResult>("generate_commit_message", { input });
+}
+
+export function suggestThreadTitle(input: SuggestThreadTitleInput) {
+  return invoke<SuggestThreadTitleResult>("suggest_thread_title", { input });
+}
+
+export function unshallowWorkspace(input: UnshallowWorkspaceInput) {
+  return invoke<UnshallowWorkspaceResult>("unshallow_workspace", { input });
+}
+
+export function fetchWorkspaceRemote(input: FetchWorkspaceRemoteInput) {
+  return invoke<FetchWorkspaceRemoteResult>("fetch_workspace_remote", { input });
+}
+
+export function bootstrapDemoWorkspace(input: BootstrapDemoWorkspaceInput = {}) {
+  return invoke<BootstrapDemoWorkspaceResult>("bootstrap_demo_workspace", { input });
+}
+
+export function getLineBlame(input: GetLineBlameInput) {
+  return invoke<GetLineBlameResult>("get_line_blame", { input });
+}
+
+export function listWorkspaceCommits(input: ListWorkspaceCommitsInput) {
+  return invoke<ListWorkspaceCommitsResult>("list_workspace_commits", { input });
+}
+
+export function getCommitDetail(input: GetCommitDetailInput) {
+  return invoke<CommitDetail>("get_commit_detail", { input });
+}
+
+export function listStaleWorkspaces(input: ListStaleWorkspacesInput = {}) {
+  return invoke<ListStaleWorkspacesResult>("list_stale_workspaces", { input });
+}
+
+export function watchWorkspace(input: WatchWorkspaceInput) {
+  return invoke<WorkspaceWatch>("watch_workspace", { input });
+}
+
+export function unwatchWorkspace(input: WatchWorkspaceInput) {
+  return invoke<boolean>("unwatch_workspace", { input });
+}
+
+export function getWorkspaceTrust(input: GetWorkspaceTrustInput) {
+  return invoke<WorkspaceTrust>("get_workspace_trust", { input });
+}
+
+export function setWorkspaceTrust(input: SetWorkspaceTrustInput) {
+  return invoke<WorkspaceTrust>("set_workspace_trust", { input });
+}
+
+export function purgeWorkspaceData(input: PurgeWorkspaceDataInput) {
+  return invoke<PurgeWorkspaceDataResult>("purge_workspace_data", { input });
+}
+
+export function exportWorkspaceData(input: ExportWorkspaceDataInput) {
+  return invoke<ExportWorkspaceDataResult>("export_workspace_data", { input });
+}
+
+export function databaseMaintenance(input: DatabaseMaintenanceInput = {}) {
+  return invoke<DatabaseMaintenanceResult>("database_maintenance", { input });
+}
+
 export function openFileInEditor(input: OpenFileInEditorInput) {
   return invoke<void>("open_file_in_editor", { input });
 }
@@ -558,6 +2028,10 @@ export function cancelAiReviewRun(input: CancelAiReviewRunInput) {
   return invoke<CancelAiReviewRunResult>("cancel_ai_review_run", { input });
 }
 
+export function cancelAiRequest(input: CancelAiRequestInput) {
+  return invoke<CancelAiRequestResult>("cancel_ai_request", { input });
+}
+
 export function listAiReviewRuns(input: ListAiReviewRunsInput = {}) {
   return invoke<ListAiReviewRunsResult>("list_ai_review_runs", { input });
 }
@@ -566,6 +2040,142 @@ export function getAiReviewRun(input: GetAiReviewRunInput) {
   return invoke<AiReviewRun>("get_ai_review_run", { input });
 }
 
+export function setAiReviewRunApproval(input: SetAiReviewRunApprovalInput) {
+  return invoke<AiReviewRun>("set_ai_review_run_approval", { input });
+}
+
+export function publishReviewCommitStatus(input: PublishReviewCommitStatusInput) {
+  return invoke<PublishReviewCommitStatusResult>("publish_review_commit_status", { input });
+}
+
+export function exportAiReviewRun(input: ExportAiReviewRunInput) {
+  return invoke<ExportAiReviewRunResult>("export_ai_review_run", { input });
+}
+
+export function compareAiReviewRuns(input: CompareAiReviewRunsInput) {
+  return invoke<CompareAiReviewRunsResult>("compare_ai_review_runs", { input });
+}
+
+export function exportReviewReport(input: ExportReviewReportInput) {
+  return invoke<ExportReviewReportResult>("export_review_report", { input });
+}
+
+export function checkoutRunSnapshot(input: RunSnapshotInput) {
+  return invoke<RunSnapshot>("checkout_run_snapshot", { input });
+}
+
+export function listRunSnapshots(input: ListRunSnapshotsInput = {}) {
+  return invoke<ListRunSnapshotsResult>("list_run_snapshots", { input });
+}
+
+export function removeRunSnapshot(input: RunSnapshotInput) {
+  return invoke<RemoveRunSnapshotResult>("remove_run_snapshot", { input });
+}
+
+export function installReviewGitHook(input: InstallReviewGitHookInput) {
+  return invoke<ReviewGitHook>("install_review_git_hook", { input });
+}
+
+export function uninstallReviewGitHook(input: UninstallReviewGitHookInput) {
+  return invoke<UninstallReviewGitHookResult>("uninstall_review_git_hook", { input });
+}
+
+export function saveReviewSchedule(input: SaveReviewScheduleInput) {
+  return invoke<ReviewSchedule>("save_review_schedule", { input });
+}
+
+export function listReviewSchedules(input: ListReviewSchedulesInput = {}) {
+  return invoke<ListReviewSchedulesResult>("list_review_schedules", { input });
+}
+
+export function deleteReviewSchedule(id: number) {
+  return invoke<DeleteReviewScheduleResult>("delete_review_schedule", { input: { id } });
+}
+
+export function createReviewerProfile(input: CreateReviewerProfileInput) {
+  return invoke<ReviewerProfile>("create_reviewer_profile", { input });
+}
+
+export function updateReviewerProfile(input: UpdateReviewerProfileInput) {
+  return invoke<ReviewerProfile>("update_reviewer_profile", { input });
+}
+
+export function listReviewerProfiles() {
+  return invoke<ListReviewerProfilesResult>("list_reviewer_profiles");
+}
+
+export function deleteReviewerProfile(id: number) {
+  return invoke<DeleteReviewerProfileResult>("delete_reviewer_profile", { input: { id } });
+}
+
+export function startReviewGroup(input: StartReviewGroupInput) {
+  return invoke<ReviewGroup>("start_review_group", { input });
+}
+
+export function getReviewGroup(groupId: number) {
+  return invoke<ReviewGroup>("get_review_group", { input: { groupId } });
+}
+
+export function listReviewGroups(input: ListReviewGroupsInput = {}) {
+  return invoke<ListReviewGroupsResult>("list_review_groups", { input });
+}
+
+export function summarizeReviewGroup(groupId: number) {
+  return invoke<ReviewGroup>("summarize_review_group", { input: { groupId } });
+}
+
+export function getReviewQueueStatus() {
+  return invoke<GetReviewQueueStatusResult>("get_review_queue_status");
+}
+
+export function signalUiActivity(input: SignalUiActivityInput = {}) {
+  return invoke<void>("signal_ui_activity", { input });
+}
+
+export function getReviewUsageSummary(input: GetReviewUsageSummaryInput = {}) {
+  return invoke<GetReviewUsageSummaryResult>("get_review_usage_summary", { input });
+}
+
+export function searchFindings(input: SearchFindingsInput = {}) {
+  return invoke<SearchFindingsResult>("search_findings", { input });
+}
+
+export function dismissFinding(input: UpdateFindingStateInput) {
+  return invoke<ReviewFindingState>("dismiss_finding", { input });
+}
+
+export function markFindingFixed(input: UpdateFindingStateInput) {
+  return invoke<ReviewFindingState>("mark_finding_fixed", { input });
+}
+
+export function listFindingStates(input: ListFindingStatesInput = {}) {
+  return invoke<ListFindingStatesResult>("list_finding_states", { input });
+}
+
+export function applyFindingFix(input: ApplyFindingFixInput) {
+  return invoke<ApplyFindingFixResult>("apply_finding_fix", { input });
+}
+
+export function listFindingActions() {
+  return invoke<ListFindingActionsResult>("list_finding_actions");
+}
+
+export function setFindingActions(input: SetFindingActionsInput) {
+  return invoke<ListFindingActionsResult>("set_finding_actions", { input });
+}
+
+export function runFindingAction(input: RunFindingActionInput) {
+  return invoke<RunFindingActionResult>("run_finding_action", { input });
+}
+
+export function getModelPerformanceStats(input: GetModelPerformanceStatsInput = {}) {
+  return invoke<GetModelPerformanceStatsResult>("get_model_performance_stats", { input });
+}
+
+export function getTeamReviewStats(input: GetTeamReviewStatsInput = {}) {
+  return invoke<GetTeamReviewStatsResult>("get_team_review_stats", { input });
+}
+
 export function createInlineReviewComment(input: CreateInlineReviewCommentInput) {
   return invoke<InlineReviewComment>("create_inline_review_comment", { input });
 }
@@ -577,3 +2187,23 @@ export function listInlineReviewComments(input: ListInlineReviewCommentsInput) {
 export function generateAiFollowUp(input: GenerateAiFollowUpInput) {
   return invoke<GenerateAiFollowUpResult>("generate_ai_follow_up", { input });
 }
+
+export function codeIntelSyncStatus(input: CodeIntelSyncStatusInput = {}) {
+  return invoke<CodeIntelSyncStatus>("code_intel_sync_status", { input });
+}
+
+export function getCodeIntelConfig(input: GetCodeIntelConfigInput) {
+  return invoke<CodeIntelWorkspaceConfig>("get_code_intel_config", { input });
+}
+
+export function setCodeIntelConfig(input: SetCodeIntelConfigInput) {
+  return invoke<CodeIntelWorkspaceConfig>("set_code_intel_config", { input });
+}
+
+export function getSymbolDefinition(input: SymbolLookupInput) {
+  return invoke<GetSymbolDefinitionResult>("get_symbol_definition", { input });
+}
+
+export function listSymbolReferences(input: SymbolLookupInput) {
+  return invoke<ListSymbolReferencesResult>("list_symbol_references", { input });
+}
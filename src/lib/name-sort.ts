@@ -0,0 +1,40 @@
+import type { NameSortOptions } from "@/lib/backend";
+
+/**
+ * Orders names like the backend's `NameCollator`: digit runs as numbers, base letters first,
+ * with accents, case and finally code units only breaking ties.
+ */
+export function createNameComparator(options: NameSortOptions = {}) {
+  const numeric = options.numeric ?? true;
+  const sensitivity = options.accentSensitive
+    ? options.caseSensitive
+      ? "variant"
+      : "accent"
+    : options.caseSensitive
+      ? "case"
+      : "base";
+  const collator = new Intl.Collator(undefined, { numeric, sensitivity, caseFirst: "lower" });
+  const tieBreaker = new Intl.Collator(undefined, {
+    numeric,
+    sensitivity: "variant",
+    caseFirst: "lower",
+  });
+  return (left: string, right: string) =>
+    collator.compare(left, right) ||
+    tieBreaker.compare(left, right) ||
+    (left < right ? -1 : left > right ? 1 : 0);
+}
+
+const compareNames = createNameComparator();
+
+/** Compares `/`-separated paths one component at a time, like the backend. */
+export function comparePaths(left: string, right: string) {
+  const leftParts = left.split("/");
+  const rightParts = right.split("/");
+  const count = Math.min(leftParts.length, rightParts.length);
+  for (let index = 0; index < count; index += 1) {
+    const byPart = compareNames(leftParts[index], rightParts[index]);
+    if (byPart !== 0) return byPart;
+  }
+  return leftParts.length - rightParts.length;
+}